@@ -0,0 +1,123 @@
+"""
+A/B测试模块
+
+对比同时运行的两个策略变体（一个实盘一个模拟盘，或各分一部分资金实盘运行）：
+- 每笔收益：交易盈亏相对该变体资金预算的收益率（bps），Welch t检验
+- 胜率：两比例z检验
+- 执行滑点：每笔成交的滑点（bps），Welch t检验
+
+p值按正态分布近似计算（样本数较少时偏乐观，建议每个变体至少30笔交易后再参考显著性）。
+"""
+
+import math
+import statistics
+from typing import Any, Dict, List
+
+# 显著性水平
+SIGNIFICANCE = 0.05
+
+
+def _p_value(z: float) -> float:
+    """双侧p值（正态近似）"""
+    return math.erfc(abs(z) / math.sqrt(2))
+
+
+def welch_test(a: List[float], b: List[float]) -> Dict[str, Any]:
+    """
+    两组样本均值差异检验
+
+    Args:
+        a: 变体A样本
+        b: 变体B样本
+
+    Returns:
+        Dict[str, Any]: 各组均值、差值（B-A）和p值；任一组样本少于2个时p值为None
+    """
+    mean_a = statistics.fmean(a) if a else 0.0
+    mean_b = statistics.fmean(b) if b else 0.0
+    result = {"mean_a": mean_a, "mean_b": mean_b, "diff": mean_b - mean_a, "p_value": None}
+    if len(a) < 2 or len(b) < 2:
+        return result
+
+    se = math.sqrt(statistics.variance(a) / len(a) + statistics.variance(b) / len(b))
+    if se > 0:
+        result["p_value"] = _p_value((mean_b - mean_a) / se)
+    return result
+
+
+def proportion_test(wins_a: int, n_a: int, wins_b: int, n_b: int) -> Dict[str, Any]:
+    """
+    两组比例差异检验
+
+    Args:
+        wins_a / n_a: 变体A成功次数和样本数
+        wins_b / n_b: 变体B成功次数和样本数
+
+    Returns:
+        Dict[str, Any]: 各组比例、差值（B-A）和p值；任一组无样本时p值为None
+    """
+    rate_a = wins_a / n_a if n_a else 0.0
+    rate_b = wins_b / n_b if n_b else 0.0
+    result = {"mean_a": rate_a, "mean_b": rate_b, "diff": rate_b - rate_a, "p_value": None}
+    if not n_a or not n_b:
+        return result
+
+    pooled = (wins_a + wins_b) / (n_a + n_b)
+    se = math.sqrt(pooled * (1 - pooled) * (1 / n_a + 1 / n_b))
+    if se > 0:
+        result["p_value"] = _p_value((rate_b - rate_a) / se)
+    return result
+
+
+class ABTest:
+    """两个策略变体的对比"""
+
+    def __init__(self, variant_a, variant_b):
+        """
+        Args:
+            variant_a: 变体A策略实例（对照组）
+            variant_b: 变体B策略实例
+        """
+        self.variant_a = variant_a
+        self.variant_b = variant_b
+
+    @staticmethod
+    def _trade_returns(strategy) -> List[float]:
+        """每笔交易盈亏相对资金预算的收益率（bps）"""
+        budget = float(strategy.allocation.budget) if strategy.allocation else float(strategy.balance)
+        if budget <= 0:
+            return []
+        return [r.pnl / budget * 10000 for r in strategy.trade_records]
+
+    def compare(self) -> Dict[str, Any]:
+        """
+        汇总两个变体的对比结果
+
+        Returns:
+            Dict[str, Any]: 变体名称、交易次数、总盈亏，以及每笔收益/胜率/滑点的检验结果
+            （各项的 significant 表示 p值低于显著性水平）
+        """
+        a, b = self.variant_a, self.variant_b
+        records_a, records_b = a.trade_records, b.trade_records
+
+        results = {
+            "return_bps": welch_test(self._trade_returns(a), self._trade_returns(b)),
+            "hit_rate": proportion_test(
+                sum(1 for r in records_a if r.pnl > 0), len(records_a),
+                sum(1 for r in records_b if r.pnl > 0), len(records_b)
+            ),
+            "slippage_bps": welch_test(
+                [r.slippage_bps for r in a.slippage.records],
+                [r.slippage_bps for r in b.slippage.records]
+            ),
+        }
+        for result in results.values():
+            result["significant"] = result["p_value"] is not None and result["p_value"] < SIGNIFICANCE
+
+        return {
+            "variants": (a.name, b.name),
+            "paper": (a.client.is_paper, b.client.is_paper),
+            "trades": (len(records_a), len(records_b)),
+            "total_pnl": (sum(r.pnl for r in records_a), sum(r.pnl for r in records_b)),
+            **results,
+        }
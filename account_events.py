@@ -0,0 +1,153 @@
+"""
+账户事件流模块
+
+私有WebSocket的交易事件（trade-event）一条消息里同时带有订单、成交、持仓和资金流水的变化，
+这里解析为带类型的事件后分发给订阅方（策略引擎）：
+- on_order_update: 订单状态（交易所订单字段的字典，补充 symbol；与模拟撮合引擎的订单回报格式一致）
+- on_fill: 成交回报（Fill）
+- on_position_update: 持仓变化（PositionUpdate，单向持仓时按净持仓方向）
+- on_funding: 资金费结算（FundingPayment，来自持仓流水中类型为资金费的记录）
+回调在SDK分发线程中执行，需要修改策略状态的订阅方自行切换到事件循环
+"""
+
+import time
+from typing import Any, Callable, Dict, List, Optional, Union
+
+from loguru import logger
+
+from edgex_types import Fill, FundingPayment, OrderSide, PositionUpdate, TradeDirection
+from fast_json import loads
+from tracing import span
+
+
+class AccountEventStream:
+    """私有频道交易事件的解析与分发"""
+
+    def __init__(self, symbol_for_contract: Callable[[str], str]):
+        """
+        Args:
+            symbol_for_contract: 合约ID转换为交易对（未知合约返回合约ID本身）
+        """
+        self.symbol_for_contract = symbol_for_contract
+        self.order_callbacks: List[Callable[[Dict[str, Any]], None]] = []
+        self.fill_callbacks: List[Callable[[Fill], None]] = []
+        self.position_callbacks: List[Callable[[PositionUpdate], None]] = []
+        self.funding_callbacks: List[Callable[[FundingPayment], None]] = []
+        self.stats = {"events": 0, "orders": 0, "fills": 0, "positions": 0, "funding": 0, "errors": 0}
+
+    def on_order_update(self, callback: Callable[[Dict[str, Any]], None]):
+        """添加订单状态回调"""
+        self.order_callbacks.append(callback)
+
+    def on_fill(self, callback: Callable[[Fill], None]):
+        """添加成交回报回调"""
+        self.fill_callbacks.append(callback)
+
+    def on_position_update(self, callback: Callable[[PositionUpdate], None]):
+        """添加持仓变化回调"""
+        self.position_callbacks.append(callback)
+
+    def on_funding(self, callback: Callable[[FundingPayment], None]):
+        """添加资金费结算回调"""
+        self.funding_callbacks.append(callback)
+
+    def handle_message(self, message: Union[str, Dict[str, Any]]):
+        """处理一条私有频道交易事件（SDK消息处理器）"""
+        with span("ws.trade_event"):
+            try:
+                data = loads(message) if isinstance(message, (str, bytes)) else message
+                content = (data.get("content") or {}).get("data") or {}
+            except Exception as e:
+                self.stats["errors"] += 1
+                logger.error(f"处理交易事件失败: {e}")
+                return
+            self.stats["events"] += 1
+
+            for order in content.get("order") or []:
+                order = dict(order)
+                order["symbol"] = self.symbol_for_contract(str(order.get("contractId", "")))
+                self._emit("orders", self.order_callbacks, order)
+
+            for record in content.get("orderFillTransaction") or []:
+                fill = self._parse(self.parse_fill, record)
+                if fill:
+                    self._emit("fills", self.fill_callbacks, fill)
+
+            for record in content.get("position") or []:
+                update = self._parse(self.parse_position, record)
+                if update:
+                    self._emit("positions", self.position_callbacks, update)
+
+            for record in content.get("positionTransaction") or []:
+                payment = self._parse(self.parse_funding, record)
+                if payment:
+                    self._emit("funding", self.funding_callbacks, payment)
+
+    def _parse(self, parser: Callable[[Dict[str, Any]], Any], record: Dict[str, Any]) -> Any:
+        try:
+            return parser(record)
+        except Exception as e:
+            self.stats["errors"] += 1
+            logger.error(f"解析交易事件记录失败: {e}")
+            return None
+
+    def _emit(self, kind: str, callbacks: List[Callable], event: Any):
+        self.stats[kind] += 1
+        for callback in callbacks:
+            try:
+                callback(event)
+            except Exception as e:
+                logger.error(f"账户事件回调函数执行失败（{kind}）: {e}")
+
+    def parse_fill(self, data: Dict[str, Any]) -> Optional[Fill]:
+        """解析成交回报为Fill（成交量为0时返回None）"""
+        size = float(data.get("fillSize", 0))
+        if size <= 0:
+            return None
+        return Fill(
+            symbol=self.symbol_for_contract(str(data.get("contractId", ""))),
+            order_id=str(data.get("orderId", "")),
+            side=OrderSide(data.get("orderSide", data.get("side", "BUY"))),
+            price=float(data.get("fillPrice", 0)),
+            size=size,
+            fee=float(data.get("fillFee", 0)),
+            is_maker=data.get("direction") == "MAKER",
+            timestamp=int(data.get("matchTime", data.get("createdTime", time.time() * 1000)))
+        )
+
+    def parse_position(self, data: Dict[str, Any]) -> Optional[PositionUpdate]:
+        """
+        解析持仓记录为PositionUpdate
+
+        openSize 带符号（多为正），均价为 openValue / openSize；双向持仓时按 positionSide 确定方向
+        """
+        signed = float(data.get("openSize", data.get("positionSize", 0)) or 0)
+        side = data.get("positionSide")
+        if side in (TradeDirection.LONG.value, TradeDirection.SHORT.value):
+            direction = TradeDirection(side)
+        else:
+            direction = TradeDirection.SHORT if signed < 0 else TradeDirection.LONG
+        size = abs(signed)
+        entry_price = abs(float(data.get("openValue", 0) or 0)) / size if size else 0.0
+        if not entry_price and size:
+            entry_price = float(data.get("avgEntryPrice", 0) or 0)
+        return PositionUpdate(
+            symbol=self.symbol_for_contract(str(data.get("contractId", ""))),
+            direction=direction,
+            size=size,
+            entry_price=entry_price,
+            timestamp=int(data.get("updatedTime", data.get("createdTime", time.time() * 1000)))
+        )
+
+    def parse_funding(self, data: Dict[str, Any]) -> Optional[FundingPayment]:
+        """解析持仓流水为FundingPayment（非资金费流水返回None）"""
+        if "FUNDING" not in str(data.get("type", "")).upper():
+            return None
+        return FundingPayment(
+            symbol=self.symbol_for_contract(str(data.get("contractId", ""))),
+            amount=float(data.get("deltaFundingFee", data.get("fundingFee", 0)) or 0),
+            timestamp=int(data.get("createdTime", time.time() * 1000))
+        )
+
+    def get_stats(self) -> Dict[str, int]:
+        return dict(self.stats)
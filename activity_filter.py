@@ -0,0 +1,118 @@
+"""
+持仓量与成交量过滤模块
+
+按合约的持仓价值和24小时成交额过滤开仓，避免在新上线或冷门合约上刷量交易：
+- 数据来自ticker（openInterest / value / size），WebSocket ticker推送时更新，没有及时推送时通过REST接口获取
+- 持仓价值（持仓量 × 最新价）或24小时成交额低于阈值时跳过开仓（已有持仓仍正常管理和平仓）
+- 尚未取得数据或数据过期时同样跳过开仓
+被跳过的信号按交易对和原因统计。
+"""
+
+import time
+from typing import Any, Dict, Optional
+
+from edgex_types import MarketActivity
+
+
+def _float(value: Any) -> float:
+    try:
+        return float(value) if value not in (None, "") else 0.0
+    except (TypeError, ValueError):
+        return 0.0
+
+
+def parse_market_activity(data: Dict[str, Any]) -> Optional[MarketActivity]:
+    """
+    解析ticker中的持仓量和24小时成交量（时间戳为接收时间，24小时成交量本身是滚动统计）
+
+    Args:
+        data: ticker数据
+
+    Returns:
+        Optional[MarketActivity]: ticker不含持仓量和成交额时为None
+    """
+    if "openInterest" not in data and "value" not in data:
+        return None
+    price = _float(data.get("lastPrice")) or _float(data.get("oraclePrice"))
+    open_interest = _float(data.get("openInterest"))
+    return MarketActivity(
+        timestamp=int(time.time() * 1000),
+        open_interest=open_interest,
+        open_interest_value=open_interest * price,
+        volume_24h=_float(data.get("size")),
+        value_24h=_float(data.get("value")),
+    )
+
+
+class ActivityFilter:
+    """持仓价值与24小时成交额过滤器"""
+
+    # 跳过原因
+    NO_DATA = "no_data"
+    LOW_OPEN_INTEREST = "low_open_interest"
+    LOW_VOLUME = "low_volume"
+
+    def __init__(self, min_open_interest: float = 0.0, min_volume_24h: float = 0.0, max_age_seconds: float = 600.0):
+        """
+        Args:
+            min_open_interest: 最低持仓价值（USDT，0为不检查）
+            min_volume_24h: 最低24小时成交额（USDT，0为不检查）
+            max_age_seconds: 数据超过多少秒未更新视为过期
+        """
+        self.min_open_interest = min_open_interest
+        self.min_volume_24h = min_volume_24h
+        self.max_age_seconds = max_age_seconds
+        self.activity: Dict[str, MarketActivity] = {}
+
+        # 统计：交易对 -> 原因 -> 跳过次数
+        self.checked = 0
+        self.skipped: Dict[str, Dict[str, int]] = {}
+
+    def update(self, symbol: str, activity: MarketActivity):
+        """更新交易对的持仓量和成交量（较旧的数据忽略）"""
+        current = self.activity.get(symbol)
+        if current is None or activity.timestamp >= current.timestamp:
+            self.activity[symbol] = activity
+
+    def is_stale(self, symbol: str, now: Optional[float] = None) -> bool:
+        """数据是否缺失或超过 max_age_seconds 未更新"""
+        activity = self.activity.get(symbol)
+        now = now if now is not None else time.time()
+        return activity is None or now - activity.timestamp / 1000 > self.max_age_seconds
+
+    def check(self, symbol: str, now: Optional[float] = None) -> Optional[str]:
+        """
+        检查是否允许开仓
+
+        Returns:
+            Optional[str]: 跳过原因，允许开仓时返回None
+        """
+        self.checked += 1
+        reason = self._reject_reason(symbol, now)
+        if reason:
+            counts = self.skipped.setdefault(symbol, {})
+            counts[reason] = counts.get(reason, 0) + 1
+        return reason
+
+    def _reject_reason(self, symbol: str, now: Optional[float]) -> Optional[str]:
+        if self.is_stale(symbol, now):
+            return self.NO_DATA
+        activity = self.activity[symbol]
+        if self.min_open_interest > 0 and activity.open_interest_value < self.min_open_interest:
+            return self.LOW_OPEN_INTEREST
+        if self.min_volume_24h > 0 and activity.value_24h < self.min_volume_24h:
+            return self.LOW_VOLUME
+        return None
+
+    def get_summary(self) -> Dict[str, Any]:
+        """获取过滤统计"""
+        return {
+            "min_open_interest": self.min_open_interest,
+            "min_volume_24h": self.min_volume_24h,
+            "checked": self.checked,
+            "skipped_total": sum(sum(c.values()) for c in self.skipped.values()),
+            "skipped": {symbol: dict(counts) for symbol, counts in self.skipped.items()},
+            "activity": {symbol: {"open_interest_value": round(a.open_interest_value, 2),
+                                  "value_24h": round(a.value_24h, 2)}
+                         for symbol, a in self.activity.items()},
+        }
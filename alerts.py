@@ -0,0 +1,84 @@
+"""
+告警模块
+
+交易安全相关的事件（连续亏损熔断、交易对冷却等）以ERROR级别写入日志，
+配置了通知Webhook地址（EDGEX_NOTIFIER_WEBHOOK_URL）时同时以JSON（{"strategy", "alert", "text"}）POST到该地址，
+配置了Discord Webhook地址时同时以红色卡片发送到Discord（见 discord_notifier.py）。
+严重级别的告警（强平风险、紧急停止、连续重连失败）在配置了SMTP时另外发送邮件（见 email_notifier.py，带限流）。
+发送到Webhook和Discord前经过通知限流（见 notify_throttle.py）：相同内容去重、各通道限速，严重告警不受限流。
+发送在后台进行，失败只记录日志，不影响交易。
+"""
+
+import asyncio
+from typing import Dict, Optional, Set
+
+import aiohttp
+from loguru import logger
+
+from discord_notifier import DiscordNotifier
+from email_notifier import EmailNotifier
+from notify_throttle import NotificationThrottle
+
+_ALERT_TIMEOUT = 10.0
+
+
+class AlertNotifier:
+    """告警发送"""
+
+    def __init__(self, webhook_url: str = "", discord: Optional[DiscordNotifier] = None,
+                 email: Optional[EmailNotifier] = None, throttle: Optional[NotificationThrottle] = None):
+        """
+        Args:
+            webhook_url: 通知Webhook地址（空为只写日志）
+            discord: Discord发送（未配置时为None）
+            email: 严重告警邮件发送（未配置时为None）
+            throttle: 通知Webhook的通知限流（None为不限流，Discord由其自身的限流处理）
+        """
+        self.webhook_url = webhook_url
+        self.discord = discord
+        self.email = email
+        self.throttle = throttle
+        self.stats = {"sent": 0, "errors": 0}
+        self._tasks: Set[asyncio.Task] = set()
+
+    def send(self, strategy: str, alert: str, text: str, critical: bool = False):
+        """
+        发送告警（需在事件循环中调用，Webhook请求在后台执行）
+
+        Args:
+            strategy: 策略名称
+            alert: 告警类型（如 loss_breaker）
+            text: 告警内容
+            critical: 是否为严重告警（同时发送邮件）
+        """
+        logger.error(f"[告警] {strategy} {alert}: {text}")
+        if critical and self.email:
+            self.email.send(strategy, alert, text)
+        if self.discord:
+            self.discord.alert(strategy, alert, text, critical)
+        if not self.webhook_url:
+            return
+        if self.throttle and not self.throttle.allow("webhook", f"{strategy}|{alert}|{text}", critical):
+            logger.debug(f"[告警] {alert} 已限流，不发送到通知Webhook")
+            return
+        try:
+            loop = asyncio.get_running_loop()
+        except RuntimeError:
+            return
+        task = loop.create_task(self._post({"strategy": strategy, "alert": alert, "text": text}))
+        self._tasks.add(task)
+        task.add_done_callback(self._tasks.discard)
+
+    async def _post(self, payload: Dict[str, str]):
+        try:
+            async with aiohttp.ClientSession(timeout=aiohttp.ClientTimeout(total=_ALERT_TIMEOUT)) as session:
+                async with session.post(self.webhook_url, json=payload) as response:
+                    if response.status >= 400:
+                        raise ValueError(f"HTTP {response.status}")
+            self.stats["sent"] += 1
+        except Exception as e:
+            self.stats["errors"] += 1
+            logger.warning(f"[告警] 发送到通知Webhook失败: {e}")
+
+    def get_stats(self) -> Optional[Dict[str, int]]:
+        return dict(self.stats) if self.webhook_url else None
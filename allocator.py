@@ -0,0 +1,231 @@
+"""
+多策略资金分配模块
+
+同一账户上并发运行多个策略实例，每个策略按权重分配名义资金预算（开仓仓位按预算计算），
+并跟踪各策略的盈亏和回撤，按规则限流或停用表现不佳的策略：
+- 回撤超过限流阈值，或交易次数足够但胜率过低：预算按比例缩小（恢复后自动解除）
+- 回撤超过停用阈值：停止开仓（保留持仓管理，直到重启）
+
+策略列表定义在JSON文件中，每个策略的 config 覆盖全局配置的对应字段：
+    [
+        {"name": "mean_reversion", "weight": 0.6, "config": {"symbols": ["BTC-USDT", "ETH-USDT"]}},
+        {"name": "scripted", "weight": 0.4, "config": {"symbols": ["SOL-USDT"], "signal_script": "examples/signal_script.py"}}
+    ]
+
+实盘策略的交易对不能重叠（单向持仓模式下同一交易对的净持仓无法区分归属）；模拟盘策略
+（config 中 paper_trading 为 true）使用独立的撮合引擎和虚拟资金，可与其他策略交易相同交易对，
+且不计入权重之和。
+"""
+
+import asyncio
+import json
+from decimal import Decimal
+from typing import Any, Dict, List
+from loguru import logger
+
+from config import Config
+
+
+class AllocationStatus:
+    """策略分配状态"""
+    ACTIVE = "active"
+    THROTTLED = "throttled"
+    DISABLED = "disabled"
+
+
+class StrategySlot:
+    """单个策略的资金分配"""
+
+    def __init__(self, name: str, weight: float, strategy):
+        """
+        Args:
+            name: 策略名称
+            weight: 资金权重（账户权益的比例）
+            strategy: 策略实例
+        """
+        self.name = name
+        self.weight = weight
+        self.strategy = strategy
+        self.status = AllocationStatus.ACTIVE
+        self.reason = ""
+        self.scale = 1.0
+
+        # 起始余额和预算在策略获取账户信息后确定
+        self.start_balance = Decimal("0")
+        self.budget = Decimal("0")
+        self.peak_equity = Decimal("0")
+        self.pnl = Decimal("0")
+        self.drawdown = 0.0
+
+    @property
+    def can_open(self) -> bool:
+        """是否允许开新仓"""
+        return self.status != AllocationStatus.DISABLED and self.budget > 0
+
+    def capital(self, available_balance: Decimal) -> Decimal:
+        """
+        计算仓位可用资金（预算按限流比例缩放，不超过账户可用余额）
+
+        Args:
+            available_balance: 账户可用余额
+
+        Returns:
+            Decimal: 可用资金
+        """
+        return min(available_balance, self.budget * Decimal(str(self.scale)))
+
+    def get_stats(self) -> Dict[str, Any]:
+        """获取分配统计"""
+        return {
+            "name": self.name,
+            "weight": self.weight,
+            "budget": float(self.budget),
+            "pnl": float(self.pnl),
+            "drawdown": self.drawdown,
+            "status": self.status,
+            "reason": self.reason,
+            "scale": self.scale,
+        }
+
+
+class CapitalAllocator:
+    """多策略资金分配器"""
+
+    def __init__(self, eval_seconds: int, throttle_drawdown: float, disable_drawdown: float,
+                 throttle_scale: float, min_trades: int, min_win_rate: float):
+        """
+        Args:
+            eval_seconds: 评估间隔（秒）
+            throttle_drawdown: 限流回撤阈值（相对预算）
+            disable_drawdown: 停用回撤阈值（相对预算）
+            throttle_scale: 限流时的预算比例
+            min_trades: 按胜率评估所需的最少交易次数
+            min_win_rate: 最低胜率（0为不检查）
+        """
+        self.eval_seconds = eval_seconds
+        self.throttle_drawdown = throttle_drawdown
+        self.disable_drawdown = disable_drawdown
+        self.throttle_scale = throttle_scale
+        self.min_trades = min_trades
+        self.min_win_rate = min_win_rate
+        self.slots: List[StrategySlot] = []
+        self.is_running = False
+
+    def add(self, name: str, weight: float, strategy) -> StrategySlot:
+        """注册策略并关联分配"""
+        slot = StrategySlot(name, weight, strategy)
+        strategy.allocation = slot
+        self.slots.append(slot)
+        return slot
+
+    def evaluate(self):
+        """更新各策略盈亏和回撤，并按规则调整分配状态"""
+        for slot in self.slots:
+            strategy = slot.strategy
+            if slot.budget <= 0:
+                if strategy.balance <= 0:
+                    continue
+                slot.start_balance = strategy.balance
+                slot.budget = strategy.balance * Decimal(str(slot.weight))
+                slot.peak_equity = slot.budget
+                logger.info(f"[资金分配] {slot.name} 预算 {float(slot.budget):.2f} USDT（权重 {slot.weight:.0%}）")
+
+            slot.pnl = strategy.balance - slot.start_balance + strategy._unrealized_pnl()
+            equity = slot.budget + slot.pnl
+            slot.peak_equity = max(slot.peak_equity, equity)
+            slot.drawdown = float((slot.peak_equity - equity) / slot.budget)
+
+            self._apply_rules(slot)
+
+    def _apply_rules(self, slot: StrategySlot):
+        if slot.status == AllocationStatus.DISABLED:
+            return
+
+        records = slot.strategy.trade_records
+        win_rate = sum(1 for r in records if r.pnl > 0) / len(records) if records else 0.0
+
+        if slot.drawdown >= self.disable_drawdown:
+            status, reason = AllocationStatus.DISABLED, f"回撤 {slot.drawdown:.2%}"
+        elif slot.drawdown >= self.throttle_drawdown:
+            status, reason = AllocationStatus.THROTTLED, f"回撤 {slot.drawdown:.2%}"
+        elif self.min_win_rate > 0 and len(records) >= self.min_trades and win_rate < self.min_win_rate:
+            status, reason = AllocationStatus.THROTTLED, f"胜率 {win_rate:.2%}"
+        else:
+            status, reason = AllocationStatus.ACTIVE, ""
+
+        slot.reason = reason
+        if status == slot.status:
+            return
+        slot.status = status
+        slot.scale = self.throttle_scale if status == AllocationStatus.THROTTLED else 1.0
+        if status == AllocationStatus.DISABLED:
+            logger.warning(f"[资金分配] {slot.name} 已停用（{reason}），停止开仓")
+        elif status == AllocationStatus.THROTTLED:
+            logger.warning(f"[资金分配] {slot.name} 限流（{reason}），预算 x{self.throttle_scale}")
+        else:
+            logger.info(f"[资金分配] {slot.name} 恢复正常预算")
+
+    async def run(self):
+        """定期评估各策略"""
+        self.is_running = True
+        while self.is_running:
+            try:
+                self.evaluate()
+            except Exception as e:
+                logger.error(f"[资金分配] 评估失败: {e}")
+            await asyncio.sleep(self.eval_seconds)
+
+    def stop(self):
+        self.is_running = False
+
+
+def load_strategy_configs(base: Config, path: str) -> List[Dict[str, Any]]:
+    """
+    读取策略列表文件，生成各策略的配置
+
+    Args:
+        base: 全局配置
+        path: 策略列表JSON文件路径
+
+    Returns:
+        List[Dict[str, Any]]: 每项包含 name / weight / config（合并后的Config）
+
+    Raises:
+        ValueError: 文件内容无效、实盘策略权重之和超过1或交易对重叠
+    """
+    with open(path, encoding="utf-8") as fp:
+        specs = json.load(fp)
+    if not isinstance(specs, list) or not specs:
+        raise ValueError("策略列表必须为非空JSON数组")
+
+    entries = []
+    seen_symbols: Dict[str, str] = {}
+    for i, spec in enumerate(specs):
+        name = str(spec.get("name") or f"strategy_{i + 1}")
+        weight = float(spec.get("weight", 0))
+        if weight <= 0:
+            raise ValueError(f"策略 {name} 的权重必须大于0")
+        if any(entry["name"] == name for entry in entries):
+            raise ValueError(f"策略名称重复: {name}")
+
+        overrides = spec.get("config") or {}
+        unknown = set(overrides) - set(Config.model_fields)
+        if unknown:
+            raise ValueError(f"策略 {name} 包含未知配置项: {', '.join(sorted(unknown))}")
+        config = Config(**{**base.model_dump(), **overrides, "strategies_file": "", "ab_test": ""})
+
+        if not config.paper_trading:
+            for symbol in config.symbols:
+                if symbol in seen_symbols:
+                    raise ValueError(f"交易对 {symbol} 同时分配给实盘策略 {seen_symbols[symbol]} 和 {name}")
+                seen_symbols[symbol] = name
+        entries.append({"name": name, "weight": weight, "config": config})
+
+    total = sum(entry["weight"] for entry in entries if not entry["config"].paper_trading)
+    if total > 1 + 1e-9:
+        raise ValueError(f"实盘策略权重之和({total:.2f})不能超过1")
+
+    webhook_ports = [e["config"].webhook_port for e in entries if e["config"].webhook_enabled]
+    if len(webhook_ports) != len(set(webhook_ports)):
+        raise ValueError("多个策略启用了相同端口的信号Webhook")
+    return entries
@@ -0,0 +1,184 @@
+"""
+订单审计日志模块
+
+把每个订单的下单意图、发送给交易所的请求参数、交易所响应以及后续状态变化
+追加写入审计文件（JSON Lines），用于事后排查和合规留存：
+- 每条记录带递增序号（seq）和哈希链：hash = sha256(上一条hash + 本条内容)，
+  任何记录被修改、删除或插入都会在校验时发现
+- 请求和响应中的密钥、签名等敏感字段写入前脱敏
+- 每条记录写入后立即刷盘；重启后从文件最后一条记录继续序号和哈希链
+
+校验：python audit_log.py verify data/audit/orders.log
+"""
+
+import argparse
+import hashlib
+import json
+import os
+import threading
+import time
+import uuid
+from typing import Dict, Optional, Any, Tuple
+
+# 事件类型
+AUDIT_INTENT = "order_intent"
+AUDIT_REQUEST = "order_request"
+AUDIT_RESPONSE = "order_response"
+AUDIT_ERROR = "order_error"
+AUDIT_CANCEL_REQUEST = "cancel_request"
+AUDIT_CANCEL_RESPONSE = "cancel_response"
+AUDIT_CANCEL_ERROR = "cancel_error"
+AUDIT_STATE = "order_state"
+
+GENESIS_HASH = "0" * 64
+
+_SENSITIVE_KEYS = ("secret", "private", "signature", "passphrase", "password", "token", "api_key", "apikey", "authorization")
+_REDACTED = "***"
+
+
+def redact(value: Any) -> Any:
+    """递归脱敏字典中的敏感字段，并把对象转换为可序列化的结构"""
+    if isinstance(value, dict):
+        return {
+            k: _REDACTED if any(s in str(k).lower() for s in _SENSITIVE_KEYS) else redact(v)
+            for k, v in value.items()
+        }
+    if isinstance(value, (list, tuple)):
+        return [redact(v) for v in value]
+    if hasattr(value, "model_dump"):
+        return redact(value.model_dump(mode="json"))
+    if hasattr(value, "__dict__") and not isinstance(value, type):
+        return redact(vars(value))
+    return value
+
+
+def _digest(prev_hash: str, record: Dict[str, Any]) -> str:
+    body = json.dumps(record, ensure_ascii=False, sort_keys=True, separators=(",", ":"), default=str)
+    return hashlib.sha256((prev_hash + body).encode()).hexdigest()
+
+
+class AuditLog:
+    """仅追加的订单审计日志（多个客户端共用同一文件时共用同一实例）"""
+
+    _instances: Dict[str, "AuditLog"] = {}
+    _instances_lock = threading.Lock()
+
+    @classmethod
+    def open(cls, path: str) -> "AuditLog":
+        """获取指定文件的审计日志实例"""
+        key = os.path.abspath(path)
+        with cls._instances_lock:
+            if key not in cls._instances:
+                cls._instances[key] = cls(path)
+            return cls._instances[key]
+
+    def __init__(self, path: str):
+        """
+        Args:
+            path: 审计文件路径
+        """
+        self.path = path
+        self._lock = threading.Lock()
+        os.makedirs(os.path.dirname(path) or ".", exist_ok=True)
+        self.seq, self.last_hash = self._resume()
+        self._fp = open(path, "a", encoding="utf-8")
+
+    def _resume(self) -> Tuple[int, str]:
+        """读取文件最后一条记录的序号和哈希"""
+        if not os.path.exists(self.path):
+            return 0, GENESIS_HASH
+        last = None
+        with open(self.path, encoding="utf-8") as fp:
+            for line in fp:
+                if line.strip():
+                    last = line
+        if last is None:
+            return 0, GENESIS_HASH
+        record = json.loads(last)
+        return record["seq"], record["hash"]
+
+    @staticmethod
+    def new_intent_id() -> str:
+        """生成关联同一下单意图各条记录的ID"""
+        return uuid.uuid4().hex[:16]
+
+    def record(self, event: str, **data: Any) -> int:
+        """
+        追加一条审计记录
+
+        Args:
+            event: 事件类型
+            **data: 事件内容（写入前脱敏）
+
+        Returns:
+            int: 记录序号
+        """
+        with self._lock:
+            entry = {
+                "seq": self.seq + 1,
+                "ts": int(time.time() * 1000),
+                "event": event,
+                "data": json.loads(json.dumps(redact(data), default=str)),
+                "prev_hash": self.last_hash,
+            }
+            entry["hash"] = _digest(self.last_hash, entry)
+            self._fp.write(json.dumps(entry, ensure_ascii=False, default=str) + "\n")
+            self._fp.flush()
+            os.fsync(self._fp.fileno())
+            self.seq = entry["seq"]
+            self.last_hash = entry["hash"]
+            return self.seq
+
+    def close(self):
+        with self._lock:
+            self._fp.close()
+
+
+def verify(path: str) -> Tuple[int, Optional[str]]:
+    """
+    校验审计文件的序号连续性和哈希链
+
+    Args:
+        path: 审计文件路径
+
+    Returns:
+        Tuple[int, Optional[str]]: (已校验的记录数, 第一处错误描述；完整时为None)
+    """
+    prev_hash = GENESIS_HASH
+    count = 0
+    with open(path, encoding="utf-8") as fp:
+        for line_no, line in enumerate(fp, 1):
+            if not line.strip():
+                continue
+            try:
+                entry = json.loads(line)
+            except ValueError:
+                return count, f"第 {line_no} 行不是有效的JSON"
+            if entry.get("seq") != count + 1:
+                return count, f"第 {line_no} 行序号不连续（期望 {count + 1}，实际 {entry.get('seq')}）"
+            if entry.get("prev_hash") != prev_hash:
+                return count, f"第 {line_no} 行前序哈希不匹配（记录被删除或重排）"
+            body = {k: v for k, v in entry.items() if k != "hash"}
+            if _digest(prev_hash, body) != entry.get("hash"):
+                return count, f"第 {line_no} 行哈希不匹配（记录被修改）"
+            prev_hash = entry["hash"]
+            count += 1
+    return count, None
+
+
+def main():
+    parser = argparse.ArgumentParser(description="订单审计日志工具")
+    sub = parser.add_subparsers(dest="command", required=True)
+    verify_parser = sub.add_parser("verify", help="校验审计文件的完整性")
+    verify_parser.add_argument("path", help="审计文件路径")
+    args = parser.parse_args()
+
+    count, error = verify(args.path)
+    if error:
+        print(f"校验失败（已校验 {count} 条）: {error}")
+        raise SystemExit(1)
+    print(f"校验通过: {count} 条记录")
+
+
+if __name__ == "__main__":
+    main()
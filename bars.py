@@ -0,0 +1,172 @@
+"""
+逐笔成交K线构建模块
+
+把逐笔成交聚合为事件驱动的K线，替代固定周期的行情快照：
+- time: 时间K线，按固定秒数切分（对齐到整周期，没有成交的周期不生成K线）
+- volume: 成交量K线，累计成交量（币数量）达到阈值时收线
+- dollar: 成交额K线，累计成交额（USDT）达到阈值时收线
+
+成交量/成交额K线不拆分单笔成交：使累计值达到阈值的那笔成交计入当前K线后收线。
+K线的 timestamp 为第一笔成交的时间（时间K线为周期开始时间），单位毫秒。
+
+回测时可用 build_bars 从回放事件（见 historical_data.py）离线构建同样的K线。
+"""
+
+import time
+from typing import Any, Dict, Iterable, List, Optional
+
+from edgex_types import PriceData
+
+BAR_TYPES = ("time", "volume", "dollar")
+
+
+class BarBuilder:
+    """K线构建器基类"""
+
+    def __init__(self, size: float):
+        """
+        Args:
+            size: K线大小（时间K线为秒，成交量K线为币数量，成交额K线为USDT）
+        """
+        if size <= 0:
+            raise ValueError(f"K线大小必须大于0: {size}")
+        self.size = size
+        self._bar: Optional[PriceData] = None
+        self.completed = 0
+
+    @property
+    def current(self) -> Optional[PriceData]:
+        """未收线的当前K线"""
+        return self._bar
+
+    def on_trade(self, price: float, size: float, timestamp: Optional[int] = None) -> List[PriceData]:
+        """
+        处理一笔成交
+
+        Args:
+            price: 成交价
+            size: 成交数量
+            timestamp: 成交时间（毫秒），None时使用当前时间
+
+        Returns:
+            List[PriceData]: 本笔成交导致收线的K线（通常为空或一根）
+        """
+        if timestamp is None:
+            timestamp = int(time.time() * 1000)
+        completed = self.on_time(timestamp)
+
+        if self._bar is None:
+            self._bar = PriceData(
+                timestamp=self._bar_start(timestamp),
+                open=price, high=price, low=price, close=price, volume=0.0
+            )
+            self._reset()
+        bar = self._bar
+        bar.high = max(bar.high, price)
+        bar.low = min(bar.low, price)
+        bar.close = price
+        bar.volume += size
+
+        if self._accumulate(price, size):
+            completed.append(self._close())
+        return completed
+
+    def on_time(self, now_ms: int) -> List[PriceData]:
+        """
+        按时间检查收线（没有新成交时由主循环调用，时间K线使用）
+
+        Returns:
+            List[PriceData]: 到期收线的K线
+        """
+        return []
+
+    def _close(self) -> PriceData:
+        bar, self._bar = self._bar, None
+        self.completed += 1
+        return bar
+
+    def _bar_start(self, timestamp: int) -> int:
+        return timestamp
+
+    def _reset(self):
+        pass
+
+    def _accumulate(self, price: float, size: float) -> bool:
+        """累计成交，返回是否达到收线条件"""
+        return False
+
+
+class TimeBarBuilder(BarBuilder):
+    """时间K线：size 为秒"""
+
+    @property
+    def interval_ms(self) -> int:
+        return max(1, int(self.size * 1000))
+
+    def _bar_start(self, timestamp: int) -> int:
+        return timestamp - timestamp % self.interval_ms
+
+    def on_time(self, now_ms: int) -> List[PriceData]:
+        if self._bar is not None and now_ms >= self._bar.timestamp + self.interval_ms:
+            return [self._close()]
+        return []
+
+
+class VolumeBarBuilder(BarBuilder):
+    """成交量K线：size 为每根K线的成交量（币数量）"""
+
+    def _reset(self):
+        self._volume = 0.0
+
+    def _accumulate(self, price: float, size: float) -> bool:
+        self._volume += size
+        return self._volume >= self.size
+
+
+class DollarBarBuilder(BarBuilder):
+    """成交额K线：size 为每根K线的成交额（USDT）"""
+
+    def _reset(self):
+        self._notional = 0.0
+
+    def _accumulate(self, price: float, size: float) -> bool:
+        self._notional += price * size
+        return self._notional >= self.size
+
+
+def create_bar_builder(bar_type: str, size: float) -> BarBuilder:
+    """
+    创建K线构建器
+
+    Args:
+        bar_type: K线类型（time / volume / dollar）
+        size: K线大小
+
+    Returns:
+        BarBuilder: K线构建器
+    """
+    builders = {"time": TimeBarBuilder, "volume": VolumeBarBuilder, "dollar": DollarBarBuilder}
+    if bar_type not in builders:
+        raise ValueError(f"不支持的K线类型: {bar_type}（可选: {', '.join(BAR_TYPES)}）")
+    return builders[bar_type](size)
+
+
+def build_bars(events: Iterable[Dict[str, Any]], symbol: str, bar_type: str, size: float) -> List[PriceData]:
+    """
+    从回放事件离线构建K线（回测使用）
+
+    Args:
+        events: 按时间排序的回放事件（只使用该交易对的 trade 事件）
+        symbol: 交易对
+        bar_type: K线类型
+        size: K线大小
+
+    Returns:
+        List[PriceData]: 已收线的K线（最后一根未收线的K线不包含在内）
+    """
+    builder = create_bar_builder(bar_type, size)
+    bars: List[PriceData] = []
+    for event in events:
+        if event.get("type") == "trade" and event.get("symbol") == symbol:
+            bars.extend(builder.on_trade(float(event["price"]), float(event["size"]), int(event["timestamp"])))
+    return bars
@@ -0,0 +1,146 @@
+"""
+基差监控模块
+
+按合约跟踪永续合约价格相对指数价格（现货指数）的基差：basis_bps = (永续价格 - 指数价格) / 指数价格 × 10000，
+永续价格取盘口中间价（无盘口时为最新成交价），指数价格来自ticker的 indexPrice（WebSocket推送或REST轮询）：
+- 每 sample_seconds 采样一次，保留最近 window 个样本计算均值、标准差和当前基差的z-score
+- 信号特征: 最新基差作为 SignalContext.basis_bps 提供给脚本信号、组合信号和特征记录
+- 报告序列: 每个样本追加写入CSV文件（timestamp, symbol, perp_price, index_price, basis_bps, zscore）
+- 开仓过滤（可选）: 基差向开仓方向拉伸过度时跳过开仓（升水时不做多、贴水时不做空），
+  条件为 方向 × 基差 超过 max_basis_bps，或 方向 × z-score 超过 max_zscore；
+  没有指数价格或数据过期时不过滤
+"""
+
+import csv
+import math
+import os
+import time
+from collections import deque
+from typing import Any, Deque, Dict, Optional, Tuple
+
+from loguru import logger
+
+# 计算z-score所需的最少样本数
+MIN_SAMPLES = 10
+
+SERIES_FIELDS = ["timestamp", "symbol", "perp_price", "index_price", "basis_bps", "zscore"]
+
+
+class BasisTracker:
+    """永续合约与指数价格的基差跟踪"""
+
+    def __init__(self, window: int = 120, sample_seconds: float = 5.0, max_basis_bps: float = 0.0,
+                 max_zscore: float = 0.0, max_age_seconds: float = 60.0, series_path: str = ""):
+        """
+        Args:
+            window: 计算均值和z-score的样本数
+            sample_seconds: 采样间隔（秒）
+            max_basis_bps: 向开仓方向的基差上限（bps，0为不检查）
+            max_zscore: 向开仓方向的基差z-score上限（0为不检查）
+            max_age_seconds: 最新样本超过多少秒视为过期（不再作为特征或用于过滤）
+            series_path: 基差序列CSV文件（空为不写入）
+        """
+        self.window = window
+        self.sample_seconds = sample_seconds
+        self.max_basis_bps = max_basis_bps
+        self.max_zscore = max_zscore
+        self.max_age_seconds = max_age_seconds
+        self.series_path = series_path
+        self.samples: Dict[str, Deque[float]] = {}
+        self.latest: Dict[str, Tuple[float, float, Optional[float]]] = {}   # 交易对 -> (采样时间, 基差, z-score)
+        self.blocked: Dict[str, int] = {}
+
+    def sample(self, symbol: str, perp_price: float, index_price: float,
+               now: Optional[float] = None) -> Optional[float]:
+        """
+        采样一次基差（距上次采样不足 sample_seconds 时忽略）
+
+        Returns:
+            Optional[float]: 本次采样的基差（bps），未采样时为None
+        """
+        now = now if now is not None else time.time()
+        if perp_price <= 0 or index_price <= 0:
+            return None
+        last = self.latest.get(symbol)
+        if last and now - last[0] < self.sample_seconds:
+            return None
+        basis = (perp_price - index_price) / index_price * 10000
+        samples = self.samples.setdefault(symbol, deque(maxlen=self.window))
+        samples.append(basis)
+        zscore = self._zscore(samples)
+        self.latest[symbol] = (now, basis, zscore)
+        self._write(now, symbol, perp_price, index_price, basis, zscore)
+        return basis
+
+    @staticmethod
+    def _zscore(samples: Deque[float]) -> Optional[float]:
+        if len(samples) < MIN_SAMPLES:
+            return None
+        mean = sum(samples) / len(samples)
+        std = math.sqrt(sum((s - mean) ** 2 for s in samples) / (len(samples) - 1))
+        return (samples[-1] - mean) / std if std > 0 else 0.0
+
+    def _fresh(self, symbol: str, now: Optional[float]) -> Optional[Tuple[float, float, Optional[float]]]:
+        latest = self.latest.get(symbol)
+        now = now if now is not None else time.time()
+        if latest is None or now - latest[0] > self.max_age_seconds:
+            return None
+        return latest
+
+    def current(self, symbol: str, now: Optional[float] = None) -> Optional[float]:
+        """最新基差（bps，无数据或过期时为None）"""
+        latest = self._fresh(symbol, now)
+        return latest[1] if latest else None
+
+    def check(self, symbol: str, direction: int, now: Optional[float] = None) -> Optional[str]:
+        """
+        检查基差是否允许开仓
+
+        Args:
+            direction: 开仓方向（1为做多，-1为做空）
+
+        Returns:
+            Optional[str]: 跳过原因，允许开仓时返回None
+        """
+        latest = self._fresh(symbol, now)
+        if latest is None:
+            return None
+        _, basis, zscore = latest
+        reason = None
+        if self.max_basis_bps > 0 and direction * basis > self.max_basis_bps:
+            reason = f"基差 {basis:+.2f} bps 超过上限 {self.max_basis_bps} bps"
+        elif self.max_zscore > 0 and zscore is not None and direction * zscore > self.max_zscore:
+            reason = f"基差 {basis:+.2f} bps 的z-score {zscore:+.2f} 超过上限 {self.max_zscore}"
+        if reason:
+            self.blocked[symbol] = self.blocked.get(symbol, 0) + 1
+        return reason
+
+    def _write(self, now: float, symbol: str, perp_price: float, index_price: float, basis: float,
+               zscore: Optional[float]):
+        if not self.series_path:
+            return
+        try:
+            os.makedirs(os.path.dirname(self.series_path) or ".", exist_ok=True)
+            is_new = not os.path.exists(self.series_path)
+            with open(self.series_path, "a", newline="") as fp:
+                writer = csv.writer(fp)
+                if is_new:
+                    writer.writerow(SERIES_FIELDS)
+                writer.writerow([int(now * 1000), symbol, perp_price, index_price, round(basis, 4),
+                                 round(zscore, 4) if zscore is not None else ""])
+        except OSError as e:
+            logger.error(f"[基差] 写入基差序列失败: {e}")
+
+    def get_stats(self) -> Dict[str, Any]:
+        symbols = {}
+        for symbol, (_, basis, zscore) in self.latest.items():
+            samples = self.samples[symbol]
+            symbols[symbol] = {
+                "basis_bps": round(basis, 2),
+                "zscore": round(zscore, 2) if zscore is not None else None,
+                "mean_bps": round(sum(samples) / len(samples), 2),
+                "min_bps": round(min(samples), 2),
+                "max_bps": round(max(samples), 2),
+                "blocked": self.blocked.get(symbol, 0),
+            }
+        return {"max_basis_bps": self.max_basis_bps, "max_zscore": self.max_zscore, "symbols": symbols}
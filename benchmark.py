@@ -0,0 +1,79 @@
+"""
+基准对比模块
+
+以同一时段、同一组交易对的买入持有组合为基准（各交易对等权重，从各交易对都有价格的第一个采样开始），
+按固定间隔同时采样策略权益（余额+未实现盈亏）和基准净值，比较两者的采样期收益率：
+- beta: 策略收益率对基准收益率的回归系数（协方差 / 基准方差）
+- alpha: 策略平均收益率中不能由 beta × 基准收益率解释的部分（按平均采样间隔年化）
+- correlation: 两者收益率的相关系数
+同时报告同期策略收益率、基准收益率和超额收益。
+"""
+
+import math
+import time
+from collections import deque
+from typing import Any, Deque, Dict, Iterable, Optional, Tuple
+
+SECONDS_PER_YEAR = 365 * 24 * 3600
+MIN_SAMPLES = 3
+
+
+class BenchmarkTracker:
+    """买入持有基准对比"""
+
+    def __init__(self, symbols: Iterable[str], sample_seconds: int = 60, capacity: int = 10000):
+        """
+        Args:
+            symbols: 基准组合的交易对
+            sample_seconds: 采样间隔（秒）
+            capacity: 保留的采样个数
+        """
+        self.symbols = list(symbols)
+        self.sample_seconds = sample_seconds
+        self.start_prices: Dict[str, float] = {}
+        self.samples: Deque[Tuple[float, float, float]] = deque(maxlen=capacity)   # (时间, 策略权益, 基准净值)
+        self._last_sample: Optional[float] = None
+
+    def sample(self, equity: float, prices: Dict[str, float], now: Optional[float] = None):
+        """采样策略权益和基准净值（未到采样间隔或缺少价格时忽略）"""
+        now = time.time() if now is None else now
+        if self._last_sample is not None and now - self._last_sample < self.sample_seconds:
+            return
+        if equity <= 0 or any(prices.get(symbol, 0) <= 0 for symbol in self.symbols):
+            return
+        if not self.start_prices:
+            self.start_prices = {symbol: prices[symbol] for symbol in self.symbols}
+        benchmark = sum(prices[s] / self.start_prices[s] for s in self.symbols) / len(self.symbols)
+        self.samples.append((now, equity, benchmark))
+        self._last_sample = now
+
+    def get_summary(self) -> Optional[Dict[str, Any]]:
+        """对比结果（采样不足时为None）"""
+        if len(self.samples) < MIN_SAMPLES:
+            return None
+        samples = list(self.samples)
+        pairs = [(b[1] / a[1] - 1, b[2] / a[2] - 1) for a, b in zip(samples, samples[1:])]
+        n = len(pairs)
+        mean_s = sum(s for s, _ in pairs) / n
+        mean_b = sum(b for _, b in pairs) / n
+        cov = sum((s - mean_s) * (b - mean_b) for s, b in pairs) / (n - 1)
+        var_s = sum((s - mean_s) ** 2 for s, _ in pairs) / (n - 1)
+        var_b = sum((b - mean_b) ** 2 for _, b in pairs) / (n - 1)
+        beta = cov / var_b if var_b > 0 else 0.0
+        correlation = cov / math.sqrt(var_s * var_b) if var_s > 0 and var_b > 0 else 0.0
+        interval = (samples[-1][0] - samples[0][0]) / n
+        periods = SECONDS_PER_YEAR / interval if interval > 0 else 0.0
+
+        strategy_return = samples[-1][1] / samples[0][1] - 1
+        benchmark_return = samples[-1][2] / samples[0][2] - 1
+        return {
+            "symbols": self.symbols,
+            "samples": len(samples),
+            "since": samples[0][0],
+            "strategy_return": strategy_return,
+            "benchmark_return": benchmark_return,
+            "excess_return": strategy_return - benchmark_return,
+            "alpha": (mean_s - beta * mean_b) * periods,
+            "beta": beta,
+            "correlation": correlation,
+        }
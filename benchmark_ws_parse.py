@@ -0,0 +1,106 @@
+"""
+WebSocket行情消息解析基准测试
+
+对比 ticker/depth/trades 频道每条消息的解析耗时：
+- baseline: SDK用标准库json解码一次（按频道分发），处理器再解码一次，并线性查找合约ID
+- fast: SDK解码一次（orjson可用时使用orjson）后直接把结果交给处理器，合约ID查反向索引
+
+用法：
+  python benchmark_ws_parse.py [--messages 20000] [--symbols 20]
+"""
+
+import argparse
+import json
+import time
+from typing import Any, Callable, Dict, List
+
+import fast_json
+from websocket_client import RealTimePriceStream
+
+
+def _messages(contract_ids: Dict[str, str], count: int) -> Dict[str, List[str]]:
+    """生成各频道的模拟消息（轮流覆盖所有合约）"""
+    cids = list(contract_ids.values())
+    ticker, depth, trades = [], [], []
+    for i in range(count):
+        cid = cids[i % len(cids)]
+        ts = 1700000000000 + i
+        ticker.append(json.dumps({
+            "type": "quote-event", "channel": f"ticker.{cid}",
+            "content": {"channel": f"ticker.{cid}", "dataType": "Snapshot", "data": [{
+                "contractId": cid, "lastPrice": "65000.5", "open": "64000.1", "high": "65500.0",
+                "low": "63900.2", "size": "1234.567", "value": "80000000.12", "oraclePrice": "65001.2",
+                "indexPrice": "64999.8", "fundingRate": "0.0001", "timestamp": str(ts)
+            }]}
+        }))
+        depth.append(json.dumps({
+            "type": "quote-event", "channel": f"depth.{cid}.15",
+            "content": {"channel": f"depth.{cid}.15", "dataType": "Changed", "data": [{
+                "contractId": cid, "depthType": "CHANGED",
+                "bids": [{"price": f"{65000 - j * 0.5:.1f}", "size": f"{0.1 * (j + 1):.3f}"} for j in range(15)],
+                "asks": [{"price": f"{65001 + j * 0.5:.1f}", "size": f"{0.1 * (j + 1):.3f}"} for j in range(15)]
+            }]}
+        }))
+        trades.append(json.dumps({
+            "type": "quote-event", "channel": f"trades.{cid}",
+            "content": {"channel": f"trades.{cid}", "dataType": "Changed", "data": [
+                {"price": "65000.5", "size": "0.012", "isBuyerMaker": j % 2 == 0, "time": str(ts)} for j in range(5)
+            ]}
+        }))
+    return {"ticker": ticker, "depth": depth, "trades": trades}
+
+
+def _baseline(stream: RealTimePriceStream, message: str) -> tuple:
+    """改造前的路径：SDK解码 + 处理器重复解码 + 线性查找合约ID"""
+    msg = json.loads(message)
+    msg.get("type")
+    data = json.loads(message)
+    channel = data.get("channel") or data.get("content", {}).get("channel", "")
+    parts = channel.split(".")
+    contract_id = parts[1] if len(parts) > 1 else ""
+    symbol = None
+    for sym, cid in stream.contract_ids.items():
+        if cid == contract_id:
+            symbol = sym
+            break
+    return symbol, data.get("content", {}).get("data", data.get("data"))
+
+
+def _fast(stream: RealTimePriceStream, message: str) -> tuple:
+    """当前路径：SDK解码一次，处理器直接使用解码结果"""
+    msg = fast_json.loads(message)
+    msg.get("type")
+    return stream._resolve_message(msg)
+
+
+def _run(func: Callable[[RealTimePriceStream, str], Any], stream: RealTimePriceStream, messages: List[str]) -> float:
+    """返回每条消息的平均耗时（微秒）"""
+    start = time.perf_counter()
+    for message in messages:
+        func(stream, message)
+    return (time.perf_counter() - start) / len(messages) * 1e6
+
+
+def main():
+    parser = argparse.ArgumentParser(description="WebSocket行情消息解析基准测试")
+    parser.add_argument("--messages", type=int, default=20000, help="每个频道的消息数")
+    parser.add_argument("--symbols", type=int, default=20, help="订阅的交易对数量")
+    args = parser.parse_args()
+
+    contract_ids = {f"SYM{i}-USDT": str(10000001 + i) for i in range(args.symbols)}
+    # 只测试解析，不建立连接
+    stream = RealTimePriceStream.__new__(RealTimePriceStream)
+    stream.contract_ids = contract_ids
+    stream._contract_symbols = {}
+
+    print(f"JSON解码: {fast_json.BACKEND} | 交易对: {args.symbols} | 每频道消息: {args.messages}")
+    print(f"{'频道':<8}{'baseline(μs)':>14}{'fast(μs)':>12}{'加速':>8}")
+    for channel, messages in _messages(contract_ids, args.messages).items():
+        assert _baseline(stream, messages[0]) == _fast(stream, messages[0])
+        baseline = _run(_baseline, stream, messages)
+        fast = _run(_fast, stream, messages)
+        print(f"{channel:<8}{baseline:>14.2f}{fast:>12.2f}{baseline / fast:>7.2f}x")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,99 @@
+"""
+订单簿校验模块
+
+每次合并深度数据后校验本地订单簿，发现损坏时丢弃并重新同步（重新订阅深度频道，等待全量快照）：
+- 一致性检查: 买卖盘价格和数量为正、买盘价格降序、卖盘价格升序、买一价低于卖一价（没有交叉）
+- 校验和（可选）: 深度数据带 checksum 字段时，与本地订单簿前25档计算的CRC32比较。
+  计算方式为按 买价:买量:卖价:卖量 交替拼接各档（一侧档位不足时只拼接另一侧），取有符号32位CRC32；
+  数值按去掉末尾 ".0" 的十进制字符串格式化
+重新同步期间（收到快照之前）的增量数据全部丢弃。
+"""
+
+import zlib
+from typing import Any, Dict, List, Optional, Set
+
+from edgex_types import OrderBook
+
+CHECKSUM_LEVELS = 25
+
+
+def _number(value: float) -> str:
+    text = repr(float(value))
+    return text[:-2] if text.endswith(".0") else text
+
+
+def book_checksum(bids: List[List[float]], asks: List[List[float]], levels: int = CHECKSUM_LEVELS) -> int:
+    """计算订单簿前 levels 档的有符号CRC32校验和"""
+    parts: List[str] = []
+    for i in range(levels):
+        if i < len(bids):
+            parts.extend((_number(bids[i][0]), _number(bids[i][1])))
+        if i < len(asks):
+            parts.extend((_number(asks[i][0]), _number(asks[i][1])))
+    crc = zlib.crc32(":".join(parts).encode())
+    return crc - (1 << 32) if crc >= (1 << 31) else crc
+
+
+def check_consistency(book: OrderBook) -> Optional[str]:
+    """检查订单簿一致性，返回问题说明（一致时为None）"""
+    for name, levels, descending in (("买盘", book.bids, True), ("卖盘", book.asks, False)):
+        previous = None
+        for price, size in levels:
+            if price <= 0 or size <= 0:
+                return f"{name}档位 {price}/{size} 价格或数量不为正"
+            if previous is not None and ((price >= previous) if descending else (price <= previous)):
+                return f"{name}价格未按{'降序' if descending else '升序'}排列（{previous} → {price}）"
+            previous = price
+    if book.bids and book.asks and book.bids[0][0] >= book.asks[0][0]:
+        return f"买一价 {book.bids[0][0]} 不低于卖一价 {book.asks[0][0]}（盘口交叉）"
+    return None
+
+
+class BookValidator:
+    """本地订单簿校验"""
+
+    def __init__(self, checksum: bool = False):
+        """
+        Args:
+            checksum: 是否校验深度数据中的 checksum 字段
+        """
+        self.checksum = checksum
+        self.awaiting_snapshot: Set[str] = set()
+        self.stats = {"checksum_ok": 0, "checksum_mismatches": 0, "inconsistent": 0, "resyncs": 0}
+
+    def accept(self, symbol: str, depth: Dict[str, Any]) -> bool:
+        """重新同步期间只接受全量快照"""
+        if str(depth.get("depthType", "SNAPSHOT")).upper() == "SNAPSHOT":
+            self.awaiting_snapshot.discard(symbol)
+            return True
+        return symbol not in self.awaiting_snapshot
+
+    def validate(self, symbol: str, book: OrderBook, depth: Dict[str, Any]) -> Optional[str]:
+        """
+        校验合并后的订单簿，未通过时进入等待快照状态
+
+        Returns:
+            Optional[str]: 未通过的原因（通过时为None）
+        """
+        reason = check_consistency(book)
+        if reason:
+            self.stats["inconsistent"] += 1
+        elif self.checksum and depth.get("checksum") not in (None, ""):
+            expected = int(depth["checksum"])
+            actual = book_checksum(book.bids, book.asks)
+            if actual != expected:
+                self.stats["checksum_mismatches"] += 1
+                reason = f"校验和不一致（本地 {actual}，交易所 {expected}）"
+            else:
+                self.stats["checksum_ok"] += 1
+        if reason:
+            self.awaiting_snapshot.add(symbol)
+            self.stats["resyncs"] += 1
+        return reason
+
+    def reset(self):
+        """新连接：订阅后先收到快照"""
+        self.awaiting_snapshot.clear()
+
+    def get_stats(self) -> Dict[str, Any]:
+        return {**self.stats, "resyncing": len(self.awaiting_snapshot), "checksum": self.checksum}
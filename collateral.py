@@ -0,0 +1,132 @@
+"""
+多币种抵押资产模块
+
+账户可以同时持有多种抵押资产（USDT/USDC等），账户资产接口按资产分别返回：
+- collateralList: 各资产余额（amount）
+- collateralAssetModelList: 各资产的权益（totalEquity）和可用余额（availableAmount）
+资产ID由元数据的 coinList 映射为名称。各资产按汇率折算为报告币种后汇总：
+- 账户余额 = Σ 资产权益 × 汇率
+- 可用保证金 = Σ max(可用余额, 0) × 汇率 × (1 - 折扣)
+报告币种本身的汇率为1；其他资产使用配置的汇率，未配置汇率的资产不计入余额和可用保证金（记录警告）。
+接口只返回旧格式（顶层 totalEquity / availableBalance），或只有一种资产且元数据中没有其名称时，视为报告币种的单一资产。
+"""
+
+from typing import Any, Dict, Iterable, Optional, Tuple
+
+from loguru import logger
+
+from edgex_types import CollateralAsset
+
+
+def _float(value: Any) -> float:
+    try:
+        return float(value) if value not in (None, "") else 0.0
+    except (TypeError, ValueError):
+        return 0.0
+
+
+def parse_asset_map(text: str) -> Dict[str, float]:
+    """
+    解析按资产配置的数值（格式: "USDC:1.0,USDT:1"，资产名称不区分大小写）
+
+    Raises:
+        ValueError: 格式错误或数值为负
+    """
+    result = {}
+    for item in text.split(","):
+        if not item.strip():
+            continue
+        coin, sep, value = item.partition(":")
+        if not sep or not coin.strip():
+            raise ValueError(f"资产配置格式错误: {item}（应为 资产:数值）")
+        try:
+            number = float(value)
+        except ValueError:
+            raise ValueError(f"资产配置数值错误: {item}")
+        if number < 0:
+            raise ValueError(f"资产配置数值不能为负数: {item}")
+        result[coin.strip().upper()] = number
+    return result
+
+
+def parse_coin_names(coin_list: Iterable[Dict[str, Any]]) -> Dict[str, str]:
+    """解析元数据 coinList 为 资产ID -> 名称"""
+    names = {}
+    for coin in coin_list:
+        coin_id = str(coin.get("coinId", ""))
+        name = str(coin.get("coinName", "")).upper()
+        if coin_id and name:
+            names[coin_id] = name
+    return names
+
+
+class CollateralModel:
+    """抵押资产解析与折算"""
+
+    def __init__(self, reporting_currency: str = "USDT", rates: Optional[Dict[str, float]] = None,
+                 haircuts: Optional[Dict[str, float]] = None, coin_names: Optional[Dict[str, str]] = None):
+        """
+        Args:
+            reporting_currency: 报告币种（余额、盈亏和保证金的计价单位）
+            rates: 资产 -> 折算为报告币种的汇率
+            haircuts: 资产 -> 计入可用保证金时的折扣比例
+            coin_names: 资产ID -> 名称（由客户端从元数据更新）
+        """
+        self.reporting_currency = reporting_currency.upper()
+        self.rates = {k.upper(): v for k, v in (rates or {}).items()}
+        self.haircuts = {k.upper(): v for k, v in (haircuts or {}).items()}
+        self.coin_names: Dict[str, str] = coin_names if coin_names is not None else {}
+        self._warned: set = set()
+
+    def rate(self, coin: str) -> Optional[float]:
+        """资产折算为报告币种的汇率（未配置时为None）"""
+        if coin == self.reporting_currency:
+            return 1.0
+        return self.rates.get(coin)
+
+    def parse(self, asset_data: Dict[str, Any]) -> Dict[str, CollateralAsset]:
+        """解析账户资产接口返回的各抵押资产"""
+        models = asset_data.get("collateralAssetModelList") or []
+        if not models:
+            coin = self.reporting_currency
+            return {coin: CollateralAsset(
+                coin_id=coin, coin=coin,
+                amount=_float(asset_data.get("totalEquity")),
+                equity=_float(asset_data.get("totalEquity")),
+                available=_float(asset_data.get("availableBalance")),
+                rate=1.0,
+            )}
+        amounts = {str(c.get("coinId", "")): _float(c.get("amount")) for c in asset_data.get("collateralList") or []}
+        collaterals = {}
+        for model in models:
+            coin_id = str(model.get("coinId", ""))
+            coin = self.coin_names.get(coin_id) or (self.reporting_currency if len(models) == 1 else coin_id)
+            collaterals[coin] = CollateralAsset(
+                coin_id=coin_id, coin=coin,
+                amount=amounts.get(coin_id, _float(model.get("totalEquity"))),
+                equity=_float(model.get("totalEquity")),
+                available=_float(model.get("availableAmount")),
+                rate=self.rate(coin),
+                haircut=self.haircuts.get(coin, 0.0),
+            )
+        return collaterals
+
+    def totals(self, collaterals: Dict[str, CollateralAsset]) -> Tuple[float, float]:
+        """
+        按报告币种汇总
+
+        Returns:
+            Tuple[float, float]: (账户余额, 可用保证金)
+        """
+        balance = 0.0
+        available = 0.0
+        for coin, asset in collaterals.items():
+            if asset.rate is None:
+                if coin not in self._warned and (asset.equity or asset.available):
+                    self._warned.add(coin)
+                    logger.warning(f"[抵押资产] {coin} 未配置折算为 {self.reporting_currency} 的汇率，"
+                                   f"不计入余额和可用保证金（EDGEX_COLLATERAL_RATES）")
+                continue
+            balance += asset.equity * asset.rate
+            available += max(asset.available, 0.0) * asset.rate * (1 - asset.haircut)
+        return balance, available
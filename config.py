@@ -3,11 +3,30 @@
 """
 
 import os
-from typing import List, Optional
+from urllib.parse import urlsplit
+from typing import Dict, List, Optional
 from pydantic import BaseModel, Field
 from dotenv import load_dotenv
 from loguru import logger
 
+from drawdown import parse_levels
+from daily_stats import load_timezone
+from feature_recorder import parse_horizons
+from metrics_server import PUSH_FORMATS
+from reconcile import ORDER_POLICIES, POSITION_POLICIES
+from regime import load_profiles
+from email_notifier import SMTP_SECURITY
+from log_rotation import LOG_COMPRESSIONS
+from price_source import PRICE_SOURCES
+from notify_throttle import CHANNELS as NOTIFY_CHANNELS, parse_rate_limits
+from funding_window import FUNDING_ACTIONS
+from collateral import parse_asset_map
+from report_sinks import REPORT_TYPES, SINK_TYPES, parse_report_sinks
+from runtime import EVENT_LOOPS, parse_cpu_list
+from signal_ensemble import ENSEMBLE_MODES, parse_weights
+from sizing import PositionSizing
+from universe import UNIVERSE_RANKS
+
 
 class Config(BaseModel):
     """机器人配置"""
@@ -22,8 +41,63 @@ class Config(BaseModel):
     public_key: Optional[str] = Field(None, description="Stark公钥")
     public_key_y_coordinate: Optional[str] = Field(None, description="Stark公钥Y坐标")
     
+    # 运行时配置
+    event_loop: str = Field("asyncio", description="事件循环实现（asyncio / uvloop）")
+    executor_workers: int = Field(0, description="默认线程池工作线程数（0为Python默认值）")
+    execution_cpus: str = Field("", description="事件循环线程（策略与下单执行）绑定的CPU，如 2 或 2-3（仅Linux，空为不绑核）")
+    market_data_cpus: str = Field("", description="行情线程（WebSocket读取/分发/发送）绑定的CPU（仅Linux，空为不绑核）")
+    
     # 网络配置
     testnet: bool = Field(False, description="是否使用测试网（False=主网，True=测试网）")
+    http_timeout: float = Field(30.0, description="REST请求总超时（秒）")
+    http_connect_timeout: float = Field(10.0, description="建立连接超时（秒，含TCP/TLS握手和等待连接池）")
+    http_pool_size: int = Field(100, description="连接池最大连接数")
+    http_pool_per_host: int = Field(30, description="连接池对API主机的最大连接数")
+    http_keepalive_seconds: float = Field(30.0, description="空闲连接保持时间（秒）")
+    http_order_pool_size: int = Field(10, description="下单/撤单专用连接池的连接数")
+    http_order_timeout: float = Field(5.0, description="下单/撤单请求超时（秒）")
+    http_query_timeout: float = Field(10.0, description="账户/订单/行情查询请求超时（秒）")
+    http_history_timeout: float = Field(30.0, description="K线历史和合约元数据请求超时（秒）")
+    signature_recv_window_ms: int = Field(5000, description="签名时间戳与交易所时间允许的偏差（毫秒），本地时钟偏差超过一半时按服务器时间校正")
+    signature_timestamp_retries: int = Field(1, description="请求因时间戳超出范围被拒绝时，校准时钟后重新签名重试的次数")
+    order_ack_timeout_seconds: float = Field(5.0, description="下单后等待交易所订单更新的时间（秒），超时按订单ID查询确认，0为不检查")
+    order_unconfirmed_expire_seconds: float = Field(60.0, description="已提交、未确认的订单持续查询不到多少秒后按已拒绝处理")
+    rest_endpoints: List[str] = Field([], description="REST API地址列表（为空时按测试网/主网使用默认地址）")
+    ws_endpoints: List[str] = Field([], description="WebSocket地址列表（为空时按测试网/主网使用默认地址）")
+    endpoint_probe_seconds: float = Field(30.0, description="端点延迟探测间隔（秒）")
+    endpoint_failover_errors: int = Field(3, description="端点连续出错多少次后切换")
+    endpoint_cooldown_seconds: float = Field(60.0, description="故障端点的冷却时间（秒）")
+    ws_ack_timeout: float = Field(5.0, description="WebSocket订阅确认超时（秒）")
+    ws_compression: bool = Field(False, description="WebSocket消息压缩（permessage-deflate）")
+    contract_spec_cache_path: str = Field("data/contracts.json", description="合约规格缓存文件（空为不保存）")
+    contract_spec_refresh_seconds: int = Field(3600, description="合约规格定期刷新间隔（秒，0为只在启动时拉取）")
+    halt_error_codes: str = Field("MAINTENANCE,TRADE_DISABLE,TRADING_DISABLE,NOT_TRADABLE,status code: 503",
+                                  description="表示交易所维护或合约暂停交易的下单错误码（逗号分隔，不区分大小写）")
+    halt_retry_seconds: float = Field(60.0, description="下单返回暂停交易错误后，恢复尝试前暂停的秒数")
+    ws_sequence_check: bool = Field(True, description="检查深度/逐笔成交序列，深度缺口时重新同步订单簿")
+    ws_book_validation: bool = Field(True, description="每次深度更新后校验本地订单簿，损坏时重新同步")
+    ws_book_checksum: bool = Field(False, description="校验深度数据中的校验和（checksum字段）")
+    ws_public_connections: int = Field(1, description="公共行情WebSocket连接数（交易对按频道数均衡分配）")
+    ws_max_channels_per_connection: int = Field(0, description="每条公共行情连接最多订阅的频道数（0为不限制）")
+    proxy_url: str = Field("", description="出站代理（http://、socks5:// 或 socks5h://，REST和WebSocket共用，空为直连）")
+    
+    # 模拟盘配置（订单由本地撮合引擎基于实时深度撮合，不会提交到交易所）
+    paper_trading: bool = Field(False, description="是否启用模拟盘（沙盒）模式")
+    paper_initial_balance: float = Field(10000.0, description="模拟盘初始资金（USDT）")
+    
+    # 影子交易配置（并行运行理想成交的模拟策略，对比决策与成交差异）
+    shadow_mode: bool = Field(False, description="是否启用影子交易对比")
+    
+    # 持仓模式（one_way=单向净持仓，hedge=多空双向持仓，需与交易所账户设置一致）
+    position_mode: str = Field("one_way", description="持仓模式")
+    
+    # 执行配置
+    execution_policy: str = Field("market", description="开仓执行策略（market=市价，limit_then_market=先挂限价单超时后转市价）")
+    limit_wait_ms: int = Field(3000, description="限价单等待成交时间（毫秒）")
+    limit_price_offset_ticks: int = Field(0, description="限价单相对最优价向盘口内侧偏移的tick数（0=挂在最优价）")
+    requote_threshold_ticks: int = Field(0, description="盘口偏离挂单价格超过该tick数时撤单重挂（0=不改价）")
+    max_requotes_per_minute: int = Field(10, description="每个交易对每分钟最多改价次数")
+    order_ttl_ms: int = Field(30000, description="限价单存活时间（毫秒），超时自动撤单，0表示不限")
     
     # 交易配置
     symbols: List[str] = Field(
@@ -31,12 +105,223 @@ class Config(BaseModel):
         description="交易对列表（支持多交易对并发交易）"
     )
     
+    # 动态交易对（定期扫描市场，按规则轮换交易对集合）
+    universe_size: int = Field(0, description="轮换选出的交易对数量（0为不轮换，只交易配置的交易对）")
+    universe_rank: str = Field("value_per_volatility", description="排名规则（value/volatility/value_per_volatility）")
+    universe_interval_seconds: int = Field(3600, description="重新扫描和轮换的间隔（秒）")
+    universe_buffer: int = Field(1, description="现有交易对排名在前 N + buffer 内时保留")
+    universe_min_value_24h: float = Field(0.0, description="参与排名的最低24小时成交额（USDT）")
+    universe_pinned: List[str] = Field([], description="始终交易的交易对（不计入轮换数量）")
+    universe_exclude: List[str] = Field([], description="不参与轮换的交易对")
+    universe_wind_down_seconds: int = Field(3600, description="离开集合后持仓未平时主动平仓的期限（秒，0为等待策略平仓）")
+    
     # 策略配置
     base_position_size: float = Field(0.05, description="基础仓位比例（5%，固定）")
+    position_size: str = Field("", description="开仓数量（单位:数值，单位为 equity/notional/contracts/risk，空为按基础仓位比例）")
+    confidence_sizing: bool = Field(False, description="是否按信号置信度调整仓位")
+    confidence_min_multiplier: float = Field(0.5, description="置信度为0时的仓位倍数")
+    confidence_max_multiplier: float = Field(1.5, description="置信度为1时的仓位倍数")
     leverage: int = Field(50, description="杠杆倍数")
+    symbol_leverage: Dict[str, int] = Field({}, description="各交易对杠杆倍数（未配置的交易对使用 leverage）")
     take_profit_pct: float = Field(0.004, description="止盈百分比（0.4%）")
     stop_loss_pct: float = Field(0.004, description="止损百分比（0.4%）")
     
+    # 标记价格（未实现盈亏、强平价估算和止盈止损触发使用标记价格，与交易所一致）
+    exit_trigger_price_type: str = Field("MARK_PRICE", description="客户端止盈止损触发价格类型（LAST_PRICE/MARK_PRICE）")
+    exit_watcher_enabled: bool = Field(False, description="每个tick检查止盈止损（独立于策略评估间隔）")
+    maintenance_margin_rate: float = Field(0.005, description="维持保证金率（强平价估算用）")
+    mark_price_poll_seconds: int = Field(5, description="无WebSocket标记价格时REST轮询间隔（秒）")
+    
+    # K线构建（由逐笔成交构建事件驱动K线，替代ticker价格快照作为策略的价格序列）
+    bar_type: str = Field("ticker", description="策略价格序列（ticker/time/volume/dollar）")
+    signal_price_source: str = Field("last", description="ticker模式下参考价格来源（last/mid/mark/index/weighted_mid）")
+    fair_price_levels: int = Field(5, description="盘口加权公允价格统计的档位数")
+    bar_size: float = Field(1.0, description="K线大小（time为秒，volume为币数量，dollar为USDT）")
+    
+    # 启动时用REST快照预填价格序列和订单簿（不必等待WebSocket积累足够数据）
+    bootstrap_klines: int = Field(100, description="启动时拉取的历史K线根数（ticker模式，0为不预填）")
+    bootstrap_kline_interval: str = Field("1m", description="预填历史K线的周期")
+    bootstrap_order_book: bool = Field(True, description="启动时用REST订单簿快照初始化本地订单簿")
+    
+    # 波动率估计器（组合风险模型和波动率状态识别共用）
+    volatility_estimator: str = Field("ewma", description="波动率估计器（simple/ewma/garch）")
+    garch_alpha: float = Field(0.08, description="GARCH(1,1) α")
+    garch_beta: float = Field(0.90, description="GARCH(1,1) β")
+    
+    # 组合波动率目标（估计各交易对波动率与相关性，缩放新开仓位）
+    vol_targeting_enabled: bool = Field(False, description="是否启用组合波动率目标")
+    target_volatility: float = Field(0.60, description="组合目标年化波动率（60%）")
+    vol_ewma_lambda: float = Field(0.94, description="EWMA衰减系数")
+    vol_sample_seconds: int = Field(60, description="波动率采样间隔（秒）")
+    vol_max_scale: float = Field(1.0, description="仓位最大放大倍数")
+    
+    # 波动率状态识别（按波动率分位数划分低/正常/高波动，切换参数或暂停开仓）
+    regime_detection_enabled: bool = Field(False, description="是否启用波动率状态识别")
+    regime_sample_seconds: int = Field(60, description="状态识别采样间隔（秒）")
+    regime_low_percentile: float = Field(0.2, description="低波动分位数阈值")
+    regime_high_percentile: float = Field(0.8, description="高波动分位数阈值")
+    regime_stand_down: List[str] = Field([], description="暂停开仓的波动率状态（low/normal/high）")
+    regime_profiles_file: str = Field("", description="按状态切换的参数配置JSON文件（空为使用各状态的默认参数）")
+    regime_trend_threshold: float = Field(0.6, description="趋势行情的效率比阈值（0-1）")
+    
+    # 开仓前盘口过滤（价差过宽或对手盘深度不足时跳过开仓）
+    liquidity_filter_enabled: bool = Field(False, description="是否启用价差与流动性过滤")
+    max_spread_bps: float = Field(10.0, description="允许的最大买卖价差（bps）")
+    liquidity_depth_levels: int = Field(1, description="计算可见流动性的对手盘档位数")
+    
+    # 持仓量与成交量过滤（持仓价值或24小时成交额过低的合约不开仓）
+    activity_filter_enabled: bool = Field(False, description="是否启用持仓量与成交量过滤")
+    min_open_interest_usd: float = Field(0.0, description="最低持仓价值（USDT，0为不检查）")
+    min_volume_24h_usd: float = Field(0.0, description="最低24小时成交额（USDT，0为不检查）")
+    activity_poll_seconds: float = Field(60.0, description="持仓量与成交量的更新间隔（秒）")
+    
+    # 基差监控（永续价格相对指数价格，见 basis.py）
+    basis_enabled: bool = Field(False, description="是否启用基差监控")
+    basis_sample_seconds: float = Field(5.0, description="基差采样间隔（秒）")
+    basis_window: int = Field(120, description="计算基差均值和z-score的样本数")
+    basis_max_bps: float = Field(0.0, description="向开仓方向的基差上限（bps，0为不过滤）")
+    basis_max_zscore: float = Field(0.0, description="向开仓方向的基差z-score上限（0为不过滤）")
+    basis_series_path: str = Field("data/basis/basis.csv", description="基差序列CSV文件（空为不写入）")
+    
+    # 资金费结算规避（结算前窗口内不持有需要支付资金费的仓位，见 funding_window.py）
+    funding_avoid_enabled: bool = Field(False, description="是否启用资金费结算规避")
+    funding_avoid_window_seconds: float = Field(300.0, description="结算前多少秒内规避需要支付资金费的持仓")
+    funding_avoid_action: str = Field("delay", description="规避动作: delay（延后开仓）/ close（延后开仓并平仓）")
+    funding_avoid_min_rate: float = Field(0.0, description="资金费率绝对值低于此值时不规避")
+    funding_poll_seconds: float = Field(60.0, description="资金费率的更新间隔（秒）")
+    
+    # 回撤仓位调节（按当前回撤平滑缩小新开仓位，回撤收窄后恢复）
+    drawdown_throttle_enabled: bool = Field(False, description="是否启用回撤仓位调节")
+    drawdown_scale_levels: str = Field("0.05:0.5,0.10:0.25", description="回撤档位（回撤:仓位比例，逗号分隔）")
+    
+    # 库存偏斜（按净方向敞口调整新开仓位，使净敞口围绕零均值回归）
+    inventory_skew_enabled: bool = Field(False, description="是否启用库存偏斜")
+    inventory_max_exposure: float = Field(5.0, description="净库存上限（净名义敞口 / 权益）")
+    inventory_skew_strength: float = Field(1.0, description="偏斜强度（1为达到上限时不再增加同向敞口）")
+    
+    # VWAP分批平仓（按历史日内成交量分布拆分大仓位的平仓订单，止损除外）
+    vwap_unwind_enabled: bool = Field(False, description="是否启用VWAP分批平仓")
+    vwap_min_notional: float = Field(10000.0, description="使用VWAP平仓的最小持仓名义价值（USDT）")
+    vwap_duration_seconds: int = Field(300, description="VWAP执行时长（秒）")
+    vwap_slices: int = Field(10, description="VWAP子订单个数")
+    
+    # OCO止盈止损（开仓后挂出只减仓止盈限价单，止损由客户端触发，一腿成交即撤销另一腿）
+    oco_exits_enabled: bool = Field(False, description="是否启用OCO止盈止损退出单")
+    
+    # 双边挂单（在中间价两侧同时挂买卖限价单刷量，不按信号开仓）
+    dual_quote_enabled: bool = Field(False, description="是否启用双边挂单模式")
+    dual_quote_spread_bps: float = Field(10.0, description="买卖挂单之间的距离（bps，相对中间价）")
+    dual_quote_notional: float = Field(100.0, description="每笔挂单的名义价值（USDT）")
+    dual_quote_requote_bps: float = Field(5.0, description="中间价偏离多少bps时重新挂单")
+    dual_quote_max_imbalance: float = Field(3.0, description="净持仓上限（挂单数量的倍数），超过时市价再平衡")
+    
+    # 脚本信号（信号逻辑由热加载的外部Python脚本定义）
+    signal_script: str = Field("", description="信号脚本路径（空为使用内置均线偏离信号）")
+    signal_ensemble: str = Field("", description="组合信号的信号源和权重（如 ma:1,zscore:1,imbalance:0.5，空为不使用）")
+    signal_ensemble_mode: str = Field("weighted", description="组合信号合并方式（vote/weighted）")
+    signal_min_agreement: float = Field(0.5, description="组合信号交易所需的最低一致度（0-1）")
+    ml_model_path: str = Field("", description="机器学习信号的ONNX模型路径（组合信号的 onnx 信号源使用）")
+    ml_signal_threshold: float = Field(0.2, description="机器学习信号评分阈值（评分绝对值超过时投票）")
+    feature_recorder_dir: str = Field("", description="特征记录目录（空为不记录）")
+    feature_recorder_horizons: str = Field("5,30,60", description="特征记录的前瞻时长（秒，逗号分隔）")
+    feature_recorder_flow_window: float = Field(60.0, description="特征记录的成交流统计窗口（秒）")
+    
+    # 多策略资金分配（策略列表文件中每个策略覆盖全局配置，按权重分配资金预算）
+    strategies_file: str = Field("", description="策略列表JSON文件路径（空为只运行单个策略）")
+    allocation_eval_seconds: int = Field(30, description="资金分配评估间隔（秒）")
+    allocation_throttle_drawdown: float = Field(0.05, description="策略回撤超过该比例（相对预算）时限流")
+    allocation_disable_drawdown: float = Field(0.10, description="策略回撤超过该比例（相对预算）时停用")
+    allocation_throttle_scale: float = Field(0.5, description="限流时的预算比例")
+    allocation_min_trades: int = Field(20, description="按胜率评估所需的最少交易次数")
+    allocation_min_win_rate: float = Field(0.0, description="低于该胜率时限流（0为不检查）")
+    ab_test: str = Field("", description="A/B测试对比的两个策略名称（逗号分隔，对照组在前）")
+    
+    # 开仓前保证金检查（所需保证金按 名义价值/杠杆 计算，额外预留一定比例）
+    margin_buffer_pct: float = Field(0.05, description="所需保证金的额外预留比例")
+    
+    # 出站订单闸门（重复订单抑制和下单频率限制）
+    duplicate_order_window_ms: int = Field(1000, description="相同订单的抑制窗口（毫秒，0为不检查）")
+    max_orders_per_second: int = Field(10, description="每秒最多下单数（0为不限制）")
+    
+    # 反手：单向持仓模式下反向信号以一笔订单平仓并反向开仓（关闭时只平仓，下一轮再开仓）
+    flip_on_reversal: bool = Field(False, description="反向信号时是否直接反手")
+    
+    # 平仓确认（平仓订单终结后持仓未归零时，按剩余数量重新提交只减仓平仓订单）
+    close_retry_attempts: int = Field(3, description="平仓未完成时的最多重试次数（0为不重试）")
+    
+    # 异常价格保护（见 price_guard.py）
+    max_price_deviation_pct: float = Field(0.0, description="价格、信号和订单价格允许偏离参考价格的比例（0为不检查）")
+    
+    # 下单前检查（见 pre_trade.py）
+    kill_switch_file: str = Field("", description="紧急停止开关文件（存在时只允许平仓，空为不检查文件）")
+    min_order_notional: float = Field(0.0, description="开仓订单最小名义价值（USDT，0为不检查）")
+    max_queued_entry_orders: int = Field(0, description="排队中的开仓订单上限（0为不限制）")
+    
+    # 手续费等级（见 fees.py）
+    fee_refresh_seconds: float = Field(3600.0, description="账户手续费率的刷新间隔（秒）")
+    min_edge_fee_multiple: float = Field(1.0, description="开仓预期收益（止盈距离）至少为往返手续费的倍数（0为不检查）")
+    
+    # 开仓期望值检查（见 ev_gate.py）
+    ev_gate_enabled: bool = Field(False, description="是否启用开仓期望值检查")
+    ev_min_bps: float = Field(0.0, description="开仓期望值下限（bps）")
+    ev_default_slippage_bps: float = Field(1.0, description="没有滑点记录时的单边滑点估计（bps，在半个价差之外）")
+    ev_log_path: str = Field("", description="逐笔期望值估算的CSV文件（空为不写入）")
+    
+    # 延迟保护（见 latency_guard.py）
+    latency_guard_enabled: bool = Field(False, description="是否启用延迟保护（延迟分位数超限时暂停开仓并放宽双边挂单价差）")
+    latency_guard_percentile: float = Field(99.0, description="延迟保护使用的延迟分位数（0-100）")
+    latency_guard_rest_ms: float = Field(500.0, description="REST延迟分位数上限（毫秒，0为不检查）")
+    latency_guard_ws_ms: float = Field(1000.0, description="WebSocket行情延迟分位数上限（毫秒，0为不检查）")
+    latency_guard_window: int = Field(200, description="每项延迟保留的最近样本数")
+    latency_guard_cooldown_seconds: float = Field(30.0, description="延迟回到上限以内后持续多少秒恢复交易")
+    latency_guard_quote_widen: float = Field(2.0, description="降级期间双边挂单价差的放宽倍数")
+    
+    # 多币种抵押资产（见 collateral.py）
+    reporting_currency: str = Field("USDT", description="报告币种（余额、盈亏和保证金的计价单位）")
+    collateral_rates: str = Field("USDC:1", description="抵押资产折算为报告币种的汇率，格式 资产:汇率，逗号分隔")
+    collateral_haircuts: str = Field("", description="抵押资产计入可用保证金时的折扣比例，格式 资产:比例，逗号分隔")
+    
+    # 对敲防护（见 wash_trade.py）
+    wash_guard_enabled: bool = Field(False, description="是否启用对敲防护")
+    wash_min_interval_ms: int = Field(0, description="开仓订单与自己反方向成交的最小时间间隔（毫秒，0为不检查）")
+    wash_min_price_bps: float = Field(0.0, description="开仓订单与窗口内自己反方向成交的最小价格距离（bps，0为不检查）")
+    wash_price_window_seconds: float = Field(60.0, description="价格距离检查的观察窗口（秒）")
+    wash_self_match_prevention: bool = Field(True, description="是否拒绝会与自己挂单成交的订单")
+    wash_audit_window_ms: int = Field(1000, description="反方向成交相距不超过多少毫秒时审计为疑似自成交")
+    wash_report_path: str = Field("data/audit/wash_trades.jsonl", description="疑似自成交审计文件路径（空为不写入）")
+    
+    # 订单审计日志（仅追加，带序号和哈希链）
+    audit_log_enabled: bool = Field(False, description="是否启用订单审计日志")
+    audit_log_path: str = Field("data/audit/orders.log", description="审计日志文件路径")
+    
+    # 信号Webhook（接收TradingView风格告警）
+    webhook_enabled: bool = Field(False, description="是否启用信号Webhook")
+    webhook_host: str = Field("0.0.0.0", description="Webhook监听地址")
+    webhook_port: int = Field(8080, description="Webhook监听端口")
+    webhook_path: str = Field("/webhook", description="Webhook路径")
+    webhook_secret: str = Field("", description="Webhook校验口令")
+    webhook_exclusive: bool = Field(False, description="仅由Webhook信号开仓（内部信号只用于持仓管理）")
+    
+    # 外部信号总线（Redis pub/sub 或 ZeroMQ）
+    signal_bus: str = Field("", description="信号总线类型（redis/zmq，空为不启用）")
+    redis_url: str = Field("redis://localhost:6379/0", description="Redis连接地址")
+    signal_bus_channel: str = Field("edgex.signals", description="Redis信号频道")
+    signal_bus_prefix: str = Field("edgex", description="Redis事件频道前缀")
+    zmq_signal_endpoint: str = Field("tcp://*:5556", description="ZeroMQ信号PULL绑定地址")
+    zmq_publish_endpoint: str = Field("tcp://*:5557", description="ZeroMQ事件PUB绑定地址")
+    
+    # 事件流（Kafka/NATS，推送交易记录、订单事件和性能快照）
+    event_stream: str = Field("", description="事件流类型（kafka/nats，空为不启用）")
+    event_stream_prefix: str = Field("edgex", description="事件主题前缀")
+    event_stream_source: str = Field("edgex-hf-bot", description="实例名称")
+    event_snapshot_seconds: int = Field(60, description="性能快照推送间隔（秒）")
+    kafka_bootstrap_servers: str = Field("localhost:9092", description="Kafka地址")
+    nats_url: str = Field("nats://localhost:4222", description="NATS地址")
+    
+    # 交易成本分析（记录订单成交和盘口/逐笔成交，离线生成TCA报告）
+    tca_enabled: bool = Field(False, description="是否记录TCA数据")
+    tca_dir: str = Field("data/tca", description="TCA数据记录目录")
+    
     # 风控配置
     min_order_size: float = Field(0.3, description="最小下单量（SOL）")
     max_position_pct: float = Field(0.5, description="最大仓位比例（50%）")
@@ -44,12 +329,86 @@ class Config(BaseModel):
     # 交易频率配置
     min_trade_interval: int = Field(5000, description="最小交易间隔（毫秒）")
     max_trade_interval: int = Field(60000, description="最大交易间隔（毫秒）")
+    loop_interval_ms: int = Field(1000, description="策略评估间隔（毫秒）")
+    trading_timezone: str = Field("UTC", description="交易日时区（IANA名称，当地零点日切）")
+    max_trades_per_day: int = Field(0, description="每个交易日最多开仓次数（0为不限制）")
+    max_open_positions: int = Field(0, description="同时持仓数上限（0为不限制）")
+    loss_cooldown_seconds: int = Field(0, description="交易对亏损后暂停开仓的时间（秒，0为不冷却）")
+    loss_cooldown_after: int = Field(1, description="交易对连续亏损多少笔后冷却")
+    loss_breaker_streak: int = Field(0, description="全局连续亏损多少笔后熔断（0为不启用）")
+    loss_breaker_seconds: int = Field(1800, description="熔断后暂停全部开仓的时间（秒）")
+    event_driven_evaluation: bool = Field(False, description="新价格到达时立即评估该交易对（不等待评估间隔）")
+    
+    # 盈亏核对
+    pnl_crosscheck_enabled: bool = Field(False, description="定期用交易所历史成交核对本地已实现盈亏和成交额")
+    pnl_crosscheck_seconds: int = Field(300, description="盈亏核对间隔（秒）")
+    pnl_crosscheck_tolerance: float = Field(0.01, description="已实现盈亏允许的差异（USDT）")
     
     # 监控配置
     performance_report_interval: int = Field(300, description="性能报告间隔（秒）")
+    report_sinks: Dict[str, List[str]] = Field({"performance": ["stdout"]}, description="各类报告的输出目标（stdout/file/metrics/notifier/discord）")
+    report_file_path: str = Field("data/reports/reports.log", description="file 输出目标的报告文件路径")
+    notifier_webhook_url: str = Field("", description="notifier 输出目标的Webhook地址")
+    discord_webhook_url: str = Field("", description="Discord Webhook地址（告警、成交通知和 discord 输出目标）")
+    discord_notify_fills: bool = Field(False, description="成交时发送Discord通知")
+    notify_dedup_seconds: int = Field(60, description="相同通知的去重窗口（秒，0为不去重）")
+    notify_rate_limits: Dict[str, int] = Field({"webhook": 30, "discord": 30}, description="各通知通道每分钟最多消息数")
+    smtp_host: str = Field("", description="严重告警邮件的SMTP服务器（空为不发送邮件）")
+    smtp_port: int = Field(587, description="SMTP端口")
+    smtp_username: str = Field("", description="SMTP用户名（空为不登录）")
+    smtp_password: str = Field("", description="SMTP密码")
+    smtp_security: str = Field("starttls", description="SMTP连接加密方式（starttls/ssl/none）")
+    smtp_from: str = Field("", description="告警邮件发件人")
+    smtp_to: List[str] = Field([], description="告警邮件收件人")
+    smtp_max_per_hour: int = Field(10, description="每小时最多发送的告警邮件数")
+    smtp_min_interval_seconds: int = Field(300, description="同一告警类型的最小邮件间隔（秒）")
+    liquidation_alert_distance: float = Field(0.0, description="标记价格距预估强平价小于该比例时发送严重告警（0为不告警）")
+    ws_reconnect_alert_failures: int = Field(3, description="WebSocket连续重连失败多少次后发送严重告警")
+    trade_records_capacity: int = Field(10000, description="内存中保留的最近交易记录数")
+    trade_records_path: str = Field("", description="交易记录溢写文件（JSON Lines，空为不溢写）")
+    equity_history_capacity: int = Field(1000, description="权益历史保留条数")
+    benchmark_sample_seconds: int = Field(60, description="买入持有基准对比的采样间隔（秒，0为不对比）")
+    performance_sample_seconds: int = Field(60, description="滚动绩效窗口的权益采样间隔（秒）")
+    
+    # 启动预热（启动检查全部通过前不下单）
+    warmup_enabled: bool = Field(True, description="是否在第一笔订单前执行启动检查")
+    warmup_retry_seconds: int = Field(30, description="启动检查未通过时重新检查的间隔（秒）")
+    
+    # 启动对账（匹配交易所遗留的挂单和持仓，完成前不交易）
+    reconcile_enabled: bool = Field(True, description="是否在启动时对账（模拟盘不对账）")
+    state_dir: str = Field("data/state", description="本地持仓/订单状态文件目录（空为不保存，遗留挂单和持仓均视为孤儿）")
+    orphan_order_policy: str = Field("cancel", description="孤儿挂单处理（cancel撤销 / adopt接管 / halt停止交易）")
+    orphan_position_policy: str = Field("adopt", description="孤儿持仓处理（adopt接管 / close平仓 / halt停止交易）")
     
     # 日志配置
     log_level: str = Field("INFO", description="日志级别")
+    log_dir: str = Field("logs", description="日志文件目录")
+    log_max_file_mb: float = Field(100.0, description="单个日志文件大小上限（MB，0为只按天轮转）")
+    log_retention_days: int = Field(30, description="日志文件保留天数")
+    log_compression: str = Field("", description="已轮转日志文件的压缩格式（空/gz/zip）")
+    log_json: bool = Field(False, description="同时写入JSON格式的日志文件")
+    
+    # 链路追踪配置
+    tracing_enabled: bool = Field(False, description="是否启用OpenTelemetry链路追踪")
+    otlp_endpoint: str = Field("http://localhost:4317", description="OTLP导出地址（gRPC）")
+    tracing_service_name: str = Field("edgex-hf-bot", description="追踪服务名称")
+    
+    # 错误上报配置
+    sentry_dsn: str = Field("", description="Sentry/GlitchTip DSN（空为不上报）")
+    sentry_environment: str = Field("", description="错误上报的环境名称（空为按模拟/实盘自动设置）")
+    sentry_max_breadcrumbs: int = Field(100, description="随错误上报的最近日志条数")
+    
+    # 延迟分析与指标接口
+    latency_profiling_enabled: bool = Field(False, description="是否启用交易链路延迟分析（HDR直方图）")
+    latency_report_seconds: float = Field(60.0, description="日志输出延迟分位数的间隔（秒）")
+    metrics_enabled: bool = Field(False, description="是否启用Prometheus指标接口")
+    metrics_host: str = Field("127.0.0.1", description="指标接口监听地址")
+    metrics_port: int = Field(9108, description="指标接口监听端口")
+    metrics_push_url: str = Field("", description="指标推送地址（Pushgateway或InfluxDB写入地址，空为不推送）")
+    metrics_push_format: str = Field("pushgateway", description="指标推送格式（pushgateway/influx）")
+    metrics_push_interval: float = Field(15.0, description="指标推送间隔（秒）")
+    metrics_push_job: str = Field("edgex_bot", description="Pushgateway的job名称")
+    metrics_push_token: str = Field("", description="InfluxDB令牌")
     
     class Config:
         env_prefix = "EDGEX_"
@@ -74,27 +433,285 @@ def load_config() -> Config:
         "public_key": os.getenv("EDGEX_PUBLIC_KEY"),
         "public_key_y_coordinate": os.getenv("EDGEX_PUBLIC_KEY_Y_COORDINATE"),
         "testnet": os.getenv("EDGEX_TESTNET", "false").lower() == "true",
+        "http_timeout": float(os.getenv("EDGEX_HTTP_TIMEOUT", "30")),
+        "http_connect_timeout": float(os.getenv("EDGEX_HTTP_CONNECT_TIMEOUT", "10")),
+        "http_pool_size": int(os.getenv("EDGEX_HTTP_POOL_SIZE", "100")),
+        "http_pool_per_host": int(os.getenv("EDGEX_HTTP_POOL_PER_HOST", "30")),
+        "http_keepalive_seconds": float(os.getenv("EDGEX_HTTP_KEEPALIVE_SECONDS", "30")),
+        "http_order_pool_size": int(os.getenv("EDGEX_HTTP_ORDER_POOL_SIZE", "10")),
+        "http_order_timeout": float(os.getenv("EDGEX_HTTP_ORDER_TIMEOUT", "5")),
+        "http_query_timeout": float(os.getenv("EDGEX_HTTP_QUERY_TIMEOUT", "10")),
+        "http_history_timeout": float(os.getenv("EDGEX_HTTP_HISTORY_TIMEOUT", "30")),
+        "signature_recv_window_ms": int(os.getenv("EDGEX_SIGNATURE_RECV_WINDOW_MS", "5000")),
+        "signature_timestamp_retries": int(os.getenv("EDGEX_SIGNATURE_TIMESTAMP_RETRIES", "1")),
+        "order_ack_timeout_seconds": float(os.getenv("EDGEX_ORDER_ACK_TIMEOUT", "5")),
+        "order_unconfirmed_expire_seconds": float(os.getenv("EDGEX_ORDER_UNCONFIRMED_EXPIRE", "60")),
+        "event_loop": os.getenv("EDGEX_EVENT_LOOP", "asyncio").lower(),
+        "executor_workers": int(os.getenv("EDGEX_EXECUTOR_WORKERS", "0")),
+        "execution_cpus": os.getenv("EDGEX_EXECUTION_CPUS", ""),
+        "market_data_cpus": os.getenv("EDGEX_MARKET_DATA_CPUS", ""),
+        "rest_endpoints": [s.strip() for s in os.getenv("EDGEX_REST_ENDPOINTS", "").split(",") if s.strip()],
+        "ws_endpoints": [s.strip() for s in os.getenv("EDGEX_WS_ENDPOINTS", "").split(",") if s.strip()],
+        "ws_ack_timeout": float(os.getenv("EDGEX_WS_ACK_TIMEOUT", "5")),
+        "ws_compression": os.getenv("EDGEX_WS_COMPRESSION", "false").lower() == "true",
+        "contract_spec_cache_path": os.getenv("EDGEX_CONTRACT_SPEC_PATH", "data/contracts.json"),
+        "contract_spec_refresh_seconds": int(os.getenv("EDGEX_CONTRACT_SPEC_REFRESH_SECONDS", "3600")),
+        "halt_error_codes": os.getenv("EDGEX_HALT_ERROR_CODES",
+                                      "MAINTENANCE,TRADE_DISABLE,TRADING_DISABLE,NOT_TRADABLE,status code: 503"),
+        "halt_retry_seconds": float(os.getenv("EDGEX_HALT_RETRY_SECONDS", "60")),
+        "ws_sequence_check": os.getenv("EDGEX_WS_SEQUENCE_CHECK", "true").lower() == "true",
+        "ws_book_validation": os.getenv("EDGEX_WS_BOOK_VALIDATION", "true").lower() == "true",
+        "ws_book_checksum": os.getenv("EDGEX_WS_BOOK_CHECKSUM", "false").lower() == "true",
+        "ws_public_connections": int(os.getenv("EDGEX_WS_PUBLIC_CONNECTIONS", "1")),
+        "ws_max_channels_per_connection": int(os.getenv("EDGEX_WS_MAX_CHANNELS_PER_CONNECTION", "0")),
+        "endpoint_probe_seconds": float(os.getenv("EDGEX_ENDPOINT_PROBE_SECONDS", "30")),
+        "endpoint_failover_errors": int(os.getenv("EDGEX_ENDPOINT_FAILOVER_ERRORS", "3")),
+        "endpoint_cooldown_seconds": float(os.getenv("EDGEX_ENDPOINT_COOLDOWN_SECONDS", "60")),
+        "proxy_url": os.getenv("EDGEX_PROXY", ""),
+        "paper_trading": os.getenv("EDGEX_PAPER_TRADING", "false").lower() == "true",
+        "paper_initial_balance": float(os.getenv("EDGEX_PAPER_INITIAL_BALANCE", "10000")),
+        "shadow_mode": os.getenv("EDGEX_SHADOW_MODE", "false").lower() == "true",
+        "position_mode": os.getenv("EDGEX_POSITION_MODE", "one_way").lower(),
+        "execution_policy": os.getenv("EDGEX_EXECUTION_POLICY", "market").lower(),
+        "limit_wait_ms": int(os.getenv("EDGEX_LIMIT_WAIT_MS", "3000")),
+        "limit_price_offset_ticks": int(os.getenv("EDGEX_LIMIT_PRICE_OFFSET_TICKS", "0")),
+        "requote_threshold_ticks": int(os.getenv("EDGEX_REQUOTE_THRESHOLD_TICKS", "0")),
+        "max_requotes_per_minute": int(os.getenv("EDGEX_MAX_REQUOTES_PER_MINUTE", "10")),
+        "order_ttl_ms": int(os.getenv("EDGEX_ORDER_TTL_MS", "30000")),
         "symbols": os.getenv("EDGEX_SYMBOLS", "SOL-USDT").split(","),
+        "universe_size": int(os.getenv("EDGEX_UNIVERSE_SIZE", "0")),
+        "universe_rank": os.getenv("EDGEX_UNIVERSE_RANK", "value_per_volatility").lower(),
+        "universe_interval_seconds": int(os.getenv("EDGEX_UNIVERSE_INTERVAL_SECONDS", "3600")),
+        "universe_buffer": int(os.getenv("EDGEX_UNIVERSE_BUFFER", "1")),
+        "universe_min_value_24h": float(os.getenv("EDGEX_UNIVERSE_MIN_VALUE_24H", "0")),
+        "universe_pinned": [s.strip() for s in os.getenv("EDGEX_UNIVERSE_PINNED", "").split(",") if s.strip()],
+        "universe_exclude": [s.strip() for s in os.getenv("EDGEX_UNIVERSE_EXCLUDE", "").split(",") if s.strip()],
+        "universe_wind_down_seconds": int(os.getenv("EDGEX_UNIVERSE_WIND_DOWN_SECONDS", "3600")),
         "base_position_size": float(os.getenv("EDGEX_BASE_POSITION_SIZE", "0.05")),
+        "position_size": os.getenv("EDGEX_POSITION_SIZE", ""),
+        "confidence_sizing": os.getenv("EDGEX_CONFIDENCE_SIZING", "false").lower() == "true",
+        "confidence_min_multiplier": float(os.getenv("EDGEX_CONFIDENCE_MIN_MULTIPLIER", "0.5")),
+        "confidence_max_multiplier": float(os.getenv("EDGEX_CONFIDENCE_MAX_MULTIPLIER", "1.5")),
         "leverage": int(os.getenv("EDGEX_LEVERAGE", "50")),
+        "symbol_leverage": {
+            k.strip(): int(v) for k, v in
+            (item.split(":") for item in os.getenv("EDGEX_SYMBOL_LEVERAGE", "").split(",") if item.strip())
+        },
         "take_profit_pct": float(os.getenv("EDGEX_TAKE_PROFIT_PCT", "0.004")),
         "stop_loss_pct": float(os.getenv("EDGEX_STOP_LOSS_PCT", "0.004")),
+        "exit_trigger_price_type": os.getenv("EDGEX_EXIT_TRIGGER_PRICE_TYPE", "MARK_PRICE").upper(),
+        "exit_watcher_enabled": os.getenv("EDGEX_EXIT_WATCHER", "false").lower() == "true",
+        "maintenance_margin_rate": float(os.getenv("EDGEX_MAINTENANCE_MARGIN_RATE", "0.005")),
+        "mark_price_poll_seconds": int(os.getenv("EDGEX_MARK_PRICE_POLL_SECONDS", "5")),
+        "bar_type": os.getenv("EDGEX_BAR_TYPE", "ticker").lower(),
+        "signal_price_source": os.getenv("EDGEX_SIGNAL_PRICE_SOURCE", "last").lower(),
+        "fair_price_levels": int(os.getenv("EDGEX_FAIR_PRICE_LEVELS", "5")),
+        "bootstrap_klines": int(os.getenv("EDGEX_BOOTSTRAP_KLINES", "100")),
+        "bootstrap_kline_interval": os.getenv("EDGEX_BOOTSTRAP_KLINE_INTERVAL", "1m"),
+        "bootstrap_order_book": os.getenv("EDGEX_BOOTSTRAP_ORDER_BOOK", "true").lower() == "true",
+        "pnl_crosscheck_enabled": os.getenv("EDGEX_PNL_CROSSCHECK", "false").lower() == "true",
+        "pnl_crosscheck_seconds": int(os.getenv("EDGEX_PNL_CROSSCHECK_SECONDS", "300")),
+        "pnl_crosscheck_tolerance": float(os.getenv("EDGEX_PNL_CROSSCHECK_TOLERANCE", "0.01")),
+        "bar_size": float(os.getenv("EDGEX_BAR_SIZE", "1")),
+        "volatility_estimator": os.getenv("EDGEX_VOLATILITY_ESTIMATOR", "ewma").lower(),
+        "garch_alpha": float(os.getenv("EDGEX_GARCH_ALPHA", "0.08")),
+        "garch_beta": float(os.getenv("EDGEX_GARCH_BETA", "0.90")),
+        "vol_targeting_enabled": os.getenv("EDGEX_VOL_TARGETING", "false").lower() == "true",
+        "target_volatility": float(os.getenv("EDGEX_TARGET_VOLATILITY", "0.60")),
+        "vol_ewma_lambda": float(os.getenv("EDGEX_VOL_EWMA_LAMBDA", "0.94")),
+        "vol_sample_seconds": int(os.getenv("EDGEX_VOL_SAMPLE_SECONDS", "60")),
+        "vol_max_scale": float(os.getenv("EDGEX_VOL_MAX_SCALE", "1.0")),
+        "regime_detection_enabled": os.getenv("EDGEX_REGIME_DETECTION", "false").lower() == "true",
+        "regime_sample_seconds": int(os.getenv("EDGEX_REGIME_SAMPLE_SECONDS", "60")),
+        "regime_low_percentile": float(os.getenv("EDGEX_REGIME_LOW_PERCENTILE", "0.2")),
+        "regime_high_percentile": float(os.getenv("EDGEX_REGIME_HIGH_PERCENTILE", "0.8")),
+        "regime_stand_down": [s.strip().lower() for s in os.getenv("EDGEX_REGIME_STAND_DOWN", "").split(",") if s.strip()],
+        "regime_profiles_file": os.getenv("EDGEX_REGIME_PROFILES_FILE", ""),
+        "regime_trend_threshold": float(os.getenv("EDGEX_REGIME_TREND_THRESHOLD", "0.6")),
+        "liquidity_filter_enabled": os.getenv("EDGEX_LIQUIDITY_FILTER", "false").lower() == "true",
+        "max_spread_bps": float(os.getenv("EDGEX_MAX_SPREAD_BPS", "10")),
+        "liquidity_depth_levels": int(os.getenv("EDGEX_LIQUIDITY_DEPTH_LEVELS", "1")),
+        "activity_filter_enabled": os.getenv("EDGEX_ACTIVITY_FILTER", "false").lower() == "true",
+        "min_open_interest_usd": float(os.getenv("EDGEX_MIN_OPEN_INTEREST", "0")),
+        "min_volume_24h_usd": float(os.getenv("EDGEX_MIN_VOLUME_24H", "0")),
+        "activity_poll_seconds": float(os.getenv("EDGEX_ACTIVITY_POLL_SECONDS", "60")),
+        "basis_enabled": os.getenv("EDGEX_BASIS", "false").lower() == "true",
+        "basis_sample_seconds": float(os.getenv("EDGEX_BASIS_SAMPLE_SECONDS", "5")),
+        "basis_window": int(os.getenv("EDGEX_BASIS_WINDOW", "120")),
+        "basis_max_bps": float(os.getenv("EDGEX_BASIS_MAX_BPS", "0")),
+        "basis_max_zscore": float(os.getenv("EDGEX_BASIS_MAX_ZSCORE", "0")),
+        "basis_series_path": os.getenv("EDGEX_BASIS_SERIES_PATH", "data/basis/basis.csv"),
+        "funding_avoid_enabled": os.getenv("EDGEX_FUNDING_AVOID", "false").lower() == "true",
+        "funding_avoid_window_seconds": float(os.getenv("EDGEX_FUNDING_AVOID_WINDOW_SECONDS", "300")),
+        "funding_avoid_action": os.getenv("EDGEX_FUNDING_AVOID_ACTION", "delay").lower(),
+        "funding_avoid_min_rate": float(os.getenv("EDGEX_FUNDING_AVOID_MIN_RATE", "0")),
+        "funding_poll_seconds": float(os.getenv("EDGEX_FUNDING_POLL_SECONDS", "60")),
+        "drawdown_throttle_enabled": os.getenv("EDGEX_DRAWDOWN_THROTTLE", "false").lower() == "true",
+        "drawdown_scale_levels": os.getenv("EDGEX_DRAWDOWN_SCALE_LEVELS", "0.05:0.5,0.10:0.25"),
+        "inventory_skew_enabled": os.getenv("EDGEX_INVENTORY_SKEW", "false").lower() == "true",
+        "inventory_max_exposure": float(os.getenv("EDGEX_INVENTORY_MAX_EXPOSURE", "5")),
+        "inventory_skew_strength": float(os.getenv("EDGEX_INVENTORY_SKEW_STRENGTH", "1")),
+        "vwap_unwind_enabled": os.getenv("EDGEX_VWAP_UNWIND", "false").lower() == "true",
+        "vwap_min_notional": float(os.getenv("EDGEX_VWAP_MIN_NOTIONAL", "10000")),
+        "vwap_duration_seconds": int(os.getenv("EDGEX_VWAP_DURATION_SECONDS", "300")),
+        "vwap_slices": int(os.getenv("EDGEX_VWAP_SLICES", "10")),
+        "oco_exits_enabled": os.getenv("EDGEX_OCO_EXITS", "false").lower() == "true",
+        "dual_quote_enabled": os.getenv("EDGEX_DUAL_QUOTE", "false").lower() == "true",
+        "dual_quote_spread_bps": float(os.getenv("EDGEX_DUAL_QUOTE_SPREAD_BPS", "10")),
+        "dual_quote_notional": float(os.getenv("EDGEX_DUAL_QUOTE_NOTIONAL", "100")),
+        "dual_quote_requote_bps": float(os.getenv("EDGEX_DUAL_QUOTE_REQUOTE_BPS", "5")),
+        "dual_quote_max_imbalance": float(os.getenv("EDGEX_DUAL_QUOTE_MAX_IMBALANCE", "3")),
+        "signal_script": os.getenv("EDGEX_SIGNAL_SCRIPT", ""),
+        "signal_ensemble": os.getenv("EDGEX_SIGNAL_ENSEMBLE", ""),
+        "signal_ensemble_mode": os.getenv("EDGEX_SIGNAL_ENSEMBLE_MODE", "weighted").lower(),
+        "signal_min_agreement": float(os.getenv("EDGEX_SIGNAL_MIN_AGREEMENT", "0.5")),
+        "ml_model_path": os.getenv("EDGEX_ML_MODEL_PATH", ""),
+        "ml_signal_threshold": float(os.getenv("EDGEX_ML_SIGNAL_THRESHOLD", "0.2")),
+        "feature_recorder_dir": os.getenv("EDGEX_FEATURE_RECORDER_DIR", ""),
+        "feature_recorder_horizons": os.getenv("EDGEX_FEATURE_RECORDER_HORIZONS", "5,30,60"),
+        "feature_recorder_flow_window": float(os.getenv("EDGEX_FEATURE_RECORDER_FLOW_WINDOW", "60")),
+        "strategies_file": os.getenv("EDGEX_STRATEGIES_FILE", ""),
+        "allocation_eval_seconds": int(os.getenv("EDGEX_ALLOCATION_EVAL_SECONDS", "30")),
+        "allocation_throttle_drawdown": float(os.getenv("EDGEX_ALLOCATION_THROTTLE_DRAWDOWN", "0.05")),
+        "allocation_disable_drawdown": float(os.getenv("EDGEX_ALLOCATION_DISABLE_DRAWDOWN", "0.10")),
+        "allocation_throttle_scale": float(os.getenv("EDGEX_ALLOCATION_THROTTLE_SCALE", "0.5")),
+        "allocation_min_trades": int(os.getenv("EDGEX_ALLOCATION_MIN_TRADES", "20")),
+        "allocation_min_win_rate": float(os.getenv("EDGEX_ALLOCATION_MIN_WIN_RATE", "0")),
+        "ab_test": os.getenv("EDGEX_AB_TEST", ""),
+        "margin_buffer_pct": float(os.getenv("EDGEX_MARGIN_BUFFER_PCT", "0.05")),
+        "duplicate_order_window_ms": int(os.getenv("EDGEX_DUPLICATE_ORDER_WINDOW_MS", "1000")),
+        "max_orders_per_second": int(os.getenv("EDGEX_MAX_ORDERS_PER_SECOND", "10")),
+        "flip_on_reversal": os.getenv("EDGEX_FLIP_ON_REVERSAL", "false").lower() == "true",
+        "close_retry_attempts": int(os.getenv("EDGEX_CLOSE_RETRY_ATTEMPTS", "3")),
+        "max_price_deviation_pct": float(os.getenv("EDGEX_MAX_PRICE_DEVIATION_PCT", "0")),
+        "kill_switch_file": os.getenv("EDGEX_KILL_SWITCH_FILE", ""),
+        "min_order_notional": float(os.getenv("EDGEX_MIN_ORDER_NOTIONAL", "0")),
+        "max_queued_entry_orders": int(os.getenv("EDGEX_MAX_QUEUED_ENTRY_ORDERS", "0")),
+        "fee_refresh_seconds": float(os.getenv("EDGEX_FEE_REFRESH_SECONDS", "3600")),
+        "min_edge_fee_multiple": float(os.getenv("EDGEX_MIN_EDGE_FEE_MULTIPLE", "1")),
+        "ev_gate_enabled": os.getenv("EDGEX_EV_GATE", "false").lower() == "true",
+        "ev_min_bps": float(os.getenv("EDGEX_EV_MIN_BPS", "0")),
+        "ev_default_slippage_bps": float(os.getenv("EDGEX_EV_DEFAULT_SLIPPAGE_BPS", "1")),
+        "ev_log_path": os.getenv("EDGEX_EV_LOG_PATH", ""),
+        "latency_guard_enabled": os.getenv("EDGEX_LATENCY_GUARD", "false").lower() == "true",
+        "latency_guard_percentile": float(os.getenv("EDGEX_LATENCY_GUARD_PERCENTILE", "99")),
+        "latency_guard_rest_ms": float(os.getenv("EDGEX_LATENCY_GUARD_REST_MS", "500")),
+        "latency_guard_ws_ms": float(os.getenv("EDGEX_LATENCY_GUARD_WS_MS", "1000")),
+        "latency_guard_window": int(os.getenv("EDGEX_LATENCY_GUARD_WINDOW", "200")),
+        "latency_guard_cooldown_seconds": float(os.getenv("EDGEX_LATENCY_GUARD_COOLDOWN_SECONDS", "30")),
+        "latency_guard_quote_widen": float(os.getenv("EDGEX_LATENCY_GUARD_QUOTE_WIDEN", "2")),
+        "reporting_currency": os.getenv("EDGEX_REPORTING_CURRENCY", "USDT").upper(),
+        "collateral_rates": os.getenv("EDGEX_COLLATERAL_RATES", "USDC:1"),
+        "collateral_haircuts": os.getenv("EDGEX_COLLATERAL_HAIRCUTS", ""),
+        "wash_guard_enabled": os.getenv("EDGEX_WASH_GUARD", "false").lower() == "true",
+        "wash_min_interval_ms": int(os.getenv("EDGEX_WASH_MIN_INTERVAL_MS", "0")),
+        "wash_min_price_bps": float(os.getenv("EDGEX_WASH_MIN_PRICE_BPS", "0")),
+        "wash_price_window_seconds": float(os.getenv("EDGEX_WASH_PRICE_WINDOW_SECONDS", "60")),
+        "wash_self_match_prevention": os.getenv("EDGEX_WASH_SELF_MATCH_PREVENTION", "true").lower() == "true",
+        "wash_audit_window_ms": int(os.getenv("EDGEX_WASH_AUDIT_WINDOW_MS", "1000")),
+        "wash_report_path": os.getenv("EDGEX_WASH_REPORT_PATH", "data/audit/wash_trades.jsonl"),
+        "audit_log_enabled": os.getenv("EDGEX_AUDIT_LOG", "false").lower() == "true",
+        "audit_log_path": os.getenv("EDGEX_AUDIT_LOG_PATH", "data/audit/orders.log"),
+        "webhook_enabled": os.getenv("EDGEX_WEBHOOK_ENABLED", "false").lower() == "true",
+        "webhook_host": os.getenv("EDGEX_WEBHOOK_HOST", "0.0.0.0"),
+        "webhook_port": int(os.getenv("EDGEX_WEBHOOK_PORT", "8080")),
+        "webhook_path": os.getenv("EDGEX_WEBHOOK_PATH", "/webhook"),
+        "webhook_secret": os.getenv("EDGEX_WEBHOOK_SECRET", ""),
+        "webhook_exclusive": os.getenv("EDGEX_WEBHOOK_EXCLUSIVE", "false").lower() == "true",
+        "signal_bus": os.getenv("EDGEX_SIGNAL_BUS", "").lower(),
+        "redis_url": os.getenv("EDGEX_REDIS_URL", "redis://localhost:6379/0"),
+        "signal_bus_channel": os.getenv("EDGEX_SIGNAL_BUS_CHANNEL", "edgex.signals"),
+        "signal_bus_prefix": os.getenv("EDGEX_SIGNAL_BUS_PREFIX", "edgex"),
+        "zmq_signal_endpoint": os.getenv("EDGEX_ZMQ_SIGNAL_ENDPOINT", "tcp://*:5556"),
+        "zmq_publish_endpoint": os.getenv("EDGEX_ZMQ_PUBLISH_ENDPOINT", "tcp://*:5557"),
+        "event_stream": os.getenv("EDGEX_EVENT_STREAM", "").lower(),
+        "event_stream_prefix": os.getenv("EDGEX_EVENT_STREAM_PREFIX", "edgex"),
+        "event_stream_source": os.getenv("EDGEX_EVENT_STREAM_SOURCE", "edgex-hf-bot"),
+        "event_snapshot_seconds": int(os.getenv("EDGEX_EVENT_SNAPSHOT_SECONDS", "60")),
+        "kafka_bootstrap_servers": os.getenv("EDGEX_KAFKA_BOOTSTRAP_SERVERS", "localhost:9092"),
+        "nats_url": os.getenv("EDGEX_NATS_URL", "nats://localhost:4222"),
+        "tca_enabled": os.getenv("EDGEX_TCA", "false").lower() == "true",
+        "tca_dir": os.getenv("EDGEX_TCA_DIR", "data/tca"),
         "min_order_size": float(os.getenv("EDGEX_MIN_ORDER_SIZE", "0.3")),
         "max_position_pct": float(os.getenv("EDGEX_MAX_POSITION_PCT", "0.5")),
         "min_trade_interval": int(os.getenv("EDGEX_MIN_TRADE_INTERVAL", "5000")),
         "max_trade_interval": int(os.getenv("EDGEX_MAX_TRADE_INTERVAL", "60000")),
+        "loop_interval_ms": int(os.getenv("EDGEX_LOOP_INTERVAL_MS", "1000")),
+        "trading_timezone": os.getenv("EDGEX_TIMEZONE", "UTC"),
+        "max_trades_per_day": int(os.getenv("EDGEX_MAX_TRADES_PER_DAY", "0")),
+        "max_open_positions": int(os.getenv("EDGEX_MAX_OPEN_POSITIONS", "0")),
+        "loss_cooldown_seconds": int(os.getenv("EDGEX_LOSS_COOLDOWN_SECONDS", "0")),
+        "loss_cooldown_after": int(os.getenv("EDGEX_LOSS_COOLDOWN_AFTER", "1")),
+        "loss_breaker_streak": int(os.getenv("EDGEX_LOSS_BREAKER_STREAK", "0")),
+        "loss_breaker_seconds": int(os.getenv("EDGEX_LOSS_BREAKER_SECONDS", "1800")),
+        "event_driven_evaluation": os.getenv("EDGEX_EVENT_DRIVEN_EVALUATION", "false").lower() == "true",
         "performance_report_interval": int(os.getenv("EDGEX_PERFORMANCE_REPORT_INTERVAL", "300")),
+        "report_sinks": parse_report_sinks(os.getenv("EDGEX_REPORT_SINKS", "performance:stdout")),
+        "report_file_path": os.getenv("EDGEX_REPORT_FILE", "data/reports/reports.log"),
+        "notifier_webhook_url": os.getenv("EDGEX_NOTIFIER_WEBHOOK_URL", ""),
+        "discord_webhook_url": os.getenv("EDGEX_DISCORD_WEBHOOK_URL", ""),
+        "discord_notify_fills": os.getenv("EDGEX_DISCORD_NOTIFY_FILLS", "false").lower() == "true",
+        "notify_dedup_seconds": int(os.getenv("EDGEX_NOTIFY_DEDUP_SECONDS", "60")),
+        "notify_rate_limits": parse_rate_limits(os.getenv("EDGEX_NOTIFY_RATE_LIMITS", "webhook:30,discord:30")),
+        "smtp_host": os.getenv("EDGEX_SMTP_HOST", ""),
+        "smtp_port": int(os.getenv("EDGEX_SMTP_PORT", "587")),
+        "smtp_username": os.getenv("EDGEX_SMTP_USERNAME", ""),
+        "smtp_password": os.getenv("EDGEX_SMTP_PASSWORD", ""),
+        "smtp_security": os.getenv("EDGEX_SMTP_SECURITY", "starttls").lower(),
+        "smtp_from": os.getenv("EDGEX_SMTP_FROM", ""),
+        "smtp_to": [s.strip() for s in os.getenv("EDGEX_SMTP_TO", "").split(",") if s.strip()],
+        "smtp_max_per_hour": int(os.getenv("EDGEX_SMTP_MAX_PER_HOUR", "10")),
+        "smtp_min_interval_seconds": int(os.getenv("EDGEX_SMTP_MIN_INTERVAL_SECONDS", "300")),
+        "liquidation_alert_distance": float(os.getenv("EDGEX_LIQUIDATION_ALERT_DISTANCE", "0")),
+        "ws_reconnect_alert_failures": int(os.getenv("EDGEX_WS_RECONNECT_ALERT_FAILURES", "3")),
+        "trade_records_capacity": int(os.getenv("EDGEX_TRADE_RECORDS_CAPACITY", "10000")),
+        "trade_records_path": os.getenv("EDGEX_TRADE_RECORDS_PATH", ""),
+        "equity_history_capacity": int(os.getenv("EDGEX_EQUITY_HISTORY_CAPACITY", "1000")),
+        "benchmark_sample_seconds": int(os.getenv("EDGEX_BENCHMARK_SAMPLE_SECONDS", "60")),
+        "performance_sample_seconds": int(os.getenv("EDGEX_PERFORMANCE_SAMPLE_SECONDS", "60")),
+        "warmup_enabled": os.getenv("EDGEX_WARMUP_ENABLED", "true").lower() == "true",
+        "warmup_retry_seconds": int(os.getenv("EDGEX_WARMUP_RETRY_SECONDS", "30")),
+        "reconcile_enabled": os.getenv("EDGEX_RECONCILE_ENABLED", "true").lower() == "true",
+        "state_dir": os.getenv("EDGEX_STATE_DIR", "data/state"),
+        "orphan_order_policy": os.getenv("EDGEX_ORPHAN_ORDER_POLICY", "cancel").lower(),
+        "orphan_position_policy": os.getenv("EDGEX_ORPHAN_POSITION_POLICY", "adopt").lower(),
         "log_level": os.getenv("EDGEX_LOG_LEVEL", "INFO"),
+        "log_dir": os.getenv("EDGEX_LOG_DIR", "logs"),
+        "log_max_file_mb": float(os.getenv("EDGEX_LOG_MAX_FILE_MB", "100")),
+        "log_retention_days": int(os.getenv("EDGEX_LOG_RETENTION_DAYS", "30")),
+        "log_compression": os.getenv("EDGEX_LOG_COMPRESSION", "").lower(),
+        "log_json": os.getenv("EDGEX_LOG_JSON", "false").lower() == "true",
+        "tracing_enabled": os.getenv("EDGEX_TRACING_ENABLED", "false").lower() == "true",
+        "otlp_endpoint": os.getenv("EDGEX_OTLP_ENDPOINT", "http://localhost:4317"),
+        "tracing_service_name": os.getenv("EDGEX_TRACING_SERVICE_NAME", "edgex-hf-bot"),
+        "sentry_dsn": os.getenv("EDGEX_SENTRY_DSN", ""),
+        "sentry_environment": os.getenv("EDGEX_SENTRY_ENVIRONMENT", ""),
+        "sentry_max_breadcrumbs": int(os.getenv("EDGEX_SENTRY_MAX_BREADCRUMBS", "100")),
+        "latency_profiling_enabled": os.getenv("EDGEX_LATENCY_PROFILING", "false").lower() == "true",
+        "latency_report_seconds": float(os.getenv("EDGEX_LATENCY_REPORT_SECONDS", "60")),
+        "metrics_enabled": os.getenv("EDGEX_METRICS_ENABLED", "false").lower() == "true",
+        "metrics_host": os.getenv("EDGEX_METRICS_HOST", "127.0.0.1"),
+        "metrics_port": int(os.getenv("EDGEX_METRICS_PORT", "9108")),
+        "metrics_push_url": os.getenv("EDGEX_METRICS_PUSH_URL", ""),
+        "metrics_push_format": os.getenv("EDGEX_METRICS_PUSH_FORMAT", "pushgateway").lower(),
+        "metrics_push_interval": float(os.getenv("EDGEX_METRICS_PUSH_INTERVAL", "15")),
+        "metrics_push_job": os.getenv("EDGEX_METRICS_PUSH_JOB", "edgex_bot"),
+        "metrics_push_token": os.getenv("EDGEX_METRICS_PUSH_TOKEN", ""),
     }
     
     try:
         config = Config(**config_dict)
         logger.info("配置加载成功")
         logger.info(f"网络模式: {'测试网' if config.testnet else '主网 ⚠️'}")
+        if config.paper_trading:
+            logger.info(f"模拟盘模式: 已启用（初始资金 {config.paper_initial_balance:.2f} USDT）")
         logger.info(f"账户ID: {config.account_id}")
         logger.info(f"交易对: {config.symbols}")
         logger.info(f"杠杆倍数: {config.leverage}x")
-        logger.info(f"基础仓位: {config.base_position_size * 100}%")
+        if config.position_size:
+            logger.info(f"开仓数量: {config.position_size}")
+        else:
+            logger.info(f"基础仓位: {config.base_position_size * 100}%")
         return config
     except Exception as e:
         logger.error(f"配置加载失败: {e}")
@@ -113,20 +730,45 @@ def validate_config(config: Config) -> bool:
     """
     errors = []
     
-    # 验证Stark签名配置（EdgeX使用Stark签名，不需要API Key）
-    if not config.stark_private_key:
-        errors.append("Stark私钥未配置（EDGEX_STARK_PRIVATE_KEY）")
-    
-    if not config.account_id:
-        errors.append("账户ID未配置（EDGEX_ACCOUNT_ID）")
+    # 验证Stark签名配置（EdgeX使用Stark签名，不需要API Key；模拟盘只使用公共行情接口）
+    if not config.paper_trading:
+        if not config.stark_private_key:
+            errors.append("Stark私钥未配置（EDGEX_STARK_PRIVATE_KEY）")
+        
+        if not config.account_id:
+            errors.append("账户ID未配置（EDGEX_ACCOUNT_ID）")
+    elif config.paper_initial_balance <= 0:
+        errors.append("模拟盘初始资金必须大于0")
     
     # 验证交易参数
     if config.base_position_size <= 0 or config.base_position_size > 1:
         errors.append("基础仓位比例必须在0-1之间")
     
+    try:
+        PositionSizing.parse(config.position_size, config.base_position_size)
+    except ValueError as e:
+        errors.append(str(e))
+    
+    if config.confidence_min_multiplier <= 0 or config.confidence_max_multiplier < config.confidence_min_multiplier:
+        errors.append("置信度仓位倍数必须大于0，且最大倍数不小于最小倍数")
+    
+    if config.universe_size < 0:
+        errors.append("轮换交易对数量不能为负数")
+    elif config.universe_size > 0:
+        if config.universe_rank not in UNIVERSE_RANKS:
+            errors.append(f"交易对排名规则必须为 {'/'.join(UNIVERSE_RANKS)}")
+        if config.universe_interval_seconds <= 0:
+            errors.append("交易对轮换间隔必须大于0")
+        if config.universe_buffer < 0 or config.universe_wind_down_seconds < 0:
+            errors.append("交易对轮换缓冲和平仓期限不能为负数")
+    
     if config.leverage < 1 or config.leverage > 100:
         errors.append("杠杆倍数必须在1-100之间")
     
+    for symbol, leverage in config.symbol_leverage.items():
+        if leverage < 1 or leverage > 100:
+            errors.append(f"{symbol} 杠杆倍数必须在1-100之间")
+    
     if config.take_profit_pct <= 0:
         errors.append("止盈百分比必须大于0")
     
@@ -137,6 +779,405 @@ def validate_config(config: Config) -> bool:
     if config.min_order_size <= 0:
         errors.append("最小下单量必须大于0")
     
+    if config.volatility_estimator not in ("simple", "ewma", "garch"):
+        errors.append("波动率估计器必须为 simple、ewma 或 garch")
+    elif config.volatility_estimator == "garch":
+        if config.garch_alpha < 0 or config.garch_beta < 0 or config.garch_alpha + config.garch_beta >= 1:
+            errors.append("GARCH参数需满足 α≥0, β≥0, α+β<1")
+    
+    if config.vol_targeting_enabled:
+        if config.target_volatility <= 0:
+            errors.append("目标波动率必须大于0")
+        if not 0 < config.vol_ewma_lambda < 1:
+            errors.append("EWMA衰减系数必须在0-1之间")
+        if config.vol_sample_seconds <= 0:
+            errors.append("波动率采样间隔必须大于0")
+        if config.vol_max_scale <= 0:
+            errors.append("仓位最大放大倍数必须大于0")
+    
+    if config.regime_detection_enabled:
+        if not 0 < config.regime_low_percentile < config.regime_high_percentile < 1:
+            errors.append("波动率状态分位数阈值必须满足 0 < 低 < 高 < 1")
+        if config.regime_sample_seconds <= 0:
+            errors.append("状态识别采样间隔必须大于0")
+        for regime in config.regime_stand_down:
+            if regime not in ("low", "normal", "high"):
+                errors.append(f"未知的波动率状态: {regime}")
+        if config.regime_profiles_file:
+            try:
+                load_profiles(config.regime_profiles_file)
+            except ValueError as e:
+                errors.append(str(e))
+        if not 0 < config.regime_trend_threshold <= 1:
+            errors.append("趋势行情的效率比阈值必须在0-1之间")
+    
+    if config.liquidity_filter_enabled:
+        if config.max_spread_bps <= 0:
+            errors.append("最大买卖价差必须大于0")
+        if config.liquidity_depth_levels <= 0:
+            errors.append("流动性统计档位数必须大于0")
+    
+    if config.activity_filter_enabled:
+        if config.min_open_interest_usd < 0 or config.min_volume_24h_usd < 0:
+            errors.append("最低持仓价值和24小时成交额不能为负数")
+        if config.activity_poll_seconds <= 0:
+            errors.append("持仓量与成交量更新间隔必须大于0")
+    
+    if config.basis_enabled:
+        if config.basis_sample_seconds <= 0:
+            errors.append("基差采样间隔必须大于0")
+        if config.basis_window < 10:
+            errors.append("基差样本数不能小于10")
+        if config.basis_max_bps < 0 or config.basis_max_zscore < 0:
+            errors.append("基差上限和z-score上限不能为负数")
+    
+    if config.funding_avoid_enabled:
+        if config.funding_avoid_window_seconds <= 0:
+            errors.append("资金费规避窗口必须大于0")
+        if config.funding_avoid_action not in FUNDING_ACTIONS:
+            errors.append(f"资金费规避动作必须为 {'/'.join(FUNDING_ACTIONS)}")
+        if config.funding_avoid_min_rate < 0:
+            errors.append("资金费规避的最低费率不能为负数")
+        if config.funding_poll_seconds <= 0:
+            errors.append("资金费率更新间隔必须大于0")
+    
+    if config.drawdown_throttle_enabled:
+        try:
+            parse_levels(config.drawdown_scale_levels)
+        except ValueError as e:
+            errors.append(f"回撤档位配置无效（EDGEX_DRAWDOWN_SCALE_LEVELS）: {e}")
+    
+    if config.inventory_skew_enabled:
+        if config.inventory_max_exposure <= 0:
+            errors.append("净库存上限必须大于0")
+        if config.inventory_skew_strength < 0:
+            errors.append("库存偏斜强度不能为负数")
+    
+    if config.vwap_unwind_enabled:
+        if config.vwap_duration_seconds <= 0 or config.vwap_slices <= 0:
+            errors.append("VWAP执行时长和子订单个数必须大于0")
+    
+    if config.signal_script and not os.path.isfile(config.signal_script):
+        errors.append(f"信号脚本不存在: {config.signal_script}")
+    
+    if config.signal_ensemble:
+        if config.signal_script:
+            errors.append("信号脚本和组合信号不能同时使用")
+        try:
+            weights = parse_weights(config.signal_ensemble)
+            if not weights:
+                errors.append("组合信号至少需要一个信号源")
+            if "onnx" in weights and not os.path.isfile(config.ml_model_path):
+                errors.append(f"机器学习信号模型不存在（EDGEX_ML_MODEL_PATH）: {config.ml_model_path}")
+        except ValueError as e:
+            errors.append(f"组合信号配置无效（EDGEX_SIGNAL_ENSEMBLE）: {e}")
+        if config.signal_ensemble_mode not in ENSEMBLE_MODES:
+            errors.append(f"组合信号合并方式必须为 {'/'.join(ENSEMBLE_MODES)}")
+        if not 0 < config.signal_min_agreement <= 1:
+            errors.append("组合信号最低一致度必须在0-1之间")
+        if config.ml_signal_threshold < 0:
+            errors.append("机器学习信号评分阈值不能为负数")
+    
+    if config.feature_recorder_dir:
+        try:
+            parse_horizons(config.feature_recorder_horizons)
+        except ValueError as e:
+            errors.append(f"特征记录前瞻时长无效（EDGEX_FEATURE_RECORDER_HORIZONS）: {e}")
+        if config.feature_recorder_flow_window <= 0:
+            errors.append("特征记录成交流统计窗口必须大于0")
+    
+    if config.strategies_file:
+        if not os.path.isfile(config.strategies_file):
+            errors.append(f"策略列表文件不存在: {config.strategies_file}")
+        
+        if config.allocation_eval_seconds <= 0:
+            errors.append("资金分配评估间隔必须大于0")
+        
+        if not 0 < config.allocation_throttle_drawdown <= config.allocation_disable_drawdown:
+            errors.append("限流回撤阈值必须大于0且不超过停用回撤阈值")
+        
+        if not 0 < config.allocation_throttle_scale <= 1:
+            errors.append("限流预算比例必须在0-1之间")
+    
+    if config.ab_test:
+        if not config.strategies_file:
+            errors.append("A/B测试需要配置策略列表文件（EDGEX_STRATEGIES_FILE）")
+        
+        variants = [v.strip() for v in config.ab_test.split(",")]
+        if len(variants) != 2 or not all(variants) or variants[0] == variants[1]:
+            errors.append("A/B测试需要指定两个不同的策略名称（如 EDGEX_AB_TEST=baseline,candidate）")
+    
+    if config.webhook_enabled and not config.webhook_secret:
+        errors.append("启用Webhook时必须设置校验口令（EDGEX_WEBHOOK_SECRET）")
+    
+    if config.signal_bus not in ("", "redis", "zmq"):
+        errors.append("信号总线类型必须为 redis 或 zmq")
+    
+    if config.event_stream not in ("", "kafka", "nats"):
+        errors.append("事件流类型必须为 kafka 或 nats")
+    elif config.event_stream and config.event_snapshot_seconds <= 0:
+        errors.append("性能快照推送间隔必须大于0")
+    
+    if config.dual_quote_enabled:
+        if config.position_mode != "one_way":
+            errors.append("双边挂单模式只支持单向持仓（EDGEX_POSITION_MODE=one_way）")
+        if config.dual_quote_spread_bps <= 0 or config.dual_quote_notional <= 0:
+            errors.append("双边挂单距离和名义价值必须大于0")
+        if config.dual_quote_requote_bps <= 0:
+            errors.append("双边挂单重新挂单阈值必须大于0")
+        if config.dual_quote_max_imbalance < 1:
+            errors.append("双边挂单净持仓上限不能小于1笔挂单")
+    
+    if config.position_mode not in ("one_way", "hedge"):
+        errors.append("持仓模式必须为 one_way 或 hedge")
+    
+    if config.execution_policy not in ("market", "limit_then_market"):
+        errors.append("执行策略必须为 market 或 limit_then_market")
+    
+    if config.limit_wait_ms <= 0:
+        errors.append("限价单等待时间必须大于0")
+    
+    if config.limit_price_offset_ticks < 0:
+        errors.append("限价单偏移tick数不能为负数")
+    
+    if config.requote_threshold_ticks < 0:
+        errors.append("改价阈值tick数不能为负数")
+    
+    if config.max_requotes_per_minute < 0:
+        errors.append("每分钟改价次数不能为负数")
+    
+    if config.order_ttl_ms < 0:
+        errors.append("订单存活时间不能为负数")
+    
+    if config.exit_trigger_price_type not in ("LAST_PRICE", "MARK_PRICE"):
+        errors.append("止盈止损触发价格类型必须为 LAST_PRICE 或 MARK_PRICE")
+    
+    if not 0 <= config.maintenance_margin_rate < 1:
+        errors.append("维持保证金率必须在0-1之间")
+    
+    if config.mark_price_poll_seconds <= 0:
+        errors.append("标记价格轮询间隔必须大于0")
+    
+    if config.loop_interval_ms <= 0 or config.min_trade_interval < 0:
+        errors.append("策略评估间隔必须大于0，最小交易间隔不能为负数")
+    
+    try:
+        load_timezone(config.trading_timezone)
+    except ValueError as e:
+        errors.append(str(e))
+    
+    if config.max_trades_per_day < 0:
+        errors.append("每日最多开仓次数不能为负数")
+    
+    if config.max_open_positions < 0 or config.loss_cooldown_seconds < 0:
+        errors.append("同时持仓数上限和亏损冷却时间不能为负数")
+    
+    if config.loss_cooldown_after < 1:
+        errors.append("触发冷却的连续亏损笔数必须大于0")
+    
+    if config.loss_breaker_streak < 0 or config.loss_breaker_seconds <= 0:
+        errors.append("熔断连续亏损笔数不能为负数，熔断时间必须大于0")
+    
+    if min(config.http_timeout, config.http_connect_timeout, config.http_order_timeout,
+           config.http_query_timeout, config.http_history_timeout) <= 0:
+        errors.append("HTTP超时必须大于0")
+    
+    if (config.http_pool_size <= 0 or config.http_pool_per_host <= 0 or config.http_order_pool_size <= 0
+            or config.http_keepalive_seconds < 0):
+        errors.append("HTTP连接池大小必须大于0，空闲连接保持时间不能为负数")
+    
+    if config.signature_recv_window_ms <= 0 or config.signature_timestamp_retries < 0:
+        errors.append("签名时间窗口必须大于0，时间戳重试次数不能为负数")
+    
+    if config.order_ack_timeout_seconds < 0 or config.order_unconfirmed_expire_seconds <= config.order_ack_timeout_seconds:
+        errors.append("下单确认超时不能为负数，未确认订单的过期时间必须大于确认超时")
+    
+    if config.orphan_order_policy not in ORDER_POLICIES:
+        errors.append(f"不支持的孤儿挂单处理方式: {config.orphan_order_policy}（可选: {', '.join(ORDER_POLICIES)}）")
+    
+    if config.orphan_position_policy not in POSITION_POLICIES:
+        errors.append(f"不支持的孤儿持仓处理方式: {config.orphan_position_policy}（可选: {', '.join(POSITION_POLICIES)}）")
+    
+    if config.event_loop not in EVENT_LOOPS:
+        errors.append(f"不支持的事件循环: {config.event_loop}（可选: {', '.join(EVENT_LOOPS)}）")
+    
+    if config.executor_workers < 0:
+        errors.append("线程池工作线程数不能为负数")
+    
+    for name, spec in (("EDGEX_EXECUTION_CPUS", config.execution_cpus), ("EDGEX_MARKET_DATA_CPUS", config.market_data_cpus)):
+        try:
+            parse_cpu_list(spec)
+        except ValueError as e:
+            errors.append(f"CPU列表配置无效（{name}）: {e}")
+    
+    if any(urlsplit(url).scheme not in ("http", "https") for url in config.rest_endpoints):
+        errors.append("REST地址必须以 http:// 或 https:// 开头")
+    
+    if any(urlsplit(url).scheme not in ("ws", "wss") for url in config.ws_endpoints):
+        errors.append("WebSocket地址必须以 ws:// 或 wss:// 开头")
+    
+    if config.endpoint_probe_seconds <= 0 or config.endpoint_failover_errors < 1 or config.endpoint_cooldown_seconds < 0:
+        errors.append("端点探测间隔必须大于0，切换错误次数至少为1，冷却时间不能为负数")
+    
+    if config.proxy_url:
+        proxy = urlsplit(config.proxy_url)
+        if proxy.scheme not in ("http", "socks5", "socks5h") or not proxy.hostname:
+            errors.append("代理地址格式错误，应为 http://、socks5:// 或 socks5h:// 开头的 主机:端口")
+    
+    if config.latency_report_seconds <= 0:
+        errors.append("延迟分位数输出间隔必须大于0")
+    
+    if config.metrics_enabled and not 0 < config.metrics_port < 65536:
+        errors.append("指标接口端口必须在1-65535之间")
+    
+    if config.metrics_push_url:
+        if not config.metrics_push_url.startswith(("http://", "https://")):
+            errors.append("指标推送地址必须以 http:// 或 https:// 开头")
+        if config.metrics_push_format not in PUSH_FORMATS:
+            errors.append(f"指标推送格式必须为 {'/'.join(PUSH_FORMATS)}")
+        if config.metrics_push_interval <= 0:
+            errors.append("指标推送间隔必须大于0")
+    
+    if config.performance_report_interval <= 0:
+        errors.append("性能报告间隔必须大于0")
+    for report_type, sinks in config.report_sinks.items():
+        if report_type not in REPORT_TYPES:
+            errors.append(f"未知的报告类型: {report_type}（可选 {'/'.join(REPORT_TYPES)}）")
+        for sink in sinks:
+            if sink not in SINK_TYPES:
+                errors.append(f"未知的报告输出目标: {sink}（可选 {'/'.join(SINK_TYPES)}）")
+    sinks_in_use = {sink for sinks in config.report_sinks.values() for sink in sinks}
+    if "file" in sinks_in_use and not config.report_file_path:
+        errors.append("报告输出到文件时必须设置 EDGEX_REPORT_FILE")
+    if "notifier" in sinks_in_use and not config.notifier_webhook_url:
+        errors.append("报告输出到通知时必须设置 EDGEX_NOTIFIER_WEBHOOK_URL")
+    if "discord" in sinks_in_use and not config.discord_webhook_url:
+        errors.append("报告输出到Discord时必须设置 EDGEX_DISCORD_WEBHOOK_URL")
+    if config.discord_notify_fills and not config.discord_webhook_url:
+        errors.append("发送成交通知时必须设置 EDGEX_DISCORD_WEBHOOK_URL")
+    if not config.log_dir or config.log_max_file_mb < 0 or config.log_retention_days <= 0:
+        errors.append("日志目录不能为空，日志文件大小上限不能为负数，保留天数必须大于0")
+    if config.log_compression not in LOG_COMPRESSIONS:
+        errors.append(f"日志压缩格式必须为 {'/'.join(c or '空' for c in LOG_COMPRESSIONS)}")
+    
+    if config.notify_dedup_seconds < 0:
+        errors.append("通知去重窗口不能为负数")
+    for channel, limit in config.notify_rate_limits.items():
+        if channel not in NOTIFY_CHANNELS:
+            errors.append(f"未知的通知通道: {channel}（可选 {'/'.join(NOTIFY_CHANNELS)}）")
+        if limit < 0:
+            errors.append(f"通知通道 {channel} 的每分钟消息数不能为负数")
+    if config.smtp_host:
+        if not config.smtp_from or not config.smtp_to:
+            errors.append("发送告警邮件时必须设置 EDGEX_SMTP_FROM 和 EDGEX_SMTP_TO")
+        if config.smtp_security not in SMTP_SECURITY:
+            errors.append(f"SMTP加密方式必须为 {'/'.join(SMTP_SECURITY)}")
+        if not 0 < config.smtp_port < 65536 or config.smtp_max_per_hour <= 0 or config.smtp_min_interval_seconds < 0:
+            errors.append("SMTP端口无效，或每小时邮件数不大于0，或邮件间隔为负数")
+    if not 0 <= config.liquidation_alert_distance < 1:
+        errors.append("强平告警距离必须在0到1之间")
+    if config.ws_reconnect_alert_failures < 1:
+        errors.append("重连失败告警次数必须大于0")
+    if "metrics" in sinks_in_use and not (config.metrics_enabled or config.metrics_push_url):
+        errors.append("报告输出到指标接口时必须启用指标接口（EDGEX_METRICS_ENABLED=true）或指标推送（EDGEX_METRICS_PUSH_URL）")
+    
+    if config.trade_records_capacity <= 0 or config.equity_history_capacity < 2:
+        errors.append("交易记录容量必须大于0，权益历史容量不能小于2")
+    
+    if config.benchmark_sample_seconds < 0:
+        errors.append("基准对比采样间隔不能为负数")
+    
+    if config.performance_sample_seconds <= 0:
+        errors.append("滚动绩效权益采样间隔必须大于0")
+    
+    if config.bar_type not in ("ticker", "time", "volume", "dollar"):
+        errors.append("K线类型必须为 ticker、time、volume 或 dollar")
+    
+    if config.signal_price_source not in PRICE_SOURCES:
+        errors.append(f"参考价格来源必须为 {'/'.join(PRICE_SOURCES)}")
+    elif config.signal_price_source != "last" and config.bar_type != "ticker":
+        errors.append("参考价格来源只适用于ticker价格序列（EDGEX_BAR_TYPE=ticker）")
+    
+    if config.warmup_retry_seconds <= 0:
+        errors.append("启动检查重试间隔必须大于0")
+    
+    if config.bootstrap_klines < 0 or config.bootstrap_klines > 1000:
+        errors.append("预填历史K线根数必须在0到1000之间")
+    if config.bootstrap_kline_interval not in ("1m", "5m", "15m", "30m", "1h", "4h", "1d"):
+        errors.append("预填历史K线周期必须为 1m/5m/15m/30m/1h/4h/1d")
+    
+    if config.fair_price_levels <= 0:
+        errors.append("公允价格统计档位数必须大于0")
+    
+    if config.bar_size <= 0:
+        errors.append("K线大小必须大于0")
+    
+    if config.ws_ack_timeout <= 0:
+        errors.append("WebSocket订阅确认超时必须大于0")
+    
+    if config.contract_spec_refresh_seconds < 0:
+        errors.append("合约规格刷新间隔不能为负数")
+    
+    if config.halt_retry_seconds <= 0:
+        errors.append("暂停交易错误的恢复尝试间隔必须大于0")
+    
+    if config.ws_public_connections < 1:
+        errors.append("公共行情WebSocket连接数必须至少为1")
+    
+    if config.ws_max_channels_per_connection < 0:
+        errors.append("每条连接的频道上限不能为负数")
+    
+    if config.pnl_crosscheck_seconds <= 0 or config.pnl_crosscheck_tolerance < 0:
+        errors.append("盈亏核对间隔必须大于0，允许差异不能为负数")
+    
+    if config.margin_buffer_pct < 0:
+        errors.append("保证金预留比例不能为负数")
+    
+    if config.duplicate_order_window_ms < 0 or config.max_orders_per_second < 0:
+        errors.append("重复订单抑制窗口和每秒下单数不能为负数")
+    
+    if config.close_retry_attempts < 0:
+        errors.append("平仓重试次数不能为负数")
+    
+    if config.fee_refresh_seconds <= 0:
+        errors.append("手续费率刷新间隔必须大于0")
+    if config.min_edge_fee_multiple < 0:
+        errors.append("预期收益的手续费倍数不能为负数")
+    
+    if config.ev_gate_enabled and config.ev_default_slippage_bps < 0:
+        errors.append("默认滑点估计不能为负数")
+    
+    if config.latency_guard_enabled:
+        if not 0 < config.latency_guard_percentile <= 100:
+            errors.append("延迟保护分位数必须在0到100之间")
+        if config.latency_guard_rest_ms < 0 or config.latency_guard_ws_ms < 0:
+            errors.append("延迟保护的延迟上限不能为负数")
+        if config.latency_guard_window < 20:
+            errors.append("延迟保护样本数不能少于20")
+        if config.latency_guard_cooldown_seconds < 0:
+            errors.append("延迟保护恢复等待时间不能为负数")
+        if config.latency_guard_quote_widen < 1:
+            errors.append("双边挂单价差放宽倍数不能小于1")
+    
+    try:
+        parse_asset_map(config.collateral_rates)
+        if any(v >= 1 for v in parse_asset_map(config.collateral_haircuts).values()):
+            errors.append("抵押资产折扣比例必须小于1")
+    except ValueError as e:
+        errors.append(str(e))
+    
+    if config.wash_guard_enabled:
+        if config.wash_min_interval_ms < 0 or config.wash_min_price_bps < 0:
+            errors.append("对敲防护的最小时间间隔和最小价格距离不能为负数")
+        if config.wash_price_window_seconds <= 0 or config.wash_audit_window_ms <= 0:
+            errors.append("对敲防护的观察窗口和审计窗口必须大于0")
+    
+    if not 0 <= config.max_price_deviation_pct < 1:
+        errors.append("价格偏离上限必须在0-1之间")
+    
+    if config.min_order_notional < 0 or config.max_queued_entry_orders < 0:
+        errors.append("最小名义价值和开仓订单排队上限不能为负数")
+    
     if errors:
         for error in errors:
             logger.error(f"配置验证失败: {error}")
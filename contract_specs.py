@@ -0,0 +1,215 @@
+"""
+合约规格缓存模块
+
+交易所元数据（/api/v1/public/meta/getMetaData 的 contractList）解析为带类型的 ContractSpec，
+供下单数量/价格取整、最小下单量和保证金估算使用：
+- 启动时先读取本地缓存文件（元数据接口暂时不可用时仍可交易），拉取成功后覆盖并写回
+- 按配置间隔定期重新拉取；公共WebSocket的 metadata 频道推送变化时立即更新
+"""
+
+import json
+import os
+import time
+from dataclasses import dataclass
+from decimal import Decimal, ROUND_DOWN, ROUND_HALF_UP
+from typing import Any, Dict, Iterable, List, Optional, Tuple
+
+from loguru import logger
+
+
+@dataclass(frozen=True)
+class RiskTier:
+    """维持保证金档位（持仓名义价值不超过上限时适用）"""
+    position_value_upper_bound: float
+    max_leverage: int
+    maintenance_margin_rate: float
+
+
+@dataclass(frozen=True)
+class ContractSpec:
+    """合约规格"""
+    contract_id: str
+    symbol: str                          # 合约名称（如 BTCUSD）
+    tick_size: float                     # 最小价格变动单位
+    step_size: float                     # 最小数量变动单位
+    min_order_size: float
+    max_order_size: float                # 0为不限制
+    max_position_size: float             # 0为不限制
+    max_leverage: int
+    risk_tiers: Tuple[RiskTier, ...]     # 按名义价值上限升序
+    funding_interval_minutes: int
+    taker_fee_rate: float
+    maker_fee_rate: float
+    enable_trade: bool
+    enable_open_position: bool
+
+    def round_price(self, price: float) -> float:
+        """价格按最小价格变动单位四舍五入"""
+        if self.tick_size <= 0:
+            return price
+        tick = Decimal(str(self.tick_size))
+        return float((Decimal(str(price)) / tick).quantize(Decimal("1"), rounding=ROUND_HALF_UP) * tick)
+
+    def round_size(self, size: float) -> float:
+        """数量按最小数量变动单位向下取整"""
+        if self.step_size <= 0:
+            return size
+        step = Decimal(str(self.step_size))
+        return float((Decimal(str(size)) / step).quantize(Decimal("1"), rounding=ROUND_DOWN) * step)
+
+    def risk_tier(self, notional: float) -> Optional[RiskTier]:
+        """持仓名义价值适用的保证金档位（超出所有档位时为最高档）"""
+        for tier in self.risk_tiers:
+            if notional <= tier.position_value_upper_bound:
+                return tier
+        return self.risk_tiers[-1] if self.risk_tiers else None
+
+    def maintenance_margin_rate(self, notional: float) -> float:
+        tier = self.risk_tier(notional)
+        return tier.maintenance_margin_rate if tier else 0.0
+
+
+def _float(value: Any) -> float:
+    try:
+        return float(value) if value not in (None, "") else 0.0
+    except (TypeError, ValueError):
+        return 0.0
+
+
+def parse_contract(data: Dict[str, Any]) -> ContractSpec:
+    """解析元数据中的一个合约"""
+    tiers = sorted(
+        (RiskTier(
+            position_value_upper_bound=_float(t.get("positionValueUpperBound")) or float("inf"),
+            max_leverage=int(_float(t.get("maxLeverage"))),
+            maintenance_margin_rate=_float(t.get("maintenanceMarginRate")),
+        ) for t in data.get("riskTierList") or []),
+        key=lambda t: t.position_value_upper_bound
+    )
+    return ContractSpec(
+        contract_id=str(data.get("contractId", "")),
+        symbol=str(data.get("contractName", "")),
+        tick_size=_float(data.get("tickSize")),
+        step_size=_float(data.get("stepSize")),
+        min_order_size=_float(data.get("minOrderSize")),
+        max_order_size=_float(data.get("maxOrderSize")),
+        max_position_size=_float(data.get("maxPositionSize")),
+        max_leverage=int(_float(data.get("displayMaxLeverage") or data.get("defaultLeverage"))
+                         or (tiers[0].max_leverage if tiers else 0)),
+        risk_tiers=tuple(tiers),
+        funding_interval_minutes=int(_float(data.get("fundingRateIntervalMin"))),
+        taker_fee_rate=_float(data.get("defaultTakerFeeRate")),
+        maker_fee_rate=_float(data.get("defaultMakerFeeRate")),
+        enable_trade=bool(data.get("enableTrade", True)),
+        enable_open_position=bool(data.get("enableOpenPosition", True)),
+    )
+
+
+class ContractSpecCache:
+    """合约规格缓存（按合约ID和合约名称查找）"""
+
+    def __init__(self, path: str = ""):
+        """
+        Args:
+            path: 缓存文件路径（空为不保存）
+        """
+        self.path = path
+        self.specs: Dict[str, ContractSpec] = {}     # 合约ID -> 规格
+        self._names: Dict[str, str] = {}             # 合约名称 -> 合约ID
+        self._raw: Dict[str, Dict[str, Any]] = {}    # 合约ID -> 原始元数据（写入缓存文件）
+        self.updated_at = 0.0
+        self.refreshes = 0
+        self.ws_updates = 0
+
+    def get(self, symbol: str) -> Optional[ContractSpec]:
+        """
+        查找合约规格
+
+        Args:
+            symbol: 合约名称或合约ID
+        """
+        return self.specs.get(self._names.get(symbol, symbol))
+
+    def alias(self, name: str, contract_id: str):
+        """登记交易对别名（配置中的交易对名称与合约名称不同时）"""
+        self._names[name] = contract_id
+
+    def update(self, contracts: Iterable[Dict[str, Any]], source: str = "rest") -> List[ContractSpec]:
+        """
+        用元数据更新缓存
+
+        Args:
+            contracts: 元数据中的合约列表（WebSocket推送可能只含变化的合约）
+            source: rest 或 ws
+
+        Returns:
+            List[ContractSpec]: 规格有变化的合约
+        """
+        changed = []
+        known = bool(self.specs)
+        for data in contracts:
+            try:
+                spec = parse_contract(data)
+            except Exception as e:
+                logger.warning(f"[合约规格] 解析合约元数据失败: {e}")
+                continue
+            if not spec.contract_id:
+                continue
+            self._raw[spec.contract_id] = dict(data)
+            self._names[spec.symbol] = spec.contract_id
+            if self.specs.get(spec.contract_id) != spec:
+                self.specs[spec.contract_id] = spec
+                changed.append(spec)
+        self.updated_at = time.time()
+        if source == "ws":
+            self.ws_updates += 1
+        else:
+            self.refreshes += 1
+        if changed and known:
+            logger.info(f"[合约规格] {len(changed)} 个合约规格已更新: {', '.join(s.symbol for s in changed[:10])}")
+        return changed
+
+    def load(self) -> bool:
+        """读取缓存文件，返回是否读取成功"""
+        if not self.path or not os.path.exists(self.path):
+            return False
+        try:
+            with open(self.path, "r", encoding="utf-8") as fp:
+                data = json.load(fp)
+            contracts = data.get("contracts", [])
+            for contract in contracts:
+                spec = parse_contract(contract)
+                self._raw[spec.contract_id] = contract
+                self._names[spec.symbol] = spec.contract_id
+                self.specs[spec.contract_id] = spec
+            self.updated_at = float(data.get("updated_at", 0))
+            logger.info(f"[合约规格] 从 {self.path} 读取 {len(contracts)} 个合约（缓存时间 "
+                        f"{time.strftime('%Y-%m-%d %H:%M:%S', time.localtime(self.updated_at))}）")
+            return True
+        except Exception as e:
+            logger.warning(f"[合约规格] 读取缓存文件 {self.path} 失败: {e}")
+            return False
+
+    def save(self):
+        """写入缓存文件（先写临时文件再替换）"""
+        if not self.path:
+            return
+        try:
+            directory = os.path.dirname(self.path)
+            if directory:
+                os.makedirs(directory, exist_ok=True)
+            tmp = f"{self.path}.tmp"
+            with open(tmp, "w", encoding="utf-8") as fp:
+                json.dump({"updated_at": self.updated_at, "contracts": list(self._raw.values())}, fp, ensure_ascii=False)
+            os.replace(tmp, self.path)
+        except Exception as e:
+            logger.warning(f"[合约规格] 写入缓存文件 {self.path} 失败: {e}")
+
+    def age(self) -> Optional[float]:
+        """距上次更新的秒数（从未更新时为None）"""
+        return time.time() - self.updated_at if self.updated_at else None
+
+    def get_stats(self) -> Dict[str, Any]:
+        age = self.age()
+        return {"contracts": len(self.specs), "refreshes": self.refreshes, "ws_updates": self.ws_updates,
+                "age_seconds": round(age, 1) if age is not None else None}
@@ -0,0 +1,103 @@
+"""
+亏损冷却模块
+
+连续亏损达到次数后暂停开仓一段时间，冷却结束前只管理已有持仓：
+- 交易对冷却: 每笔平仓交易按盈亏更新该交易对的连续亏损次数，盈利（或持平）时清零；
+  连续亏损达到 after 次时该交易对进入冷却（after 为1即每笔亏损后冷却），连续亏损次数随之清零
+- 全局熔断: 按所有交易对的平仓交易顺序统计连续亏损，达到笔数后暂停全部开仓
+"""
+
+import time
+from typing import Any, Dict, Optional
+
+
+class SymbolCooldowns:
+    """亏损后的交易对冷却"""
+
+    def __init__(self, seconds: float, after: int = 1):
+        """
+        Args:
+            seconds: 冷却时长（秒，0为不冷却）
+            after: 连续亏损多少笔后冷却
+        """
+        self.seconds = seconds
+        self.after = max(after, 1)
+        self.losses: Dict[str, int] = {}      # 交易对 -> 连续亏损次数
+        self.until: Dict[str, float] = {}     # 交易对 -> 冷却结束时间
+        self.triggered = 0
+
+    def record(self, symbol: str, pnl: float, now: Optional[float] = None) -> bool:
+        """
+        记录一笔平仓交易
+
+        Returns:
+            bool: 是否因此进入冷却
+        """
+        if pnl >= 0:
+            self.losses.pop(symbol, None)
+            return False
+        self.losses[symbol] = self.losses.get(symbol, 0) + 1
+        if self.seconds <= 0 or self.losses[symbol] < self.after:
+            return False
+        self.losses.pop(symbol)
+        self.until[symbol] = (now if now is not None else time.time()) + self.seconds
+        self.triggered += 1
+        return True
+
+    def remaining(self, symbol: str, now: Optional[float] = None) -> float:
+        """冷却剩余秒数（未冷却为0）"""
+        until = self.until.get(symbol)
+        if until is None:
+            return 0.0
+        left = until - (now if now is not None else time.time())
+        if left <= 0:
+            del self.until[symbol]
+            return 0.0
+        return left
+
+    def get_stats(self) -> Dict[str, Any]:
+        now = time.time()
+        active = {symbol: self.remaining(symbol, now) for symbol in list(self.until)}
+        return {"active": {s: left for s, left in active.items() if left > 0},
+                "losing_streaks": dict(self.losses), "triggered": self.triggered}
+
+
+class LossCircuitBreaker:
+    """全局连续亏损熔断：所有交易对合计连续亏损达到笔数后暂停全部开仓"""
+
+    def __init__(self, streak: int, seconds: float):
+        """
+        Args:
+            streak: 触发熔断的连续亏损笔数（0为不启用）
+            seconds: 熔断后暂停开仓的时间（秒）
+        """
+        self.streak = streak
+        self.seconds = seconds
+        self.losses = 0
+        self.until = 0.0
+        self.triggered = 0
+
+    def record(self, pnl: float, now: Optional[float] = None) -> bool:
+        """
+        记录一笔平仓交易
+
+        Returns:
+            bool: 是否因此触发熔断
+        """
+        if pnl >= 0:
+            self.losses = 0
+            return False
+        self.losses += 1
+        if self.streak <= 0 or self.losses < self.streak:
+            return False
+        self.losses = 0
+        self.until = (now if now is not None else time.time()) + self.seconds
+        self.triggered += 1
+        return True
+
+    def remaining(self, now: Optional[float] = None) -> float:
+        """熔断剩余秒数（未熔断为0）"""
+        return max(self.until - (now if now is not None else time.time()), 0.0)
+
+    def get_stats(self) -> Dict[str, Any]:
+        return {"losing_streak": self.losses, "remaining": self.remaining(), "triggered": self.triggered}
@@ -0,0 +1,135 @@
+"""
+交易日统计模块
+
+按配置时区的自然日（默认UTC）统计当日开仓次数、成交额和已实现盈亏：
+- 跨过当地零点时输出前一交易日汇总并清零，每日开仓次数上限、交易量进度和今日盈亏随之重置
+- 配置状态目录时写入状态文件（数据变化时写入），重启后同一交易日内继续累计；跨日重启时从零开始
+"""
+
+import json
+import os
+from datetime import datetime, timezone
+from typing import Any, Dict, Optional
+from zoneinfo import ZoneInfo
+
+from loguru import logger
+
+
+def load_timezone(name: str):
+    """
+    解析时区名称
+
+    Args:
+        name: IANA时区名称（如 "UTC"、"Asia/Shanghai"）
+
+    Raises:
+        ValueError: 未知时区
+    """
+    if name.upper() == "UTC":
+        return timezone.utc
+    try:
+        return ZoneInfo(name)
+    except (KeyError, ValueError) as e:
+        raise ValueError(f"未知时区: {name}") from e
+
+
+class DailyStats:
+    """当日交易统计"""
+
+    def __init__(self, tz_name: str = "UTC", path: str = ""):
+        """
+        Args:
+            tz_name: 交易日时区（IANA名称）
+            path: 状态文件路径（空为不保存）
+        """
+        self.tz = load_timezone(tz_name)
+        self.path = path
+        self.day = self._today()
+        self.entries = 0     # 开仓订单数
+        self.trades = 0      # 平仓交易数
+        self.volume = 0.0    # 成交额（USDT）
+        self.pnl = 0.0       # 已实现盈亏（USDT）
+        self.previous: Optional[Dict[str, Any]] = None  # 本次运行中结束的上一个交易日汇总
+        self._dirty = False
+        self._load()
+
+    def _today(self, now: Optional[float] = None) -> str:
+        return datetime.fromtimestamp(now, self.tz).date().isoformat() if now is not None \
+            else datetime.now(self.tz).date().isoformat()
+
+    def _load(self):
+        if not self.path or not os.path.exists(self.path):
+            return
+        try:
+            with open(self.path, "r", encoding="utf-8") as fp:
+                data = json.load(fp)
+        except (OSError, ValueError) as e:
+            logger.warning(f"[交易日] 读取 {self.path} 失败，当日统计从零开始: {e}")
+            return
+        if data.get("day") != self.day:
+            return
+        self.entries = int(data.get("entries", 0))
+        self.trades = int(data.get("trades", 0))
+        self.volume = float(data.get("volume", 0.0))
+        self.pnl = float(data.get("pnl", 0.0))
+        logger.info(f"[交易日] 恢复 {self.day} 统计: 开仓 {self.entries} 次，成交额 {self.volume:.2f} USDT，"
+                    f"盈亏 {self.pnl:.4f} USDT")
+
+    def maybe_rollover(self, now: Optional[float] = None) -> bool:
+        """
+        检查是否进入新的交易日，是则输出前一日汇总并清零（主循环调用）
+
+        Returns:
+            bool: 是否发生了日切
+        """
+        today = self._today(now)
+        if today == self.day:
+            return False
+        logger.info(f"[交易日] {self.day} 结束: 开仓 {self.entries} 次，平仓 {self.trades} 笔，"
+                    f"成交额 {self.volume:.2f} USDT，盈亏 {self.pnl:.4f} USDT")
+        self.previous = self.to_dict()
+        self.day = today
+        self.entries = 0
+        self.trades = 0
+        self.volume = 0.0
+        self.pnl = 0.0
+        self._dirty = True
+        self.save()
+        return True
+
+    def record_entry(self):
+        """记录一次开仓下单"""
+        self.maybe_rollover()
+        self.entries += 1
+        self._dirty = True
+
+    def record_fill(self, notional: float):
+        """记录一笔成交的成交额"""
+        self.maybe_rollover()
+        self.volume += notional
+        self._dirty = True
+
+    def record_trade(self, pnl: float):
+        """记录一笔平仓交易的已实现盈亏"""
+        self.maybe_rollover()
+        self.trades += 1
+        self.pnl += pnl
+        self._dirty = True
+
+    def save(self):
+        """数据有变化时写入状态文件"""
+        if not self.path or not self._dirty:
+            return
+        try:
+            os.makedirs(os.path.dirname(self.path) or ".", exist_ok=True)
+            tmp_path = f"{self.path}.tmp"
+            with open(tmp_path, "w", encoding="utf-8") as fp:
+                json.dump(self.to_dict(), fp)
+            os.replace(tmp_path, self.path)
+            self._dirty = False
+        except OSError as e:
+            logger.error(f"[交易日] 写入 {self.path} 失败: {e}")
+
+    def to_dict(self) -> Dict[str, Any]:
+        return {"day": self.day, "entries": self.entries, "trades": self.trades,
+                "volume": self.volume, "pnl": self.pnl}
@@ -0,0 +1,166 @@
+"""
+Discord通知模块
+
+设置了 Discord Webhook 地址（EDGEX_DISCORD_WEBHOOK_URL）时，以富文本卡片（embed）发送：
+- 告警: 连续亏损熔断、交易对冷却等（红色），与通知Webhook同时发送
+- 成交: 开仓、加仓、平仓（开仓蓝色，平仓按盈亏为绿色/红色），EDGEX_DISCORD_NOTIFY_FILLS=true 时发送
+- 报告: 作为报告输出目标 discord（见 report_sinks.py），如交易日汇总（灰色，数值字段逐项列出）
+可与通用通知Webhook（EDGEX_NOTIFIER_WEBHOOK_URL）同时使用，也可只使用其中之一。
+告警和成交卡片发送前经过通知限流（见 notify_throttle.py，通道 discord），严重告警不受限流；报告不限流。
+发送在后台进行，失败只记录日志，不影响交易；Discord限流（HTTP 429）时按返回的等待时间重试一次。
+"""
+
+import asyncio
+from datetime import datetime, timezone
+from typing import Any, Dict, List, Optional, Set, Tuple
+
+import aiohttp
+from loguru import logger
+
+from notify_throttle import NotificationThrottle
+
+_DISCORD_TIMEOUT = 10.0
+_MAX_FIELDS = 25             # Discord 单个embed最多25个字段
+_MAX_DESCRIPTION = 4096
+
+COLOR_INFO = 0x3498DB
+COLOR_PROFIT = 0x2ECC71
+COLOR_LOSS = 0xE74C3C
+COLOR_ALERT = 0xE74C3C
+COLOR_REPORT = 0x95A5A6
+
+
+def build_embed(title: str, description: str = "", color: int = COLOR_INFO,
+                fields: Optional[List[Tuple[str, Any]]] = None, footer: str = "") -> Dict[str, Any]:
+    """
+    构造Discord embed
+
+    Args:
+        title: 标题
+        description: 正文（超长时截断）
+        color: 侧边颜色
+        fields: (名称, 值) 列表，按行内字段显示（最多25个）
+        footer: 页脚（如策略名称）
+    """
+    embed: Dict[str, Any] = {
+        "title": title,
+        "color": color,
+        "timestamp": datetime.now(timezone.utc).isoformat(timespec="seconds"),
+    }
+    if description:
+        embed["description"] = description[:_MAX_DESCRIPTION]
+    if fields:
+        embed["fields"] = [{"name": str(name), "value": str(value), "inline": True}
+                           for name, value in fields[:_MAX_FIELDS]]
+    if footer:
+        embed["footer"] = {"text": footer}
+    return embed
+
+
+def _format_value(value: Any) -> str:
+    if isinstance(value, float):
+        return f"{value:.4f}"
+    return str(value)
+
+
+class DiscordNotifier:
+    """Discord Webhook 发送"""
+
+    def __init__(self, webhook_url: str = "", notify_fills: bool = False,
+                 throttle: Optional[NotificationThrottle] = None):
+        """
+        Args:
+            webhook_url: Discord Webhook地址（空为不发送）
+            notify_fills: 是否发送成交通知
+            throttle: 告警和成交卡片的通知限流（None为不限流）
+        """
+        self.webhook_url = webhook_url
+        self.notify_fills = notify_fills and bool(webhook_url)
+        self.throttle = throttle
+        self.stats = {"sent": 0, "errors": 0, "rate_limited": 0}
+        self._tasks: Set[asyncio.Task] = set()
+
+    @classmethod
+    def from_config(cls, config, throttle: Optional[NotificationThrottle] = None) -> "DiscordNotifier":
+        return cls(config.discord_webhook_url, config.discord_notify_fills, throttle)
+
+    @property
+    def enabled(self) -> bool:
+        return bool(self.webhook_url)
+
+    def alert(self, strategy: str, alert: str, text: str, critical: bool = False):
+        """发送告警卡片（后台，严重告警不受限流）"""
+        self._send_background(build_embed(f"告警: {alert}", text, COLOR_ALERT, footer=strategy), critical)
+
+    def fill(self, strategy: str, symbol: str, action: str, direction: str, size: float, price: float,
+             pnl: Optional[float] = None):
+        """
+        发送成交卡片（后台，未启用成交通知时忽略）
+
+        Args:
+            strategy: 策略名称
+            symbol: 交易对
+            action: 开仓/加仓/部分平仓/平仓
+            direction: 持仓方向（LONG/SHORT）
+            size: 成交数量
+            price: 成交价格
+            pnl: 平仓盈亏（开仓时为None）
+        """
+        if not self.notify_fills:
+            return
+        fields: List[Tuple[str, Any]] = [("方向", direction), ("数量", size), ("价格", f"{price:.4f}")]
+        if pnl is None:
+            color = COLOR_INFO
+        else:
+            color = COLOR_PROFIT if pnl >= 0 else COLOR_LOSS
+            fields.append(("盈亏 (USDT)", f"{pnl:.4f}"))
+        self._send_background(build_embed(f"{symbol} {action}", "", color, fields, footer=strategy))
+
+    async def report(self, report_type: str, strategy: str, text: str, values: Dict[str, float]):
+        """发送报告卡片（等待发送完成，失败时抛出异常）"""
+        fields = [(key, _format_value(value)) for key, value in values.items()]
+        await self._post(build_embed(f"报告: {report_type}", text, COLOR_REPORT, fields, footer=strategy))
+
+    def _send_background(self, embed: Dict[str, Any], critical: bool = False):
+        if not self.webhook_url:
+            return
+        if self.throttle:
+            content = f"{embed.get('footer')}|{embed['title']}|{embed.get('description', '')}|{embed.get('fields')}"
+            if not self.throttle.allow("discord", content, critical):
+                logger.debug(f"[Discord] {embed['title']} 已限流，不发送")
+                return
+        try:
+            loop = asyncio.get_running_loop()
+        except RuntimeError:
+            return
+        task = loop.create_task(self._post_logged(embed))
+        self._tasks.add(task)
+        task.add_done_callback(self._tasks.discard)
+
+    async def _post_logged(self, embed: Dict[str, Any]):
+        try:
+            await self._post(embed)
+        except Exception as e:
+            logger.warning(f"[Discord] 发送失败: {e}")
+
+    async def _post(self, embed: Dict[str, Any]):
+        payload = {"embeds": [embed]}
+        try:
+            async with aiohttp.ClientSession(timeout=aiohttp.ClientTimeout(total=_DISCORD_TIMEOUT)) as session:
+                for attempt in range(2):
+                    async with session.post(self.webhook_url, json=payload) as response:
+                        if response.status == 429 and attempt == 0:
+                            self.stats["rate_limited"] += 1
+                            body = await response.json(content_type=None)
+                            await asyncio.sleep(float(body.get("retry_after", 1.0)))
+                            continue
+                        if response.status >= 400:
+                            raise ValueError(f"HTTP {response.status}")
+                        break
+            self.stats["sent"] += 1
+        except Exception:
+            self.stats["errors"] += 1
+            raise
+
+    def get_stats(self) -> Optional[Dict[str, int]]:
+        return dict(self.stats) if self.webhook_url else None
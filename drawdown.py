@@ -0,0 +1,100 @@
+"""
+回撤仓位调节模块
+
+按当前回撤（相对权益峰值）平滑缩小新开仓位：在配置的回撤档位之间线性插值，
+超过最后一档后保持最后一档的比例；权益回升、回撤收窄时仓位随之恢复。
+
+档位格式为 "回撤:仓位比例"，逗号分隔，例如 "0.05:0.5,0.10:0.25" 表示
+回撤5%时半仓、10%时四分之一仓，回撤0%时为满仓。
+"""
+
+from decimal import Decimal
+from typing import Dict, List, Optional, Any, Tuple
+from loguru import logger
+
+
+def parse_levels(spec: str) -> List[Tuple[float, float]]:
+    """
+    解析回撤档位
+
+    Args:
+        spec: 档位字符串（"回撤:仓位比例"，逗号分隔）
+
+    Returns:
+        List[Tuple[float, float]]: 按回撤升序排列的 (回撤, 仓位比例)
+
+    Raises:
+        ValueError: 格式无效、回撤不在(0, 1)内、比例不在[0, 1]内或比例随回撤增大
+    """
+    levels = []
+    for item in spec.split(","):
+        try:
+            drawdown, scale = item.split(":")
+            levels.append((float(drawdown), float(scale)))
+        except ValueError:
+            raise ValueError(f"回撤档位格式无效: {item}")
+    levels.sort()
+
+    previous = 1.0
+    for drawdown, scale in levels:
+        if not 0 < drawdown < 1 or not 0 <= scale <= 1:
+            raise ValueError(f"回撤档位无效: {drawdown}:{scale}")
+        if scale > previous:
+            raise ValueError("仓位比例不能随回撤加深而增大")
+        previous = scale
+    return levels
+
+
+class DrawdownThrottle:
+    """回撤仓位调节器"""
+
+    def __init__(self, levels: List[Tuple[float, float]]):
+        """
+        Args:
+            levels: 按回撤升序排列的 (回撤, 仓位比例)
+        """
+        self.points = [(0.0, 1.0), *levels]
+        self.peak_equity: Optional[Decimal] = None
+        self.equity = Decimal("0")
+        self.drawdown = 0.0
+        self.max_drawdown = 0.0
+        self._level = 0
+
+    def update(self, equity: Decimal):
+        """
+        更新权益
+
+        Args:
+            equity: 当前权益（余额+未实现盈亏）
+        """
+        if equity <= 0:
+            return
+        self.equity = equity
+        if self.peak_equity is None or equity > self.peak_equity:
+            self.peak_equity = equity
+        self.drawdown = float((self.peak_equity - equity) / self.peak_equity)
+        self.max_drawdown = max(self.max_drawdown, self.drawdown)
+
+        # 跨越档位时记录日志
+        level = sum(1 for drawdown, _ in self.points[1:] if self.drawdown >= drawdown)
+        if level != self._level:
+            action = "缩小" if level > self._level else "恢复"
+            logger.info(f"[回撤调节] 当前回撤 {self.drawdown:.2%}，仓位{action}至 x{self.scale():.2f}")
+            self._level = level
+
+    def scale(self) -> float:
+        """当前回撤对应的仓位比例"""
+        for (d0, s0), (d1, s1) in zip(self.points, self.points[1:]):
+            if self.drawdown < d1:
+                return s0 + (s1 - s0) * (self.drawdown - d0) / (d1 - d0)
+        return self.points[-1][1]
+
+    def get_summary(self) -> Dict[str, Any]:
+        """获取回撤调节状态"""
+        return {
+            "equity": float(self.equity),
+            "peak_equity": float(self.peak_equity or 0),
+            "drawdown": self.drawdown,
+            "max_drawdown": self.max_drawdown,
+            "scale": self.scale(),
+        }
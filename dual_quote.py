@@ -0,0 +1,210 @@
+"""
+双边挂单模块（Delta中性刷量）
+
+每个交易对同时在中间价两侧各挂一笔限价单（买单低于中间价、卖单高于中间价，相距 spread_bps），
+两侧都成交时赚取价差并完成一轮刷量，只在一侧先成交到另一侧成交之间持有临时敞口：
+- 某一侧成交后按当前中间价重新挂出该侧，另一侧继续挂单等待成交（成交后抵消敞口）
+- 中间价偏离挂单时的中间价超过 requote_bps 时撤单并按新中间价重新挂出
+- 净持仓达到 max_imbalance 笔挂单数量时不再挂出增加敞口的一侧
+- 同一侧连续成交使净持仓超过上限时（行情单边运行），撤销双边挂单并用只减仓市价单平掉净持仓后重新挂单
+- 价差倍数（spread_scale，延迟保护降级时放宽）变化时撤单并按新价差重新挂出
+策略不再按信号开仓；需要能收到订单状态和成交回报（模拟撮合引擎或私有WebSocket），只支持单向持仓模式。
+"""
+
+import asyncio
+from typing import Any, Dict, Optional
+
+from loguru import logger
+
+from edgex_types import Order, OrderSide, OrderType
+from order_tracker import OrderTracker, TrackedOrder
+
+
+class DualQuoteManager:
+    """双边挂单管理器"""
+
+    def __init__(self, client, order_tracker: OrderTracker, spread_bps: float = 10.0, requote_bps: float = 5.0,
+                 max_imbalance: float = 3.0):
+        """
+        Args:
+            client: EdgeX客户端
+            order_tracker: 订单跟踪器（挂单的状态回报由此分发）
+            spread_bps: 买卖挂单之间的距离（bps，相对中间价，每侧各一半）
+            requote_bps: 中间价偏离挂单时的中间价超过多少bps时重新挂单
+            max_imbalance: 净持仓上限（挂单数量的倍数），超过时市价平掉净持仓
+        """
+        self.client = client
+        self.order_tracker = order_tracker
+        self.spread_bps = spread_bps
+        self.requote_bps = requote_bps
+        self.max_imbalance = max_imbalance
+        self.spread_scale = 1.0
+        self.quotes: Dict[str, Dict[OrderSide, str]] = {}       # 交易对 -> 方向 -> 挂单ID
+        self.quote_mids: Dict[str, float] = {}                  # 交易对 -> 挂单时的中间价
+        self.quote_scales: Dict[str, float] = {}                # 交易对 -> 挂单时的价差倍数
+        self.rebalancing: Dict[str, str] = {}                   # 交易对 -> 再平衡市价单ID
+        self._by_order: Dict[str, str] = {}
+        self._lock = asyncio.Lock()
+        order_tracker.add_update_callback(self._on_order_update)
+
+        # 统计
+        self.stats = {"placed": 0, "requoted": 0, "buy_fills": 0, "sell_fills": 0, "rebalances": 0, "errors": 0}
+        self.streaks: Dict[str, Dict[str, Any]] = {}            # 交易对 -> 最近成交方向及连续次数
+
+    async def update(self, symbol: str, mid: float, net: float, size: float, leverage: int,
+                     round_price=None) -> None:
+        """
+        按当前中间价和净持仓维护交易对的双边挂单
+
+        Args:
+            symbol: 交易对
+            mid: 当前中间价
+            net: 当前净持仓（多头为正，合约数量）
+            size: 每笔挂单数量（已按数量步长取整）
+            leverage: 杠杆倍数
+            round_price: 价格取整函数（按最小价格变动单位）
+        """
+        async with self._lock:
+            rebalance_id = self.rebalancing.get(symbol)
+            if rebalance_id:
+                tracked = self.order_tracker.get(rebalance_id)
+                if tracked and not tracked.is_done:
+                    return
+                del self.rebalancing[symbol]
+                # 再平衡成交后持仓由成交回报更新，下一轮再挂单
+                return
+
+            limit = self.max_imbalance * size
+            if abs(net) > limit:
+                await self._rebalance(symbol, net, leverage, mid)
+                return
+
+            drifted = self.quote_mids.get(symbol) and abs(mid - self.quote_mids[symbol]) / mid * 10000 > self.requote_bps
+            rescaled = symbol in self.quote_scales and self.quote_scales[symbol] != self.spread_scale
+            if drifted or rescaled:
+                self.stats["requoted"] += 1
+                await self._cancel_symbol(symbol)
+
+            half = self.spread_bps * self.spread_scale / 20000
+            sides = self.quotes.setdefault(symbol, {})
+            for side in (OrderSide.BUY, OrderSide.SELL):
+                order_id = sides.get(side)
+                tracked = self.order_tracker.get(order_id) if order_id else None
+                if order_id and (tracked is None or not tracked.is_done):
+                    continue
+                sides.pop(side, None)
+                # 挂单成交后会使净持仓超过上限的一侧暂不挂出
+                after = net + size if side == OrderSide.BUY else net - size
+                if abs(after) > limit:
+                    continue
+                price = mid * (1 - half) if side == OrderSide.BUY else mid * (1 + half)
+                if round_price:
+                    price = round_price(price)
+                order_id = await self._place(symbol, side, size, price, leverage)
+                if order_id:
+                    sides[side] = order_id
+                    self.quote_mids[symbol] = mid
+                    self.quote_scales[symbol] = self.spread_scale
+
+    async def _place(self, symbol: str, side: OrderSide, size: float, price: float, leverage: int) -> Optional[str]:
+        order = Order(symbol=symbol, side=side, order_type=OrderType.LIMIT, quantity=size, price=price, leverage=leverage)
+        try:
+            result = await self.client.place_order(order)
+        except Exception as e:
+            self.stats["errors"] += 1
+            logger.error(f"[双边挂单] {symbol} {side.value} 挂单失败: {e}")
+            return None
+        order_id = str((result or {}).get("data", {}).get("orderId", ""))
+        if not order_id:
+            return None
+        # 挂单常驻盘口，不受订单存活时间限制
+        self.order_tracker.register(order_id, order, price).ttl_ms = None
+        self._by_order[order_id] = symbol
+        self.stats["placed"] += 1
+        logger.debug(f"[双边挂单] {symbol} 挂出 {side.value} {size} @ {price}（订单 {order_id}）")
+        return order_id
+
+    async def _rebalance(self, symbol: str, net: float, leverage: int, mid: float):
+        """撤销双边挂单并用只减仓市价单平掉净持仓"""
+        if not await self._cancel_symbol(symbol):
+            return
+        side = OrderSide.SELL if net > 0 else OrderSide.BUY
+        order = Order(symbol=symbol, side=side, order_type=OrderType.MARKET, quantity=abs(net), leverage=leverage,
+                      reduce_only=True)
+        logger.warning(f"[双边挂单] {symbol} 净持仓 {net:+} 超过上限，市价再平衡")
+        try:
+            result = await self.client.place_order(order)
+        except Exception as e:
+            self.stats["errors"] += 1
+            logger.error(f"[双边挂单] {symbol} 再平衡下单失败: {e}")
+            return
+        order_id = str((result or {}).get("data", {}).get("orderId", ""))
+        if order_id:
+            self.order_tracker.register(order_id, order, mid)
+            self.rebalancing[symbol] = order_id
+        self.stats["rebalances"] += 1
+
+    async def _cancel_symbol(self, symbol: str) -> bool:
+        """
+        撤销交易对的双边挂单
+
+        Returns:
+            bool: 挂单是否都已不在盘口
+        """
+        sides = self.quotes.get(symbol, {})
+        ok = True
+        for side, order_id in list(sides.items()):
+            tracked = self.order_tracker.get(order_id)
+            if tracked and tracked.is_done:
+                sides.pop(side, None)
+                continue
+            try:
+                await self.client.cancel_order(symbol, order_id)
+                sides.pop(side, None)
+            except Exception as e:
+                tracked = self.order_tracker.get(order_id)
+                if tracked and tracked.is_done:
+                    sides.pop(side, None)
+                    continue
+                ok = False
+                logger.error(f"[双边挂单] {symbol} 撤销挂单 {order_id} 失败: {e}")
+        if ok:
+            self.quote_mids.pop(symbol, None)
+            self.quote_scales.pop(symbol, None)
+        return ok
+
+    async def cancel(self, symbol: str) -> bool:
+        """撤销交易对的双边挂单（暂停交易、停止策略时调用）"""
+        async with self._lock:
+            return await self._cancel_symbol(symbol)
+
+    async def cancel_all(self):
+        """撤销全部双边挂单"""
+        for symbol in list(self.quotes):
+            await self.cancel(symbol)
+
+    def _on_order_update(self, tracked: TrackedOrder):
+        """挂单终结：统计成交方向和同一侧连续成交次数"""
+        symbol = self._by_order.get(tracked.order_id)
+        if not symbol or not tracked.is_done:
+            return
+        del self._by_order[tracked.order_id]
+        if tracked.filled <= 0:
+            return
+        side = tracked.side.value
+        self.stats["buy_fills" if tracked.side == OrderSide.BUY else "sell_fills"] += 1
+        streak = self.streaks.get(symbol)
+        if streak and streak["side"] == side:
+            streak["count"] += 1
+        else:
+            self.streaks[symbol] = {"side": side, "count": 1}
+
+    def get_stats(self) -> Dict[str, Any]:
+        """获取双边挂单统计"""
+        return {
+            **self.stats,
+            "spread_bps": self.spread_bps,
+            "spread_scale": self.spread_scale,
+            "active": {symbol: len(sides) for symbol, sides in self.quotes.items() if sides},
+            "streaks": {symbol: dict(streak) for symbol, streak in self.streaks.items()},
+        }
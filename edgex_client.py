@@ -4,9 +4,12 @@ EdgeX API客户端
 """
 
 import asyncio
+import re
 import sys
+import time
 import os
-from typing import List, Optional, Dict, Any
+from collections import deque
+from typing import Deque, List, Optional, Dict, Any
 from loguru import logger
 
 # 添加SDK路径
@@ -20,14 +23,78 @@ try:
         CreateOrderParams,
         CancelOrderParams,
         GetActiveOrderParams,
-        GetKLineParams
+        GetHistoryOrderParams,
+        GetKLineParams,
+        GetOrderBookDepthParams,
+        OrderFillTransactionParams,
+        RequestClock,
+        TransportError
     )
 except ImportError as e:
     logger.error(f"导入edgex_sdk失败: {e}")
     logger.error("请确保SDK已正确安装在sdk/edgex_sdk目录")
     raise
 
-from edgex_types import AccountInfo, PriceData, Order, OrderSide, OrderType, Position, TradeDirection
+from edgex_types import (
+    AccountInfo, FeeRates, Fill, FundingRate, MarketActivity, MarkPriceData, OrderBook, PriceData, Order, OrderSide, OrderType, Position, PositionMode, TradeDirection, position_key,
+    TRIGGER_ORDER_TYPES
+)
+from tracing import traced, annotate
+from contract_specs import ContractSpec, ContractSpecCache
+from activity_filter import parse_market_activity
+from funding_window import parse_funding_rate
+from collateral import CollateralModel, parse_asset_map, parse_coin_names
+from fees import parse_fee_setting
+from pre_trade import KillSwitch, KillSwitchCheck, LotTickCheck, OrderRejected, PreTradeValidator, RateLimitCheck
+from endpoints import EndpointPool, probe_rest
+from exchange_errors import ApiResponse, StrategyError, error_for, from_exception, parse_response, unwrap
+from latency import LATENCY_ORDER_TO_ACK, LatencyProfiler
+from latency_guard import LatencyGuard
+from matching_engine import MatchingEngine
+from order_gate import DuplicateOrderError, OrderGate
+from order_channel import PRIORITY_CANCEL, PRIORITY_ENTRY, PRIORITY_EXIT, OrderChannel
+from audit_log import (
+    AUDIT_CANCEL_ERROR, AUDIT_CANCEL_REQUEST, AUDIT_CANCEL_RESPONSE, AUDIT_ERROR, AUDIT_INTENT,
+    AUDIT_REQUEST, AUDIT_RESPONSE, AuditLog
+)
+
+
+_SERVER_ERROR = re.compile(r"status code: 5\d\d")
+
+
+def _is_endpoint_error(error: Exception) -> bool:
+    """是否为端点不可用类错误（网络/代理/超时/5xx），业务错误（如下单被拒）不计入"""
+    if isinstance(error, (TransportError, asyncio.TimeoutError, OSError)):
+        return True
+    return bool(_SERVER_ERROR.search(str(error)))
+
+
+class OrderStateUnknownError(Exception):
+    """下单结果未知：订单可能已被交易所接受，调用方不应立即重发"""
+
+
+class RequestTimeoutError(OrderStateUnknownError, TimeoutError):
+    """REST请求超过该类接口的超时预算（请求可能已被交易所处理，结果未知）"""
+    
+    def __init__(self, endpoint: str, timeout: float):
+        super().__init__(f"{endpoint} 请求超时（{timeout}秒）")
+        self.endpoint = endpoint
+        self.timeout = timeout
+
+
+class UnconfirmedOrderError(OrderStateUnknownError):
+    """下单响应为成功但缺少订单ID（订单可能已创建，无法按ID跟踪）"""
+
+
+# 各类REST接口的超时预算：下单/撤单最短，查询次之，历史数据和元数据最长
+TIMEOUT_ORDER = "order"
+TIMEOUT_QUERY = "query"
+TIMEOUT_HISTORY = "history"
+
+# 历史查询的分页大小和最大页数，模拟盘保留的成交条数
+HISTORY_PAGE_SIZE = 100
+HISTORY_MAX_PAGES = 50
+PAPER_FILL_HISTORY = 10000
 
 
 class EdgeXClient:
@@ -35,6 +102,10 @@ class EdgeXClient:
     
     # 合约ID映射缓存（类级别，所有实例共享）
     _contract_id_cache: Dict[str, str] = {}
+    # 合约ID到最小价格变动单位的缓存
+    _tick_size_cache: Dict[str, float] = {}
+    # 抵押资产ID到名称的缓存（元数据 coinList）
+    _coin_name_cache: Dict[str, str] = {}
     _cache_initialized: bool = False
     
     def __init__(self, config):
@@ -46,13 +117,201 @@ class EdgeXClient:
         """
         self.config = config
         self.sdk_client: Optional[EdgeXSDKClient] = None
+        
+        # 模拟盘模式下订单由本地撮合引擎处理，行情仍来自交易所公共接口
+        self.simulator: Optional[MatchingEngine] = None
+        if getattr(config, "paper_trading", False):
+            self.simulator = MatchingEngine()
+            logger.info("模拟盘模式已启用，订单将由本地撮合引擎撮合")
+        
+        # 模拟盘的成交历史（供 get_user_trades 查询）
+        self._paper_fills: Deque[Fill] = deque(maxlen=PAPER_FILL_HISTORY)
+        if self.simulator:
+            self.simulator.add_fill_callback(self._paper_fills.append)
+        
+        # 出站订单闸门：抑制重复订单，限制下单频率
+        self.gate = OrderGate(
+            duplicate_window_ms=getattr(config, "duplicate_order_window_ms", 0),
+            max_orders_per_second=getattr(config, "max_orders_per_second", 0)
+        )
+        
+        # 订单通道：下单/撤单按优先级经专用任务发送（撤单 > 平仓 > 开仓）
+        self.channel = OrderChannel(self.gate)
+        
+        # 下单前检查链（依赖账户状态的检查由策略追加）
+        self.kill_switch = KillSwitch(getattr(config, "kill_switch_file", ""))
+        self.pre_trade = PreTradeValidator([
+            KillSwitchCheck(self.kill_switch),
+            LotTickCheck(self.get_contract_spec),
+            RateLimitCheck(lambda: self.channel.queued(PRIORITY_ENTRY), getattr(config, "max_queued_entry_orders", 0)),
+        ])
+        
+        # 多币种抵押资产：各资产按汇率折算为报告币种后汇总为余额和可用保证金
+        self.collateral = CollateralModel(
+            getattr(config, "reporting_currency", "USDT"),
+            parse_asset_map(getattr(config, "collateral_rates", "")),
+            parse_asset_map(getattr(config, "collateral_haircuts", "")),
+            coin_names=EdgeXClient._coin_name_cache
+        )
+        
+        # 交易链路延迟直方图（可选）
+        self.latency: Optional[LatencyProfiler] = LatencyProfiler.create(config)
+        # 延迟保护：记录每次REST请求的往返时间（WebSocket行情延迟由策略记录）
+        self.latency_guard: Optional[LatencyGuard] = LatencyGuard(
+            percentile=getattr(config, "latency_guard_percentile", 99.0),
+            rest_limit_ms=getattr(config, "latency_guard_rest_ms", 500.0),
+            ws_limit_ms=getattr(config, "latency_guard_ws_ms", 1000.0),
+            window=getattr(config, "latency_guard_window", 200),
+            cooldown_seconds=getattr(config, "latency_guard_cooldown_seconds", 30.0),
+            quote_widen=getattr(config, "latency_guard_quote_widen", 2.0)
+        ) if getattr(config, "latency_guard_enabled", False) else None
+        
+        # 订单审计日志：记录下单意图、请求、响应（状态变化由订单跟踪器写入）
+        self.audit: Optional[AuditLog] = None
+        if getattr(config, "audit_log_enabled", False):
+            self.audit = AuditLog.open(config.audit_log_path)
+        
+        # 模拟盘的杠杆设置（交易对 -> 杠杆倍数）
+        self._paper_leverage: Dict[str, int] = {}
+        
+        # 合约规格：先读取本地缓存，元数据拉取成功后覆盖
+        self.contract_specs = ContractSpecCache(getattr(config, "contract_spec_cache_path", ""))
+        if self.contract_specs.load():
+            self._index_contracts(self.contract_specs.specs.values())
+        
+        # REST请求超时预算（秒）和各接口的超时次数
+        self.timeouts = {
+            TIMEOUT_ORDER: getattr(config, "http_order_timeout", 5.0),
+            TIMEOUT_QUERY: getattr(config, "http_query_timeout", 10.0),
+            TIMEOUT_HISTORY: getattr(config, "http_history_timeout", 30.0),
+        }
+        self.timeout_counts: Dict[str, int] = {}
+        self.rejection_counts: Dict[str, int] = {}   # 交易所拒绝请求的错误类型 -> 次数
+        
+        # 多个REST地址：按延迟选择，连续出错时切换
+        rest_endpoints = getattr(config, "rest_endpoints", [])
+        self.endpoints: Optional[EndpointPool] = EndpointPool(
+            "REST", rest_endpoints,
+            failover_errors=getattr(config, "endpoint_failover_errors", 3),
+            cooldown_seconds=getattr(config, "endpoint_cooldown_seconds", 60.0)
+        ) if rest_endpoints else None
+        
+        # 签名时间戳来源（REST和私有WebSocket共用，按服务器时间校正）
+        self.clock = RequestClock(recv_window_ms=getattr(config, "signature_recv_window_ms", 5000))
+        
         self._initialize_sdk()
     
+    def _audit(self, event: str, **data: Any):
+        if self.audit:
+            self.audit.record(event, paper=self.is_paper, **data)
+    
+    async def _request(self, budget: str, endpoint: str, awaitable):
+        """
+        按接口类别的超时预算执行REST请求
+        
+        Args:
+            budget: 超时预算类别（TIMEOUT_ORDER/TIMEOUT_QUERY/TIMEOUT_HISTORY）
+            endpoint: 接口名称（日志和统计用）
+            awaitable: SDK请求
+            
+        Raises:
+            RequestTimeoutError: 超过超时预算
+        """
+        timeout = self.timeouts[budget]
+        started = time.perf_counter()
+        try:
+            result = await asyncio.wait_for(awaitable, timeout=timeout)
+        except asyncio.TimeoutError:
+            if self.latency_guard:
+                self.latency_guard.record_rest(timeout * 1000)
+            self.timeout_counts[endpoint] = self.timeout_counts.get(endpoint, 0) + 1
+            self._endpoint_error()
+            raise RequestTimeoutError(endpoint, timeout)
+        except Exception as e:
+            if _is_endpoint_error(e):
+                self._endpoint_error()
+                raise
+            if self.endpoints:
+                self.endpoints.record_success()
+            rejection = from_exception(endpoint, e)
+            if rejection is None:
+                raise
+            self._record_rejection(rejection)
+            raise rejection from e
+        if self.latency_guard:
+            self.latency_guard.record_rest((time.perf_counter() - started) * 1000)
+        if self.endpoints:
+            self.endpoints.record_success()
+        return result
+    
+    async def _api(self, budget: str, endpoint: str, awaitable) -> ApiResponse:
+        """
+        执行REST请求并解析标准响应
+        
+        Raises:
+            StrategyError: 交易所返回错误（包括HTTP 200但 code 不是 SUCCESS 的响应）
+        """
+        return self._check_response(endpoint, await self._request(budget, endpoint, awaitable))
+    
+    def _check_response(self, endpoint: str, response: Any) -> ApiResponse:
+        """解析标准响应，code 不是 SUCCESS 时记录并抛出对应类型的 StrategyError"""
+        try:
+            return unwrap(endpoint, response)
+        except StrategyError as e:
+            self._record_rejection(e)
+            raise
+    
+    def _record_rejection(self, error: StrategyError):
+        self.rejection_counts[error.kind] = self.rejection_counts.get(error.kind, 0) + 1
+    
+    def _endpoint_error(self):
+        """当前REST端点出错一次，连续出错达到阈值时切换端点"""
+        if self.endpoints and self.endpoints.record_error():
+            self._use_endpoint(self.endpoints.current)
+    
+    def _use_endpoint(self, base_url: str):
+        """后续请求改用新的REST地址（连接池中旧地址的连接会自然过期）"""
+        if self.sdk_client:
+            self.sdk_client.async_client.base_url = base_url
+    
+    async def probe_endpoints(self):
+        """探测所有REST端点的延迟，必要时切换到更快的端点"""
+        if not self.endpoints or not self.sdk_client:
+            return
+        async_client = self.sdk_client.async_client
+        await async_client._ensure_session()
+        urls = self.endpoints.urls
+        latencies = await asyncio.gather(*(probe_rest(async_client.session, url, async_client.proxy) for url in urls))
+        for url, latency in zip(urls, latencies):
+            self.endpoints.record_latency(url, latency)
+        switched = self.endpoints.select()
+        if switched:
+            self._use_endpoint(switched)
+    
+    async def sync_clock(self):
+        """测量本地时钟与交易所服务器时间的偏差，超过签名时间窗口一半时校正签名时间戳"""
+        try:
+            offset = await self._request(TIMEOUT_QUERY, "get_server_time", self.sdk_client.async_client.sync_clock())
+        except Exception as e:
+            logger.warning(f"[签名时钟] 获取服务器时间失败: {e}")
+            return
+        if self.clock.offset_ms:
+            logger.warning(f"[签名时钟] 本地时钟与服务器时间相差 {offset}ms，签名时间戳已按服务器时间校正")
+        else:
+            logger.info(f"[签名时钟] 本地时钟与服务器时间相差 {offset}ms，在签名时间窗口 {self.clock.recv_window_ms}ms 以内")
+    
+    @property
+    def is_paper(self) -> bool:
+        """是否为模拟盘模式"""
+        return self.simulator is not None
+    
     def _initialize_sdk(self):
         """初始化SDK客户端"""
         try:
             # 确定base_url
-            if self.config.testnet:
+            if self.endpoints:
+                base_url = self.endpoints.current
+            elif self.config.testnet:
                 base_url = "https://testnet.edgex.exchange"
             else:
                 base_url = "https://pro.edgex.exchange"
@@ -63,80 +322,113 @@ class EdgeXClient:
             self.sdk_client = EdgeXSDKClient(
                 base_url=base_url,
                 account_id=int(self.config.account_id) if self.config.account_id else 0,
-                stark_private_key=self.config.stark_private_key or ""
+                stark_private_key=self.config.stark_private_key or "",
+                timeout=self.config.http_timeout,
+                connector_limit=self.config.http_pool_size,
+                limit_per_host=self.config.http_pool_per_host,
+                keepalive_timeout=self.config.http_keepalive_seconds,
+                connect_timeout=self.config.http_connect_timeout,
+                proxy=self.config.proxy_url or None,
+                order_connector_limit=self.config.http_order_pool_size,
+                clock=self.clock,
+                timestamp_retries=getattr(self.config, "signature_timestamp_retries", 1)
             )
             
             logger.info("EdgeX SDK初始化成功")
             
-            # 异步初始化合约ID缓存（不等待完成）
+            # 异步初始化合约ID缓存和校准签名时钟（不等待完成）
             asyncio.create_task(self._init_contract_cache())
+            if not self.is_paper:
+                asyncio.create_task(self.sync_clock())
             
         except Exception as e:
             logger.error(f"EdgeX SDK初始化失败: {e}")
             raise
     
+    @traced("edgex.rest.get_account_info")
     async def get_account_info(self) -> AccountInfo:
         """获取账户信息"""
+        if self.is_paper:
+            return AccountInfo(
+                balance=self.config.paper_initial_balance,
+                available_balance=self.config.paper_initial_balance,
+                positions={},
+                collaterals=self.collateral.parse({
+                    "totalEquity": self.config.paper_initial_balance,
+                    "availableBalance": self.config.paper_initial_balance
+                }),
+                reporting_currency=self.collateral.reporting_currency
+            )
+        
         try:
             # 获取账户资产
-            asset_response = await self.sdk_client.account.get_account_asset()
-            
-            if not asset_response or asset_response.get("code") != "SUCCESS":
-                raise ValueError(f"获取账户资产失败: {asset_response}")
-            
-            asset_data = asset_response.get("data", {})
+            asset_response = await self._api(
+                TIMEOUT_QUERY, "get_account_asset", self.sdk_client.account.get_account_asset()
+            )
+            asset_data = asset_response.data or {}
             
-            # 解析余额
-            balance = float(asset_data.get("totalEquity", 0))
-            available_balance = float(asset_data.get("availableBalance", 0))
+            # 解析各抵押资产并按报告币种汇总余额和可用保证金
+            await self._init_contract_cache()
+            collaterals = self.collateral.parse(asset_data)
+            balance, available_balance = self.collateral.totals(collaterals)
             
             # 获取持仓信息
             positions = {}
-            positions_response = await self.sdk_client.account.get_account_positions()
+            # 持仓查询失败时抛出异常（不能当作没有持仓）
+            positions_response = await self._api(
+                TIMEOUT_QUERY, "get_account_positions", self.sdk_client.account.get_account_positions()
+            )
+            
+            position_list = (positions_response.data or {}).get("positionList", [])
             
-            if positions_response and positions_response.get("code") == "SUCCESS":
-                position_list = positions_response.get("data", {}).get("positionList", [])
+            # 持仓按交易对名称索引（与策略和成交回报一致），需要合约ID映射
+            await self._init_contract_cache()
+            
+            for pos_data in position_list:
+                contract_id = pos_data.get("contractId", "")
+                if not contract_id:
+                    continue
                 
-                for pos_data in position_list:
-                    contract_id = pos_data.get("contractId", "")
-                    if not contract_id:
-                        continue
-                    
-                    # 解析方向
-                    position_side = pos_data.get("positionSide", "LONG")
-                    if position_side == "LONG":
-                        direction = TradeDirection.LONG
-                    elif position_side == "SHORT":
-                        direction = TradeDirection.SHORT
-                    else:
-                        continue
-                    
-                    # 解析持仓大小（可能是负数表示方向）
-                    size = abs(float(pos_data.get("positionSize", 0)))
-                    
-                    if size > 0:  # 只记录有持仓的
-                        position = Position(
-                            symbol=contract_id,
-                            direction=direction,
-                            size=size,
-                            entry_price=float(pos_data.get("avgEntryPrice", 0)),
-                            stop_loss=0.0,  # SDK不直接提供，需要从订单中获取
-                            take_profit=0.0,  # SDK不直接提供，需要从订单中获取
-                            leverage=int(pos_data.get("leverage", 1)),
-                            opening_time=int(pos_data.get("createdTime", 0))
-                        )
-                        positions[contract_id] = position
+                # 解析方向
+                position_side = pos_data.get("positionSide", "LONG")
+                if position_side == "LONG":
+                    direction = TradeDirection.LONG
+                elif position_side == "SHORT":
+                    direction = TradeDirection.SHORT
+                else:
+                    continue
+                
+                # 解析持仓大小（可能是负数表示方向）
+                size = abs(float(pos_data.get("positionSize", 0)))
+                
+                if size > 0:  # 只记录有持仓的
+                    symbol = self.get_symbol_by_contract_id(contract_id)
+                    position = Position(
+                        symbol=symbol,
+                        direction=direction,
+                        size=size,
+                        entry_price=float(pos_data.get("avgEntryPrice", 0)),
+                        stop_loss=0.0,  # SDK不直接提供，需要从订单中获取
+                        take_profit=0.0,  # SDK不直接提供，需要从订单中获取
+                        leverage=int(pos_data.get("leverage", 1)),
+                        opening_time=int(pos_data.get("createdTime", 0)) // 1000
+                    )
+                    mode = PositionMode(getattr(self.config, "position_mode", "one_way"))
+                    positions[position_key(symbol, direction, mode)] = position
             
             return AccountInfo(
                 balance=balance,
                 available_balance=available_balance,
-                positions=positions
+                positions=positions,
+                collaterals=collaterals,
+                reporting_currency=self.collateral.reporting_currency
             )
             
         except Exception as e:
             logger.error(f"获取账户信息失败: {e}")
             raise
     
+    @traced("edgex.rest.get_klines")
     async def get_klines(self, symbol: str, interval: str, limit: int) -> List[PriceData]:
         """
         获取K线数据
@@ -178,12 +470,8 @@ class EdgeXClient:
             )
             
             # 获取K线数据
-            response = await self.sdk_client.quote.get_k_line(params)
-            
-            if not response or response.get("code") != "SUCCESS":
-                raise ValueError(f"获取K线数据失败: {response}")
-            
-            kline_list = response.get("data", {}).get("dataList", [])
+            response = await self._api(TIMEOUT_HISTORY, "get_k_line", self.sdk_client.quote.get_k_line(params))
+            kline_list = (response.data or {}).get("dataList", [])
             
             # 转换为PriceData格式
             price_data_list = []
@@ -204,16 +492,61 @@ class EdgeXClient:
             logger.error(f"获取K线数据失败: {e}")
             raise
     
-    async def place_order(self, order: Order) -> Dict[str, Any]:
+    @traced("edgex.rest.place_order")
+    async def place_order(self, order: Order, allow_duplicate: bool = False, source: str = "") -> Dict[str, Any]:
         """
         下单
         
         Args:
             order: 订单对象
+            allow_duplicate: 是否允许短时间内重复发送相同订单（仍受频率限制）
+            source: 下单来源（如交易控制台的手动操作为 manual），写入审计日志
             
         Returns:
             Dict[str, Any]: 下单响应
+            
+        Raises:
+            ValueError: 条件单缺少触发价，或止损限价单缺少限价
+            OrderRejected: 未通过下单前检查（数量和价格按合约步长取整后直接写回订单）
+            DuplicateOrderError: 重复订单被抑制
         """
+        annotate(symbol=order.symbol, side=order.side.value, quantity=order.quantity)
+        
+        if order.order_type in TRIGGER_ORDER_TYPES and not order.trigger_price:
+            raise ValueError(f"{order.order_type.value} 订单需要触发价")
+        if order.order_type == OrderType.STOP_LIMIT and not order.price:
+            raise ValueError("STOP_LIMIT 订单需要限价")
+        
+        intent_id = AuditLog.new_intent_id()
+        self._audit(AUDIT_INTENT, intent_id=intent_id, order=order, **({"source": source} if source else {}))
+        
+        try:
+            self.pre_trade.validate(order)
+            self.gate.check(order, allow_duplicate)
+        except OrderRejected as e:
+            self._audit(AUDIT_ERROR, intent_id=intent_id, error=str(e))
+            raise
+        except DuplicateOrderError as e:
+            self._audit(AUDIT_ERROR, intent_id=intent_id, error=str(e))
+            self.pre_trade.record_rejection("duplicate", str(e))
+            raise
+        
+        # 平仓（只减仓）订单优先于开仓订单发送
+        priority = PRIORITY_EXIT if order.reduce_only else PRIORITY_ENTRY
+        return await self.channel.submit(priority, lambda: self._send_order(order, intent_id))
+    
+    async def _send_order(self, order: Order, intent_id: str) -> Dict[str, Any]:
+        """发送订单（由订单通道调用）"""
+        if self.is_paper:
+            sim_order = self.simulator.submit(order)
+            response = {
+                "code": "SUCCESS",
+                "data": {"orderId": sim_order.order_id, "status": sim_order.status}
+            }
+            self._audit(AUDIT_RESPONSE, intent_id=intent_id, response=response)
+            self._validate_order_response(parse_response(response))
+            return response
+        
         try:
             # 转换OrderSide
             if order.side == OrderSide.BUY:
@@ -221,53 +554,107 @@ class EdgeXClient:
             else:
                 sdk_side = SDKOrderSide.SELL
             
-            # 转换OrderType
-            if order.order_type == OrderType.MARKET:
-                sdk_type = SDKOrderType.MARKET
-            else:
-                sdk_type = SDKOrderType.LIMIT
+            # 转换OrderType（条件单的签名金额按触发价计算）
+            sdk_type = SDKOrderType(order.order_type.value)
+            sign_price = order.price or order.trigger_price
+            
+            # 交易对名称需转换为合约ID
+            contract_id = order.symbol
+            if not contract_id.isdigit():
+                contract_id = await self.get_contract_id_by_symbol(order.symbol)
+                if not contract_id:
+                    raise ValueError(f"无法找到交易对 {order.symbol} 的合约ID")
             
             # 创建订单参数
             params = CreateOrderParams(
-                contract_id=order.symbol,
-                price=str(order.price) if order.price else "0",
+                contract_id=contract_id,
+                price=str(sign_price) if sign_price else "0",
                 size=str(order.quantity),
                 type=sdk_type,
                 side=sdk_side.value,
-                reduce_only=False
+                reduce_only=order.reduce_only,
+                position_side=order.position_side.value if order.position_side else None,
+                trigger_price=str(order.trigger_price) if order.trigger_price else None,
+                trigger_price_type=order.trigger_price_type.value if order.trigger_price else None
             )
             
             # 下单
-            response = await self.sdk_client.create_order(params)
-            
-            if not response or response.get("code") != "SUCCESS":
-                error_msg = response.get("errorParam", {}).get("message", "未知错误")
-                raise ValueError(f"下单失败: {error_msg}")
+            self._audit(AUDIT_REQUEST, intent_id=intent_id, params=params)
+            sent = time.perf_counter()
+            raw = await self._request(TIMEOUT_ORDER, "create_order", self.sdk_client.create_order(params))
+            if self.latency:
+                self.latency.record_since(LATENCY_ORDER_TO_ACK, sent)
+            self._audit(AUDIT_RESPONSE, intent_id=intent_id, response=raw)
+            parsed = self._check_response("create_order", raw)
+            self._validate_order_response(parsed)
+            response = parsed.raw
             
-            logger.info(f"订单提交成功: {order.symbol} {order.side.value} {order.quantity}")
+            logger.info(
+                f"订单提交成功: {order.symbol} {order.side.value} {order.order_type.value} {order.quantity}"
+                + (f" @ {order.price}" if order.order_type in (OrderType.LIMIT, OrderType.STOP_LIMIT) else "")
+                + (f" 触发价 {order.trigger_price} ({order.trigger_price_type.value})" if order.trigger_price else "")
+            )
             return response
             
+        except OrderStateUnknownError as e:
+            # 交易所可能已接受订单，调用方不应立即重发
+            self._audit(AUDIT_ERROR, intent_id=intent_id, error=str(e), timeout=isinstance(e, RequestTimeoutError))
+            logger.warning(f"下单结果未知: {order.symbol} {order.side.value} {order.quantity} - {e}")
+            raise
         except Exception as e:
+            self._audit(AUDIT_ERROR, intent_id=intent_id, error=str(e))
             logger.error(f"下单失败: {e}")
             raise
     
-    async def set_leverage(self, symbol: str, leverage: int) -> Dict[str, Any]:
+    def _validate_order_response(self, response: ApiResponse):
         """
-        设置杠杆
+        检查下单响应：必须包含订单ID；带订单状态时不能是已拒绝（缺少状态的由订单确认检查处理）
         
-        注意: EdgeX的杠杆设置可能需要通过其他API或在下单时指定
-        这里提供一个占位实现
+        Raises:
+            UnconfirmedOrderError: 缺少订单ID
+            StrategyError: 订单状态为已拒绝
+        """
+        data = response.data if isinstance(response.data, dict) else {}
+        if not str(data.get("orderId") or ""):
+            raise UnconfirmedOrderError(f"create_order 响应缺少订单ID: {str(response.raw)[:200]}")
+        if str(data.get("status")) == "REJECTED":
+            error = error_for("create_order", "ORDER_REJECTED", str(data.get("cancelReason") or ""))
+            self._record_rejection(error)
+            raise error
+    
+    async def _resolve_contract_id(self, symbol: str) -> str:
+        """交易对名称转换为合约ID"""
+        if symbol.isdigit():
+            return symbol
+        contract_id = await self.get_contract_id_by_symbol(symbol)
+        if not contract_id:
+            raise ValueError(f"无法找到交易对 {symbol} 的合约ID")
+        return contract_id
+    
+    @traced("edgex.rest.set_leverage")
+    async def set_leverage(self, symbol: str, leverage: int) -> Dict[str, Any]:
+        """
+        设置杠杆（账户在该合约上的杠杆倍数）
         
         Args:
-            symbol: 合约ID
+            symbol: 交易对名称或合约ID
             leverage: 杠杆倍数
             
         Returns:
             Dict[str, Any]: 设置响应
         """
+        if self.is_paper:
+            self._paper_leverage[symbol] = leverage
+            logger.info(f"设置模拟杠杆: {symbol} {leverage}x")
+            return {"code": "SUCCESS", "data": {"symbol": symbol, "leverage": leverage}}
+        
         try:
+            contract_id = await self._resolve_contract_id(symbol)
             logger.info(f"设置杠杆: {symbol} {leverage}x")
-            logger.warning("EdgeX SDK暂不支持单独设置杠杆，杠杆在账户级别配置")
+            await self._api(
+                TIMEOUT_QUERY, "update_leverage_setting",
+                self.sdk_client.account.update_leverage_setting(contract_id, str(leverage))
+            )
             
             return {
                 "code": "SUCCESS",
@@ -281,34 +668,103 @@ class EdgeXClient:
             logger.error(f"设置杠杆失败: {e}")
             raise
     
-    async def cancel_order(self, symbol: str, order_id: str) -> Dict[str, Any]:
+    @traced("edgex.rest.get_leverage")
+    async def get_leverage(self, symbol: str) -> Optional[int]:
+        """
+        查询账户在该合约上生效的杠杆倍数
+        
+        Args:
+            symbol: 交易对名称或合约ID
+            
+        Returns:
+            Optional[int]: 杠杆倍数；交易所未返回该合约设置时为None
+        """
+        if self.is_paper:
+            return self._paper_leverage.get(symbol)
+        
+        contract_id = await self._resolve_contract_id(symbol)
+        response = await self._api(TIMEOUT_QUERY, "get_account_by_id", self.sdk_client.account.get_account_by_id())
+        
+        # 未单独设置的合约使用账户默认设置
+        data = response.data or {}
+        setting = data.get("contractIdToTradeSetting", {}).get(contract_id) or data.get("defaultTradeSetting") or {}
+        leverage = setting.get("maxLeverage")
+        return int(float(leverage)) if leverage else None
+    
+    async def get_fee_rates(self, symbols: List[str]) -> Dict[str, FeeRates]:
+        """
+        查询账户在各合约上的手续费率（见 fees.py）
+        
+        Args:
+            symbols: 交易对名称列表
+            
+        Returns:
+            Dict[str, FeeRates]: 交易对 -> 手续费率；账户未设置且合约规格未加载的交易对不包含在内
+        """
+        settings: Dict[str, Any] = {}
+        default: Dict[str, Any] = {}
+        if not self.is_paper:
+            response = await self._api(TIMEOUT_QUERY, "get_account_by_id", self.sdk_client.account.get_account_by_id())
+            data = response.data or {}
+            settings = data.get("contractIdToTradeSetting") or {}
+            default = data.get("defaultTradeSetting") or {}
+        
+        rates = {}
+        for symbol in symbols:
+            spec = self.get_contract_spec(symbol)
+            contract_id = spec.contract_id if spec else await self._resolve_contract_id(symbol)
+            fees = parse_fee_setting(settings.get(contract_id)) or parse_fee_setting(default)
+            if fees:
+                rates[symbol] = FeeRates(taker=fees[0], maker=fees[1], source="account")
+            elif spec:
+                rates[symbol] = FeeRates(taker=spec.taker_fee_rate, maker=spec.maker_fee_rate, source="contract")
+        return rates
+    
+    @traced("edgex.rest.cancel_order")
+    async def cancel_order(self, symbol: str, order_id: str, source: str = "") -> Dict[str, Any]:
         """
         取消订单
         
         Args:
             symbol: 合约ID
             order_id: 订单ID
+            source: 撤单来源（写入审计日志）
             
         Returns:
             Dict[str, Any]: 取消响应
         """
+        self._audit(AUDIT_CANCEL_REQUEST, symbol=symbol, order_id=order_id, **({"source": source} if source else {}))
+        return await self.channel.submit(PRIORITY_CANCEL, lambda: self._send_cancel(symbol, order_id), throttled=False)
+    
+    async def _send_cancel(self, symbol: str, order_id: str) -> Dict[str, Any]:
+        """发送撤单请求（由订单通道调用）"""
+        if self.is_paper:
+            if not self.simulator.cancel(order_id):
+                self._audit(AUDIT_CANCEL_ERROR, symbol=symbol, order_id=order_id, error="模拟订单不存在或已完成")
+                raise ValueError(f"取消订单失败: 模拟订单 {order_id} 不存在或已完成")
+            logger.info(f"取消模拟订单成功: {symbol} {order_id}")
+            response = {"code": "SUCCESS", "data": {"orderId": order_id}}
+            self._audit(AUDIT_CANCEL_RESPONSE, symbol=symbol, order_id=order_id, response=response)
+            return response
+        
         try:
             params = CancelOrderParams(
                 order_id=order_id
             )
             
-            response = await self.sdk_client.cancel_order(params)
-            
-            if not response or response.get("code") != "SUCCESS":
-                raise ValueError(f"取消订单失败: {response}")
+            raw = await self._request(TIMEOUT_ORDER, "cancel_order", self.sdk_client.cancel_order(params))
+            self._audit(AUDIT_CANCEL_RESPONSE, symbol=symbol, order_id=order_id, response=raw)
+            response = self._check_response("cancel_order", raw).raw
             
             logger.info(f"取消订单成功: {symbol} {order_id}")
             return response
             
         except Exception as e:
+            self._audit(AUDIT_CANCEL_ERROR, symbol=symbol, order_id=order_id, error=str(e))
             logger.error(f"取消订单失败: {e}")
             raise
     
+    @traced("edgex.rest.get_open_orders")
     async def get_open_orders(self, symbol: Optional[str] = None) -> List[Dict[str, Any]]:
         """
         获取未成交订单
@@ -319,6 +775,9 @@ class EdgeXClient:
         Returns:
             List[Dict[str, Any]]: 订单列表
         """
+        if self.is_paper:
+            return [o.to_dict() for o in self.simulator.get_open_orders(symbol)]
+        
         try:
             params = GetActiveOrderParams(
                 size="100",
@@ -329,18 +788,197 @@ class EdgeXClient:
             if symbol:
                 params.filter_contract_id_list = [symbol]
             
-            response = await self.sdk_client.get_active_orders(params)
-            
-            if not response or response.get("code") != "SUCCESS":
-                raise ValueError(f"获取未成交订单失败: {response}")
-            
-            order_list = response.get("data", {}).get("dataList", [])
+            response = await self._api(TIMEOUT_QUERY, "get_active_orders", self.sdk_client.get_active_orders(params))
+            order_list = (response.data or {}).get("dataList", [])
             return order_list
             
         except Exception as e:
             logger.error(f"获取未成交订单失败: {e}")
             raise
     
+    async def _fetch_history_pages(self, endpoint: str, fetch, params) -> List[Dict[str, Any]]:
+        """按 nextPageOffsetData 逐页拉取历史记录（最多 HISTORY_MAX_PAGES 页）"""
+        records: List[Dict[str, Any]] = []
+        for _ in range(HISTORY_MAX_PAGES):
+            response = await self._api(TIMEOUT_HISTORY, endpoint, fetch(params))
+            data = response.data or {}
+            records.extend(data.get("dataList", []) or [])
+            next_offset = data.get("nextPageOffsetData", "")
+            if not next_offset:
+                return records
+            params.offset_data = next_offset
+        logger.warning(f"{endpoint} 超过 {HISTORY_MAX_PAGES} 页，只返回前 {len(records)} 条记录")
+        return records
+    
+    def _parse_fill_transaction(self, data: Dict[str, Any]) -> Optional[Fill]:
+        """解析历史成交记录为Fill（字段与私有频道成交回报一致）"""
+        try:
+            size = float(data.get("fillSize", 0))
+            if size <= 0:
+                return None
+            return Fill(
+                symbol=self.get_symbol_by_contract_id(str(data.get("contractId", ""))),
+                order_id=str(data.get("orderId", "")),
+                side=OrderSide(data.get("orderSide", data.get("side", "BUY"))),
+                price=float(data.get("fillPrice", 0)),
+                size=size,
+                fee=float(data.get("fillFee", 0)),
+                is_maker=data.get("direction") == "MAKER",
+                timestamp=int(data.get("matchTime", data.get("createdTime", 0)))
+            )
+        except Exception as e:
+            logger.error(f"解析历史成交记录失败: {e}")
+            return None
+    
+    @traced("edgex.rest.get_user_trades")
+    async def get_user_trades(self, symbol: Optional[str] = None, start_ms: int = 0,
+                              end_ms: int = 0) -> List[Fill]:
+        """
+        获取账户的历史成交（交易所记录），按成交时间升序
+        
+        Args:
+            symbol: 可选的交易对名称或合约ID筛选
+            start_ms: 起始时间（毫秒，含），0为不限
+            end_ms: 结束时间（毫秒，不含），0为不限
+            
+        Returns:
+            List[Fill]: 成交列表
+        """
+        if self.is_paper:
+            fills = [f for f in self._paper_fills
+                     if (not symbol or f.symbol == symbol)
+                     and f.timestamp >= start_ms and (not end_ms or f.timestamp < end_ms)]
+            return sorted(fills, key=lambda f: f.timestamp)
+        
+        try:
+            params = OrderFillTransactionParams(
+                size=str(HISTORY_PAGE_SIZE),
+                offset_data="",
+                filter_start_created_time_inclusive=start_ms,
+                filter_end_created_time_exclusive=end_ms
+            )
+            if symbol:
+                params.filter_contract_id_list = [await self._resolve_contract_id(symbol)]
+            
+            records = await self._fetch_history_pages(
+                "get_order_fill_transactions", self.sdk_client.get_order_fill_transactions, params)
+            fills = [fill for fill in map(self._parse_fill_transaction, records) if fill]
+            return sorted(fills, key=lambda f: f.timestamp)
+            
+        except Exception as e:
+            logger.error(f"获取历史成交失败: {e}")
+            raise
+    
+    @traced("edgex.rest.get_order_history")
+    async def get_order_history(self, symbol: Optional[str] = None, start_ms: int = 0,
+                                end_ms: int = 0) -> List[Dict[str, Any]]:
+        """
+        获取已终结（成交、撤销）的历史订单
+        
+        Args:
+            symbol: 可选的交易对名称或合约ID筛选
+            start_ms: 起始创建时间（毫秒，含），0为不限
+            end_ms: 结束创建时间（毫秒，不含），0为不限
+            
+        Returns:
+            List[Dict[str, Any]]: 订单列表（交易所订单字段）
+        """
+        if self.is_paper:
+            return [
+                o.to_dict() for o in self.simulator.orders.values()
+                if o.status in ("FILLED", "CANCELED")
+                and (not symbol or o.symbol == symbol)
+                and o.created_at >= start_ms and (not end_ms or o.created_at < end_ms)
+            ]
+        
+        try:
+            params = GetHistoryOrderParams(
+                size=str(HISTORY_PAGE_SIZE),
+                offset_data="",
+                filter_start_created_time_inclusive=start_ms,
+                filter_end_created_time_exclusive=end_ms
+            )
+            if symbol:
+                params.filter_contract_id_list = [await self._resolve_contract_id(symbol)]
+            
+            return await self._fetch_history_pages(
+                "get_history_orders", self.sdk_client.get_history_orders, params)
+            
+        except Exception as e:
+            logger.error(f"获取历史订单失败: {e}")
+            raise
+    
+    @traced("edgex.rest.get_orders_by_id")
+    async def get_orders_by_id(self, order_ids: List[str]) -> List[Dict[str, Any]]:
+        """
+        按订单ID查询订单（挂单或历史订单）
+        
+        Args:
+            order_ids: 订单ID列表
+            
+        Returns:
+            List[Dict[str, Any]]: 查到的订单（交易所订单字段），交易所不存在的订单不返回
+        """
+        if self.is_paper:
+            return [self.simulator.orders[i].to_dict() for i in order_ids if i in self.simulator.orders]
+        
+        try:
+            response = await self._api(TIMEOUT_QUERY, "get_orders_by_id", self.sdk_client.get_orders_by_id(order_ids))
+            data = response.data
+            return data if isinstance(data, list) else (data or {}).get("dataList", [])
+            
+        except Exception as e:
+            logger.error(f"按ID查询订单失败: {e}")
+            raise
+    
+    @traced("edgex.rest.get_order_book")
+    async def get_order_book(self, symbol: str, levels: int = 15) -> OrderBook:
+        """
+        通过REST接口获取订单簿快照（启动时在WebSocket深度快照到达前使用）
+        
+        Args:
+            symbol: 交易对名称
+            levels: 档位数（交易所支持 15 / 200）
+            
+        Returns:
+            OrderBook: 订单簿快照
+        """
+        try:
+            contract_id = await self.get_contract_id_by_symbol(symbol)
+            if not contract_id:
+                raise ValueError(f"无法找到交易对 {symbol} 的合约ID")
+            params = GetOrderBookDepthParams(contract_id=contract_id, limit=levels)
+            response = await self._api(
+                TIMEOUT_QUERY, "get_order_book_depth", self.sdk_client.quote.get_order_book_depth(params)
+            )
+            data = response.data or {}
+            depth = data[0] if isinstance(data, list) and data else data
+            if not depth:
+                raise ValueError("订单簿数据为空")
+            
+            def parse(levels_data) -> List[List[float]]:
+                parsed = []
+                for level in levels_data or []:
+                    if isinstance(level, dict):
+                        price, size = float(level.get("price", 0)), float(level.get("size", 0))
+                    else:
+                        price, size = float(level[0]), float(level[1])
+                    if size > 0:
+                        parsed.append([price, size])
+                return parsed
+            
+            return OrderBook(
+                symbol=symbol,
+                bids=sorted(parse(depth.get("bids")), reverse=True),
+                asks=sorted(parse(depth.get("asks"))),
+                timestamp=int(time.time() * 1000)
+            )
+            
+        except Exception as e:
+            logger.error(f"获取订单簿失败: {e}")
+            raise
+    
+    @traced("edgex.rest.get_ticker")
     async def get_ticker(self, contract_id: str) -> Dict[str, Any]:
         """
         获取ticker数据
@@ -352,13 +990,12 @@ class EdgeXClient:
             Dict[str, Any]: ticker数据
         """
         try:
-            response = await self.sdk_client.quote.get_24_hour_quote(contract_id)
-            
-            if not response or response.get("code") != "SUCCESS":
-                raise ValueError(f"获取ticker失败: {response}")
+            response = await self._api(
+                TIMEOUT_QUERY, "get_24_hour_quote", self.sdk_client.quote.get_24_hour_quote(contract_id)
+            )
             
             # 返回第一个ticker数据
-            ticker_list = response.get("data", [])
+            ticker_list = response.data or []
             if ticker_list and len(ticker_list) > 0:
                 return ticker_list[0]
             else:
@@ -368,28 +1005,93 @@ class EdgeXClient:
             logger.error(f"获取ticker失败: {e}")
             raise
     
+    async def get_mark_price(self, symbol: str) -> Optional[MarkPriceData]:
+        """
+        通过REST接口获取标记价格和指数价格（无WebSocket时轮询使用）
+        
+        Args:
+            symbol: 交易对名称或合约ID
+            
+        Returns:
+            Optional[MarkPriceData]: 标记价格；ticker未返回标记价格时为None
+        """
+        ticker = await self.get_ticker(await self._resolve_contract_id(symbol))
+        mark_price = float(ticker.get("oraclePrice") or 0)
+        if mark_price <= 0:
+            return None
+        return MarkPriceData(
+            timestamp=int(time.time() * 1000),
+            mark_price=mark_price,
+            index_price=float(ticker.get("indexPrice") or 0)
+        )
+    
+    async def get_market_activity(self, symbol: str) -> Optional[MarketActivity]:
+        """
+        通过REST接口获取持仓量与24小时成交量（WebSocket没有及时推送时使用）
+        
+        Args:
+            symbol: 交易对名称或合约ID
+            
+        Returns:
+            Optional[MarketActivity]: ticker不含持仓量和成交额时为None
+        """
+        ticker = await self.get_ticker(await self._resolve_contract_id(symbol))
+        return parse_market_activity(ticker)
+    
+    async def get_funding_rate(self, symbol: str) -> Optional[FundingRate]:
+        """
+        通过REST接口获取资金费率和下次结算时间（WebSocket没有及时推送时使用）
+        
+        Args:
+            symbol: 交易对名称或合约ID
+            
+        Returns:
+            Optional[FundingRate]: ticker不含资金费率时为None
+        """
+        ticker = await self.get_ticker(await self._resolve_contract_id(symbol))
+        return parse_funding_rate(ticker)
+    
+    @staticmethod
+    def _index_contracts(specs):
+        """合约规格写入合约ID映射和最小价格变动单位缓存"""
+        for spec in specs:
+            if spec.symbol and spec.contract_id:
+                EdgeXClient._contract_id_cache[spec.symbol] = spec.contract_id
+                # 同时添加反向映射
+                EdgeXClient._contract_id_cache[spec.contract_id] = spec.contract_id
+                if spec.tick_size > 0:
+                    EdgeXClient._tick_size_cache[spec.contract_id] = spec.tick_size
+    
     async def _init_contract_cache(self):
         """初始化合约ID缓存"""
         if EdgeXClient._cache_initialized:
             return
+        await self.refresh_contract_specs()
+    
+    async def refresh_contract_specs(self) -> bool:
+        """
+        拉取交易所元数据，更新合约ID映射和合约规格缓存（并写入缓存文件）
         
+        Returns:
+            bool: 是否拉取成功
+        """
         try:
-            metadata = await self.sdk_client.get_metadata()
+            try:
+                metadata = await self._api(TIMEOUT_HISTORY, "get_metadata", self.sdk_client.get_metadata())
+            except StrategyError as e:
+                logger.warning(f"获取元数据失败（{e}），合约ID缓存未初始化" if not EdgeXClient._cache_initialized
+                               else f"获取元数据失败（{e}），继续使用已缓存的合约规格")
+                return False
             
-            if not metadata or metadata.get("code") != "SUCCESS":
-                logger.warning("获取元数据失败，合约ID缓存未初始化")
-                return
-            
-            contracts = metadata.get("data", {}).get("contractList", [])
-            
-            for contract in contracts:
-                contract_name = contract.get("contractName")
-                contract_id = contract.get("contractId")
-                if contract_name and contract_id:
-                    EdgeXClient._contract_id_cache[contract_name] = contract_id
-                    # 同时添加反向映射
-                    EdgeXClient._contract_id_cache[contract_id] = contract_id
+            data = metadata.data or {}
+            contracts = data.get("contractList", [])
+            EdgeXClient._coin_name_cache.update(parse_coin_names(data.get("coinList", [])))
+            self.contract_specs.update(contracts)
+            self._index_contracts(self.contract_specs.specs.values())
+            self.contract_specs.save()
             
+            if EdgeXClient._cache_initialized:
+                return True
             EdgeXClient._cache_initialized = True
             logger.info(f"合约ID缓存初始化完成，共 {len(EdgeXClient._contract_id_cache)} 个映射")
             
@@ -403,6 +1105,31 @@ class EdgeXClient:
             
         except Exception as e:
             logger.error(f"初始化合约ID缓存失败: {e}")
+        return EdgeXClient._cache_initialized
+    
+    def apply_metadata_update(self, contracts: List[Dict[str, Any]]):
+        """
+        应用WebSocket metadata频道推送的合约元数据
+        
+        Args:
+            contracts: 有变化的合约元数据
+        """
+        changed = self.contract_specs.update(contracts, source="ws")
+        if changed:
+            self._index_contracts(changed)
+            self.contract_specs.save()
+    
+    def get_contract_spec(self, symbol: str) -> Optional[ContractSpec]:
+        """
+        获取合约规格
+        
+        Args:
+            symbol: 交易对名称或合约ID
+            
+        Returns:
+            Optional[ContractSpec]: 合约规格，元数据未加载时返回None
+        """
+        return self.contract_specs.get(EdgeXClient._contract_id_cache.get(symbol, symbol))
     
     async def get_contract_id_by_symbol(self, symbol_name: str) -> Optional[str]:
         """
@@ -472,6 +1199,19 @@ class EdgeXClient:
         logger.warning(f"未找到交易对 {symbol_name} 的合约ID")
         return None
     
+    def get_tick_size(self, symbol: str) -> Optional[float]:
+        """
+        获取最小价格变动单位
+        
+        Args:
+            symbol: 交易对名称或合约ID
+            
+        Returns:
+            Optional[float]: 最小价格变动单位，元数据未加载时返回None
+        """
+        contract_id = EdgeXClient._contract_id_cache.get(symbol, symbol)
+        return EdgeXClient._tick_size_cache.get(contract_id)
+    
     def get_symbol_by_contract_id(self, contract_id: str) -> str:
         """
         根据合约ID获取交易对名称
@@ -490,6 +1230,7 @@ class EdgeXClient:
     
     async def close(self):
         """关闭客户端"""
+        await self.channel.close()
         if self.sdk_client:
             await self.sdk_client.close()
             logger.info("EdgeX客户端已关闭")
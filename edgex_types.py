@@ -22,6 +22,24 @@ class OrderType(str, Enum):
     """订单类型"""
     MARKET = "MARKET"
     LIMIT = "LIMIT"
+    STOP_MARKET = "STOP_MARKET"                # 止损：价格不利方向穿越触发价后市价成交
+    STOP_LIMIT = "STOP_LIMIT"                  # 止损：触发后按 price 挂限价单
+    TAKE_PROFIT_MARKET = "TAKE_PROFIT_MARKET"  # 止盈：价格有利方向穿越触发价后市价成交
+
+class TriggerPriceType(str, Enum):
+    """条件单触发价格类型"""
+    LAST_PRICE = "LAST_PRICE"  # 最新成交价
+    MARK_PRICE = "MARK_PRICE"  # 标记价格
+
+# 条件单（需要触发价）
+TRIGGER_ORDER_TYPES = {OrderType.STOP_MARKET, OrderType.STOP_LIMIT, OrderType.TAKE_PROFIT_MARKET}
+# 按市价成交的订单类型（条件单触发后）
+MARKET_ORDER_TYPES = {OrderType.MARKET, OrderType.STOP_MARKET, OrderType.TAKE_PROFIT_MARKET}
+
+class PositionMode(str, Enum):
+    """持仓模式"""
+    ONE_WAY = "one_way"  # 单向持仓：每个交易对一个净持仓
+    HEDGE = "hedge"      # 双向持仓：每个交易对多空分别持仓
 
 class PriceData(BaseModel):
     """价格数据"""
@@ -51,12 +69,27 @@ class Position(BaseModel):
     take_profit: float
     leverage: int
     opening_time: int
+    confidence: Optional[float] = None  # 开仓信号的置信度
+    mae: float = 0.0                    # 持仓期间最大不利偏移（相对开仓均价的比例）
+    mfe: float = 0.0                    # 持仓期间最大有利偏移（相对开仓均价的比例）
+
+class CollateralAsset(BaseModel):
+    """抵押资产（金额均为资产本位）"""
+    coin_id: str
+    coin: str               # 资产名称（USDT/USDC等，元数据中没有时为资产ID）
+    amount: float           # 资产余额
+    equity: float           # 资产权益（含该资产计价的未实现盈亏）
+    available: float        # 可用余额（已扣除占用的保证金和冻结金额）
+    rate: Optional[float]   # 折算为报告币种的汇率（未配置时为None，不计入余额）
+    haircut: float = 0.0    # 计入可用保证金时的折扣比例
 
 class AccountInfo(BaseModel):
-    """账户信息"""
+    """账户信息（balance / available_balance 为各抵押资产按报告币种折算后的合计）"""
     balance: float
     available_balance: float
     positions: Dict[str, Position] = {}
+    collaterals: Dict[str, CollateralAsset] = {}  # 资产名称 -> 抵押资产
+    reporting_currency: str = "USDT"
 
 class Order(BaseModel):
     """订单信息"""
@@ -66,6 +99,35 @@ class Order(BaseModel):
     quantity: float
     price: Optional[float] = None
     leverage: int
+    reduce_only: bool = False
+    position_side: Optional[TradeDirection] = None  # 双向持仓模式下的持仓方向
+    trigger_price: Optional[float] = None  # 条件单触发价
+    trigger_price_type: TriggerPriceType = TriggerPriceType.LAST_PRICE  # 条件单触发价格类型
+
+class Fill(BaseModel):
+    """成交回报"""
+    symbol: str
+    order_id: str
+    side: OrderSide
+    price: float
+    size: float
+    fee: float = 0.0
+    is_maker: bool = False
+    timestamp: int
+
+class PositionUpdate(BaseModel):
+    """交易所推送的持仓变化"""
+    symbol: str
+    direction: TradeDirection
+    size: float                 # 持仓数量（为0表示已平仓）
+    entry_price: float
+    timestamp: int
+
+class FundingPayment(BaseModel):
+    """资金费结算"""
+    symbol: str
+    amount: float               # 正数为收到，负数为支付（USDT）
+    timestamp: int
 
 class TradeRecord(BaseModel):
     """交易记录"""
@@ -77,6 +139,9 @@ class TradeRecord(BaseModel):
     pnl: float
     timestamp: int
     duration: int
+    confidence: Optional[float] = None  # 开仓信号的置信度
+    mae: Optional[float] = None         # 最大不利偏移（相对开仓均价的比例）
+    mfe: Optional[float] = None         # 最大有利偏移（相对开仓均价的比例）
 
 class PerformanceReport(BaseModel):
     """性能报告"""
@@ -108,6 +173,32 @@ class MarketData(BaseModel):
     bid: Optional[float] = None
     ask: Optional[float] = None
 
+class MarkPriceData(BaseModel):
+    """标记价格与指数价格"""
+    timestamp: int
+    mark_price: float   # 标记价格（EdgeX为预言机价格 oraclePrice）
+    index_price: float  # 指数价格（现货指数）
+
+class MarketActivity(BaseModel):
+    """合约持仓量与24小时成交量（来自ticker）"""
+    timestamp: int
+    open_interest: float        # 持仓量（合约数量）
+    open_interest_value: float  # 持仓价值（USDT，持仓量 × 最新价）
+    volume_24h: float           # 24小时成交量（合约数量）
+    value_24h: float            # 24小时成交额（USDT）
+
+class FeeRates(BaseModel):
+    """账户在合约上的手续费率"""
+    taker: float    # 吃单手续费率
+    maker: float    # 挂单手续费率（可能为负，即返佣）
+    source: str     # account（账户设置）/ contract（合约默认费率）
+
+class FundingRate(BaseModel):
+    """合约资金费率与下次结算时间（来自ticker）"""
+    timestamp: int
+    funding_rate: float         # 当前资金费率（正数为多头支付空头）
+    next_funding_time: int      # 下次结算时间（毫秒，ticker未提供时为0）
+
 class OrderBook(BaseModel):
     """订单簿"""
     symbol: str
@@ -139,3 +230,10 @@ class StrategyConfig(BaseModel):
     target_volatility: float = 0.60  # 60%年化波动率
     min_order_size: float = 0.3  # 最小下单量（SOL）
     min_balance_multiplier: float = 1.2
+
+
+def position_key(symbol: str, direction: TradeDirection, mode: PositionMode) -> str:
+    """持仓索引键：单向持仓为交易对，双向持仓为 交易对:方向"""
+    if mode == PositionMode.HEDGE:
+        return f"{symbol}:{direction.value}"
+    return symbol
@@ -0,0 +1,132 @@
+"""
+邮件告警模块
+
+只发送严重级别的告警（持仓接近强平价、紧急停止开关打开、WebSocket连续重连失败），通过SMTP发送给配置的收件人。
+为避免错误风暴时邮件刷屏，发送前做两级限流：
+- 同一告警类型在最小间隔内只发送一封
+- 全部告警每小时最多发送若干封
+被限流的告警只写日志并计数，下一封邮件的正文中附上期间被抑制的告警数。
+SMTP发送在线程池中执行，失败只记录日志，不影响交易。
+"""
+
+import asyncio
+import smtplib
+import time
+from collections import deque
+from email.message import EmailMessage
+from typing import Any, Deque, Dict, List, Optional, Set
+
+from loguru import logger
+
+SMTP_SECURITY = ("starttls", "ssl", "none")
+_SMTP_TIMEOUT = 15.0
+
+
+class EmailNotifier:
+    """严重告警邮件发送"""
+
+    def __init__(self, host: str, port: int, sender: str, recipients: List[str], username: str = "",
+                 password: str = "", security: str = "starttls", max_per_hour: int = 10,
+                 min_interval: float = 300.0):
+        """
+        Args:
+            host: SMTP服务器
+            port: SMTP端口
+            sender: 发件人地址
+            recipients: 收件人地址
+            username: SMTP用户名（空为不登录）
+            password: SMTP密码
+            security: 连接加密方式（starttls/ssl/none）
+            max_per_hour: 每小时最多发送的邮件数
+            min_interval: 同一告警类型的最小发送间隔（秒）
+        """
+        self.host = host
+        self.port = port
+        self.sender = sender
+        self.recipients = recipients
+        self.username = username
+        self.password = password
+        self.security = security
+        self.max_per_hour = max_per_hour
+        self.min_interval = min_interval
+        self._sent_times: Deque[float] = deque()
+        self._last_by_alert: Dict[str, float] = {}
+        self._suppressed_since_last = 0
+        self.stats = {"sent": 0, "errors": 0, "suppressed": 0}
+        self._tasks: Set[asyncio.Task] = set()
+
+    @classmethod
+    def from_config(cls, config) -> "EmailNotifier":
+        return cls(config.smtp_host, config.smtp_port, config.smtp_from, config.smtp_to, config.smtp_username,
+                   config.smtp_password, config.smtp_security, config.smtp_max_per_hour,
+                   config.smtp_min_interval_seconds)
+
+    def allow(self, alert: str, now: Optional[float] = None) -> bool:
+        """按告警类型间隔和每小时上限判断是否发送（允许时记入发送计数）"""
+        now = time.time() if now is None else now
+        while self._sent_times and now - self._sent_times[0] >= 3600:
+            self._sent_times.popleft()
+        last = self._last_by_alert.get(alert)
+        if (last is not None and now - last < self.min_interval) or len(self._sent_times) >= self.max_per_hour:
+            self.stats["suppressed"] += 1
+            self._suppressed_since_last += 1
+            return False
+        self._sent_times.append(now)
+        self._last_by_alert[alert] = now
+        return True
+
+    def send(self, strategy: str, alert: str, text: str):
+        """
+        发送严重告警邮件（需在事件循环中调用，SMTP发送在后台执行）
+
+        Args:
+            strategy: 策略名称
+            alert: 告警类型
+            text: 告警内容
+        """
+        if not self.allow(alert):
+            logger.warning(f"[邮件告警] {alert} 已限流，不发送邮件")
+            return
+        try:
+            loop = asyncio.get_running_loop()
+        except RuntimeError:
+            return
+        body = text
+        if self._suppressed_since_last:
+            body += f"\n\n（上一封邮件之后有 {self._suppressed_since_last} 条严重告警因限流未发送，详见日志）"
+            self._suppressed_since_last = 0
+        message = self._build_message(f"[严重告警] {strategy} {alert}", body)
+        task = loop.create_task(self._send(message))
+        self._tasks.add(task)
+        task.add_done_callback(self._tasks.discard)
+
+    def _build_message(self, subject: str, body: str) -> EmailMessage:
+        message = EmailMessage()
+        message["Subject"] = subject
+        message["From"] = self.sender
+        message["To"] = ", ".join(self.recipients)
+        message.set_content(body)
+        return message
+
+    async def _send(self, message: EmailMessage):
+        try:
+            await asyncio.get_running_loop().run_in_executor(None, self._deliver, message)
+            self.stats["sent"] += 1
+        except Exception as e:
+            self.stats["errors"] += 1
+            logger.warning(f"[邮件告警] 发送失败: {e}")
+
+    def _deliver(self, message: EmailMessage):
+        if self.security == "ssl":
+            server = smtplib.SMTP_SSL(self.host, self.port, timeout=_SMTP_TIMEOUT)
+        else:
+            server = smtplib.SMTP(self.host, self.port, timeout=_SMTP_TIMEOUT)
+        with server:
+            if self.security == "starttls":
+                server.starttls()
+            if self.username:
+                server.login(self.username, self.password)
+            server.send_message(message)
+
+    def get_stats(self) -> Dict[str, Any]:
+        return {**self.stats, "recipients": len(self.recipients)}
@@ -0,0 +1,201 @@
+"""
+多端点选择模块
+
+配置多个REST/WebSocket地址时，不重启机器人即可在端点之间切换：
+- 定期探测各端点延迟（REST请求服务器时间接口，WebSocket测量TCP/TLS握手耗时），优先使用最快的健康端点
+- 新端点的延迟低于当前端点的 SWITCH_MARGIN 倍才切换，避免延迟抖动导致来回切换
+- 端点连续出错（网络错误、超时、5xx、探测失败、WebSocket断线）达到阈值时标记为故障，
+  冷却期内不再选用；当前端点故障时立即切换到延迟最低的健康端点
+"""
+
+import asyncio
+import time
+from typing import Any, Dict, List, Optional
+from urllib.parse import urlsplit
+
+import aiohttp
+from loguru import logger
+
+SWITCH_MARGIN = 0.8
+PROBE_TIMEOUT_SECONDS = 5.0
+
+
+class Endpoint:
+    """单个端点的延迟与健康状态"""
+
+    def __init__(self, url: str):
+        self.url = url
+        self.latency_ms: Optional[float] = None
+        self.errors = 0          # 连续错误次数
+        self.failures = 0        # 累计标记故障次数
+        self.down_until = 0.0    # 故障冷却结束时间
+
+    def is_healthy(self, now: float) -> bool:
+        return now >= self.down_until
+
+
+class EndpointPool:
+    """一组可互相替代的端点（REST或WebSocket）"""
+
+    def __init__(self, name: str, urls: List[str], failover_errors: int = 3, cooldown_seconds: float = 60.0):
+        """
+        Args:
+            name: 名称（日志用）
+            urls: 端点地址，按优先级排列（没有延迟数据时使用第一个）
+            failover_errors: 连续出错多少次标记为故障
+            cooldown_seconds: 故障端点的冷却时间（秒）
+        """
+        if not urls:
+            raise ValueError(f"{name} 端点列表不能为空")
+        self.name = name
+        self.endpoints = [Endpoint(url.rstrip("/")) for url in urls]
+        self.failover_errors = max(1, failover_errors)
+        self.cooldown_seconds = cooldown_seconds
+        self.switches = 0
+        self._current = self.endpoints[0]
+
+    @property
+    def current(self) -> str:
+        return self._current.url
+
+    @property
+    def urls(self) -> List[str]:
+        return [ep.url for ep in self.endpoints]
+
+    def _find(self, url: str) -> Optional[Endpoint]:
+        url = url.rstrip("/")
+        return next((ep for ep in self.endpoints if ep.url == url), None)
+
+    def _record_error(self, ep: Endpoint, now: float) -> bool:
+        """记录一次错误，达到阈值时标记故障，返回是否标记"""
+        ep.errors += 1
+        if ep.errors < self.failover_errors:
+            return False
+        ep.errors = 0
+        ep.failures += 1
+        ep.down_until = now + self.cooldown_seconds
+        logger.warning(f"[端点] {self.name} {ep.url} 连续出错{self.failover_errors}次，"
+                       f"{self.cooldown_seconds:.0f}秒内不再使用")
+        return True
+
+    def record_success(self):
+        """当前端点请求成功（清零连续错误）"""
+        self._current.errors = 0
+
+    def record_error(self, now: Optional[float] = None) -> bool:
+        """
+        当前端点出错一次
+
+        Returns:
+            bool: 是否达到阈值被标记为故障（此时已尝试切换，调用方应按 current 重新连接）
+        """
+        now = time.time() if now is None else now
+        if not self._record_error(self._current, now):
+            return False
+        self.select(now)
+        return True
+
+    def record_latency(self, url: str, latency_ms: Optional[float], now: Optional[float] = None):
+        """
+        记录探测结果
+
+        Args:
+            url: 端点地址
+            latency_ms: 延迟（毫秒），None表示探测失败（计为一次错误）
+        """
+        ep = self._find(url)
+        if ep is None:
+            return
+        ep.latency_ms = latency_ms
+        if latency_ms is None:
+            self._record_error(ep, time.time() if now is None else now)
+        else:
+            ep.errors = 0
+
+    def select(self, now: Optional[float] = None) -> Optional[str]:
+        """
+        按健康状态和延迟重新选择端点
+
+        Returns:
+            Optional[str]: 切换后的端点地址，未切换时为None
+        """
+        now = time.time() if now is None else now
+        healthy = [ep for ep in self.endpoints if ep.is_healthy(now)]
+        if not healthy:
+            # 全部故障：保持当前端点，等待冷却结束
+            return None
+        measured = [ep for ep in healthy if ep.latency_ms is not None]
+        best = min(measured, key=lambda ep: ep.latency_ms) if measured else healthy[0]
+        cur = self._current
+        if best is cur:
+            return None
+        if cur.is_healthy(now):
+            if best.latency_ms is None or cur.latency_ms is None or best.latency_ms >= cur.latency_ms * SWITCH_MARGIN:
+                return None
+            reason = f"延迟 {cur.latency_ms:.0f}ms -> {best.latency_ms:.0f}ms"
+        else:
+            reason = "当前端点故障"
+        self._current = best
+        self.switches += 1
+        logger.warning(f"[端点] {self.name} 切换到 {best.url}（{reason}）")
+        return best.url
+
+    def stats(self, now: Optional[float] = None) -> Dict[str, Any]:
+        now = time.time() if now is None else now
+        return {
+            "current": self.current,
+            "switches": self.switches,
+            "endpoints": [
+                {
+                    "url": ep.url,
+                    "latency_ms": ep.latency_ms,
+                    "healthy": ep.is_healthy(now),
+                    "failures": ep.failures,
+                }
+                for ep in self.endpoints
+            ],
+        }
+
+
+async def probe_rest(session: aiohttp.ClientSession, url: str, proxy: Optional[str] = None,
+                     timeout: float = PROBE_TIMEOUT_SECONDS) -> Optional[float]:
+    """
+    探测REST端点延迟（请求服务器时间接口）
+
+    Returns:
+        Optional[float]: 往返耗时（毫秒），失败时为None
+    """
+    start = time.perf_counter()
+    try:
+        async with session.get(f"{url}/api/v1/public/meta/getServerTime", proxy=proxy,
+                               timeout=aiohttp.ClientTimeout(total=timeout)) as response:
+            await response.read()
+            if response.status != 200:
+                return None
+    except Exception as e:
+        logger.debug(f"[端点] 探测 {url} 失败: {e}")
+        return None
+    return (time.perf_counter() - start) * 1000
+
+
+async def probe_ws(url: str, timeout: float = PROBE_TIMEOUT_SECONDS) -> Optional[float]:
+    """
+    探测WebSocket端点延迟（TCP连接和TLS握手耗时，不建立WebSocket会话）
+
+    Returns:
+        Optional[float]: 耗时（毫秒），失败时为None
+    """
+    parts = urlsplit(url)
+    tls = parts.scheme == "wss"
+    start = time.perf_counter()
+    try:
+        _, writer = await asyncio.wait_for(
+            asyncio.open_connection(parts.hostname, parts.port or (443 if tls else 80), ssl=True if tls else None),
+            timeout=timeout
+        )
+    except (OSError, asyncio.TimeoutError) as e:
+        logger.debug(f"[端点] 探测 {url} 失败: {e}")
+        return None
+    latency = (time.perf_counter() - start) * 1000
+    writer.close()
+    return latency
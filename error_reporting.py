@@ -0,0 +1,123 @@
+"""
+错误上报模块（可选，需要 sentry-sdk）
+
+设置了 EDGEX_SENTRY_DSN 时把生产环境的错误上报到 Sentry 或兼容的服务（如 GlitchTip，使用同样的DSN格式）：
+- 未捕获的异常: 主线程和事件循环任务中未处理的异常由 sentry-sdk 自动上报（需在事件循环中初始化）
+- 策略严重错误: 策略主循环、开仓/平仓/持仓管理失败、平仓重试耗尽等由 capture_error 显式上报，
+  附带标签（策略、交易对、订单ID）；预期内的交易所业务拒绝（保证金不足、价格超出范围、请求限频，见 exchange_errors.py）不上报
+- 近期事件: INFO及以上的日志作为面包屑（breadcrumb）随错误一起上报，便于还原出错前的订单、成交和信号
+未安装 sentry-sdk 或未设置DSN时，所有接口退化为空操作，不影响交易主流程。
+"""
+
+from typing import Any, Optional
+
+from loguru import logger
+
+try:
+    import sentry_sdk
+    from sentry_sdk.integrations.asyncio import AsyncioIntegration
+    SENTRY_AVAILABLE = True
+except ImportError:
+    SENTRY_AVAILABLE = False
+
+_enabled = False
+_sink_id: Optional[int] = None
+
+_BREADCRUMB_LEVELS = {"INFO": "info", "SUCCESS": "info", "WARNING": "warning", "ERROR": "error", "CRITICAL": "fatal"}
+
+
+def _breadcrumb_sink(message):
+    record = message.record
+    level = _BREADCRUMB_LEVELS.get(record["level"].name)
+    if level is None:
+        return
+    sentry_sdk.add_breadcrumb(category=record["name"], message=record["message"], level=level,
+                              timestamp=record["time"])
+
+
+def init_error_reporting(config) -> bool:
+    """
+    初始化错误上报
+
+    Args:
+        config: 配置对象（sentry_dsn / sentry_environment / sentry_max_breadcrumbs）
+
+    Returns:
+        bool: 错误上报是否成功启用
+    """
+    global _enabled, _sink_id
+
+    if not config.sentry_dsn:
+        return False
+
+    if not SENTRY_AVAILABLE:
+        logger.warning("已设置错误上报DSN，但未安装sentry-sdk依赖，错误上报将被禁用")
+        return False
+
+    try:
+        environment = config.sentry_environment or ("paper" if config.paper_trading else "production")
+        sentry_sdk.init(
+            dsn=config.sentry_dsn,
+            environment=environment,
+            max_breadcrumbs=config.sentry_max_breadcrumbs,
+            integrations=[AsyncioIntegration()],
+            send_default_pii=False,
+        )
+        sentry_sdk.set_tag("testnet", str(config.testnet).lower())
+        _sink_id = logger.add(_breadcrumb_sink, level="INFO", format="{message}")
+        _enabled = True
+        logger.info(f"错误上报已启用 (environment={environment})")
+        return True
+
+    except Exception as e:
+        logger.error(f"错误上报初始化失败: {e}")
+        _enabled = False
+        return False
+
+
+def capture_error(error: Any, strategy: str = "", symbol: Optional[str] = None, order_id: Optional[str] = None,
+                  **context: Any):
+    """
+    上报策略严重错误
+
+    Args:
+        error: 异常，或错误描述（没有异常对象时）
+        strategy: 策略名称
+        symbol: 交易对
+        order_id: 订单ID
+        context: 其他上下文（作为附加数据）
+    """
+    if not _enabled or getattr(error, "expected", False):
+        return
+    try:
+        with sentry_sdk.push_scope() as scope:
+            if strategy:
+                scope.set_tag("strategy", strategy)
+            if symbol:
+                scope.set_tag("symbol", symbol)
+            if order_id:
+                scope.set_tag("order_id", order_id)
+            if context:
+                scope.set_context("strategy_error", context)
+            if isinstance(error, BaseException):
+                sentry_sdk.capture_exception(error)
+            else:
+                sentry_sdk.capture_message(str(error), level="error")
+    except Exception as e:
+        logger.debug(f"错误上报失败: {e}")
+
+
+def shutdown_error_reporting(timeout: float = 5.0):
+    """发送尚未上报的错误并停止上报"""
+    global _enabled, _sink_id
+
+    if not _enabled:
+        return
+    if _sink_id is not None:
+        logger.remove(_sink_id)
+        _sink_id = None
+    try:
+        sentry_sdk.flush(timeout=timeout)
+    except Exception as e:
+        logger.error(f"刷新错误上报失败: {e}")
+    _enabled = False
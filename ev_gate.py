@@ -0,0 +1,120 @@
+"""
+开仓期望值检查模块
+
+每次开仓前估算交易的期望收益（bps，相对开仓价），低于阈值时跳过：
+- 信号优势: 以信号置信度作为止盈概率 p，毛期望 = p × 止盈距离 - (1 - p) × 止损距离
+- 手续费: 往返手续费率（见 fees.py，费率未知时按0计）
+- 价差与滑点: 开仓和平仓各计一次执行成本。有该交易对最近的滑点记录时，执行成本取
+  max(当前半个价差, 最近平均滑点)（滑点相对到达中间价计算，已包含穿越价差的成本）；
+  没有记录时为 当前半个价差 + default_slippage_bps
+- 期望值 = 毛期望 - 手续费 - 开仓执行成本 - 平仓执行成本，低于 min_ev_bps 时跳过开仓
+跳过和通过的交易按交易对统计各分项的平均值，可选逐笔写入CSV文件用于调整阈值。
+"""
+
+import csv
+import os
+import time
+from dataclasses import asdict, dataclass
+from typing import Any, Dict, Optional
+
+from loguru import logger
+
+LOG_FIELDS = ["timestamp", "symbol", "direction", "confidence", "gross_bps", "fee_bps", "entry_cost_bps",
+              "exit_cost_bps", "ev_bps", "skipped"]
+
+
+@dataclass
+class EvEstimate:
+    """一次开仓的期望值估算（bps）"""
+    gross_bps: float        # 信号毛期望
+    fee_bps: float          # 往返手续费
+    entry_cost_bps: float   # 开仓价差与滑点
+    exit_cost_bps: float    # 平仓价差与滑点
+
+    @property
+    def ev_bps(self) -> float:
+        return self.gross_bps - self.fee_bps - self.entry_cost_bps - self.exit_cost_bps
+
+
+def execution_cost(half_spread_bps: Optional[float], slippage_bps: Optional[float], default_slippage_bps: float) -> float:
+    """单边执行成本（bps）：有滑点记录时取与半个价差中的较大者，否则为半个价差加默认滑点"""
+    half_spread = half_spread_bps or 0.0
+    if slippage_bps is None:
+        return half_spread + default_slippage_bps
+    return max(half_spread, slippage_bps)
+
+
+class EvGate:
+    """开仓期望值检查"""
+
+    def __init__(self, min_ev_bps: float = 0.0, default_slippage_bps: float = 1.0, log_path: str = ""):
+        """
+        Args:
+            min_ev_bps: 期望值下限（bps）
+            default_slippage_bps: 没有滑点记录时的单边滑点估计（bps，在半个价差之外）
+            log_path: 逐笔估算的CSV文件（空为不写入）
+        """
+        self.min_ev_bps = min_ev_bps
+        self.default_slippage_bps = default_slippage_bps
+        self.log_path = log_path
+        # 统计：交易对 -> {"passed"/"skipped": 次数, 各分项合计}
+        self.stats: Dict[str, Dict[str, Any]] = {}
+
+    @staticmethod
+    def gross(confidence: float, take_profit_bps: float, stop_loss_bps: float) -> float:
+        """信号毛期望（bps）"""
+        p = max(0.0, min(1.0, confidence))
+        return p * take_profit_bps - (1 - p) * stop_loss_bps
+
+    def check(self, symbol: str, direction: str, confidence: float, estimate: EvEstimate) -> Optional[str]:
+        """
+        记录估算并判断是否开仓
+
+        Returns:
+            Optional[str]: 跳过原因，允许开仓时返回None
+        """
+        ev = estimate.ev_bps
+        skipped = ev < self.min_ev_bps
+        entry = self.stats.setdefault(symbol, {"passed": 0, "skipped": 0, "passed_sum": self._zero(),
+                                               "skipped_sum": self._zero()})
+        key = "skipped" if skipped else "passed"
+        entry[key] += 1
+        totals = entry[f"{key}_sum"]
+        for field, value in {**asdict(estimate), "ev_bps": ev}.items():
+            totals[field] += value
+        self._write(symbol, direction, confidence, estimate, skipped)
+        if not skipped:
+            return None
+        return (f"期望值 {ev:+.2f} bps 低于阈值 {self.min_ev_bps} bps（毛期望 {estimate.gross_bps:+.2f}，"
+                f"手续费 {estimate.fee_bps:.2f}，开仓成本 {estimate.entry_cost_bps:.2f}，平仓成本 {estimate.exit_cost_bps:.2f}）")
+
+    @staticmethod
+    def _zero() -> Dict[str, float]:
+        return {"gross_bps": 0.0, "fee_bps": 0.0, "entry_cost_bps": 0.0, "exit_cost_bps": 0.0, "ev_bps": 0.0}
+
+    def _write(self, symbol: str, direction: str, confidence: float, estimate: EvEstimate, skipped: bool):
+        if not self.log_path:
+            return
+        try:
+            os.makedirs(os.path.dirname(self.log_path) or ".", exist_ok=True)
+            is_new = not os.path.exists(self.log_path)
+            with open(self.log_path, "a", newline="") as fp:
+                writer = csv.writer(fp)
+                if is_new:
+                    writer.writerow(LOG_FIELDS)
+                writer.writerow([int(time.time() * 1000), symbol, direction, round(confidence, 4),
+                                 round(estimate.gross_bps, 4), round(estimate.fee_bps, 4),
+                                 round(estimate.entry_cost_bps, 4), round(estimate.exit_cost_bps, 4),
+                                 round(estimate.ev_bps, 4), int(skipped)])
+        except OSError as e:
+            logger.error(f"[期望值] 写入估算记录失败: {e}")
+
+    def get_stats(self) -> Dict[str, Any]:
+        """各交易对通过/跳过次数及各分项平均值（bps）"""
+        symbols = {}
+        for symbol, entry in self.stats.items():
+            symbols[symbol] = {"passed": entry["passed"], "skipped": entry["skipped"]}
+            for key in ("passed", "skipped"):
+                if entry[key]:
+                    symbols[symbol][f"{key}_avg"] = {k: round(v / entry[key], 2) for k, v in entry[f"{key}_sum"].items()}
+        return {"min_ev_bps": self.min_ev_bps, "symbols": symbols}
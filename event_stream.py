@@ -0,0 +1,198 @@
+"""
+事件流模块
+
+把交易记录、订单事件和性能快照以统一的JSON格式推送到 Kafka 或 NATS，供下游分析管道消费：
+- <prefix>.trades: 平仓产生的交易记录
+- <prefix>.orders: 订单状态变化（提交、部分成交、成交、撤销）
+- <prefix>.performance: 定期的性能统计快照
+
+每条消息格式为 {"type": ..., "timestamp": 毫秒, "source": 实例名称, "data": {...}}。
+依赖为可选项（aiokafka / nats-py），未安装时不启用，不影响交易主流程。
+"""
+
+import asyncio
+import json
+import time
+from abc import ABC, abstractmethod
+from typing import Dict, List, Optional, Any
+from loguru import logger
+
+try:
+    from aiokafka import AIOKafkaProducer
+    KAFKA_AVAILABLE = True
+except ImportError:
+    KAFKA_AVAILABLE = False
+
+try:
+    import nats
+    NATS_AVAILABLE = True
+except ImportError:
+    NATS_AVAILABLE = False
+
+
+# 事件类型
+EVENT_TRADE = "trades"
+EVENT_ORDER = "orders"
+EVENT_PERFORMANCE = "performance"
+
+
+class EventStreamer(ABC):
+    """事件流生产者基类"""
+
+    BACKEND = ""
+
+    # 待发送事件队列上限（下游不可用时丢弃最早的事件）
+    MAX_PENDING = 10000
+
+    def __init__(self, prefix: str, source: str):
+        """
+        Args:
+            prefix: 主题前缀
+            source: 实例名称（写入每条消息，区分多个机器人实例）
+        """
+        self.prefix = prefix
+        self.source = source
+        self._queue: Optional[asyncio.Queue] = None
+        self._task: Optional[asyncio.Task] = None
+
+        # 统计
+        self.sent: Dict[str, int] = {}
+        self.dropped = 0
+
+    async def start(self):
+        """连接并启动发送任务"""
+        self._queue = asyncio.Queue(maxsize=self.MAX_PENDING)
+        await self._connect()
+        self._task = asyncio.create_task(self._drain())
+
+    async def stop(self):
+        """发送剩余事件后断开连接"""
+        if self._task:
+            self._task.cancel()
+            self._task = None
+        while self._queue and not self._queue.empty():
+            await self._send_one(*self._queue.get_nowait())
+        await self._disconnect()
+
+    def emit(self, event_type: str, data: Dict[str, Any]):
+        """
+        推送事件（非阻塞，需在事件循环线程中调用）
+
+        Args:
+            event_type: 事件类型（trades/orders/performance）
+            data: 事件内容
+        """
+        if self._queue is None:
+            return
+        if self._queue.full():
+            self._queue.get_nowait()
+            self.dropped += 1
+        message = {"type": event_type, "timestamp": int(time.time() * 1000), "source": self.source, "data": data}
+        self._queue.put_nowait((event_type, message))
+
+    async def _drain(self):
+        while True:
+            await self._send_one(*await self._queue.get())
+
+    async def _send_one(self, event_type: str, message: Dict[str, Any]):
+        try:
+            payload = json.dumps(message, ensure_ascii=False, default=str).encode()
+            await self._send(f"{self.prefix}.{event_type}", payload)
+            self.sent[event_type] = self.sent.get(event_type, 0) + 1
+        except Exception as e:
+            self.dropped += 1
+            logger.warning(f"[事件流] 发送 {event_type} 失败: {e}")
+
+    @abstractmethod
+    async def _connect(self):
+        """建立连接"""
+
+    @abstractmethod
+    async def _disconnect(self):
+        """断开连接"""
+
+    @abstractmethod
+    async def _send(self, topic: str, payload: bytes):
+        """发送一条消息"""
+
+    def get_stats(self) -> Dict[str, Any]:
+        """获取发送统计"""
+        return {"backend": self.BACKEND, "sent": dict(self.sent), "dropped": self.dropped}
+
+
+class KafkaStreamer(EventStreamer):
+    """Kafka 事件流"""
+
+    BACKEND = "kafka"
+
+    def __init__(self, prefix: str, source: str, bootstrap_servers: str):
+        """
+        Args:
+            bootstrap_servers: Kafka地址（逗号分隔）
+        """
+        super().__init__(prefix, source)
+        self.bootstrap_servers = bootstrap_servers
+        self._producer = None
+
+    async def _connect(self):
+        self._producer = AIOKafkaProducer(bootstrap_servers=self.bootstrap_servers)
+        await self._producer.start()
+        logger.info(f"[事件流] Kafka已连接: {self.bootstrap_servers}，主题 {self.prefix}.*")
+
+    async def _disconnect(self):
+        if self._producer:
+            await self._producer.stop()
+
+    async def _send(self, topic: str, payload: bytes):
+        await self._producer.send(topic, payload, key=self.source.encode())
+
+
+class NatsStreamer(EventStreamer):
+    """NATS 事件流"""
+
+    BACKEND = "nats"
+
+    def __init__(self, prefix: str, source: str, url: str):
+        """
+        Args:
+            url: NATS地址（逗号分隔）
+        """
+        super().__init__(prefix, source)
+        self.url = url
+        self._nc = None
+
+    async def _connect(self):
+        self._nc = await nats.connect(servers=self.url.split(","))
+        logger.info(f"[事件流] NATS已连接: {self.url}，主题 {self.prefix}.*")
+
+    async def _disconnect(self):
+        if self._nc:
+            await self._nc.drain()
+
+    async def _send(self, topic: str, payload: bytes):
+        await self._nc.publish(topic, payload)
+
+
+def create_event_streamer(config) -> Optional[EventStreamer]:
+    """
+    根据配置创建事件流生产者
+
+    Args:
+        config: 配置对象（event_stream / event_stream_prefix / event_stream_source /
+            kafka_bootstrap_servers / nats_url）
+
+    Returns:
+        Optional[EventStreamer]: 事件流生产者，未启用或依赖未安装时返回None
+    """
+    backend = config.event_stream
+    if backend == "kafka":
+        if not KAFKA_AVAILABLE:
+            logger.warning("已启用Kafka事件流，但未安装aiokafka依赖，事件流将被禁用")
+            return None
+        return KafkaStreamer(config.event_stream_prefix, config.event_stream_source, config.kafka_bootstrap_servers)
+    if backend == "nats":
+        if not NATS_AVAILABLE:
+            logger.warning("已启用NATS事件流，但未安装nats-py依赖，事件流将被禁用")
+            return None
+        return NatsStreamer(config.event_stream_prefix, config.event_stream_source, config.nats_url)
+    return None
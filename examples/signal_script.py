@@ -0,0 +1,22 @@
+"""
+信号脚本示例（EDGEX_SIGNAL_SCRIPT=examples/signal_script.py）
+
+均线偏离回归，并用盘口不平衡度过滤：价格低于MA5超过0.2%且买盘不弱于卖盘时做多，反之做空。
+文件保存后机器人自动重新加载。
+"""
+
+THRESHOLD = 0.002
+
+
+def generate_signal(ctx):
+    if len(ctx.closes) < 5 or ctx.spread_bps() > 10:
+        return None
+
+    deviation = ctx.deviation(5)
+    imbalance = ctx.imbalance(5)
+
+    if deviation < -THRESHOLD and imbalance > -0.2:
+        return {"direction": "long", "confidence": min(abs(deviation) / THRESHOLD / 2, 1.0)}
+    if deviation > THRESHOLD and imbalance < 0.2:
+        return {"direction": "short", "confidence": min(abs(deviation) / THRESHOLD / 2, 1.0)}
+    return None
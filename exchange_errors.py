@@ -0,0 +1,163 @@
+"""
+交易所响应与错误码模块
+
+EdgeX REST接口统一返回 {"code", "msg", "data", "errorParam"} 格式的响应，code 为 SUCCESS 时请求成功：
+- 响应解析为 ApiResponse，code 不是 SUCCESS 的响应（包括HTTP 200但响应体为错误）一律抛出异常，不再按成功处理
+- 错误码映射为 StrategyError 的子类：保证金不足（InsufficientMarginError）、价格超出范围（PriceOutOfRangeError）、
+  请求限频（RateLimitedError），其他错误为 ExchangeError
+- SDK以 ValueError 抛出的错误（"request failed with code: ..." / "status code: 429" 等）同样解析错误码后转换
+错误码按关键字匹配（不区分大小写），交易所新增的错误码只要包含相应关键字即可归类。
+"""
+
+import re
+from dataclasses import dataclass, field
+from typing import Any, Dict, Optional, Tuple, Type
+
+SUCCESS_CODE = "SUCCESS"
+
+
+class StrategyError(ValueError):
+    """交易所拒绝请求（继承 ValueError，与SDK原有的错误类型兼容）"""
+
+    kind = "other"
+    label = "其他错误"
+    # 预期内的业务拒绝（不是程序错误），不上报错误跟踪
+    expected = False
+
+    def __init__(self, endpoint: str, code: str, message: str = "", status: Optional[int] = None):
+        self.endpoint = endpoint
+        self.code = code
+        self.message = message
+        self.status = status
+        detail = f"{code}" + (f" - {message}" if message else "") + (f"（HTTP {status}）" if status else "")
+        super().__init__(f"{endpoint} 失败: {self.label}: {detail}")
+
+
+class ExchangeError(StrategyError):
+    """未归类的交易所错误"""
+
+
+class InsufficientMarginError(StrategyError):
+    """保证金或可用余额不足"""
+    kind = "insufficient_margin"
+    label = "保证金不足"
+    expected = True
+
+
+class PriceOutOfRangeError(StrategyError):
+    """订单价格超出交易所允许的范围"""
+    kind = "price_out_of_range"
+    label = "价格超出范围"
+    expected = True
+
+
+class RateLimitedError(StrategyError):
+    """请求频率超过交易所限制"""
+    kind = "rate_limited"
+    label = "请求限频"
+    expected = True
+
+
+# 错误类型 -> 错误码关键字（任一组的全部关键字都出现时归为该类型）
+ERROR_CLASSES: Tuple[Tuple[Type[StrategyError], Tuple[Tuple[str, ...], ...]], ...] = (
+    (RateLimitedError, (("rate", "limit"), ("too", "many"), ("frequen",))),
+    (InsufficientMarginError, (("margin",), ("insufficient", "balance"), ("insufficient", "equity"),
+                               ("available", "not", "enough"), ("balance", "not", "enough"))),
+    (PriceOutOfRangeError, (("price", "range"), ("price", "limit"), ("price", "deviat"), ("price", "too"))),
+)
+
+ERROR_KINDS = {cls.kind: cls.label for cls, _ in ERROR_CLASSES}
+ERROR_KINDS[ExchangeError.kind] = ExchangeError.label
+
+
+def error_for(endpoint: str, code: str, message: str = "", status: Optional[int] = None) -> StrategyError:
+    """按错误码（及错误信息）构造对应类型的错误"""
+    if status == 429:
+        return RateLimitedError(endpoint, code, message, status)
+    text = f"{code} {message}".lower()
+    for cls, keyword_sets in ERROR_CLASSES:
+        if any(all(keyword in text for keyword in keywords) for keywords in keyword_sets):
+            return cls(endpoint, code, message, status)
+    return ExchangeError(endpoint, code, message, status)
+
+
+@dataclass
+class ApiResponse:
+    """解析后的REST响应"""
+    code: str
+    msg: str = ""
+    data: Any = None
+    error_param: Any = None
+    raw: Dict[str, Any] = field(default_factory=dict)
+
+    @property
+    def ok(self) -> bool:
+        return self.code == SUCCESS_CODE
+
+    def message(self) -> str:
+        """错误信息（msg 为空时取 errorParam）"""
+        if self.msg:
+            return self.msg
+        if isinstance(self.error_param, dict):
+            return str(self.error_param.get("message") or self.error_param)
+        return str(self.error_param or "")
+
+
+def parse_response(response: Any) -> ApiResponse:
+    """解析响应（不是标准格式的响应视为错误 INVALID_RESPONSE）"""
+    if not isinstance(response, dict) or "code" not in response:
+        return ApiResponse(code="INVALID_RESPONSE", msg=str(response)[:200])
+    return ApiResponse(
+        code=str(response.get("code")),
+        msg=str(response.get("msg") or ""),
+        data=response.get("data"),
+        error_param=response.get("errorParam"),
+        raw=response,
+    )
+
+
+def unwrap(endpoint: str, response: Any) -> ApiResponse:
+    """
+    解析响应并检查是否成功
+
+    Raises:
+        StrategyError: 响应 code 不是 SUCCESS
+    """
+    parsed = parse_response(response)
+    if not parsed.ok:
+        raise error_for(endpoint, parsed.code, parsed.message())
+    return parsed
+
+
+_STATUS_PATTERN = re.compile(r"status code: (\d+)")
+_CODE_PATTERNS = (
+    re.compile(r"request failed with code: ([A-Za-z0-9_]+)"),
+    re.compile(r"""['"]code['"]\s*:\s*['"]([^'"]+)['"]"""),
+)
+_MESSAGE_PATTERNS = (
+    re.compile(r"""['"](?:msg|message)['"]\s*:\s*['"]([^'"]*)['"]"""),
+    re.compile(r"msg: (.*?)(?:, error params:|$)"),
+)
+
+
+def from_exception(endpoint: str, error: Exception) -> Optional[StrategyError]:
+    """
+    将SDK抛出的请求错误转换为对应类型的错误
+
+    Returns:
+        Optional[StrategyError]: 不是交易所返回的错误（如参数或签名错误）时为None
+    """
+    if isinstance(error, StrategyError):
+        return error
+    text = str(error)
+    if "request failed" not in text:
+        return None
+    status_match = _STATUS_PATTERN.search(text)
+    status = int(status_match.group(1)) if status_match else None
+    code = next((m.group(1) for m in (p.search(text) for p in _CODE_PATTERNS) if m), None)
+    if code is None and status is None:
+        return None
+    message = next((m.group(1) for m in (p.search(text) for p in _MESSAGE_PATTERNS) if m), "")
+    if not message and "error params:" in text:
+        message = text.split("error params:", 1)[1].strip()[:200]
+    return error_for(endpoint, code or f"HTTP_{status}", message, status)
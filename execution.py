@@ -0,0 +1,278 @@
+"""
+订单执行模块
+
+根据执行策略把策略层的下单意图转换为具体的交易所订单：
+- market: 直接市价成交
+- limit_then_market: 先在买一/卖一（或盘口内侧）挂被动限价单，通过订单回报等待成交，
+  超时后撤单并以市价补足剩余数量。降低吃单手续费，同时限制执行延迟。
+"""
+
+import time
+from collections import deque
+from enum import Enum
+from typing import Callable, Dict, List, Optional, Any
+from loguru import logger
+
+from edgex_types import Order, OrderBook, OrderSide, OrderType
+from order_tracker import OrderTracker
+from tracing import traced, annotate
+
+
+class ExecutionPolicy(str, Enum):
+    """执行策略"""
+    MARKET = "market"
+    LIMIT_THEN_MARKET = "limit_then_market"
+
+
+class OrderRouter:
+    """智能订单路由"""
+
+    # 撤单后等待最终订单状态的时间（秒）
+    CANCEL_CONFIRM_TIMEOUT = 2.0
+    # 挂单期间检查盘口变化的间隔（秒）
+    REQUOTE_CHECK_INTERVAL = 0.2
+
+    def __init__(self, client, tracker: OrderTracker, config,
+                 book_provider: Callable[[str], Optional[OrderBook]]):
+        """
+        初始化订单路由
+
+        Args:
+            client: EdgeX客户端
+            tracker: 订单跟踪器
+            config: 配置对象（execution_policy/limit_wait_ms/limit_price_offset_ticks/
+                requote_threshold_ticks/max_requotes_per_minute）
+            book_provider: 获取交易对本地订单簿的函数
+        """
+        self.client = client
+        self.tracker = tracker
+        self.book_provider = book_provider
+        self.policy = ExecutionPolicy(getattr(config, "execution_policy", "market"))
+        self.limit_wait_ms = getattr(config, "limit_wait_ms", 3000)
+        self.limit_price_offset_ticks = getattr(config, "limit_price_offset_ticks", 0)
+        self.requote_threshold_ticks = getattr(config, "requote_threshold_ticks", 0)
+        self.max_requotes_per_minute = getattr(config, "max_requotes_per_minute", 10)
+        self._requote_times: Dict[str, deque] = {}
+
+        # 统计
+        self.limit_attempts = 0
+        self.limit_filled = 0
+        self.escalations = 0
+        self.expired_cancels = 0
+        self.requotes = 0
+
+    @traced("execution.route")
+    async def execute(self, order: Order, reference_price: Optional[float] = None) -> List[Dict[str, Any]]:
+        """
+        按执行策略执行订单
+
+        Args:
+            order: 订单对象（市价单）
+            reference_price: 决策时的参考价格
+
+        Returns:
+            List[Dict[str, Any]]: 各子订单的下单响应
+        """
+        annotate(symbol=order.symbol, policy=self.policy.value, quantity=order.quantity)
+
+        if self.policy == ExecutionPolicy.MARKET or order.order_type != OrderType.MARKET:
+            return [await self.client.place_order(order)]
+
+        limit_price = self._passive_price(order.symbol, order.side)
+        if limit_price is None:
+            logger.warning(f"[执行] {order.symbol} 无可用盘口数据，直接市价成交")
+            return [await self.client.place_order(order)]
+
+        return await self._limit_then_market(order, limit_price, reference_price)
+
+    async def _limit_then_market(self, order: Order, limit_price: float,
+                                 reference_price: Optional[float]) -> List[Dict[str, Any]]:
+        """
+        先挂限价单，超时撤单后市价补足剩余数量
+
+        等待期间若盘口偏离挂单价格超过阈值，撤单并按最新盘口重新挂单（受频率限制）。
+        """
+        responses: List[Dict[str, Any]] = []
+        remaining = float(order.quantity)
+        deadline = time.monotonic() + self.limit_wait_ms / 1000
+        self.limit_attempts += 1
+
+        while True:
+            limit_order = order.model_copy(update={
+                "order_type": OrderType.LIMIT, "price": limit_price, "quantity": remaining
+            })
+            response = await self.client.place_order(limit_order)
+            responses.append(response)
+            order_id = str(response.get("data", {}).get("orderId", ""))
+            if not order_id:
+                raise ValueError(f"限价单响应缺少订单ID: {response}")
+
+            self.tracker.register(order_id, limit_order, reference_price)
+            logger.info(
+                f"[执行] {order.symbol} 挂限价单 {order.side.value} {remaining} @ {limit_price}，"
+                f"剩余等待 {max(deadline - time.monotonic(), 0) * 1000:.0f}ms"
+            )
+
+            tracked, requote_price = await self._wait_or_requote(order, order_id, deadline)
+            if tracked.status == "FILLED":
+                self.limit_filled += 1
+                logger.info(f"[执行] {order.symbol} 限价单完全成交，均价 {tracked.avg_fill_price:.4f}")
+                return responses
+
+            # 超时或需要改价：撤单并等待最终状态，确认实际成交数量
+            if not tracked.is_done:
+                try:
+                    await self.client.cancel_order(order.symbol, order_id)
+                except Exception as e:
+                    logger.warning(f"[执行] {order.symbol} 撤销限价单失败（可能已成交）: {e}")
+                tracked = await self.tracker.wait_until_done(order_id, self.CANCEL_CONFIRM_TIMEOUT)
+
+            if tracked.status == "FILLED":
+                self.limit_filled += 1
+                return responses
+
+            if not tracked.is_done:
+                # 未收到撤单确认，无法确定已成交数量，放弃补单以免超额成交
+                logger.warning(f"[执行] {order.symbol} 未收到订单 {order_id} 的最终状态，放弃后续下单")
+                return responses
+
+            remaining -= tracked.filled
+            if remaining <= 1e-12:
+                return responses
+
+            if requote_price is None:
+                break
+
+            self.requotes += 1
+            self._requote_times.setdefault(order.symbol, deque()).append(time.monotonic())
+            logger.info(f"[执行] {order.symbol} 盘口移动，改价重挂 {limit_price} -> {requote_price}")
+            limit_price = requote_price
+
+        self.escalations += 1
+        logger.info(
+            f"[执行] {order.symbol} 限价单超时（已成交 {float(order.quantity) - remaining}/{order.quantity}），"
+            f"市价补足 {remaining}"
+        )
+        market_order = order.model_copy(update={"quantity": remaining})
+        response = await self.client.place_order(market_order)
+        responses.append(response)
+        order_id = str(response.get("data", {}).get("orderId", ""))
+        if order_id:
+            self.tracker.register(order_id, market_order, reference_price)
+        return responses
+
+    async def _wait_or_requote(self, order: Order, order_id: str, deadline: float) -> tuple:
+        """
+        等待挂单成交，期间检查是否需要改价
+
+        Returns:
+            tuple: (跟踪记录, 新的挂单价格或None)
+        """
+        while True:
+            wait = deadline - time.monotonic()
+            if self.requote_threshold_ticks > 0:
+                wait = min(wait, self.REQUOTE_CHECK_INTERVAL)
+
+            tracked = await self.tracker.wait_until_done(order_id, max(wait, 0))
+            if tracked.is_done or time.monotonic() >= deadline:
+                return tracked, None
+
+            new_price = self._requote_price(order.symbol, order.side, tracked.price)
+            if new_price is not None:
+                return tracked, new_price
+
+    def _requote_price(self, symbol: str, side: OrderSide, resting_price: Optional[float]) -> Optional[float]:
+        """
+        判断挂单是否需要改价
+
+        Returns:
+            Optional[float]: 新的挂单价格，无需改价或超过改价频率时返回None
+        """
+        if self.requote_threshold_ticks <= 0 or not resting_price:
+            return None
+
+        tick = self.client.get_tick_size(symbol)
+        new_price = self._passive_price(symbol, side)
+        if not tick or new_price is None:
+            return None
+
+        if abs(new_price - resting_price) < self.requote_threshold_ticks * tick:
+            return None
+
+        # 改价频率限制（每交易对每分钟）
+        times = self._requote_times.setdefault(symbol, deque())
+        now = time.monotonic()
+        while times and now - times[0] > 60:
+            times.popleft()
+        if len(times) >= self.max_requotes_per_minute:
+            return None
+
+        return new_price
+
+    async def cancel_expired(self) -> List[str]:
+        """
+        撤销超过存活时间的挂单（防止信号失效后很久才成交）
+
+        Returns:
+            List[str]: 已请求撤销的订单ID
+        """
+        canceled = []
+        for tracked in self.tracker.get_expired_orders():
+            tracked.cancel_requested = True
+            try:
+                await self.client.cancel_order(tracked.symbol, tracked.order_id)
+                canceled.append(tracked.order_id)
+                self.expired_cancels += 1
+                logger.info(
+                    f"[执行] {tracked.symbol} 订单 {tracked.order_id} 超过存活时间 {tracked.ttl_ms}ms，"
+                    f"已撤单（已成交 {tracked.filled}/{tracked.quantity}）"
+                )
+            except Exception as e:
+                logger.warning(f"[执行] {tracked.symbol} 撤销超时订单 {tracked.order_id} 失败: {e}")
+        return canceled
+
+    def _passive_price(self, symbol: str, side: OrderSide) -> Optional[float]:
+        """
+        计算被动限价
+
+        买单挂在买一价（卖单挂在卖一价），按配置向盘口内侧偏移若干tick，
+        但不会越过对手价（保持挂单方身份）。
+
+        Returns:
+            Optional[float]: 限价，盘口数据不可用时返回None
+        """
+        book = self.book_provider(symbol) if self.book_provider else None
+        if not book or not book.bids or not book.asks:
+            return None
+
+        best_bid = float(book.bids[0][0])
+        best_ask = float(book.asks[0][0])
+        if best_bid <= 0 or best_ask <= 0 or best_bid >= best_ask:
+            return None
+
+        tick = self.client.get_tick_size(symbol) or 0.0
+        offset = tick * self.limit_price_offset_ticks
+
+        if side == OrderSide.BUY:
+            price = best_bid + offset
+            if price >= best_ask:
+                price = best_ask - tick if tick > 0 else best_bid
+        else:
+            price = best_ask - offset
+            if price <= best_bid:
+                price = best_bid + tick if tick > 0 else best_ask
+
+        if tick > 0:
+            price = round(round(price / tick) * tick, 10)
+        return price
+
+    def get_stats(self) -> Dict[str, Any]:
+        """获取执行统计"""
+        return {
+            "policy": self.policy.value,
+            "limit_attempts": self.limit_attempts,
+            "limit_filled": self.limit_filled,
+            "escalations": self.escalations,
+            "expired_cancels": self.expired_cancels,
+            "requotes": self.requotes,
+        }
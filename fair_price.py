@@ -0,0 +1,45 @@
+"""
+盘口公允价格模块
+
+用本地订单簿前N档计算按挂单量加权的公允价格，作为信号生成的可选价格来源（替代ticker最新成交价）：
+- 买卖两侧分别计算前N档的成交量加权均价（VWAP）
+- 两侧均价按对手盘挂单量交叉加权：买盘挂单越多，公允价格越靠近卖方均价（买压更强），反之亦然
+N=1 时即为常用的微观价格（microprice）。最新成交价只在成交时变化并在买卖价之间跳动，
+公允价格随盘口挂单变化连续更新，对偏离均线的判断噪声更小。
+"""
+
+from typing import List, Optional
+
+from edgex_types import OrderBook
+
+
+def _side_vwap(levels: List[List[float]], n: int) -> tuple:
+    """前n档的挂单量加权均价和总挂单量"""
+    size = 0.0
+    value = 0.0
+    for price, qty in levels[:n]:
+        size += qty
+        value += price * qty
+    return (value / size if size > 0 else 0.0), size
+
+
+def weighted_mid(book: Optional[OrderBook], levels: int = 5) -> Optional[float]:
+    """
+    计算盘口加权公允价格
+
+    Args:
+        book: 本地订单簿
+        levels: 每侧统计的档位数
+
+    Returns:
+        Optional[float]: 公允价格，盘口缺失、单边为空或交叉时为None
+    """
+    if not book or not book.bids or not book.asks:
+        return None
+    if book.bids[0][0] >= book.asks[0][0]:
+        return None
+    bid_price, bid_size = _side_vwap(book.bids, levels)
+    ask_price, ask_size = _side_vwap(book.asks, levels)
+    if bid_size <= 0 or ask_size <= 0:
+        return None
+    return (bid_price * ask_size + ask_price * bid_size) / (bid_size + ask_size)
@@ -0,0 +1,21 @@
+"""
+JSON解码
+
+行情热路径（ticker/depth/trades）使用：安装 orjson 时使用 orjson，否则回退到标准库 json。
+两者解码结果一致，解码失败都抛出 ValueError 的子类。
+"""
+
+import json
+from typing import Any, Union
+
+try:
+    import orjson
+    BACKEND = "orjson"
+
+    def loads(data: Union[str, bytes]) -> Any:
+        return orjson.loads(data)
+except ImportError:
+    BACKEND = "json"
+
+    def loads(data: Union[str, bytes]) -> Any:
+        return json.loads(data)
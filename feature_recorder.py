@@ -0,0 +1,192 @@
+"""
+特征记录模块（可选，写入Parquet需要 pyarrow）
+
+在每个决策点（每次生成交易信号时）记录完整的特征向量，经过各前瞻时长后附上前瞻收益率作为标签，
+生成离线训练用的数据集。模型特征由 features.extract_features 计算，与实盘推理完全一致。
+每行包含：
+- timestamp（毫秒）、symbol、price（决策时价格）
+- 模型特征（features.FEATURE_NAMES）
+- 盘口状态: best_bid、best_ask、bid_depth_5、ask_depth_5
+- 成交流（最近 flow_window 秒的逐笔成交）: flow_buy_volume、flow_sell_volume、flow_trades、flow_imbalance
+- 基差: basis_bps（永续价格相对指数价格，未启用基差监控或无数据时为空）
+- 信号: signal（LONG/SHORT/HOLD）、confidence
+- 标签: forward_return_<N>s（决策后N秒的价格相对决策价格的收益率）
+所有前瞻时长都有标签后该行才会写入；停止时仍未标注完成的行丢弃。
+数据按批写入目录下的 features-<时间>-<序号>.parquet 文件（Parquet不支持追加），可用 pandas.read_parquet(目录) 一次读取。
+"""
+
+import os
+import time
+from collections import deque
+from typing import Any, Deque, Dict, List, Optional, Tuple
+
+from loguru import logger
+
+from edgex_types import TradeSignal
+from features import FEATURE_NAMES, extract_features
+from script_strategy import SignalContext
+
+try:
+    import pyarrow as pa
+    import pyarrow.parquet as pq
+    PARQUET_AVAILABLE = True
+except ImportError:
+    PARQUET_AVAILABLE = False
+
+FLUSH_ROWS = 1000
+FLUSH_INTERVAL_SECONDS = 300
+
+
+def parse_horizons(text: str) -> List[int]:
+    """
+    解析前瞻时长（秒，逗号分隔）
+
+    Raises:
+        ValueError: 格式错误或时长不大于0
+    """
+    try:
+        horizons = sorted({int(item) for item in text.split(",") if item.strip()})
+    except ValueError:
+        raise ValueError(f"前瞻时长必须为整数秒: {text}")
+    if not horizons or horizons[0] <= 0:
+        raise ValueError(f"前瞻时长必须大于0: {text}")
+    return horizons
+
+
+class TradeFlow:
+    """最近一段时间的逐笔成交统计"""
+
+    def __init__(self, window: float):
+        """
+        Args:
+            window: 统计窗口（秒）
+        """
+        self.window = window
+        self.trades: Dict[str, Deque[Tuple[float, float, Optional[bool]]]] = {}
+
+    def on_trade(self, symbol: str, size: float, is_buyer_maker: Optional[bool], now: float):
+        self.trades.setdefault(symbol, deque()).append((now, size, is_buyer_maker))
+
+    def metrics(self, symbol: str, now: float) -> Dict[str, float]:
+        trades = self.trades.get(symbol, deque())
+        while trades and trades[0][0] < now - self.window:
+            trades.popleft()
+        # 卖方为挂单方（is_buyer_maker=False）时为主动买入
+        buy = sum(size for _, size, maker in trades if maker is False)
+        sell = sum(size for _, size, maker in trades if maker is True)
+        return {
+            "flow_buy_volume": buy,
+            "flow_sell_volume": sell,
+            "flow_trades": float(len(trades)),
+            "flow_imbalance": (buy - sell) / (buy + sell) if buy + sell > 0 else 0.0,
+        }
+
+
+class FeatureRecorder:
+    """决策点特征记录"""
+
+    def __init__(self, directory: str, horizons: List[int], flow_window: float = 60.0):
+        """
+        Args:
+            directory: 输出目录
+            horizons: 前瞻时长（秒）
+            flow_window: 成交流统计窗口（秒）
+        """
+        os.makedirs(directory, exist_ok=True)
+        self.directory = directory
+        self.horizons = sorted(horizons)
+        self.flow = TradeFlow(flow_window)
+        self._pending: Dict[str, Deque[Dict[str, Any]]] = {}   # 交易对 -> 等待标注的行（按时间顺序）
+        self._labeled: List[Dict[str, Any]] = []
+        self._last_flush = time.time()
+        self._files = 0
+        self.stats = {"recorded": 0, "labeled": 0, "written": 0, "dropped": 0, "errors": 0}
+
+    def on_trade(self, symbol: str, price: float, size: float, is_buyer_maker: Optional[bool] = None):
+        """逐笔成交回调"""
+        self.flow.on_trade(symbol, size, is_buyer_maker, time.time())
+
+    def record(self, ctx: SignalContext, signal: TradeSignal, now: Optional[float] = None):
+        """
+        记录决策点（先用当前价格标注之前的行）
+
+        Args:
+            ctx: 生成信号时的行情上下文
+            signal: 生成的交易信号
+            now: 当前时间（秒）
+        """
+        now = time.time() if now is None else now
+        self._label(ctx.symbol, ctx.price, now)
+        features = extract_features(ctx)
+        if features is None or ctx.price <= 0:
+            return
+        row: Dict[str, Any] = {
+            "timestamp": int(now * 1000),
+            "symbol": ctx.symbol,
+            "price": ctx.price,
+            **dict(zip(FEATURE_NAMES, features)),
+            "best_bid": ctx.best_bid(),
+            "best_ask": ctx.best_ask(),
+            "bid_depth_5": ctx.depth("bid", 5),
+            "ask_depth_5": ctx.depth("ask", 5),
+            **self.flow.metrics(ctx.symbol, now),
+            "basis_bps": ctx.basis_bps,
+            "signal": signal.direction.value,
+            "confidence": signal.confidence,
+        }
+        self._pending.setdefault(ctx.symbol, deque()).append(row)
+        self.stats["recorded"] += 1
+        self.maybe_flush(now)
+
+    def _label(self, symbol: str, price: float, now: float):
+        """为已经过前瞻时长的行填入前瞻收益率"""
+        pending = self._pending.get(symbol)
+        if not pending or price <= 0:
+            return
+        for row in pending:
+            elapsed = now - row["timestamp"] / 1000
+            if elapsed < self.horizons[0]:
+                break
+            for horizon in self.horizons:
+                key = f"forward_return_{horizon}s"
+                if elapsed >= horizon and key not in row:
+                    row[key] = price / row["price"] - 1
+        while pending and f"forward_return_{self.horizons[-1]}s" in pending[0]:
+            self._labeled.append(pending.popleft())
+            self.stats["labeled"] += 1
+
+    def maybe_flush(self, now: Optional[float] = None):
+        """已标注的行达到批大小或距上次写入超过间隔时写入文件"""
+        now = time.time() if now is None else now
+        if len(self._labeled) >= FLUSH_ROWS or (self._labeled and now - self._last_flush >= FLUSH_INTERVAL_SECONDS):
+            self.flush()
+
+    def flush(self):
+        """把已标注的行写入新的Parquet文件（失败时保留，下次重试）"""
+        self._last_flush = time.time()
+        if not self._labeled:
+            return
+        self._files += 1
+        path = os.path.join(self.directory, f"features-{time.strftime('%Y%m%d-%H%M%S')}-{self._files:04d}.parquet")
+        try:
+            pq.write_table(pa.Table.from_pylist(self._labeled), path)
+        except Exception as e:
+            self.stats["errors"] += 1
+            logger.error(f"[特征记录] 写入 {path} 失败: {e}")
+            return
+        self.stats["written"] += len(self._labeled)
+        logger.info(f"[特征记录] 已写入 {len(self._labeled)} 行: {path}")
+        self._labeled = []
+
+    def close(self):
+        """写入已标注的行，丢弃尚未标注完成的行"""
+        self.flush()
+        dropped = sum(len(rows) for rows in self._pending.values())
+        if dropped:
+            self.stats["dropped"] += dropped
+            logger.info(f"[特征记录] 停止时 {dropped} 行尚未标注完成，已丢弃")
+        self._pending.clear()
+
+    def get_stats(self) -> Dict[str, Any]:
+        pending = sum(len(rows) for rows in self._pending.values())
+        return {**self.stats, "pending": pending, "directory": self.directory}
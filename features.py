@@ -0,0 +1,152 @@
+"""
+特征提取模块
+
+机器学习信号（见 ml_signal.py）的输入特征。实盘和离线训练数据使用同一个提取函数，保证训练与推理一致：
+- 实盘: 由价格序列和本地订单簿构造 SignalContext 后调用 extract_features
+- 离线: 按时间回放撮合引擎的回放事件文件（见 historical_data.py），在每个价格事件处用同样方式提取特征，
+  并附上之后 horizon 个价格事件的收益率作为训练标签
+
+特征（顺序固定，模型输入为 [1, len(FEATURE_NAMES)] 的float32张量）：
+- deviation_5 / deviation_20: 当前价格相对5/20周期均线的偏离比例
+- zscore_20: 当前价格相对最近20个价格的z-score
+- return_1 / return_10: 最近1/10个价格的收益率
+- volatility_20: 最近20个对数收益率的标准差
+- spread_bps: 买卖价差（bps，无盘口时为0）
+- imbalance_1 / imbalance_5: 盘口第1档/前5档不平衡度
+
+用法：
+  python features.py export data/history/btc.jsonl --symbol BTC-USDT --horizon 10 --out data/features/btc.csv
+"""
+
+import argparse
+import csv
+import math
+import os
+from collections import deque
+from typing import Deque, Dict, Iterable, Iterator, List, Optional, Tuple
+
+from edgex_types import OrderBook, PriceData
+from historical_data import read_events
+from script_strategy import SignalContext
+
+FEATURE_NAMES = [
+    "deviation_5", "deviation_20", "zscore_20", "return_1", "return_10",
+    "volatility_20", "spread_bps", "imbalance_1", "imbalance_5",
+]
+
+# 提取特征所需的最少价格数
+MIN_HISTORY = 21
+
+
+def _zscore(closes: List[float], period: int) -> float:
+    window = closes[-period:]
+    if len(window) < 3:
+        return 0.0
+    mean = sum(window) / len(window)
+    std = math.sqrt(sum((c - mean) ** 2 for c in window) / (len(window) - 1))
+    return (window[-1] - mean) / std if std > 0 else 0.0
+
+
+def _return(closes: List[float], period: int) -> float:
+    if len(closes) <= period or closes[-period - 1] <= 0:
+        return 0.0
+    return closes[-1] / closes[-period - 1] - 1
+
+
+def extract_features(ctx: SignalContext) -> Optional[List[float]]:
+    """
+    提取特征向量
+
+    Returns:
+        Optional[List[float]]: 按 FEATURE_NAMES 顺序的特征，价格数据不足 MIN_HISTORY 个时返回None
+    """
+    if len(ctx.closes) < MIN_HISTORY:
+        return None
+    return [
+        ctx.deviation(5),
+        ctx.deviation(20),
+        _zscore(ctx.closes, 20),
+        _return(ctx.closes, 1),
+        _return(ctx.closes, 10),
+        ctx.volatility(20),
+        ctx.spread_bps(),
+        ctx.imbalance(1),
+        ctx.imbalance(5),
+    ]
+
+
+def replay_features(events: Iterable[Dict], symbol: str, horizon: int,
+                    history: int = 200) -> Iterator[Tuple[int, List[float], float]]:
+    """
+    回放事件并在每个价格（price/trade）事件处提取特征
+
+    Args:
+        events: 按时间排序的回放事件
+        symbol: 交易对
+        horizon: 标签的前瞻价格事件个数
+        history: 保留的价格个数
+
+    Yields:
+        (时间戳, 特征, 之后 horizon 个价格事件的收益率)
+    """
+    klines: List[PriceData] = []
+    book: Optional[OrderBook] = None
+    pending: Deque[Tuple[int, int, List[float], float]] = deque()   # 等待标签的 (序号, 时间戳, 特征, 价格)
+    index = 0
+    for event in events:
+        if event.get("symbol") != symbol:
+            continue
+        event_type = event.get("type")
+        timestamp = int(event.get("timestamp", 0))
+        if event_type == "depth":
+            book = OrderBook(symbol=symbol, bids=event.get("bids", []), asks=event.get("asks", []), timestamp=timestamp)
+            continue
+        if event_type not in ("price", "trade"):
+            continue
+        price = float(event["price"])
+        klines.append(PriceData(timestamp=timestamp, open=price, high=price, low=price, close=price, volume=0.0))
+        del klines[:-history]
+
+        while pending and index - pending[0][0] >= horizon:
+            _, ts, features, entry = pending.popleft()
+            yield ts, features, price / entry - 1 if entry > 0 else 0.0
+        features = extract_features(SignalContext(symbol, klines, book, None, {}))
+        if features is not None:
+            pending.append((index, timestamp, features, price))
+        index += 1
+
+
+def export_features(events: Iterable[Dict], symbol: str, horizon: int, path: str) -> int:
+    """
+    导出训练数据（CSV：timestamp、各特征、forward_return）
+
+    Returns:
+        int: 写入的样本数
+    """
+    os.makedirs(os.path.dirname(path) or ".", exist_ok=True)
+    count = 0
+    with open(path, "w", newline="", encoding="utf-8") as fp:
+        writer = csv.writer(fp)
+        writer.writerow(["timestamp", *FEATURE_NAMES, "forward_return"])
+        for timestamp, features, label in replay_features(events, symbol, horizon):
+            writer.writerow([timestamp, *features, label])
+            count += 1
+    return count
+
+
+def main():
+    parser = argparse.ArgumentParser(description="机器学习信号特征提取")
+    sub = parser.add_subparsers(dest="command", required=True)
+    export_parser = sub.add_parser("export", help="由回放事件文件导出训练数据")
+    export_parser.add_argument("path", help="回放事件文件（JSON Lines）")
+    export_parser.add_argument("--symbol", required=True, help="交易对")
+    export_parser.add_argument("--horizon", type=int, default=10, help="标签的前瞻价格事件个数（默认10）")
+    export_parser.add_argument("--out", required=True, help="输出文件（CSV）")
+
+    args = parser.parse_args()
+    count = export_features(read_events(args.path), args.symbol, args.horizon, args.out)
+    print(f"已写入 {count} 个样本: {args.out}")
+
+
+if __name__ == "__main__":
+    main()
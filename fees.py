@@ -0,0 +1,91 @@
+"""
+手续费等级模块
+
+从交易所获取账户在各合约上的吃单/挂单手续费率，替代"手续费为0"的假设：
+- 费率来源: 账户对该合约的单独设置（contractIdToTradeSetting）> 账户默认设置（defaultTradeSetting），
+  设置中 isSetFeeRate 为真时使用其 takerFeeRate / makerFeeRate；都没有设置时使用合约默认费率
+  （元数据 defaultTakerFeeRate / defaultMakerFeeRate）。模拟盘使用合约默认费率
+- 预期收益检查: 开仓的预期收益（止盈距离）低于往返手续费 × min_edge_multiple 时跳过开仓；
+  开仓按执行策略计费（限价优先时按挂单费率，否则按吃单费率），平仓按吃单费率计
+- 模拟撮合: 费率同步给模拟撮合引擎，模拟成交按挂单/吃单收取手续费
+"""
+
+from typing import Any, Dict, Optional, Tuple
+
+from edgex_types import FeeRates
+
+
+def _float(value: Any) -> float:
+    try:
+        return float(value) if value not in (None, "") else 0.0
+    except (TypeError, ValueError):
+        return 0.0
+
+
+def parse_fee_setting(setting: Optional[Dict[str, Any]]) -> Optional[Tuple[float, float]]:
+    """
+    解析账户交易设置中的手续费率
+
+    Returns:
+        Optional[Tuple[float, float]]: (吃单费率, 挂单费率)，未单独设置费率时为None
+    """
+    if not setting or str(setting.get("isSetFeeRate", "")).lower() != "true":
+        return None
+    return _float(setting.get("takerFeeRate")), _float(setting.get("makerFeeRate"))
+
+
+class FeeSchedule:
+    """各合约的手续费率与预期收益检查"""
+
+    def __init__(self, min_edge_multiple: float = 1.0):
+        """
+        Args:
+            min_edge_multiple: 预期收益至少为往返手续费的倍数（0为不检查）
+        """
+        self.min_edge_multiple = min_edge_multiple
+        self.rates: Dict[str, FeeRates] = {}
+        self.skipped: Dict[str, int] = {}
+
+    def update(self, rates: Dict[str, FeeRates]) -> Dict[str, FeeRates]:
+        """
+        更新费率
+
+        Returns:
+            Dict[str, FeeRates]: 费率有变化的交易对
+        """
+        changed = {symbol: rate for symbol, rate in rates.items() if self.rates.get(symbol) != rate}
+        self.rates.update(rates)
+        return changed
+
+    def round_trip(self, symbol: str, entry_maker: bool = False) -> Optional[float]:
+        """往返手续费率（开仓 + 吃单平仓，费率未知时为None）"""
+        rate = self.rates.get(symbol)
+        if rate is None:
+            return None
+        return (rate.maker if entry_maker else rate.taker) + rate.taker
+
+    def check_edge(self, symbol: str, expected_return: float, entry_maker: bool = False) -> Optional[str]:
+        """
+        检查开仓的预期收益是否足以覆盖往返手续费
+
+        Args:
+            expected_return: 预期收益率（止盈距离 / 开仓价）
+
+        Returns:
+            Optional[str]: 跳过原因，允许开仓时返回None（费率未知时不检查）
+        """
+        if self.min_edge_multiple <= 0:
+            return None
+        cost = self.round_trip(symbol, entry_maker)
+        if cost is None or expected_return >= cost * self.min_edge_multiple:
+            return None
+        self.skipped[symbol] = self.skipped.get(symbol, 0) + 1
+        return (f"预期收益 {expected_return * 10000:.2f} bps 低于往返手续费 {cost * 10000:.2f} bps"
+                + (f" × {self.min_edge_multiple}" if self.min_edge_multiple != 1 else ""))
+
+    def get_stats(self) -> Dict[str, Any]:
+        return {
+            "min_edge_multiple": self.min_edge_multiple,
+            "rates": {symbol: rate.model_dump() for symbol, rate in self.rates.items()},
+            "skipped": dict(self.skipped),
+        }
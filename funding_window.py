@@ -0,0 +1,119 @@
+"""
+资金费结算规避模块
+
+资金费在结算时刻按持仓收取：费率为正时多头支付空头，为负时空头支付多头。刷量策略持仓时间短，
+可以在结算前的一段时间内避免持有需要支付资金费的仓位：
+- 资金费率和下次结算时间来自ticker（fundingRate / nextFundingTime），WebSocket推送时更新，没有及时推送时通过REST接口获取；
+  ticker未提供下次结算时间时按合约的资金费结算间隔（fundingRateIntervalMin，从UTC零点起整倍数）推算
+- 结算前 window_seconds 内，需要支付资金费的方向（|费率| 不低于 min_rate）延后开仓，结算后恢复
+- 动作为 close 时，窗口内需要支付资金费的已有持仓同时主动平仓（收取资金费的一方照常开仓和持有）
+资金费率未知时不限制。
+"""
+
+import time
+from typing import Any, Dict, Optional
+
+from edgex_types import FundingRate
+
+FUNDING_ACTIONS = ("delay", "close")
+
+
+def _float(value: Any) -> float:
+    try:
+        return float(value) if value not in (None, "") else 0.0
+    except (TypeError, ValueError):
+        return 0.0
+
+
+def parse_funding_rate(data: Dict[str, Any]) -> Optional[FundingRate]:
+    """
+    解析ticker中的资金费率和下次结算时间（时间戳为接收时间）
+
+    Returns:
+        Optional[FundingRate]: ticker不含资金费率时为None
+    """
+    if data.get("fundingRate") in (None, ""):
+        return None
+    return FundingRate(
+        timestamp=int(time.time() * 1000),
+        funding_rate=_float(data.get("fundingRate")),
+        next_funding_time=int(_float(data.get("nextFundingTime"))),
+    )
+
+
+def next_settlement(interval_minutes: int, now_ms: int) -> Optional[int]:
+    """按结算间隔推算下次结算时间（毫秒，间隔未知时为None）"""
+    if interval_minutes <= 0:
+        return None
+    interval_ms = interval_minutes * 60 * 1000
+    return (now_ms // interval_ms + 1) * interval_ms
+
+
+class FundingWindow:
+    """资金费结算前的开仓延后与平仓"""
+
+    def __init__(self, window_seconds: float = 300.0, min_rate: float = 0.0, action: str = "delay"):
+        """
+        Args:
+            window_seconds: 结算前多少秒内规避需要支付资金费的持仓
+            min_rate: 资金费率绝对值低于此值时不规避
+            action: delay（只延后开仓）或 close（同时平掉需要支付资金费的持仓）
+        """
+        self.window_seconds = window_seconds
+        self.min_rate = min_rate
+        self.action = action
+        self.rates: Dict[str, FundingRate] = {}
+
+        # 统计：交易对 -> 次数
+        self.delayed: Dict[str, int] = {}
+        self.closed: Dict[str, int] = {}
+
+    def update(self, symbol: str, rate: FundingRate):
+        """更新交易对的资金费率（较旧的数据忽略）"""
+        current = self.rates.get(symbol)
+        if current is None or rate.timestamp >= current.timestamp:
+            self.rates[symbol] = rate
+
+    def seconds_to_settlement(self, symbol: str, interval_minutes: int = 0,
+                              now: Optional[float] = None) -> Optional[float]:
+        """距下次结算的秒数（无法确定时为None）"""
+        now = now if now is not None else time.time()
+        now_ms = int(now * 1000)
+        rate = self.rates.get(symbol)
+        settlement = rate.next_funding_time if rate and rate.next_funding_time > now_ms else None
+        settlement = settlement or next_settlement(interval_minutes, now_ms)
+        return (settlement - now_ms) / 1000 if settlement else None
+
+    def avoid(self, symbol: str, direction: int, interval_minutes: int = 0,
+              now: Optional[float] = None) -> Optional[str]:
+        """
+        该方向的持仓是否需要规避下次结算
+
+        Args:
+            direction: 持仓方向（1为多头，-1为空头）
+            interval_minutes: 合约资金费结算间隔（ticker未提供下次结算时间时使用）
+
+        Returns:
+            Optional[str]: 需要规避时返回原因
+        """
+        rate = self.rates.get(symbol)
+        if rate is None or abs(rate.funding_rate) < self.min_rate or direction * rate.funding_rate <= 0:
+            return None
+        remaining = self.seconds_to_settlement(symbol, interval_minutes, now)
+        if remaining is None or remaining > self.window_seconds:
+            return None
+        side = "多头" if direction > 0 else "空头"
+        return f"资金费率 {rate.funding_rate:+.6f}，{remaining:.0f} 秒后结算，{side}需支付资金费"
+
+    def record(self, symbol: str, closed: bool = False):
+        counts = self.closed if closed else self.delayed
+        counts[symbol] = counts.get(symbol, 0) + 1
+
+    def get_stats(self) -> Dict[str, Any]:
+        return {
+            "window_seconds": self.window_seconds,
+            "action": self.action,
+            "rates": {symbol: rate.funding_rate for symbol, rate in self.rates.items()},
+            "delayed": dict(self.delayed),
+            "closed": dict(self.closed),
+        }
@@ -0,0 +1,277 @@
+"""
+外部历史数据导入
+
+EdgeX 新上线合约的历史数据有限，回测时可以使用第三方数据源。本模块把第三方数据归一化为
+撮合引擎的回放事件（与TCA记录的 market.jsonl 格式一致），可直接用于 MatchingEngine.replay、
+VolumeProfile.load 等：
+- depth: {"type": "depth", "symbol", "bids": [[价格, 数量], ...], "asks": [...], "timestamp"}
+- trade: {"type": "trade", "symbol", "price", "size", "is_buyer_maker", "timestamp"}
+- price: {"type": "price", "symbol", "price", "timestamp"}
+- mark_price: {"type": "mark_price", "symbol", "price", "timestamp"}
+
+支持的数据源：
+- binance-klines: Binance K线（data.binance.vision 的CSV，有无表头均可），按收盘时间生成 price 事件
+- tardis-trades: tardis.dev trades
+- tardis-book: tardis.dev book_snapshot_5 / book_snapshot_25
+- tardis-ticker: tardis.dev derivative_ticker（标记价格和最新价）
+
+文件可为 .csv、.csv.gz、.zip（读取第一个文件）或同样列结构的 .parquet（需要 pyarrow）。
+时间戳统一换算为毫秒（秒/毫秒/微秒自动识别）。
+
+用法：
+  python historical_data.py import tardis-trades trades.csv.gz --symbol BTC-USDT --out data/history/btc.jsonl
+  python historical_data.py merge data/history/btc_trades.jsonl data/history/btc_book.jsonl --out data/history/btc.jsonl
+"""
+
+import argparse
+import csv
+import gzip
+import heapq
+import io
+import json
+import os
+import zipfile
+from typing import Any, Callable, Dict, Iterable, Iterator, List, Optional
+
+from edgex_types import PriceData
+
+# Binance K线CSV的列（无表头时使用）
+BINANCE_KLINE_COLUMNS = [
+    "open_time", "open", "high", "low", "close", "volume", "close_time",
+    "quote_volume", "count", "taker_buy_volume", "taker_buy_quote_volume", "ignore",
+]
+
+
+def to_millis(value: Any) -> int:
+    """秒/毫秒/微秒时间戳统一换算为毫秒"""
+    ts = int(float(value))
+    if ts >= 10 ** 14:
+        return ts // 1000
+    if ts >= 10 ** 11:
+        return ts
+    return ts * 1000
+
+
+def _read_table(path: str, columns: Optional[List[str]] = None) -> Iterator[Dict[str, Any]]:
+    """
+    逐行读取表格文件
+
+    Args:
+        path: 文件路径（.csv / .csv.gz / .zip / .parquet）
+        columns: 无表头时使用的列名（首行首列为数字时视为无表头）
+
+    Yields:
+        Dict[str, Any]: 列名 -> 值
+    """
+    if path.endswith(".parquet"):
+        try:
+            import pandas as pd
+            frame = pd.read_parquet(path)
+        except ImportError as e:
+            raise ImportError(f"读取Parquet需要安装 pyarrow: {e}")
+        yield from frame.to_dict("records")
+        return
+
+    if path.endswith(".zip"):
+        with zipfile.ZipFile(path) as archive:
+            text = io.TextIOWrapper(archive.open(archive.namelist()[0]), encoding="utf-8")
+            yield from _read_csv(text, columns)
+        return
+
+    opener = gzip.open if path.endswith(".gz") else open
+    with opener(path, "rt", encoding="utf-8") as fp:
+        yield from _read_csv(fp, columns)
+
+
+def _read_csv(fp, columns: Optional[List[str]]) -> Iterator[Dict[str, Any]]:
+    reader = csv.reader(fp)
+    first = next(reader, None)
+    if first is None:
+        return
+    try:
+        float(first[0])
+        has_header = False
+    except ValueError:
+        has_header = True
+
+    if has_header:
+        header = first
+    elif columns is None:
+        raise ValueError("文件没有表头，且该数据源没有默认列名")
+    else:
+        header = columns
+        yield dict(zip(header, first))
+    for row in reader:
+        if row:
+            yield dict(zip(header, row))
+
+
+def _symbol(row: Dict[str, Any], symbol: Optional[str]) -> str:
+    """输出交易对：指定时覆盖数据源中的交易对"""
+    if symbol:
+        return symbol
+    if not row.get("symbol"):
+        raise ValueError("数据中没有交易对，需要指定 --symbol")
+    return str(row["symbol"])
+
+
+def load_binance_klines(path: str) -> List[PriceData]:
+    """
+    读取Binance K线为 PriceData 序列（策略信号回放使用）
+
+    Args:
+        path: K线文件路径
+
+    Returns:
+        List[PriceData]: 按开盘时间排序的K线（timestamp为开盘时间，毫秒）
+    """
+    klines = [
+        PriceData(
+            timestamp=to_millis(row["open_time"]),
+            open=float(row["open"]),
+            high=float(row["high"]),
+            low=float(row["low"]),
+            close=float(row["close"]),
+            volume=float(row["volume"])
+        )
+        for row in _read_table(path, BINANCE_KLINE_COLUMNS)
+    ]
+    klines.sort(key=lambda k: k.timestamp)
+    return klines
+
+
+def binance_klines(path: str, symbol: Optional[str] = None) -> Iterator[Dict[str, Any]]:
+    """Binance K线 -> price 事件（收盘时间、收盘价）"""
+    for row in _read_table(path, BINANCE_KLINE_COLUMNS):
+        yield {
+            "type": "price", "symbol": _symbol(row, symbol),
+            "price": float(row["close"]), "timestamp": to_millis(row["close_time"])
+        }
+
+
+def tardis_trades(path: str, symbol: Optional[str] = None) -> Iterator[Dict[str, Any]]:
+    """tardis.dev trades -> trade 事件（side为主动方，sell表示买方为挂单方）"""
+    for row in _read_table(path):
+        side = str(row.get("side", "")).lower()
+        yield {
+            "type": "trade", "symbol": _symbol(row, symbol),
+            "price": float(row["price"]), "size": float(row["amount"]),
+            "is_buyer_maker": side == "sell" if side in ("buy", "sell") else None,
+            "timestamp": to_millis(row["timestamp"])
+        }
+
+
+def tardis_book(path: str, symbol: Optional[str] = None) -> Iterator[Dict[str, Any]]:
+    """tardis.dev book_snapshot_N -> depth 事件（完整快照）"""
+    for row in _read_table(path):
+        levels = {"bids": [], "asks": []}
+        for side in levels:
+            i = 0
+            while f"{side}[{i}].price" in row:
+                price, amount = row[f"{side}[{i}].price"], row[f"{side}[{i}].amount"]
+                if price not in ("", None) and amount not in ("", None):
+                    levels[side].append([float(price), float(amount)])
+                i += 1
+        yield {
+            "type": "depth", "symbol": _symbol(row, symbol),
+            "bids": levels["bids"], "asks": levels["asks"],
+            "timestamp": to_millis(row["timestamp"])
+        }
+
+
+def tardis_ticker(path: str, symbol: Optional[str] = None) -> Iterator[Dict[str, Any]]:
+    """tardis.dev derivative_ticker -> mark_price 事件（及有最新价时的 price 事件）"""
+    for row in _read_table(path):
+        ts = to_millis(row["timestamp"])
+        sym = _symbol(row, symbol)
+        if row.get("mark_price") not in ("", None):
+            yield {"type": "mark_price", "symbol": sym, "price": float(row["mark_price"]), "timestamp": ts}
+        if row.get("last_price") not in ("", None):
+            yield {"type": "price", "symbol": sym, "price": float(row["last_price"]), "timestamp": ts}
+
+
+SOURCES: Dict[str, Callable[[str, Optional[str]], Iterator[Dict[str, Any]]]] = {
+    "binance-klines": binance_klines,
+    "tardis-trades": tardis_trades,
+    "tardis-book": tardis_book,
+    "tardis-ticker": tardis_ticker,
+}
+
+
+def import_events(source: str, paths: Iterable[str], symbol: Optional[str] = None) -> List[Dict[str, Any]]:
+    """
+    导入第三方数据为回放事件
+
+    Args:
+        source: 数据源（见 SOURCES）
+        paths: 数据文件（可多个，如按天分割的文件）
+        symbol: 输出交易对（如 BTC-USDT），None时使用数据中的交易对
+
+    Returns:
+        List[Dict[str, Any]]: 按时间排序的回放事件
+    """
+    if source not in SOURCES:
+        raise ValueError(f"不支持的数据源: {source}（可选: {', '.join(SOURCES)}）")
+    events = [event for path in paths for event in SOURCES[source](path, symbol)]
+    events.sort(key=lambda e: e["timestamp"])
+    return events
+
+
+def read_events(path: str) -> Iterator[Dict[str, Any]]:
+    """读取回放事件文件（JSON Lines）"""
+    with open(path, encoding="utf-8") as fp:
+        for line in fp:
+            if line.strip():
+                yield json.loads(line)
+
+
+def merge_events(*streams: Iterable[Dict[str, Any]]) -> Iterator[Dict[str, Any]]:
+    """按时间戳合并多个已排序的事件流（如同一交易对的成交、盘口和标记价格）"""
+    return heapq.merge(*streams, key=lambda e: e["timestamp"])
+
+
+def write_events(events: Iterable[Dict[str, Any]], path: str) -> int:
+    """
+    写入回放事件文件
+
+    Returns:
+        int: 写入的事件数
+    """
+    os.makedirs(os.path.dirname(path) or ".", exist_ok=True)
+    count = 0
+    with open(path, "w", encoding="utf-8") as fp:
+        for event in events:
+            fp.write(json.dumps(event, ensure_ascii=False) + "\n")
+            count += 1
+    return count
+
+
+def main():
+    parser = argparse.ArgumentParser(description="外部历史数据导入")
+    sub = parser.add_subparsers(dest="command", required=True)
+
+    import_parser = sub.add_parser("import", help="导入第三方数据为回放事件")
+    import_parser.add_argument("source", choices=list(SOURCES), help="数据源")
+    import_parser.add_argument("paths", nargs="+", help="数据文件")
+    import_parser.add_argument("--symbol", help="输出交易对（如 BTC-USDT），默认使用数据中的交易对")
+    import_parser.add_argument("--out", required=True, help="输出文件（JSON Lines）")
+
+    merge_parser = sub.add_parser("merge", help="按时间合并多个回放事件文件")
+    merge_parser.add_argument("paths", nargs="+", help="回放事件文件")
+    merge_parser.add_argument("--out", required=True, help="输出文件（JSON Lines）")
+
+    args = parser.parse_args()
+    try:
+        if args.command == "import":
+            events = import_events(args.source, args.paths, args.symbol)
+        else:
+            events = merge_events(*(read_events(p) for p in args.paths))
+        count = write_events(events, args.out)
+    except (ValueError, KeyError) as e:
+        print(f"导入失败: {e}")
+        raise SystemExit(1)
+    print(f"已写入 {count} 个事件: {args.out}")
+
+
+if __name__ == "__main__":
+    main()
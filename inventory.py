@@ -0,0 +1,56 @@
+"""
+库存偏斜模块
+
+跟踪所有持仓的净方向敞口（库存 = Σ 带方向名义价值 / 权益，多头为正），按库存调整新开仓位，
+使刷量策略的净敞口围绕零均值回归：
+- 增加库存的信号（库存为多时做多、库存为空时做空）按 1 - 强度 × 库存比例 缩小仓位，库存达到上限时不开仓
+- 减少库存的信号按 1 + 强度 × 库存比例 放大仓位（库存比例 = |库存| / 上限，最大为1）
+- 库存为零时不调整
+"""
+
+from typing import Any, Dict
+
+
+class InventorySkew:
+    """库存偏斜"""
+
+    def __init__(self, max_inventory: float, strength: float = 1.0):
+        """
+        Args:
+            max_inventory: 库存上限（净名义敞口 / 权益）
+            strength: 偏斜强度（1为库存达到上限时不再增加库存、减少库存的仓位加倍）
+        """
+        self.max_inventory = max_inventory
+        self.strength = strength
+        self.inventory = 0.0
+        self.stats = {"reduced": 0, "boosted": 0, "blocked": 0}
+
+    def update(self, weights: Dict[str, float]) -> float:
+        """按各交易对的带方向权重更新净库存"""
+        self.inventory = sum(weights.values())
+        return self.inventory
+
+    def scale(self, direction: int) -> float:
+        """
+        新开仓位的调整倍数
+
+        Args:
+            direction: 开仓方向（1为做多，-1为做空）
+
+        Returns:
+            float: 仓位倍数（0为不开仓）
+        """
+        ratio = max(-1.0, min(1.0, self.inventory / self.max_inventory))
+        skew = direction * ratio
+        if skew > 0:
+            scale = max(0.0, 1.0 - self.strength * skew)
+            self.stats["blocked" if scale <= 0 else "reduced"] += 1
+            return scale
+        if skew < 0:
+            self.stats["boosted"] += 1
+            return 1.0 - self.strength * skew
+        return 1.0
+
+    def get_stats(self) -> Dict[str, Any]:
+        return {"inventory": round(self.inventory, 4), "max_inventory": self.max_inventory,
+                "strength": self.strength, **self.stats}
@@ -0,0 +1,127 @@
+"""
+交易链路延迟分析模块
+
+用HDR直方图记录交易链路各阶段的耗时（微秒精度，记录 O(1)，内存固定），用于发现延迟回退：
+- signal_to_order: 生成信号到订单交给客户端（风控检查、仓位计算、账户查询等）
+- order_to_ack: 下单请求发出到收到交易所确认（REST往返，仅实盘）
+- loop_iteration: 策略主循环单轮耗时（不含等待）
+
+每隔固定时间在日志中输出本周期的分位数（p50/p90/p99/p99.9/最大值），
+累计分位数在性能报告和指标接口（/metrics，见 metrics_server.py）中输出。
+
+依赖 hdrhistogram 包；未安装时延迟分析自动禁用，不影响交易主流程。
+"""
+
+import time
+from typing import Dict, List, Optional
+
+from loguru import logger
+
+try:
+    from hdrh.histogram import HdrHistogram
+    HDR_AVAILABLE = True
+except ImportError:
+    HDR_AVAILABLE = False
+
+LATENCY_SIGNAL_TO_ORDER = "signal_to_order"
+LATENCY_ORDER_TO_ACK = "order_to_ack"
+LATENCY_LOOP_ITERATION = "loop_iteration"
+STAGES = {
+    LATENCY_SIGNAL_TO_ORDER: "信号->下单",
+    LATENCY_ORDER_TO_ACK: "下单->确认",
+    LATENCY_LOOP_ITERATION: "主循环",
+}
+PERCENTILES = (50.0, 90.0, 99.0, 99.9)
+
+# 记录范围：1微秒 ~ 60秒，3位有效数字
+_LOWEST_US = 1
+_HIGHEST_US = 60_000_000
+_SIGNIFICANT_FIGURES = 3
+
+
+def _new_histogram() -> "HdrHistogram":
+    return HdrHistogram(_LOWEST_US, _HIGHEST_US, _SIGNIFICANT_FIGURES)
+
+
+def _summarize(histogram: "HdrHistogram") -> Dict[str, float]:
+    """直方图摘要（毫秒）"""
+    summary = {"count": histogram.get_total_count()}
+    for p in PERCENTILES:
+        summary[f"p{p:g}"] = histogram.get_value_at_percentile(p) / 1000
+    summary["max"] = histogram.get_max_value() / 1000
+    return summary
+
+
+class LatencyProfiler:
+    """交易链路延迟直方图"""
+
+    def __init__(self, report_seconds: float = 60.0):
+        """
+        Args:
+            report_seconds: 日志输出周期分位数的间隔（秒）
+        """
+        self.report_seconds = report_seconds
+        self._total = {stage: _new_histogram() for stage in STAGES}
+        self._interval = {stage: _new_histogram() for stage in STAGES}
+        self._sum_us = {stage: 0 for stage in STAGES}
+        self._last_report = time.time()
+
+    @classmethod
+    def create(cls, config) -> Optional["LatencyProfiler"]:
+        """按配置创建（未启用或未安装hdrhistogram时返回None）"""
+        if not getattr(config, "latency_profiling_enabled", False):
+            return None
+        if not HDR_AVAILABLE:
+            logger.warning("已启用延迟分析，但未安装hdrhistogram依赖，延迟分析将被禁用")
+            return None
+        return cls(getattr(config, "latency_report_seconds", 60.0))
+
+    def record(self, stage: str, seconds: float):
+        """记录一次耗时（秒）"""
+        us = min(max(int(seconds * 1_000_000), _LOWEST_US), _HIGHEST_US)
+        self._total[stage].record_value(us)
+        self._interval[stage].record_value(us)
+        self._sum_us[stage] += us
+
+    def record_since(self, stage: str, start: Optional[float]):
+        """记录从 start（time.perf_counter()）到现在的耗时，start为None时忽略"""
+        if start is not None:
+            self.record(stage, time.perf_counter() - start)
+
+    def maybe_report(self, label: str = "", now: Optional[float] = None):
+        """距上次输出超过间隔时在日志中输出本周期分位数并清空周期直方图（主循环调用）"""
+        now = time.time() if now is None else now
+        if now - self._last_report < self.report_seconds:
+            return
+        self._last_report = now
+        lines: List[str] = []
+        for stage, name in STAGES.items():
+            histogram = self._interval[stage]
+            if histogram.get_total_count() == 0:
+                continue
+            s = _summarize(histogram)
+            lines.append(f"{name} {s['count']}次 p50 {s['p50']:.2f} p90 {s['p90']:.2f} "
+                         f"p99 {s['p99']:.2f} p99.9 {s['p99.9']:.2f} max {s['max']:.2f}ms")
+            histogram.reset()
+        if lines:
+            logger.info(f"[延迟]{f' {label}' if label else ''} " + " | ".join(lines))
+
+    def get_summary(self) -> Dict[str, Dict[str, float]]:
+        """各阶段的累计分位数（毫秒）"""
+        return {stage: _summarize(h) for stage, h in self._total.items() if h.get_total_count()}
+
+    def prometheus_samples(self) -> List[tuple]:
+        """
+        Prometheus summary 样本
+
+        Returns:
+            List[tuple]: (阶段, 分位数或"count"/"sum", 值)，耗时单位为秒，没有样本时分位数为NaN
+        """
+        samples = []
+        for stage, histogram in self._total.items():
+            count = histogram.get_total_count()
+            for p in PERCENTILES:
+                samples.append((stage, p / 100, histogram.get_value_at_percentile(p) / 1_000_000 if count else float("nan")))
+            samples.append((stage, "count", count))
+            samples.append((stage, "sum", self._sum_us[stage] / 1_000_000))
+        return samples
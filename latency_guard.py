@@ -0,0 +1,142 @@
+"""
+延迟保护模块
+
+行情数据滞后加上撤单变慢时，按信号下的市价单和常驻盘口的挂单都容易被快速行情吃掉。按最近的延迟分位数判断
+连接状态是否恶化：
+- REST延迟: 每次REST请求的往返时间（发出到收到响应，超时的请求按超时预算计）
+- WebSocket延迟: 收到ticker的本地时间 - ticker中的交易所时间戳（依赖本地时钟与交易所时钟同步）
+- 最近 window 个样本的 percentile 分位数超过 rest_limit_ms 或 ws_limit_ms 时进入降级状态：
+  暂停按信号开仓（平仓不受影响），双边挂单的价差按 quote_widen 倍放宽
+- 两项延迟都回到上限以内并持续 cooldown_seconds 后恢复
+样本不足 MIN_SAMPLES 个时不判断该项延迟。
+"""
+
+import time
+from collections import deque
+from typing import Any, Deque, Dict, Optional
+
+# 计算分位数所需的最少样本数
+MIN_SAMPLES = 20
+
+
+def nearest_rank(samples, q: float) -> Optional[float]:
+    """样本的q分位数（q为0-100，最近秩法，无样本时为None）"""
+    if not samples:
+        return None
+    ordered = sorted(samples)
+    index = max(0, min(len(ordered) - 1, int(len(ordered) * q / 100 + 0.5) - 1))
+    return ordered[index]
+
+
+class LatencyGuard:
+    """按REST/WebSocket延迟分位数降级交易"""
+
+    def __init__(self, percentile: float = 99.0, rest_limit_ms: float = 500.0, ws_limit_ms: float = 1000.0,
+                 window: int = 200, cooldown_seconds: float = 30.0, quote_widen: float = 2.0):
+        """
+        Args:
+            percentile: 判断使用的延迟分位数（0-100）
+            rest_limit_ms: REST延迟分位数上限（毫秒，0为不检查）
+            ws_limit_ms: WebSocket延迟分位数上限（毫秒，0为不检查）
+            window: 每项延迟保留的最近样本数
+            cooldown_seconds: 延迟回到上限以内后持续多少秒恢复
+            quote_widen: 降级期间双边挂单价差的放宽倍数
+        """
+        self.percentile = percentile
+        self.rest_limit_ms = rest_limit_ms
+        self.ws_limit_ms = ws_limit_ms
+        self.cooldown_seconds = cooldown_seconds
+        self.quote_widen = quote_widen
+        self.rest_samples: Deque[float] = deque(maxlen=window)
+        self.ws_samples: Deque[float] = deque(maxlen=window)
+
+        self.degraded = False
+        self.reason = ""
+        self._healthy_since: Optional[float] = None
+
+        # 统计
+        self.degraded_count = 0
+        self.degraded_seconds = 0.0
+        self.blocked_entries = 0
+        self._degraded_at: Optional[float] = None
+
+    def record_rest(self, elapsed_ms: float):
+        """记录一次REST请求的往返时间"""
+        self.rest_samples.append(elapsed_ms)
+
+    def record_ws(self, exchange_ms: int, now: Optional[float] = None):
+        """按交易所时间戳记录一条WebSocket消息的延迟"""
+        if exchange_ms <= 0:
+            return
+        now = now if now is not None else time.time()
+        self.ws_samples.append(max(0.0, now * 1000 - exchange_ms))
+
+    def current(self) -> Dict[str, Optional[float]]:
+        """当前REST/WebSocket延迟分位数（毫秒，样本不足时为None）"""
+        return {
+            "rest_ms": nearest_rank(self.rest_samples, self.percentile) if len(self.rest_samples) >= MIN_SAMPLES else None,
+            "ws_ms": nearest_rank(self.ws_samples, self.percentile) if len(self.ws_samples) >= MIN_SAMPLES else None,
+        }
+
+    def _breach(self) -> Optional[str]:
+        latency = self.current()
+        label = f"p{self.percentile:g}"
+        if self.rest_limit_ms > 0 and latency["rest_ms"] is not None and latency["rest_ms"] > self.rest_limit_ms:
+            return f"REST延迟{label} {latency['rest_ms']:.0f}ms 超过上限 {self.rest_limit_ms:g}ms"
+        if self.ws_limit_ms > 0 and latency["ws_ms"] is not None and latency["ws_ms"] > self.ws_limit_ms:
+            return f"WebSocket延迟{label} {latency['ws_ms']:.0f}ms 超过上限 {self.ws_limit_ms:g}ms"
+        return None
+
+    def update(self, now: Optional[float] = None) -> Optional[bool]:
+        """
+        按最近的延迟更新降级状态
+
+        Returns:
+            Optional[bool]: 进入降级时为True，恢复时为False，状态未变化时为None
+        """
+        now = now if now is not None else time.time()
+        breach = self._breach()
+        if breach:
+            self._healthy_since = None
+            self.reason = breach
+            if not self.degraded:
+                self.degraded = True
+                self.degraded_count += 1
+                self._degraded_at = now
+                return True
+            return None
+        if not self.degraded:
+            return None
+        if self._healthy_since is None:
+            self._healthy_since = now
+        if now - self._healthy_since < self.cooldown_seconds:
+            return None
+        self.degraded = False
+        self.reason = ""
+        self._healthy_since = None
+        if self._degraded_at is not None:
+            self.degraded_seconds += now - self._degraded_at
+            self._degraded_at = None
+        return False
+
+    def spread_scale(self) -> float:
+        """双边挂单价差倍数（降级期间放宽）"""
+        return self.quote_widen if self.degraded else 1.0
+
+    def get_stats(self) -> Dict[str, Any]:
+        latency = self.current()
+        degraded_seconds = self.degraded_seconds
+        if self._degraded_at is not None:
+            degraded_seconds += time.time() - self._degraded_at
+        return {
+            "percentile": self.percentile,
+            "rest_ms": round(latency["rest_ms"], 1) if latency["rest_ms"] is not None else None,
+            "ws_ms": round(latency["ws_ms"], 1) if latency["ws_ms"] is not None else None,
+            "rest_limit_ms": self.rest_limit_ms,
+            "ws_limit_ms": self.ws_limit_ms,
+            "degraded": self.degraded,
+            "reason": self.reason,
+            "degraded_count": self.degraded_count,
+            "degraded_seconds": round(degraded_seconds, 1),
+            "blocked_entries": self.blocked_entries,
+        }
@@ -0,0 +1,93 @@
+"""
+开仓前盘口过滤模块
+
+下单前根据WebSocket本地订单簿检查当前买卖价差和对手盘可见深度：
+- 价差超过阈值（bps）时跳过开仓，避免在流动性差时支付过高的点差成本
+- 对手盘前若干档的挂单量小于下单数量时跳过开仓，避免市价单吃穿多档
+被跳过的信号按交易对和原因统计。
+"""
+
+from typing import Dict, Optional, Any
+
+from edgex_types import OrderBook, OrderSide
+
+
+class LiquidityFilter:
+    """价差与流动性过滤器"""
+
+    # 跳过原因
+    NO_BOOK = "no_book"
+    WIDE_SPREAD = "wide_spread"
+    THIN_BOOK = "thin_book"
+
+    def __init__(self, max_spread_bps: float = 10.0, depth_levels: int = 1):
+        """
+        初始化过滤器
+
+        Args:
+            max_spread_bps: 允许的最大买卖价差（bps，相对中间价）
+            depth_levels: 计算可见流动性时统计的对手盘档位数
+        """
+        self.max_spread_bps = max_spread_bps
+        self.depth_levels = depth_levels
+
+        # 统计：交易对 -> 原因 -> 跳过次数
+        self.checked = 0
+        self.skipped: Dict[str, Dict[str, int]] = {}
+
+    def check(self, symbol: str, book: Optional[OrderBook], side: OrderSide, quantity: float) -> Optional[str]:
+        """
+        检查是否允许开仓
+
+        Args:
+            symbol: 交易对
+            book: 本地订单簿
+            side: 下单方向
+            quantity: 下单数量
+
+        Returns:
+            Optional[str]: 跳过原因，允许开仓时返回None
+        """
+        self.checked += 1
+        reason = self._reject_reason(book, side, quantity)
+        if reason:
+            counts = self.skipped.setdefault(symbol, {})
+            counts[reason] = counts.get(reason, 0) + 1
+        return reason
+
+    def _reject_reason(self, book: Optional[OrderBook], side: OrderSide, quantity: float) -> Optional[str]:
+        if not book or not book.bids or not book.asks:
+            return self.NO_BOOK
+
+        best_bid = float(book.bids[0][0])
+        best_ask = float(book.asks[0][0])
+        if best_bid <= 0 or best_ask <= best_bid:
+            return self.NO_BOOK
+
+        if self.spread_bps(book) > self.max_spread_bps:
+            return self.WIDE_SPREAD
+
+        levels = book.asks if side == OrderSide.BUY else book.bids
+        visible = sum(float(q) for _, q in levels[:self.depth_levels])
+        if visible < quantity:
+            return self.THIN_BOOK
+
+        return None
+
+    @staticmethod
+    def spread_bps(book: OrderBook) -> float:
+        """买卖价差（bps，相对中间价）"""
+        best_bid = float(book.bids[0][0])
+        best_ask = float(book.asks[0][0])
+        mid = (best_bid + best_ask) / 2
+        return (best_ask - best_bid) / mid * 10000 if mid > 0 else 0.0
+
+    def get_summary(self) -> Dict[str, Any]:
+        """获取过滤统计"""
+        return {
+            "max_spread_bps": self.max_spread_bps,
+            "depth_levels": self.depth_levels,
+            "checked": self.checked,
+            "skipped_total": sum(sum(c.values()) for c in self.skipped.values()),
+            "skipped": {symbol: dict(counts) for symbol, counts in self.skipped.items()},
+        }
@@ -0,0 +1,71 @@
+"""
+日志文件轮转模块
+
+文件日志（文本和JSON）按天轮转，并限制单个文件大小：
+- 每天第一条日志写入新文件（按日志时间的本地日期）
+- 当前文件写入下一条日志后会超过大小上限时提前轮转，同一天的旧文件由 loguru 重命名保留
+- 超过保留天数的文件删除，可选压缩已轮转的文件
+"""
+
+import os
+from datetime import date
+from typing import Optional
+
+from loguru import logger
+
+LOG_COMPRESSIONS = ("", "gz", "zip")
+
+
+class DailySizeRotation:
+    """按天和文件大小轮转（loguru 的 rotation 回调，每个日志处理器使用独立实例）"""
+
+    def __init__(self, max_bytes: int = 0):
+        """
+        Args:
+            max_bytes: 单个文件大小上限（字节，0为不限制）
+        """
+        self.max_bytes = max_bytes
+        self._day: Optional[date] = None
+
+    def __call__(self, message, file) -> bool:
+        day = message.record["time"].date()
+        if self._day is None:
+            self._day = day
+        if day != self._day:
+            self._day = day
+            return True
+        if self.max_bytes > 0:
+            file.seek(0, os.SEEK_END)
+            return file.tell() + len(message) > self.max_bytes
+        return False
+
+
+def add_file_logging(config):
+    """
+    按配置添加文件日志处理器（文本日志，及可选的JSON日志）
+
+    Args:
+        config: 配置对象（log_dir / log_max_file_mb / log_retention_days / log_compression / log_json）
+    """
+    os.makedirs(config.log_dir, exist_ok=True)
+    max_bytes = int(config.log_max_file_mb * 1024 * 1024)
+    options = {
+        "level": "DEBUG",
+        "retention": f"{config.log_retention_days} days",
+        "compression": config.log_compression or None,
+        "encoding": "utf-8",
+    }
+    logger.add(
+        os.path.join(config.log_dir, "trading_bot_{time:YYYY-MM-DD}.log"),
+        format="{time:YYYY-MM-DD HH:mm:ss} | {level: <8} | {name}:{function}:{line} - {message}",
+        rotation=DailySizeRotation(max_bytes),
+        **options
+    )
+    if config.log_json:
+        # 每行一个JSON对象（loguru 序列化格式，含时间、级别、模块、消息和异常）
+        logger.add(
+            os.path.join(config.log_dir, "trading_bot_{time:YYYY-MM-DD}.json.log"),
+            serialize=True,
+            rotation=DailySizeRotation(max_bytes),
+            **options
+        )
@@ -1,7 +1,12 @@
 """
 EdgeX高频交易机器人主程序
+
+用法:
+    python main.py            运行机器人
+    python main.py console    运行机器人并打开手动交易控制台（见 manual_console.py）
 """
 
+import argparse
 import asyncio
 import signal
 import sys
@@ -10,15 +15,29 @@ from loguru import logger
 from config import load_config, validate_config
 from strategy import HighFrequencyStrategy
 from monitor import PerformanceMonitor
+from manual_console import ManualConsole
+from allocator import CapitalAllocator, load_strategy_configs
+from ab_test import ABTest
+from tracing import init_tracing, shutdown_tracing
+from error_reporting import init_error_reporting, shutdown_error_reporting
+from log_rotation import add_file_logging
+from runtime import configure_runtime, install_event_loop
+from metrics_server import MetricsPusher, MetricsRenderer, MetricsServer
+from report_sinks import ReportDispatcher, ReportMetrics
 import edgex_types  # 确保模块被导入
 
 class TradingBot:
     """交易机器人主类"""
     
-    def __init__(self):
-        self.config = load_config()
-        self.strategy = None
-        self.monitor = None
+    def __init__(self, config=None, console: bool = False):
+        self.config = config or load_config()
+        self.console_enabled = console
+        self.strategies = []
+        self.monitors = []
+        self.allocator = None
+        self.metrics_server = None
+        self.metrics_pusher = None
+        self.report_metrics = ReportMetrics()
         self.is_running = False
         
         # 设置日志
@@ -38,14 +57,8 @@ class TradingBot:
             level="INFO"
         )
         
-        # 添加文件输出
-        logger.add(
-            "logs/trading_bot_{time:YYYY-MM-DD}.log",
-            format="{time:YYYY-MM-DD HH:mm:ss} | {level: <8} | {name}:{function}:{line} - {message}",
-            level="DEBUG",
-            rotation="1 day",
-            retention="30 days"
-        )
+        # 添加文件输出（按天和大小轮转，可选JSON日志）
+        add_file_logging(self.config)
         
         logger.info("日志系统初始化完成")
     
@@ -68,17 +81,59 @@ class TradingBot:
                 logger.error("配置验证失败，请检查配置文件")
                 return False
             
-            if not self.config.stark_private_key or not self.config.account_id:
+            if not self.config.paper_trading and (not self.config.stark_private_key or not self.config.account_id):
                 logger.error("Stark私钥或账户ID未配置，请检查.env文件")
                 logger.error("必填项：EDGEX_STARK_PRIVATE_KEY 和 EDGEX_ACCOUNT_ID")
                 return False
             
-            # 创建策略实例
-            self.strategy = HighFrequencyStrategy(self.config)
-            logger.info("策略初始化完成")
+            # 运行时调优（默认线程池、事件循环线程绑核）
+            configure_runtime(self.config)
+            
+            # 初始化链路追踪（可选）
+            init_tracing(self.config)
+            
+            # 初始化错误上报（可选）
+            init_error_reporting(self.config)
             
-            # 创建性能监控器
-            self.monitor = PerformanceMonitor(self.strategy)
+            # 创建策略实例（配置策略列表时按权重分配资金并发运行多个策略）
+            if self.config.strategies_file:
+                self.allocator = CapitalAllocator(
+                    eval_seconds=self.config.allocation_eval_seconds,
+                    throttle_drawdown=self.config.allocation_throttle_drawdown,
+                    disable_drawdown=self.config.allocation_disable_drawdown,
+                    throttle_scale=self.config.allocation_throttle_scale,
+                    min_trades=self.config.allocation_min_trades,
+                    min_win_rate=self.config.allocation_min_win_rate
+                )
+                for entry in load_strategy_configs(self.config, self.config.strategies_file):
+                    if not validate_config(entry["config"]):
+                        logger.error(f"策略 {entry['name']} 配置验证失败")
+                        return False
+                    strategy = HighFrequencyStrategy(entry["config"], name=entry["name"])
+                    self.allocator.add(entry["name"], entry["weight"], strategy)
+                    self.strategies.append(strategy)
+            else:
+                self.strategies.append(HighFrequencyStrategy(self.config))
+            logger.info(f"策略初始化完成（{len(self.strategies)} 个）")
+            
+            # A/B测试：对照组的性能报告中输出两个变体的对比
+            ab_test = None
+            if self.config.ab_test:
+                by_name = {strategy.name: strategy for strategy in self.strategies}
+                names = [name.strip() for name in self.config.ab_test.split(",")]
+                missing = [name for name in names if name not in by_name]
+                if missing:
+                    logger.error(f"A/B测试的策略不存在: {', '.join(missing)}")
+                    return False
+                ab_test = ABTest(by_name[names[0]], by_name[names[1]])
+            
+            # 创建性能监控器（各策略共用报告输出目标）
+            sinks = ReportDispatcher.from_config(self.config, self.report_metrics)
+            self.monitors = [
+                PerformanceMonitor(strategy, ab_test=ab_test if ab_test and strategy is ab_test.variant_a else None,
+                                   sinks=sinks)
+                for strategy in self.strategies
+            ]
             logger.info("性能监控器初始化完成")
             
             # 创建价格流（可选，用于WebSocket实时数据）
@@ -106,13 +161,34 @@ class TradingBot:
             self.is_running = True
             
             # 启动性能监控
-            await self.monitor.start_monitoring()
+            for monitor in self.monitors:
+                await monitor.start_monitoring()
+            
+            # 启动指标接口和指标推送（可选）
+            renderer = MetricsRenderer(
+                lambda: {s.name: s.latency for s in self.strategies if s.latency},
+                reports=self.report_metrics,
+                pre_trade=lambda: {s.name: s.client.pre_trade for s in self.strategies}
+            )
+            if self.config.metrics_enabled:
+                self.metrics_server = MetricsServer(self.config.metrics_host, self.config.metrics_port, renderer)
+                await self.metrics_server.start()
+            if self.config.metrics_push_url:
+                self.metrics_pusher = MetricsPusher(
+                    renderer, self.config.metrics_push_url, self.config.metrics_push_format,
+                    self.config.metrics_push_interval, self.config.metrics_push_job, self.config.metrics_push_token
+                )
+                await self.metrics_pusher.start()
             
             # 启动策略
-            strategy_task = asyncio.create_task(self.strategy.run())
+            tasks = [asyncio.create_task(strategy.run()) for strategy in self.strategies]
+            if self.allocator:
+                tasks.append(asyncio.create_task(self.allocator.run()))
+            if self.console_enabled:
+                tasks.append(asyncio.create_task(ManualConsole(self.strategies).run()))
             
             # 等待任务完成
-            await asyncio.gather(strategy_task, return_exceptions=True)
+            await asyncio.gather(*tasks, return_exceptions=True)
             
         except Exception as e:
             logger.error(f"启动失败: {e}")
@@ -129,12 +205,23 @@ class TradingBot:
         
         try:
             # 停止策略
-            if self.strategy:
-                self.strategy.stop()
+            if self.allocator:
+                self.allocator.stop()
+            for strategy in self.strategies:
+                strategy.stop()
             
             # 停止监控
-            if self.monitor:
-                await self.monitor.stop_monitoring()
+            for monitor in self.monitors:
+                await monitor.stop_monitoring()
+            
+            if self.metrics_server:
+                await self.metrics_server.stop()
+            if self.metrics_pusher:
+                await self.metrics_pusher.stop()
+            
+            # 刷新追踪数据
+            shutdown_tracing()
+            shutdown_error_reporting()
             
             logger.info("交易机器人已停止")
             
@@ -156,19 +243,26 @@ class TradingBot:
         finally:
             await self.stop()
 
-async def main():
+async def main(config=None, console: bool = False):
     """主函数"""
     # 创建必要的目录
     import os
     os.makedirs("logs", exist_ok=True)
     
     # 创建并运行机器人
-    bot = TradingBot()
+    bot = TradingBot(config, console=console)
     await bot.run()
 
 if __name__ == "__main__":
+    parser = argparse.ArgumentParser(description="EdgeX高频交易机器人")
+    sub = parser.add_subparsers(dest="command")
+    sub.add_parser("console", help="运行机器人并打开手动交易控制台")
+    args = parser.parse_args()
     try:
-        asyncio.run(main())
+        # 事件循环实现需要在启动事件循环之前选择
+        config = load_config()
+        install_event_loop(config.event_loop)
+        asyncio.run(main(config, console=args.command == "console"))
     except KeyboardInterrupt:
         logger.info("程序被用户中断")
     except Exception as e:
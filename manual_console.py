@@ -0,0 +1,181 @@
+"""
+手动交易控制台
+
+`python main.py console` 启动机器人的同时在终端读取手动操作命令，命令作用于运行中的策略。
+下单和撤单与自动交易经过相同的下单前检查（步长、频率、熔断开关）、重复订单抑制、订单通道和审计日志
+（审计记录标注 source=manual），成交回报同样更新持仓和交易记录：
+- order <交易对> <buy|sell> <数量> [限价] [reduce]: 下单，不带限价为市价单，reduce 为只减仓
+- cancel <交易对> <订单ID>: 撤单
+- close <交易对> [long|short]: 市价平掉该交易对的持仓（可只平一个方向）
+- leverage <交易对> <倍数>: 调整杠杆，交易所确认生效前该交易对禁止开仓
+- pause <交易对> / resume <交易对>: 暂停/恢复该交易对按信号开仓（已有持仓照常止盈止损）
+- status: 持仓、挂单和手动暂停的交易对
+- use <策略名>: 多策略运行时切换操作的策略
+- quit: 退出控制台（机器人继续运行）
+"""
+
+import asyncio
+import shlex
+import sys
+import threading
+from typing import Awaitable, Callable, Dict, List, Optional
+
+from loguru import logger
+
+from edgex_types import OrderSide, TradeDirection
+
+HELP = "\n".join(line[2:] for line in __doc__.splitlines() if line.startswith("- "))
+
+_SIDES = {"buy": OrderSide.BUY, "long": OrderSide.BUY, "sell": OrderSide.SELL, "short": OrderSide.SELL}
+_DIRECTIONS = {"long": TradeDirection.LONG, "short": TradeDirection.SHORT}
+
+
+class ConsoleError(ValueError):
+    """命令格式错误"""
+
+
+def _number(value: str, name: str) -> float:
+    try:
+        return float(value)
+    except ValueError:
+        raise ConsoleError(f"{name}必须是数字: {value}")
+
+
+class ManualConsole:
+    """手动交易控制台"""
+
+    def __init__(self, strategies: List):
+        """
+        Args:
+            strategies: 运行中的策略（默认操作第一个）
+        """
+        self.strategies = {strategy.name: strategy for strategy in strategies}
+        self.current = strategies[0]
+        self._commands: Dict[str, Callable[[List[str]], Awaitable[str]]] = {
+            "order": self._order,
+            "cancel": self._cancel,
+            "close": self._close,
+            "leverage": self._leverage,
+            "pause": self._pause,
+            "resume": self._resume,
+            "status": self._status,
+            "use": self._use,
+        }
+
+    async def execute(self, line: str) -> str:
+        """执行一行命令，返回输出（命令无效或操作失败时返回错误信息）"""
+        try:
+            args = shlex.split(line)
+        except ValueError as e:
+            return f"错误: {e}"
+        if not args:
+            return ""
+        command = args[0].lower()
+        if command == "help":
+            return HELP
+        handler = self._commands.get(command)
+        if handler is None:
+            return f"未知命令: {command}（输入 help 查看命令）"
+        try:
+            return await handler(args[1:])
+        except Exception as e:
+            logger.warning(f"[控制台] {line.strip()} 失败: {e}")
+            return f"错误: {e}"
+
+    async def run(self):
+        """从标准输入读取命令直到 quit 或输入结束"""
+        loop = asyncio.get_running_loop()
+        lines: asyncio.Queue = asyncio.Queue()
+
+        def read():
+            # 守护线程读取输入，退出时不等待阻塞的 readline
+            for line in sys.stdin:
+                loop.call_soon_threadsafe(lines.put_nowait, line)
+            loop.call_soon_threadsafe(lines.put_nowait, None)
+
+        threading.Thread(target=read, name="manual-console", daemon=True).start()
+        print(f"手动交易控制台（策略 {self.current.name}），输入 help 查看命令")
+        while True:
+            line = await lines.get()
+            if line is None or line.strip().lower() in ("quit", "exit"):
+                break
+            output = await self.execute(line)
+            if output:
+                print(output)
+        logger.info("[控制台] 已退出，机器人继续运行")
+
+    @staticmethod
+    def _expect(args: List[str], minimum: int, maximum: int, usage: str):
+        if not minimum <= len(args) <= maximum:
+            raise ConsoleError(f"用法: {usage}")
+
+    async def _order(self, args: List[str]) -> str:
+        self._expect(args, 3, 5, "order <交易对> <buy|sell> <数量> [限价] [reduce]")
+        side = _SIDES.get(args[1].lower())
+        if side is None:
+            raise ConsoleError(f"方向必须是 buy 或 sell: {args[1]}")
+        extra = [a.lower() for a in args[3:]]
+        reduce_only = "reduce" in extra
+        prices = [a for a in extra if a != "reduce"]
+        if len(prices) > 1:
+            raise ConsoleError("用法: order <交易对> <buy|sell> <数量> [限价] [reduce]")
+        price: Optional[float] = _number(prices[0], "限价") if prices else None
+        order_id = await self.current.manual_order(args[0], side, _number(args[2], "数量"), price, reduce_only)
+        return f"已提交订单 {order_id}"
+
+    async def _cancel(self, args: List[str]) -> str:
+        self._expect(args, 2, 2, "cancel <交易对> <订单ID>")
+        await self.current.manual_cancel(args[0], args[1])
+        return f"已撤销订单 {args[1]}"
+
+    async def _close(self, args: List[str]) -> str:
+        self._expect(args, 1, 2, "close <交易对> [long|short]")
+        direction = None
+        if len(args) == 2:
+            direction = _DIRECTIONS.get(args[1].lower())
+            if direction is None:
+                raise ConsoleError(f"持仓方向必须是 long 或 short: {args[1]}")
+        count = await self.current.manual_close(args[0], direction)
+        return f"已提交 {count} 个持仓的平仓订单"
+
+    async def _leverage(self, args: List[str]) -> str:
+        self._expect(args, 2, 2, "leverage <交易对> <倍数>")
+        leverage = _number(args[1], "杠杆倍数")
+        if leverage != int(leverage):
+            raise ConsoleError(f"杠杆倍数必须是整数: {args[1]}")
+        await self.current.manual_set_leverage(args[0], int(leverage))
+        return f"{args[0]} 杠杆已确认 {int(leverage)}x"
+
+    async def _pause(self, args: List[str]) -> str:
+        self._expect(args, 1, 1, "pause <交易对>")
+        self.current.manual_pause(args[0])
+        return f"{args[0]} 已暂停开仓"
+
+    async def _resume(self, args: List[str]) -> str:
+        self._expect(args, 1, 1, "resume <交易对>")
+        self.current.manual_pause(args[0], paused=False)
+        return f"{args[0]} 已恢复开仓"
+
+    async def _status(self, args: List[str]) -> str:
+        strategy = self.current
+        lines = [f"策略 {strategy.name} | 余额 {float(strategy.balance):.2f} USDT"]
+        for position in strategy.positions.values():
+            lines.append(f"持仓 {position.symbol} {position.direction.value} {position.size} @ {position.entry_price}"
+                         f" 止损 {position.stop_loss} 止盈 {position.take_profit}")
+        for tracked in strategy.order_tracker.get_open_orders():
+            lines.append(f"挂单 {tracked.order_id} {tracked.symbol} {tracked.side.value} {tracked.order_type.value} "
+                         f"{tracked.filled}/{tracked.quantity}" + (f" @ {tracked.price}" if tracked.price else "")
+                         + f" {tracked.status}")
+        if strategy.manual_paused:
+            lines.append(f"手动暂停开仓: {', '.join(sorted(strategy.manual_paused))}")
+        if strategy.leverage_blocked:
+            lines.append("杠杆未确认: " + ", ".join(f"{s}（{r}）" for s, r in strategy.leverage_blocked.items()))
+        return "\n".join(lines)
+
+    async def _use(self, args: List[str]) -> str:
+        self._expect(args, 1, 1, "use <策略名>")
+        strategy = self.strategies.get(args[0])
+        if strategy is None:
+            raise ConsoleError(f"策略不存在: {args[0]}（可选: {', '.join(self.strategies)}）")
+        self.current = strategy
+        return f"当前策略: {strategy.name}"
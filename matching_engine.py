@@ -0,0 +1,503 @@
+"""
+模拟撮合引擎（沙盒/模拟盘模式）
+
+基于实时或录制的深度与成交数据维护本地订单簿，对策略的模拟订单进行撮合：
+- 市价单按档位吃单，计入对本地订单簿的冲击
+- 限价单可成交部分立即成交，剩余部分挂单排队
+- 挂单记录排在其前面的挂单量（queue_ahead），只有前方队列被成交/撤单消耗后才会成交
+- 成交价穿越挂单价格时视为全部成交
+- 条件单（止损/止盈）在触发价格（最新价或标记价格）穿越触发价后按市价或限价执行；
+  尚未收到标记价格时，按标记价格触发的条件单以最新价判断
+- 按模拟成交维护持仓，只减仓订单（含触发后的条件单）的数量不超过可平持仓，没有可平持仓时取消
+
+用于模拟盘和回测，提供比"按收盘价成交"更真实的执行效果。
+"""
+
+import itertools
+import threading
+import time
+from typing import Callable, Dict, Iterable, List, Optional, Tuple
+from loguru import logger
+
+from edgex_types import (
+    Fill, Order, OrderSide, OrderType, TradeDirection, TriggerPriceType, MARKET_ORDER_TYPES, TRIGGER_ORDER_TYPES
+)
+
+
+class SimOrder:
+    """模拟订单"""
+
+    def __init__(self, order_id: str, order: Order):
+        self.order_id = order_id
+        self.symbol = order.symbol
+        self.side = order.side
+        self.order_type = order.order_type
+        self.price = order.price
+        self.trigger_price = order.trigger_price
+        self.trigger_price_type = order.trigger_price_type
+        self.quantity = float(order.quantity)
+        self.reduce_only = order.reduce_only
+        self.position_side = order.position_side
+        self.filled = 0.0
+        self.queue_ahead = 0.0
+        self.status = "UNTRIGGERED" if order.order_type in TRIGGER_ORDER_TYPES else "OPEN"
+        self.created_at = int(time.time() * 1000)
+
+    @property
+    def remaining(self) -> float:
+        """剩余未成交数量"""
+        return max(self.quantity - self.filled, 0.0)
+
+    def should_trigger(self, price: Optional[float]) -> bool:
+        """触发价格是否穿越触发价（止损向不利方向，止盈向有利方向）"""
+        if self.status != "UNTRIGGERED" or price is None:
+            return False
+        is_buy = self.side == OrderSide.BUY
+        if self.order_type == OrderType.TAKE_PROFIT_MARKET:
+            return price <= self.trigger_price if is_buy else price >= self.trigger_price
+        return price >= self.trigger_price if is_buy else price <= self.trigger_price
+
+    def to_dict(self) -> Dict:
+        """转换为与交易所订单字段一致的字典"""
+        return {
+            "id": self.order_id,
+            "contractId": self.symbol,
+            "side": self.side.value,
+            "type": self.order_type.value,
+            "price": str(self.price or 0),
+            "size": str(self.quantity),
+            "cumFillSize": str(self.filled),
+            "status": self.status,
+            "createdTime": str(self.created_at),
+            **({"triggerPrice": str(self.trigger_price), "triggerPriceType": self.trigger_price_type.value}
+               if self.trigger_price else {}),
+        }
+
+
+class SimulatedOrderBook:
+    """单个交易对的模拟订单簿"""
+
+    def __init__(self, symbol: str, taker_fee_rate: float = 0.0, maker_fee_rate: float = 0.0):
+        self.symbol = symbol
+        self.taker_fee_rate = taker_fee_rate
+        self.maker_fee_rate = maker_fee_rate
+        self.bids: Dict[float, float] = {}
+        self.asks: Dict[float, float] = {}
+        self.last_price: Optional[float] = None
+        self.mark_price: Optional[float] = None
+        self.resting: Dict[str, SimOrder] = {}
+        self.untriggered: Dict[str, SimOrder] = {}
+        # 上次深度快照以来各档位的成交量 (挂单方向, 价格) -> 数量，深度减少量扣除成交后才视为撤单
+        self._traded_at: Dict[Tuple[OrderSide, float], float] = {}
+
+    def best_bid(self) -> Optional[float]:
+        return max(self.bids) if self.bids else None
+
+    def best_ask(self) -> Optional[float]:
+        return min(self.asks) if self.asks else None
+
+    def apply_depth(self, bids: List[Tuple[float, float]], asks: List[Tuple[float, float]]) -> List[Fill]:
+        """
+        应用深度快照
+
+        档位数量减少中扣除上次快照以来该档位的成交量（成交已在 apply_trade 中扣减队列），
+        剩余部分视为撤单，按比例缩减排在模拟订单前方的队列。
+
+        Args:
+            bids: 买盘 [(价格, 数量), ...]
+            asks: 卖盘 [(价格, 数量), ...]
+
+        Returns:
+            List[Fill]: 因盘口穿越产生的成交
+        """
+        new_bids = {float(p): float(q) for p, q in bids if float(q) > 0}
+        new_asks = {float(p): float(q) for p, q in asks if float(q) > 0}
+
+        for order in self.resting.values():
+            book = self.bids if order.side == OrderSide.BUY else self.asks
+            new_book = new_bids if order.side == OrderSide.BUY else new_asks
+            # 成交后档位剩余的数量
+            old_qty = book.get(order.price, 0.0) - self._traded_at.get((order.side, order.price), 0.0)
+            new_qty = new_book.get(order.price, 0.0)
+            if old_qty > 0 and new_qty < old_qty:
+                order.queue_ahead *= new_qty / old_qty
+
+        self._traded_at.clear()
+        self.bids = new_bids
+        self.asks = new_asks
+
+        # 对手盘穿越挂单价格，视为挂单被成交
+        fills = []
+        best_bid = self.best_bid()
+        best_ask = self.best_ask()
+        for order in list(self.resting.values()):
+            if order.side == OrderSide.BUY and best_ask is not None and best_ask <= order.price:
+                fills.append(self._fill(order, order.remaining, order.price, is_maker=True))
+            elif order.side == OrderSide.SELL and best_bid is not None and best_bid >= order.price:
+                fills.append(self._fill(order, order.remaining, order.price, is_maker=True))
+
+        return fills
+
+    def apply_trade(self, price: float, size: float, is_buyer_maker: Optional[bool] = None) -> List[Fill]:
+        """
+        应用一笔市场成交
+
+        Args:
+            price: 成交价格
+            size: 成交数量
+            is_buyer_maker: 买方是否为挂单方（True表示主动卖出）
+
+        Returns:
+            List[Fill]: 模拟挂单的成交
+        """
+        self.last_price = price
+
+        if is_buyer_maker is None:
+            best_bid = self.best_bid()
+            is_buyer_maker = best_bid is not None and price <= best_bid
+
+        maker_side = OrderSide.BUY if is_buyer_maker else OrderSide.SELL
+        level = (maker_side, price)
+        self._traded_at[level] = self._traded_at.get(level, 0.0) + size
+
+        fills = []
+        # 该笔成交在同一档位依次分配给先挂出的模拟订单
+        left = size
+        for order in sorted(self.resting.values(), key=lambda o: o.created_at):
+            if order.side == OrderSide.BUY:
+                traded_through = price < order.price
+                at_level = price == order.price and is_buyer_maker
+            else:
+                traded_through = price > order.price
+                at_level = price == order.price and not is_buyer_maker
+
+            if traded_through:
+                fills.append(self._fill(order, order.remaining, order.price, is_maker=True))
+            elif at_level:
+                available = left - order.queue_ahead
+                order.queue_ahead = max(order.queue_ahead - left, 0.0)
+                if available > 0:
+                    quantity = min(order.remaining, available)
+                    fills.append(self._fill(order, quantity, order.price, is_maker=True))
+                    left -= quantity
+
+        return fills
+
+    def _trigger_reference(self, order: SimOrder) -> Optional[float]:
+        """条件单的触发判断价格"""
+        if order.trigger_price_type == TriggerPriceType.MARK_PRICE and self.mark_price is not None:
+            return self.mark_price
+        return self.last_price
+
+    def check_triggers(self) -> List[SimOrder]:
+        """
+        按最新价/标记价格检查条件单，触发的订单转为市价/限价执行
+
+        Returns:
+            List[SimOrder]: 本次触发的订单
+        """
+        triggered = [o for o in self.untriggered.values() if o.should_trigger(self._trigger_reference(o))]
+        for order in triggered:
+            del self.untriggered[order.order_id]
+            order.status = "OPEN"
+        return triggered
+
+    def submit(self, order: SimOrder) -> List[Fill]:
+        """
+        提交模拟订单
+
+        未触发的条件单先挂起，最新价已穿越触发价时立即触发。
+
+        Args:
+            order: 模拟订单
+
+        Returns:
+            List[Fill]: 立即成交部分
+
+        Raises:
+            ValueError: 没有可用的行情数据
+        """
+        if order.status == "UNTRIGGERED":
+            if not order.should_trigger(self._trigger_reference(order)):
+                self.untriggered[order.order_id] = order
+                return []
+            order.status = "OPEN"
+
+        is_buy = order.side == OrderSide.BUY
+        book = self.asks if is_buy else self.bids
+        limit = order.price if order.order_type not in MARKET_ORDER_TYPES else None
+
+        fills = []
+        for level_price in sorted(book, reverse=not is_buy):
+            if order.remaining <= 0:
+                break
+            if limit is not None and ((is_buy and level_price > limit) or (not is_buy and level_price < limit)):
+                break
+            take = min(book[level_price], order.remaining)
+            book[level_price] -= take
+            if book[level_price] <= 0:
+                del book[level_price]
+            fills.append(self._fill(order, take, level_price, is_maker=False))
+
+        if order.remaining <= 0:
+            return fills
+
+        if order.order_type in MARKET_ORDER_TYPES:
+            # 盘口深度不足，按最后成交价/最差档位补足
+            fallback = fills[-1].price if fills else self.last_price
+            if fallback is None:
+                order.status = "CANCELED"
+                raise ValueError(f"{self.symbol} 无可用行情数据，模拟订单被拒绝")
+            fills.append(self._fill(order, order.remaining, fallback, is_maker=False))
+            return fills
+
+        # 限价单剩余部分挂单，排在同价位已有挂单之后
+        same_side = self.bids if is_buy else self.asks
+        order.queue_ahead = same_side.get(order.price, 0.0)
+        self.resting[order.order_id] = order
+        return fills
+
+    def cancel(self, order_id: str) -> bool:
+        """撤销挂单（含未触发的条件单）"""
+        order = self.resting.pop(order_id, None) or self.untriggered.pop(order_id, None)
+        if not order:
+            return False
+        order.status = "CANCELED"
+        return True
+
+    def _fill(self, order: SimOrder, size: float, price: float, is_maker: bool) -> Fill:
+        """生成成交并更新订单状态"""
+        order.filled += size
+        if order.remaining <= 1e-12:
+            order.status = "FILLED"
+            self.resting.pop(order.order_id, None)
+        else:
+            order.status = "PARTIALLY_FILLED"
+
+        fee_rate = self.maker_fee_rate if is_maker else self.taker_fee_rate
+        return Fill(
+            symbol=order.symbol,
+            order_id=order.order_id,
+            side=order.side,
+            price=price,
+            size=size,
+            fee=price * size * fee_rate,
+            is_maker=is_maker,
+            timestamp=int(time.time() * 1000)
+        )
+
+
+class MatchingEngine:
+    """多交易对模拟撮合引擎"""
+
+    def __init__(self, taker_fee_rate: float = 0.0, maker_fee_rate: float = 0.0):
+        """
+        初始化撮合引擎
+
+        Args:
+            taker_fee_rate: 吃单手续费率
+            maker_fee_rate: 挂单手续费率
+        """
+        self.taker_fee_rate = taker_fee_rate
+        self.maker_fee_rate = maker_fee_rate
+        self.books: Dict[str, SimulatedOrderBook] = {}
+        self.fee_rates: Dict[str, Tuple[float, float]] = {}  # 交易对 -> (吃单费率, 挂单费率)，未设置时使用默认费率
+        self.orders: Dict[str, SimOrder] = {}
+        # 模拟持仓: (交易对, 持仓方向) -> 数量；单向持仓的方向为None，数量为净持仓（多为正）
+        self.positions: Dict[Tuple[str, Optional[TradeDirection]], float] = {}
+        self.fill_callbacks: List[Callable[[Fill], None]] = []
+        self.order_callbacks: List[Callable[[Dict], None]] = []
+        self._ids = itertools.count(1)
+        # 行情回调来自WebSocket线程，下单来自事件循环，需要加锁
+        self._lock = threading.RLock()
+
+    def add_fill_callback(self, callback: Callable[[Fill], None]):
+        """添加成交回调函数"""
+        self.fill_callbacks.append(callback)
+
+    def add_order_callback(self, callback: Callable[[Dict], None]):
+        """添加订单状态回调函数（参数格式与交易所订单更新一致）"""
+        self.order_callbacks.append(callback)
+
+    def _book(self, symbol: str) -> SimulatedOrderBook:
+        if symbol not in self.books:
+            taker, maker = self.fee_rates.get(symbol, (self.taker_fee_rate, self.maker_fee_rate))
+            self.books[symbol] = SimulatedOrderBook(symbol, taker, maker)
+        return self.books[symbol]
+
+    def set_fee_rates(self, symbol: str, taker_fee_rate: float, maker_fee_rate: float):
+        """设置交易对的手续费率（之后的模拟成交生效）"""
+        with self._lock:
+            self.fee_rates[symbol] = (taker_fee_rate, maker_fee_rate)
+            book = self.books.get(symbol)
+            if book:
+                book.taker_fee_rate = taker_fee_rate
+                book.maker_fee_rate = maker_fee_rate
+
+    def on_depth(self, symbol: str, bids: List[Tuple[float, float]], asks: List[Tuple[float, float]]):
+        """深度更新（完整快照）"""
+        with self._lock:
+            fills = self._book(symbol).apply_depth(bids, asks)
+        self._emit(fills)
+
+    def on_trade(self, symbol: str, price: float, size: float, is_buyer_maker: Optional[bool] = None):
+        """市场成交更新"""
+        with self._lock:
+            fills = self._book(symbol).apply_trade(price, size, is_buyer_maker)
+        self._emit(fills)
+        self._trigger(symbol)
+
+    def on_price(self, symbol: str, price: float):
+        """最新价更新（无深度数据时作为市价单成交参考）"""
+        with self._lock:
+            self._book(symbol).last_price = price
+        self._trigger(symbol)
+
+    def on_mark_price(self, symbol: str, price: float):
+        """标记价格更新（按标记价格触发的条件单）"""
+        with self._lock:
+            self._book(symbol).mark_price = price
+        self._trigger(symbol)
+
+    def _closable(self, order: SimOrder) -> float:
+        """只减仓订单可平的持仓数量"""
+        is_buy = order.side == OrderSide.BUY
+        if order.position_side is None:
+            net = self.positions.get((order.symbol, None), 0.0)
+            return max(-net, 0.0) if is_buy else max(net, 0.0)
+        closing_side = OrderSide.SELL if order.position_side == TradeDirection.LONG else OrderSide.BUY
+        return self.positions.get((order.symbol, order.position_side), 0.0) if order.side == closing_side else 0.0
+
+    def _limit_reduce_only(self, order: SimOrder) -> bool:
+        """只减仓订单的数量缩减到可平持仓（已成交部分之外），没有可平持仓时取消订单，返回订单是否可执行"""
+        if not order.reduce_only:
+            return True
+        closable = self._closable(order)
+        if closable <= 1e-12:
+            order.status = "CANCELED"
+            logger.warning(f"[模拟撮合] {order.symbol} 只减仓订单 {order.order_id} 没有可平持仓，已取消")
+            return False
+        if order.remaining > closable:
+            order.quantity = order.filled + closable
+        return True
+
+    def _record_position(self, fill: Fill):
+        """按成交更新模拟持仓"""
+        order = self.orders.get(fill.order_id)
+        signed = fill.size if fill.side == OrderSide.BUY else -fill.size
+        if order is None or order.position_side is None:
+            key = (fill.symbol, None)
+            self.positions[key] = self.positions.get(key, 0.0) + signed
+            return
+        key = (fill.symbol, order.position_side)
+        opening = (order.position_side == TradeDirection.LONG) == (fill.side == OrderSide.BUY)
+        self.positions[key] = max(self.positions.get(key, 0.0) + (fill.size if opening else -fill.size), 0.0)
+
+    def _trigger(self, symbol: str):
+        """执行被触发的条件单"""
+        with self._lock:
+            book = self._book(symbol)
+            triggered = book.check_triggers()
+            results = []
+            for order in triggered:
+                if not self._limit_reduce_only(order):
+                    results.append((order, []))
+                    continue
+                try:
+                    results.append((order, book.submit(order)))
+                except ValueError as e:
+                    logger.warning(f"[模拟撮合] {symbol} 条件单 {order.order_id} 触发后执行失败: {e}")
+                    results.append((order, []))
+
+        for order, fills in results:
+            logger.info(
+                f"[模拟撮合] {symbol} {order.side.value} {order.order_type.value} 触发 "
+                f"(触发价 {order.trigger_price} {order.trigger_price_type.value}) -> 成交 {order.filled} ({order.status})"
+            )
+            self._emit(fills)
+            if not fills:
+                self._emit_order(order)
+
+    def submit(self, order: Order) -> SimOrder:
+        """
+        提交订单
+
+        Args:
+            order: 订单对象
+
+        Returns:
+            SimOrder: 模拟订单
+        """
+        sim_order = SimOrder(f"SIM-{next(self._ids)}", order)
+        with self._lock:
+            self.orders[sim_order.order_id] = sim_order
+            fills = self._book(order.symbol).submit(sim_order) if self._limit_reduce_only(sim_order) else []
+
+        logger.info(
+            f"[模拟撮合] {order.symbol} {order.side.value} {order.order_type.value} "
+            f"{sim_order.quantity} -> 成交 {sim_order.filled} ({sim_order.status})"
+        )
+        self._emit(fills)
+        self._emit_order(sim_order)
+        return sim_order
+
+    def cancel(self, order_id: str) -> bool:
+        """撤销订单"""
+        with self._lock:
+            order = self.orders.get(order_id)
+            if not order:
+                return False
+            canceled = self._book(order.symbol).cancel(order_id)
+        if canceled:
+            self._emit_order(order)
+        return canceled
+
+    def get_open_orders(self, symbol: Optional[str] = None) -> List[SimOrder]:
+        """获取未完成的模拟挂单"""
+        with self._lock:
+            return [
+                o for book in self.books.values() for o in [*book.resting.values(), *book.untriggered.values()]
+                if symbol is None or o.symbol == symbol
+            ]
+
+    def replay(self, events: Iterable[Dict]):
+        """
+        回放录制的行情事件
+
+        Args:
+            events: 事件序列，每个事件包含 type（depth/trade/price/mark_price）、symbol 及对应字段
+        """
+        for event in events:
+            event_type = event.get("type")
+            symbol = event.get("symbol", "")
+            if event_type == "depth":
+                self.on_depth(symbol, event.get("bids", []), event.get("asks", []))
+            elif event_type == "trade":
+                self.on_trade(symbol, float(event["price"]), float(event["size"]), event.get("is_buyer_maker"))
+            elif event_type == "price":
+                self.on_price(symbol, float(event["price"]))
+            elif event_type == "mark_price":
+                self.on_mark_price(symbol, float(event["price"]))
+
+    def _emit(self, fills: List[Fill]):
+        """分发成交回调"""
+        for fill in fills:
+            with self._lock:
+                self._record_position(fill)
+            for callback in self.fill_callbacks:
+                try:
+                    callback(fill)
+                except Exception as e:
+                    logger.error(f"成交回调函数执行失败: {e}")
+            order = self.orders.get(fill.order_id)
+            if order and order.order_type != OrderType.MARKET:
+                self._emit_order(order)
+
+    def _emit_order(self, order: SimOrder):
+        """分发订单状态回调"""
+        data = order.to_dict()
+        for callback in self.order_callbacks:
+            try:
+                callback(data)
+            except Exception as e:
+                logger.error(f"订单回调函数执行失败: {e}")
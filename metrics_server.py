@@ -0,0 +1,237 @@
+"""
+指标接口模块
+
+以Prometheus文本格式在 /metrics 输出各策略的交易链路延迟分位数（见 latency.py），
+供Prometheus抓取，在Grafana中跟踪延迟回退。报告输出目标包含 metrics 时，
+同时输出最近一次报告中的数值（见 report_sinks.py）。下单前检查的通过/拒绝次数按拒绝原因输出（见 pre_trade.py）。
+
+无法被抓取时（如运行在NAT之后），可按固定间隔把同样的指标推送出去：
+- pushgateway: 以Prometheus文本格式 PUT 到 Pushgateway 的 /metrics/job/<job>（每次替换该job的全部指标）
+- influx: 转换为InfluxDB行协议 POST 到写入地址（如 http://host:8086/api/v2/write?org=..&bucket=..&precision=ns），
+  指标名为measurement、标签为tag、数值为 value 字段；配置令牌时带 Authorization: Token 请求头
+推送失败只记录日志，下一个间隔重试。
+"""
+
+import asyncio
+import re
+import time
+from typing import Callable, Dict, List, Optional
+
+import aiohttp
+from aiohttp import web
+from loguru import logger
+
+from latency import LatencyProfiler
+from pre_trade import PreTradeValidator
+from report_sinks import ReportMetrics
+
+_LATENCY_METRIC = "edgex_latency_seconds"
+_REPORT_METRIC = "edgex_report_value"
+_PRE_TRADE_METRIC = "edgex_pretrade_orders_total"
+
+PUSH_FORMATS = ("pushgateway", "influx")
+_PUSH_TIMEOUT = 10.0
+_SAMPLE_LINE = re.compile(r'^([a-zA-Z_:][a-zA-Z0-9_:]*)(?:\{(.*)\})? (\S+)$')
+_LABEL = re.compile(r'([a-zA-Z_][a-zA-Z0-9_]*)="((?:[^"\\]|\\.)*)"')
+
+
+def format_latency_metrics(profilers: Dict[str, LatencyProfiler]) -> str:
+    """
+    按Prometheus文本格式输出延迟摘要
+
+    Args:
+        profilers: 策略名称 -> 延迟直方图
+
+    Returns:
+        str: 指标文本
+    """
+    lines: List[str] = [
+        f"# HELP {_LATENCY_METRIC} Trade pipeline latency by stage",
+        f"# TYPE {_LATENCY_METRIC} summary",
+    ]
+    for strategy, profiler in profilers.items():
+        for stage, key, value in profiler.prometheus_samples():
+            labels = f'strategy="{strategy}",stage="{stage}"'
+            if key in ("count", "sum"):
+                lines.append(f"{_LATENCY_METRIC}_{key}{{{labels}}} {value}")
+            else:
+                lines.append(f'{_LATENCY_METRIC}{{{labels},quantile="{key:g}"}} {value}')
+    return "\n".join(lines) + "\n"
+
+
+def format_report_metrics(reports: ReportMetrics) -> str:
+    """按Prometheus文本格式输出最近一次报告的数值"""
+    lines: List[str] = [
+        f"# HELP {_REPORT_METRIC} Latest value reported by the performance monitor",
+        f"# TYPE {_REPORT_METRIC} gauge",
+    ]
+    for (strategy, report, field), value in sorted(reports.values.items()):
+        lines.append(f'{_REPORT_METRIC}{{strategy="{strategy}",report="{report}",field="{field}"}} {value}')
+    return "\n".join(lines) + "\n"
+
+
+def format_pre_trade_metrics(validators: Dict[str, PreTradeValidator]) -> str:
+    """按Prometheus文本格式输出下单前检查结果（result 为 passed 或拒绝原因）"""
+    lines: List[str] = [
+        f"# HELP {_PRE_TRADE_METRIC} Orders checked by the pre-trade pipeline by result",
+        f"# TYPE {_PRE_TRADE_METRIC} counter",
+    ]
+    for strategy, validator in validators.items():
+        lines.append(f'{_PRE_TRADE_METRIC}{{strategy="{strategy}",result="passed"}} {validator.passed}')
+        for reason, count in sorted(validator.rejections.items()):
+            lines.append(f'{_PRE_TRADE_METRIC}{{strategy="{strategy}",result="{reason}"}} {count}')
+    return "\n".join(lines) + "\n"
+
+
+class MetricsRenderer:
+    """汇总各来源的指标文本（指标接口和推送共用）"""
+
+    def __init__(self, collect: Callable[[], Dict[str, LatencyProfiler]],
+                 reports: Optional[ReportMetrics] = None,
+                 pre_trade: Optional[Callable[[], Dict[str, PreTradeValidator]]] = None):
+        """
+        Args:
+            collect: 返回当前各策略延迟直方图的函数
+            reports: 报告数值（报告输出目标包含 metrics 时）
+            pre_trade: 返回当前各策略下单前检查的函数
+        """
+        self.collect = collect
+        self.reports = reports
+        self.pre_trade = pre_trade
+
+    def render(self) -> str:
+        text = format_latency_metrics(self.collect())
+        if self.reports:
+            text += format_report_metrics(self.reports)
+        if self.pre_trade:
+            text += format_pre_trade_metrics(self.pre_trade())
+        return text
+
+
+def _escape_tag(value: str) -> str:
+    return value.replace("\\", "\\\\").replace(",", "\\,").replace("=", "\\=").replace(" ", "\\ ")
+
+
+def to_line_protocol(text: str, timestamp_ns: int) -> str:
+    """
+    把Prometheus文本格式转换为InfluxDB行协议（跳过注释和非数值样本）
+
+    Args:
+        text: Prometheus文本
+        timestamp_ns: 时间戳（纳秒）
+    """
+    lines: List[str] = []
+    for line in text.splitlines():
+        match = _SAMPLE_LINE.match(line.strip())
+        if not match:
+            continue
+        name, labels, value = match.groups()
+        try:
+            number = float(value)
+        except ValueError:
+            continue
+        if number != number or number in (float("inf"), float("-inf")):
+            continue
+        tags = "".join(f",{key}={_escape_tag(val)}" for key, val in _LABEL.findall(labels or "") if val)
+        lines.append(f"{name}{tags} value={number!r} {timestamp_ns}")
+    return "\n".join(lines) + "\n"
+
+
+class MetricsPusher:
+    """按固定间隔推送指标"""
+
+    def __init__(self, renderer: MetricsRenderer, url: str, fmt: str = "pushgateway",
+                 interval: float = 15.0, job: str = "edgex_bot", token: str = ""):
+        """
+        Args:
+            renderer: 指标文本来源
+            url: Pushgateway地址（如 http://host:9091）或InfluxDB写入地址
+            fmt: 推送格式（pushgateway/influx）
+            interval: 推送间隔（秒）
+            job: Pushgateway的job名称
+            token: InfluxDB令牌（空为不认证）
+        """
+        if fmt not in PUSH_FORMATS:
+            raise ValueError(f"未知的指标推送格式: {fmt}（可选 {'/'.join(PUSH_FORMATS)}）")
+        self.renderer = renderer
+        self.url = url.rstrip("/") + f"/metrics/job/{job}" if fmt == "pushgateway" else url
+        self.fmt = fmt
+        self.interval = interval
+        self.token = token
+        self.stats = {"pushed": 0, "errors": 0}
+        self._task: Optional[asyncio.Task] = None
+
+    async def start(self):
+        self._task = asyncio.create_task(self._run())
+        logger.info(f"[指标] 指标推送已启动: {self.fmt} -> {self.url}（每 {self.interval:g} 秒）")
+
+    async def stop(self):
+        """停止定时推送，并推送最后一次"""
+        if self._task:
+            self._task.cancel()
+            try:
+                await self._task
+            except asyncio.CancelledError:
+                pass
+            self._task = None
+            await self.push()
+
+    async def _run(self):
+        while True:
+            await asyncio.sleep(self.interval)
+            await self.push()
+
+    async def push(self) -> bool:
+        """推送一次当前指标"""
+        text = self.renderer.render()
+        headers = {"Content-Type": "text/plain; charset=utf-8"}
+        if self.fmt == "influx":
+            text = to_line_protocol(text, time.time_ns())
+            if self.token:
+                headers["Authorization"] = f"Token {self.token}"
+        method = "PUT" if self.fmt == "pushgateway" else "POST"
+        try:
+            async with aiohttp.ClientSession(timeout=aiohttp.ClientTimeout(total=_PUSH_TIMEOUT)) as session:
+                async with session.request(method, self.url, data=text.encode(), headers=headers) as response:
+                    if response.status >= 400:
+                        raise ValueError(f"HTTP {response.status}: {(await response.text())[:200]}")
+        except Exception as e:
+            self.stats["errors"] += 1
+            logger.warning(f"[指标] 推送到 {self.url} 失败: {e}")
+            return False
+        self.stats["pushed"] += 1
+        return True
+
+
+class MetricsServer:
+    """Prometheus指标HTTP服务"""
+
+    def __init__(self, host: str, port: int, renderer: MetricsRenderer):
+        """
+        Args:
+            host: 监听地址
+            port: 监听端口
+            renderer: 指标文本来源
+        """
+        self.host = host
+        self.port = port
+        self.renderer = renderer
+        self._runner: Optional[web.AppRunner] = None
+
+    async def start(self):
+        """启动HTTP服务"""
+        app = web.Application()
+        app.router.add_get("/metrics", self._handle)
+        self._runner = web.AppRunner(app)
+        await self._runner.setup()
+        await web.TCPSite(self._runner, self.host, self.port).start()
+        logger.info(f"[指标] 指标接口已启动: http://{self.host}:{self.port}/metrics")
+
+    async def stop(self):
+        """停止HTTP服务"""
+        if self._runner:
+            await self._runner.cleanup()
+            self._runner = None
+
+    async def _handle(self, request: web.Request) -> web.Response:
+        return web.Response(text=self.renderer.render(), content_type="text/plain", charset="utf-8")
@@ -0,0 +1,80 @@
+"""
+机器学习信号模块（可选，需要 onnxruntime）
+
+加载ONNX模型，以 features.py 提取的特征向量为输入（[1, 特征数] 的float32张量）输出交易方向评分。
+支持的模型输出（取第一个输出）：
+- 1个值: 评分，正数看多、负数看空（如回归预测的前瞻收益率或 [-1, 1] 的分数）
+- 2个值: [看空概率, 看多概率]，评分为两者之差
+- 3个值: [看空概率, 持有概率, 看多概率]，评分为看多与看空概率之差
+模型作为组合信号的信号源 onnx 使用（见 signal_ensemble.py），评分绝对值超过阈值时投票。
+训练数据用 features.py export 由回放事件文件导出，与实盘使用同一个特征提取函数。
+"""
+
+from typing import Any, Dict, List, Optional
+
+from loguru import logger
+
+from features import FEATURE_NAMES
+
+try:
+    import numpy as np
+    import onnxruntime as ort
+    ONNX_AVAILABLE = True
+except ImportError:
+    ONNX_AVAILABLE = False
+
+
+class OnnxModel:
+    """ONNX模型推理"""
+
+    def __init__(self, path: str):
+        """
+        Args:
+            path: 模型文件路径
+
+        Raises:
+            ValueError: 未安装 onnxruntime、模型无法加载或输入维度与特征数不一致
+        """
+        if not ONNX_AVAILABLE:
+            raise ValueError("未安装 onnxruntime，无法加载ONNX模型")
+        try:
+            self.session = ort.InferenceSession(path, providers=["CPUExecutionProvider"])
+        except Exception as e:
+            raise ValueError(f"无法加载ONNX模型 {path}: {e}")
+        model_input = self.session.get_inputs()[0]
+        width = model_input.shape[-1] if model_input.shape else None
+        if isinstance(width, int) and width != len(FEATURE_NAMES):
+            raise ValueError(f"模型输入维度 {width} 与特征数 {len(FEATURE_NAMES)} 不一致")
+        self.path = path
+        self.input_name = model_input.name
+        self.stats: Dict[str, Any] = {"predictions": 0, "errors": 0, "last_score": None}
+        logger.info(f"[机器学习信号] 已加载模型 {path}（输入 {self.input_name}，{len(FEATURE_NAMES)} 个特征）")
+
+    def predict(self, features: List[float]) -> Optional[float]:
+        """
+        推理评分
+
+        Returns:
+            Optional[float]: 评分（正数看多），推理失败时返回None
+        """
+        try:
+            output = self.session.run(None, {self.input_name: np.asarray([features], dtype=np.float32)})[0]
+            values = np.asarray(output, dtype=np.float64).reshape(-1)
+            if values.size == 1:
+                score = float(values[0])
+            elif values.size == 2:
+                score = float(values[1] - values[0])
+            elif values.size == 3:
+                score = float(values[2] - values[0])
+            else:
+                raise ValueError(f"不支持的模型输出长度 {values.size}")
+        except Exception as e:
+            self.stats["errors"] += 1
+            logger.error(f"[机器学习信号] 模型推理失败: {e}")
+            return None
+        self.stats["predictions"] += 1
+        self.stats["last_score"] = score
+        return score
+
+    def get_stats(self) -> Dict[str, Any]:
+        return {"path": self.path, **self.stats}
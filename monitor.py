@@ -1,22 +1,39 @@
 """
 性能监控模块
+
+只读取策略发布的状态快照（见 snapshot.py），不访问策略内部字段，生成报告不影响交易路径。
+报告按配置输出到标准输出、文件、指标接口或通知Webhook（见 report_sinks.py）。
 """
 
 import asyncio
 from datetime import datetime, timedelta
-from typing import Dict, Any
+from typing import Any, Callable, Dict, List, Optional, Tuple
 from loguru import logger
 
 from edgex_types import PerformanceReport
+from exchange_errors import ERROR_KINDS
+from snapshot import StrategySnapshot
+from report_sinks import REPORT_DAILY, REPORT_PERFORMANCE, ReportDispatcher
 from strategy import HighFrequencyStrategy
+from ab_test import ABTest
 
 class PerformanceMonitor:
     """性能监控器"""
     
-    def __init__(self, strategy: HighFrequencyStrategy):
-        self.strategy = strategy
+    def __init__(self, strategy: HighFrequencyStrategy, ab_test: Optional[ABTest] = None,
+                 sinks: Optional[ReportDispatcher] = None):
+        """
+        Args:
+            strategy: 被监控的策略（只使用其快照通道）
+            ab_test: A/B测试（对照组的报告中输出对比）
+            sinks: 报告输出目标（默认性能报告输出到标准输出）
+        """
+        self.snapshots = strategy.snapshots
+        self.ab_test = ab_test
+        self.sinks = sinks or ReportDispatcher({REPORT_PERFORMANCE: ["stdout"]})
         self.is_monitoring = False
         self.monitor_task = None
+        self.daily_task = None
     
     async def start_monitoring(self):
         """启动监控"""
@@ -26,6 +43,8 @@ class PerformanceMonitor:
         
         self.is_monitoring = True
         self.monitor_task = asyncio.create_task(self._monitoring_loop())
+        if self.sinks.enabled(REPORT_DAILY):
+            self.daily_task = asyncio.create_task(self._daily_loop())
         logger.info("性能监控已启动")
     
     async def stop_monitoring(self):
@@ -34,12 +53,13 @@ class PerformanceMonitor:
             return
         
         self.is_monitoring = False
-        if self.monitor_task:
-            self.monitor_task.cancel()
-            try:
-                await self.monitor_task
-            except asyncio.CancelledError:
-                pass
+        for task in (self.monitor_task, self.daily_task):
+            if task:
+                task.cancel()
+                try:
+                    await task
+                except asyncio.CancelledError:
+                    pass
         
         logger.info("性能监控已停止")
     
@@ -47,11 +67,16 @@ class PerformanceMonitor:
         """监控循环"""
         while self.is_monitoring:
             try:
-                report = await self._generate_performance_report()
-                self._print_report(report)
+                # 策略主循环发布第一份快照之前等待
+                snapshot = self.snapshots.latest or await self.snapshots.wait_newer()
+                report = await self._generate_performance_report(snapshot)
+                if self.sinks.enabled(REPORT_PERFORMANCE):
+                    await self.sinks.dispatch(REPORT_PERFORMANCE, snapshot.name,
+                                              self._format_report(report, snapshot),
+                                              self._report_values(report, snapshot))
                 
                 # 等待下次报告
-                await asyncio.sleep(self.strategy.config.performance_report_interval)
+                await asyncio.sleep(snapshot.report_interval)
                 
             except asyncio.CancelledError:
                 break
@@ -59,79 +84,599 @@ class PerformanceMonitor:
                 logger.error(f"监控循环错误: {e}")
                 await asyncio.sleep(60)  # 出错后等待1分钟
     
-    async def _generate_performance_report(self) -> PerformanceReport:
+    async def _daily_loop(self):
+        """交易日结束时输出前一交易日汇总（快照中的前一日统计变化时）"""
+        reported = None
+        version = 0
+        while self.is_monitoring:
+            try:
+                snapshot = await self.snapshots.wait_newer(version)
+                version = snapshot.version
+                previous = snapshot.stats["daily"].get("previous")
+                if reported is None:
+                    # 启动时已有的前一日汇总不重复输出
+                    reported = previous["day"] if previous else ""
+                    continue
+                if previous and previous["day"] != reported:
+                    reported = previous["day"]
+                    text = (f"[{snapshot.name}] 交易日 {previous['day']} ({snapshot.trading_timezone}) 汇总: "
+                            f"开仓 {previous['entries']} 次 | 平仓 {previous['trades']} 笔 | "
+                            f"成交额 {previous['volume']:.2f} USDT | 盈亏 {previous['pnl']:.4f} USDT")
+                    await self.sinks.dispatch(REPORT_DAILY, snapshot.name, text,
+                                              {k: previous[k] for k in ("entries", "trades", "volume", "pnl")})
+            except asyncio.CancelledError:
+                break
+            except Exception as e:
+                logger.error(f"交易日汇总输出错误: {e}")
+                await asyncio.sleep(60)
+    
+    @staticmethod
+    def _report_values(report: PerformanceReport, snapshot: StrategySnapshot) -> Dict[str, float]:
+        """性能报告中的数值字段（metrics/notifier 输出）"""
+        stats = snapshot.stats
+        return {
+            "portfolio_value": report.portfolio_value,
+            "today_pnl": report.today_pnl,
+            "today_trades": report.today_trades,
+            "daily_volume": report.daily_volume,
+            "volume_ratio": report.volume_ratio,
+            "win_rate": stats["win_rate"],
+            "total_trades": stats["total_trades"],
+            "active_positions": stats["active_positions"],
+        }
+    
+    async def _generate_performance_report(self, snapshot: StrategySnapshot) -> PerformanceReport:
         """生成性能报告"""
-        stats = self.strategy.get_performance_stats()
+        stats = snapshot.stats
         
         # 计算交易量
-        daily_volume = float(self.strategy._calculate_daily_volume())
-        volume_target = float(self.strategy.balance) * 100.0
+        daily_volume = snapshot.daily_volume
+        volume_target = snapshot.balance * 100.0
         volume_ratio = daily_volume / volume_target if volume_target > 0 else 0
         
-        # 计算今日盈亏
-        today_pnl = 0.0
-        now = datetime.now()
-        for record in self.strategy.trade_records:
-            trade_time = datetime.fromtimestamp(record.timestamp)
-            if (now - trade_time).total_seconds() < 86400:  # 24小时内
-                today_pnl += float(record.pnl)
+        # 今日（按配置时区的交易日）盈亏
+        daily = stats["daily"]
+        today_pnl = daily["pnl"]
+        
+        risk = stats.get("risk") or {}
         
         return PerformanceReport(
             timestamp=datetime.now(),
             portfolio_value=float(stats["balance"]),
-            current_volatility=0.0,  # 波动率已移除
-            target_volatility=0.0,    # 波动率已移除
-            volatility_ratio=0.0,     # 波动率已移除
+            current_volatility=risk.get("portfolio_volatility", 0.0),
+            target_volatility=risk.get("target_volatility", 0.0),
+            volatility_ratio=risk.get("volatility_ratio", 0.0),
             daily_volume=daily_volume,
             volume_target=volume_target,
             volume_ratio=volume_ratio,
-            today_trades=stats["total_trades"],
+            today_trades=daily["trades"],
             today_pnl=today_pnl,
             trading_interval=stats["trading_interval"]
         )
     
-    def _print_report(self, report: PerformanceReport):
-        """打印报告"""
-        print("\n" + "="*70)
-        print("多币种高频策略性能报告 (v3.4 - WebSocket版)")
-        print("="*70)
-        print(f"时间: {report.timestamp.strftime('%Y-%m-%d %H:%M:%S')}")
-        print(f"净值: {report.portfolio_value:.2f} USDT")
-        print(f"今日盈亏: {report.today_pnl:.4f} USDT")
-        print(f"交易量: {report.daily_volume:.2f} / {report.volume_target:.2f} ({report.volume_ratio*100:.2f}%)")
-        print(f"交易次数: {report.today_trades}")
-        print(f"交易间隔: {report.trading_interval}秒")
+    def _format_report(self, report: PerformanceReport, snapshot: StrategySnapshot) -> str:
+        """生成报告文本"""
+        lines: List[str] = []
+        out = lines.append
+        out("\n" + "="*70)
+        title = f"[{snapshot.name}] " if snapshot.allocated else ""
+        out(f"{title}多币种高频策略性能报告 (v3.4 - WebSocket版)")
+        out("="*70)
+        out(f"时间: {report.timestamp.strftime('%Y-%m-%d %H:%M:%S')}")
+        out(f"净值: {report.portfolio_value:.2f} USDT")
+        out(f"今日盈亏: {report.today_pnl:.4f} USDT")
+        out(f"交易量: {report.daily_volume:.2f} / {report.volume_target:.2f} ({report.volume_ratio*100:.2f}%)")
+        out(f"交易次数: {report.today_trades}")
+        out(f"交易间隔: {report.trading_interval}毫秒")
         
         # 添加详细统计
-        stats = self.strategy.get_performance_stats()
-        print(f"胜率: {stats['win_rate']*100:.2f}%")
-        print(f"盈利交易: {stats['winning_trades']}")
-        print(f"亏损交易: {stats['losing_trades']}")
-        print(f"活跃仓位: {stats['active_positions']}")
+        stats = snapshot.stats
+        daily = stats["daily"]
+        limit = f" / {daily['max_entries']}" if daily["max_entries"] else ""
+        out(f"交易日: {daily['day']} ({snapshot.trading_timezone}) | 今日开仓: {daily['entries']}{limit}")
+        
+        # 多币种抵押资产（只有报告币种一种资产时不显示）
+        collateral = stats["collateral"]
+        currency = collateral["reporting_currency"]
+        if any(coin != currency for coin in collateral["assets"]):
+            out(f"抵押资产 (折算为 {currency}，可用保证金 {stats['available_balance']:.2f}):")
+            for coin, asset in collateral["assets"].items():
+                if asset["rate"] is None:
+                    out(f"  {coin}: 余额 {asset['amount']:.4f} | 权益 {asset['equity']:.4f} | 未配置汇率，不计入")
+                    continue
+                haircut = f" | 折扣 {asset['haircut']*100:.1f}%" if asset["haircut"] else ""
+                out(f"  {coin}: 余额 {asset['amount']:.4f} | 权益 {asset['equity']:.4f} | 可用 {asset['available']:.4f} | "
+                    f"汇率 {asset['rate']} ≈ {asset['equity'] * asset['rate']:.2f} {currency}{haircut}")
+        out(f"胜率: {stats['win_rate']*100:.2f}%")
+        out(f"盈利交易: {stats['winning_trades']}")
+        out(f"亏损交易: {stats['losing_trades']}")
+        out(f"活跃仓位: {stats['active_positions']}")
+        if stats["confidence"]:
+            names = {"low": "低", "medium": "中", "high": "高"}
+            out("按置信度: " + " | ".join(
+                f"{names[name]} {group['trades']}笔 胜率 {group['win_rate']*100:.1f}% 平均 {group['avg_pnl']:.4f}"
+                for name, group in stats["confidence"].items()))
+        out("滚动绩效:")
+        for name, window in stats["rolling_performance"].items():
+            sharpe = f"{window['sharpe']:.2f}" if window["sharpe"] is not None else "-"
+            label = "启动以来" if name == "inception" else f"最近{name}"
+            out(f"  {label}: {window['trades']} 笔 | 胜率 {window['win_rate']*100:.1f}% | 盈亏 {window['pnl']:.4f} USDT | "
+                f"夏普 {sharpe}" + (" (交易记录不完整)" if window["partial"] else ""))
+        excursions = stats.get("excursions")
+        if excursions:
+            def dist(d):
+                return " / ".join(f"{d[f'p{p}']*100:.3f}%" for p in (50, 75, 90)) + f" (最大 {d['max']*100:.3f}%)"
+            out(f"持仓偏移 ({excursions['trades']} 笔，P50/P75/P90):")
+            out(f"  MAE: {dist(excursions['mae'])} | MFE: {dist(excursions['mfe'])}")
+            if excursions["winners_mae"]:
+                out(f"  盈利交易MAE: {dist(excursions['winners_mae'])}（参考止损距离）")
+            if excursions["losers_mfe"]:
+                out(f"  亏损交易MFE: {dist(excursions['losers_mfe'])}（参考止盈距离）")
+        benchmark = stats.get("benchmark")
+        if benchmark:
+            out(f"基准对比 (等权买入持有 {', '.join(benchmark['symbols'])}): 策略 {benchmark['strategy_return']*100:+.2f}% | "
+                f"基准 {benchmark['benchmark_return']*100:+.2f}% | 超额 {benchmark['excess_return']*100:+.2f}% | "
+                f"alpha(年化) {benchmark['alpha']*100:+.2f}% | beta {benchmark['beta']:.2f} | 相关系数 {benchmark['correlation']:.2f}")
+        breaker = stats.get("loss_breaker")
+        if breaker and breaker["remaining"] > 0:
+            out(f"连续亏损熔断: 暂停开仓，剩余 {breaker['remaining']:.0f}秒（已触发 {breaker['triggered']} 次）")
+        cooldowns = stats.get("cooldowns")
+        if cooldowns and cooldowns["active"]:
+            out("亏损冷却: " + " | ".join(f"{symbol} 剩余 {left:.0f}秒" for symbol, left in cooldowns["active"].items()))
         
         # 显示各交易对持仓
         if stats['active_positions'] > 0:
-            print(f"\n持仓详情:")
-            for symbol, position in self.strategy.positions.items():
-                print(f"  {symbol}: {position.direction.value} | "
+            out(f"\n持仓详情:")
+            liquidation = {(p["symbol"], p["direction"]): p for p in stats["mark_price"]["positions"]}
+            for position in snapshot.positions:
+                risk = liquidation.get((position.symbol, position.direction.value))
+                out(f"  {position.symbol}: {position.direction.value} | "
                       f"数量: {float(position.size):.6f} | "
-                      f"入场: {float(position.entry_price):.2f}")
+                      f"入场: {float(position.entry_price):.2f}"
+                      + (f" | 标记价: {risk['mark_price']:.2f} | 预估强平价: {risk['liquidation_price']:.2f} "
+                         f"(距离 {risk['distance']*100:.2f}%)" if risk and risk["mark_price"] else ""))
+        
+        # 标记价格
+        marks = stats["mark_price"]["symbols"]
+        if marks:
+            out(f"\n标记价格:")
+            for symbol, m in marks.items():
+                basis = (m["mark"] - m["index"]) / m["index"] * 10000 if m["index"] else 0.0
+                out(f"  {symbol}: 最新 {m['last'] or 0:.4f} | 标记 {m['mark']:.4f} | 指数 {m['index']:.4f} | "
+                      f"标记-指数 {basis:+.2f} bps")
+        
+        # K线构建
+        bars = stats.get("bars")
+        if bars:
+            out(f"\nK线: {bars['type']} (大小 {bars['size']:g}) | 已收线 " +
+                  " | ".join(f"{s} {n}" for s, n in bars["symbols"].items()))
+        
+        # 组合风险分解
+        if stats.get("risk"):
+            risk = stats["risk"]
+            out(f"\n组合风险:")
+            out(f"  组合波动率: {report.current_volatility*100:.2f}% / 目标 {report.target_volatility*100:.2f}% "
+                  f"({report.volatility_ratio*100:.1f}%, {risk['samples']} 个样本)")
+            for symbol, component in risk["components"].items():
+                out(f"  {symbol}: 权重 {component['weight']:+.2f} | "
+                      f"波动率 {component['volatility']*100:.2f}% | "
+                      f"风险贡献 {component['contribution']*100:.1f}%")
+        
+        # 波动率状态
+        if stats.get("regimes"):
+            regimes = stats["regimes"]
+            out(f"\n波动率状态:")
+            for symbol, state in regimes["symbols"].items():
+                profile = (f" | 参数配置: {state['profile']} (趋势强度 {state['trend']:.2f})"
+                           if regimes["profiles"] else "")
+                out(f"  {symbol}: {state['regime']} (分位数 {state['percentile']*100:.1f}%){profile}")
+            if regimes["profiles"]:
+                out(f"  参数配置: {', '.join(regimes['profiles'])} | 切换 {regimes['profile_switches']} 次")
+            if regimes["stand_down"]:
+                out(f"  暂停开仓状态: {', '.join(regimes['stand_down'])} | 跳过信号: {regimes['skipped_entries']}")
+        
+        # 执行滑点
+        slippage = stats.get("slippage")
+        if slippage and slippage["orders"]:
+            out(f"\n执行滑点 (相对信号时刻到达价格):")
+            out(f"  订单数: {slippage['orders']} | 平均: {slippage['avg_bps']:.2f} bps | "
+                  f"加权: {slippage['weighted_bps']:.2f} bps | 最大: {slippage['max_bps']:.2f} bps | "
+                  f"成本: {slippage['cost']:.4f} USDT")
+            out(f"  开仓: {slippage['entries']['avg_bps']:.2f} bps ({slippage['entries']['orders']} 笔) | "
+                  f"平仓: {slippage['exits']['avg_bps']:.2f} bps ({slippage['exits']['orders']} 笔)")
+            for symbol, s in slippage["symbols"].items():
+                out(f"  {symbol}: {s['avg_bps']:.2f} bps ({s['orders']} 笔, 成本 {s['cost']:.4f} USDT)")
+        
+        # 保证金检查
+        margin = stats.get("margin")
+        if margin:
+            out(f"\n可用保证金: {margin['free_margin']:.2f} USDT | 保证金不足缩小仓位 {margin['shrunk']} 次 | 放弃开仓 {margin['rejected']} 次")
+        
+        # 平仓确认
+        closes = stats.get("close_confirmation")
+        if closes and (closes["retries"] or closes["failed"]):
+            out(f"\n平仓确认: 已归零 {closes['confirmed']} 次 | 重试 {closes['retries']} 次 | 重试后仍未平完 {closes['failed']} 次")
+        
+        # OCO止盈止损
+        dual = stats.get("dual_quote")
+        if dual:
+            active = ", ".join(f"{s} {n}" for s, n in dual["active"].items()) or "无"
+            out(f"\n双边挂单 (距离 {dual['spread_bps']} bps): 挂单中 {active} | 已挂出 {dual['placed']} | "
+                f"重新挂单 {dual['requoted']} | 成交 买 {dual['buy_fills']} / 卖 {dual['sell_fills']} | "
+                f"再平衡 {dual['rebalances']} | 错误 {dual['errors']}")
+            for symbol, streak in dual["streaks"].items():
+                if streak["count"] > 1:
+                    out(f"  {symbol} 连续 {streak['count']} 次 {streak['side']} 成交")
+        
+        oco = stats.get("oco")
+        if oco:
+            out(f"\nOCO退出单: 生效 {oco['active']} | 已挂出 {oco['placed']} | 止盈成交 {oco['take_profit_filled']} | "
+                  f"止损触发 {oco['stop_loss_triggered']} | 止盈腿失效 {oco['detached']}")
+        
+        # 杠杆
+        leverage = stats.get("leverage")
+        if leverage:
+            out("\n杠杆: " + " | ".join(f"{s} {x}x" for s, x in leverage["symbols"].items()))
+            for symbol, reason in leverage["blocked"].items():
+                out(f"  ⚠ {symbol} 禁止开仓: {reason}")
+        
+        # 交易对轮换
+        universe = stats.get("universe")
+        if universe:
+            out(f"\n交易对轮换: 前 {universe['size']} 个 | 扫描 {universe['scans']} 次 | 轮换 {universe['rotations']} 次 "
+                f"（加入 {universe['added']} / 离开 {universe['removed']}）")
+            out(f"  当前: {', '.join(universe['symbols'])}")
+            for symbol, seconds in universe["winding_down"].items():
+                out(f"  ⚠ {symbol} 已离开集合 {seconds} 秒，等待平仓")
+        
+        # 交易所维护/合约暂停交易
+        trading_status = stats.get("trading_status")
+        if trading_status and trading_status["affected"]:
+            labels = {"halted": "暂停交易", "close_only": "只平仓"}
+            out(f"\n交易状态: {len(trading_status['affected'])} 个交易对受影响 | 累计暂停 {trading_status['halts']} 次")
+            for symbol, info in trading_status["affected"].items():
+                out(f"  ⚠ {symbol} {labels.get(info['status'], info['status'])}: {info['reason']}")
+        
+        # REST请求超时
+        timeouts = stats.get("request_timeouts")
+        if timeouts:
+            out("\nREST请求超时: " + " | ".join(f"{endpoint} {n} 次" for endpoint, n in timeouts.items()))
+        
+        # 交易所拒绝请求（按错误类型）
+        rejections = stats.get("exchange_rejections")
+        if rejections:
+            out("\n交易所拒绝: " + " | ".join(f"{ERROR_KINDS.get(kind, kind)} {n} 次" for kind, n in rejections.items()))
+        
+        # 手动交易控制台的操作
+        manual = stats.get("manual")
+        if manual and (any(v for k, v in manual.items() if k != "paused") or manual["paused"]):
+            out(f"\n手动操作: 下单 {manual['orders']} | 撤单 {manual['cancels']} | 平仓 {manual['closes']} | "
+                f"调整杠杆 {manual['leverage']} | 暂停开仓 {', '.join(manual['paused']) or '无'}")
+        
+        # 下单确认（超时未收到订单更新时按ID查询）
+        confirmation = stats.get("order_confirmation")
+        if confirmation and (confirmation["polled"] or confirmation["unconfirmed"]):
+            out(f"\n下单确认: 查询确认 {confirmation['polled']} 笔 | 未确认 {confirmation['unconfirmed']} 笔"
+                f"（此后确认 {confirmation['confirmed_late']}，按拒绝处理 {confirmation['missing']}，"
+                f"当前 {confirmation['open_unconfirmed']}）")
+        
+        # 签名时钟校正与时间戳重试
+        clock = stats.get("signature_clock")
+        if clock and (clock["offset_ms"] or clock["retries"]):
+            out(f"\n签名时钟: 校正 {clock['offset_ms']:+d}ms（窗口 {clock['recv_window_ms']}ms）| "
+                f"时间戳超出范围重试 {clock['retries']} 次")
+        
+        # WebSocket消息队列背压
+        backpressure = stats.get("ws_backpressure")
+        if backpressure:
+            names = {"public": "行情", "private": "私有"}
+            out("\nWebSocket队列: " + " | ".join(
+                f"{names.get(name, name)} 积压 {b['inbox_depth']}/{b['max_inbox_depth']} "
+                f"队列满等待 {b['queue_full_waits']} 次"
+                + "".join(f" 合并{t} {n} 条" for t, n in b["coalesced"].items())
+                for name, b in backpressure.items()
+            ) + "（积压为当前/最大深度，无消息丢弃）")
+        
+        # 行情订阅
+        subscriptions = stats.get("ws_subscriptions")
+        if subscriptions:
+            out(f"\nWebSocket订阅: 已确认 {subscriptions['active']} | 未确认 {subscriptions['unconfirmed']} | "
+                f"等待确认 {subscriptions['pending']} | 确认超时 {subscriptions['ack_timeouts']} 次 | "
+                f"服务器错误 {subscriptions['errors']} 次")
+            if len(subscriptions["connections"]) > 1:
+                out("  各连接频道数: " + " | ".join(f"{name} {n}" for name, n in subscriptions["connections"].items()))
+        
+        # 下单前检查
+        fees = stats["fees"]
+        if fees["rates"]:
+            rates = " | ".join(f"{symbol} {r['taker'] * 100:.4f}%/{r['maker'] * 100:.4f}%"
+                               + ("" if r["source"] == "account" else "（默认）")
+                               for symbol, r in fees["rates"].items())
+            out(f"\n手续费 (吃单/挂单): {rates}")
+            if fees["skipped"]:
+                skipped = ", ".join(f"{symbol} {n}" for symbol, n in fees["skipped"].items())
+                out(f"  预期收益不足往返手续费 ×{fees['min_edge_multiple']} 跳过开仓: {skipped}")
+        
+        ev = stats.get("ev_gate")
+        if ev:
+            out(f"\n期望值检查 (阈值 {ev['min_ev_bps']} bps):")
+            for symbol, s in ev["symbols"].items():
+                line = f"  {symbol}: 通过 {s['passed']} | 跳过 {s['skipped']}"
+                for key, label in (("passed_avg", "通过"), ("skipped_avg", "跳过")):
+                    avg = s.get(key)
+                    if avg:
+                        line += (f" | {label}均值 EV {avg['ev_bps']:+.2f} (毛 {avg['gross_bps']:+.2f} / 费 {avg['fee_bps']:.2f} / "
+                                 f"开 {avg['entry_cost_bps']:.2f} / 平 {avg['exit_cost_bps']:.2f})")
+                out(line)
         
-        print("="*70)
+        guard = stats.get("latency_guard")
+        if guard:
+            rest = f"{guard['rest_ms']:.0f}ms" if guard["rest_ms"] is not None else "样本不足"
+            ws = f"{guard['ws_ms']:.0f}ms" if guard["ws_ms"] is not None else "样本不足"
+            out(f"\n延迟保护 (p{guard['percentile']:g}): REST {rest} / 上限 {guard['rest_limit_ms']:g}ms | "
+                f"WebSocket {ws} / 上限 {guard['ws_limit_ms']:g}ms")
+            status = f"降级中（{guard['reason']}）" if guard["degraded"] else "正常"
+            out(f"  状态: {status} | 降级 {guard['degraded_count']} 次，共 {guard['degraded_seconds']:.0f} 秒 | "
+                f"跳过开仓 {guard['blocked_entries']} 次")
+        
+        wash = stats.get("wash_guard")
+        if wash:
+            blocked = wash["blocked"]
+            out(f"\n对敲防护: 拒绝 时间间隔 {blocked['interval']} / 价格距离 {blocked['price_distance']} / "
+                f"自成交 {blocked['self_match']} | 疑似自成交 {wash['flagged']} 笔")
+            for record in wash["recent"]:
+                out(f"  {record['symbol']} {record['side']} {record['price']} ↔ {record['opposite_price']} "
+                    f"相隔 {record['interval_ms']}ms（订单 {record['order_id']} / {record['opposite_order_id']}）")
+        
+        pre_trade = stats.get("pre_trade")
+        if pre_trade and (pre_trade["rejected"] or pre_trade["kill_switch"]):
+            reasons = " | ".join(f"{reason} {n}" for reason, n in sorted(pre_trade["rejections"].items()))
+            out(f"\n下单检查: 通过 {pre_trade['passed']} | 拒绝 {pre_trade['rejected']}" + (f"（{reasons}）" if reasons else ""))
+            if pre_trade["kill_switch"]:
+                out(f"  紧急停止已打开: {pre_trade['kill_switch']}（只允许平仓）")
+        
+        # 合约规格缓存
+        specs = stats.get("contract_specs")
+        if specs and specs["contracts"]:
+            age = f"{specs['age_seconds'] / 60:.0f} 分钟前" if specs["age_seconds"] is not None else "未知"
+            out(f"\n合约规格: {specs['contracts']} 个合约 | 更新于 {age} | "
+                f"定期刷新 {specs['refreshes']} 次 | 推送更新 {specs['ws_updates']} 次")
+        
+        # 行情序列检查
+        sequence = stats.get("ws_sequence")
+        if sequence and any(sequence.values()):
+            out(f"\n行情序列: 深度缺口 {sequence['depth_gaps']} | 深度乱序 {sequence['depth_out_of_order']} | "
+                f"重新同步 {sequence['depth_resyncs']} 次（进行中 {sequence['resyncing']}） | "
+                f"重复成交 {sequence['trade_duplicates']} | 乱序成交 {sequence['trade_out_of_order']}")
+        
+        # 订单簿校验
+        book = stats.get("ws_book")
+        if book and (book["resyncs"] or book["checksum_ok"]):
+            checksum = f"校验和通过 {book['checksum_ok']} / 不一致 {book['checksum_mismatches']} | " if book["checksum"] else ""
+            out(f"\n订单簿校验: {checksum}一致性问题 {book['inconsistent']} | 重新同步 {book['resyncs']} 次（进行中 {book['resyncing']}）")
+        
+        # 消息压缩
+        compression = stats.get("ws_compression")
+        if compression:
+            out("\nWebSocket压缩: " + " | ".join(
+                f"{name} {'permessage-deflate' if c['negotiated'] else '未协商'} "
+                f"{c['compressed_bytes'] / 1024:.1f}KB → {c['inflated_bytes'] / 1024:.1f}KB"
+                + (f"（节省 {1 - c['compressed_bytes'] / c['inflated_bytes']:.0%}）" if c['inflated_bytes'] else "")
+                for name, c in compression.items()
+            ))
+        
+        # 私有频道账户事件
+        account = stats.get("account_events")
+        if account:
+            out(f"\n账户事件: {account['events']} 条 | 订单 {account['orders']} | 成交 {account['fills']} | "
+                f"持仓 {account['positions']}（与本地不一致 {account['position_mismatches']} 次） | "
+                f"资金费 {account['funding']} 笔 合计 {account['funding_total']:+.4f} USDT | 解析错误 {account['errors']}")
+        
+        # 启动对账
+        warmup = stats.get("warmup")
+        if warmup and not warmup["passed"]:
+            failed = " | ".join(f"{name}: {detail}" for name, detail in warmup["failed"].items())
+            out(f"\n启动检查: 未通过（暂停交易）| 尝试 {warmup['attempts']} 次 | {failed}")
+        
+        reconcile = stats.get("reconcile")
+        if reconcile:
+            state = "已完成" if reconcile["completed"] else "未完成（暂停交易）"
+            out(f"\n启动对账: {state} | 尝试 {reconcile['attempts']} 次 | "
+                  f"撤销孤儿挂单 {reconcile['canceled_orders']} | 接管孤儿挂单 {reconcile['adopted_orders']} | "
+                  f"接管孤儿持仓 {reconcile['adopted_positions']} | 平掉孤儿持仓 {reconcile['closed_positions']} | "
+                  f"离线已平仓 {reconcile['vanished_positions']}")
+        
+        # 盈亏核对
+        crosscheck = stats.get("pnl_crosscheck")
+        if crosscheck and crosscheck["last"]:
+            last = crosscheck["last"]
+            exchange, local = last["exchange"], last["local"]
+            state = "一致" if last["matched"] else "⚠ 不一致"
+            out(f"\n盈亏核对: {state} | 成交 交易所 {exchange['fills']} / 本地 {local['fills']} 笔 | "
+                  f"已实现盈亏 交易所 {exchange['realized_pnl']:.4f} / 本地 {local['realized_pnl']:.4f} USDT | "
+                  f"成交额 交易所 {exchange['volume']:.2f} / 本地 {local['volume']:.2f} USDT | "
+                  f"手续费 {exchange['fees']:.4f} USDT | 核对 {crosscheck['checks']} 次，不一致 {crosscheck['mismatches']} 次")
+        
+        # 多端点
+        endpoints = stats.get("endpoints")
+        if endpoints:
+            out("\n端点:")
+            for name, pool in (("REST", endpoints["rest"]), ("WebSocket", endpoints["websocket"])):
+                if not pool:
+                    continue
+                out(f"  {name} 当前: {pool['current']} (切换 {pool['switches']} 次)")
+                for ep in pool["endpoints"]:
+                    latency = f"{ep['latency_ms']:.0f}ms" if ep["latency_ms"] is not None else "-"
+                    state = "正常" if ep["healthy"] else "故障冷却中"
+                    out(f"    {ep['url']}: 延迟 {latency} | {state} | 故障 {ep['failures']} 次")
+        
+        # 交易链路延迟
+        latency = stats.get("latency")
+        if latency:
+            names = {"signal_to_order": "信号->下单", "order_to_ack": "下单->确认", "loop_iteration": "主循环"}
+            out("\n延迟分位数 (ms):")
+            for stage, s in latency.items():
+                out(f"  {names.get(stage, stage)}: {s['count']} 次 | p50 {s['p50']:.2f} | p90 {s['p90']:.2f} | "
+                      f"p99 {s['p99']:.2f} | p99.9 {s['p99.9']:.2f} | 最大 {s['max']:.2f}")
+        
+        # 订单通道
+        channel = stats.get("order_channel")
+        if channel and any(c["submitted"] for c in channel.values()):
+            names = {"cancel": "撤单", "exit": "平仓", "entry": "开仓"}
+            out("\n订单通道: " + " | ".join(
+                f"{names[p]} {c['submitted']} 笔 排队 {c['depth']}/{c['max_depth']} "
+                f"等待 {c['avg_wait_ms']:.1f}/{c['max_wait_ms']:.1f}ms"
+                for p, c in channel.items() if c["submitted"]
+            ) + "（排队为当前/最大深度，等待为平均/最大）")
+        
+        # 订单闸门
+        gate = stats.get("order_gate")
+        if gate and (gate["suppressed"] or gate["throttled"]):
+            out(f"\n订单闸门: 放行 {gate['passed']} | 抑制重复 {gate['suppressed']} | "
+                  f"限频等待 {gate['throttled']} 次 (共 {gate['throttle_wait']:.2f} 秒)")
+        
+        # 资金分配
+        if stats.get("allocation"):
+            alloc = stats["allocation"]
+            reason = f"（{alloc['reason']}）" if alloc["reason"] else ""
+            out(f"\n资金分配: 权重 {alloc['weight']:.0%} | 预算 {alloc['budget']:.2f} USDT | "
+                  f"盈亏 {alloc['pnl']:.4f} USDT | 回撤 {alloc['drawdown']*100:.2f}% | "
+                  f"状态 {alloc['status']}{reason}")
+        
+        # A/B测试
+        if self.ab_test:
+            self._format_ab_test(self.ab_test.compare(), out)
+        
+        # 脚本信号
+        if stats.get("script"):
+            script = stats["script"]
+            out(f"\n脚本信号: {script['path']} | 加载 {script['reloads']} 次 | 错误 {script['errors']} 次")
+        
+        # 组合信号
+        if stats.get("ensemble"):
+            ensemble = stats["ensemble"]
+            out(f"\n组合信号: {ensemble['sources']} | 评估 {ensemble['evaluations']} 次 | 信号 {ensemble['signals']} 次 | "
+                  f"一致度不足 {ensemble['below_agreement']} 次 | 方向冲突 {ensemble['conflicts']} 次")
+            out("  投票: " + " | ".join(f"{name} 多{votes['long']}/空{votes['short']}" for name, votes in ensemble["votes"].items()))
+            for symbol, detail in ensemble["last"].items():
+                out(f"  {symbol}: " + ", ".join(f"{name}={vote or '-'}" for name, vote in detail.items()))
+            for name, model in ensemble["models"].items():
+                last = f"{model['last_score']:.4f}" if model["last_score"] is not None else "-"
+                out(f"  {name} 模型: {model['path']} | 推理 {model['predictions']} 次 | 错误 {model['errors']} 次 | 最近评分 {last}")
+        
+        # 特征记录
+        if stats.get("feature_recorder"):
+            recorder = stats["feature_recorder"]
+            out(f"\n特征记录: {recorder['directory']} | 记录 {recorder['recorded']} | 已写入 {recorder['written']} | "
+                  f"待标注 {recorder['pending']} | 写入失败 {recorder['errors']} 次")
+        
+        # 信号Webhook
+        if stats.get("webhook"):
+            webhook = stats["webhook"]
+            out(f"\n信号Webhook: 收到 {webhook['received']} | 接受 {webhook['accepted']} | 拒绝 {webhook['rejected']}")
+        
+        # 外部信号总线
+        if stats.get("signal_bus"):
+            bus = stats["signal_bus"]
+            out(f"\n信号总线 ({bus['backend']}): 收到 {bus['received']} | 拒绝 {bus['rejected']} | "
+                  f"发布 {bus['published']} | 丢弃 {bus['dropped']}")
+        
+        # 事件流
+        if stats.get("event_stream"):
+            stream = stats["event_stream"]
+            sent = ", ".join(f"{k} {v}" for k, v in stream["sent"].items()) or "无"
+            out(f"\n事件流 ({stream['backend']}): 已发送 {sent} | 丢弃 {stream['dropped']}")
+        
+        # 通知限流
+        throttle = stats.get("notify_throttle")
+        if throttle and throttle["channels"]:
+            out("\n通知: " + " | ".join(
+                f"{channel} 发送 {c['sent']}（严重 {c['escalated']}）去重 {c['deduplicated']} 限速 {c['rate_limited']}"
+                for channel, c in throttle["channels"].items()))
+        
+        # VWAP分批平仓
+        if stats.get("vwap"):
+            vwap = stats["vwap"]
+            profiles = ", ".join(vwap["profiles"]) or "无（均匀分配）"
+            out(f"\nVWAP分批平仓: {vwap['executions']} 次, {vwap['child_orders']} 笔子订单 | 成交量分布: {profiles}")
+        
+        # 盘口过滤
+        if stats.get("liquidity_filter"):
+            lf = stats["liquidity_filter"]
+            out(f"\n盘口过滤 (最大价差 {lf['max_spread_bps']} bps, 统计 {lf['depth_levels']} 档):")
+            out(f"  检查信号: {lf['checked']} | 跳过: {lf['skipped_total']}")
+            for symbol, counts in lf["skipped"].items():
+                reasons = ", ".join(f"{reason} {count}" for reason, count in counts.items())
+                out(f"  {symbol}: {reasons}")
+        
+        # 持仓量与成交量过滤
+        if stats.get("activity_filter"):
+            af = stats["activity_filter"]
+            out(f"\n持仓量/成交量过滤 (最低持仓价值 {af['min_open_interest']:.0f} USDT, "
+                f"最低24小时成交额 {af['min_volume_24h']:.0f} USDT):")
+            out(f"  检查信号: {af['checked']} | 跳过: {af['skipped_total']}")
+            for symbol, activity in af["activity"].items():
+                counts = af["skipped"].get(symbol, {})
+                reasons = ", ".join(f"{reason} {count}" for reason, count in counts.items())
+                out(f"  {symbol}: 持仓价值 {activity['open_interest_value']:.0f} | "
+                    f"24小时成交额 {activity['value_24h']:.0f}" + (f" | 跳过: {reasons}" if reasons else ""))
+        
+        # 基差监控
+        if stats.get("basis"):
+            basis = stats["basis"]
+            out(f"\n基差 (永续 - 指数):")
+            for symbol, b in basis["symbols"].items():
+                zscore = f"{b['zscore']:+.2f}" if b["zscore"] is not None else "-"
+                out(f"  {symbol}: {b['basis_bps']:+.2f} bps (z {zscore}) | 均值 {b['mean_bps']:+.2f} | "
+                    f"区间 [{b['min_bps']:+.2f}, {b['max_bps']:+.2f}]" + (f" | 跳过开仓 {b['blocked']}" if b["blocked"] else ""))
+        
+        # 资金费结算规避
+        if stats.get("funding_window"):
+            fw = stats["funding_window"]
+            out(f"\n资金费规避 (结算前 {fw['window_seconds']:.0f} 秒, {fw['action']}):")
+            for symbol, rate in fw["rates"].items():
+                delayed = fw["delayed"].get(symbol, 0)
+                closed = fw["closed"].get(symbol, 0)
+                out(f"  {symbol}: 费率 {rate:+.6f} | 延后开仓 {delayed} | 结算前平仓 {closed}")
+        
+        # 回撤仓位调节
+        if stats.get("drawdown_throttle"):
+            dd = stats["drawdown_throttle"]
+            out(f"\n回撤调节: 当前回撤 {dd['drawdown']*100:.2f}% (最大 {dd['max_drawdown']*100:.2f}%, "
+                  f"峰值权益 {dd['peak_equity']:.2f} USDT) | 开仓仓位 x{dd['scale']:.2f}")
+        
+        # 库存偏斜
+        if stats.get("inventory_skew"):
+            inv = stats["inventory_skew"]
+            out(f"\n库存偏斜: 净库存 {inv['inventory']:+.2f} / 上限 {inv['max_inventory']} (强度 {inv['strength']}) | "
+                f"缩小 {inv['reduced']} 次 | 放大 {inv['boosted']} 次 | 达上限跳过 {inv['blocked']} 次")
+        
+        # 影子交易对比
+        if snapshot.shadow:
+            shadow = snapshot.shadow
+            out(f"\n影子交易对比:")
+            out(f"  决策分歧: {shadow['divergences']} / {shadow['evaluations']} 次评估")
+            out(f"  平均滑点: {shadow['avg_slippage_bps']:.2f} bps (最大 {shadow['max_slippage_bps']:.2f} bps, {shadow['fills_compared']} 笔成交)")
+            out(f"  执行落差: {shadow['implementation_shortfall']:.4f} USDT")
+            out(f"  影子收益: {shadow['shadow_return_pct']:.4f}% ({shadow['shadow_trades']} 笔)")
+        
+        out("="*70)
+        return "\n".join(lines)
     
     async def get_detailed_stats(self) -> Dict[str, Any]:
         """获取详细统计信息"""
-        stats = self.strategy.get_performance_stats()
+        snapshot = self.snapshots.latest or await self.snapshots.wait_newer()
+        stats = snapshot.stats
         
         # 计算更多统计信息
-        total_pnl = sum(float(record.pnl) for record in self.strategy.trade_records)
-        avg_trade_pnl = total_pnl / len(self.strategy.trade_records) if self.strategy.trade_records else 0
+        total_pnl = snapshot.total_pnl
+        avg_trade_pnl = total_pnl / snapshot.total_trades if snapshot.total_trades else 0
         
         # 计算最大回撤
-        max_drawdown = self._calculate_max_drawdown()
+        max_drawdown = self._calculate_max_drawdown(snapshot.equity_history)
         
         # 计算夏普比率（简化版）
-        sharpe_ratio = self._calculate_sharpe_ratio()
+        sharpe_ratio = self._calculate_sharpe_ratio(snapshot.equity_history)
         
         return {
             **stats,
@@ -139,18 +684,37 @@ class PerformanceMonitor:
             "avg_trade_pnl": avg_trade_pnl,
             "max_drawdown": max_drawdown,
             "sharpe_ratio": sharpe_ratio,
-            "total_trade_records": len(self.strategy.trade_records)
+            "total_trade_records": snapshot.trade_records,
+            "spilled_trade_records": snapshot.spilled_trade_records
         }
-    
-    def _calculate_max_drawdown(self) -> float:
+
+    def _format_ab_test(self, result: Dict[str, Any], out: Callable[[str], None]):
+        """打印A/B测试对比（B相对A的差值，p值按正态近似）"""
+        name_a, name_b = result["variants"]
+        modes = ["模拟盘" if paper else "实盘" for paper in result["paper"]]
+        out(f"\nA/B测试: {name_a}({modes[0]}) vs {name_b}({modes[1]})")
+        out(f"  交易次数: {result['trades'][0]} vs {result['trades'][1]} | "
+              f"总盈亏: {result['total_pnl'][0]:.4f} vs {result['total_pnl'][1]:.4f} USDT")
+        for key, label, scale, unit in (
+            ("return_bps", "每笔收益", 1, " bps"),
+            ("hit_rate", "胜率", 100, "%"),
+            ("slippage_bps", "滑点", 1, " bps"),
+        ):
+            item = result[key]
+            p_value = f"p={item['p_value']:.3f}" if item["p_value"] is not None else "样本不足"
+            flag = " *显著*" if item["significant"] else ""
+            out(f"  {label}: {item['mean_a']*scale:.2f}{unit} vs {item['mean_b']*scale:.2f}{unit} "
+                  f"(差 {item['diff']*scale:+.2f}{unit}, {p_value}){flag}")
+
+    def _calculate_max_drawdown(self, equity_history: Tuple[float, ...]) -> float:
         """计算最大回撤"""
-        if len(self.strategy.equity_history) < 2:
+        if len(equity_history) < 2:
             return 0.0
         
-        peak = self.strategy.equity_history[0]
+        peak = equity_history[0]
         max_dd = 0.0
         
-        for value in self.strategy.equity_history:
+        for value in equity_history:
             if value > peak:
                 peak = value
             else:
@@ -159,14 +723,14 @@ class PerformanceMonitor:
         
         return max_dd
     
-    def _calculate_sharpe_ratio(self) -> float:
+    def _calculate_sharpe_ratio(self, equity_history: Tuple[float, ...]) -> float:
         """计算夏普比率（简化版）"""
-        if len(self.strategy.equity_history) < 2:
+        if len(equity_history) < 2:
             return 0.0
         
         returns = []
-        for i in range(1, len(self.strategy.equity_history)):
-            ret = (self.strategy.equity_history[i] - self.strategy.equity_history[i-1]) / self.strategy.equity_history[i-1]
+        for i in range(1, len(equity_history)):
+            ret = (equity_history[i] - equity_history[i-1]) / equity_history[i-1]
             returns.append(ret)
         
         if len(returns) < 2:
@@ -0,0 +1,95 @@
+"""
+通知限流模块
+
+在告警和通知发送到各通道（通知Webhook、Discord）之前统一限流：
+- 去重: 同一通道上内容相同的消息在去重窗口内只发送一次
+- 限速: 每个通道每分钟最多发送的消息数（滑动窗口）
+- 升级: 严重级别的消息（见 alerts.py 的 critical）不受去重和限速限制，直接发送
+被限流的消息已写入日志，只是不发送到该通道。邮件只发送严重告警，由 email_notifier.py 单独限流。
+"""
+
+import time
+from collections import deque
+from typing import Any, Deque, Dict, Optional
+
+CHANNELS = ("webhook", "discord")
+
+
+def parse_rate_limits(text: str) -> Dict[str, int]:
+    """
+    解析各通道每分钟消息上限
+
+    Args:
+        text: "通道:条数,..."（如 "webhook:30,discord:20"）
+
+    Raises:
+        ValueError: 格式错误
+    """
+    limits: Dict[str, int] = {}
+    for item in text.split(","):
+        if not item.strip():
+            continue
+        channel, _, count = item.partition(":")
+        try:
+            limits[channel.strip().lower()] = int(count)
+        except ValueError:
+            raise ValueError(f"通知限速格式错误: {item}（应为 通道:每分钟条数）")
+    return limits
+
+
+class NotificationThrottle:
+    """通知去重和限速"""
+
+    def __init__(self, dedup_seconds: float = 60.0, rate_limits: Optional[Dict[str, int]] = None):
+        """
+        Args:
+            dedup_seconds: 去重窗口（秒，0为不去重）
+            rate_limits: 通道 -> 每分钟最多消息数（未列出或为0的通道不限速）
+        """
+        self.dedup_seconds = dedup_seconds
+        self.rate_limits = rate_limits or {}
+        self._recent: Dict[tuple, float] = {}                # (通道, 内容) -> 最近发送时间
+        self._sent: Dict[str, Deque[float]] = {}             # 通道 -> 最近一分钟的发送时间
+        self.stats: Dict[str, Dict[str, int]] = {}
+
+    @classmethod
+    def from_config(cls, config) -> "NotificationThrottle":
+        return cls(config.notify_dedup_seconds, config.notify_rate_limits)
+
+    def allow(self, channel: str, message: str, critical: bool = False, now: Optional[float] = None) -> bool:
+        """
+        判断消息是否发送到通道（允许时记入去重和限速）
+
+        Args:
+            channel: 通道名称
+            message: 去重用的消息内容
+            critical: 严重级别（不受限流）
+            now: 当前时间（秒）
+        """
+        now = time.time() if now is None else now
+        stats = self.stats.setdefault(channel, {"sent": 0, "deduplicated": 0, "rate_limited": 0, "escalated": 0})
+        sent = self._sent.setdefault(channel, deque())
+        while sent and now - sent[0] >= 60:
+            sent.popleft()
+        if self.dedup_seconds > 0 and len(self._recent) > 1000:
+            self._recent = {k: t for k, t in self._recent.items() if now - t < self.dedup_seconds}
+
+        if critical:
+            stats["escalated"] += 1
+        else:
+            last = self._recent.get((channel, message))
+            if last is not None and now - last < self.dedup_seconds:
+                stats["deduplicated"] += 1
+                return False
+            limit = self.rate_limits.get(channel, 0)
+            if limit > 0 and len(sent) >= limit:
+                stats["rate_limited"] += 1
+                return False
+        stats["sent"] += 1
+        sent.append(now)
+        self._recent[(channel, message)] = now
+        return True
+
+    def get_stats(self) -> Dict[str, Any]:
+        return {"dedup_seconds": self.dedup_seconds, "rate_limits": dict(self.rate_limits),
+                "channels": {channel: dict(stats) for channel, stats in self.stats.items()}}
@@ -0,0 +1,185 @@
+"""
+OCO（二选一）止盈止损模块
+
+开仓订单成交后为持仓挂出关联的止盈/止损退出腿，其中一腿成交即撤销另一腿。
+EdgeX 不支持原生OCO，由客户端根据订单状态回报模拟：
+- 止盈腿：只减仓限价单，挂在交易所盘口，价格为持仓的止盈价
+- 止损腿：由策略按最新价格触发，触发时先撤销止盈腿再市价平仓
+- 止盈腿成交完成后止损腿随之失效；止盈腿未成交即被撤销/拒绝时解除关联，
+  由策略恢复客户端止盈检查
+- 加仓后持仓数量变化时撤销原止盈腿，按新数量重新挂单
+
+需要能收到订单状态回报（模拟撮合引擎或私有WebSocket）。
+"""
+
+import asyncio
+from typing import Dict, Optional, Any
+from loguru import logger
+
+from edgex_types import Order, OrderSide, OrderType, Position, TradeDirection
+from order_tracker import OrderTracker, TrackedOrder
+
+
+class OcoPair:
+    """一个持仓的止盈/止损关联退出单"""
+
+    def __init__(self, key: str, symbol: str, take_profit_order_id: str,
+                 take_profit: float, stop_loss: float, quantity: float):
+        self.key = key
+        self.symbol = symbol
+        self.take_profit_order_id = take_profit_order_id
+        self.take_profit = take_profit
+        self.stop_loss = stop_loss
+        self.quantity = quantity
+
+
+class OcoManager:
+    """OCO退出单管理器"""
+
+    def __init__(self, client, order_tracker: OrderTracker):
+        """
+        Args:
+            client: EdgeX客户端
+            order_tracker: 订单跟踪器（止盈腿的状态回报由此分发）
+        """
+        self.client = client
+        self.order_tracker = order_tracker
+        self.pairs: Dict[str, OcoPair] = {}
+        self._by_order: Dict[str, str] = {}
+        self._lock = asyncio.Lock()
+        order_tracker.add_update_callback(self._on_order_update)
+
+        # 统计
+        self.placed = 0
+        self.take_profit_filled = 0
+        self.stop_loss_triggered = 0
+        self.detached = 0
+
+    def get(self, key: str) -> Optional[OcoPair]:
+        """获取持仓的关联退出单"""
+        return self.pairs.get(key)
+
+    async def place(self, key: str, position: Position, position_side: Optional[TradeDirection]) -> Optional[OcoPair]:
+        """
+        为持仓挂出（或按最新持仓数量和止盈止损价更新）关联退出单
+
+        Args:
+            key: 持仓索引键
+            position: 持仓
+            position_side: 双向持仓模式下的持仓方向（单向持仓为None）
+
+        Returns:
+            Optional[OcoPair]: 关联退出单；持仓没有止盈价或下单失败时为None
+        """
+        async with self._lock:
+            return await self._place(key, position, position_side)
+
+    async def _place(self, key: str, position: Position, position_side: Optional[TradeDirection]) -> Optional[OcoPair]:
+        existing = self.pairs.get(key)
+        if existing and (existing.quantity, existing.take_profit, existing.stop_loss) == \
+                (position.size, position.take_profit, position.stop_loss):
+            return existing
+        if existing and not await self.cancel(key):
+            return None
+        if position.take_profit <= 0:
+            return None
+
+        order = Order(
+            symbol=position.symbol,
+            side=OrderSide.SELL if position.direction == TradeDirection.LONG else OrderSide.BUY,
+            order_type=OrderType.LIMIT,
+            quantity=position.size,
+            price=position.take_profit,
+            leverage=position.leverage,
+            reduce_only=True,
+            position_side=position_side
+        )
+        try:
+            result = await self.client.place_order(order)
+        except Exception as e:
+            logger.error(f"[OCO] {position.symbol} 止盈腿下单失败，使用客户端止盈检查: {e}")
+            return None
+
+        order_id = str((result or {}).get("data", {}).get("orderId", ""))
+        if not order_id:
+            return None
+
+        # 止盈腿常驻盘口，不受订单存活时间限制
+        self.order_tracker.register(order_id, order, position.take_profit).ttl_ms = None
+
+        pair = OcoPair(key, position.symbol, order_id, position.take_profit, position.stop_loss, position.size)
+        self.pairs[key] = pair
+        self._by_order[order_id] = key
+        self.placed += 1
+        logger.info(
+            f"[OCO] {position.symbol} 挂出退出单: 止盈 {position.take_profit} (订单 {order_id}) / "
+            f"止损 {position.stop_loss} (客户端触发)，数量 {position.size}"
+        )
+
+        # 订单状态回报可能先于下单响应到达
+        tracked = self.order_tracker.get(order_id)
+        if tracked and tracked.is_done:
+            self._on_order_update(tracked)
+        return self.pairs.get(key)
+
+    async def cancel(self, key: str, stop_triggered: bool = False) -> bool:
+        """
+        撤销持仓的止盈腿（止损触发、反向信号等其他途径平仓前调用）
+
+        Args:
+            key: 持仓索引键
+            stop_triggered: 是否因止损腿触发而撤销
+
+        Returns:
+            bool: 止盈腿是否已不在盘口（撤单失败时返回False，调用方不应继续平仓，
+            以免与可能已成交的止盈腿重复平仓）
+        """
+        pair = self.pairs.get(key)
+        if not pair:
+            return True
+
+        tracked = self.order_tracker.get(pair.take_profit_order_id)
+        if not (tracked and tracked.is_done):
+            try:
+                await self.client.cancel_order(pair.symbol, pair.take_profit_order_id)
+            except Exception as e:
+                tracked = self.order_tracker.get(pair.take_profit_order_id)
+                if not (tracked and tracked.is_done):
+                    logger.error(f"[OCO] {pair.symbol} 撤销止盈腿 {pair.take_profit_order_id} 失败: {e}")
+                    return False
+
+        self._detach(key)
+        if stop_triggered:
+            self.stop_loss_triggered += 1
+            logger.info(f"[OCO] {pair.symbol} 止损腿触发，已撤销止盈腿 {pair.take_profit_order_id}")
+        return True
+
+    def _detach(self, key: str) -> Optional[OcoPair]:
+        pair = self.pairs.pop(key, None)
+        if pair:
+            self._by_order.pop(pair.take_profit_order_id, None)
+        return pair
+
+    def _on_order_update(self, tracked: TrackedOrder):
+        """止盈腿终结：成交则止损腿失效，未成交则解除关联"""
+        key = self._by_order.get(tracked.order_id)
+        if not key or not tracked.is_done:
+            return
+
+        pair = self._detach(key)
+        if tracked.filled > 0:
+            self.take_profit_filled += 1
+            logger.info(f"[OCO] {pair.symbol} 止盈腿成交 {tracked.filled} @ {tracked.avg_fill_price:.4f}，止损腿已撤销")
+        else:
+            self.detached += 1
+            logger.warning(f"[OCO] {pair.symbol} 止盈腿 {tracked.order_id} 未成交即结束（{tracked.status}），恢复客户端止盈检查")
+
+    def get_stats(self) -> Dict[str, Any]:
+        """获取OCO统计"""
+        return {
+            "active": len(self.pairs),
+            "placed": self.placed,
+            "take_profit_filled": self.take_profit_filled,
+            "stop_loss_triggered": self.stop_loss_triggered,
+            "detached": self.detached,
+        }
@@ -0,0 +1,162 @@
+"""
+订单通道
+
+下单和撤单请求经由专用的高优先级通道发送，行情突发不会拖慢退出订单：
+- 独立任务按优先级出队：撤单 > 平仓（只减仓）订单 > 开仓订单，同一优先级先进先出
+- 下单频率限制（见 order_gate.py）在通道中执行：等待额度期间到达的撤单立即发送，
+  平仓订单排在等待中的开仓订单之前；撤单不受频率限制
+- 请求出队后立即并发发送（不等待前一个请求完成），使用SDK中为订单保留的独立连接池，
+  不会排在行情/K线查询之后等待连接
+- 统计各优先级的排队深度和排队耗时（入队到发出），在性能报告中输出
+"""
+
+import asyncio
+import itertools
+import time
+from typing import Any, Awaitable, Callable, Dict, Optional, Set
+
+from loguru import logger
+
+from order_gate import OrderGate
+
+PRIORITY_CANCEL = 0
+PRIORITY_EXIT = 1
+PRIORITY_ENTRY = 2
+PRIORITY_NAMES = {PRIORITY_CANCEL: "cancel", PRIORITY_EXIT: "exit", PRIORITY_ENTRY: "entry"}
+
+
+class _Request:
+    """排队中的订单请求"""
+
+    __slots__ = ("priority", "seq", "send", "throttled", "future", "enqueued", "blocked_at")
+
+    def __init__(self, priority: int, seq: int, send: Callable[[], Awaitable[Any]], throttled: bool,
+                 future: asyncio.Future):
+        self.priority = priority
+        self.seq = seq
+        self.send = send
+        self.throttled = throttled
+        self.future = future
+        self.enqueued = time.monotonic()
+        self.blocked_at: Optional[float] = None
+
+    def __lt__(self, other: "_Request") -> bool:
+        return (self.priority, self.seq) < (other.priority, other.seq)
+
+
+class OrderChannel:
+    """按优先级发送订单请求的专用通道"""
+
+    def __init__(self, gate: OrderGate):
+        """
+        Args:
+            gate: 出站订单闸门（提供下单频率限制额度）
+        """
+        self.gate = gate
+        self._queue: "asyncio.PriorityQueue[_Request]" = asyncio.PriorityQueue()
+        self._seq = itertools.count()
+        self._wakeup = asyncio.Event()
+        self._task: Optional[asyncio.Task] = None
+        self._inflight: Set[asyncio.Task] = set()
+        self._stats: Dict[int, Dict[str, float]] = {
+            priority: {"submitted": 0, "depth": 0, "max_depth": 0, "total_wait": 0.0, "max_wait": 0.0}
+            for priority in PRIORITY_NAMES
+        }
+
+    async def submit(self, priority: int, send: Callable[[], Awaitable[Any]], throttled: bool = True) -> Any:
+        """
+        提交请求并等待结果
+
+        Args:
+            priority: 优先级（PRIORITY_CANCEL / PRIORITY_EXIT / PRIORITY_ENTRY）
+            send: 发送请求的协程函数（出队时调用）
+            throttled: 是否受下单频率限制
+
+        Returns:
+            Any: send 的返回值（send 抛出的异常原样抛出）
+        """
+        if self._task is None or self._task.done():
+            self._task = asyncio.create_task(self._run())
+        request = _Request(priority, next(self._seq), send, throttled, asyncio.get_running_loop().create_future())
+        stats = self._stats[priority]
+        stats["submitted"] += 1
+        stats["depth"] += 1
+        stats["max_depth"] = max(stats["max_depth"], stats["depth"])
+        self._queue.put_nowait(request)
+        self._wakeup.set()
+        return await request.future
+
+    async def _run(self):
+        """出队并发送请求（通道任务）"""
+        while True:
+            self._wakeup.clear()
+            request = await self._queue.get()
+            if request.future.done():
+                # 调用方已取消
+                self._dequeued(request)
+                continue
+
+            if request.throttled:
+                delay = self.gate.slot_delay()
+                if delay > 0:
+                    # 放回队列等待额度，期间到达的更高优先级请求会先出队
+                    if request.blocked_at is None:
+                        request.blocked_at = time.monotonic()
+                    self._queue.put_nowait(request)
+                    try:
+                        await asyncio.wait_for(self._wakeup.wait(), timeout=delay)
+                    except asyncio.TimeoutError:
+                        pass
+                    continue
+                if request.blocked_at is not None:
+                    self.gate.record_throttle(time.monotonic() - request.blocked_at)
+                self.gate.take_slot()
+
+            self._dequeued(request)
+            task = asyncio.create_task(self._send(request))
+            self._inflight.add(task)
+            task.add_done_callback(self._inflight.discard)
+
+    def _dequeued(self, request: _Request):
+        stats = self._stats[request.priority]
+        stats["depth"] -= 1
+        wait = time.monotonic() - request.enqueued
+        stats["total_wait"] += wait
+        stats["max_wait"] = max(stats["max_wait"], wait)
+
+    @staticmethod
+    async def _send(request: _Request):
+        try:
+            result = await request.send()
+        except Exception as e:
+            if not request.future.done():
+                request.future.set_exception(e)
+            else:
+                logger.debug(f"[订单通道] 调用方已取消，请求结果: {e}")
+            return
+        if not request.future.done():
+            request.future.set_result(result)
+
+    async def close(self):
+        """停止通道任务（已发出的请求继续完成）"""
+        if self._task:
+            self._task.cancel()
+            self._task = None
+
+    def queued(self, priority: int) -> int:
+        """该优先级当前排队中的请求数"""
+        return int(self._stats[priority]["depth"])
+
+    def get_stats(self) -> Dict[str, Dict[str, float]]:
+        """各优先级的提交数、当前/最大排队深度、平均/最大排队耗时（毫秒）"""
+        summary = {}
+        for priority, stats in self._stats.items():
+            dequeued = stats["submitted"] - stats["depth"]
+            summary[PRIORITY_NAMES[priority]] = {
+                "submitted": stats["submitted"],
+                "depth": stats["depth"],
+                "max_depth": stats["max_depth"],
+                "avg_wait_ms": stats["total_wait"] / dequeued * 1000 if dequeued else 0.0,
+                "max_wait_ms": stats["max_wait"] * 1000,
+            }
+        return summary
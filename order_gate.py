@@ -0,0 +1,100 @@
+"""
+出站订单闸门
+
+所有下单请求在发送前经过闸门：
+- 重复订单抑制：时间窗口内相同交易对/方向/数量/价格（及触发价、只减仓、持仓方向）的订单视为重复
+  （逻辑缺陷或重试导致），直接拒绝
+- 下单频率限制：滑动窗口内每秒最多发送的订单数，超过时等待到有空闲额度再发送
+  （等待在订单通道中进行，见 order_channel.py，平仓订单优先获得额度）
+"""
+
+import time
+from collections import deque
+from typing import Deque, Dict, Any, Tuple
+
+from edgex_types import Order
+
+
+class DuplicateOrderError(ValueError):
+    """重复订单被抑制"""
+
+
+class OrderGate:
+    """出站订单闸门"""
+
+    def __init__(self, duplicate_window_ms: int, max_orders_per_second: int):
+        """
+        Args:
+            duplicate_window_ms: 重复订单判定窗口（毫秒，0为不检查）
+            max_orders_per_second: 每秒最多下单数（0为不限制）
+        """
+        self.duplicate_window_ms = duplicate_window_ms
+        self.max_orders_per_second = max_orders_per_second
+        self._recent: Dict[Tuple, int] = {}
+        self._sent: Deque[float] = deque()
+
+        # 统计
+        self.passed = 0
+        self.suppressed = 0
+        self.throttled = 0
+        self.throttle_wait = 0.0
+
+    @staticmethod
+    def _key(order: Order) -> Tuple:
+        return (order.symbol, order.side, order.quantity, order.price, order.trigger_price,
+                order.reduce_only, order.position_side)
+
+    def check(self, order: Order, allow_duplicate: bool = False):
+        """
+        重复订单检查（通过后记录该订单）
+
+        Args:
+            order: 待发送的订单
+            allow_duplicate: 是否跳过重复检查（有意连续发送相同订单，如VWAP子订单）
+
+        Raises:
+            DuplicateOrderError: 窗口内已发送过相同订单
+        """
+        now_ms = int(time.time() * 1000)
+        if self.duplicate_window_ms > 0:
+            if not allow_duplicate:
+                self._recent = {k: t for k, t in self._recent.items() if now_ms - t < self.duplicate_window_ms}
+                key = self._key(order)
+                if key in self._recent:
+                    self.suppressed += 1
+                    raise DuplicateOrderError(
+                        f"{now_ms - self._recent[key]}ms 内已发送相同订单 "
+                        f"{order.symbol} {order.side.value} {order.quantity} @ {order.price or '市价'}，已抑制"
+                    )
+            self._recent[self._key(order)] = now_ms
+        self.passed += 1
+
+    def slot_delay(self) -> float:
+        """距下一个下单额度的等待时间（秒），有空闲额度时为0"""
+        if self.max_orders_per_second <= 0:
+            return 0.0
+        now = time.monotonic()
+        while self._sent and now - self._sent[0] >= 1.0:
+            self._sent.popleft()
+        if len(self._sent) < self.max_orders_per_second:
+            return 0.0
+        return max(1.0 - (now - self._sent[0]), 0.0)
+
+    def take_slot(self):
+        """占用一个下单额度（slot_delay 为0时调用）"""
+        if self.max_orders_per_second > 0:
+            self._sent.append(time.monotonic())
+
+    def record_throttle(self, wait: float):
+        """记录一次限频等待"""
+        self.throttled += 1
+        self.throttle_wait += wait
+
+    def get_stats(self) -> Dict[str, Any]:
+        """获取闸门统计"""
+        return {
+            "passed": self.passed,
+            "suppressed": self.suppressed,
+            "throttled": self.throttled,
+            "throttle_wait": self.throttle_wait,
+        }
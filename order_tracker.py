@@ -0,0 +1,347 @@
+"""
+订单跟踪模块
+
+汇总订单状态更新（私有WebSocket或模拟撮合引擎）与成交回报，
+为执行逻辑提供"等待订单完成"等异步接口。
+
+下单响应只说明请求被接受，订单在收到交易所的订单更新或成交回报（或按ID查询到订单）后才算确认；
+确认超时仍查询不到的订单标记为 UNCONFIRMED（已提交、未确认），由策略的下单确认检查继续处理。
+"""
+
+import asyncio
+import time
+from typing import Callable, Dict, List, Optional, Any
+from loguru import logger
+
+from edgex_types import Fill, Order, OrderSide, OrderType
+
+
+# 订单终结状态
+TERMINAL_STATUSES = {"FILLED", "CANCELED", "REJECTED", "EXPIRED"}
+
+# 已提交但超过确认超时仍未得到交易所确认的订单状态
+UNCONFIRMED = "UNCONFIRMED"
+
+
+class TrackedOrder:
+    """被跟踪的订单"""
+
+    def __init__(self, order_id: str, order: Order, reference_price: Optional[float] = None,
+                 ttl_ms: Optional[int] = None):
+        self.order_id = order_id
+        self.symbol = order.symbol
+        self.side = order.side
+        self.order_type = order.order_type
+        self.price = order.price
+        self.quantity = float(order.quantity)
+        self.reduce_only = order.reduce_only
+        self.position_side = order.position_side
+        self.reference_price = reference_price
+        self.filled = 0.0
+        self.fill_value = 0.0
+        self.fee = 0.0
+        self.status = "PENDING"
+        # 是否收到过交易所的订单更新或成交回报
+        self.confirmed = False
+        self.created_at = int(time.time() * 1000)
+        self.updated_at = self.created_at
+        self.fills: List[Fill] = []
+        # 存活时间（毫秒），超时后自动撤单；None表示不限
+        self.ttl_ms = ttl_ms
+        self.cancel_requested = False
+        # 是否已触发完成回调
+        self.completion_reported = False
+
+    @property
+    def remaining(self) -> float:
+        """剩余未成交数量"""
+        return max(self.quantity - self.filled, 0.0)
+
+    @property
+    def avg_fill_price(self) -> float:
+        """成交均价"""
+        return self.fill_value / self.filled if self.filled > 0 else 0.0
+
+    @property
+    def is_done(self) -> bool:
+        """订单是否已终结"""
+        return self.status in TERMINAL_STATUSES
+
+    def is_expired(self, now_ms: int) -> bool:
+        """订单是否已超过存活时间"""
+        return self.ttl_ms is not None and not self.is_done and now_ms - self.created_at >= self.ttl_ms
+
+    def to_dict(self) -> Dict[str, Any]:
+        """转换为字典（事件推送用）"""
+        return {
+            "order_id": self.order_id,
+            "symbol": self.symbol,
+            "side": self.side.value,
+            "order_type": self.order_type.value,
+            "price": self.price,
+            "quantity": self.quantity,
+            "filled": self.filled,
+            "avg_fill_price": self.avg_fill_price,
+            "fee": self.fee,
+            "status": self.status,
+            "reduce_only": self.reduce_only,
+            "reference_price": self.reference_price,
+            "created_at": self.created_at,
+            "updated_at": self.updated_at,
+        }
+
+
+class OrderTracker:
+    """订单跟踪器"""
+
+    def __init__(self, default_ttl_ms: Optional[int] = None):
+        """
+        初始化订单跟踪器
+
+        Args:
+            default_ttl_ms: 限价单的默认存活时间（毫秒），None表示不限
+        """
+        self.default_ttl_ms = default_ttl_ms
+        self.orders: Dict[str, TrackedOrder] = {}
+        self._events: Dict[str, asyncio.Event] = {}
+        self._loop: Optional[asyncio.AbstractEventLoop] = None
+        self.done_callbacks: List[Callable[[TrackedOrder], None]] = []
+        self.update_callbacks: List[Callable[[TrackedOrder], None]] = []
+
+    def add_update_callback(self, callback: Callable[[TrackedOrder], None]):
+        """添加订单状态变化回调（订单更新或成交回报后触发）"""
+        self.update_callbacks.append(callback)
+
+    def add_done_callback(self, callback: Callable[[TrackedOrder], None]):
+        """添加订单完成回调（订单终结且有成交时触发一次）"""
+        self.done_callbacks.append(callback)
+
+    def register(self, order_id: str, order: Order, reference_price: Optional[float] = None,
+                 ttl_ms: Optional[int] = None) -> TrackedOrder:
+        """
+        注册需要跟踪的订单
+
+        Args:
+            order_id: 交易所订单ID
+            order: 订单对象
+            reference_price: 下单时的参考价格
+            ttl_ms: 存活时间（毫秒），默认使用跟踪器的默认值（仅限价单，条件单不过期）
+
+        Returns:
+            TrackedOrder: 跟踪记录
+        """
+        self._loop = asyncio.get_running_loop()
+
+        if ttl_ms is None and order.order_type == OrderType.LIMIT:
+            ttl_ms = self.default_ttl_ms
+
+        tracked = self.orders.get(order_id)
+        if tracked is None:
+            tracked = TrackedOrder(order_id, order, reference_price, ttl_ms)
+            self.orders[order_id] = tracked
+        else:
+            # 订单更新可能先于下单响应到达
+            tracked.symbol = order.symbol
+            tracked.reduce_only = order.reduce_only
+            tracked.position_side = order.position_side
+            tracked.reference_price = reference_price
+            tracked.ttl_ms = ttl_ms
+
+        self._events.setdefault(order_id, asyncio.Event())
+        if tracked.is_done:
+            self._events[order_id].set()
+            self._check_done(tracked)
+        return tracked
+
+    def get(self, order_id: str) -> Optional[TrackedOrder]:
+        """获取跟踪记录"""
+        return self.orders.get(order_id)
+
+    def get_open_orders(self, symbol: Optional[str] = None) -> List[TrackedOrder]:
+        """获取未终结的订单"""
+        return [
+            o for o in self.orders.values()
+            if not o.is_done and (symbol is None or o.symbol == symbol)
+        ]
+
+    def on_order_update(self, data: Dict[str, Any]):
+        """
+        处理订单状态更新（字段与EdgeX订单一致：id/status/cumFillSize/cumFillValue）
+
+        Args:
+            data: 订单数据
+        """
+        order_id = str(data.get("id", ""))
+        if not order_id:
+            return
+
+        tracked = self.orders.get(order_id)
+        if tracked is None:
+            # 非本进程注册的订单（如重启前遗留的挂单）同样适用默认存活时间
+            try:
+                order_type = OrderType(data.get("type"))
+            except ValueError:
+                order_type = OrderType.MARKET
+            ttl_ms = self.default_ttl_ms if order_type == OrderType.LIMIT else None
+            tracked = TrackedOrder(order_id, Order(
+                symbol=data.get("symbol") or data.get("contractId", ""),
+                side=OrderSide(data.get("side", "BUY")),
+                order_type=order_type,
+                quantity=float(data.get("size", 0)),
+                price=float(data.get("price", 0)) or None,
+                leverage=1
+            ), ttl_ms=ttl_ms)
+            self.orders[order_id] = tracked
+
+        tracked.confirmed = True
+        prev_state = (tracked.status, tracked.filled)
+        status = str(data.get("status", tracked.status))
+        cum_fill_size = float(data.get("cumFillSize", tracked.filled) or 0)
+        if cum_fill_size > tracked.filled:
+            cum_fill_value = data.get("cumFillValue")
+            tracked.fill_value = float(cum_fill_value) if cum_fill_value else tracked.fill_value
+            tracked.filled = cum_fill_size
+
+        if tracked.filled > 0 and tracked.remaining > 0 and status == "OPEN":
+            status = "PARTIALLY_FILLED"
+
+        tracked.status = status
+        tracked.updated_at = int(time.time() * 1000)
+        self._notify(order_id)
+        if (tracked.status, tracked.filled) != prev_state:
+            self._emit_update(tracked)
+        self._check_done(tracked)
+
+    def on_fill(self, fill: Fill):
+        """
+        处理成交回报
+
+        Args:
+            fill: 成交回报
+        """
+        tracked = self.orders.get(fill.order_id)
+        if tracked is None:
+            return
+
+        tracked.confirmed = True
+        tracked.fills.append(fill)
+        tracked.fee += fill.fee
+
+        # 以逐笔成交累计为准（订单更新的cumFillSize可能滞后）
+        filled = sum(f.size for f in tracked.fills)
+        if filled > tracked.filled:
+            tracked.filled = filled
+            tracked.fill_value = sum(f.size * f.price for f in tracked.fills)
+
+        if tracked.remaining <= 1e-12:
+            tracked.status = "FILLED"
+        elif not tracked.is_done:
+            tracked.status = "PARTIALLY_FILLED"
+
+        tracked.updated_at = int(time.time() * 1000)
+        self._notify(fill.order_id)
+        self._emit_update(tracked)
+        self._check_done(tracked)
+
+    def _emit_update(self, tracked: TrackedOrder):
+        """分发订单状态变化回调"""
+        for callback in self.update_callbacks:
+            try:
+                callback(tracked)
+            except Exception as e:
+                logger.error(f"订单更新回调函数执行失败: {e}")
+
+    def _check_done(self, tracked: TrackedOrder):
+        """订单终结且成交均价可用时触发完成回调"""
+        if tracked.completion_reported or not tracked.is_done or tracked.fill_value <= 0:
+            return
+
+        tracked.completion_reported = True
+        for callback in self.done_callbacks:
+            try:
+                callback(tracked)
+            except Exception as e:
+                logger.error(f"订单完成回调函数执行失败: {e}")
+
+    def get_unconfirmed(self, min_age_ms: int) -> List[TrackedOrder]:
+        """获取提交超过 min_age_ms 仍未收到交易所确认的未终结订单"""
+        cutoff = int(time.time() * 1000) - min_age_ms
+        return [o for o in self.orders.values() if not o.confirmed and not o.is_done and o.created_at <= cutoff]
+
+    def mark_unconfirmed(self, order_id: str):
+        """订单超过确认超时仍查询不到：标记为已提交、未确认"""
+        tracked = self.orders.get(order_id)
+        if tracked is None or tracked.confirmed or tracked.is_done or tracked.status == UNCONFIRMED:
+            return
+        tracked.status = UNCONFIRMED
+        tracked.updated_at = int(time.time() * 1000)
+        self._emit_update(tracked)
+
+    def mark_missing(self, order_id: str):
+        """未确认的订单在交易所始终不存在：按已拒绝终结（唤醒等待的协程）"""
+        tracked = self.orders.get(order_id)
+        if tracked is None or tracked.confirmed or tracked.is_done:
+            return
+        tracked.status = "REJECTED"
+        tracked.updated_at = int(time.time() * 1000)
+        self._notify(order_id)
+        self._emit_update(tracked)
+
+    def get_expired_orders(self) -> List[TrackedOrder]:
+        """获取已超过存活时间且尚未请求撤单的订单"""
+        now_ms = int(time.time() * 1000)
+        return [o for o in self.orders.values() if not o.cancel_requested and o.is_expired(now_ms)]
+
+    async def wait_until_done(self, order_id: str, timeout: float) -> Optional[TrackedOrder]:
+        """
+        等待订单终结（完全成交/撤销）或超时
+
+        Args:
+            order_id: 订单ID
+            timeout: 超时时间（秒）
+
+        Returns:
+            Optional[TrackedOrder]: 跟踪记录（超时返回当前状态）
+        """
+        tracked = self.orders.get(order_id)
+        if tracked is None:
+            return None
+
+        deadline = time.monotonic() + timeout
+        event = self._events.setdefault(order_id, asyncio.Event())
+        while not tracked.is_done:
+            remaining = deadline - time.monotonic()
+            if remaining <= 0:
+                break
+            event.clear()
+            try:
+                await asyncio.wait_for(event.wait(), timeout=remaining)
+            except asyncio.TimeoutError:
+                break
+
+        return tracked
+
+    def _notify(self, order_id: str):
+        """唤醒等待该订单的协程（线程安全）"""
+        event = self._events.get(order_id)
+        if event is None:
+            return
+
+        if self._loop and self._loop.is_running():
+            try:
+                running = asyncio.get_running_loop()
+            except RuntimeError:
+                running = None
+            if running is self._loop:
+                event.set()
+            else:
+                self._loop.call_soon_threadsafe(event.set)
+        else:
+            event.set()
+
+    def prune(self, max_age_seconds: int = 3600):
+        """清理已终结的历史订单"""
+        cutoff = int(time.time() * 1000) - max_age_seconds * 1000
+        for order_id in [oid for oid, o in self.orders.items() if o.is_done and o.updated_at < cutoff]:
+            self.orders.pop(order_id, None)
+            self._events.pop(order_id, None)
@@ -0,0 +1,146 @@
+"""
+成交盈亏重建模块
+
+用交易所记录的历史成交（见 EdgeXClient.get_user_trades）重建已实现盈亏和成交额，与本地交易记录交叉核对，
+发现漏收成交回报、本地盈亏计算错误等问题：
+- 按交易对净持仓的平均成本法计算：同向成交更新开仓均价，反向成交按均价结算已实现盈亏，超出持仓的部分反向开仓
+- 与本地口径一致，平仓成交的手续费计入该笔盈亏，开仓成交的手续费单独统计
+- 只统计本次启动之后的成交；启动前已有的持仓（对账接管）在此期间的平仓会被当作反向开仓，
+  双向持仓模式下按净持仓计算，这两种情况下的差异不代表本地记录有误
+"""
+
+import time
+from typing import Any, Dict, Iterable, List, Optional
+
+from loguru import logger
+
+from edgex_types import Fill, OrderSide
+
+
+class PnlSummary:
+    """单个交易对（或合计）的重建结果"""
+
+    def __init__(self):
+        self.fills = 0
+        self.volume = 0.0          # 成交额（USDT）
+        self.gross_pnl = 0.0       # 未扣手续费的已实现盈亏
+        self.closing_fees = 0.0    # 平仓成交的手续费
+        self.fees = 0.0            # 全部手续费
+        self.position = 0.0        # 净持仓（多为正）
+        self.entry_price = 0.0     # 净持仓均价
+
+    @property
+    def realized_pnl(self) -> float:
+        """已实现盈亏（扣除平仓手续费，与本地交易记录口径一致）"""
+        return self.gross_pnl - self.closing_fees
+
+    def apply(self, fill: Fill):
+        """按平均成本法计入一笔成交"""
+        self.fills += 1
+        self.volume += fill.size * fill.price
+        self.fees += fill.fee
+        signed = fill.size if fill.side == OrderSide.BUY else -fill.size
+
+        if self.position and (self.position > 0) != (signed > 0):
+            closed = min(abs(self.position), abs(signed))
+            direction = 1 if self.position > 0 else -1
+            self.gross_pnl += (fill.price - self.entry_price) * closed * direction
+            self.closing_fees += fill.fee
+            self.position += closed * -direction
+            signed += closed * direction
+            if abs(self.position) < 1e-12:
+                self.position = 0.0
+                self.entry_price = 0.0
+
+        if abs(signed) > 1e-12:
+            new_position = self.position + signed
+            self.entry_price = (abs(self.position) * self.entry_price + abs(signed) * fill.price) / abs(new_position)
+            self.position = new_position
+
+    def to_dict(self) -> Dict[str, float]:
+        return {"fills": self.fills, "volume": self.volume, "realized_pnl": self.realized_pnl,
+                "gross_pnl": self.gross_pnl, "fees": self.fees, "position": self.position}
+
+
+def reconstruct_pnl(fills: Iterable[Fill]) -> Dict[str, PnlSummary]:
+    """
+    按成交重建各交易对的已实现盈亏和成交额
+
+    Args:
+        fills: 成交列表（按成交时间升序）
+
+    Returns:
+        Dict[str, PnlSummary]: 交易对 -> 重建结果
+    """
+    summaries: Dict[str, PnlSummary] = {}
+    for fill in fills:
+        summaries.setdefault(fill.symbol, PnlSummary()).apply(fill)
+    return summaries
+
+
+class PnlCrossCheck:
+    """交易所成交与本地交易记录的定期核对"""
+
+    def __init__(self, tolerance: float):
+        """
+        Args:
+            tolerance: 已实现盈亏允许的差异（USDT）
+        """
+        self.tolerance = tolerance
+        self.start_ms = int(time.time() * 1000)
+        self.local_fills = 0
+        self.local_volume = 0.0
+        self.checks = 0
+        self.mismatches = 0
+        self.last: Optional[Dict[str, Any]] = None
+
+    def record_local_fill(self, fill: Fill):
+        """记录一笔本地收到的成交回报"""
+        self.local_fills += 1
+        self.local_volume += fill.size * fill.price
+
+    async def run(self, client, symbols: List[str], trade_records: Iterable) -> Dict[str, Any]:
+        """
+        拉取启动以来的交易所成交，重建盈亏并与本地记录比较，差异超出容差时告警
+
+        Args:
+            client: EdgeXClient
+            symbols: 本策略交易对
+            trade_records: 本地交易记录（TradeRecord，时间戳为秒）
+        """
+        fills = await client.get_user_trades(start_ms=self.start_ms)
+        summaries = reconstruct_pnl(f for f in fills if f.symbol in symbols)
+        exchange = PnlSummary()
+        for summary in summaries.values():
+            exchange.fills += summary.fills
+            exchange.volume += summary.volume
+            exchange.gross_pnl += summary.gross_pnl
+            exchange.closing_fees += summary.closing_fees
+            exchange.fees += summary.fees
+
+        start_seconds = self.start_ms // 1000
+        local_pnl = sum(r.pnl for r in trade_records if r.timestamp >= start_seconds)
+        pnl_diff = exchange.realized_pnl - local_pnl
+        matched = abs(pnl_diff) <= self.tolerance and exchange.fills == self.local_fills
+
+        self.checks += 1
+        if not matched:
+            self.mismatches += 1
+            logger.warning(f"[盈亏核对] 交易所成交 {exchange.fills} 笔 / 本地 {self.local_fills} 笔，"
+                           f"交易所已实现盈亏 {exchange.realized_pnl:.4f} / 本地 {local_pnl:.4f} USDT"
+                           f"（差异 {pnl_diff:+.4f}），成交额 {exchange.volume:.2f} / {self.local_volume:.2f} USDT")
+        else:
+            logger.debug(f"[盈亏核对] 一致: 成交 {exchange.fills} 笔，已实现盈亏 {local_pnl:.4f} USDT")
+
+        self.last = {
+            "matched": matched,
+            "exchange": exchange.to_dict(),
+            "local": {"fills": self.local_fills, "volume": self.local_volume, "realized_pnl": local_pnl},
+            "pnl_diff": pnl_diff,
+            "symbols": {s: summary.to_dict() for s, summary in summaries.items()},
+            "checked_at": int(time.time()),
+        }
+        return self.last
+
+    def get_stats(self) -> Dict[str, Any]:
+        return {"checks": self.checks, "mismatches": self.mismatches, "last": self.last}
@@ -0,0 +1,183 @@
+"""
+下单前检查模块
+
+每笔订单在进入订单闸门前依次经过一组检查（任一项拒绝即不发送），拒绝原因按类别计数：
+- kill_switch: 紧急停止开关打开时只允许只减仓订单（平仓），开关可由代码或开关文件（存在即打开）控制
+- lot_tick: 数量按合约数量步长向下取整、价格和触发价按价格步长取整（直接修改订单），
+  取整后数量为0、开仓数量低于交易所最小下单量或超过单笔上限时拒绝
+- rate_limit: 开仓订单排队数已达上限时拒绝（信号已过时，平仓订单不受限）
+- 策略提供的检查（见 HighFrequencyStrategy）: 最小名义价值（min_notional）、保证金是否充足（margin）、
+  单个交易对仓位上限（risk_limit）
+订单闸门的重复订单抑制同样计入拒绝统计（duplicate）
+"""
+
+import os
+from typing import Callable, Dict, List, Optional
+
+from loguru import logger
+
+from contract_specs import ContractSpec
+from edgex_types import Order, TRIGGER_ORDER_TYPES
+
+
+class OrderRejected(ValueError):
+    """订单未通过下单前检查"""
+
+    def __init__(self, reason: str, message: str):
+        super().__init__(f"[{reason}] {message}")
+        self.reason = reason
+
+
+class PreTradeCheck:
+    """检查项：check() 通过时返回None（可直接修改订单），拒绝时返回原因说明"""
+
+    name = ""
+
+    def check(self, order: Order) -> Optional[str]:
+        raise NotImplementedError
+
+
+class FunctionCheck(PreTradeCheck):
+    """由函数实现的检查项"""
+
+    def __init__(self, name: str, func: Callable[[Order], Optional[str]]):
+        self.name = name
+        self.func = func
+
+    def check(self, order: Order) -> Optional[str]:
+        return self.func(order)
+
+
+class KillSwitch:
+    """紧急停止开关"""
+
+    def __init__(self, path: str = ""):
+        """
+        Args:
+            path: 开关文件（文件存在时视为打开，空为不使用）
+        """
+        self.path = path
+        self.reason: Optional[str] = None
+
+    @property
+    def engaged(self) -> bool:
+        return self.reason is not None or bool(self.path and os.path.exists(self.path))
+
+    def engage(self, reason: str):
+        """打开开关（停止开仓）"""
+        if self.reason is None:
+            logger.error(f"[紧急停止] 停止开仓: {reason}")
+        self.reason = reason
+
+    def release(self):
+        """关闭开关（开关文件需另行删除）"""
+        if self.reason is not None:
+            logger.info("[紧急停止] 已解除")
+        self.reason = None
+
+    def describe(self) -> Optional[str]:
+        if self.reason is not None:
+            return self.reason
+        if self.path and os.path.exists(self.path):
+            return f"开关文件 {self.path} 存在"
+        return None
+
+
+class KillSwitchCheck(PreTradeCheck):
+    name = "kill_switch"
+
+    def __init__(self, switch: KillSwitch):
+        self.switch = switch
+
+    def check(self, order: Order) -> Optional[str]:
+        if order.reduce_only or not self.switch.engaged:
+            return None
+        return f"紧急停止开关已打开（{self.switch.describe()}），只允许平仓"
+
+
+class LotTickCheck(PreTradeCheck):
+    name = "lot_tick"
+
+    def __init__(self, spec_provider: Callable[[str], Optional[ContractSpec]]):
+        """
+        Args:
+            spec_provider: 交易对 -> 合约规格（未加载时返回None，此时不取整）
+        """
+        self.spec_provider = spec_provider
+
+    def check(self, order: Order) -> Optional[str]:
+        spec = self.spec_provider(order.symbol)
+        if spec is None:
+            return None
+        quantity = spec.round_size(order.quantity)
+        if quantity <= 0:
+            return f"数量 {order.quantity} 按步长 {spec.step_size} 取整后为0"
+        if not order.reduce_only and spec.min_order_size > 0 and quantity < spec.min_order_size:
+            return f"数量 {quantity} 低于交易所最小下单量 {spec.min_order_size}"
+        if spec.max_order_size > 0 and quantity > spec.max_order_size:
+            return f"数量 {quantity} 超过单笔上限 {spec.max_order_size}"
+        order.quantity = quantity
+        if order.price:
+            order.price = spec.round_price(order.price)
+        if order.order_type in TRIGGER_ORDER_TYPES and order.trigger_price:
+            order.trigger_price = spec.round_price(order.trigger_price)
+        return None
+
+
+class RateLimitCheck(PreTradeCheck):
+    name = "rate_limit"
+
+    def __init__(self, queued_entries: Callable[[], int], max_queued: int):
+        """
+        Args:
+            queued_entries: 当前排队中的开仓订单数
+            max_queued: 排队上限（0为不限制）
+        """
+        self.queued_entries = queued_entries
+        self.max_queued = max_queued
+
+    def check(self, order: Order) -> Optional[str]:
+        if order.reduce_only or self.max_queued <= 0:
+            return None
+        queued = self.queued_entries()
+        if queued >= self.max_queued:
+            return f"已有 {queued} 笔开仓订单等待下单额度"
+        return None
+
+
+class PreTradeValidator:
+    """下单前检查链"""
+
+    def __init__(self, checks: Optional[List[PreTradeCheck]] = None):
+        self.checks: List[PreTradeCheck] = list(checks or [])
+        self.passed = 0
+        self.rejections: Dict[str, int] = {}
+        self.last_rejection: Optional[str] = None
+
+    def add(self, check: PreTradeCheck):
+        """追加检查项"""
+        self.checks.append(check)
+
+    def validate(self, order: Order):
+        """
+        依次执行检查（取整等调整直接作用于订单）
+
+        Raises:
+            OrderRejected: 任一检查拒绝
+        """
+        for check in self.checks:
+            message = check.check(order)
+            if message:
+                self.record_rejection(check.name, f"{order.symbol} {order.side.value} {order.quantity}: {message}")
+                raise OrderRejected(check.name, message)
+        self.passed += 1
+
+    def record_rejection(self, reason: str, message: str):
+        """记录一次拒绝（也用于检查链之外的拒绝，如重复订单抑制）"""
+        self.rejections[reason] = self.rejections.get(reason, 0) + 1
+        self.last_rejection = f"[{reason}] {message}"
+        logger.warning(f"[下单检查] 拒绝 {message}（{reason}）")
+
+    def get_stats(self) -> Dict[str, object]:
+        return {"passed": self.passed, "rejected": sum(self.rejections.values()),
+                "rejections": dict(self.rejections), "last_rejection": self.last_rejection}
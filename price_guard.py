@@ -0,0 +1,85 @@
+"""
+异常价格保护模块
+
+防止错误的K线数据、瞬时插针或解析错误产生的离谱价格进入交易：
+- 价格序列: 新价格偏离该交易对最近一个已确认价格超过 max_deviation 时不计入价格序列；
+  连续 confirm_ticks 个价格彼此一致（互相偏离不超过 max_deviation）时视为真实跳变，以此重新确认
+- 信号和订单价格: 偏离参考价格（盘口中间价，无盘口时为已确认价格）超过 max_deviation 时拒绝
+"""
+
+from typing import Any, Dict, Optional, Tuple
+
+from loguru import logger
+
+
+class PriceGuard:
+    """异常价格检查"""
+
+    def __init__(self, max_deviation: float, confirm_ticks: int = 3):
+        """
+        Args:
+            max_deviation: 允许的最大偏离比例（如 0.05 为5%）
+            confirm_ticks: 跳变后需要连续一致的价格个数
+        """
+        self.max_deviation = max_deviation
+        self.confirm_ticks = max(confirm_ticks, 1)
+        self.validated: Dict[str, float] = {}                 # 交易对 -> 最近已确认价格
+        self._pending: Dict[str, Tuple[float, int]] = {}     # 交易对 -> (跳变后的候选价格, 连续一致次数)
+        self.stats = {"rejected_prices": 0, "rejected_signals": 0, "rebased": 0}
+
+    @staticmethod
+    def deviation(price: float, reference: float) -> float:
+        return abs(price - reference) / reference if reference > 0 else 0.0
+
+    def observe(self, symbol: str, price: float) -> bool:
+        """
+        检查价格序列中的新价格
+
+        Returns:
+            bool: 是否接受（计入价格序列）
+        """
+        if price <= 0:
+            self.stats["rejected_prices"] += 1
+            return False
+        validated = self.validated.get(symbol)
+        if validated is None or self.deviation(price, validated) <= self.max_deviation:
+            self.validated[symbol] = price
+            self._pending.pop(symbol, None)
+            return True
+
+        candidate, count = self._pending.get(symbol, (price, 0))
+        count = count + 1 if self.deviation(price, candidate) <= self.max_deviation else 1
+        if count >= self.confirm_ticks:
+            logger.warning(f"[价格保护] {symbol} 价格由 {validated} 跳变至 {price}，连续 {count} 个价格一致，重新确认")
+            self.validated[symbol] = price
+            self._pending.pop(symbol, None)
+            self.stats["rebased"] += 1
+            return True
+        self._pending[symbol] = (price if count == 1 else candidate, count)
+        self.stats["rejected_prices"] += 1
+        logger.warning(f"[价格保护] {symbol} 价格 {price} 偏离已确认价格 {validated} "
+                       f"{self.deviation(price, validated):.2%}，不计入价格序列")
+        return False
+
+    def reference(self, symbol: str, mid: Optional[float] = None) -> Optional[float]:
+        """参考价格：盘口中间价，无盘口时为已确认价格"""
+        return mid or self.validated.get(symbol)
+
+    def check(self, symbol: str, price: float, mid: Optional[float] = None) -> Optional[str]:
+        """
+        检查价格是否偏离参考价格
+
+        Returns:
+            Optional[str]: 超出允许偏离时的原因说明（没有参考价格时不检查）
+        """
+        reference = self.reference(symbol, mid)
+        if not reference:
+            return None
+        deviation = self.deviation(price, reference)
+        if deviation > self.max_deviation:
+            source = "盘口中间价" if mid else "已确认价格"
+            return f"价格 {price} 偏离{source} {reference} {deviation:.2%}（上限 {self.max_deviation:.2%}）"
+        return None
+
+    def get_stats(self) -> Dict[str, Any]:
+        return dict(self.stats)
@@ -0,0 +1,113 @@
+"""
+参考价格来源模块
+
+ticker模式下进入策略价格序列（均线、信号、止盈止损判断）的价格，每个策略可单独配置（策略列表的 config 中设置
+signal_price_source）。每次ticker推送时按所选来源取样，来源暂时不可用时使用最新成交价：
+- last: ticker最新成交价
+- mid: 盘口买一卖一中间价
+- mark: 标记价格（EdgeX为预言机价格，与交易所强平和条件单触发一致）
+- index: 指数价格（现货指数）
+- weighted_mid: 盘口加权公允价格（见 fair_price.py）
+"""
+
+from typing import Any, Dict, Optional
+
+from edgex_types import MarkPriceData, OrderBook
+from fair_price import weighted_mid
+
+PRICE_SOURCES = ("last", "mid", "mark", "index", "weighted_mid")
+
+
+class PriceSource:
+    """参考价格来源"""
+
+    name = "last"
+    needs_depth = False  # 是否需要订阅盘口深度
+
+    def __init__(self):
+        self.stats = {"samples": 0, "fallbacks": 0}
+
+    def _price(self, last: float, book: Optional[OrderBook], mark: Optional[MarkPriceData]) -> Optional[float]:
+        return last
+
+    def price(self, last: float, book: Optional[OrderBook] = None, mark: Optional[MarkPriceData] = None) -> float:
+        """
+        取样参考价格
+
+        Args:
+            last: 最新成交价
+            book: 本地订单簿
+            mark: 最新标记价格/指数价格
+
+        Returns:
+            float: 参考价格（来源不可用时为最新成交价）
+        """
+        self.stats["samples"] += 1
+        price = self._price(last, book, mark)
+        if price is None or price <= 0:
+            self.stats["fallbacks"] += 1
+            return last
+        return price
+
+    def get_stats(self) -> Dict[str, Any]:
+        return {"source": self.name, **self.stats}
+
+
+class MidPriceSource(PriceSource):
+    name = "mid"
+    needs_depth = True
+
+    def _price(self, last, book, mark):
+        if not book or not book.bids or not book.asks or book.bids[0][0] >= book.asks[0][0]:
+            return None
+        return (book.bids[0][0] + book.asks[0][0]) / 2
+
+
+class MarkPriceSource(PriceSource):
+    name = "mark"
+
+    def _price(self, last, book, mark):
+        return mark.mark_price if mark else None
+
+
+class IndexPriceSource(PriceSource):
+    name = "index"
+
+    def _price(self, last, book, mark):
+        return mark.index_price if mark else None
+
+
+class WeightedMidSource(PriceSource):
+    name = "weighted_mid"
+    needs_depth = True
+
+    def __init__(self, levels: int = 5):
+        super().__init__()
+        self.levels = levels
+
+    def _price(self, last, book, mark):
+        return weighted_mid(book, self.levels)
+
+
+def create_price_source(name: str, levels: int = 5) -> PriceSource:
+    """
+    按名称创建参考价格来源
+
+    Args:
+        name: 来源名称（见 PRICE_SOURCES）
+        levels: weighted_mid 每侧统计的档位数
+
+    Raises:
+        ValueError: 未知的来源
+    """
+    if name == "last":
+        return PriceSource()
+    if name == "mid":
+        return MidPriceSource()
+    if name == "mark":
+        return MarkPriceSource()
+    if name == "index":
+        return IndexPriceSource()
+    if name == "weighted_mid":
+        return WeightedMidSource(levels)
+    raise ValueError(f"未知的价格来源: {name}（可选 {'/'.join(PRICE_SOURCES)}）")
@@ -0,0 +1,144 @@
+"""
+启动对账模块
+
+进程崩溃或被强制停止后，交易所上可能留有上次运行的挂单和持仓。策略启动时先对账再交易：
+- 运行期间把本策略的持仓（含止盈止损）和未终结订单保存到本地状态文件（内容变化时写入，停止时写入）
+- 启动时查询交易所的未成交订单和持仓，与本地状态匹配：
+  - 本地有记录的订单重新登记跟踪；没有记录的孤儿订单按配置撤销（cancel）、接管（adopt）或停止交易（halt）
+  - 本地有记录的持仓恢复止盈止损；没有记录的孤儿持仓按配置接管（adopt，按策略配置计算止盈止损）、
+    平仓（close）或停止交易（halt）
+  - 本地有记录但交易所已不存在的持仓（离线期间被平仓或强平）丢弃
+- 对账完成前不开仓也不平仓；对账失败（查询或撤单出错、停止交易策略）时定期重新对账，
+  每次对账都以交易所当前状态为准，重复执行结果一致
+"""
+
+import json
+import os
+from typing import Any, Dict, List, Optional
+
+from loguru import logger
+
+from edgex_types import Position
+from order_tracker import TrackedOrder
+
+ORDER_POLICIES = ("cancel", "adopt", "halt")
+POSITION_POLICIES = ("adopt", "close", "halt")
+
+
+class StateStore:
+    """本地持仓与订单状态文件"""
+
+    def __init__(self, path: str):
+        """
+        Args:
+            path: 状态文件路径（JSON）
+        """
+        self.path = path
+        self._last_written: Optional[str] = None
+
+    def load(self) -> Dict[str, Dict[str, Any]]:
+        """
+        读取状态文件
+
+        Returns:
+            Dict: {"positions": 持仓索引键 -> 持仓字段, "orders": 订单ID -> 订单字段}，文件不存在或损坏时为空
+        """
+        state: Dict[str, Dict[str, Any]] = {"positions": {}, "orders": {}}
+        if not os.path.exists(self.path):
+            return state
+        try:
+            with open(self.path, "r", encoding="utf-8") as fp:
+                data = json.load(fp)
+            state["positions"] = dict(data.get("positions") or {})
+            state["orders"] = dict(data.get("orders") or {})
+        except (OSError, ValueError, AttributeError) as e:
+            logger.warning(f"[对账] 读取状态文件 {self.path} 失败，按无本地状态对账: {e}")
+        return state
+
+    def save(self, positions: Dict[str, Position], orders: List[TrackedOrder]):
+        """
+        写入当前持仓和未终结订单（内容未变化时跳过，先写临时文件再替换，避免写到一半时崩溃损坏文件）
+
+        Args:
+            positions: 持仓索引键 -> 持仓
+            orders: 未终结的跟踪订单
+        """
+        data = {
+            "positions": {key: position.model_dump(mode="json") for key, position in positions.items()},
+            "orders": {
+                o.order_id: {
+                    "symbol": o.symbol,
+                    "side": o.side.value,
+                    "order_type": o.order_type.value,
+                    "quantity": o.quantity,
+                    "price": o.price,
+                    "reduce_only": o.reduce_only,
+                    "position_side": o.position_side.value if o.position_side else None,
+                    "reference_price": o.reference_price,
+                }
+                for o in orders
+            },
+        }
+        text = json.dumps(data, ensure_ascii=False, sort_keys=True)
+        if text == self._last_written:
+            return
+        try:
+            directory = os.path.dirname(self.path)
+            if directory:
+                os.makedirs(directory, exist_ok=True)
+            tmp_path = f"{self.path}.tmp"
+            with open(tmp_path, "w", encoding="utf-8") as fp:
+                fp.write(text)
+            os.replace(tmp_path, self.path)
+            self._last_written = text
+        except OSError as e:
+            logger.error(f"[对账] 写入状态文件 {self.path} 失败: {e}")
+
+
+class ReconcilePlan:
+    """交易所状态与本地状态的匹配结果"""
+
+    def __init__(self):
+        self.known_orders: List[Dict[str, Any]] = []      # 本地有记录的挂单（交易所订单字典）
+        self.orphan_orders: List[Dict[str, Any]] = []     # 本地没有记录的挂单
+        self.restored: Dict[str, Position] = {}           # 本地有记录的持仓（已恢复止盈止损）
+        self.orphan_positions: Dict[str, Position] = {}   # 本地没有记录的持仓
+        self.vanished: List[str] = []                     # 本地有记录但交易所已不存在的持仓索引键
+
+
+def plan_reconciliation(exchange_orders: List[Dict[str, Any]], exchange_positions: Dict[str, Position],
+                        state: Dict[str, Dict[str, Any]]) -> ReconcilePlan:
+    """
+    匹配交易所状态与本地状态
+
+    持仓按索引键和方向匹配，数量和开仓均价以交易所为准，止盈止损和开仓时间取本地记录。
+
+    Args:
+        exchange_orders: 交易所未成交订单（已筛选为本策略交易对）
+        exchange_positions: 交易所持仓（持仓索引键 -> 持仓，已筛选为本策略交易对）
+        state: 本地状态（StateStore.load 的返回值）
+
+    Returns:
+        ReconcilePlan: 匹配结果
+    """
+    plan = ReconcilePlan()
+    local_orders = state.get("orders", {})
+    for order in exchange_orders:
+        if str(order.get("id", "")) in local_orders:
+            plan.known_orders.append(order)
+        else:
+            plan.orphan_orders.append(order)
+
+    local_positions = state.get("positions", {})
+    for key, position in exchange_positions.items():
+        local = local_positions.get(key)
+        if local and local.get("direction") == position.direction.value:
+            plan.restored[key] = position.model_copy(update={
+                "stop_loss": float(local.get("stop_loss") or 0),
+                "take_profit": float(local.get("take_profit") or 0),
+                "opening_time": int(local.get("opening_time") or position.opening_time),
+            })
+        else:
+            plan.orphan_positions[key] = position
+    plan.vanished = [key for key in local_positions if key not in exchange_positions]
+    return plan
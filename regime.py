@@ -0,0 +1,275 @@
+"""
+波动率状态识别模块
+
+按固定间隔对价格采样，用波动率估计器（volatility模块）估计当前波动率，并以其在历史分布中的分位数
+把各交易对的市场状态划分为低/正常/高波动。策略可按状态切换参数（仓位、
+入场阈值、止盈止损宽度），或在指定状态下暂停开仓。
+
+参数配置文件（JSON）可定义多组命名的参数配置，每组指定适用的波动率状态和（可选）趋势条件：
+    {"trending": {"regimes": ["normal", "high"], "trending": true, "size_scale": 0.5, "threshold_scale": 2.0, "exit_scale": 1.5},
+     "calm": {"regimes": ["low"], "threshold_scale": 0.75, "exit_scale": 0.75}}
+趋势强度为最近 window 个采样价格的效率比（|净变动| / 逐个变动绝对值之和，0-1），不低于趋势阈值时视为趋势行情。
+按文件中的顺序使用第一个匹配的配置，没有匹配时使用该波动率状态的默认参数（配置名即状态名）。
+"""
+
+import json
+import math
+import time
+from collections import deque
+from enum import Enum
+from typing import Callable, Dict, Iterable, List, Optional, Any
+from loguru import logger
+
+from volatility import VolatilityEstimator, estimator_factory
+
+
+class VolatilityRegime(str, Enum):
+    """波动率状态"""
+    LOW = "low"
+    NORMAL = "normal"
+    HIGH = "high"
+
+
+class RegimeParams:
+    """状态对应的策略参数倍数"""
+
+    def __init__(self, size_scale: float = 1.0, threshold_scale: float = 1.0, exit_scale: float = 1.0):
+        """
+        Args:
+            size_scale: 仓位倍数
+            threshold_scale: 入场偏离阈值倍数
+            exit_scale: 止盈止损宽度倍数
+        """
+        self.size_scale = size_scale
+        self.threshold_scale = threshold_scale
+        self.exit_scale = exit_scale
+
+
+class RegimeProfile:
+    """命名的参数配置"""
+
+    def __init__(self, name: str, params: RegimeParams, regimes: Iterable[str], trending: Optional[bool] = None):
+        """
+        Args:
+            name: 配置名称
+            params: 参数倍数
+            regimes: 适用的波动率状态
+            trending: 是否只在趋势行情（True）或非趋势行情（False）使用，None为不区分
+        """
+        self.name = name
+        self.params = params
+        self.regimes = {VolatilityRegime(r) for r in regimes}
+        self.trending = trending
+
+    def matches(self, regime: VolatilityRegime, trending: bool) -> bool:
+        return regime in self.regimes and (self.trending is None or self.trending == trending)
+
+
+def load_profiles(path: str) -> List[RegimeProfile]:
+    """
+    读取参数配置文件
+
+    Raises:
+        ValueError: 文件格式错误、状态未知或倍数不大于0
+    """
+    try:
+        with open(path, encoding="utf-8") as fp:
+            data = json.load(fp)
+    except (OSError, json.JSONDecodeError) as e:
+        raise ValueError(f"无法读取参数配置文件 {path}: {e}")
+    if not isinstance(data, dict) or not data:
+        raise ValueError("参数配置文件应为非空的 {配置名: 参数} 对象")
+
+    profiles = []
+    for name, item in data.items():
+        if not isinstance(item, dict):
+            raise ValueError(f"参数配置 {name} 应为对象")
+        try:
+            regimes = [str(r).lower() for r in item.get("regimes", [r.value for r in VolatilityRegime])]
+            params = RegimeParams(*(float(item.get(key, 1.0)) for key in ("size_scale", "threshold_scale", "exit_scale")))
+            profile = RegimeProfile(name, params, regimes, item.get("trending"))
+        except (TypeError, ValueError) as e:
+            raise ValueError(f"参数配置 {name} 无效: {e}")
+        if min(params.size_scale, params.threshold_scale, params.exit_scale) <= 0:
+            raise ValueError(f"参数配置 {name} 的倍数必须大于0")
+        profiles.append(profile)
+    return profiles
+
+
+# 默认参数：低波动收窄阈值和止盈止损，高波动减半仓位并放宽
+DEFAULT_REGIME_PARAMS: Dict[VolatilityRegime, RegimeParams] = {
+    VolatilityRegime.LOW: RegimeParams(size_scale=1.0, threshold_scale=0.75, exit_scale=0.75),
+    VolatilityRegime.NORMAL: RegimeParams(),
+    VolatilityRegime.HIGH: RegimeParams(size_scale=0.5, threshold_scale=1.5, exit_scale=1.5),
+}
+
+
+class _SymbolState:
+    """单个交易对的采样状态"""
+
+    def __init__(self, estimator: VolatilityEstimator, history: int, window: int):
+        self.last_price: Optional[float] = None
+        self.last_sample_time: Optional[float] = None
+        self.estimator = estimator
+        self.vols: deque = deque(maxlen=history)
+        self.regime = VolatilityRegime.NORMAL
+        self.percentile = 0.5
+        self.prices: deque = deque(maxlen=window + 1)
+        self.trend = 0.0
+        self.profile: Optional[RegimeProfile] = None
+
+
+class RegimeClassifier:
+    """基于波动率分位数的状态分类器"""
+
+    def __init__(self, sample_seconds: int = 60, window: int = 30, history: int = 500,
+                 low_percentile: float = 0.2, high_percentile: float = 0.8, min_history: int = 50,
+                 hysteresis: float = 0.1, stand_down: Iterable[str] = (),
+                 estimator: Optional[Callable[[], VolatilityEstimator]] = None,
+                 profiles: Optional[List[RegimeProfile]] = None, trend_threshold: float = 0.6):
+        """
+        初始化分类器
+
+        Args:
+            sample_seconds: 价格采样间隔（秒）
+            window: 默认估计器的窗口/最少样本数
+            history: 用于计算分位数的历史波动率个数
+            low_percentile: 低于该分位数为低波动
+            high_percentile: 高于该分位数为高波动
+            min_history: 开始分类前所需的最少历史波动率个数（之前视为正常）
+            hysteresis: 退出低/高波动状态需额外越过的分位数（避免在阈值附近反复切换）
+            stand_down: 暂停开仓的状态列表（如 ["high"]）
+            estimator: 波动率估计器构造函数（默认为滚动窗口标准差）
+            profiles: 命名的参数配置（按顺序匹配）
+            trend_threshold: 趋势行情的效率比阈值
+        """
+        self.sample_seconds = sample_seconds
+        self.window = window
+        self.estimator = estimator or estimator_factory("simple", window=window)
+        self.history = history
+        self.low_percentile = low_percentile
+        self.high_percentile = high_percentile
+        self.min_history = min_history
+        self.hysteresis = hysteresis
+        self.stand_down = {VolatilityRegime(s) for s in stand_down}
+        self.params = dict(DEFAULT_REGIME_PARAMS)
+        self.profiles = profiles or []
+        self.trend_threshold = trend_threshold
+        self.states: Dict[str, _SymbolState] = {}
+
+        # 统计：各状态下被跳过的开仓信号、参数配置切换次数
+        self.skipped_entries: Dict[str, int] = {}
+        self.profile_switches = 0
+
+    def update(self, symbol: str, price: float, now: Optional[float] = None) -> Optional[VolatilityRegime]:
+        """
+        更新价格（未到采样间隔时忽略）
+
+        Args:
+            symbol: 交易对
+            price: 最新价格
+            now: 当前时间（秒）
+
+        Returns:
+            Optional[VolatilityRegime]: 状态发生变化时返回新状态，否则None
+        """
+        if price <= 0:
+            return None
+
+        now = time.time() if now is None else now
+        state = self.states.get(symbol)
+        if state is None:
+            state = self.states[symbol] = _SymbolState(self.estimator(), self.history, self.window)
+        if state.last_sample_time is not None and now - state.last_sample_time < self.sample_seconds:
+            return None
+
+        prev = state.last_price
+        state.last_price = price
+        state.last_sample_time = now
+        state.prices.append(price)
+        if not prev:
+            return None
+
+        state.estimator.update(math.log(price / prev))
+        if not state.estimator.is_ready:
+            return None
+
+        vol = state.estimator.volatility()
+        state.vols.append(vol)
+        if len(state.vols) < self.min_history:
+            return None
+
+        state.percentile = sum(1 for v in state.vols if v <= vol) / len(state.vols)
+        low = self.low_percentile + (self.hysteresis if state.regime == VolatilityRegime.LOW else 0.0)
+        high = self.high_percentile - (self.hysteresis if state.regime == VolatilityRegime.HIGH else 0.0)
+        if state.percentile <= low:
+            regime = VolatilityRegime.LOW
+        elif state.percentile >= high:
+            regime = VolatilityRegime.HIGH
+        else:
+            regime = VolatilityRegime.NORMAL
+
+        previous = self.profile(symbol)
+        changed = regime != state.regime
+        if changed:
+            logger.info(
+                f"[波动率状态] {symbol} {state.regime.value} -> {regime.value} "
+                f"(分位数 {state.percentile * 100:.1f}%)"
+            )
+            state.regime = regime
+        if self.profiles:
+            self._update_profile(symbol, state, previous)
+        return regime if changed else None
+
+    def _update_profile(self, symbol: str, state: _SymbolState, previous: str):
+        """按当前状态和趋势强度选择参数配置"""
+        prices = list(state.prices)
+        path = sum(abs(b - a) for a, b in zip(prices, prices[1:]))
+        state.trend = abs(prices[-1] - prices[0]) / path if path > 0 else 0.0
+        trending = state.trend >= self.trend_threshold
+        profile = next((p for p in self.profiles if p.matches(state.regime, trending)), None)
+        state.profile = profile
+        current = self.profile(symbol)
+        if current != previous:
+            self.profile_switches += 1
+            logger.info(f"[参数配置] {symbol} {previous} -> {current} "
+                        f"(波动率状态 {state.regime.value}，趋势强度 {state.trend:.2f})")
+
+    def regime(self, symbol: str) -> VolatilityRegime:
+        """当前状态"""
+        state = self.states.get(symbol)
+        return state.regime if state else VolatilityRegime.NORMAL
+
+    def profile(self, symbol: str) -> str:
+        """当前参数配置名称（没有匹配的配置时为状态名）"""
+        state = self.states.get(symbol)
+        return state.profile.name if state and state.profile else self.regime(symbol).value
+
+    def params_for(self, symbol: str) -> RegimeParams:
+        """当前参数配置（没有匹配的配置时为状态的默认参数）"""
+        state = self.states.get(symbol)
+        if state and state.profile:
+            return state.profile.params
+        return self.params[self.regime(symbol)]
+
+    def should_stand_down(self, symbol: str) -> bool:
+        """当前状态是否暂停开仓（并记录跳过次数）"""
+        regime = self.regime(symbol)
+        if regime not in self.stand_down:
+            return False
+        self.skipped_entries[regime.value] = self.skipped_entries.get(regime.value, 0) + 1
+        return True
+
+    def get_summary(self) -> Dict[str, Any]:
+        """获取各交易对状态摘要"""
+        return {
+            "symbols": {
+                symbol: {"regime": state.regime.value, "percentile": state.percentile,
+                         "profile": self.profile(symbol), "trend": state.trend}
+                for symbol, state in self.states.items()
+            },
+            "profiles": [p.name for p in self.profiles],
+            "profile_switches": self.profile_switches,
+            "stand_down": sorted(r.value for r in self.stand_down),
+            "skipped_entries": dict(self.skipped_entries),
+        }
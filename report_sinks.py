@@ -0,0 +1,132 @@
+"""
+报告输出模块
+
+性能监控生成的各类报告按配置输出到一个或多个目标：
+- 报告类型: performance（定期性能报告）、daily（交易日结束时的当日汇总）
+- 输出目标:
+  - stdout: 打印到标准输出
+  - file: 追加写入报告文件（每份报告前带时间和策略名称）
+  - metrics: 报告中的数值在指标接口（/metrics，见 metrics_server.py）中以 edgex_report_value 输出
+  - notifier: 以JSON（{"strategy", "report", "text", "values"}）POST到通知Webhook地址
+  - discord: 以卡片（正文为报告文本，数值字段逐项列出）发送到Discord Webhook地址（见 discord_notifier.py）
+- 配置格式: "performance:stdout+file,daily:notifier+discord"，未列出的报告类型不输出
+"""
+
+import asyncio
+import os
+from datetime import datetime
+from typing import Dict, List, Optional
+
+import aiohttp
+from loguru import logger
+
+from discord_notifier import DiscordNotifier
+
+REPORT_PERFORMANCE = "performance"
+REPORT_DAILY = "daily"
+REPORT_TYPES = (REPORT_PERFORMANCE, REPORT_DAILY)
+SINK_TYPES = ("stdout", "file", "metrics", "notifier", "discord")
+
+_NOTIFIER_TIMEOUT = 10.0
+
+
+def parse_report_sinks(text: str) -> Dict[str, List[str]]:
+    """
+    解析报告输出配置
+
+    Args:
+        text: "报告类型:目标+目标,..."（如 "performance:stdout+file,daily:notifier"）
+
+    Returns:
+        Dict[str, List[str]]: 报告类型 -> 输出目标列表
+    """
+    routes: Dict[str, List[str]] = {}
+    for item in text.split(","):
+        if not item.strip():
+            continue
+        report_type, _, sinks = item.partition(":")
+        routes[report_type.strip().lower()] = [s.strip().lower() for s in sinks.split("+") if s.strip()]
+    return routes
+
+
+class ReportMetrics:
+    """最近一次报告的数值（供指标接口输出）"""
+
+    def __init__(self):
+        self.values: Dict[tuple, float] = {}  # (策略, 报告类型, 字段) -> 数值
+
+    def update(self, strategy: str, report_type: str, values: Dict[str, float]):
+        for key, value in values.items():
+            self.values[(strategy, report_type, key)] = float(value)
+
+
+class ReportDispatcher:
+    """按报告类型把报告发送到配置的输出目标"""
+
+    def __init__(self, routes: Dict[str, List[str]], file_path: str = "", notifier_url: str = "",
+                 metrics: Optional[ReportMetrics] = None, discord: Optional[DiscordNotifier] = None):
+        """
+        Args:
+            routes: 报告类型 -> 输出目标列表
+            file_path: file 目标的报告文件路径
+            notifier_url: notifier 目标的Webhook地址
+            metrics: metrics 目标写入的数值存储（未提供时忽略 metrics 目标）
+            discord: discord 目标的发送（未提供时忽略 discord 目标）
+        """
+        self.routes = routes
+        self.file_path = file_path
+        self.notifier_url = notifier_url
+        self.metrics = metrics
+        self.discord = discord
+        self.errors = 0
+
+    @classmethod
+    def from_config(cls, config, metrics: Optional[ReportMetrics] = None) -> "ReportDispatcher":
+        discord = DiscordNotifier(config.discord_webhook_url) if config.discord_webhook_url else None
+        return cls(config.report_sinks, config.report_file_path, config.notifier_webhook_url, metrics, discord)
+
+    def enabled(self, report_type: str) -> bool:
+        """该报告类型是否有输出目标"""
+        return bool(self.routes.get(report_type))
+
+    async def dispatch(self, report_type: str, strategy: str, text: str, values: Dict[str, float]):
+        """
+        输出一份报告（单个目标失败只记录日志，不影响其他目标）
+
+        Args:
+            report_type: 报告类型
+            strategy: 策略名称
+            text: 报告文本
+            values: 报告中的数值字段
+        """
+        for sink in self.routes.get(report_type, []):
+            try:
+                if sink == "stdout":
+                    print(text)
+                elif sink == "file":
+                    await asyncio.get_running_loop().run_in_executor(None, self._append_file, strategy, text)
+                elif sink == "metrics":
+                    if self.metrics:
+                        self.metrics.update(strategy, report_type, values)
+                elif sink == "notifier":
+                    await self._notify(report_type, strategy, text, values)
+                elif sink == "discord":
+                    if self.discord:
+                        await self.discord.report(report_type, strategy, text, values)
+            except Exception as e:
+                self.errors += 1
+                logger.error(f"[报告] {report_type} 报告输出到 {sink} 失败: {e}")
+
+    def _append_file(self, strategy: str, text: str):
+        directory = os.path.dirname(self.file_path)
+        if directory:
+            os.makedirs(directory, exist_ok=True)
+        with open(self.file_path, "a", encoding="utf-8") as fp:
+            fp.write(f"# {datetime.now().isoformat(timespec='seconds')} {strategy}\n{text}\n")
+
+    async def _notify(self, report_type: str, strategy: str, text: str, values: Dict[str, float]):
+        payload = {"strategy": strategy, "report": report_type, "text": text, "values": values}
+        async with aiohttp.ClientSession(timeout=aiohttp.ClientTimeout(total=_NOTIFIER_TIMEOUT)) as session:
+            async with session.post(self.notifier_url, json=payload) as response:
+                if response.status >= 400:
+                    raise ValueError(f"HTTP {response.status}")
@@ -0,0 +1,210 @@
+"""
+组合风险模型
+
+按固定间隔对所有交易对的最新价格采样，用可配置的波动率估计器（volatility模块）
+估计各交易对的波动率，用EWMA估计两两相关性，并据此：
+- 计算当前持仓组合的年化波动率
+- 缩放新开仓位，使组合波动率跟踪目标波动率（target_volatility）
+- 输出风险分解（各交易对对组合方差的贡献占比）
+"""
+
+import math
+import time
+from typing import Callable, Dict, List, Optional, Any
+
+from volatility import VolatilityEstimator, estimator_factory
+
+
+class PortfolioRiskModel:
+    """组合风险模型"""
+
+    SECONDS_PER_YEAR = 365 * 24 * 3600
+
+    def __init__(self, symbols: List[str], target_volatility: float, ewma_lambda: float = 0.94,
+                 sample_seconds: int = 60, min_samples: int = 30, max_scale: float = 1.0,
+                 estimator: Optional[Callable[[], VolatilityEstimator]] = None):
+        """
+        初始化风险模型
+
+        Args:
+            symbols: 交易对列表
+            target_volatility: 组合目标年化波动率（如0.6表示60%）
+            ewma_lambda: 相关性EWMA衰减系数（RiskMetrics日频为0.94）
+            sample_seconds: 价格采样间隔（秒）
+            min_samples: 开始缩放仓位前所需的最少收益率样本数
+            max_scale: 仓位缩放倍数上限
+            estimator: 单交易对波动率估计器构造函数（默认EWMA）
+        """
+        self.symbols = list(symbols)
+        self.target_volatility = target_volatility
+        self.ewma_lambda = ewma_lambda
+        self.sample_seconds = sample_seconds
+        self.min_samples = min_samples
+        self.max_scale = max_scale
+
+        self.periods_per_year = self.SECONDS_PER_YEAR / sample_seconds
+        self.last_prices: Dict[str, float] = {}
+        self.last_sample_time: Optional[float] = None
+        self.samples = 0
+
+        # 各交易对波动率估计器
+        self.estimator = estimator or estimator_factory("ewma", ewma_lambda=ewma_lambda, window=min_samples)
+        self.estimators: Dict[str, VolatilityEstimator] = {}
+
+        # EWMA协方差（每期收益率，仅用于相关性），键为排序后的交易对二元组
+        self.ewma_cov: Dict[tuple, float] = {}
+
+    def _key(self, a: str, b: str) -> tuple:
+        return (a, b) if a <= b else (b, a)
+
+    def sample(self, prices: Dict[str, float], now: Optional[float] = None) -> bool:
+        """
+        采样价格并更新协方差估计（未到采样间隔时忽略）
+
+        Args:
+            prices: 交易对 -> 最新价格
+            now: 当前时间（秒），默认为系统时间
+
+        Returns:
+            bool: 是否完成了一次更新
+        """
+        now = time.time() if now is None else now
+        if self.last_sample_time is not None and now - self.last_sample_time < self.sample_seconds:
+            return False
+
+        returns: Dict[str, float] = {}
+        for symbol, price in prices.items():
+            prev = self.last_prices.get(symbol)
+            if prev and prev > 0 and price and price > 0:
+                returns[symbol] = math.log(price / prev)
+
+        self.last_prices = {s: p for s, p in prices.items() if p and p > 0}
+        self.last_sample_time = now
+        if not returns:
+            return False
+
+        for symbol, ret in returns.items():
+            self.estimators.setdefault(symbol, self.estimator()).update(ret)
+
+        lam = self.ewma_lambda
+        symbols = sorted(returns)
+        for i, a in enumerate(symbols):
+            for b in symbols[i:]:
+                key = self._key(a, b)
+                product = returns[a] * returns[b]
+                # 首个样本直接作为初始估计
+                self.ewma_cov[key] = lam * self.ewma_cov[key] + (1 - lam) * product if key in self.ewma_cov else product
+
+        self.samples += 1
+        return True
+
+    @property
+    def is_ready(self) -> bool:
+        """样本是否足够"""
+        return self.samples >= self.min_samples
+
+    def _var(self, symbol: str) -> float:
+        """交易对每期方差（来自波动率估计器）"""
+        estimator = self.estimators.get(symbol)
+        return estimator.variance if estimator else 0.0
+
+    def _cov(self, a: str, b: str) -> float:
+        """每期协方差：估计器方差 + EWMA相关性"""
+        if a == b:
+            return self._var(a)
+        return self.correlation(a, b) * math.sqrt(self._var(a) * self._var(b))
+
+    def volatility(self, symbol: str) -> float:
+        """交易对年化波动率"""
+        estimator = self.estimators.get(symbol)
+        return estimator.volatility(self.periods_per_year) if estimator else 0.0
+
+    def correlation(self, a: str, b: str) -> float:
+        """两个交易对的相关系数"""
+        if a == b:
+            return 1.0
+        var_a = self.ewma_cov.get(self._key(a, a), 0.0)
+        var_b = self.ewma_cov.get(self._key(b, b), 0.0)
+        if var_a <= 0 or var_b <= 0:
+            return 0.0
+        return self.ewma_cov.get(self._key(a, b), 0.0) / math.sqrt(var_a * var_b)
+
+    def _variance(self, weights: Dict[str, float]) -> float:
+        """组合每期方差 w'Σw"""
+        total = 0.0
+        for a, wa in weights.items():
+            for b, wb in weights.items():
+                total += wa * wb * self._cov(a, b)
+        return max(total, 0.0)
+
+    def portfolio_volatility(self, weights: Dict[str, float]) -> float:
+        """
+        组合年化波动率
+
+        Args:
+            weights: 交易对 -> 带方向的名义敞口/权益（多头为正，空头为负）
+        """
+        return math.sqrt(self._variance(weights) * self.periods_per_year)
+
+    def entry_scale(self, symbol: str, weight: float, current_weights: Dict[str, float]) -> float:
+        """
+        计算新开仓位的缩放倍数，使加入后的组合波动率不超过目标
+
+        在 [0, max_scale] 内求解 k，使 vol(current + k * weight) = target。
+
+        Args:
+            symbol: 交易对
+            weight: 计划仓位的带方向权重
+            current_weights: 现有持仓权重
+
+        Returns:
+            float: 缩放倍数（样本不足时为1）
+        """
+        if not self.is_ready or weight == 0:
+            return 1.0
+
+        target_var = (self.target_volatility ** 2) / self.periods_per_year
+        base = dict(current_weights)
+        base_var = self._variance(base)
+        if base_var >= target_var:
+            # 现有组合已超过目标，只允许降低风险的仓位
+            reduced = self._variance({**base, symbol: base.get(symbol, 0.0) + weight}) < base_var
+            return 1.0 if reduced else 0.0
+
+        # vol²(k) = a·k² + b·k + c，解 vol²(k) = target
+        own_var = self._var(symbol)
+        a = weight * weight * own_var
+        b = 2 * weight * sum(w * self._cov(symbol, s) for s, w in base.items())
+        c = base_var - target_var
+        if a <= 0:
+            return self.max_scale
+
+        disc = b * b - 4 * a * c
+        k = (-b + math.sqrt(max(disc, 0.0))) / (2 * a)
+        return min(max(k, 0.0), self.max_scale)
+
+    def risk_decomposition(self, weights: Dict[str, float]) -> Dict[str, Any]:
+        """
+        风险分解
+
+        Returns:
+            Dict[str, Any]: 组合波动率、目标波动率及各交易对的波动率、权重、方差贡献占比
+        """
+        total_var = self._variance(weights)
+        components = {}
+        for symbol, w in weights.items():
+            marginal = sum(wb * self._cov(symbol, b) for b, wb in weights.items())
+            components[symbol] = {
+                "weight": w,
+                "volatility": self.volatility(symbol),
+                "contribution": w * marginal / total_var if total_var > 0 else 0.0,
+            }
+
+        portfolio_vol = math.sqrt(total_var * self.periods_per_year)
+        return {
+            "portfolio_volatility": portfolio_vol,
+            "target_volatility": self.target_volatility,
+            "volatility_ratio": portfolio_vol / self.target_volatility if self.target_volatility > 0 else 0.0,
+            "samples": self.samples,
+            "components": components,
+        }
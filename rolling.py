@@ -0,0 +1,105 @@
+"""
+滚动窗口统计模块
+
+逐笔（逐根K线）更新的固定窗口统计，每次更新 O(1)，不再对整段历史切片重新求和：
+- 均值、样本方差/标准差、z-score
+- 维护窗口内 (x - 基准值) 的累计和与平方和，基准值取窗口内的早期样本，
+  避免价格量级较大而波动很小时平方和相减的精度损失
+- 每滚动一整个窗口重新精确求和一次（均摊 O(1)），消除浮点累计误差
+"""
+
+import math
+from collections import deque
+from typing import Deque, Optional
+
+
+class RollingWindow:
+    """固定长度滚动窗口统计"""
+
+    def __init__(self, size: int):
+        """
+        Args:
+            size: 窗口长度
+        """
+        if size <= 0:
+            raise ValueError(f"窗口长度必须大于0: {size}")
+        self.size = size
+        self.values: Deque[float] = deque()
+        self._shift = 0.0
+        self._sum = 0.0
+        self._sum_sq = 0.0
+        self._evicted = 0
+
+    def update(self, value: float):
+        """加入一个样本（窗口已满时移出最早的样本）"""
+        if not self.values:
+            self._shift = value
+        if len(self.values) == self.size:
+            old = self.values.popleft() - self._shift
+            self._sum -= old
+            self._sum_sq -= old * old
+            self._evicted += 1
+        self.values.append(value)
+        d = value - self._shift
+        self._sum += d
+        self._sum_sq += d * d
+        if self._evicted >= self.size:
+            self._resum()
+
+    def _resum(self):
+        """以窗口首个样本为基准重新精确求和"""
+        self._shift = self.values[0]
+        self._sum = math.fsum(v - self._shift for v in self.values)
+        self._sum_sq = math.fsum((v - self._shift) ** 2 for v in self.values)
+        self._evicted = 0
+
+    def clear(self):
+        self.values.clear()
+        self._sum = self._sum_sq = 0.0
+        self._evicted = 0
+
+    @property
+    def count(self) -> int:
+        return len(self.values)
+
+    @property
+    def is_full(self) -> bool:
+        return len(self.values) == self.size
+
+    @property
+    def last(self) -> Optional[float]:
+        return self.values[-1] if self.values else None
+
+    @property
+    def mean(self) -> float:
+        n = len(self.values)
+        return self._shift + self._sum / n if n else 0.0
+
+    @property
+    def variance(self) -> float:
+        """样本方差（n-1），样本不足2个时为0"""
+        n = len(self.values)
+        if n < 2:
+            return 0.0
+        return max((self._sum_sq - self._sum * self._sum / n) / (n - 1), 0.0)
+
+    @property
+    def std(self) -> float:
+        return math.sqrt(self.variance)
+
+    def zscore(self, value: Optional[float] = None) -> float:
+        """
+        z-score
+
+        Args:
+            value: 待评估的值，默认为最新样本
+
+        Returns:
+            float: (value - 均值) / 标准差，标准差为0时为0
+        """
+        if value is None:
+            value = self.last
+        std = self.std
+        if value is None or std == 0:
+            return 0.0
+        return (value - self.mean) / std
@@ -0,0 +1,99 @@
+"""
+滚动绩效窗口模块
+
+按多个滚动时间窗口（1小时、24小时、7天、30天）及启动以来统计绩效，便于及早发现策略衰减：
+- 交易数、胜率、盈亏: 按平仓时间落在窗口内的交易记录统计（基于内存中保留的最近交易记录，
+  缓冲已满且最早的记录晚于窗口起点时标记为不完整）；启动以来使用累计计数
+- 夏普比率: 按固定间隔采样权益（余额+未实现盈亏），以窗口内各采样期收益率的均值/标准差按采样间隔年化（无风险利率为0）；
+  启动以来的夏普比率按全部采样期收益率的累计均值和方差计算，不受保留的采样个数限制
+"""
+
+import math
+import time
+from collections import deque
+from typing import Any, Deque, Dict, List, Optional, Tuple
+
+from trade_store import TradeRecordBuffer
+
+SECONDS_PER_YEAR = 365 * 24 * 3600
+
+# (名称, 窗口长度秒)
+WINDOWS: List[Tuple[str, int]] = [("1h", 3600), ("24h", 86400), ("7d", 7 * 86400), ("30d", 30 * 86400)]
+INCEPTION = "inception"
+
+
+def _sharpe(count: int, mean: float, m2: float, periods_per_year: float) -> Optional[float]:
+    """年化夏普比率（收益率个数不足2或标准差为0时为None）"""
+    if count < 2:
+        return None
+    std = math.sqrt(m2 / (count - 1))
+    return mean / std * math.sqrt(periods_per_year) if std > 0 else None
+
+
+class RollingPerformance:
+    """多窗口滚动绩效"""
+
+    def __init__(self, sample_seconds: int = 60):
+        """
+        Args:
+            sample_seconds: 权益采样间隔（秒）
+        """
+        self.sample_seconds = sample_seconds
+        self.periods_per_year = SECONDS_PER_YEAR / sample_seconds
+        self.equity: Deque[Tuple[float, float]] = deque(maxlen=WINDOWS[-1][1] // sample_seconds + 1)
+        # 启动以来的采样期收益率（Welford 累计均值和平方差）
+        self._count = 0
+        self._mean = 0.0
+        self._m2 = 0.0
+
+    def sample(self, equity: float, now: Optional[float] = None):
+        """采样权益（未到采样间隔时忽略）"""
+        now = time.time() if now is None else now
+        if equity <= 0 or (self.equity and now - self.equity[-1][0] < self.sample_seconds):
+            return
+        if self.equity:
+            ret = equity / self.equity[-1][1] - 1
+            self._count += 1
+            delta = ret - self._mean
+            self._mean += delta / self._count
+            self._m2 += delta * (ret - self._mean)
+        self.equity.append((now, equity))
+
+    def _window_sharpe(self, start: float) -> Optional[float]:
+        values = [equity for ts, equity in self.equity if ts >= start]
+        returns = [b / a - 1 for a, b in zip(values, values[1:])]
+        if len(returns) < 2:
+            return None
+        mean = sum(returns) / len(returns)
+        return _sharpe(len(returns), mean, sum((r - mean) ** 2 for r in returns), self.periods_per_year)
+
+    def summary(self, records: TradeRecordBuffer, now: Optional[float] = None) -> Dict[str, Dict[str, Any]]:
+        """
+        各窗口绩效
+
+        Returns:
+            Dict[str, Dict[str, Any]]: 窗口名称 -> {trades, win_rate, pnl, sharpe, partial}
+        """
+        now = time.time() if now is None else now
+        buffered = list(records)
+        full = records.records.maxlen is not None and len(buffered) >= records.records.maxlen
+        oldest = buffered[0].timestamp if buffered else now
+        result: Dict[str, Dict[str, Any]] = {}
+        for name, seconds in WINDOWS:
+            start = now - seconds
+            pnls = [r.pnl for r in buffered if r.timestamp >= start]
+            result[name] = {
+                "trades": len(pnls),
+                "win_rate": sum(1 for pnl in pnls if pnl > 0) / len(pnls) if pnls else 0.0,
+                "pnl": sum(pnls),
+                "sharpe": self._window_sharpe(start),
+                "partial": full and oldest > start,
+            }
+        result[INCEPTION] = {
+            "trades": records.total,
+            "win_rate": records.wins / records.total if records.total else 0.0,
+            "pnl": records.total_pnl,
+            "sharpe": _sharpe(self._count, self._mean, self._m2, self.periods_per_year),
+            "partial": False,
+        }
+        return result
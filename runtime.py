@@ -0,0 +1,109 @@
+"""
+运行时调优模块
+
+面向专用交易主机的低抖动设置（均为可选，默认保持Python默认行为）：
+- 事件循环实现：asyncio（默认）或 uvloop
+- 默认线程池的工作线程数（run_in_executor / asyncio.to_thread 使用）
+- CPU绑核（仅Linux）：事件循环线程（策略计算与下单执行）和行情线程（WebSocket读取、分发、发送）
+  分别绑定到指定的CPU，行情处理与下单执行互不抢占；建议配合内核参数 isolcpus 隔离这些CPU
+
+行情本身已在独立线程中接收和分发（见 sdk/edgex_sdk/ws/client.py），不占用事件循环。
+"""
+
+import asyncio
+import os
+import threading
+from concurrent.futures import ThreadPoolExecutor
+from typing import Optional, Set
+
+from loguru import logger
+
+EVENT_LOOPS = ("asyncio", "uvloop")
+
+
+def parse_cpu_list(spec: str) -> Set[int]:
+    """
+    解析CPU列表
+
+    Args:
+        spec: 逗号分隔的CPU编号或范围，例如 "2" 、"2,3" 、"4-7"，空字符串表示不绑核
+
+    Returns:
+        Set[int]: CPU编号集合
+
+    Raises:
+        ValueError: 格式错误
+    """
+    cpus: Set[int] = set()
+    for part in (p.strip() for p in spec.split(",")):
+        if not part:
+            continue
+        if "-" in part:
+            start, end = (int(x) for x in part.split("-", 1))
+            if start > end:
+                raise ValueError(f"CPU范围起点大于终点: {part}")
+            cpus.update(range(start, end + 1))
+        else:
+            cpus.add(int(part))
+    if any(cpu < 0 for cpu in cpus):
+        raise ValueError(f"CPU编号不能为负数: {spec}")
+    return cpus
+
+
+def pin_current_thread(cpus: Optional[Set[int]], label: str) -> bool:
+    """
+    把当前线程绑定到指定CPU（Linux上CPU亲和性按线程生效）
+
+    Args:
+        cpus: CPU编号集合，为空时不绑核
+        label: 线程用途（日志用）
+
+    Returns:
+        bool: 是否绑核成功
+    """
+    if not cpus:
+        return False
+    if not hasattr(os, "sched_setaffinity"):
+        logger.warning(f"[运行时] 当前系统不支持CPU绑核，{label}线程未绑核")
+        return False
+    try:
+        os.sched_setaffinity(0, cpus)
+    except OSError as e:
+        logger.warning(f"[运行时] {label}线程绑定CPU {sorted(cpus)} 失败: {e}")
+        return False
+    logger.info(f"[运行时] {label}线程 {threading.current_thread().name} 已绑定CPU {sorted(cpus)}")
+    return True
+
+
+def install_event_loop(kind: str):
+    """
+    选择事件循环实现（需在 asyncio.run 之前调用）
+
+    Args:
+        kind: asyncio 或 uvloop
+
+    Raises:
+        ImportError: 选择uvloop但未安装
+    """
+    if kind == "uvloop":
+        try:
+            import uvloop
+        except ImportError:
+            raise ImportError("EDGEX_EVENT_LOOP=uvloop 需要安装 uvloop: pip install uvloop")
+        asyncio.set_event_loop_policy(uvloop.EventLoopPolicy())
+        logger.info("[运行时] 使用uvloop事件循环")
+
+
+def configure_runtime(config):
+    """
+    在事件循环线程中应用运行时设置（默认线程池、事件循环线程绑核）
+
+    Args:
+        config: 配置对象
+    """
+    loop = asyncio.get_running_loop()
+    if config.executor_workers > 0:
+        loop.set_default_executor(ThreadPoolExecutor(max_workers=config.executor_workers,
+                                                     thread_name_prefix="edgex-worker"))
+        logger.info(f"[运行时] 默认线程池工作线程数: {config.executor_workers}")
+    pin_current_thread(parse_cpu_list(config.execution_cpus), "事件循环")
@@ -0,0 +1,195 @@
+"""
+脚本化信号模块
+
+信号逻辑定义在外部Python脚本中，文件修改后自动重新加载（无需重启机器人），
+便于快速迭代策略。脚本需定义函数：
+
+    def generate_signal(ctx):
+        if ctx.deviation(5) < -0.002 and ctx.spread_bps() < 5:
+            return {"direction": "long", "confidence": 0.8}
+        return None
+
+返回 None 或 {"direction": "hold"} 表示持有；direction 为 long/short 时可选提供
+confidence、stop_loss、take_profit（未提供止盈止损时按策略配置计算）。
+ctx 提供价格指标（sma/ema/deviation/volatility）、盘口特征（spread_bps/imbalance/depth）、
+基差（basis_bps，永续价格相对指数价格，未启用基差监控或无数据时为None）、
+当前持仓方向以及跨调用保留的 state 字典（重新加载脚本时清空）。
+
+加载或执行失败时记录错误：加载失败继续使用上一个版本，执行失败本轮视为持有。
+"""
+
+import math
+import os
+import traceback
+from typing import Any, Callable, Dict, List, Optional
+from loguru import logger
+
+from edgex_types import OrderBook, PriceData, TradeDirection
+
+
+class SignalContext:
+    """脚本可用的行情上下文"""
+
+    def __init__(self, symbol: str, klines: List[PriceData], book: Optional[OrderBook],
+                 position: Optional[TradeDirection], state: Dict[str, Any], basis_bps: Optional[float] = None):
+        self.symbol = symbol
+        self.klines = klines
+        self.closes = [k.close for k in klines]
+        self.price = self.closes[-1] if self.closes else 0.0
+        self.book = book
+        self.position = position.value.lower() if position else None
+        self.state = state
+        self.basis_bps = basis_bps
+
+    def sma(self, period: int) -> float:
+        """简单移动平均（数据不足时使用全部数据）"""
+        window = self.closes[-period:]
+        return sum(window) / len(window) if window else 0.0
+
+    def ema(self, period: int) -> float:
+        """指数移动平均"""
+        if not self.closes:
+            return 0.0
+        alpha = 2 / (period + 1)
+        value = self.closes[0]
+        for close in self.closes[1:]:
+            value = alpha * close + (1 - alpha) * value
+        return value
+
+    def deviation(self, period: int) -> float:
+        """当前价格相对SMA的偏离比例"""
+        ma = self.sma(period)
+        return (self.price - ma) / ma if ma else 0.0
+
+    def volatility(self, period: int = 20) -> float:
+        """最近period个对数收益率的标准差（每根K线）"""
+        closes = [c for c in self.closes[-(period + 1):] if c > 0]
+        returns = [math.log(b / a) for a, b in zip(closes, closes[1:])]
+        if len(returns) < 2:
+            return 0.0
+        mean = sum(returns) / len(returns)
+        return math.sqrt(sum((r - mean) ** 2 for r in returns) / (len(returns) - 1))
+
+    def best_bid(self) -> float:
+        return float(self.book.bids[0][0]) if self.book and self.book.bids else 0.0
+
+    def best_ask(self) -> float:
+        return float(self.book.asks[0][0]) if self.book and self.book.asks else 0.0
+
+    def mid(self) -> float:
+        """盘口中间价（无盘口时为最新价）"""
+        bid, ask = self.best_bid(), self.best_ask()
+        return (bid + ask) / 2 if bid and ask else self.price
+
+    def spread_bps(self) -> float:
+        """买卖价差（bps，无盘口时为0）"""
+        bid, ask = self.best_bid(), self.best_ask()
+        return (ask - bid) / ((ask + bid) / 2) * 10000 if bid and ask else 0.0
+
+    def depth(self, side: str, levels: int = 5) -> float:
+        """买盘（bid）或卖盘（ask）前levels档的挂单量"""
+        if not self.book:
+            return 0.0
+        book_side = self.book.bids if side == "bid" else self.book.asks
+        return sum(float(q) for _, q in book_side[:levels])
+
+    def imbalance(self, levels: int = 5) -> float:
+        """盘口不平衡度 (买量-卖量)/(买量+卖量)，范围[-1, 1]"""
+        bid, ask = self.depth("bid", levels), self.depth("ask", levels)
+        return (bid - ask) / (bid + ask) if bid + ask > 0 else 0.0
+
+
+class ScriptedSignal:
+    """热加载的脚本信号"""
+
+    def __init__(self, path: str):
+        """
+        Args:
+            path: 脚本路径
+        """
+        self.path = path
+        self._func: Optional[Callable[[SignalContext], Any]] = None
+        self._mtime: Optional[float] = None
+        self.states: Dict[str, Dict[str, Any]] = {}
+
+        # 统计
+        self.reloads = 0
+        self.errors = 0
+
+        self._reload_if_changed()
+        if self._func is None:
+            raise ValueError(f"无法加载信号脚本: {path}")
+
+    def _reload_if_changed(self):
+        """脚本文件有变化时重新加载"""
+        try:
+            mtime = os.path.getmtime(self.path)
+        except OSError as e:
+            if self._mtime is not None:
+                logger.error(f"[脚本信号] 无法读取脚本 {self.path}: {e}")
+                self._mtime = None
+            return
+
+        if mtime == self._mtime:
+            return
+        self._mtime = mtime
+
+        namespace: Dict[str, Any] = {"__name__": "signal_script", "__file__": self.path}
+        try:
+            with open(self.path, encoding="utf-8") as fp:
+                exec(compile(fp.read(), self.path, "exec"), namespace)
+            func = namespace.get("generate_signal")
+            if not callable(func):
+                raise ValueError("脚本未定义 generate_signal(ctx) 函数")
+        except Exception as e:
+            self.errors += 1
+            logger.error(f"[脚本信号] 加载 {self.path} 失败，继续使用上一版本: {e}")
+            return
+
+        self._func = func
+        self.states.clear()
+        self.reloads += 1
+        logger.info(f"[脚本信号] 已加载 {self.path}（第 {self.reloads} 次）")
+
+    def evaluate(self, symbol: str, klines: List[PriceData], book: Optional[OrderBook],
+                 position: Optional[TradeDirection], basis_bps: Optional[float] = None) -> Optional[Dict[str, Any]]:
+        """
+        执行脚本
+
+        Args:
+            symbol: 交易对
+            klines: 价格历史
+            book: 本地订单簿
+            position: 当前持仓方向
+            basis_bps: 最新基差（bps）
+
+        Returns:
+            Optional[Dict[str, Any]]: 包含 direction（TradeDirection）及可选 confidence/stop_loss/take_profit，
+            持有或执行失败时返回None
+        """
+        self._reload_if_changed()
+        ctx = SignalContext(symbol, klines, book, position, self.states.setdefault(symbol, {}), basis_bps)
+        try:
+            result = self._func(ctx)
+            if not result:
+                return None
+            if isinstance(result, str):
+                result = {"direction": result}
+            direction = TradeDirection(str(result.get("direction", "hold")).upper())
+        except Exception as e:
+            self.errors += 1
+            logger.error(f"[脚本信号] {symbol} 执行失败: {e}\n{traceback.format_exc(limit=3)}")
+            return None
+
+        if direction == TradeDirection.HOLD:
+            return None
+        return {
+            "direction": direction,
+            "confidence": float(result.get("confidence", 1.0)),
+            "stop_loss": float(result.get("stop_loss") or 0.0),
+            "take_profit": float(result.get("take_profit") or 0.0),
+        }
+
+    def get_stats(self) -> Dict[str, Any]:
+        """获取脚本统计"""
+        return {"path": self.path, "reloads": self.reloads, "errors": self.errors}
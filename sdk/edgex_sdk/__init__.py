@@ -3,6 +3,8 @@ EdgeX Python SDK - A Python SDK for interacting with the EdgeX Exchange API.
 """
 
 from .client import Client
+from .internal.async_client import TimestampError, TransportError
+from .internal.clock import RequestClock
 from .internal.signing_adapter import SigningAdapter
 from .internal.starkex_signing_adapter import StarkExSigningAdapter
 from .order.types import (
@@ -12,6 +14,7 @@ from .order.types import (
     CreateOrderParams,
     CancelOrderParams,
     GetActiveOrderParams,
+    GetHistoryOrderParams,
     OrderFillTransactionParams
 )
 from .account.client import (
@@ -49,6 +52,7 @@ __all__ = [
     "CreateOrderParams",
     "CancelOrderParams",
     "GetActiveOrderParams",
+    "GetHistoryOrderParams",
     "OrderFillTransactionParams",
     "GetPositionTransactionPageParams",
     "GetCollateralTransactionPageParams",
@@ -68,5 +72,8 @@ __all__ = [
     "GetWithdrawalRecordsParams",
     "WebSocketManager",
     "SigningAdapter",
-    "StarkExSigningAdapter"
+    "StarkExSigningAdapter",
+    "TransportError",
+    "TimestampError",
+    "RequestClock"
 ]
\ No newline at end of file
@@ -4,6 +4,7 @@ from typing import Dict, Any, Optional, List, Union
 from decimal import Decimal
 
 from .internal.async_client import AsyncClient
+from .internal.clock import RequestClock
 from .internal.signing_adapter import SigningAdapter
 from .internal.starkex_signing_adapter import StarkExSigningAdapter
 from .account.client import Client as AccountClient
@@ -13,14 +14,20 @@ from .metadata.client import Client as MetadataClient
 from .order.client import Client as OrderClient
 from .quote.client import Client as QuoteClient
 from .transfer.client import Client as TransferClient
-from .order.types import CreateOrderParams, CancelOrderParams, GetActiveOrderParams, OrderFillTransactionParams
+from .order.types import (
+    CreateOrderParams, CancelOrderParams, GetActiveOrderParams, GetHistoryOrderParams, OrderFillTransactionParams
+)
 
 
 class Client:
     """Main EdgeX SDK client."""
 
     def __init__(self, base_url: str, account_id: int, stark_private_key: str,
-                 signing_adapter: Optional[SigningAdapter] = None, timeout: float = 30.0):
+                 signing_adapter: Optional[SigningAdapter] = None, timeout: float = 30.0,
+                 connector_limit: int = 100, limit_per_host: int = 30,
+                 keepalive_timeout: float = 30.0, connect_timeout: Optional[float] = None,
+                 proxy: Optional[str] = None, order_connector_limit: int = 10,
+                 clock: Optional[RequestClock] = None, timestamp_retries: int = 1):
         """
         Initialize the EdgeX SDK client.
 
@@ -30,6 +37,14 @@ class Client:
             stark_private_key: Stark private key for signing
             signing_adapter: Optional signing adapter (defaults to StarkExSigningAdapter)
             timeout: Request timeout in seconds
+            connector_limit: Maximum number of connections in the pool
+            limit_per_host: Maximum number of pooled connections to the API host
+            keepalive_timeout: Seconds an idle pooled connection is kept open for reuse
+            connect_timeout: Timeout for acquiring a connection, None for no separate limit
+            proxy: Outbound proxy URL (http://, socks5:// or socks5h://), None for direct connections
+            order_connector_limit: Connections reserved for order submission and cancel requests
+            clock: Timestamp source for signed requests (a new RequestClock if not provided)
+            timestamp_retries: Times a request rejected for its timestamp is re-signed and retried
         """
         # Use StarkExSigningAdapter as default if none provided
         if signing_adapter is None:
@@ -41,7 +56,15 @@ class Client:
             account_id=account_id,
             stark_pri_key=stark_private_key,
             signing_adapter=signing_adapter,
-            timeout=timeout
+            timeout=timeout,
+            connector_limit=connector_limit,
+            limit_per_host=limit_per_host,
+            keepalive_timeout=keepalive_timeout,
+            connect_timeout=connect_timeout,
+            proxy=proxy,
+            order_connector_limit=order_connector_limit,
+            clock=clock,
+            timestamp_retries=timestamp_retries
         )
 
         # Initialize API clients
@@ -89,8 +112,8 @@ class Client:
         Returns:
             Dict[str, Any]: The created order
         """
-        # Get metadata first
-        metadata = await self.get_metadata()
+        # Get metadata first (over the order connection pool, like the order itself)
+        metadata = await self.metadata.get_metadata(priority=True)
         if not metadata:
             raise ValueError("failed to get metadata")
 
@@ -133,6 +156,18 @@ class Client:
         """
         return await self.order.get_active_orders(params)
 
+    async def get_history_orders(self, params: GetHistoryOrderParams) -> Dict[str, Any]:
+        """
+        Get historical (filled, canceled) orders with pagination and filters.
+
+        Args:
+            params: Historical order query parameters
+
+        Returns:
+            Dict[str, Any]: The historical orders
+        """
+        return await self.order.get_history_orders(params)
+
     async def get_order_fill_transactions(self, params: OrderFillTransactionParams) -> Dict[str, Any]:
         """
         Get order fill transactions with pagination and filters.
@@ -145,6 +180,18 @@ class Client:
         """
         return await self.order.get_order_fill_transactions(params)
 
+    async def get_orders_by_id(self, order_ids: List[str]) -> Dict[str, Any]:
+        """
+        Get orders (active or historical) by order ID.
+
+        Args:
+            order_ids: Order IDs to look up
+
+        Returns:
+            Dict[str, Any]: The orders found
+        """
+        return await self.order.get_orders_by_id(order_ids)
+
     async def get_account_asset(self) -> Dict[str, Any]:
         """Get the account asset information."""
         return await self.account.get_account_asset()
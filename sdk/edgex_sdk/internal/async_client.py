@@ -8,7 +8,9 @@ import json
 
 import aiohttp
 from Crypto.Hash import keccak
+from urllib.parse import urlsplit, urlunsplit
 
+from .clock import RequestClock, is_timestamp_error
 from .signing_adapter import SigningAdapter
 
 # Import field prime for modular arithmetic
@@ -22,6 +24,14 @@ except ImportError:
 LIMIT_ORDER_WITH_FEE_TYPE = 3
 
 
+class TransportError(ValueError):
+    """The request did not reach the API or got no response (connection, proxy or timeout failure)."""
+
+
+class TimestampError(ValueError):
+    """The API rejected the request timestamp as outside its accepted window."""
+
+
 class L2Signature:
     """Represents a signature for L2 operations."""
 
@@ -36,7 +46,10 @@ class AsyncClient:
 
     def __init__(self, base_url: str, account_id: int, stark_pri_key: str, 
                  signing_adapter: Optional[SigningAdapter] = None,
-                 timeout: float = 30.0, connector_limit: int = 100):
+                 timeout: float = 30.0, connector_limit: int = 100, limit_per_host: int = 30,
+                 keepalive_timeout: float = 30.0, connect_timeout: Optional[float] = None,
+                 proxy: Optional[str] = None, order_connector_limit: int = 10,
+                 clock: Optional[RequestClock] = None, timestamp_retries: int = 1):
         """
         Initialize the async internal client.
 
@@ -47,8 +60,20 @@ class AsyncClient:
             signing_adapter: Optional signing adapter to use for cryptographic operations
             timeout: Request timeout in seconds
             connector_limit: Maximum number of connections in the pool
+            limit_per_host: Maximum number of pooled connections to the API host
+            keepalive_timeout: Seconds an idle pooled connection is kept open for reuse
+            connect_timeout: Timeout for acquiring a connection (including TCP/TLS setup), None for no separate limit
+            proxy: Outbound proxy URL (http://, socks5:// or socks5h://, credentials allowed), None for direct connections.
+                SOCKS proxies require the aiohttp-socks package
+            order_connector_limit: Connections in the separate pool reserved for order submission and cancel requests,
+                so they never wait for a connection behind market data or history queries
+            clock: Timestamp source for signed requests (a new RequestClock if not provided)
+            timestamp_retries: Times a request rejected for its timestamp is re-signed and retried
+                after syncing with the server clock
         """
         self.base_url = base_url
+        self.clock = clock or RequestClock()
+        self.timestamp_retries = timestamp_retries
         self.account_id = account_id
         self.stark_pri_key = stark_pri_key
         
@@ -59,8 +84,14 @@ class AsyncClient:
         
         # Store configuration for later session creation
         self._session = None
+        self._order_session = None
+        self._order_connector_limit = order_connector_limit
         self._timeout = timeout
         self._connector_limit = connector_limit
+        self._limit_per_host = limit_per_host
+        self._keepalive_timeout = keepalive_timeout
+        self._connect_timeout = connect_timeout
+        self._proxy = proxy or None
         self._closed = False
 
     async def __aenter__(self):
@@ -73,30 +104,60 @@ class AsyncClient:
         await self.close()
 
     async def _ensure_session(self):
-        """Ensure the aiohttp session is created."""
+        """Ensure the aiohttp sessions (general and order) are created."""
+        # Create connectors and sessions when needed (inside event loop)
         if self._session is None or self._session.closed:
-            # Create connector and session when needed (inside event loop)
-            timeout_config = aiohttp.ClientTimeout(total=self._timeout)
-            connector = aiohttp.TCPConnector(
-                limit=self._connector_limit,
-                limit_per_host=30,
-                keepalive_timeout=30,
-                enable_cleanup_closed=True
-            )
-
-            self._session = aiohttp.ClientSession(
-                timeout=timeout_config,
-                connector=connector,
-                headers={
-                    "Content-Type": "application/json",
-                    "Accept": "application/json"
-                }
-            )
+            self._session = self._create_session(self._connector_limit, self._limit_per_host)
+        if self._order_session is None or self._order_session.closed:
+            self._order_session = self._create_session(self._order_connector_limit, self._order_connector_limit)
+
+    def _create_session(self, limit: int, limit_per_host: int) -> aiohttp.ClientSession:
+        """Create a session with its own connection pool."""
+        timeout_config = aiohttp.ClientTimeout(total=self._timeout, connect=self._connect_timeout)
+        connector_options = dict(
+            limit=limit,
+            limit_per_host=limit_per_host,
+            keepalive_timeout=self._keepalive_timeout,
+            enable_cleanup_closed=True
+        )
+        if self._proxy and urlsplit(self._proxy).scheme.startswith("socks"):
+            connector = self._socks_connector(**connector_options)
+        else:
+            connector = aiohttp.TCPConnector(**connector_options)
+
+        return aiohttp.ClientSession(
+            timeout=timeout_config,
+            connector=connector,
+            headers={
+                "Content-Type": "application/json",
+                "Accept": "application/json"
+            }
+        )
+
+    def _socks_connector(self, **options) -> aiohttp.BaseConnector:
+        """Create a connector that tunnels every connection through the SOCKS proxy."""
+        try:
+            from aiohttp_socks import ProxyConnector
+        except ImportError:
+            raise ImportError("SOCKS proxy requires aiohttp-socks: pip install aiohttp-socks")
+        parts = urlsplit(self._proxy)
+        # socks5h: resolve host names on the proxy side
+        rdns = parts.scheme == "socks5h"
+        url = urlunsplit(parts._replace(scheme="socks5")) if rdns else self._proxy
+        return ProxyConnector.from_url(url, rdns=rdns, **options)
+
+    @property
+    def proxy(self) -> Optional[str]:
+        """Proxy URL to pass on each request (HTTP proxies only, SOCKS is handled by the connector)."""
+        if self._proxy and not urlsplit(self._proxy).scheme.startswith("socks"):
+            return self._proxy
+        return None
 
     async def close(self):
         """Close the HTTP session and cleanup resources."""
-        if self._session and not self._session.closed:
-            await self._session.close()
+        for session in (self._session, self._order_session):
+            if session and not session.closed:
+                await session.close()
         self._closed = True
 
     @property
@@ -106,6 +167,17 @@ class AsyncClient:
             raise RuntimeError("Session not initialized. Use 'async with client:' or call '_ensure_session()'")
         return self._session
 
+    @property
+    def order_session(self) -> aiohttp.ClientSession:
+        """Get the HTTP session reserved for order submission and cancel requests."""
+        if self._order_session is None or self._order_session.closed:
+            raise RuntimeError("Session not initialized. Use 'async with client:' or call '_ensure_session()'")
+        return self._order_session
+
+    def session_for(self, priority: bool) -> aiohttp.ClientSession:
+        """Order-path requests (priority) use the reserved pool, everything else the general pool."""
+        return self.order_session if priority else self.session
+
     def get_account_id(self) -> int:
         """Get the account ID."""
         return self.account_id
@@ -163,7 +235,8 @@ class AsyncClient:
         method: str, 
         path: str, 
         data: Optional[Dict[str, Any]] = None,
-        params: Optional[Dict[str, Any]] = None
+        params: Optional[Dict[str, Any]] = None,
+        priority: bool = False
     ) -> Dict[str, Any]:
         """
         Make an authenticated HTTP request.
@@ -173,6 +246,7 @@ class AsyncClient:
             path: API path (e.g., '/api/v1/private/order/createOrder')
             data: JSON data for POST requests
             params: Query parameters for GET requests
+            priority: Send over the connection pool reserved for order requests
 
         Returns:
             Dict[str, Any]: Response JSON data
@@ -180,10 +254,59 @@ class AsyncClient:
         Raises:
             ValueError: If the request fails
         """
+        for attempt in range(self.timestamp_retries + 1):
+            try:
+                return await self._send_signed(method, path, data, params, priority)
+            except TimestampError:
+                if attempt >= self.timestamp_retries:
+                    raise
+            # The body (client order ID, L2 signature) is unchanged, so a retried order cannot be placed twice
+            self.clock.retries += 1
+            try:
+                await self.sync_clock(force=True)
+            except ValueError:
+                pass
+
+    async def sync_clock(self, force: bool = False) -> int:
+        """
+        Measure the offset to the server clock and apply it to request timestamps.
+
+        Args:
+            force: Apply the offset even if it is within half the receive window
+
+        Returns:
+            int: Measured offset (server - local, ms)
+
+        Raises:
+            ValueError: If the server time could not be fetched
+        """
+        await self._ensure_session()
+        sent = time.time() * 1000
+        try:
+            async with self.session.get(f"{self.base_url}/api/v1/public/meta/getServerTime",
+                                        proxy=self.proxy) as response:
+                resp_data = await response.json()
+        except (aiohttp.ClientError, json.JSONDecodeError) as e:
+            raise TransportError(f"HTTP request failed: {str(e)}")
+        received = time.time() * 1000
+        server_ms = (resp_data.get("data") or {}).get("timeMillis") if isinstance(resp_data, dict) else None
+        if not server_ms:
+            raise ValueError(f"unexpected server time response: {resp_data}")
+        return self.clock.sync(int(server_ms), sent, received, force)
+
+    async def _send_signed(
+        self,
+        method: str,
+        path: str,
+        data: Optional[Dict[str, Any]],
+        params: Optional[Dict[str, Any]],
+        priority: bool
+    ) -> Dict[str, Any]:
+        """Sign the request with a fresh timestamp and send it once."""
         await self._ensure_session()
         
         # Generate timestamp
-        timestamp = int(time.time() * 1000)
+        timestamp = self.clock.now_ms()
         
         # Build full URL
         url = f"{self.base_url}{path}"
@@ -206,34 +329,43 @@ class AsyncClient:
         
         # Make the request
         try:
-            async with self.session.request(
+            async with self.session_for(priority).request(
                 method=method,
                 url=url,
                 json=data,
                 params=params,
-                headers=headers
+                headers=headers,
+                proxy=self.proxy
             ) as response:
                 if response.status != 200:
                     try:
                         error_detail = await response.json()
-                        raise ValueError(f"request failed with status code: {response.status}, response: {error_detail}")
                     except (aiohttp.ContentTypeError, json.JSONDecodeError):
-                        text = await response.text()
-                        raise ValueError(f"request failed with status code: {response.status}, response: {text}")
+                        error_detail = await response.text()
+                    # Classify on the envelope's code/msg only: error bodies often carry an unrelated "timestamp" field
+                    error = ValueError
+                    if isinstance(error_detail, dict) and is_timestamp_error(
+                            f"{error_detail.get('code') or ''} {error_detail.get('msg') or ''}"):
+                        error = TimestampError
+                    raise error(f"request failed with status code: {response.status}, response: {error_detail}")
                 
                 resp_data = await response.json()
                 
                 # Check response code
                 if resp_data.get("code") != "SUCCESS":
+                    error = TimestampError if is_timestamp_error(f"{resp_data.get('code') or ''} {resp_data.get('msg') or ''}") else ValueError
+                    detail = f"request failed with code: {resp_data.get('code')}"
+                    if resp_data.get("msg"):
+                        detail += f", msg: {resp_data.get('msg')}"
                     error_param = resp_data.get("errorParam")
                     if error_param:
-                        raise ValueError(f"request failed with error params: {error_param}")
-                    raise ValueError(f"request failed with code: {resp_data.get('code')}")
+                        detail += f", error params: {error_param}"
+                    raise error(detail)
                 
                 return resp_data
                 
         except aiohttp.ClientError as e:
-            raise ValueError(f"HTTP request failed: {str(e)}")
+            raise TransportError(f"HTTP request failed: {str(e)}")
 
     def _build_signature_content(
         self, 
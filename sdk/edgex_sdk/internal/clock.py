@@ -0,0 +1,73 @@
+import threading
+import time
+from typing import Any, Dict, Optional
+
+# Error codes/messages the API uses when a signed request's timestamp is outside the accepted window
+TIMESTAMP_ERROR_MARKERS = ("timestamp", "recv_window", "recvwindow")
+
+
+def is_timestamp_error(message: str) -> bool:
+    """Whether an API error message reports the request timestamp as out of range."""
+    text = message.lower()
+    return any(marker in text for marker in TIMESTAMP_ERROR_MARKERS)
+
+
+class RequestClock:
+    """
+    Timestamp source for signed requests (REST headers, private WebSocket auth, order expiry).
+
+    Timestamps are local wall-clock milliseconds corrected by the measured offset to the server clock,
+    and strictly increasing: a wall-clock step backwards (NTP adjustment) or two requests signed within
+    the same millisecond never produce a repeated or older timestamp.
+    """
+
+    def __init__(self, recv_window_ms: int = 5000):
+        """
+        Args:
+            recv_window_ms: Maximum difference between a request timestamp and server time the API accepts.
+                When a server time sample shows the local clock off by more than half of it, the offset is applied
+        """
+        self.recv_window_ms = recv_window_ms
+        self.offset_ms = 0
+        self._last_ms = 0
+        self._lock = threading.Lock()
+        self.syncs = 0
+        self.retries = 0
+        self.last_sync_ms: Optional[int] = None
+
+    def now_ms(self) -> int:
+        """Next timestamp for a signed request (server-corrected, strictly increasing)."""
+        with self._lock:
+            timestamp = max(int(time.time() * 1000) + self.offset_ms, self._last_ms + 1)
+            self._last_ms = timestamp
+            return timestamp
+
+    def sync(self, server_ms: int, sent_ms: float, received_ms: float, force: bool = False) -> int:
+        """
+        Update the offset from a server time sample.
+
+        Args:
+            server_ms: Server time in the response
+            sent_ms: Local wall-clock time (ms) the request was sent
+            received_ms: Local wall-clock time (ms) the response arrived
+            force: Apply the offset even if it is within half the receive window (after a timestamp rejection)
+
+        Returns:
+            int: Measured offset (server - local, ms)
+        """
+        measured = int(server_ms - (sent_ms + received_ms) / 2)
+        with self._lock:
+            # Small differences are within the accepted window: don't start correcting for round-trip jitter
+            if force or self.offset_ms or abs(measured) * 2 > self.recv_window_ms:
+                self.offset_ms = measured
+            self.syncs += 1
+            self.last_sync_ms = int(received_ms)
+        return measured
+
+    def get_stats(self) -> Dict[str, Any]:
+        return {
+            "recv_window_ms": self.recv_window_ms,
+            "offset_ms": self.offset_ms,
+            "syncs": self.syncs,
+            "retries": self.retries,
+        }
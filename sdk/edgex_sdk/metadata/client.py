@@ -1,6 +1,9 @@
+import asyncio
 from typing import Dict, Any
 
-from ..internal.async_client import AsyncClient
+import aiohttp
+
+from ..internal.async_client import AsyncClient, TransportError
 
 
 class Client:
@@ -15,10 +18,13 @@ class Client:
         """
         self.async_client = async_client
 
-    async def get_metadata(self) -> Dict[str, Any]:
+    async def get_metadata(self, priority: bool = False) -> Dict[str, Any]:
         """
         Get the exchange metadata.
 
+        Args:
+            priority: Send over the connection pool reserved for order requests
+
         Returns:
             Dict[str, Any]: The exchange metadata
 
@@ -31,7 +37,7 @@ class Client:
         url = f"{self.async_client.base_url}/api/v1/public/meta/getMetaData"
 
         try:
-            async with self.async_client.session.get(url) as response:
+            async with self.async_client.session_for(priority).get(url, proxy=self.async_client.proxy) as response:
                 if response.status != 200:
                     try:
                         error_detail = await response.json()
@@ -53,6 +59,8 @@ class Client:
         except Exception as e:
             if isinstance(e, ValueError):
                 raise
+            if isinstance(e, (aiohttp.ClientError, asyncio.TimeoutError, OSError)):
+                raise TransportError(f"request failed: {str(e)}")
             raise ValueError(f"request failed: {str(e)}")
 
     async def get_server_time(self) -> Dict[str, Any]:
@@ -71,7 +79,7 @@ class Client:
         url = f"{self.async_client.base_url}/api/v1/public/meta/getServerTime"
 
         try:
-            async with self.async_client.session.get(url) as response:
+            async with self.async_client.session.get(url, proxy=self.async_client.proxy) as response:
                 if response.status != 200:
                     try:
                         error_detail = await response.json()
@@ -93,4 +101,6 @@ class Client:
         except Exception as e:
             if isinstance(e, ValueError):
                 raise
+            if isinstance(e, (aiohttp.ClientError, asyncio.TimeoutError, OSError)):
+                raise TransportError(f"request failed: {str(e)}")
             raise ValueError(f"request failed: {str(e)}")
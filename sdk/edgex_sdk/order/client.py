@@ -1,5 +1,4 @@
 import math
-import time
 from decimal import Decimal
 from typing import Dict, Any, Optional, List
 
@@ -8,6 +7,7 @@ from .types import (
     CreateOrderParams,
     CancelOrderParams,
     GetActiveOrderParams,
+    GetHistoryOrderParams,
     OrderFillTransactionParams,
     TimeInForce,
     OrderType
@@ -42,9 +42,9 @@ class Client:
         """
         # Set default TimeInForce based on order type if not specified
         if not params.time_in_force:
-            if params.type == OrderType.MARKET:
+            if params.type in (OrderType.MARKET, OrderType.STOP_MARKET, OrderType.TAKE_PROFIT_MARKET):
                 params.time_in_force = TimeInForce.IMMEDIATE_OR_CANCEL
-            elif params.type == OrderType.LIMIT:
+            elif params.type in (OrderType.LIMIT, OrderType.STOP_LIMIT, OrderType.TAKE_PROFIT_LIMIT):
                 params.time_in_force = TimeInForce.GOOD_TIL_CANCEL
 
         # Find the contract from metadata
@@ -101,7 +101,7 @@ class Client:
         amount_fee = int(amount_fee_dm * Decimal("1000000"))  # Shift 6 decimal places
 
         nonce = self.async_client.calc_nonce(client_order_id)
-        l2_expire_time = int(time.time() * 1000) + (14 * 24 * 60 * 60 * 1000)  # 14 days
+        l2_expire_time = self.async_client.clock.now_ms() + (14 * 24 * 60 * 60 * 1000)  # 14 days
 
         # Calculate signature using asset IDs from metadata
         expire_time_unix = l2_expire_time // (60 * 60 * 1000)
@@ -134,7 +134,9 @@ class Client:
         expire_time_str = str(l2_expire_time - 864000000)  # 10 days earlier
         value_str = str(value_dm)
 
-        price_str = params.price if params.type == OrderType.LIMIT else "0"
+        price_str = params.price if params.type in (
+            OrderType.LIMIT, OrderType.STOP_LIMIT, OrderType.TAKE_PROFIT_LIMIT
+        ) else "0"
 
         # Prepare request data
         request_data = {
@@ -155,12 +157,18 @@ class Client:
             "expireTime": expire_time_str,
             "reduceOnly": params.reduce_only
         }
+        if params.position_side:
+            request_data["positionSide"] = params.position_side
+        if params.trigger_price:
+            request_data["triggerPrice"] = params.trigger_price
+            request_data["triggerPriceType"] = params.trigger_price_type or "LAST_PRICE"
 
         # Execute request using async client
         return await self.async_client.make_authenticated_request(
             method="POST",
             path="/api/v1/private/order/createOrder",
-            data=request_data
+            data=request_data,
+            priority=True
         )
 
     async def cancel_order(self, params: CancelOrderParams) -> Dict[str, Any]:
@@ -203,7 +211,8 @@ class Client:
         return await self.async_client.make_authenticated_request(
             method="POST",
             path=path,
-            data=request_data
+            data=request_data,
+            priority=True
         )
 
     async def get_active_orders(self, params: GetActiveOrderParams) -> Dict[str, Any]:
@@ -261,6 +270,61 @@ class Client:
             params=query_params
         )
 
+    async def get_history_orders(self, params: GetHistoryOrderParams) -> Dict[str, Any]:
+        """
+        Get historical (filled, canceled) orders with pagination and filters.
+
+        Args:
+            params: Historical order query parameters
+
+        Returns:
+            Dict[str, Any]: The historical orders
+
+        Raises:
+            ValueError: If the request fails
+        """
+        # Build query parameters
+        query_params = {
+            "accountId": str(self.async_client.get_account_id())
+        }
+
+        # Add pagination parameters
+        if params.size:
+            query_params["size"] = params.size
+        if params.offset_data:
+            query_params["offsetData"] = params.offset_data
+
+        # Add filter parameters
+        if params.filter_coin_id_list:
+            query_params["filterCoinIdList"] = ",".join(params.filter_coin_id_list)
+        if params.filter_contract_id_list:
+            query_params["filterContractIdList"] = ",".join(params.filter_contract_id_list)
+        if params.filter_type_list:
+            query_params["filterTypeList"] = ",".join(params.filter_type_list)
+        if params.filter_status_list:
+            query_params["filterStatusList"] = ",".join(params.filter_status_list)
+
+        # Add boolean filters
+        if params.filter_is_liquidate is not None:
+            query_params["filterIsLiquidateList"] = str(params.filter_is_liquidate).lower()
+        if params.filter_is_deleverage is not None:
+            query_params["filterIsDeleverageList"] = str(params.filter_is_deleverage).lower()
+        if params.filter_is_position_tpsl is not None:
+            query_params["filterIsPositionTpslList"] = str(params.filter_is_position_tpsl).lower()
+
+        # Add time filters
+        if params.filter_start_created_time_inclusive > 0:
+            query_params["filterStartCreatedTimeInclusive"] = str(params.filter_start_created_time_inclusive)
+        if params.filter_end_created_time_exclusive > 0:
+            query_params["filterEndCreatedTimeExclusive"] = str(params.filter_end_created_time_exclusive)
+
+        # Execute request using async client
+        return await self.async_client.make_authenticated_request(
+            method="GET",
+            path="/api/v1/private/order/getHistoryOrderPage",
+            params=query_params
+        )
+
     async def get_order_fill_transactions(self, params: OrderFillTransactionParams) -> Dict[str, Any]:
         """
         Get order fill transactions with pagination and filters.
@@ -314,6 +378,28 @@ class Client:
             params=query_params
         )
 
+    async def get_orders_by_id(self, order_ids: List[str]) -> Dict[str, Any]:
+        """
+        Get orders (active or historical) by order ID.
+
+        Args:
+            order_ids: Order IDs to look up
+
+        Returns:
+            Dict[str, Any]: The orders found; unknown IDs are omitted
+
+        Raises:
+            ValueError: If the request fails
+        """
+        return await self.async_client.make_authenticated_request(
+            method="GET",
+            path="/api/v1/private/order/getOrderById",
+            params={
+                "accountId": str(self.async_client.get_account_id()),
+                "orderIdList": ",".join(order_ids)
+            }
+        )
+
     async def get_max_order_size(self, contract_id: str, price: float) -> Dict[str, Any]:
         """
         Get the maximum order size for a given contract and price.
@@ -104,6 +104,9 @@ class CreateOrderParams:
     l2_expire_time: Optional[int] = None
     time_in_force: Optional[str] = None
     reduce_only: bool = False
+    position_side: Optional[str] = None  # LONG/SHORT, hedge-mode accounts only
+    trigger_price: Optional[str] = None  # Required for STOP_*/TAKE_PROFIT_* orders
+    trigger_price_type: Optional[str] = None  # LAST_PRICE/MARK_PRICE/INDEX_PRICE
 
 
 @dataclass
@@ -1,6 +1,9 @@
+import asyncio
 from typing import Dict, Any, List
 
-from ..internal.async_client import AsyncClient
+import aiohttp
+
+from ..internal.async_client import AsyncClient, TransportError
 
 
 class GetKLineParams:
@@ -83,7 +86,7 @@ class Client:
         }
 
         try:
-            async with self.async_client.session.get(url, params=params) as response:
+            async with self.async_client.session.get(url, params=params, proxy=self.async_client.proxy) as response:
                 if response.status != 200:
                     try:
                         error_detail = await response.json()
@@ -105,6 +108,8 @@ class Client:
         except Exception as e:
             if isinstance(e, ValueError):
                 raise
+            if isinstance(e, (aiohttp.ClientError, asyncio.TimeoutError, OSError)):
+                raise TransportError(f"request failed: {str(e)}")
             raise ValueError(f"request failed: {str(e)}")
 
     async def get_24_hour_quote(self, contract_id: str) -> Dict[str, Any]:
@@ -129,7 +134,7 @@ class Client:
         }
 
         try:
-            async with self.async_client.session.get(url, params=params) as response:
+            async with self.async_client.session.get(url, params=params, proxy=self.async_client.proxy) as response:
                 if response.status != 200:
                     try:
                         error_detail = await response.json()
@@ -151,6 +156,8 @@ class Client:
         except Exception as e:
             if isinstance(e, ValueError):
                 raise
+            if isinstance(e, (aiohttp.ClientError, asyncio.TimeoutError, OSError)):
+                raise TransportError(f"request failed: {str(e)}")
             raise ValueError(f"request failed: {str(e)}")
 
     async def get_k_line(self, params: GetKLineParams) -> Dict[str, Any]:
@@ -190,7 +197,7 @@ class Client:
         url = f"{self.async_client.base_url}/api/v1/public/quote/getKline"
 
         try:
-            async with self.async_client.session.get(url, params=query_params) as response:
+            async with self.async_client.session.get(url, params=query_params, proxy=self.async_client.proxy) as response:
                 if response.status != 200:
                     try:
                         error_detail = await response.json()
@@ -212,6 +219,8 @@ class Client:
         except Exception as e:
             if isinstance(e, ValueError):
                 raise
+            if isinstance(e, (aiohttp.ClientError, asyncio.TimeoutError, OSError)):
+                raise TransportError(f"request failed: {str(e)}")
             raise ValueError(f"request failed: {str(e)}")
 
     async def get_order_book_depth(self, params: GetOrderBookDepthParams) -> Dict[str, Any]:
@@ -239,7 +248,7 @@ class Client:
         url = f"{self.async_client.base_url}/api/v1/public/quote/getDepth"
 
         try:
-            async with self.async_client.session.get(url, params=query_params) as response:
+            async with self.async_client.session.get(url, params=query_params, proxy=self.async_client.proxy) as response:
                 if response.status != 200:
                     try:
                         error_detail = await response.json()
@@ -261,6 +270,8 @@ class Client:
         except Exception as e:
             if isinstance(e, ValueError):
                 raise
+            if isinstance(e, (aiohttp.ClientError, asyncio.TimeoutError, OSError)):
+                raise TransportError(f"request failed: {str(e)}")
             raise ValueError(f"request failed: {str(e)}")
 
     async def get_multi_contract_k_line(self, params: GetMultiContractKLineParams) -> Dict[str, Any]:
@@ -287,7 +298,7 @@ class Client:
         }
 
         try:
-            async with self.async_client.session.get(url, params=query_params) as response:
+            async with self.async_client.session.get(url, params=query_params, proxy=self.async_client.proxy) as response:
                 if response.status != 200:
                     try:
                         error_detail = await response.json()
@@ -309,4 +320,6 @@ class Client:
         except Exception as e:
             if isinstance(e, ValueError):
                 raise
+            if isinstance(e, (aiohttp.ClientError, asyncio.TimeoutError, OSError)):
+                raise TransportError(f"request failed: {str(e)}")
             raise ValueError(f"request failed: {str(e)}")
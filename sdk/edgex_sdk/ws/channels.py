@@ -0,0 +1,132 @@
+"""
+Typed payloads for public WebSocket channels.
+
+Quote events carry their channel name ("<type>.<contractId>[.<param>]") and a list of
+records under content.data. decode() turns such a message into a ChannelMessage whose
+records are typed for the known channel types (ticker, depth, trades) and left as dicts
+for any other channel.
+"""
+
+from dataclasses import dataclass, field
+from typing import Any, Callable, Dict, List, Optional, Tuple
+
+
+@dataclass
+class Ticker:
+    """24h ticker record."""
+    contract_id: str
+    last_price: float
+    open: float
+    high: float
+    low: float
+    size: float
+    oracle_price: float
+    index_price: float
+    timestamp: int
+
+
+@dataclass
+class DepthUpdate:
+    """Order book snapshot or incremental change. Levels are (price, size); size 0 removes the level."""
+    contract_id: str
+    is_snapshot: bool
+    bids: List[Tuple[float, float]] = field(default_factory=list)
+    asks: List[Tuple[float, float]] = field(default_factory=list)
+
+
+@dataclass
+class Trade:
+    """Public trade record."""
+    contract_id: str
+    price: float
+    size: float
+    is_buyer_maker: Optional[bool]
+    timestamp: int
+
+
+@dataclass
+class ChannelMessage:
+    """A decoded quote event."""
+    channel: str
+    channel_type: str
+    contract_id: str
+    data: List[Any]
+    raw: Dict[str, Any] = field(repr=False, default_factory=dict)
+
+
+def _float(value: Any) -> float:
+    return float(value) if value not in (None, "") else 0.0
+
+
+def _level(level: Any) -> Tuple[float, float]:
+    if isinstance(level, dict):
+        return _float(level.get("price")), _float(level.get("size"))
+    return float(level[0]), float(level[1])
+
+
+def decode_ticker(contract_id: str, record: Dict[str, Any]) -> Ticker:
+    last = _float(record.get("lastPrice"))
+    return Ticker(
+        contract_id=contract_id,
+        last_price=last,
+        open=_float(record.get("open", last)),
+        high=_float(record.get("high", last)),
+        low=_float(record.get("low", last)),
+        size=_float(record.get("size")),
+        oracle_price=_float(record.get("oraclePrice")),
+        index_price=_float(record.get("indexPrice")),
+        timestamp=int(record.get("timestamp") or 0),
+    )
+
+
+def decode_depth(contract_id: str, record: Dict[str, Any]) -> DepthUpdate:
+    return DepthUpdate(
+        contract_id=contract_id,
+        is_snapshot=str(record.get("depthType", "SNAPSHOT")).upper() == "SNAPSHOT",
+        bids=[_level(level) for level in record.get("bids", []) or []],
+        asks=[_level(level) for level in record.get("asks", []) or []],
+    )
+
+
+def decode_trade(contract_id: str, record: Dict[str, Any]) -> Trade:
+    return Trade(
+        contract_id=contract_id,
+        price=_float(record.get("price")),
+        size=_float(record.get("size")),
+        is_buyer_maker=record.get("isBuyerMaker"),
+        timestamp=int(record.get("time") or record.get("timestamp") or 0),
+    )
+
+
+DECODERS: Dict[str, Callable[[str, Dict[str, Any]], Any]] = {
+    "ticker": decode_ticker,
+    "depth": decode_depth,
+    "trades": decode_trade,
+}
+
+
+def decode(msg: Dict[str, Any]) -> ChannelMessage:
+    """
+    Decode a quote event into a ChannelMessage.
+
+    Args:
+        msg: The decoded JSON message
+
+    Returns:
+        ChannelMessage: Records are typed for known channel types, dicts otherwise
+    """
+    content = msg.get("content") or {}
+    channel = msg.get("channel") or content.get("channel", "")
+    parts = channel.split(".", 2)
+    channel_type = parts[0]
+    contract_id = parts[1] if len(parts) > 1 else ""
+
+    records = content.get("data", msg.get("data"))
+    if records is None:
+        records = []
+    elif isinstance(records, dict):
+        records = [records]
+
+    decoder = DECODERS.get(channel_type)
+    data = [decoder(contract_id, record) for record in records] if decoder else list(records)
+    return ChannelMessage(channel=channel, channel_type=channel_type, contract_id=contract_id, data=data, raw=msg)
@@ -1,23 +1,84 @@
 import asyncio
 import binascii
+import fnmatch
+import itertools
 import json
 import logging
+import os
+import queue
 import threading
 import time
-from typing import Dict, Any, List, Optional, Callable, Union
+from typing import Dict, Any, List, Optional, Callable, Set
+from urllib.parse import urlsplit, unquote
 
 import websocket
 from Crypto.Hash import keccak
 
+from ..internal.clock import RequestClock
 from ..internal.signing_adapter import SigningAdapter
 
 from ..internal.client import Client as InternalClient
+from .channels import decode
+from .compression import EXTENSION_OFFER, Inflater, negotiated
 
+try:
+    import orjson
+    _loads = orjson.loads
+except ImportError:
+    _loads = json.loads
 
-class Client:
-    """WebSocket client for real-time data."""
 
-    def __init__(self, url: str, is_private: bool, account_id: int, stark_pri_key: str, signing_adapter: Optional[SigningAdapter] = None):
+_STOP = object()
+
+
+class _ChannelRoute:
+    """A handler registered for a channel pattern."""
+
+    __slots__ = ("pattern", "handler", "typed", "coalesce", "key")
+
+    def __init__(self, pattern: str, handler: Callable[[Any], None], typed: bool, coalesce: bool, key: int):
+        self.pattern = pattern
+        self.handler = handler
+        self.typed = typed
+        self.coalesce = coalesce
+        self.key = key
+
+    def __call__(self, payload: Any):
+        if self.typed:
+            payload = [decode(msg) for msg in payload] if self.coalesce else decode(payload)
+        self.handler(payload)
+
+
+class Client:
+    """
+    WebSocket client for real-time data.
+
+    The connection is owned by two threads so that neither reads nor writes need a lock:
+    a reader thread that only receives and decodes messages, and a writer thread that
+    is the only sender (subscriptions, pings, pongs are queued to it). Decoded messages
+    go through a bounded queue to a dispatcher thread that runs the handlers, so slow
+    handlers apply backpressure instead of delaying pong replies.
+
+    Backpressure policy per message type: by default the reader blocks while the queue is
+    full, so no message is ever dropped (private order events rely on this). Types registered
+    with coalesce=True take at most one queue slot per channel: messages arriving while the
+    channel is already queued are appended to its pending batch and handed to the handler
+    together, so a burst of book updates cannot fill the queue and the handler can publish
+    only the latest state.
+
+    Quote events can also be routed by channel: handlers registered with on_channel() for a
+    glob pattern (e.g. "depth.10000001.*", "ticker.*") receive the messages of every matching
+    channel, decoded into typed ChannelMessage objects (see channels.py) on the dispatcher thread.
+    Channel handlers run in addition to the handler registered for the message type.
+
+    With compression=True the client offers permessage-deflate and inflates compressed
+    messages on the reader thread (see compression.py).
+    """
+
+    def __init__(self, url: str, is_private: bool, account_id: int, stark_pri_key: str,
+                 signing_adapter: Optional[SigningAdapter] = None, inbox_size: int = 10000,
+                 proxy: Optional[str] = None, cpu_affinity: Optional[Set[int]] = None,
+                 compression: bool = False, clock: Optional[RequestClock] = None):
         """
         Initialize the WebSocket client.
 
@@ -26,8 +87,19 @@ class Client:
             is_private: Whether this is a private WebSocket connection
             account_id: Account ID for authentication
             stark_pri_key: Stark private key for signing
+            inbox_size: Maximum number of decoded messages waiting for their handlers
+            proxy: Outbound proxy URL (http://, socks5:// or socks5h://), None for direct connections.
+                SOCKS proxies require the python-socks package
+            cpu_affinity: CPUs to pin the connection threads to (Linux only), None to leave them unpinned
+            compression: Offer permessage-deflate and inflate compressed (also gzip/zlib binary) messages
+            clock: Timestamp source for the connection signature (a new RequestClock if not provided)
         """
         self.url = url
+        self.clock = clock or RequestClock()
+        self.compression = compression
+        self._inflater: Optional[Inflater] = None
+        self.cpu_affinity = cpu_affinity
+        self.proxy = proxy or None
         self.is_private = is_private
         self.account_id = account_id
         self.stark_pri_key = stark_pri_key
@@ -39,15 +111,43 @@ class Client:
 
         self.conn = None
         self.handlers = {}
+        self.parsed_handlers = set()
+        self.coalesced_handlers = set()
         self.done = threading.Event()
         self.ping_thread = None
+        self.message_thread = None
+        self.inbox: queue.Queue = queue.Queue(maxsize=inbox_size)
+        self.outbox: queue.Queue = queue.Queue(maxsize=1000)
+        self.max_inbox_depth = 0
+        self.queue_full_waits = 0
+        self.coalesced: Dict[str, int] = {}
+        self._pending: Dict[Any, List[Any]] = {}
+        self._pending_lock = threading.Lock()
+        self.channel_routes: List[_ChannelRoute] = []
+        self._route_cache: Dict[str, List[_ChannelRoute]] = {}
+        self._route_keys = itertools.count()
         self.subscriptions = set()
         self.on_connect_hooks = []
         self.on_message_hooks = []
         self.on_disconnect_hooks = []
+        self.on_ack_hooks = []
 
         self.logger = logging.getLogger(__name__)
 
+    def _proxy_options(self) -> Dict[str, Any]:
+        """Translate the proxy URL into websocket-client connection options."""
+        if not self.proxy:
+            return {}
+        parts = urlsplit(self.proxy)
+        options: Dict[str, Any] = {
+            "proxy_type": parts.scheme,
+            "http_proxy_host": parts.hostname,
+            "http_proxy_port": parts.port or (1080 if parts.scheme.startswith("socks") else 80),
+        }
+        if parts.username:
+            options["http_proxy_auth"] = (unquote(parts.username), unquote(parts.password or ""))
+        return options
+
     def connect(self):
         """
         Establish a WebSocket connection.
@@ -57,9 +157,11 @@ class Client:
         """
         headers = {}
         url = self.url
+        if self.compression:
+            headers["Sec-WebSocket-Extensions"] = EXTENSION_OFFER
 
         # Add timestamp parameter for both public and private connections
-        timestamp = int(time.time() * 1000)
+        timestamp = self.clock.now_ms()
 
         if self.is_private:
             # Add timestamp header
@@ -87,19 +189,38 @@ class Client:
             separator = "&" if "?" in url else "?"
             url = f"{url}{separator}timestamp={timestamp}"
 
-        # Create WebSocket connection
+        # Create WebSocket connection (single reader and single writer thread, no socket locks needed)
         try:
-            self.conn = websocket.create_connection(url, header=headers)
+            # Compressed text payloads are only valid UTF-8 after inflating, which recv() does not know about
+            self.conn = websocket.create_connection(url, header=headers, enable_multithread=False,
+                                                    skip_utf8_validation=self.compression,
+                                                    **self._proxy_options())
         except Exception as e:
             raise ValueError(f"failed to connect to WebSocket: {str(e)}")
+        self._inflater = Inflater(negotiated(self.conn)) if self.compression else None
 
-        # Start ping thread
         self.done.clear()
+        self.inbox = queue.Queue(maxsize=self.inbox.maxsize)
+        with self._pending_lock:
+            self._pending.clear()
+        self.outbox = queue.Queue(maxsize=self.outbox.maxsize)
+
+        # Start writer thread (the only thread sending on the connection)
+        self.writer_thread = threading.Thread(target=self._write_loop, args=(self.conn, self.outbox))
+        self.writer_thread.daemon = True
+        self.writer_thread.start()
+
+        # Start ping thread
         self.ping_thread = threading.Thread(target=self._ping_loop)
         self.ping_thread.daemon = True
         self.ping_thread.start()
 
-        # Start message handling thread
+        # Start dispatcher thread (runs the handlers)
+        self.dispatch_thread = threading.Thread(target=self._dispatch_loop, args=(self.inbox,))
+        self.dispatch_thread.daemon = True
+        self.dispatch_thread.start()
+
+        # Start reader thread
         self.message_thread = threading.Thread(target=self._handle_messages)
         self.message_thread.daemon = True
         self.message_thread.start()
@@ -108,16 +229,125 @@ class Client:
         for hook in self.on_connect_hooks:
             hook()
 
+    @property
+    def is_connected(self) -> bool:
+        """Whether the connection is open and the reader thread is still running."""
+        return (self.conn is not None and not self.done.is_set()
+                and self.message_thread is not None and self.message_thread.is_alive())
+
     def close(self):
         """Close the WebSocket connection."""
         self.done.set()
+        for box in (self.outbox, self.inbox):
+            try:
+                box.put_nowait(_STOP)
+            except queue.Full:
+                pass
 
         if self.conn:
             self.conn.close()
             self.conn = None
 
+    def _send(self, msg: Dict[str, Any]):
+        """
+        Queue a message for the writer thread.
+
+        Raises:
+            ValueError: If the send queue is full
+        """
+        try:
+            self.outbox.put_nowait(json.dumps(msg))
+        except queue.Full:
+            raise ValueError("send queue is full")
+
+    def _pin_thread(self):
+        """Pin the calling connection thread to the configured CPUs."""
+        if not self.cpu_affinity or not hasattr(os, "sched_setaffinity"):
+            return
+        try:
+            os.sched_setaffinity(0, self.cpu_affinity)
+        except OSError as e:
+            self.logger.warning(f"Failed to set CPU affinity {sorted(self.cpu_affinity)}: {str(e)}")
+
+    def _write_loop(self, conn, outbox: queue.Queue):
+        """Send queued messages (the only thread writing to the connection)."""
+        self._pin_thread()
+        while True:
+            data = outbox.get()
+            if data is _STOP or self.done.is_set():
+                break
+            try:
+                conn.send(data)
+            except Exception as e:
+                self.logger.error(f"Failed to send message: {str(e)}")
+                break
+
+    def _dispatch_loop(self, inbox: queue.Queue):
+        """Run handlers for messages queued by the reader thread."""
+        self._pin_thread()
+        while True:
+            item = inbox.get()
+            if item is _STOP:
+                break
+            handler, payload, channel = item
+            if channel is not None:
+                with self._pending_lock:
+                    payload = self._pending.pop(channel, [])
+            try:
+                handler(payload)
+            except Exception as e:
+                self.logger.error(f"Error in message handler: {str(e)}")
+
+    def _dispatch(self, msg_type: str, msg: Dict[str, Any], message: str):
+        """
+        Queue a message for its handler.
+
+        Blocks while the queue is full. Coalesced types only queue a marker for the channel
+        and append the payload to the channel's pending batch when a marker is already queued.
+        """
+        handler = self.handlers.get(msg_type)
+        if handler is None:
+            return
+        payload = msg if msg_type in self.parsed_handlers else message
+        pending_key = (msg.get("channel") or msg_type) if msg_type in self.coalesced_handlers else None
+        self._enqueue(handler, payload, pending_key, msg_type)
+
+    def _routes_for(self, channel: str) -> List[_ChannelRoute]:
+        """Channel handlers whose pattern matches the channel (cached per channel)."""
+        cache = self._route_cache
+        routes = cache.get(channel)
+        if routes is None:
+            routes = [route for route in self.channel_routes if fnmatch.fnmatchcase(channel, route.pattern)]
+            cache[channel] = routes
+        return routes
+
+    def _enqueue(self, handler: Callable[[Any], None], payload: Any, pending_key: Any, msg_type: str):
+        """Queue a payload for a handler; pending_key coalesces payloads into the key's pending batch."""
+        if pending_key is not None:
+            with self._pending_lock:
+                batch = self._pending.get(pending_key)
+                if batch is not None:
+                    batch.append(payload)
+                    self.coalesced[msg_type] = self.coalesced.get(msg_type, 0) + 1
+                    return
+                self._pending[pending_key] = [payload]
+            item = (handler, None, pending_key)
+        else:
+            item = (handler, payload, None)
+        while not self.done.is_set():
+            try:
+                self.inbox.put(item, timeout=1)
+                break
+            except queue.Full:
+                self.queue_full_waits += 1
+                self.logger.warning("Message queue is full, handlers are falling behind")
+        depth = self.inbox.qsize()
+        if depth > self.max_inbox_depth:
+            self.max_inbox_depth = depth
+
     def _ping_loop(self):
         """Send periodic ping messages."""
+        self._pin_thread()
         while not self.done.is_set():
             if self.conn:
                 ping_msg = {
@@ -126,7 +356,7 @@ class Client:
                 }
 
                 try:
-                    self.conn.send(json.dumps(ping_msg))
+                    self._send(ping_msg)
                 except Exception as e:
                     self.logger.error(f"Failed to send ping: {str(e)}")
                     break
@@ -134,14 +364,27 @@ class Client:
             # Wait for 30 seconds or until done
             self.done.wait(30)
 
+    def _recv(self):
+        """Receive one message, inflating it when compression is enabled."""
+        if self._inflater is None:
+            return self.conn.recv()
+        opcode, data = self.conn.recv_data()
+        if opcode not in (websocket.ABNF.OPCODE_TEXT, websocket.ABNF.OPCODE_BINARY):
+            return ""
+        data = self._inflater.inflate(opcode, data)
+        return data.decode("utf-8") if opcode == websocket.ABNF.OPCODE_TEXT else data
+
     def _handle_messages(self):
-        """Process incoming WebSocket messages."""
+        """Receive and decode incoming WebSocket messages (reader thread)."""
+        self._pin_thread()
+        if self._inflater is not None:
+            self._inflater.bind_thread()
         while not self.done.is_set():
             if not self.conn:
                 break
 
             try:
-                message = self.conn.recv()
+                message = self._recv()
 
                 # Call message hooks
                 for hook in self.on_message_hooks:
@@ -149,8 +392,8 @@ class Client:
 
                 # Parse message
                 try:
-                    msg = json.loads(message)
-                except json.JSONDecodeError:
+                    msg = _loads(message)
+                except ValueError:
                     continue
 
                 # Handle ping messages
@@ -158,19 +401,22 @@ class Client:
                     self._handle_pong(msg.get("time", ""))
                     continue
 
+                # Subscription acknowledgements and errors
+                if msg.get("type") in ("subscribed", "unsubscribed", "error"):
+                    for hook in self.on_ack_hooks:
+                        hook(msg)
+
                 # Handle quote events
                 if msg.get("type") == "quote-event":
                     channel = msg.get("channel", "")
                     channel_type = channel.split(".")[0] if "." in channel else channel
-
-                    if channel_type in self.handlers:
-                        self.handlers[channel_type](message)
+                    for route in self._routes_for(channel):
+                        self._enqueue(route, msg, (route.key, channel) if route.coalesce else None, channel_type)
+                    self._dispatch(channel_type, msg, message)
                     continue
 
                 # Call registered handlers for other message types
-                msg_type = msg.get("type", "")
-                if msg_type in self.handlers:
-                    self.handlers[msg_type](message)
+                self._dispatch(msg.get("type", ""), msg, message)
 
             except Exception as e:
                 self.logger.error(f"Error handling message: {str(e)}")
@@ -181,6 +427,13 @@ class Client:
 
                 break
 
+        # Let the dispatcher finish queued messages and stop the writer
+        for box in (self.inbox, self.outbox):
+            try:
+                box.put_nowait(_STOP)
+            except queue.Full:
+                pass
+
     def _handle_pong(self, timestamp: str):
         """
         Send pong response to server ping.
@@ -194,7 +447,7 @@ class Client:
         }
 
         try:
-            self.conn.send(json.dumps(pong_msg))
+            self._send(pong_msg)
         except Exception as e:
             self.logger.error(f"Failed to send pong: {str(e)}")
 
@@ -227,7 +480,7 @@ class Client:
             sub_msg.update(params)
 
         try:
-            self.conn.send(json.dumps(sub_msg))
+            self._send(sub_msg)
             self.subscriptions.add(topic)
             return True
         except Exception as e:
@@ -258,21 +511,83 @@ class Client:
         }
 
         try:
-            self.conn.send(json.dumps(unsub_msg))
+            self._send(unsub_msg)
             self.subscriptions.discard(topic)
             return True
         except Exception as e:
             raise ValueError(f"failed to unsubscribe: {str(e)}")
 
-    def on_message(self, msg_type: str, handler: Callable[[str], None]):
+    def on_message(self, msg_type: str, handler: Callable[[Any], None], parsed: bool = False,
+                   coalesce: bool = False):
         """
         Register a handler for a specific message type.
 
         Args:
             msg_type: The message type to handle
             handler: The handler function
+            parsed: Pass the already decoded message dict instead of the raw string,
+                so the handler does not decode the message a second time
+            coalesce: Pass a list with every message of the channel that arrived while the
+                handler was busy (oldest first) instead of one message per call
         """
         self.handlers[msg_type] = handler
+        for enabled, types in ((parsed, self.parsed_handlers), (coalesce, self.coalesced_handlers)):
+            if enabled:
+                types.add(msg_type)
+            else:
+                types.discard(msg_type)
+
+    def on_channel(self, pattern: str, handler: Callable[[Any], None], typed: bool = True,
+                   coalesce: bool = False):
+        """
+        Register a handler for quote events on channels matching a glob pattern.
+
+        Several handlers may match the same channel; each receives every message.
+
+        Args:
+            pattern: Channel pattern, e.g. "ticker.*", "depth.10000001.*"
+            handler: The handler function
+            typed: Pass a decoded ChannelMessage (typed records) instead of the message dict
+            coalesce: Pass a list with every message of the channel that arrived while the
+                handler was busy (oldest first) instead of one message per call
+        """
+        route = _ChannelRoute(pattern, handler, typed, coalesce, next(self._route_keys))
+        # Copy on write: the reader thread iterates the current list without a lock
+        self.channel_routes = [*self.channel_routes, route]
+        self._route_cache = {}
+
+    def off_channel(self, pattern: str, handler: Optional[Callable[[Any], None]] = None):
+        """
+        Remove the handlers registered for a channel pattern.
+
+        Args:
+            pattern: The pattern passed to on_channel()
+            handler: Only remove this handler (None removes all handlers of the pattern)
+        """
+        self.channel_routes = [
+            route for route in self.channel_routes
+            if route.pattern != pattern or (handler is not None and route.handler != handler)
+        ]
+        self._route_cache = {}
+
+    def backpressure_stats(self) -> Dict[str, Any]:
+        """Queue depth, blocked reader waits and coalesced message counts by type."""
+        return {
+            "inbox_depth": self.inbox.qsize(),
+            "max_inbox_depth": self.max_inbox_depth,
+            "queue_full_waits": self.queue_full_waits,
+            "coalesced": dict(self.coalesced),
+        }
+
+    def compression_stats(self) -> Optional[Dict[str, Any]]:
+        """Whether permessage-deflate was negotiated and bytes received compressed vs inflated (None when disabled)."""
+        if self._inflater is None:
+            return None
+        return {
+            "negotiated": self._inflater.deflate,
+            "compressed_bytes": self._inflater.compressed_bytes,
+            "inflated_bytes": self._inflater.inflated_bytes,
+        }
 
     def on_message_hook(self, hook: Callable[[str], None]):
         """
@@ -283,6 +598,16 @@ class Client:
         """
         self.on_message_hooks.append(hook)
 
+    def on_ack(self, hook: Callable[[Dict[str, Any]], None]):
+        """
+        Register a hook for subscription acknowledgements ("subscribed"/"unsubscribed")
+        and server errors. Hooks run on the reader thread and must not block.
+
+        Args:
+            hook: Called with the decoded message
+        """
+        self.on_ack_hooks.append(hook)
+
     def on_connect(self, hook: Callable[[], None]):
         """
         Register a hook that will be called when connection is established.
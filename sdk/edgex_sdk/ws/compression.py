@@ -0,0 +1,110 @@
+"""
+Message compression for WebSocket connections.
+
+websocket-client does not implement permessage-deflate (RFC 7692): it neither offers the
+extension nor accepts frames with the RSV1 bit set. When compression is enabled the client
+offers the extension in the handshake; if the server accepts it, frames of that connection
+may carry RSV1 and their payload is raw deflate data, inflated here with one decompressor
+per connection (the server may keep its compression context between messages).
+
+Payloads compressed by the application instead (binary frames holding gzip or zlib data)
+are inflated as well, whether or not the extension was negotiated.
+"""
+
+import threading
+import zlib
+from typing import Optional
+
+import websocket
+
+EXTENSION_OFFER = "permessage-deflate; client_max_window_bits"
+
+_DEFLATE_TAIL = b"\x00\x00\xff\xff"
+_reader = threading.local()
+_patched = False
+_patch_lock = threading.Lock()
+
+
+def _install_rsv1_support():
+    """
+    Let frames with RSV1 pass validation on reader threads of connections that negotiated
+    permessage-deflate, and record whether the current message is compressed.
+
+    The frame parser is shared by all connections, so the check is switched on per thread.
+    """
+    global _patched
+    with _patch_lock:
+        if _patched:
+            return
+        abnf = websocket.ABNF
+        validate = abnf.validate
+
+        def validate_rsv1(frame, skip_utf8_validation=False):
+            if not getattr(_reader, "deflate", False):
+                return validate(frame, skip_utf8_validation)
+            if frame.opcode in (abnf.OPCODE_TEXT, abnf.OPCODE_BINARY):
+                # First frame of a message; continuation frames never carry RSV1
+                _reader.compressed = bool(frame.rsv1)
+            if not frame.rsv1:
+                return validate(frame, skip_utf8_validation)
+            frame.rsv1 = 0
+            try:
+                return validate(frame, skip_utf8_validation)
+            finally:
+                frame.rsv1 = 1
+
+        abnf.validate = validate_rsv1
+        _patched = True
+
+
+def negotiated(conn) -> bool:
+    """Whether the server accepted permessage-deflate in the handshake response."""
+    headers = conn.getheaders() or {}
+    for name, value in headers.items():
+        if name.lower() == "sec-websocket-extensions" and "permessage-deflate" in value.lower():
+            return True
+    return False
+
+
+class Inflater:
+    """Decompresses the messages of one connection (used on its reader thread only)."""
+
+    def __init__(self, deflate: bool):
+        """
+        Args:
+            deflate: Whether permessage-deflate was negotiated for the connection
+        """
+        self.deflate = deflate
+        self._decompressor = zlib.decompressobj(-zlib.MAX_WBITS) if deflate else None
+        self.compressed_bytes = 0
+        self.inflated_bytes = 0
+        if deflate:
+            _install_rsv1_support()
+
+    def bind_thread(self):
+        """Mark the calling thread as the connection's reader thread."""
+        _reader.deflate = self.deflate
+        _reader.compressed = False
+
+    def inflate(self, opcode: int, data: bytes) -> Optional[bytes]:
+        """
+        Decompress the payload of a received message.
+
+        Args:
+            opcode: Opcode of the message's first frame
+            data: Message payload as received
+
+        Returns:
+            Optional[bytes]: The decompressed payload, or data unchanged when not compressed
+        """
+        if self.deflate and getattr(_reader, "compressed", False):
+            _reader.compressed = False
+            inflated = self._decompressor.decompress(data + _DEFLATE_TAIL)
+        elif opcode == websocket.ABNF.OPCODE_BINARY and data[:2] in (b"\x1f\x8b", b"\x78\x01", b"\x78\x9c", b"\x78\xda"):
+            # gzip or zlib payload (wbits 47 detects either header)
+            inflated = zlib.decompress(data, 47)
+        else:
+            return data
+        self.compressed_bytes += len(data)
+        self.inflated_bytes += len(inflated)
+        return inflated
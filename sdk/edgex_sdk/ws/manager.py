@@ -1,6 +1,7 @@
 import logging
-from typing import Dict, Any, List, Optional, Callable
+from typing import Dict, Any, List, Optional, Callable, Set
 
+from ..internal.clock import RequestClock
 from ..internal.signing_adapter import SigningAdapter
 from ..internal.starkex_signing_adapter import StarkExSigningAdapter
 from .client import Client
@@ -9,7 +10,9 @@ from .client import Client
 class Manager:
     """Manager for WebSocket connections."""
 
-    def __init__(self, base_url: str, account_id: int, stark_pri_key: str, signing_adapter: Optional[SigningAdapter] = None):
+    def __init__(self, base_url: str, account_id: int, stark_pri_key: str, signing_adapter: Optional[SigningAdapter] = None,
+                 proxy: Optional[str] = None, cpu_affinity: Optional[Set[int]] = None,
+                 compression: bool = False, clock: Optional[RequestClock] = None):
         """
         Initialize the WebSocket manager.
 
@@ -18,8 +21,16 @@ class Manager:
             account_id: Account ID for authentication
             stark_pri_key: Stark private key for signing
             signing_adapter: Optional signing adapter (defaults to StarkExSigningAdapter)
+            proxy: Outbound proxy URL (http://, socks5:// or socks5h://), None for direct connections
+            cpu_affinity: CPUs to pin the connection threads to (Linux only)
+            compression: Offer permessage-deflate and inflate compressed messages
+            clock: Timestamp source for connection signatures, shared with the REST client to reuse its server offset
         """
+        self.clock = clock
         self.base_url = base_url
+        self.proxy = proxy
+        self.cpu_affinity = cpu_affinity
+        self.compression = compression
         self.account_id = account_id
         self.stark_pri_key = stark_pri_key
 
@@ -46,7 +57,11 @@ class Manager:
                 is_private=False,
                 account_id=self.account_id,
                 stark_pri_key=self.stark_pri_key,
-                signing_adapter=self.signing_adapter
+                signing_adapter=self.signing_adapter,
+                proxy=self.proxy,
+                cpu_affinity=self.cpu_affinity,
+                compression=self.compression,
+                clock=self.clock
             )
 
         return self.public_client
@@ -64,7 +79,11 @@ class Manager:
                 is_private=True,
                 account_id=self.account_id,
                 stark_pri_key=self.stark_pri_key,
-                signing_adapter=self.signing_adapter
+                signing_adapter=self.signing_adapter,
+                proxy=self.proxy,
+                cpu_affinity=self.cpu_affinity,
+                compression=self.compression,
+                clock=self.clock
             )
 
         return self.private_client
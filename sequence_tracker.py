@@ -0,0 +1,121 @@
+"""
+行情序列检查模块
+
+按频道跟踪交易所提供的序列号，发现丢包或乱序时不再基于损坏的本地订单簿交易：
+- 深度: 增量消息带 startVersion/endVersion，下一条增量的 startVersion 应为上一条 endVersion + 1。
+  出现缺口时丢弃本地订单簿并重新订阅该深度频道（服务器订阅后先推送全量快照），收到快照前的增量全部忽略；
+  版本号不大于已处理版本的增量为重复或乱序消息，直接丢弃。没有版本号时按消息时间戳检查乱序
+- 逐笔成交: 按 ticketId（没有时按成交时间）去掉重复和乱序到达的成交，成交无法补发，只记录次数
+"""
+
+from typing import Any, Dict, Optional
+
+from loguru import logger
+
+DEPTH_OK = "ok"
+DEPTH_SNAPSHOT = "snapshot"
+DEPTH_STALE = "stale"        # 重复或乱序的增量（丢弃）
+DEPTH_GAP = "gap"            # 检测到缺口，需要重新同步
+DEPTH_RESYNCING = "resyncing"  # 等待快照期间的增量（丢弃）
+
+
+def _int(value: Any) -> Optional[int]:
+    try:
+        return int(value) if value not in (None, "") else None
+    except (TypeError, ValueError):
+        return None
+
+
+class SequenceTracker:
+    """深度和逐笔成交频道的序列检查"""
+
+    def __init__(self):
+        self.depth_versions: Dict[str, int] = {}     # 频道 -> 已处理的最新版本号（无版本号时为时间戳）
+        self.resyncing: Dict[str, bool] = {}         # 频道 -> 是否在等待快照
+        self.trade_cursors: Dict[str, tuple] = {}    # 频道 -> 最后一笔成交的 (ticketId, 时间)
+        self.stats = {"depth_gaps": 0, "depth_out_of_order": 0, "depth_resyncs": 0,
+                      "trade_duplicates": 0, "trade_out_of_order": 0}
+
+    def check_depth(self, channel: str, depth: Dict[str, Any]) -> str:
+        """
+        检查一条深度数据
+
+        Returns:
+            str: DEPTH_OK / DEPTH_SNAPSHOT 可以合并；DEPTH_STALE / DEPTH_RESYNCING 丢弃；
+                DEPTH_GAP 丢弃并重新同步（只在进入重新同步时返回一次）
+        """
+        start = _int(depth.get("startVersion"))
+        end = _int(depth.get("endVersion"))
+        if end is None:
+            start = end = _int(depth.get("timestamp") or depth.get("time"))
+            sequential = False
+        else:
+            sequential = start is not None
+
+        if str(depth.get("depthType", "SNAPSHOT")).upper() == "SNAPSHOT":
+            self.resyncing.pop(channel, None)
+            if end is not None:
+                self.depth_versions[channel] = end
+            else:
+                self.depth_versions.pop(channel, None)
+            return DEPTH_SNAPSHOT
+
+        if self.resyncing.get(channel):
+            return DEPTH_RESYNCING
+        last = self.depth_versions.get(channel)
+        if last is None or end is None:
+            if end is not None:
+                self.depth_versions[channel] = end
+            return DEPTH_OK
+
+        if (end <= last) if sequential else (end < last):
+            self.stats["depth_out_of_order"] += 1
+            if not sequential:
+                # 只有时间戳时无法判断乱序消息是否已包含在本地订单簿中，重新同步
+                return self._start_resync(channel, f"消息时间戳 {end} 早于上一条 {last}")
+            return DEPTH_STALE
+        if sequential and start > last + 1:
+            self.stats["depth_gaps"] += 1
+            return self._start_resync(channel, f"版本缺口 {last} → {start}")
+        self.depth_versions[channel] = end
+        return DEPTH_OK
+
+    def _start_resync(self, channel: str, reason: str) -> str:
+        self.resyncing[channel] = True
+        self.depth_versions.pop(channel, None)
+        self.stats["depth_resyncs"] += 1
+        logger.warning(f"[序列] {channel} {reason}，丢弃本地订单簿并重新同步")
+        return DEPTH_GAP
+
+    def resync_failed(self, channel: str):
+        """重新订阅失败：保持等待快照状态（下次连接时的快照会恢复）"""
+        self.resyncing[channel] = True
+
+    def check_trade(self, channel: str, trade: Dict[str, Any]) -> bool:
+        """检查一笔成交，返回是否为新成交（重复或乱序到达的返回False）"""
+        ticket = _int(trade.get("ticketId"))
+        ts = _int(trade.get("time") or trade.get("timestamp")) or 0
+        last = self.trade_cursors.get(channel)
+        if last is not None:
+            last_ticket, last_ts = last
+            if ticket is not None and last_ticket is not None:
+                if ticket == last_ticket:
+                    self.stats["trade_duplicates"] += 1
+                    return False
+                if ticket < last_ticket:
+                    self.stats["trade_out_of_order"] += 1
+                    return False
+            elif ts < last_ts:
+                self.stats["trade_out_of_order"] += 1
+                return False
+        self.trade_cursors[channel] = (ticket, ts)
+        return True
+
+    def reset(self):
+        """新连接：订阅后先收到快照，之前的序列状态作废"""
+        self.depth_versions.clear()
+        self.resyncing.clear()
+        self.trade_cursors.clear()
+
+    def get_stats(self) -> Dict[str, Any]:
+        return {**self.stats, "resyncing": sum(1 for v in self.resyncing.values() if v)}
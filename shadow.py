@@ -0,0 +1,164 @@
+"""
+影子交易模块
+
+在实盘（或模拟盘）策略旁并行运行一份"理想化"的模拟策略：使用同一份行情和信号，
+但按回测假设以信号价格立即全部成交。逐笔对比两者的决策与成交，统计：
+- 决策分歧（一方开/平仓而另一方没有）
+- 成交滑点（实际成交价相对信号价格，bps）
+- 执行落差（implementation shortfall，USDT）
+用于量化回测假设与生产环境之间的差距。
+"""
+
+from decimal import Decimal
+from typing import Dict, List, Optional, Any
+from loguru import logger
+
+from edgex_types import Fill, OrderSide, TradeDirection, TradeSignal
+
+
+class ShadowPosition:
+    """影子持仓（按信号价格理想成交）"""
+
+    def __init__(self, direction: TradeDirection, entry_price: Decimal, stop_loss: float, take_profit: float):
+        self.direction = direction
+        self.entry_price = entry_price
+        self.stop_loss = stop_loss
+        self.take_profit = take_profit
+
+
+class ShadowTrader:
+    """影子交易器"""
+
+    def __init__(self):
+        self.positions: Dict[str, ShadowPosition] = {}
+
+        # 当前评估周期内的决策（交易对 -> 动作）
+        self.shadow_actions: Dict[str, str] = {}
+        self.live_actions: Dict[str, str] = {}
+
+        # 实盘最近一次下单的决策价格（用于计算成交滑点）
+        self.live_decision_prices: Dict[str, Decimal] = {}
+
+        # 统计
+        self.evaluations = 0
+        self.divergences: Dict[str, int] = {}
+        self.slippage_bps: List[float] = []
+        self.implementation_shortfall = Decimal("0")
+        self.shadow_return = Decimal("0")
+        self.shadow_trades = 0
+
+    def on_signal(self, symbol: str, signal: TradeSignal, current_price: Decimal):
+        """
+        影子策略处理信号（理想成交）
+
+        Args:
+            symbol: 交易对
+            signal: 与实盘相同的交易信号
+            current_price: 当前价格
+        """
+        self.evaluations += 1
+        action = "NONE"
+        position = self.positions.get(symbol)
+
+        if position:
+            hit_tp = position.take_profit > 0 and (
+                (position.direction == TradeDirection.LONG and current_price >= Decimal(str(position.take_profit))) or
+                (position.direction == TradeDirection.SHORT and current_price <= Decimal(str(position.take_profit)))
+            )
+            hit_sl = position.stop_loss > 0 and (
+                (position.direction == TradeDirection.LONG and current_price <= Decimal(str(position.stop_loss))) or
+                (position.direction == TradeDirection.SHORT and current_price >= Decimal(str(position.stop_loss)))
+            )
+            reverse = signal.direction != TradeDirection.HOLD and signal.direction != position.direction
+
+            if hit_tp or hit_sl or reverse:
+                self._close(symbol, current_price)
+                action = "CLOSE"
+
+        elif signal.direction != TradeDirection.HOLD:
+            self.positions[symbol] = ShadowPosition(
+                direction=signal.direction,
+                entry_price=Decimal(str(signal.price)),
+                stop_loss=signal.stop_loss,
+                take_profit=signal.take_profit
+            )
+            action = f"OPEN_{signal.direction.value}"
+
+        self.shadow_actions[symbol] = action
+
+    def on_live_order(self, symbol: str, action: str, decision_price: Decimal):
+        """
+        记录实盘在本周期的下单决策
+
+        Args:
+            symbol: 交易对
+            action: 动作（OPEN_LONG / OPEN_SHORT / CLOSE）
+            decision_price: 决策时的价格
+        """
+        self.live_actions[symbol] = action
+        self.live_decision_prices[symbol] = decision_price
+
+    def end_evaluation(self, symbol: str):
+        """评估周期结束，对比影子与实盘的决策"""
+        shadow_action = self.shadow_actions.pop(symbol, "NONE")
+        live_action = self.live_actions.pop(symbol, "NONE")
+
+        if shadow_action != live_action:
+            key = f"{shadow_action}->{live_action}"
+            self.divergences[key] = self.divergences.get(key, 0) + 1
+            logger.info(f"[影子交易] {symbol} 决策分歧: 影子={shadow_action} 实盘={live_action}")
+
+    def on_live_fill(self, fill: Fill):
+        """
+        实盘成交回报，相对决策价格计算滑点与执行落差
+
+        Args:
+            fill: 成交回报
+        """
+        decision_price = self.live_decision_prices.get(fill.symbol)
+        if not decision_price or decision_price <= 0:
+            return
+
+        price = Decimal(str(fill.price))
+        size = Decimal(str(fill.size))
+        sign = Decimal("1") if fill.side == OrderSide.BUY else Decimal("-1")
+
+        # 正值表示成交比决策价格更差
+        slippage = sign * (price - decision_price) / decision_price * Decimal("10000")
+        self.slippage_bps.append(float(slippage))
+        self.implementation_shortfall += sign * (price - decision_price) * size + Decimal(str(fill.fee))
+
+        if abs(slippage) >= 5:
+            logger.info(
+                f"[影子交易] {fill.symbol} 成交偏离: 决策价 {float(decision_price):.4f} "
+                f"成交价 {float(price):.4f} ({float(slippage):.2f} bps)"
+            )
+
+    def _close(self, symbol: str, price: Decimal):
+        """影子平仓，累计收益率"""
+        position = self.positions.pop(symbol)
+        if position.entry_price <= 0:
+            return
+
+        ret = (price - position.entry_price) / position.entry_price
+        if position.direction == TradeDirection.SHORT:
+            ret = -ret
+
+        self.shadow_return += ret
+        self.shadow_trades += 1
+
+    def get_summary(self) -> Dict[str, Any]:
+        """获取影子交易统计摘要"""
+        slippages = self.slippage_bps
+        return {
+            "evaluations": self.evaluations,
+            "divergences": sum(self.divergences.values()),
+            "divergence_breakdown": dict(self.divergences),
+            "fills_compared": len(slippages),
+            "avg_slippage_bps": sum(slippages) / len(slippages) if slippages else 0.0,
+            "max_slippage_bps": max(slippages) if slippages else 0.0,
+            "implementation_shortfall": float(self.implementation_shortfall),
+            "shadow_trades": self.shadow_trades,
+            "shadow_return_pct": float(self.shadow_return) * 100,
+            "shadow_open_positions": len(self.positions),
+        }
@@ -0,0 +1,265 @@
+"""
+外部信号总线模块
+
+通过 Redis pub/sub 或 ZeroMQ 与外部研究系统实时交互：
+- 消费交易信号：消息格式与信号Webhook的告警JSON相同（ticker/action/price/stop_loss/take_profit），
+  经与内部信号相同的风控检查后执行
+- 发布事件：成交回报（fills）和成交后的持仓快照（positions），JSON格式
+
+Redis：订阅 <channel> 频道接收信号，向 <prefix>.fills / <prefix>.positions 频道发布事件。
+ZeroMQ：PULL套接字（绑定signal_endpoint）接收信号，PUB套接字（绑定publish_endpoint）
+发布 [topic, json] 两帧消息，topic 为 fills / positions。
+
+依赖为可选项（redis>=4.2 / pyzmq），未安装时总线无法启动，不影响交易主流程。
+"""
+
+import asyncio
+import json
+from abc import ABC, abstractmethod
+from typing import Awaitable, Callable, Dict, List, Optional, Any
+from loguru import logger
+
+from edgex_types import TradeSignal
+from webhook_server import AlertError, parse_alert
+
+try:
+    import redis.asyncio as aioredis
+    REDIS_AVAILABLE = True
+except ImportError:
+    REDIS_AVAILABLE = False
+
+try:
+    import zmq
+    import zmq.asyncio
+    ZMQ_AVAILABLE = True
+except ImportError:
+    ZMQ_AVAILABLE = False
+
+
+# 发布的事件主题
+TOPIC_FILLS = "fills"
+TOPIC_POSITIONS = "positions"
+
+
+class SignalBus(ABC):
+    """信号总线基类"""
+
+    BACKEND = ""
+
+    # 待发布事件队列上限（外部系统不可用时丢弃最早的事件）
+    MAX_PENDING = 10000
+
+    def __init__(self, symbols: List[str], handler: Callable[[str, TradeSignal], Awaitable[None]]):
+        """
+        Args:
+            symbols: 配置的交易对列表
+            handler: 信号处理协程（参数为动作和交易信号）
+        """
+        self.symbols = symbols
+        self.handler = handler
+        self._queue: Optional[asyncio.Queue] = None
+        self._tasks: List[asyncio.Task] = []
+
+        # 统计
+        self.received = 0
+        self.rejected = 0
+        self.published = 0
+        self.dropped = 0
+
+    async def start(self):
+        """连接并启动收发任务"""
+        self._queue = asyncio.Queue(maxsize=self.MAX_PENDING)
+        await self._connect()
+        self._tasks = [asyncio.create_task(self._consume()), asyncio.create_task(self._drain())]
+
+    async def stop(self):
+        """停止收发任务并断开连接"""
+        for task in self._tasks:
+            task.cancel()
+        self._tasks = []
+        await self._disconnect()
+
+    def publish(self, topic: str, payload: Dict[str, Any]):
+        """
+        发布事件（非阻塞，需在事件循环线程中调用）
+
+        Args:
+            topic: 主题（fills/positions）
+            payload: 事件内容
+        """
+        if self._queue is None:
+            return
+        if self._queue.full():
+            self._queue.get_nowait()
+            self.dropped += 1
+        self._queue.put_nowait((topic, payload))
+
+    async def _drain(self):
+        """发布队列中的事件"""
+        while True:
+            topic, payload = await self._queue.get()
+            try:
+                await self._send(topic, json.dumps(payload, ensure_ascii=False, default=str))
+                self.published += 1
+            except Exception as e:
+                self.dropped += 1
+                logger.warning(f"[信号总线] 发布 {topic} 失败: {e}")
+
+    async def _on_message(self, raw: Any):
+        """处理收到的信号消息"""
+        self.received += 1
+        try:
+            payload = json.loads(raw)
+            if not isinstance(payload, dict):
+                raise AlertError("信号必须为JSON对象")
+            action, signal = parse_alert(payload, self.symbols)
+        except (ValueError, AlertError) as e:
+            self.rejected += 1
+            logger.warning(f"[信号总线] 信号无效: {e}")
+            return
+
+        logger.info(f"[信号总线] 收到信号 {signal.symbol} {action} {signal.direction.value}")
+        try:
+            await self.handler(action, signal)
+        except Exception as e:
+            logger.error(f"[信号总线] 处理信号失败: {e}")
+
+    @abstractmethod
+    async def _connect(self):
+        """建立连接"""
+
+    @abstractmethod
+    async def _disconnect(self):
+        """断开连接"""
+
+    @abstractmethod
+    async def _consume(self):
+        """接收信号循环"""
+
+    @abstractmethod
+    async def _send(self, topic: str, data: str):
+        """发送一条事件"""
+
+    def get_stats(self) -> Dict[str, Any]:
+        """获取收发统计"""
+        return {
+            "backend": self.BACKEND,
+            "received": self.received,
+            "rejected": self.rejected,
+            "published": self.published,
+            "dropped": self.dropped,
+        }
+
+
+class RedisSignalBus(SignalBus):
+    """Redis pub/sub 信号总线"""
+
+    BACKEND = "redis"
+
+    def __init__(self, symbols: List[str], handler: Callable[[str, TradeSignal], Awaitable[None]],
+                 url: str, channel: str, prefix: str):
+        """
+        Args:
+            url: Redis连接地址
+            channel: 接收信号的频道
+            prefix: 发布事件的频道前缀
+        """
+        super().__init__(symbols, handler)
+        self.url = url
+        self.channel = channel
+        self.prefix = prefix
+        self._redis = None
+        self._pubsub = None
+
+    async def _connect(self):
+        self._redis = aioredis.from_url(self.url)
+        self._pubsub = self._redis.pubsub()
+        await self._pubsub.subscribe(self.channel)
+        logger.info(f"[信号总线] Redis已连接: {self.url}，订阅 {self.channel}，发布 {self.prefix}.*")
+
+    async def _disconnect(self):
+        if self._pubsub:
+            await self._pubsub.unsubscribe(self.channel)
+            await self._pubsub.close()
+        if self._redis:
+            await self._redis.close()
+
+    async def _consume(self):
+        async for message in self._pubsub.listen():
+            if message.get("type") == "message":
+                await self._on_message(message["data"])
+
+    async def _send(self, topic: str, data: str):
+        await self._redis.publish(f"{self.prefix}.{topic}", data)
+
+
+class ZmqSignalBus(SignalBus):
+    """ZeroMQ 信号总线"""
+
+    BACKEND = "zmq"
+
+    def __init__(self, symbols: List[str], handler: Callable[[str, TradeSignal], Awaitable[None]],
+                 signal_endpoint: str, publish_endpoint: str):
+        """
+        Args:
+            signal_endpoint: 接收信号的PULL套接字绑定地址
+            publish_endpoint: 发布事件的PUB套接字绑定地址
+        """
+        super().__init__(symbols, handler)
+        self.signal_endpoint = signal_endpoint
+        self.publish_endpoint = publish_endpoint
+        self._context = None
+        self._pull = None
+        self._pub = None
+
+    async def _connect(self):
+        self._context = zmq.asyncio.Context()
+        self._pull = self._context.socket(zmq.PULL)
+        self._pull.bind(self.signal_endpoint)
+        self._pub = self._context.socket(zmq.PUB)
+        self._pub.bind(self.publish_endpoint)
+        logger.info(f"[信号总线] ZeroMQ已绑定: 信号 {self.signal_endpoint}，事件 {self.publish_endpoint}")
+
+    async def _disconnect(self):
+        for sock in (self._pull, self._pub):
+            if sock:
+                sock.close(linger=0)
+        if self._context:
+            self._context.term()
+
+    async def _consume(self):
+        while True:
+            await self._on_message(await self._pull.recv())
+
+    async def _send(self, topic: str, data: str):
+        await self._pub.send_multipart([topic.encode(), data.encode()])
+
+
+def create_signal_bus(config, handler: Callable[[str, TradeSignal], Awaitable[None]],
+                      symbols: Optional[List[str]] = None) -> Optional[SignalBus]:
+    """
+    根据配置创建信号总线
+
+    Args:
+        config: 配置对象（signal_bus / redis_url / signal_bus_channel / signal_bus_prefix /
+            zmq_signal_endpoint / zmq_publish_endpoint）
+        handler: 信号处理协程
+        symbols: 接受信号的交易对列表（默认为配置的交易对）
+
+    Returns:
+        Optional[SignalBus]: 信号总线，未启用或依赖未安装时返回None
+    """
+    backend = config.signal_bus
+    symbols = symbols if symbols is not None else config.symbols
+    if backend == "redis":
+        if not REDIS_AVAILABLE:
+            logger.warning("已启用Redis信号总线，但未安装redis依赖，信号总线将被禁用")
+            return None
+        return RedisSignalBus(symbols, handler, config.redis_url,
+                              config.signal_bus_channel, config.signal_bus_prefix)
+    if backend == "zmq":
+        if not ZMQ_AVAILABLE:
+            logger.warning("已启用ZeroMQ信号总线，但未安装pyzmq依赖，信号总线将被禁用")
+            return None
+        return ZmqSignalBus(symbols, handler, config.zmq_signal_endpoint, config.zmq_publish_endpoint)
+    return None
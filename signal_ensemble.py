@@ -0,0 +1,281 @@
+"""
+组合信号模块
+
+多个信号源分别对当前行情投票，按权重合并后决定交易方向（配置格式 "信号源:权重"，逗号分隔）：
+- ma: 均线偏离（价格高于均线超过阈值做空，低于均线做多，与内置信号相同）
+- zscore: 价格相对最近 ZSCORE_PERIOD 个价格的z-score，超过 ZSCORE_THRESHOLD 时反向交易
+- imbalance: 盘口前 IMBALANCE_LEVELS 档不平衡度，买盘占优超过 IMBALANCE_THRESHOLD 做多，卖盘占优做空
+- momentum: 最近 MOMENTUM_PERIOD 个价格的涨跌幅，超过偏离阈值时顺势交易
+- onnx: ONNX模型评分（见 ml_signal.py），评分绝对值超过机器学习信号阈值时按评分方向交易
+合并方式：
+- vote: 每个信号源按权重投票
+- weighted: 投票再乘以信号强度（0-1，刚达到阈值时为0，越过阈值越多越接近1）
+某一方向的得分占全部信号源权重之和的比例（一致度）达到最低一致度且高于反方向时交易，一致度作为信号置信度。
+没有意见（未达到阈值或数据不足）的信号源不投票，但其权重仍计入总权重。
+"""
+
+import math
+from typing import Any, Dict, List, Optional, Tuple
+
+from loguru import logger
+
+from edgex_types import TradeDirection
+from features import extract_features
+from ml_signal import ONNX_AVAILABLE, OnnxModel
+from script_strategy import SignalContext
+
+ENSEMBLE_VOTE = "vote"
+ENSEMBLE_WEIGHTED = "weighted"
+ENSEMBLE_MODES = (ENSEMBLE_VOTE, ENSEMBLE_WEIGHTED)
+
+ZSCORE_PERIOD = 20
+ZSCORE_THRESHOLD = 2.0
+IMBALANCE_LEVELS = 5
+IMBALANCE_THRESHOLD = 0.3
+MOMENTUM_PERIOD = 10
+
+# 信号源投票：(方向, 强度)
+Vote = Tuple[TradeDirection, float]
+
+
+def _strength(value: float, threshold: float) -> float:
+    """越过阈值的程度：刚达到阈值时为0，越过越多越接近1"""
+    return 1 - threshold / abs(value) if threshold > 0 else 1.0
+
+
+class SignalSource:
+    """信号源：vote() 返回投票（方向, 强度），没有意见时返回None"""
+
+    name = ""
+
+    def vote(self, ctx: SignalContext, threshold_scale: float = 1.0) -> Optional[Vote]:
+        """
+        Args:
+            ctx: 行情上下文
+            threshold_scale: 阈值缩放（随波动率状态调整）
+        """
+        raise NotImplementedError
+
+    def get_stats(self) -> Optional[Dict[str, Any]]:
+        return None
+
+
+class MADeviationSource(SignalSource):
+    """均线偏离（均值回归）"""
+
+    name = "ma"
+
+    def __init__(self, period: int, threshold: float):
+        self.period = period
+        self.threshold = threshold
+
+    def vote(self, ctx: SignalContext, threshold_scale: float = 1.0) -> Optional[Vote]:
+        if len(ctx.closes) < 2:
+            return None
+        deviation = ctx.deviation(self.period)
+        threshold = self.threshold * threshold_scale
+        if abs(deviation) <= threshold:
+            return None
+        direction = TradeDirection.SHORT if deviation > 0 else TradeDirection.LONG
+        return direction, _strength(deviation, threshold)
+
+
+class ZScoreSource(SignalSource):
+    """z-score（均值回归）"""
+
+    name = "zscore"
+
+    def __init__(self, period: int = ZSCORE_PERIOD, threshold: float = ZSCORE_THRESHOLD):
+        self.period = period
+        self.threshold = threshold
+
+    def vote(self, ctx: SignalContext, threshold_scale: float = 1.0) -> Optional[Vote]:
+        window = ctx.closes[-self.period:]
+        if len(window) < 3:
+            return None
+        mean = sum(window) / len(window)
+        std = math.sqrt(sum((c - mean) ** 2 for c in window) / (len(window) - 1))
+        if std == 0:
+            return None
+        zscore = (ctx.price - mean) / std
+        threshold = self.threshold * threshold_scale
+        if abs(zscore) <= threshold:
+            return None
+        direction = TradeDirection.SHORT if zscore > 0 else TradeDirection.LONG
+        return direction, _strength(zscore, threshold)
+
+
+class ImbalanceSource(SignalSource):
+    """盘口不平衡度（买盘占优做多）"""
+
+    name = "imbalance"
+
+    def __init__(self, levels: int = IMBALANCE_LEVELS, threshold: float = IMBALANCE_THRESHOLD):
+        self.levels = levels
+        self.threshold = threshold
+
+    def vote(self, ctx: SignalContext, threshold_scale: float = 1.0) -> Optional[Vote]:
+        imbalance = ctx.imbalance(self.levels)
+        if abs(imbalance) <= self.threshold:
+            return None
+        direction = TradeDirection.LONG if imbalance > 0 else TradeDirection.SHORT
+        return direction, _strength(imbalance, self.threshold)
+
+
+class MomentumSource(SignalSource):
+    """动量（顺势）"""
+
+    name = "momentum"
+
+    def __init__(self, threshold: float, period: int = MOMENTUM_PERIOD):
+        self.period = period
+        self.threshold = threshold
+
+    def vote(self, ctx: SignalContext, threshold_scale: float = 1.0) -> Optional[Vote]:
+        if len(ctx.closes) <= self.period or ctx.closes[-self.period - 1] <= 0:
+            return None
+        change = ctx.price / ctx.closes[-self.period - 1] - 1
+        threshold = self.threshold * threshold_scale
+        if abs(change) <= threshold:
+            return None
+        direction = TradeDirection.LONG if change > 0 else TradeDirection.SHORT
+        return direction, _strength(change, threshold)
+
+
+class OnnxSource(SignalSource):
+    """ONNX模型评分"""
+
+    name = "onnx"
+
+    def __init__(self, model: OnnxModel, threshold: float):
+        self.model = model
+        self.threshold = threshold
+
+    def vote(self, ctx: SignalContext, threshold_scale: float = 1.0) -> Optional[Vote]:
+        features = extract_features(ctx)
+        if features is None:
+            return None
+        score = self.model.predict(features)
+        if score is None or abs(score) <= self.threshold:
+            return None
+        direction = TradeDirection.LONG if score > 0 else TradeDirection.SHORT
+        return direction, _strength(score, self.threshold)
+
+    def get_stats(self) -> Optional[Dict[str, Any]]:
+        return self.model.get_stats()
+
+
+SOURCE_NAMES = (MADeviationSource.name, ZScoreSource.name, ImbalanceSource.name, MomentumSource.name, OnnxSource.name)
+
+
+def parse_weights(text: str) -> Dict[str, float]:
+    """
+    解析信号源权重配置（"ma:1,zscore:0.5"，省略权重时为1）
+
+    Raises:
+        ValueError: 信号源未知、重复或权重不大于0
+    """
+    weights: Dict[str, float] = {}
+    for item in text.split(","):
+        if not item.strip():
+            continue
+        name, sep, weight = item.partition(":")
+        name = name.strip().lower()
+        if name not in SOURCE_NAMES:
+            raise ValueError(f"未知的信号源: {name}（可选 {'/'.join(SOURCE_NAMES)}）")
+        if name in weights:
+            raise ValueError(f"信号源重复: {name}")
+        try:
+            value = float(weight) if sep else 1.0
+        except ValueError:
+            raise ValueError(f"信号源权重无效: {item.strip()}")
+        if value <= 0:
+            raise ValueError(f"信号源权重必须大于0: {item.strip()}")
+        weights[name] = value
+    return weights
+
+
+class SignalEnsemble:
+    """多个信号源的加权投票"""
+
+    def __init__(self, sources: List[Tuple[SignalSource, float]], mode: str = ENSEMBLE_WEIGHTED,
+                 min_agreement: float = 0.5):
+        """
+        Args:
+            sources: (信号源, 权重) 列表
+            mode: 合并方式（vote/weighted）
+            min_agreement: 交易所需的最低一致度（0-1）
+        """
+        if mode not in ENSEMBLE_MODES:
+            raise ValueError(f"未知的信号合并方式: {mode}（可选 {'/'.join(ENSEMBLE_MODES)}）")
+        self.sources = sources
+        self.mode = mode
+        self.min_agreement = min_agreement
+        self.total_weight = sum(weight for _, weight in sources)
+        self.stats = {"evaluations": 0, "signals": 0, "below_agreement": 0, "conflicts": 0}
+        self.votes: Dict[str, Dict[str, int]] = {source.name: {"long": 0, "short": 0} for source, _ in sources}
+        self.last: Dict[str, Dict[str, Any]] = {}   # 交易对 -> 最近一次投票详情
+
+    @classmethod
+    def from_config(cls, config, period: int, threshold: float) -> "SignalEnsemble":
+        """
+        Args:
+            config: 配置
+            period: 均线周期
+            threshold: 偏离阈值（均线偏离和动量信号源使用）
+        """
+        factories = {
+            MADeviationSource.name: lambda: MADeviationSource(period, threshold),
+            ZScoreSource.name: ZScoreSource,
+            ImbalanceSource.name: ImbalanceSource,
+            MomentumSource.name: lambda: MomentumSource(threshold),
+            OnnxSource.name: lambda: OnnxSource(OnnxModel(config.ml_model_path), config.ml_signal_threshold),
+        }
+        weights = parse_weights(config.signal_ensemble)
+        if OnnxSource.name in weights and not ONNX_AVAILABLE:
+            logger.warning("组合信号包含 onnx 信号源，但未安装onnxruntime依赖，该信号源将被禁用")
+            del weights[OnnxSource.name]
+        return cls([(factories[name](), weight) for name, weight in weights.items()],
+                   config.signal_ensemble_mode, config.signal_min_agreement)
+
+    def evaluate(self, symbol: str, ctx: SignalContext, threshold_scale: float = 1.0) -> Optional[Vote]:
+        """
+        合并各信号源的投票
+
+        Returns:
+            Optional[Vote]: (方向, 一致度)，未达到最低一致度或两个方向得分相同时返回None
+        """
+        self.stats["evaluations"] += 1
+        scores = {TradeDirection.LONG: 0.0, TradeDirection.SHORT: 0.0}
+        detail: Dict[str, Any] = {}
+        for source, weight in self.sources:
+            vote = source.vote(ctx, threshold_scale)
+            if vote is None:
+                detail[source.name] = None
+                continue
+            direction, strength = vote
+            scores[direction] += weight * (strength if self.mode == ENSEMBLE_WEIGHTED else 1.0)
+            self.votes[source.name][direction.value.lower()] += 1
+            detail[source.name] = f"{direction.value.lower()}:{strength:.2f}"
+        self.last[symbol] = detail
+
+        long_score, short_score = scores[TradeDirection.LONG], scores[TradeDirection.SHORT]
+        if long_score == short_score:
+            if long_score > 0:
+                self.stats["conflicts"] += 1
+            return None
+        direction = TradeDirection.LONG if long_score > short_score else TradeDirection.SHORT
+        agreement = max(long_score, short_score) / self.total_weight if self.total_weight > 0 else 0.0
+        if agreement < self.min_agreement:
+            self.stats["below_agreement"] += 1
+            return None
+        self.stats["signals"] += 1
+        return direction, agreement
+
+    def describe(self) -> str:
+        sources = ", ".join(f"{source.name}:{weight:g}" for source, weight in self.sources)
+        return f"{sources}（{self.mode}，最低一致度 {self.min_agreement:.0%}）"
+
+    def get_stats(self) -> Dict[str, Any]:
+        models = {source.name: source.get_stats() for source, _ in self.sources if source.get_stats() is not None}
+        return {**self.stats, "sources": self.describe(), "votes": self.votes, "last": dict(self.last), "models": models}
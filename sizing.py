@@ -0,0 +1,103 @@
+"""
+开仓数量模块
+
+开仓数量按配置的单位换算为币的数量（配置格式 "单位:数值"）：
+- equity: 资金比例（如 equity:0.05，名义价值为资金的5%）
+- notional: 固定名义价值（USDT，如 notional:200）
+- contracts: 固定数量（币，如 contracts:0.01）
+- risk: 每笔风险占资金的比例（如 risk:0.01），按止损距离换算：数量 = 资金 × 比例 / |开仓价 - 止损价|，
+  止损价未知时按止损百分比计算止损距离
+未配置时按 base_position_size 的资金比例计算（与 equity 相同）
+"""
+
+from decimal import Decimal
+
+SIZING_EQUITY = "equity"
+SIZING_NOTIONAL = "notional"
+SIZING_CONTRACTS = "contracts"
+SIZING_RISK = "risk"
+SIZING_UNITS = (SIZING_EQUITY, SIZING_NOTIONAL, SIZING_CONTRACTS, SIZING_RISK)
+
+
+class PositionSizing:
+    """开仓数量的单位和数值"""
+
+    def __init__(self, unit: str, value: float):
+        """
+        Args:
+            unit: 单位（equity/notional/contracts/risk）
+            value: 数值（比例、USDT或币的数量）
+
+        Raises:
+            ValueError: 单位未知或数值不大于0，比例超过1
+        """
+        if unit not in SIZING_UNITS:
+            raise ValueError(f"未知的仓位单位: {unit}（可选 {'/'.join(SIZING_UNITS)}）")
+        if value <= 0:
+            raise ValueError(f"仓位数值必须大于0: {value}")
+        if unit in (SIZING_EQUITY, SIZING_RISK) and value > 1:
+            raise ValueError(f"{unit} 仓位为资金比例，必须在0-1之间: {value}")
+        self.unit = unit
+        self.value = Decimal(str(value))
+
+    @classmethod
+    def parse(cls, text: str, default_equity_pct: float) -> "PositionSizing":
+        """
+        解析仓位配置
+
+        Args:
+            text: "单位:数值"（空字符串为按资金比例 default_equity_pct）
+            default_equity_pct: 未配置时的资金比例
+
+        Raises:
+            ValueError: 格式错误
+        """
+        if not text.strip():
+            return cls(SIZING_EQUITY, default_equity_pct)
+        unit, sep, value = text.partition(":")
+        if not sep:
+            raise ValueError(f"仓位配置格式应为 单位:数值，实际为 {text}")
+        try:
+            amount = float(value)
+        except ValueError:
+            raise ValueError(f"仓位数值无效: {value}")
+        return cls(unit.strip().lower(), amount)
+
+    @classmethod
+    def from_config(cls, config) -> "PositionSizing":
+        return cls.parse(config.position_size, config.base_position_size)
+
+    def size(self, capital: Decimal, price: Decimal, stop_distance: Decimal) -> Decimal:
+        """
+        换算开仓数量（币，未按最小下单量和步长调整）
+
+        Args:
+            capital: 可用于本策略的资金（USDT）
+            price: 开仓参考价格
+            stop_distance: 止损距离（价格差，risk 单位使用）
+
+        Raises:
+            ValueError: 价格或止损距离不大于0
+        """
+        if price <= 0:
+            raise ValueError("当前价格必须大于零")
+        if self.unit == SIZING_CONTRACTS:
+            return self.value
+        if self.unit == SIZING_NOTIONAL:
+            return self.value / price
+        if capital <= 0:
+            raise ValueError("账户余额必须大于零")
+        if self.unit == SIZING_EQUITY:
+            return capital * self.value / price
+        if stop_distance <= 0:
+            raise ValueError("止损距离必须大于零")
+        return capital * self.value / stop_distance
+
+    def describe(self) -> str:
+        if self.unit == SIZING_EQUITY:
+            return f"资金的 {float(self.value):.2%}"
+        if self.unit == SIZING_NOTIONAL:
+            return f"名义价值 {float(self.value):g} USDT"
+        if self.unit == SIZING_CONTRACTS:
+            return f"固定数量 {float(self.value):g}"
+        return f"每笔风险为资金的 {float(self.value):.2%}（按止损距离换算）"
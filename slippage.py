@@ -0,0 +1,107 @@
+"""
+执行滑点统计模块
+
+对每个已成交订单，比较信号时刻的到达价格（盘口中间价，无盘口时为最新价）与成交均价，
+计算滑点（bps，正值表示成本：买入高于到达价 / 卖出低于到达价），
+按交易对、开仓/平仓汇总，用于量化执行成本。
+"""
+
+from typing import Dict, List, Optional, Any
+
+from edgex_types import OrderSide
+
+
+class SlippageRecord:
+    """单个订单的滑点记录"""
+
+    def __init__(self, order_id: str, symbol: str, side: OrderSide, reduce_only: bool,
+                 arrival_price: float, avg_fill_price: float, size: float, timestamp: int):
+        self.order_id = order_id
+        self.symbol = symbol
+        self.side = side
+        self.reduce_only = reduce_only
+        self.arrival_price = arrival_price
+        self.avg_fill_price = avg_fill_price
+        self.size = size
+        self.timestamp = timestamp
+
+        sign = 1 if side == OrderSide.BUY else -1
+        self.slippage_bps = sign * (avg_fill_price - arrival_price) / arrival_price * 10000
+        # 滑点成本（USDT）
+        self.cost = sign * (avg_fill_price - arrival_price) * size
+
+
+class SlippageTracker:
+    """执行滑点统计"""
+
+    def __init__(self, max_records: int = 1000):
+        """
+        Args:
+            max_records: 保留的最近滑点记录数
+        """
+        self.max_records = max_records
+        self.records: List[SlippageRecord] = []
+
+    def record(self, order_id: str, symbol: str, side: OrderSide, reduce_only: bool,
+               arrival_price: Optional[float], avg_fill_price: float, size: float,
+               timestamp: int) -> Optional[SlippageRecord]:
+        """
+        记录一个已成交订单
+
+        Args:
+            order_id: 订单ID
+            symbol: 交易对
+            side: 买卖方向
+            reduce_only: 是否为平仓订单
+            arrival_price: 信号时刻的到达价格
+            avg_fill_price: 成交均价
+            size: 成交数量
+            timestamp: 成交时间（毫秒）
+
+        Returns:
+            Optional[SlippageRecord]: 滑点记录，价格无效时返回None
+        """
+        if not arrival_price or arrival_price <= 0 or avg_fill_price <= 0 or size <= 0:
+            return None
+
+        record = SlippageRecord(order_id, symbol, side, reduce_only, arrival_price, avg_fill_price, size, timestamp)
+        self.records.append(record)
+        if len(self.records) > self.max_records:
+            self.records = self.records[-self.max_records:]
+        return record
+
+    def estimate(self, symbol: str, reduce_only: bool, recent: int = 50) -> Optional[float]:
+        """
+        交易对最近开仓（或平仓）订单的平均滑点（bps）
+
+        Returns:
+            Optional[float]: 没有记录时为None
+        """
+        records = [r for r in self.records if r.symbol == symbol and r.reduce_only == reduce_only][-recent:]
+        return sum(r.slippage_bps for r in records) / len(records) if records else None
+
+    @staticmethod
+    def _aggregate(records: List[SlippageRecord]) -> Dict[str, Any]:
+        """汇总一组滑点记录"""
+        if not records:
+            return {"orders": 0, "avg_bps": 0.0, "weighted_bps": 0.0, "max_bps": 0.0, "cost": 0.0}
+
+        notional = sum(r.arrival_price * r.size for r in records)
+        return {
+            "orders": len(records),
+            "avg_bps": sum(r.slippage_bps for r in records) / len(records),
+            # 按名义价值加权
+            "weighted_bps": sum(r.slippage_bps * r.arrival_price * r.size for r in records) / notional,
+            "max_bps": max(r.slippage_bps for r in records),
+            "cost": sum(r.cost for r in records),
+        }
+
+    def get_summary(self) -> Dict[str, Any]:
+        """获取滑点统计（总体、开仓/平仓、各交易对）"""
+        symbols = sorted({r.symbol for r in self.records})
+        return {
+            **self._aggregate(self.records),
+            "entries": self._aggregate([r for r in self.records if not r.reduce_only]),
+            "exits": self._aggregate([r for r in self.records if r.reduce_only]),
+            "symbols": {s: self._aggregate([r for r in self.records if r.symbol == s]) for s in symbols},
+        }
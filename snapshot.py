@@ -0,0 +1,84 @@
+"""
+策略状态快照模块
+
+策略主循环定期把对外展示的状态（性能统计、持仓、权益历史等）复制为不可变快照并发布到快照通道，
+性能监控、事件流等读取方只读取最新快照，不直接访问策略内部字段，也不会在读取过程中看到交易路径改到一半的状态：
+- 通道只保留最新一份快照（类似watch通道），发布不阻塞、不排队，慢的读取方只会跳过中间版本
+- 读取方可按版本号等待下一次发布
+"""
+
+import asyncio
+import time
+from dataclasses import dataclass, field
+from typing import Any, Dict, Optional, Tuple
+
+from edgex_types import Position
+
+
+@dataclass(frozen=True)
+class StrategySnapshot:
+    """策略状态快照（发布后不再修改）"""
+    name: str
+    timestamp: float
+    stats: Dict[str, Any]                          # get_performance_stats() 的结果
+    positions: Tuple[Position, ...] = ()           # 持仓副本
+    balance: float = 0.0
+    daily_volume: float = 0.0                      # 当日成交额（USDT）
+    equity_history: Tuple[float, ...] = ()         # 权益历史
+    total_pnl: float = 0.0                         # 累计已实现盈亏（含已转存的记录）
+    total_trades: int = 0                          # 累计交易次数（含已转存的记录）
+    trade_records: int = 0                         # 内存中的交易记录条数
+    spilled_trade_records: int = 0                 # 已转存到文件的交易记录条数
+    shadow: Optional[Dict[str, Any]] = None        # 影子交易对比（未启用时为None）
+    allocated: bool = False                        # 是否由多策略资金分配管理
+    trading_timezone: str = "UTC"
+    report_interval: int = 300                     # 性能报告间隔（秒）
+    version: int = field(default=0, compare=False)
+
+
+class SnapshotChannel:
+    """只保留最新快照的发布通道"""
+
+    def __init__(self):
+        self._latest: Optional[StrategySnapshot] = None
+        self._version = 0
+        self._changed = asyncio.Event()
+
+    @property
+    def latest(self) -> Optional[StrategySnapshot]:
+        """最新快照（尚未发布时为None）"""
+        return self._latest
+
+    @property
+    def version(self) -> int:
+        return self._version
+
+    def publish(self, snapshot: StrategySnapshot):
+        """发布新快照（替换上一份，唤醒等待中的读取方）"""
+        self._version += 1
+        object.__setattr__(snapshot, "version", self._version)
+        self._latest = snapshot
+        self._changed.set()
+        self._changed = asyncio.Event()
+
+    async def wait_newer(self, version: int = 0, timeout: Optional[float] = None) -> Optional[StrategySnapshot]:
+        """
+        等待版本号大于 version 的快照
+
+        Args:
+            version: 读取方已看到的版本号（0为等待第一份快照）
+            timeout: 最长等待时间（秒，None为一直等待）
+
+        Returns:
+            Optional[StrategySnapshot]: 最新快照，超时时返回当前快照（可能为None）
+        """
+        if self._version <= version:
+            try:
+                await asyncio.wait_for(self._changed.wait(), timeout)
+            except asyncio.TimeoutError:
+                pass
+        return self._latest
+
+    def age(self) -> Optional[float]:
+        """最新快照距今的秒数（尚未发布时为None）"""
+        return time.time() - self._latest.timestamp if self._latest else None
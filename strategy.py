@@ -29,18 +29,74 @@
 
 import asyncio
 import math
+import os
 import time
+from collections import deque
 from decimal import Decimal, ROUND_HALF_UP, ROUND_DOWN
-from typing import List, Optional, Dict, Any
+from typing import Deque, List, Optional, Dict, Any, Set, Tuple
 from datetime import datetime, timedelta
 from loguru import logger
 from websocket_client import RealTimePriceStream
 
 from edgex_types import (
     PriceData, TradeSignal, Position, TradeDirection, 
-    AccountInfo, Order, OrderSide, OrderType, TradeRecord
+    AccountInfo, Order, OrderSide, OrderType, TradeRecord, Fill, PositionMode, position_key,
+    MarkPriceData, TriggerPriceType, PositionUpdate, FundingPayment, CollateralAsset
 )
-from edgex_client import EdgeXClient
+from edgex_client import EdgeXClient, OrderStateUnknownError
+from tracing import traced, annotate
+from shadow import ShadowTrader
+from order_tracker import UNCONFIRMED, OrderTracker, TrackedOrder
+from pre_trade import FunctionCheck
+from execution import ExecutionPolicy, OrderRouter
+from risk_model import PortfolioRiskModel
+from regime import RegimeClassifier, RegimeParams, load_profiles
+from volatility import estimator_factory
+from liquidity_filter import LiquidityFilter
+from activity_filter import ActivityFilter
+from basis import BasisTracker
+from funding_window import FundingWindow
+from fees import FeeSchedule
+from ev_gate import EvEstimate, EvGate, execution_cost
+from universe import MarketScanner, UniverseRotation
+from inventory import InventorySkew
+from dual_quote import DualQuoteManager
+from wash_trade import WashTradeGuard
+from slippage import SlippageTracker
+from tca import MARKET_FILE, TcaRecorder
+from vwap import VolumeProfile, VwapExecutor
+from webhook_server import ACTION_OPEN, WebhookServer
+from signal_bus import TOPIC_FILLS, TOPIC_POSITIONS, create_signal_bus
+from event_stream import EVENT_ORDER, EVENT_PERFORMANCE, EVENT_TRADE, create_event_streamer
+from script_strategy import ScriptedSignal, SignalContext
+from signal_ensemble import SignalEnsemble
+from benchmark import BenchmarkTracker
+from rolling_performance import RollingPerformance
+from feature_recorder import PARQUET_AVAILABLE, FeatureRecorder, parse_horizons
+from drawdown import DrawdownThrottle, parse_levels
+from audit_log import AUDIT_STATE
+from oco import OcoManager
+from bars import BarBuilder, create_bar_builder
+from rolling import RollingWindow
+from trade_store import TradeRecordBuffer
+from endpoints import EndpointPool, probe_ws
+from runtime import parse_cpu_list
+from latency import LATENCY_LOOP_ITERATION, LATENCY_SIGNAL_TO_ORDER
+from reconcile import StateStore, plan_reconciliation
+from warmup import StartupWarmup
+from trading_status import STATUS_LABELS, SymbolStatus, TradingStatusMonitor, parse_error_codes
+from daily_stats import DailyStats
+from cooldown import LossCircuitBreaker, SymbolCooldowns
+from alerts import AlertNotifier
+from discord_notifier import DiscordNotifier
+from email_notifier import EmailNotifier
+from error_reporting import capture_error
+from notify_throttle import NotificationThrottle
+from price_source import create_price_source
+from price_guard import PriceGuard
+from pnl_reconstruction import PnlCrossCheck
+from sizing import PositionSizing
+from snapshot import SnapshotChannel, StrategySnapshot
 
 
 class StrategyConfig:
@@ -59,10 +115,12 @@ class StrategyConfig:
         "10000004": Decimal("0.01"),    # BNB
     }
     
-    def __init__(self):
-        # 仓位配置
-        self.base_position_size = Decimal("0.05")  # 5%仓位（每个币种）
-        self.leverage = 50  # 杠杆倍数
+    def __init__(self, leverage: int = 50, symbol_leverage: Optional[Dict[str, int]] = None,
+                 sizing: Optional[PositionSizing] = None):
+        # 仓位配置（开仓数量的单位和数值，默认资金的5%，每个币种）
+        self.sizing = sizing or PositionSizing("equity", 0.05)
+        self.leverage = leverage  # 默认杠杆倍数
+        self.symbol_leverage = symbol_leverage or {}  # 各交易对杠杆倍数
         
         # 止盈止损
         self.take_profit_pct = Decimal("0.004")  # 0.4%止盈
@@ -87,72 +145,454 @@ class StrategyConfig:
             Decimal: 最小下单量
         """
         return self.MIN_ORDER_SIZES.get(symbol, Decimal("0.05"))
+    
+    def get_leverage(self, symbol: str) -> int:
+        """
+        获取指定交易对的杠杆倍数
+        
+        Args:
+            symbol: 交易对名称
+            
+        Returns:
+            int: 杠杆倍数
+        """
+        return self.symbol_leverage.get(symbol, self.leverage)
 
 
 class HighFrequencyStrategy:
     """多币种高频交易策略"""
     
+    # 策略名称（TCA报告按策略汇总）
+    STRATEGY_NAME = "multi_symbol_hf"
+    
+    # 下单请求超时后订单状态未知，在此期间不对同一交易对/持仓重新下单（等待成交回报）
+    UNCERTAIN_ORDER_HOLD_SECONDS = 30
+    
     # 市场配置（加密货币专用）
     MINUTES_PER_HOUR = 60
     HOURS_PER_DAY = 24
     DAYS_PER_YEAR = 365
     TRADING_MINUTES_PER_DAY = MINUTES_PER_HOUR * HOURS_PER_DAY
     
-    def __init__(self, config):
+    # 外部信号反手时等待反向持仓平仓完成的时间（秒）
+    CLOSE_CONFIRM_TIMEOUT = 5.0
+    
+    # 平仓订单终结后等待成交回报更新持仓的时间（秒），之后仍有持仓则重新提交剩余数量
+    CLOSE_FILL_GRACE_SECONDS = 1.0
+    
+    # 启动对账失败或按配置停止交易时，重新对账的间隔（秒）
+    RECONCILE_RETRY_SECONDS = 30
+    
+    # 状态快照的发布间隔（秒）
+    SNAPSHOT_SECONDS = 1.0
+    
+    def __init__(self, config, name: Optional[str] = None):
         """
         初始化策略
         
         Args:
             config: 配置对象，包含API密钥、交易对列表等信息
+            name: 策略名称（多策略运行时区分各实例，默认 STRATEGY_NAME）
         """
         self.config = config
-        self.strategy_config = StrategyConfig()
+        self.name = name or self.STRATEGY_NAME
+        # 当前交易对集合（动态交易对轮换时增减，不修改配置中的交易对列表）
+        self.symbols: List[str] = list(config.symbols)
+        self.strategy_config = StrategyConfig(config.leverage, config.symbol_leverage, PositionSizing.from_config(config))
         self.client = EdgeXClient(config)
         
+        # 下单前检查：客户端已包含紧急停止、步长取整和排队上限，这里追加依赖账户状态的检查
+        self.client.pre_trade.add(FunctionCheck("min_notional", self._check_min_notional))
+        self.client.pre_trade.add(FunctionCheck("margin", self._check_margin))
+        self.client.pre_trade.add(FunctionCheck("risk_limit", self._check_risk_limit))
+        
+        # 异常价格保护：偏离过大的价格不计入价格序列，偏离参考价格的信号和订单价格被拒绝
+        self.price_guard = PriceGuard(config.max_price_deviation_pct) if config.max_price_deviation_pct > 0 else None
+        if self.price_guard:
+            self.client.pre_trade.add(FunctionCheck("price_deviation", self._check_price_deviation))
+        
+        # 对敲防护：与自己的反方向成交保持时间和价格距离，拒绝会与自己挂单成交的订单，审计疑似自成交
+        self.wash_guard = WashTradeGuard(
+            min_interval_ms=config.wash_min_interval_ms,
+            min_price_bps=config.wash_min_price_bps,
+            self_match_prevention=config.wash_self_match_prevention,
+            window_seconds=config.wash_price_window_seconds,
+            audit_window_ms=config.wash_audit_window_ms,
+            report_path=config.wash_report_path
+        ) if config.wash_guard_enabled else None
+        if self.wash_guard:
+            self.client.pre_trade.add(FunctionCheck("wash_trade", self._check_wash_trade))
+        
+        # 交易链路延迟直方图（未启用时为None，下单确认耗时由客户端记录）
+        self.latency = self.client.latency
+        self._signal_started: Dict[str, float] = {}  # 交易对 -> 本轮信号生成时间（perf_counter）
+        
         # 账户状态
         self.balance = Decimal("0")
         self.available_balance = Decimal("0")
-        self.positions: Dict[str, Position] = {}
+        self.collaterals: Dict[str, CollateralAsset] = {}  # 资产名称 -> 抵押资产（余额按报告币种汇总到 balance）
+        self.positions: Dict[str, Position] = {}  # 索引键见 position_key
+        self.position_mode = PositionMode(config.position_mode)
+        # 私有频道推送的持仓与资金费（实盘）
+        self.exchange_positions: Dict[str, PositionUpdate] = {}  # 索引键见 position_key
+        self.funding_totals: Dict[str, float] = {}  # 交易对 -> 累计资金费（正数为收到）
+        self.position_mismatches = 0
         
         # 交易记录（按交易对分类）
-        self.trade_records: List[TradeRecord] = []
-        self.equity_history: List[Decimal] = []
+        self.trade_records = TradeRecordBuffer(config.trade_records_capacity, config.trade_records_path, self.name)
+        self.equity_history: Deque[Decimal] = deque(maxlen=config.equity_history_capacity)
+        self.benchmark: Optional[BenchmarkTracker] = BenchmarkTracker(
+            self.symbols, config.benchmark_sample_seconds
+        ) if config.benchmark_sample_seconds > 0 else None
+        self.rolling_performance = RollingPerformance(config.performance_sample_seconds)
+        # 当日开仓次数、成交额和盈亏（按配置时区日切，实盘时保存在状态目录中，重启后同一交易日继续累计）
+        self.daily = DailyStats(
+            config.trading_timezone,
+            os.path.join(config.state_dir, f"{self.name}.daily.json") if config.state_dir and not config.paper_trading else ""
+        )
+        # 连续亏损后的交易对冷却和全局熔断（触发时发送告警）
+        self.cooldowns = SymbolCooldowns(config.loss_cooldown_seconds, config.loss_cooldown_after)
+        self.loss_breaker = LossCircuitBreaker(config.loss_breaker_streak, config.loss_breaker_seconds)
+        # Discord通知（告警、成交）
+        self.notify_throttle = NotificationThrottle.from_config(config)
+        self.discord = DiscordNotifier.from_config(config, self.notify_throttle) if config.discord_webhook_url else None
+        self.email = EmailNotifier.from_config(config) if config.smtp_host else None
+        self.alerts = AlertNotifier(config.notifier_webhook_url, self.discord, self.email, self.notify_throttle)
+        self._liquidation_alerted: Set[str] = set()  # 已发送强平风险告警的持仓（距离恢复后清除）
+        self._kill_switch_alerted = False
         self.price_history: Dict[str, List[PriceData]] = {}  # 价格历史记录
+        self.rolling_closes: Dict[str, RollingWindow] = {}  # 均线窗口（随价格序列逐笔更新）
+        self.mark_prices: Dict[str, MarkPriceData] = {}  # 标记价格/指数价格
+        self._last_mark_poll = 0.0
+        
+        # 多个WebSocket地址：按延迟选择，断线时切换（REST端点由客户端管理）
+        self.ws_endpoints: Optional[EndpointPool] = EndpointPool(
+            "WebSocket", config.ws_endpoints,
+            failover_errors=config.endpoint_failover_errors,
+            cooldown_seconds=config.endpoint_cooldown_seconds
+        ) if config.ws_endpoints else None
+        self._last_endpoint_probe = 0.0
+        self._ws_switch_task: Optional[asyncio.Task] = None
+        self._ws_reconnect_failures = 0
+        
+        # 逐笔成交K线构建（ticker模式为空）
+        self.bar_builders: Dict[str, BarBuilder] = (
+            {s: create_bar_builder(config.bar_type, config.bar_size) for s in self.symbols}
+            if config.bar_type != "ticker" else {}
+        )
+        # 动态交易对：定期扫描市场并轮换交易对集合，离开集合的交易对停止开仓，持仓平掉后取消订阅
+        self.scanner: Optional[MarketScanner] = None
+        self.universe: Optional[UniverseRotation] = None
+        if config.universe_size > 0:
+            self.scanner = MarketScanner(self.client, config.universe_rank, config.universe_min_value_24h)
+            self.universe = UniverseRotation(config.universe_size, config.universe_buffer)
+        self._last_universe_scan = 0.0
+        self._universe_task: Optional[asyncio.Task] = None
+        self._winding_down: Dict[str, float] = {}  # 交易对 -> 离开集合的时间
+        
+        # ticker模式下进入价格序列的参考价格来源
+        self.price_source = create_price_source(config.signal_price_source, config.fair_price_levels)
         
-        # 各交易对的最后交易时间
+        # 各交易对的最后开仓时间（毫秒）
         self.last_trade_times: Dict[str, int] = {}
         
+        # 事件触发评估：新价格到达时唤醒主循环，只评估价格有更新的交易对（未启用时为None，按固定间隔评估）
+        self._evaluation_event: Optional[asyncio.Event] = asyncio.Event() if config.event_driven_evaluation else None
+        self._dirty_symbols: Set[str] = set()
+        self._last_full_evaluation = 0.0
+        
+        # 止盈止损监控：每个tick（最新价或标记价格更新）检查该交易对持仓，不等待策略评估（未启用时为None）
+        self._exit_event: Optional[asyncio.Event] = asyncio.Event() if config.exit_watcher_enabled else None
+        self._exit_symbols: Set[str] = set()
+        self._tick_prices: Dict[str, float] = {}  # 交易对 -> 最新成交价（ticker）
+        self._exit_watcher_task: Optional[asyncio.Task] = None
+        self._closing_now: Set[str] = set()  # 正在提交平仓订单的持仓索引键
+        
         # WebSocket价格流
         self.price_stream: Optional[RealTimePriceStream] = None
         self.contract_ids: Dict[str, str] = {}  # 交易对到合约ID的映射
         
+        # 待成交开仓信号的止盈止损（成交回报建仓时使用）
+        self.pending_exits: Dict[str, TradeSignal] = {}
+        self._loop: Optional[asyncio.AbstractEventLoop] = None
+        
+        # 影子交易：并行运行理想成交的模拟策略，对比决策与成交
+        self.shadow: Optional[ShadowTrader] = ShadowTrader() if config.shadow_mode else None
+        
+        # 波动率估计器（风险模型和状态识别共用）
+        vol_estimator = estimator_factory(
+            config.volatility_estimator,
+            ewma_lambda=config.vol_ewma_lambda,
+            garch_alpha=config.garch_alpha,
+            garch_beta=config.garch_beta
+        )
+        
+        # 组合风险模型：按目标波动率缩放新开仓位
+        self.risk_model: Optional[PortfolioRiskModel] = None
+        if config.vol_targeting_enabled:
+            self.risk_model = PortfolioRiskModel(
+                symbols=self.symbols,
+                target_volatility=config.target_volatility,
+                ewma_lambda=config.vol_ewma_lambda,
+                sample_seconds=config.vol_sample_seconds,
+                max_scale=config.vol_max_scale,
+                estimator=vol_estimator
+            )
+        
+        # 波动率状态识别：按状态切换参数或暂停开仓
+        self.regime: Optional[RegimeClassifier] = None
+        if config.regime_detection_enabled:
+            self.regime = RegimeClassifier(
+                sample_seconds=config.regime_sample_seconds,
+                low_percentile=config.regime_low_percentile,
+                high_percentile=config.regime_high_percentile,
+                stand_down=config.regime_stand_down,
+                estimator=vol_estimator,
+                profiles=load_profiles(config.regime_profiles_file) if config.regime_profiles_file else None,
+                trend_threshold=config.regime_trend_threshold
+            )
+        
+        # 回撤仓位调节：按当前回撤平滑缩小新开仓位
+        self.drawdown_throttle: Optional[DrawdownThrottle] = None
+        if config.drawdown_throttle_enabled:
+            self.drawdown_throttle = DrawdownThrottle(parse_levels(config.drawdown_scale_levels))
+        
+        # 库存偏斜：按所有持仓的净方向敞口调整新开仓位，使净敞口围绕零均值回归
+        self.inventory_skew: Optional[InventorySkew] = None
+        if config.inventory_skew_enabled:
+            self.inventory_skew = InventorySkew(config.inventory_max_exposure, config.inventory_skew_strength)
+        
+        # 开仓前盘口过滤：价差过宽或对手盘深度不足时跳过开仓
+        self.liquidity_filter: Optional[LiquidityFilter] = None
+        if config.liquidity_filter_enabled:
+            self.liquidity_filter = LiquidityFilter(
+                max_spread_bps=config.max_spread_bps,
+                depth_levels=config.liquidity_depth_levels
+            )
+        
+        # 持仓量与成交量过滤：持仓价值或24小时成交额过低的合约不开仓
+        self.activity_filter: Optional[ActivityFilter] = None
+        if config.activity_filter_enabled:
+            self.activity_filter = ActivityFilter(
+                min_open_interest=config.min_open_interest_usd,
+                min_volume_24h=config.min_volume_24h_usd,
+                max_age_seconds=config.activity_poll_seconds * 3
+            )
+        self._last_activity_poll = 0.0
+        
+        # 基差监控：永续价格相对指数价格的基差作为信号特征和报告序列，可选在基差拉伸过度时跳过开仓
+        self.basis: Optional[BasisTracker] = BasisTracker(
+            window=config.basis_window,
+            sample_seconds=config.basis_sample_seconds,
+            max_basis_bps=config.basis_max_bps,
+            max_zscore=config.basis_max_zscore,
+            max_age_seconds=max(config.mark_price_poll_seconds * 3, config.basis_sample_seconds * 3),
+            series_path=config.basis_series_path
+        ) if config.basis_enabled else None
+        
+        # 资金费结算规避：结算前窗口内需要支付资金费的方向延后开仓（可选同时平仓）
+        self.funding_window: Optional[FundingWindow] = FundingWindow(
+            window_seconds=config.funding_avoid_window_seconds,
+            min_rate=config.funding_avoid_min_rate,
+            action=config.funding_avoid_action
+        ) if config.funding_avoid_enabled else None
+        self._last_funding_poll = 0.0
+        
+        # 手续费等级：定期获取账户在各合约上的手续费率，用于预期收益检查和模拟成交
+        self.fees = FeeSchedule(config.min_edge_fee_multiple)
+        self._last_fee_refresh = 0.0
+        
+        # 开仓期望值检查：信号优势扣除手续费、价差和滑点后低于阈值时跳过开仓
+        self.ev_gate: Optional[EvGate] = EvGate(
+            min_ev_bps=config.ev_min_bps,
+            default_slippage_bps=config.ev_default_slippage_bps,
+            log_path=config.ev_log_path
+        ) if config.ev_gate_enabled else None
+        
+        # 订单跟踪与执行路由（开仓订单按执行策略下单）
+        self.order_tracker = OrderTracker(default_ttl_ms=config.order_ttl_ms or None)
+        self.router = OrderRouter(
+            self.client, self.order_tracker, config,
+            book_provider=lambda sym: self.price_stream.get_order_book(sym) if self.price_stream else None
+        )
+        self._execution_tasks: Dict[str, asyncio.Task] = {}
+        
+        # 执行滑点：成交均价相对信号时刻到达价格
+        self.slippage = SlippageTracker()
+        self.order_tracker.add_done_callback(self._on_order_done)
+        
+        # 交易成本分析数据记录
+        self.tca: Optional[TcaRecorder] = TcaRecorder(config.tca_dir) if config.tca_enabled else None
+        
+        # VWAP分批平仓（成交量分布来自TCA记录的逐笔成交）
+        self.vwap: Optional[VwapExecutor] = None
+        self._unwind_tasks: Dict[str, asyncio.Task] = {}
+        if config.vwap_unwind_enabled:
+            profiles = VolumeProfile.load(os.path.join(config.tca_dir, MARKET_FILE))
+            self.vwap = VwapExecutor(
+                self.client, self.order_tracker, profiles,
+                duration_seconds=config.vwap_duration_seconds,
+                slices=config.vwap_slices
+            )
+        
+        # OCO止盈止损：止盈腿挂在盘口，止损腿由客户端触发
+        self.oco: Optional[OcoManager] = OcoManager(self.client, self.order_tracker) if config.oco_exits_enabled else None
+        
+        # 双边挂单（Delta中性刷量）：不按信号开仓，在中间价两侧各挂一笔限价单
+        self.dual_quote: Optional[DualQuoteManager] = DualQuoteManager(
+            self.client, self.order_tracker,
+            spread_bps=config.dual_quote_spread_bps,
+            requote_bps=config.dual_quote_requote_bps,
+            max_imbalance=config.dual_quote_max_imbalance
+        ) if config.dual_quote_enabled else None
+        
+        # 保证金检查：账户同步时持仓占用的保证金，以及因保证金不足缩小/拒绝的开仓次数
+        self._synced_margin = Decimal("0")
+        self.margin_stats = {"shrunk": 0, "rejected": 0}
+        
+        # 杠杆与配置不一致（或无法确认）的交易对，禁止开仓
+        self.leverage_blocked: Dict[str, str] = {}
+        
+        # 手动操作（交易控制台）：手动暂停开仓的交易对，各类操作次数
+        self.manual_paused: Set[str] = set()
+        self.manual_stats = {"orders": 0, "cancels": 0, "closes": 0, "leverage": 0, "pauses": 0}
+        
+        # 交易所维护或合约暂停交易的交易对（合约元数据或下单错误），恢复后自动继续
+        self.trading_status = TradingStatusMonitor(parse_error_codes(config.halt_error_codes),
+                                                   config.halt_retry_seconds)
+        
+        # 各持仓在途的平仓订单ID（成交回报到达前不重复平仓）
+        self._closing_orders: Dict[str, str] = {}
+        
+        # 平仓确认：平仓订单终结后检查持仓是否归零，未归零时按剩余数量重试
+        self._close_confirms: Dict[str, asyncio.Task] = {}
+        self.close_stats = {"confirmed": 0, "retries": 0, "failed": 0}
+        self.flip_count = 0  # 反手次数
+        
+        # 下单结果未知（请求超时、订单未确认）的交易对（开仓）或持仓索引键（平仓） -> 暂停下单截止时间
+        self._uncertain_orders: Dict[str, float] = {}
+        
+        # 下单确认：超时未收到订单更新的订单按ID查询，查不到时标记为已提交、未确认
+        self._last_ack_check = 0.0
+        self.order_confirm_stats = {"polled": 0, "unconfirmed": 0, "confirmed_late": 0, "missing": 0}
+        
+        # 模拟盘：撮合引擎的成交回报驱动持仓变化
+        if self.client.is_paper:
+            self.client.simulator.add_fill_callback(self._on_fill)
+            self.client.simulator.add_order_callback(self._on_order_update)
+        
+        # 信号Webhook：外部告警转换为交易信号，经相同风控检查后执行
+        self.webhook: Optional[WebhookServer] = None
+        if config.webhook_enabled:
+            self.webhook = WebhookServer(
+                host=config.webhook_host,
+                port=config.webhook_port,
+                path=config.webhook_path,
+                secret=config.webhook_secret,
+                symbols=self.symbols,
+                handler=self._handle_external_signal
+            )
+        
+        # 外部信号总线（Redis/ZeroMQ）：消费交易信号，发布成交和持仓
+        self.signal_bus = create_signal_bus(config, self._handle_external_signal, self.symbols)
+        
+        # 脚本信号：信号逻辑由热加载的外部脚本定义
+        self.script: Optional[ScriptedSignal] = ScriptedSignal(config.signal_script) if config.signal_script else None
+        
+        # 组合信号：多个信号源加权投票
+        self.ensemble: Optional[SignalEnsemble] = SignalEnsemble.from_config(
+            config, self.strategy_config.medium_ma_period, float(self.strategy_config.deviation_threshold)
+        ) if config.signal_ensemble else None
+        
+        # 特征记录：每个决策点的特征和前瞻收益率，用于离线训练
+        self.feature_recorder: Optional[FeatureRecorder] = None
+        if config.feature_recorder_dir:
+            if PARQUET_AVAILABLE:
+                self.feature_recorder = FeatureRecorder(config.feature_recorder_dir,
+                                                        parse_horizons(config.feature_recorder_horizons),
+                                                        config.feature_recorder_flow_window)
+            else:
+                logger.warning("已启用特征记录，但未安装pyarrow依赖，特征记录将被禁用")
+        
+        # 事件流（Kafka/NATS）：推送交易记录、订单事件和性能快照
+        self.event_streamer = create_event_streamer(config)
+        self._last_snapshot_time = 0.0
+        self.order_tracker.add_update_callback(self._on_tracked_order_update)
+        if self.client.audit:
+            logger.info(f"订单审计日志: {self.client.audit.path}")
+        
+        # 启动预热：启动检查全部通过前不下单（先于启动对账）
+        self.warmup = StartupWarmup([
+            ("streams", "行情连接", self._check_streams),
+            ("market_data", "行情数据", self._check_market_data),
+            ("account", "账户访问", self._check_account),
+            ("leverage", "杠杆", self._check_leverage),
+            ("contract_specs", "合约规格", self._check_contract_specs),
+            ("signed_request", "签名请求", self._check_signed_request),
+        ], config.warmup_enabled)
+        
+        # 启动对账：对账完成前不交易（模拟盘没有遗留的挂单和持仓）
+        self.reconciled = self.client.is_paper or not config.reconcile_enabled
+        self.state_store: Optional[StateStore] = (
+            StateStore(os.path.join(config.state_dir, f"{self.name}.json"))
+            if config.state_dir and not self.reconciled else None
+        )
+        self._last_reconcile_attempt = 0.0
+        self.reconcile_stats = {"attempts": 0, "adopted_orders": 0, "canceled_orders": 0,
+                                "adopted_positions": 0, "closed_positions": 0, "vanished_positions": 0}
+        
+        # 盈亏核对：定期用交易所历史成交重建已实现盈亏和成交额，与本地交易记录比较（未启用时为None）
+        self.pnl_crosscheck: Optional[PnlCrossCheck] = (
+            PnlCrossCheck(config.pnl_crosscheck_tolerance) if config.pnl_crosscheck_enabled else None
+        )
+        self._last_pnl_crosscheck = time.time()
+        self._pnl_crosscheck_task: Optional[asyncio.Task] = None
+        # 合约规格定期刷新
+        self._last_spec_refresh = time.time()
+        self._spec_refresh_task: Optional[asyncio.Task] = None
+        
+        # 状态快照：主循环定期发布，性能监控和事件流只读取快照（见 snapshot.py）
+        self.snapshots = SnapshotChannel()
+        self._last_snapshot_publish = 0.0
+        
+        # 多策略资金分配（由 CapitalAllocator 关联，单策略运行时为None）
+        self.allocation = None
+        
         # 运行状态
         self.is_running = False
-        self.min_trade_interval = 5000  # 最小交易间隔（毫秒）
-        self.max_trade_interval = 60000  # 最大交易间隔（毫秒）
+        self.min_trade_interval = config.min_trade_interval  # 同一交易对两次开仓的最小间隔（毫秒）
+        self.max_trade_interval = config.max_trade_interval  # 最大交易间隔（毫秒）
         
         logger.info("="*60)
         logger.info("多币种高频策略初始化（v3.4 - WebSocket版）")
         logger.info(f"数据源: WebSocket实时价格流")
         logger.info(f"市场类型: 加密货币（24小时交易）")
-        logger.info(f"交易对数量: {len(self.config.symbols)}")
-        logger.info(f"交易对列表: {', '.join(self.config.symbols)}")
-        logger.info(f"杠杆倍数: {self.strategy_config.leverage}x")
+        logger.info(f"交易对数量: {len(self.symbols)}")
+        logger.info(f"交易对列表: {', '.join(self.symbols)}")
+        logger.info(f"杠杆倍数: {', '.join(f'{s} {self.strategy_config.get_leverage(s)}x' for s in self.symbols)}")
+        logger.info(f"持仓模式: {self.position_mode.value}")
+        logger.info(f"执行策略: {self.router.policy.value}")
         logger.info("✅ 各币种最小下单量:")
-        for symbol in self.config.symbols:
-            min_size = self.strategy_config.get_min_order_size(symbol)
-            logger.info(f"   - {symbol}: {min_size}")
+        for symbol in self.symbols:
+            min_size = self._min_order_size(symbol)
+            spec = self.client.get_contract_spec(symbol)
+            step = f"（数量步长 {spec.step_size}，价格步长 {spec.tick_size}）" if spec else ""
+            logger.info(f"   - {symbol}: {min_size}{step}")
         logger.info("="*60)
     
     async def run(self):
         """运行策略主循环"""
         self.is_running = True
+        self._loop = asyncio.get_running_loop()
         logger.info("策略开始运行...")
         
         try:
             # 更新账户信息
             await self._update_account_info()
             
+            # 设置并确认各交易对杠杆
+            await self._configure_leverage()
+            
             # 尝试初始化WebSocket连接（非阻塞，失败不影响主流程）
             try:
                 await self._initialize_websocket()
@@ -165,23 +605,151 @@ class HighFrequencyStrategy:
                 logger.warning(f"WebSocket初始化失败，将使用REST API获取数据: {e}")
                 self.price_stream = None
             
+            # 用REST快照预填价格序列和订单簿，启动后即可生成信号
+            await self._bootstrap_market_data()
+            await self.warmup.run()
+            
+            if self.webhook:
+                await self.webhook.start()
+            
+            if self.signal_bus:
+                try:
+                    await self.signal_bus.start()
+                except Exception as e:
+                    logger.error(f"[信号总线] 启动失败: {e}")
+                    self.signal_bus = None
+            
+            if self.event_streamer:
+                try:
+                    await self.event_streamer.start()
+                except Exception as e:
+                    logger.error(f"[事件流] 启动失败: {e}")
+                    self.event_streamer = None
+            
+            if self._exit_event:
+                self._exit_watcher_task = asyncio.create_task(self._watch_exits())
+            
             while self.is_running:
                 try:
-                    # 对每个交易对执行策略
-                    for symbol in self.config.symbols:
-                        await self._execute_strategy_for_symbol(symbol)
+                    iteration_start = time.perf_counter()
+                    
+                    # 撤销超时挂单，交易对在本轮重新评估
+                    await self.router.cancel_expired()
+                    
+                    if self.risk_model:
+                        self.risk_model.sample(self._latest_prices())
+                    
+                    if self.regime:
+                        for sym, price in self._latest_prices().items():
+                            self.regime.update(sym, price)
+                    
+                    if self.bar_builders:
+                        self._flush_bars()
+                    
+                    self.trade_records.maybe_spill()
+                    self.daily.maybe_rollover()
+                    
+                    # WebSocket没有推送标记价格时通过REST轮询
+                    await self._poll_mark_prices()
+                    await self._update_market_activity()
+                    self._sample_basis()
+                    await self._update_funding_rates()
+                    await self._refresh_fee_rates()
+                    await self._avoid_funding_settlement()
+                    self._check_latency_guard()
+                    await self._confirm_orders()
+                    
+                    await self._check_endpoints()
+                    self._check_critical_risks()
+                    self._check_trading_status()
+                    
+                    if self.drawdown_throttle:
+                        self.drawdown_throttle.update(self._equity())
+                    
+                    equity = float(self._equity())
+                    self.rolling_performance.sample(equity)
+                    if self.benchmark:
+                        self.benchmark.sample(equity, self._latest_prices())
+                    
+                    if not self.warmup.passed and time.time() - self.warmup.last_run >= self.config.warmup_retry_seconds:
+                        await self.warmup.run()
+                    
+                    if self.warmup.passed and not self.reconciled \
+                            and time.time() - self._last_reconcile_attempt >= self.RECONCILE_RETRY_SECONDS:
+                        await self._reconcile()
+                    
+                    if self.universe and self._trading_ready():
+                        await self._progress_wind_down()
+                        if time.time() - self._last_universe_scan >= self.config.universe_interval_seconds \
+                                and (self._universe_task is None or self._universe_task.done()):
+                            self._last_universe_scan = time.time()
+                            self._universe_task = asyncio.create_task(self._rotate_universe())
                     
-                    # 等待下次交易
-                    await asyncio.sleep(1)  # 1秒间隔
+                    # 对每个交易对执行策略（启动检查通过、启动对账完成前不交易）
+                    if self._trading_ready():
+                        for symbol in self._symbols_to_evaluate():
+                            await self._execute_strategy_for_symbol(symbol)
+                        if self.state_store:
+                            self.state_store.save(self.positions, self.order_tracker.get_open_orders())
+                    self.daily.save()
+                    
+                    if self.pnl_crosscheck and time.time() - self._last_pnl_crosscheck >= self.config.pnl_crosscheck_seconds \
+                            and not (self._pnl_crosscheck_task and not self._pnl_crosscheck_task.done()):
+                        self._last_pnl_crosscheck = time.time()
+                        self._pnl_crosscheck_task = asyncio.create_task(self._run_pnl_crosscheck())
+                    
+                    if self.config.contract_spec_refresh_seconds > 0 \
+                            and time.time() - self._last_spec_refresh >= self.config.contract_spec_refresh_seconds \
+                            and not (self._spec_refresh_task and not self._spec_refresh_task.done()):
+                        self._last_spec_refresh = time.time()
+                        self._spec_refresh_task = asyncio.create_task(self.client.refresh_contract_specs())
+                    
+                    if time.time() - self._last_snapshot_publish >= self.SNAPSHOT_SECONDS:
+                        self.publish_snapshot()
+                    
+                    # 定期推送性能快照
+                    if self.event_streamer and time.time() - self._last_snapshot_time >= self.config.event_snapshot_seconds:
+                        self._last_snapshot_time = time.time()
+                        self.event_streamer.emit(EVENT_PERFORMANCE, self.snapshots.latest.stats)
+                    
+                    if self.latency:
+                        self.latency.record_since(LATENCY_LOOP_ITERATION, iteration_start)
+                        self.latency.maybe_report(self.name)
+                    
+                    # 等待下次交易（事件触发模式下新价格到达时提前唤醒）
+                    await self._wait_next_iteration()
                     
                 except Exception as e:
                     logger.error(f"策略执行错误: {e}")
+                    capture_error(e, self.name, stage="main_loop")
                     await asyncio.sleep(5)  # 出错后等待5秒
                     
         except asyncio.CancelledError:
             logger.info("策略被取消")
         finally:
             self.is_running = False
+            for task in [*self._execution_tasks.values(), *self._unwind_tasks.values()]:
+                task.cancel()
+            if self._exit_watcher_task:
+                self._exit_watcher_task.cancel()
+            if self._pnl_crosscheck_task:
+                self._pnl_crosscheck_task.cancel()
+            if self._spec_refresh_task:
+                self._spec_refresh_task.cancel()
+            if self.webhook:
+                await self.webhook.stop()
+            if self.signal_bus:
+                await self.signal_bus.stop()
+            if self.event_streamer:
+                await self.event_streamer.stop()
+            if self.dual_quote:
+                try:
+                    await self.dual_quote.cancel_all()
+                except Exception as e:
+                    logger.error(f"[双边挂单] 撤销挂单失败: {e}")
+            if self.state_store and self.reconciled:
+                self.state_store.save(self.positions, self.order_tracker.get_open_orders())
+            self.daily.save()
             if self.price_stream:
                 try:
                     await self.price_stream.stop()
@@ -189,11 +757,117 @@ class HighFrequencyStrategy:
                     pass
             logger.info("策略已停止")
     
+    async def _reconcile(self):
+        """
+        启动对账：以交易所当前的挂单和持仓为准匹配本地状态文件（见 reconcile.py），
+        孤儿挂单/持仓按配置处理，全部处理成功后才允许交易
+        """
+        self._last_reconcile_attempt = time.time()
+        self.reconcile_stats["attempts"] += 1
+        try:
+            account_info = await self.client.get_account_info()
+            managed = self._managed_symbols()
+            positions = {k: p for k, p in account_info.positions.items() if p.symbol in managed}
+            orders = []
+            for order in await self.client.get_open_orders():
+                symbol = self.client.get_symbol_by_contract_id(str(order.get("contractId", "")))
+                if symbol in managed:
+                    orders.append({**order, "symbol": symbol})
+        except Exception as e:
+            logger.error(f"[对账] 查询交易所挂单和持仓失败，{self.RECONCILE_RETRY_SECONDS}秒后重试: {e}")
+            return
+        
+        state = self.state_store.load() if self.state_store else {"positions": {}, "orders": {}}
+        plan = plan_reconciliation(orders, positions, state)
+        halted: List[str] = []
+        failed = False
+        
+        for order in plan.known_orders:
+            self._adopt_order(order, state["orders"].get(str(order["id"])))
+        for order in plan.orphan_orders:
+            desc = f"{order['symbol']} {order.get('side')} {order.get('size')}@{order.get('price')} (ID: {order['id']})"
+            if self.config.orphan_order_policy == "adopt":
+                self._adopt_order(order)
+                self.reconcile_stats["adopted_orders"] += 1
+                logger.warning(f"[对账] 接管孤儿挂单 {desc}")
+            elif self.config.orphan_order_policy == "cancel":
+                try:
+                    await self.client.cancel_order(order["symbol"], str(order["id"]))
+                    self.reconcile_stats["canceled_orders"] += 1
+                    logger.warning(f"[对账] 已撤销孤儿挂单 {desc}")
+                except Exception as e:
+                    failed = True
+                    logger.error(f"[对账] 撤销孤儿挂单 {desc} 失败: {e}")
+            else:
+                halted.append(f"挂单 {desc}")
+        
+        self.positions = dict(plan.restored)
+        closing: List[Position] = []
+        for key, position in plan.orphan_positions.items():
+            desc = f"{position.symbol} {position.direction.value} {position.size}@{position.entry_price}"
+            if self.config.orphan_position_policy == "halt":
+                self.positions[key] = position
+                halted.append(f"持仓 {desc}")
+                continue
+            stop_loss, take_profit = self._exit_levels(position.symbol, position.direction, Decimal(str(position.entry_price)))
+            position = position.model_copy(update={"stop_loss": stop_loss, "take_profit": take_profit})
+            self.positions[key] = position
+            if self.config.orphan_position_policy == "close":
+                closing.append(position)
+            else:
+                self.reconcile_stats["adopted_positions"] += 1
+                logger.warning(f"[对账] 接管孤儿持仓 {desc}，止损 {stop_loss:.4f} 止盈 {take_profit:.4f}")
+        for key in plan.vanished:
+            self.reconcile_stats["vanished_positions"] += 1
+            logger.warning(f"[对账] 本地记录的持仓 {key} 在交易所已不存在（离线期间已平仓），丢弃")
+        self._synced_margin = self._position_margin()
+        
+        if halted:
+            logger.error(f"[对账] 发现孤儿{'、'.join(halted)}，按配置停止交易，人工处理后将自动重新对账")
+            return
+        if failed:
+            logger.error(f"[对账] 未完成，{self.RECONCILE_RETRY_SECONDS}秒后重新对账")
+            return
+        
+        # 孤儿持仓在其他处理全部成功后才平仓，重新对账不会重复平仓
+        for position in closing:
+            logger.warning(f"[对账] 平掉孤儿持仓 {position.symbol} {position.direction.value} {position.size}")
+            price = self._get_current_price(self.price_history.get(position.symbol, [])) or Decimal(str(position.entry_price))
+            if not await self._close_position(position, price, self._calculate_pnl(position, price), urgent=True):
+                logger.error(f"[对账] 孤儿持仓 {position.symbol} {position.direction.value} 平仓未提交，"
+                             f"{self.RECONCILE_RETRY_SECONDS}秒后重新对账")
+                return
+            self.reconcile_stats["closed_positions"] += 1
+        
+        self.reconciled = True
+        logger.info(f"[对账] 完成: 挂单 {len(orders)} 笔（孤儿 {len(plan.orphan_orders)}），"
+                    f"持仓 {len(positions)} 个（孤儿 {len(plan.orphan_positions)}），开始交易")
+    
+    def _adopt_order(self, order: Dict[str, Any], local: Optional[Dict[str, Any]] = None):
+        """把交易所挂单登记到订单跟踪器（local 为本地状态文件中的订单记录）"""
+        local = local or {}
+        try:
+            order_type = OrderType(order.get("type"))
+        except ValueError:
+            order_type = OrderType.LIMIT
+        symbol = order["symbol"]
+        self.order_tracker.register(str(order["id"]), Order(
+            symbol=symbol,
+            side=OrderSide(order.get("side", "BUY")),
+            order_type=order_type,
+            quantity=float(order.get("size", 0)),
+            price=float(order.get("price") or 0) or None,
+            leverage=self.strategy_config.get_leverage(symbol),
+            reduce_only=bool(order.get("reduceOnly", local.get("reduce_only", False))),
+            position_side=TradeDirection(local["position_side"]) if local.get("position_side") else None
+        ), local.get("reference_price"))
+        self.order_tracker.on_order_update(order)
+    
     async def _initialize_websocket(self):
         """初始化WebSocket连接"""
         try:
             # 获取合约ID映射
-            for symbol in self.config.symbols:
+            for symbol in self.symbols:
                 contract_id = await self.client.get_contract_id_by_symbol(symbol)
                 if contract_id:
                     self.contract_ids[symbol] = contract_id
@@ -202,22 +876,82 @@ class HighFrequencyStrategy:
                     logger.error(f"无法获取 {symbol} 的合约ID")
             
             # 确定base_url (WebSocket使用wss://，SDK会自动添加/api/v1/public/ws路径)
-            if self.config.testnet:
+            if self.ws_endpoints:
+                base_url = self.ws_endpoints.current
+            elif self.config.testnet:
                 base_url = "wss://testnet.edgex.exchange"
             else:
                 base_url = "wss://pro.edgex.exchange"
             
-            # 创建价格流
+            # 创建价格流（限价执行和盘口过滤需要盘口数据，实盘需要私有频道的订单回报）
             self.price_stream = RealTimePriceStream(
-                symbols=self.config.symbols,
+                symbols=self.symbols,
                 contract_ids=self.contract_ids,
                 base_url=base_url,
                 account_id=int(self.config.account_id) if self.config.account_id else 0,
-                stark_private_key=self.config.stark_private_key or ""
+                stark_private_key=self.config.stark_private_key or "",
+                subscribe_depth=(
+                    self.client.is_paper
+                    or self.router.policy == ExecutionPolicy.LIMIT_THEN_MARKET
+                    or self.liquidity_filter is not None
+                    or self.tca is not None
+                    or self.script is not None
+                    or self.ensemble is not None
+                    or self.feature_recorder is not None
+                    or self.price_source.needs_depth
+                    or self.dual_quote is not None
+                ),
+                subscribe_trades=(self.client.is_paper or self.tca is not None or bool(self.bar_builders)
+                                  or self.feature_recorder is not None),
+                enable_private=not self.client.is_paper,
+                proxy=self.config.proxy_url or None,
+                cpu_affinity=parse_cpu_list(self.config.market_data_cpus) or None,
+                ack_timeout=self.config.ws_ack_timeout,
+                compression=self.config.ws_compression,
+                connections=self.config.ws_public_connections,
+                max_channels_per_connection=self.config.ws_max_channels_per_connection,
+                sequence_check=self.config.ws_sequence_check,
+                book_validation=self.config.ws_book_validation,
+                book_checksum=self.config.ws_book_checksum,
+                clock=self.client.clock
             )
             
             # 添加价格回调
             self.price_stream.add_price_callback(self._on_price_update)
+            self.price_stream.add_mark_price_callback(self._on_mark_price_update)
+            # 合约规格变化推送
+            self.price_stream.add_metadata_callback(
+                lambda contracts: self._call_in_loop(self.client.apply_metadata_update, contracts)
+            )
+            
+            # 实盘：私有频道的订单/成交回报驱动订单跟踪和持仓变化，持仓推送用于核对本地持仓，资金费累计统计
+            if not self.client.is_paper:
+                events = self.price_stream.account_events
+                events.on_order_update(self._on_order_update)
+                events.on_fill(self._on_fill)
+                events.on_position_update(self._on_position_update)
+                events.on_funding(self._on_funding)
+            
+            # 模拟盘：深度和逐笔成交驱动撮合引擎
+            if self.client.is_paper:
+                simulator = self.client.simulator
+                self.price_stream.add_depth_callback(
+                    lambda sym, book: simulator.on_depth(sym, book.bids, book.asks)
+                )
+                self.price_stream.add_trade_callback(simulator.on_trade)
+            
+            # K线构建：逐笔成交聚合为策略的价格序列
+            if self.bar_builders:
+                self.price_stream.add_trade_callback(self._on_trade_for_bars)
+            
+            # TCA：记录盘口和逐笔成交作为成本基准
+            if self.tca:
+                self.price_stream.add_depth_callback(self.tca.on_depth)
+                self.price_stream.add_trade_callback(self.tca.on_trade)
+            
+            # 特征记录：逐笔成交用于成交流特征
+            if self.feature_recorder:
+                self.price_stream.add_trade_callback(self.feature_recorder.on_trade)
             
             logger.info("WebSocket价格流初始化完成")
             
@@ -228,24 +962,451 @@ class HighFrequencyStrategy:
     def _on_price_update(self, symbol: str, price_data: PriceData):
         """价格更新回调函数"""
         try:
-            # 更新价格历史
-            if symbol not in self.price_history:
-                self.price_history[symbol] = []
+            # 更新价格历史（K线模式下价格序列由逐笔成交构建）
+            if not self.bar_builders:
+                self._append_price_history(symbol, self._signal_price(symbol, price_data))
+            
+            if self.client.is_paper:
+                self.client.simulator.on_price(symbol, price_data.close)
+            
+            self._track_excursion(symbol, price_data.close)
             
-            self.price_history[symbol].append(price_data)
+            if self.client.latency_guard:
+                self.client.latency_guard.record_ws(price_data.timestamp)
             
-            # 保持历史记录在合理范围内
-            if len(self.price_history[symbol]) > 1000:
-                self.price_history[symbol] = self.price_history[symbol][-1000:]
+            if self._exit_event:
+                self._tick_prices[symbol] = price_data.close
+                self._request_exit_check(symbol)
             
             logger.debug(f"{symbol}: 价格更新 {price_data.close}")
             
         except Exception as e:
             logger.error(f"处理价格更新失败: {e}")
     
+    def _track_excursion(self, symbol: str, price: float):
+        """按逐笔价格更新持仓的最大不利/有利偏移"""
+        for position in self._positions_for(symbol):
+            if position.entry_price <= 0 or price <= 0:
+                continue
+            move = price / position.entry_price - 1
+            if position.direction == TradeDirection.SHORT:
+                move = -move
+            position.mfe = max(position.mfe, move)
+            position.mae = max(position.mae, -move)
+    
+    def _symbols_to_evaluate(self) -> List[str]:
+        """本轮评估的交易对：固定间隔模式为全部；事件触发模式为价格有更新的交易对，每个评估间隔评估一次全部"""
+        now = time.monotonic()
+        if self._evaluation_event is None or now - self._last_full_evaluation >= self.config.loop_interval_ms / 1000:
+            self._last_full_evaluation = now
+            self._dirty_symbols.clear()
+            return list(self.symbols)
+        symbols = [s for s in self.symbols if s in self._dirty_symbols]
+        self._dirty_symbols.difference_update(symbols)
+        return symbols
+    
+    async def _wait_next_iteration(self):
+        """等待评估间隔（毫秒），事件触发模式下有新价格时立即返回"""
+        timeout = self.config.loop_interval_ms / 1000
+        if self._evaluation_event is None:
+            await asyncio.sleep(timeout)
+            return
+        try:
+            await asyncio.wait_for(self._evaluation_event.wait(), timeout)
+        except asyncio.TimeoutError:
+            pass
+        self._evaluation_event.clear()
+    
+    def _request_evaluation(self, symbol: str):
+        """事件触发模式：标记交易对待评估并唤醒主循环（可能来自WebSocket线程）"""
+        if self._evaluation_event is None:
+            return
+        self._dirty_symbols.add(symbol)
+        if not self._evaluation_event.is_set() and self._loop and self._loop.is_running():
+            self._loop.call_soon_threadsafe(self._evaluation_event.set)
+    
+    def _signal_price(self, symbol: str, price_data: PriceData) -> PriceData:
+        """按配置的参考价格来源取样（最新成交价、中间价、标记/指数价格或盘口加权公允价格，不可用时为最新成交价）"""
+        if self.price_source.name == "last":
+            return price_data
+        book = self.price_stream.get_order_book(symbol) if self.price_stream and self.price_source.needs_depth else None
+        price = self.price_source.price(price_data.close, book, self.mark_prices.get(symbol))
+        if price == price_data.close:
+            return price_data
+        return price_data.model_copy(update={"open": price, "high": price, "low": price, "close": price})
+    
+    def _trading_ready(self) -> bool:
+        """启动检查已通过且启动对账已完成"""
+        return self.warmup.passed and self.reconciled
+    
+    async def _check_streams(self) -> Tuple[bool, str]:
+        """行情WebSocket已连接（实盘还需私有频道已连接）"""
+        if not self.price_stream:
+            return False, "WebSocket未初始化"
+        if not self.price_stream.is_connected:
+            return False, "公共行情连接未建立"
+        if not self.client.is_paper and not self.price_stream.private_connected:
+            return False, "私有频道（订单/成交回报）未连接"
+        return True, f"{len(self.price_stream.shards)} 条公共连接" + ("" if self.client.is_paper else "，私有频道已连接")
+    
+    async def _check_market_data(self) -> Tuple[bool, str]:
+        """各交易对已有足够的价格（ticker模式不少于中期均线周期），订阅深度时已有订单簿"""
+        missing = []
+        for symbol in self.symbols:
+            if self.bar_builders:
+                if not (self.price_stream and self.price_stream.get_latest_price(symbol)):
+                    missing.append(f"{symbol} 无最新价格")
+            elif len(self.price_history.get(symbol, [])) < self.strategy_config.medium_ma_period:
+                missing.append(f"{symbol} 价格 {len(self.price_history.get(symbol, []))}/{self.strategy_config.medium_ma_period}")
+            if self.price_stream and self.price_stream.subscribe_depth and not self.price_stream.get_order_book(symbol):
+                missing.append(f"{symbol} 无订单簿")
+        if missing:
+            return False, "；".join(missing)
+        return True, f"{len(self.symbols)} 个交易对数据就绪"
+    
+    async def _check_account(self) -> Tuple[bool, str]:
+        """账户信息可以查询"""
+        account_info = await self.client.get_account_info()
+        return True, f"余额 {float(account_info.balance):.2f} USDT，可用 {float(account_info.available_balance):.2f} USDT"
+    
+    async def _check_leverage(self) -> Tuple[bool, str]:
+        """各交易对杠杆已确认（启动时设置失败的交易对重新设置）"""
+        if self.leverage_blocked:
+            await self._configure_leverage()
+        if self.leverage_blocked:
+            return False, "；".join(f"{symbol} {reason}" for symbol, reason in self.leverage_blocked.items())
+        return True, ", ".join(f"{s} {self.strategy_config.get_leverage(s)}x" for s in self.symbols)
+    
+    async def _check_contract_specs(self) -> Tuple[bool, str]:
+        """各交易对合约规格已加载（数量/价格步长、最小下单量）"""
+        missing = [symbol for symbol in self.symbols if self.client.get_contract_spec(symbol) is None]
+        if missing:
+            return False, f"缺少合约规格: {', '.join(missing)}"
+        return True, f"{len(self.symbols)} 个交易对已加载"
+    
+    async def _check_signed_request(self) -> Tuple[bool, str]:
+        """发送一个需要签名的只读请求（查询挂单），确认私钥和账户ID可用"""
+        if self.client.is_paper:
+            return True, "模拟交易，跳过"
+        orders = await self.client.get_open_orders()
+        return True, f"查询挂单成功（{len(orders)} 笔）"
+    
+    async def _bootstrap_market_data(self, symbols: Optional[List[str]] = None):
+        """
+        启动时用REST历史K线预填价格序列（ticker模式），用REST订单簿快照初始化尚未收到深度快照的本地订单簿
+        
+        Args:
+            symbols: 预填的交易对（默认为全部，交易对轮换新加入的交易对单独预填）
+        """
+        symbols = symbols if symbols is not None else list(self.symbols)
+        async def bootstrap_klines(symbol: str):
+            if len(self.price_history.get(symbol, [])) >= self.strategy_config.medium_ma_period:
+                return
+            try:
+                klines = await self.client.get_klines(symbol, self.config.bootstrap_kline_interval,
+                                                      self.config.bootstrap_klines)
+            except Exception as e:
+                logger.warning(f"[预填] {symbol} 获取历史K线失败，等待实时价格积累: {e}")
+                return
+            self._prepend_price_history(symbol, klines)
+        
+        async def bootstrap_book(symbol: str):
+            if self.price_stream.get_order_book(symbol):
+                return
+            try:
+                book = await self.client.get_order_book(symbol, self.price_stream.depth_level)
+            except Exception as e:
+                logger.warning(f"[预填] {symbol} 获取订单簿快照失败，等待WebSocket深度快照: {e}")
+                return
+            if book.bids and book.asks and self.price_stream.seed_order_book(book):
+                logger.info(f"[预填] {symbol} 已用REST订单簿快照初始化（买 {len(book.bids)} 档 / 卖 {len(book.asks)} 档）")
+        
+        tasks = []
+        if self.config.bootstrap_klines > 0 and not self.bar_builders:
+            tasks.extend(bootstrap_klines(symbol) for symbol in symbols)
+        if self.config.bootstrap_order_book and self.price_stream and self.price_stream.subscribe_depth:
+            tasks.extend(bootstrap_book(symbol) for symbol in symbols)
+        if tasks:
+            await asyncio.gather(*tasks)
+    
+    def _prepend_price_history(self, symbol: str, klines: List[PriceData]):
+        """把早于现有价格序列的历史K线插到序列前面，并按合并后的序列重建均线窗口"""
+        existing = self.price_history.get(symbol, [])
+        first = existing[0].timestamp if existing else None
+        older = sorted((k for k in klines if k.close > 0 and (first is None or k.timestamp < first)),
+                       key=lambda k: k.timestamp)
+        if not older:
+            return
+        history = (older + existing)[-1000:]
+        self.price_history[symbol] = history
+        window = RollingWindow(self.strategy_config.medium_ma_period)
+        for price in history[-window.size:]:
+            window.update(price.close)
+        self.rolling_closes[symbol] = window
+        logger.info(f"[预填] {symbol} 已用 {len(older)} 根{self.config.bootstrap_kline_interval}历史K线预填价格序列"
+                    f"（共 {len(history)} 个价格）")
+        self._request_evaluation(symbol)
+    
+    def _append_price_history(self, symbol: str, price_data: PriceData):
+        """追加价格序列（启用异常价格保护时，偏离已确认价格过大的价格被丢弃）"""
+        if self.price_guard and not self.price_guard.observe(symbol, price_data.close):
+            return
+        if symbol not in self.price_history:
+            self.price_history[symbol] = []
+        
+        self.price_history[symbol].append(price_data)
+        
+        # 保持历史记录在合理范围内
+        if len(self.price_history[symbol]) > 1000:
+            self.price_history[symbol] = self.price_history[symbol][-1000:]
+        
+        if symbol not in self.rolling_closes:
+            self.rolling_closes[symbol] = RollingWindow(self.strategy_config.medium_ma_period)
+        self.rolling_closes[symbol].update(price_data.close)
+        self._request_evaluation(symbol)
+    
+    def _on_trade_for_bars(self, symbol: str, price: float, size: float, is_buyer_maker: Optional[bool]):
+        """逐笔成交回调：聚合为K线"""
+        builder = self.bar_builders.get(symbol)
+        if not builder:
+            return
+        for bar in builder.on_trade(price, size):
+            self._append_price_history(symbol, bar)
+            logger.debug(f"[K线] {symbol} 收线 O:{bar.open} H:{bar.high} L:{bar.low} C:{bar.close} V:{bar.volume}")
+    
+    def _flush_bars(self):
+        """没有新成交时按时间收线（时间K线）"""
+        now_ms = int(time.time() * 1000)
+        for symbol, builder in self.bar_builders.items():
+            for bar in builder.on_time(now_ms):
+                self._append_price_history(symbol, bar)
+    
+    def _on_mark_price_update(self, symbol: str, mark: MarkPriceData):
+        """标记价格更新回调函数"""
+        self.mark_prices[symbol] = mark
+        if self.client.is_paper:
+            self.client.simulator.on_mark_price(symbol, mark.mark_price)
+        self._request_exit_check(symbol)
+    
+    def _request_exit_check(self, symbol: str):
+        """止盈止损监控：标记交易对待检查并唤醒监控任务（可能来自WebSocket线程）"""
+        if self._exit_event is None:
+            return
+        self._exit_symbols.add(symbol)
+        if not self._exit_event.is_set() and self._loop and self._loop.is_running():
+            self._loop.call_soon_threadsafe(self._exit_event.set)
+    
+    async def _watch_exits(self):
+        """止盈止损监控任务：行情tick到达时检查该交易对持仓的止盈止损，触发时立即平仓"""
+        while self.is_running:
+            await self._exit_event.wait()
+            self._exit_event.clear()
+            symbols, self._exit_symbols = self._exit_symbols, set()
+            if not self._trading_ready():
+                continue
+            for symbol in symbols:
+                price = self._tick_prices.get(symbol) or self._latest_prices().get(symbol)
+                if not price:
+                    continue
+                for position in self._positions_for(symbol):
+                    key = position_key(symbol, position.direction, self.position_mode)
+                    if self._order_in_flight(symbol, key):
+                        continue
+                    try:
+                        await self._check_exit_levels(position, Decimal(str(price)))
+                    except Exception as e:
+                        logger.error(f"[止盈止损监控] {symbol} 检查失败: {e}")
+    
+    async def _poll_mark_prices(self):
+        """为没有及时收到WebSocket标记价格的交易对通过REST接口获取标记价格"""
+        interval = self.config.mark_price_poll_seconds
+        if time.time() - self._last_mark_poll < interval:
+            return
+        self._last_mark_poll = time.time()
+        
+        now_ms = int(time.time() * 1000)
+        for symbol in self.symbols:
+            mark = self.mark_prices.get(symbol)
+            if mark and now_ms - mark.timestamp < interval * 1000:
+                continue
+            try:
+                mark = await self.client.get_mark_price(symbol)
+            except Exception as e:
+                logger.debug(f"[标记价格] {symbol} 获取失败: {e}")
+                continue
+            if mark:
+                self._on_mark_price_update(symbol, mark)
+    
+    def _sample_basis(self):
+        """采样各交易对的基差（永续价格取盘口中间价，无盘口时为最新价格；标记价格过期时跳过）"""
+        if not self.basis:
+            return
+        now_ms = int(time.time() * 1000)
+        latest = self._latest_prices()
+        for symbol in self.symbols:
+            mark = self.mark_prices.get(symbol)
+            if not mark or now_ms - mark.timestamp > self.basis.max_age_seconds * 1000:
+                continue
+            perp_price = self._book_mid(symbol) or latest.get(symbol)
+            if perp_price:
+                self.basis.sample(symbol, perp_price, mark.index_price)
+    
+    def _check_latency_guard(self):
+        """按REST/WebSocket延迟分位数更新降级状态，状态变化时告警并调整双边挂单价差"""
+        guard = self.client.latency_guard
+        if not guard:
+            return
+        changed = guard.update()
+        if changed is None:
+            return
+        if self.dual_quote:
+            self.dual_quote.spread_scale = guard.spread_scale()
+        if changed:
+            text = f"{guard.reason}，暂停开仓" + (f"，双边挂单价差放宽 {guard.quote_widen:g} 倍" if self.dual_quote else "")
+            logger.warning(f"[延迟保护] {text}")
+            self.alerts.send(self.name, "latency_degraded", text)
+        else:
+            logger.info("[延迟保护] 延迟已恢复正常，恢复开仓")
+            self.alerts.send(self.name, "latency_recovered", "延迟已恢复正常，恢复开仓")
+    
+    async def _refresh_fee_rates(self):
+        """定期获取账户手续费率（模拟盘同步给撮合引擎）"""
+        if time.time() - self._last_fee_refresh < self.config.fee_refresh_seconds:
+            return
+        self._last_fee_refresh = time.time()
+        try:
+            rates = await self.client.get_fee_rates(self.symbols)
+        except Exception as e:
+            logger.warning(f"[手续费] 获取账户手续费率失败: {e}")
+            return
+        for symbol, rate in self.fees.update(rates).items():
+            source = "账户设置" if rate.source == "account" else "合约默认"
+            logger.info(f"[手续费] {symbol} 吃单 {rate.taker * 100:.4f}% / 挂单 {rate.maker * 100:.4f}%（{source}）")
+            if self.client.is_paper:
+                self.client.simulator.set_fee_rates(symbol, rate.taker, rate.maker)
+    
+    def _funding_interval(self, symbol: str) -> int:
+        spec = self.client.get_contract_spec(symbol)
+        return spec.funding_interval_minutes if spec else 0
+    
+    async def _update_funding_rates(self):
+        """更新各交易对的资金费率（优先使用WebSocket ticker，没有及时推送时通过REST接口获取）"""
+        if not self.funding_window:
+            return
+        interval = self.config.funding_poll_seconds
+        if time.time() - self._last_funding_poll < interval:
+            return
+        self._last_funding_poll = time.time()
+        
+        now_ms = int(time.time() * 1000)
+        for symbol in self.symbols:
+            rate = self.price_stream.get_funding_rate(symbol) if self.price_stream else None
+            if not rate or now_ms - rate.timestamp >= interval * 1000:
+                try:
+                    rate = await self.client.get_funding_rate(symbol)
+                except Exception as e:
+                    logger.debug(f"[资金费] {symbol} 获取资金费率失败: {e}")
+                    continue
+            if rate:
+                self.funding_window.update(symbol, rate)
+    
+    async def _avoid_funding_settlement(self):
+        """动作为 close 时，结算前窗口内平掉需要支付资金费的持仓"""
+        if not self.funding_window or self.funding_window.action != "close":
+            return
+        for position in list(self.positions.values()):
+            symbol = position.symbol
+            direction = 1 if position.direction == TradeDirection.LONG else -1
+            reason = self.funding_window.avoid(symbol, direction, self._funding_interval(symbol))
+            if not reason or not self.trading_status.can_close(symbol):
+                continue
+            key = position_key(symbol, position.direction, self.position_mode)
+            if self._order_in_flight(symbol, key):
+                continue
+            price = self._get_current_price(self.price_history.get(symbol, [])) or Decimal(str(position.entry_price))
+            logger.info(f"[资金费] {symbol} {reason}，结算前平仓")
+            self.funding_window.record(symbol, closed=True)
+            await self._close_position(position, price, self._calculate_pnl(position, price), urgent=True)
+    
+    async def _update_market_activity(self):
+        """更新各交易对的持仓量与24小时成交量（优先使用WebSocket ticker，没有及时推送时通过REST接口获取）"""
+        if not self.activity_filter:
+            return
+        interval = self.config.activity_poll_seconds
+        if time.time() - self._last_activity_poll < interval:
+            return
+        self._last_activity_poll = time.time()
+        
+        now_ms = int(time.time() * 1000)
+        for symbol in self.symbols:
+            activity = self.price_stream.get_market_activity(symbol) if self.price_stream else None
+            if not activity or now_ms - activity.timestamp >= interval * 1000:
+                try:
+                    activity = await self.client.get_market_activity(symbol)
+                except Exception as e:
+                    logger.debug(f"[持仓量] {symbol} 获取失败: {e}")
+                    continue
+            if activity:
+                self.activity_filter.update(symbol, activity)
+    
+    async def _run_pnl_crosscheck(self):
+        """用交易所历史成交核对本地盈亏（后台任务，查询失败时等下个周期重试）"""
+        try:
+            await self.pnl_crosscheck.run(self.client, self.symbols, self.trade_records)
+        except Exception as e:
+            logger.warning(f"[盈亏核对] 查询交易所历史成交失败: {e}")
+    
+    async def _check_endpoints(self):
+        """定期探测REST/WebSocket端点延迟并选择最快的健康端点；WebSocket持续断线时切换端点重连"""
+        probe = time.time() - self._last_endpoint_probe >= self.config.endpoint_probe_seconds
+        if probe:
+            self._last_endpoint_probe = time.time()
+            await self.client.probe_endpoints()
+        
+        if not self.ws_endpoints or not self.price_stream:
+            return
+        if self._ws_switch_task and not self._ws_switch_task.done():
+            return
+        
+        target = None
+        if not self.price_stream.is_connected:
+            if self.ws_endpoints.record_error():
+                target = self.ws_endpoints.current
+        elif probe:
+            self.ws_endpoints.record_success()
+            # 使用代理时直连探测的延迟没有参考意义，只做故障切换
+            if not self.config.proxy_url:
+                urls = self.ws_endpoints.urls
+                latencies = await asyncio.gather(*(probe_ws(url) for url in urls))
+                for url, latency in zip(urls, latencies):
+                    self.ws_endpoints.record_latency(url, latency)
+                target = self.ws_endpoints.select()
+        
+        if target:
+            self._ws_switch_task = asyncio.create_task(self._switch_ws_endpoint(target))
+    
+    async def _switch_ws_endpoint(self, base_url: str):
+        """重连到指定的WebSocket地址（后台执行，不阻塞主循环）"""
+        try:
+            await self.price_stream.switch_endpoint(base_url)
+        except Exception as e:
+            logger.error(f"[端点] WebSocket连接 {base_url} 失败: {e}")
+            self._ws_reconnect_failures += 1
+            if self._ws_reconnect_failures == self.config.ws_reconnect_alert_failures:
+                self.alerts.send(self.name, "ws_reconnect_failed",
+                                 f"WebSocket连续 {self._ws_reconnect_failures} 次重连失败（最近: {base_url}: {e}）",
+                                 critical=True)
+            return
+        self._ws_reconnect_failures = 0
+    
     def stop(self):
         """停止策略"""
         self.is_running = False
+        if self.tca:
+            self.tca.close()
+        if self.feature_recorder:
+            self.feature_recorder.close()
+        self.trade_records.spill()
         logger.info("正在停止策略...")
     
     async def _update_account_info(self):
@@ -254,27 +1415,36 @@ class HighFrequencyStrategy:
             account_info = await self.client.get_account_info()
             self.balance = Decimal(str(account_info.balance))
             self.available_balance = Decimal(str(account_info.available_balance))
-            self.positions = account_info.positions
+            self.collaterals = account_info.collaterals
+            # 只保留本策略交易对的持仓（多策略共用账户时各自管理）
+            managed = self._managed_symbols()
+            self.positions = {k: p for k, p in account_info.positions.items() if p.symbol in managed}
+            # 交易所返回的可用余额已扣除此时持仓占用的保证金
+            self._synced_margin = self._position_margin()
             
-            # 记录权益历史
+            # 记录权益历史（超过容量时丢弃最早的记录）
             self.equity_history.append(self.balance)
-            
-            # 限制历史长度
-            if len(self.equity_history) > 1000:
-                self.equity_history = self.equity_history[-1000:]
                 
         except Exception as e:
             logger.error(f"更新账户信息失败: {e}")
             raise
     
+    @traced("strategy.evaluate")
     async def _execute_strategy_for_symbol(self, symbol: str):
         """为指定交易对执行策略"""
+        annotate(symbol=symbol)
+        
         try:
             # 首先尝试从WebSocket获取价格历史数据
             klines = self.price_history.get(symbol, [])
             
+            # K线模式：等待逐笔成交构建的K线，不混入ticker价格
+            if self.bar_builders and not klines:
+                logger.debug(f"{symbol}: 等待{self.config.bar_type}K线收线")
+                return
+            
             # 如果WebSocket数据不足，尝试从REST API获取Ticker数据构建简单K线
-            if len(klines) < self.strategy_config.medium_ma_period:
+            if not self.bar_builders and len(klines) < self.strategy_config.medium_ma_period:
                 logger.debug(f"{symbol}: WebSocket数据不足 (收到{len(klines)}/{self.strategy_config.medium_ma_period})，尝试使用Ticker数据")
                 
                 # 获取合约ID
@@ -308,13 +1478,7 @@ class HighFrequencyStrategy:
                             )
                             
                             # 添加到历史记录
-                            if symbol not in self.price_history:
-                                self.price_history[symbol] = []
-                            self.price_history[symbol].append(price_data)
-                            
-                            # 保持历史记录在合理范围
-                            if len(self.price_history[symbol]) > 100:
-                                self.price_history[symbol] = self.price_history[symbol][-100:]
+                            self._append_price_history(symbol, price_data)
                             
                             # 使用ticker数据生成信号（基于单点价格）
                             klines = [price_data]
@@ -333,17 +1497,88 @@ class HighFrequencyStrategy:
             latest_price = klines[-1].close
             logger.debug(f"{symbol}: 当前价格: {latest_price}")
             
+            # 双边挂单模式：不生成信号，维护双边挂单
+            if self.dual_quote:
+                await self._update_dual_quotes(symbol, latest_price)
+                return
+            
             # 生成交易信号
             signal = self._generate_signal(symbol, klines)
+            self._signal_started[symbol] = time.perf_counter()
+            if self.feature_recorder:
+                self.feature_recorder.record(self._signal_context(symbol), signal)
+            
+            # 仅由Webhook开仓：内部信号只用于止盈止损等持仓管理
+            if self.webhook and self.config.webhook_exclusive:
+                signal = signal.model_copy(update={"direction": TradeDirection.HOLD})
+            
+            if self.shadow:
+                self.shadow.on_signal(symbol, signal, self._get_current_price(klines))
             
             # 检查是否有现有持仓
-            if symbol in self.positions:
-                await self._manage_position(symbol, signal, klines)
-            else:
+            positions = self._positions_for(symbol)
+            for position in positions:
+                await self._manage_position(position, signal, klines)
+            
+            # 单向持仓：无持仓时开仓；双向持仓：信号方向无持仓时开仓
+            if self.position_mode == PositionMode.HEDGE:
+                if not any(p.direction == signal.direction for p in self._positions_for(symbol)):
+                    await self._open_position(symbol, signal, klines)
+            elif not positions:
                 await self._open_position(symbol, signal, klines)
+            
+            if self.shadow:
+                self.shadow.end_evaluation(symbol)
                 
         except Exception as e:
             logger.error(f"{symbol}: 执行策略失败 - {e}")
+            capture_error(e, self.name, symbol, stage="evaluate")
+        finally:
+            self._signal_started.pop(symbol, None)
+    
+    async def _update_dual_quotes(self, symbol: str, last_price: float):
+        """维护交易对的双边挂单（不允许开仓时撤销挂单）"""
+        if not self._fills_streaming:
+            logger.debug(f"[双边挂单] {symbol} 收不到成交回报，暂不挂单")
+            return
+        if (not self.trading_status.can_open(symbol) or symbol in self.leverage_blocked
+                or symbol in self.manual_paused or symbol in self._winding_down or self.client.kill_switch.describe()):
+            await self.dual_quote.cancel(symbol)
+            return
+        
+        mid = self._book_mid(symbol) or last_price
+        min_size = self._min_order_size(symbol)
+        size = max(self._round_to_step(symbol, Decimal(str(self.config.dual_quote_notional / mid))), min_size)
+        net = sum(p.size if p.direction == TradeDirection.LONG else -p.size for p in self._positions_for(symbol))
+        spec = self.client.get_contract_spec(symbol)
+        await self.dual_quote.update(symbol, mid, net, float(size), self.strategy_config.get_leverage(symbol),
+                                     spec.round_price if spec else None)
+    
+    def _estimate_ev(self, symbol: str, signal: TradeSignal) -> EvEstimate:
+        """估算开仓的期望值（见 ev_gate.py）"""
+        take_profit_bps = abs(signal.take_profit - signal.price) / signal.price * 10000 if signal.take_profit else 0.0
+        stop_loss_bps = abs(signal.price - signal.stop_loss) / signal.price * 10000 if signal.stop_loss else 0.0
+        entry_maker = self.config.execution_policy == "limit_then_market"
+        fee = self.fees.round_trip(symbol, entry_maker) or 0.0
+        
+        half_spread = None
+        book = self.price_stream.get_order_book(symbol) if self.price_stream else None
+        if book and book.bids and book.asks:
+            bid, ask = float(book.bids[0][0]), float(book.asks[0][0])
+            if 0 < bid < ask:
+                half_spread = (ask - bid) / (ask + bid) * 10000
+        default = self.ev_gate.default_slippage_bps
+        return EvEstimate(
+            gross_bps=EvGate.gross(signal.confidence, take_profit_bps, stop_loss_bps),
+            fee_bps=fee * 10000,
+            entry_cost_bps=execution_cost(half_spread, self.slippage.estimate(symbol, False), default),
+            exit_cost_bps=execution_cost(half_spread, self.slippage.estimate(symbol, True), default),
+        )
+    
+    def _record_signal_to_order(self, symbol: str):
+        """记录本轮信号到下单的耗时（不在信号评估中发出的订单，如Webhook信号，不记录）"""
+        if self.latency:
+            self.latency.record_since(LATENCY_SIGNAL_TO_ORDER, self._signal_started.get(symbol))
     
     def _generate_signal(self, symbol: str, klines: List[PriceData]) -> TradeSignal:
         """
@@ -359,20 +1594,22 @@ class HighFrequencyStrategy:
         # 获取当前价格
         current_price = self._get_current_price(klines)
         
-        # 如果历史数据不足，使用价格历史计算均线
-        all_klines = self.price_history.get(symbol, [])
-        
-        # 使用所有可用的历史数据
-        if len(all_klines) >= self.strategy_config.medium_ma_period:
-            # 有足够的历史数据，使用标准MA策略
-            medium_ma = self._calculate_moving_average(all_klines, self.strategy_config.medium_ma_period)
-        elif len(all_klines) >= 2:
-            # 数据不足，使用所有可用数据计算MA
-            medium_ma = self._calculate_moving_average(all_klines, len(all_klines))
-            logger.debug(f"[信号生成] {symbol} 使用 {len(all_klines)} 根K线计算MA（需要{self.strategy_config.medium_ma_period}）")
+        if self.script:
+            return self._generate_scripted_signal(symbol, current_price)
+        if self.ensemble:
+            return self._generate_ensemble_signal(symbol, current_price)
+        
+        # 均线窗口随价格序列逐笔更新；历史数据不足时窗口内为所有可用数据
+        window = self.rolling_closes.get(symbol)
+        count = window.count if window else 0
+        
+        if count >= 2:
+            medium_ma = Decimal(str(window.mean))
+            if not window.is_full:
+                logger.debug(f"[信号生成] {symbol} 使用 {count} 根K线计算MA（需要{self.strategy_config.medium_ma_period}）")
         else:
             # 数据太少，暂时持有
-            logger.debug(f"[信号生成] {symbol} 历史数据不足（{len(all_klines)}<2），持有")
+            logger.debug(f"[信号生成] {symbol} 历史数据不足（{count}<2），持有")
             return TradeSignal(
                 symbol=symbol,
                 direction=TradeDirection.HOLD,
@@ -384,19 +1621,21 @@ class HighFrequencyStrategy:
         
         price_deviation = self._calculate_price_deviation(current_price, medium_ma)
         
+        # 按波动率状态调整阈值
+        params = self._regime_params(symbol)
+        deviation_threshold = self.strategy_config.deviation_threshold * Decimal(str(params.threshold_scale))
+        
         # 判断方向
-        if price_deviation > self.strategy_config.deviation_threshold:
+        if price_deviation > deviation_threshold:
             # 价格高于均线，做空
             direction = TradeDirection.SHORT
-            stop_loss = float(current_price * (Decimal("1") + self.strategy_config.stop_loss_pct))
-            take_profit = float(current_price * (Decimal("1") - self.strategy_config.take_profit_pct))
+            stop_loss, take_profit = self._exit_levels(symbol, direction, current_price)
             logger.info(f"[信号] {symbol} 做空 - 偏离: {float(price_deviation) * 100:.4f}%")
             
-        elif price_deviation < -self.strategy_config.deviation_threshold:
+        elif price_deviation < -deviation_threshold:
             # 价格低于均线，做多
             direction = TradeDirection.LONG
-            stop_loss = float(current_price * (Decimal("1") - self.strategy_config.stop_loss_pct))
-            take_profit = float(current_price * (Decimal("1") + self.strategy_config.take_profit_pct))
+            stop_loss, take_profit = self._exit_levels(symbol, direction, current_price)
             logger.info(f"[信号] {symbol} 做多 - 偏离: {float(price_deviation) * 100:.4f}%")
             
         else:
@@ -411,7 +1650,8 @@ class HighFrequencyStrategy:
                 take_profit=0.0
             )
         
-        confidence = float(abs(price_deviation))
+        # 置信度：刚达到阈值时为0，偏离越大越接近1
+        confidence = float(1 - deviation_threshold / abs(price_deviation)) if deviation_threshold > 0 else 1.0
         
         return TradeSignal(
             symbol=symbol,
@@ -422,17 +1662,345 @@ class HighFrequencyStrategy:
             take_profit=take_profit
         )
     
-    async def _open_position(self, symbol: str, signal: TradeSignal, klines: List[PriceData]):
-        """开仓"""
-        if signal.direction == TradeDirection.HOLD:
-            return
-        
-        try:
-            # 计算仓位大小
-            current_price = Decimal(str(signal.price))
-            
-            # 获取该币种的最小下单量
-            min_order_size = self.strategy_config.get_min_order_size(symbol)
+    def _generate_scripted_signal(self, symbol: str, current_price: Decimal) -> TradeSignal:
+        """由信号脚本生成交易信号（未提供止盈止损时按策略配置计算）"""
+        positions = self._positions_for(symbol)
+        result = self.script.evaluate(
+            symbol,
+            self.price_history.get(symbol, []),
+            self.price_stream.get_order_book(symbol) if self.price_stream else None,
+            positions[0].direction if positions else None,
+            self.basis.current(symbol) if self.basis else None
+        )
+        if not result:
+            return TradeSignal(
+                symbol=symbol,
+                direction=TradeDirection.HOLD,
+                confidence=0.0,
+                price=float(current_price),
+                stop_loss=0.0,
+                take_profit=0.0
+            )
+        
+        direction = result["direction"]
+        stop_loss, take_profit = self._exit_levels(symbol, direction, current_price)
+        logger.info(f"[信号] {symbol} 脚本信号 {direction.value} - 置信度: {result['confidence']:.2f}")
+        return TradeSignal(
+            symbol=symbol,
+            direction=direction,
+            confidence=result["confidence"],
+            price=float(current_price),
+            stop_loss=result["stop_loss"] or stop_loss,
+            take_profit=result["take_profit"] or take_profit
+        )
+    
+    def _signal_context(self, symbol: str) -> SignalContext:
+        """组合信号和特征记录使用的行情上下文"""
+        positions = self._positions_for(symbol)
+        return SignalContext(
+            symbol,
+            self.price_history.get(symbol, []),
+            self.price_stream.get_order_book(symbol) if self.price_stream else None,
+            positions[0].direction if positions else None,
+            {},
+            self.basis.current(symbol) if self.basis else None
+        )
+    
+    def _generate_ensemble_signal(self, symbol: str, current_price: Decimal) -> TradeSignal:
+        """由组合信号生成交易信号（一致度作为置信度）"""
+        result = self.ensemble.evaluate(symbol, self._signal_context(symbol), self._regime_params(symbol).threshold_scale)
+        if not result:
+            logger.debug(f"[信号] {symbol} 组合信号持有 - 投票: {self.ensemble.last.get(symbol)}")
+            return TradeSignal(
+                symbol=symbol,
+                direction=TradeDirection.HOLD,
+                confidence=0.0,
+                price=float(current_price),
+                stop_loss=0.0,
+                take_profit=0.0
+            )
+        
+        direction, agreement = result
+        stop_loss, take_profit = self._exit_levels(symbol, direction, current_price)
+        logger.info(f"[信号] {symbol} 组合信号 {direction.value} - 一致度: {agreement:.2f}，投票: {self.ensemble.last[symbol]}")
+        return TradeSignal(
+            symbol=symbol,
+            direction=direction,
+            confidence=agreement,
+            price=float(current_price),
+            stop_loss=stop_loss,
+            take_profit=take_profit
+        )
+    
+    def _exit_levels(self, symbol: str, direction: TradeDirection, price: Decimal) -> tuple:
+        """
+        按配置的止盈止损比例（随波动率状态缩放）计算止损价和止盈价
+        
+        Returns:
+            tuple: (止损价, 止盈价)
+        """
+        exit_scale = Decimal(str(self._regime_params(symbol).exit_scale))
+        stop_loss_pct = self.strategy_config.stop_loss_pct * exit_scale
+        take_profit_pct = self.strategy_config.take_profit_pct * exit_scale
+        if direction == TradeDirection.LONG:
+            return float(price * (1 - stop_loss_pct)), float(price * (1 + take_profit_pct))
+        return float(price * (1 + stop_loss_pct)), float(price * (1 - take_profit_pct))
+    
+    async def _configure_leverage(self):
+        """
+        启动时为每个交易对设置配置的杠杆，并向交易所确认生效的杠杆
+        
+        设置失败、无法查询或生效杠杆与配置不一致的交易对加入 leverage_blocked，
+        不再开新仓（已有持仓仍可正常平仓）。
+        """
+        for symbol in self.symbols:
+            await self._configure_symbol_leverage(symbol)
+    
+    async def _configure_symbol_leverage(self, symbol: str):
+        """为交易对设置配置的杠杆并确认生效（失败时加入 leverage_blocked）"""
+        leverage = self.strategy_config.get_leverage(symbol)
+        try:
+            await self.client.set_leverage(symbol, leverage)
+            effective = await self.client.get_leverage(symbol)
+        except Exception as e:
+            self.leverage_blocked[symbol] = f"设置失败: {e}"
+            logger.error(f"[杠杆] {symbol} 设置 {leverage}x 失败，禁止开仓: {e}")
+            return
+        
+        if effective != leverage:
+            self.leverage_blocked[symbol] = f"生效杠杆 {effective}x ≠ 配置 {leverage}x"
+            logger.error(f"[杠杆] {symbol} 交易所生效杠杆为 {effective}x，与配置的 {leverage}x 不一致，禁止开仓")
+        else:
+            self.leverage_blocked.pop(symbol, None)
+            logger.info(f"[杠杆] {symbol} 已确认 {leverage}x")
+    
+    def _symbol_contract_id(self, symbol: str) -> Optional[str]:
+        """交易对的合约ID（WebSocket映射或合约规格）"""
+        if symbol in self.contract_ids:
+            return self.contract_ids[symbol]
+        spec = self.client.get_contract_spec(symbol)
+        return spec.contract_id if spec else None
+    
+    async def _rotate_universe(self):
+        """扫描市场并按轮换规则更新交易对集合（后台任务）"""
+        try:
+            pinned = {self._symbol_contract_id(s) for s in self.config.universe_pinned} - {None}
+            self.universe.pinned = pinned
+            self.universe.exclude = {self._symbol_contract_id(s) for s in self.config.universe_exclude} - {None}
+            ranked = await self.scanner.scan(self.client.contract_specs.specs.values())
+            if not ranked:
+                logger.warning("[轮换] 市场扫描没有结果，保持当前交易对集合")
+                return
+            
+            current: Dict[str, str] = {}
+            for symbol in self.symbols:
+                contract_id = self._symbol_contract_id(symbol)
+                if contract_id and symbol not in self._winding_down:
+                    current[contract_id] = symbol
+            added, removed = self.universe.select(ranked, current, self.scanner.failed)
+            
+            for contract_id in removed:
+                symbol = current[contract_id]
+                self._winding_down[symbol] = time.time()
+                logger.info(f"[轮换] {symbol} 离开交易对集合，停止开仓，持仓平掉后取消订阅")
+            for result in added:
+                # 仍在平仓的交易对重新入选时直接恢复
+                returning = next((s for s in self._winding_down if self._symbol_contract_id(s) == result.contract_id), None)
+                if returning:
+                    del self._winding_down[returning]
+                    logger.info(f"[轮换] {returning} 重新入选交易对集合，恢复开仓")
+                else:
+                    await self._add_symbol(result.symbol, result.contract_id)
+            
+            if added or removed:
+                summary = (f"加入: {', '.join(r.symbol for r in added) or '无'}；"
+                           f"离开: {', '.join(current[c] for c in removed) or '无'}；"
+                           f"当前: {', '.join(s for s in self.symbols if s not in self._winding_down)}")
+                logger.info(f"[轮换] 交易对集合已更新 - {summary}")
+                self.alerts.send(self.name, "universe_rotation", f"交易对集合已更新 - {summary}")
+            else:
+                logger.info(f"[轮换] 交易对集合不变（扫描 {len(ranked)} 个合约）")
+        except Exception as e:
+            logger.error(f"[轮换] 更新交易对集合失败: {e}")
+    
+    async def _add_symbol(self, symbol: str, contract_id: str):
+        """交易对加入集合：设置杠杆、订阅行情并预填价格序列"""
+        self.symbols.append(symbol)
+        self.contract_ids[symbol] = contract_id
+        if self.config.bar_type != "ticker":
+            self.bar_builders[symbol] = create_bar_builder(self.config.bar_type, self.config.bar_size)
+        await self._configure_symbol_leverage(symbol)
+        if self.price_stream:
+            await self.price_stream.add_symbol(symbol, contract_id)
+        await self._bootstrap_market_data([symbol])
+        self._last_fee_refresh = 0.0
+        logger.info(f"[轮换] {symbol} 加入交易对集合")
+    
+    async def _progress_wind_down(self):
+        """离开集合的交易对：持仓已平且没有在途订单时取消订阅；超过平仓期限仍有持仓时主动平仓"""
+        deadline = self.config.universe_wind_down_seconds
+        for symbol, since in list(self._winding_down.items()):
+            positions = [p for p in self.positions.values() if p.symbol == symbol]
+            if not positions:
+                task = self._execution_tasks.get(symbol)
+                if (task is None or task.done()) and not self._order_uncertain(symbol):
+                    await self._remove_symbol(symbol)
+                continue
+            if deadline <= 0 or time.time() - since < deadline:
+                continue
+            for position in positions:
+                key = position_key(symbol, position.direction, self.position_mode)
+                if self._order_in_flight(symbol, key):
+                    continue
+                price = self._get_current_price(self.price_history.get(symbol, [])) or Decimal(str(position.entry_price))
+                logger.warning(f"[轮换] {symbol} 离开集合超过 {deadline} 秒仍有持仓，主动平仓")
+                await self._close_position(position, price, self._calculate_pnl(position, price), urgent=True)
+    
+    async def _remove_symbol(self, symbol: str):
+        """交易对移出集合：取消订阅并清理行情状态"""
+        self._winding_down.pop(symbol, None)
+        if symbol in self.symbols:
+            self.symbols.remove(symbol)
+        self.bar_builders.pop(symbol, None)
+        self.price_history.pop(symbol, None)
+        self.rolling_closes.pop(symbol, None)
+        self.leverage_blocked.pop(symbol, None)
+        if self.price_stream:
+            await self.price_stream.remove_symbol(symbol)
+        logger.info(f"[轮换] {symbol} 已移出交易对集合")
+    
+    @traced("trade.open_position")
+    async def _open_position(self, symbol: str, signal: TradeSignal, klines: List[PriceData],
+                             flip: Optional[Position] = None) -> bool:
+        """
+        开仓
+        
+        flip 为反向持仓时（单向持仓模式反手），订单数量为新仓位加上该持仓数量，一笔订单平仓并反向开仓。
+        
+        Returns:
+            bool: 是否已提交订单
+        """
+        if signal.direction == TradeDirection.HOLD:
+            return False
+        
+        if symbol in self.manual_paused:
+            logger.debug(f"[开仓] {symbol} 已手动暂停开仓，跳过")
+            return False
+        
+        if self.price_guard:
+            reason = self.price_guard.check(symbol, signal.price, self._book_mid(symbol))
+            if reason:
+                self.price_guard.stats["rejected_signals"] += 1
+                logger.warning(f"[价格保护] {symbol} 信号被拒绝: {reason}")
+                return False
+        
+        # 上一笔开仓订单仍在执行中，或下单结果未知
+        task = self._execution_tasks.get(symbol)
+        if task and not task.done():
+            return False
+        if self._order_uncertain(symbol):
+            return False
+        
+        annotate(symbol=symbol, direction=signal.direction.value, signal_price=signal.price)
+        
+        if self.regime and self.regime.should_stand_down(symbol):
+            logger.info(f"[开仓] {symbol} 当前波动率状态为 {self.regime.regime(symbol).value}，暂停开仓")
+            return False
+        
+        if self.allocation and not self.allocation.can_open:
+            logger.debug(f"[开仓] {symbol} 策略 {self.name} 已停用或尚未分配预算，跳过开仓")
+            return False
+        
+        if symbol in self.leverage_blocked:
+            logger.debug(f"[开仓] {symbol} 杠杆未确认（{self.leverage_blocked[symbol]}），跳过开仓")
+            return False
+        
+        if self.activity_filter:
+            reason = self.activity_filter.check(symbol)
+            if reason:
+                activity = self.activity_filter.activity.get(symbol)
+                detail = (f"持仓价值 {activity.open_interest_value:.0f} USDT，24小时成交额 {activity.value_24h:.0f} USDT"
+                          if activity else "无数据")
+                logger.debug(f"[开仓] {symbol} 持仓量/成交量过滤跳过开仓（{reason}，{detail}）")
+                return False
+        
+        guard = self.client.latency_guard
+        if guard and guard.degraded:
+            guard.blocked_entries += 1
+            logger.debug(f"[开仓] {symbol} 延迟保护降级中（{guard.reason}），跳过开仓")
+            return False
+        
+        if signal.take_profit > 0 and signal.price > 0:
+            reason = self.fees.check_edge(symbol, abs(signal.take_profit - signal.price) / signal.price,
+                                          entry_maker=self.config.execution_policy == "limit_then_market")
+            if reason:
+                logger.debug(f"[开仓] {symbol} {reason}，跳过开仓")
+                return False
+        
+        if self.ev_gate and signal.price > 0:
+            estimate = self._estimate_ev(symbol, signal)
+            reason = self.ev_gate.check(symbol, signal.direction.value, signal.confidence, estimate)
+            if reason:
+                logger.info(f"[期望值] {symbol} {signal.direction.value} {reason}，跳过开仓")
+                return False
+        
+        if self.funding_window:
+            reason = self.funding_window.avoid(symbol, 1 if signal.direction == TradeDirection.LONG else -1,
+                                               self._funding_interval(symbol))
+            if reason:
+                self.funding_window.record(symbol)
+                logger.debug(f"[开仓] {symbol} {reason}，延后{signal.direction.value}开仓")
+                return False
+        
+        if self.basis:
+            reason = self.basis.check(symbol, 1 if signal.direction == TradeDirection.LONG else -1)
+            if reason:
+                logger.info(f"[开仓] {symbol} {reason}，跳过{signal.direction.value}开仓")
+                return False
+        
+        if symbol in self._winding_down:
+            logger.debug(f"[开仓] {symbol} 已离开交易对集合，只平仓不开仓")
+            return False
+        
+        if not self.trading_status.can_open(symbol):
+            logger.debug(f"[开仓] {symbol} {STATUS_LABELS[self.trading_status.status[symbol]]}"
+                         f"（{self.trading_status.reasons[symbol]}），跳过开仓")
+            return False
+        
+        last_trade = self.last_trade_times.get(symbol)
+        if last_trade is not None and int(time.time() * 1000) - last_trade < self.min_trade_interval:
+            logger.debug(f"[开仓] {symbol} 距上次开仓不足 {self.min_trade_interval}ms，跳过开仓")
+            return False
+        
+        self.daily.maybe_rollover()
+        if self.config.max_trades_per_day and self.daily.entries >= self.config.max_trades_per_day:
+            logger.debug(f"[开仓] {symbol} 今日开仓次数已达上限 {self.config.max_trades_per_day}，跳过开仓")
+            return False
+        
+        breaker = self.loss_breaker.remaining()
+        if breaker > 0:
+            logger.debug(f"[开仓] {symbol} 连续亏损熔断中（剩余 {breaker:.0f} 秒），跳过开仓")
+            return False
+        
+        cooldown = self.cooldowns.remaining(symbol)
+        if cooldown > 0:
+            logger.debug(f"[开仓] {symbol} 亏损冷却中（剩余 {cooldown:.0f} 秒），跳过开仓")
+            return False
+        
+        # 同时持仓数上限（反手不增加持仓数）
+        if self.config.max_open_positions and not flip and len(self.positions) >= self.config.max_open_positions:
+            logger.debug(f"[开仓] {symbol} 持仓数已达上限 {self.config.max_open_positions}，跳过开仓")
+            return False
+        
+        try:
+            # 计算仓位大小
+            current_price = Decimal(str(signal.price))
+            
+            # 多策略运行时按分配的预算计算仓位
+            capital = self.allocation.capital(self.available_balance) if self.allocation else self.available_balance
+            
+            # 获取该币种的最小下单量
+            min_order_size = self._min_order_size(symbol)
             
             # 检查最小余额要求
             min_required_balance = (
@@ -441,187 +2009,1245 @@ class HighFrequencyStrategy:
                 self.strategy_config.min_balance_multiplier
             )
             
-            if self.available_balance < min_required_balance:
+            if capital < min_required_balance:
                 logger.warning(
                     f"[开仓] {symbol} 账户余额不足，无法满足最小下单量要求 "
-                    f"(当前: {float(self.available_balance):.2f} USDT, "
+                    f"(当前: {float(capital):.2f} USDT, "
                     f"最小需求: {float(min_required_balance):.2f} USDT)"
                 )
-                return
+                return False
             
             # 计算仓位大小
+            stop_distance = abs(current_price - Decimal(str(signal.stop_loss))) if signal.stop_loss else (
+                current_price * self.strategy_config.stop_loss_pct)
             position_size = self._calculate_position_size(
-                self.available_balance,
-                self.strategy_config.base_position_size,
+                capital,
                 current_price,
-                min_order_size
+                min_order_size,
+                stop_distance
             )
             
+            # 按波动率状态调整仓位
+            size_scale = self._regime_params(symbol).size_scale
+            if size_scale != 1.0:
+                position_size = (position_size * Decimal(str(size_scale))).quantize(Decimal("0.000001"), rounding=ROUND_DOWN)
+                logger.info(f"[开仓] {symbol} 参数配置 {self.regime.profile(symbol)}，仓位 x{size_scale}")
+            
+            # 按信号置信度调整仓位
+            if self.config.confidence_sizing:
+                confidence_scale = self._confidence_multiplier(signal.confidence)
+                position_size = (position_size * Decimal(str(confidence_scale))).quantize(Decimal("0.000001"), rounding=ROUND_DOWN)
+                logger.info(f"[开仓] {symbol} 信号置信度 {signal.confidence:.2f}，仓位 x{confidence_scale:.2f}")
+            
+            # 按当前回撤调整仓位
+            if self.drawdown_throttle:
+                dd_scale = self.drawdown_throttle.scale()
+                if dd_scale < 1.0:
+                    position_size = (position_size * Decimal(str(dd_scale))).quantize(Decimal("0.000001"), rounding=ROUND_DOWN)
+                    logger.info(f"[开仓] {symbol} 当前回撤 {self.drawdown_throttle.drawdown:.2%}，仓位 x{dd_scale:.2f}")
+            
+            # 按净库存调整仓位：增加库存时缩小，减少库存时放大
+            if self.inventory_skew:
+                inventory = self.inventory_skew.update(self._portfolio_weights())
+                skew = self.inventory_skew.scale(1 if signal.direction == TradeDirection.LONG else -1)
+                if skew <= 0:
+                    logger.info(f"[开仓] {symbol} 净库存 {inventory:+.2f} 已达上限，不再增加同向敞口")
+                    return False
+                if skew != 1.0:
+                    position_size = (position_size * Decimal(str(skew))).quantize(Decimal("0.000001"), rounding=ROUND_DOWN)
+                    logger.info(f"[开仓] {symbol} 净库存 {inventory:+.2f}，仓位 x{skew:.2f}")
+            
             # 验证最小下单量
             if position_size < min_order_size:
                 logger.error(
                     f"[开仓] {symbol} 计算仓位({float(position_size)}) "
                     f"小于最小下单量({float(min_order_size)})"
                 )
-                return
+                return False
             
             # 计算杠杆仓位
-            leverage_position = position_size * Decimal(str(self.strategy_config.leverage))
+            leverage = self.strategy_config.get_leverage(symbol)
+            leverage_position = position_size * Decimal(str(leverage))
+            
+            # 组合波动率目标：按加入后的组合波动率缩放仓位
+            if self.risk_model and self.balance > 0:
+                sign = 1 if signal.direction == TradeDirection.LONG else -1
+                weight = sign * float(leverage_position * current_price / self.balance)
+                scale = self.risk_model.entry_scale(symbol, weight, self._portfolio_weights())
+                if scale != 1.0:
+                    scaled = (leverage_position * Decimal(str(scale))).quantize(Decimal("0.000001"), rounding=ROUND_DOWN)
+                    logger.info(
+                        f"[开仓] {symbol} 组合波动率目标缩放 x{scale:.3f}: "
+                        f"{float(leverage_position):.6f} -> {float(scaled):.6f}"
+                    )
+                    leverage_position = scaled
+                if leverage_position < min_order_size:
+                    logger.info(f"[开仓] {symbol} 缩放后仓位小于最小下单量，跳过（组合风险已达目标）")
+                    return False
+            
+            # 保证金检查：所需保证金超过可用保证金时缩小仓位，不足最小下单量时放弃开仓
+            leverage_position = self._fit_to_margin(symbol, leverage_position, current_price, min_order_size)
+            if leverage_position <= 0:
+                return False
+            
+            # 按合约数量步长向下取整
+            leverage_position = self._round_to_step(symbol, leverage_position)
+            if leverage_position < min_order_size:
+                logger.info(f"[开仓] {symbol} 按数量步长取整后小于最小下单量，跳过")
+                return False
+            
+            # 盘口过滤：检查当前价差和对手盘可见深度
+            if self.liquidity_filter:
+                side = OrderSide.BUY if signal.direction == TradeDirection.LONG else OrderSide.SELL
+                book = self.price_stream.get_order_book(symbol) if self.price_stream else None
+                reason = self.liquidity_filter.check(symbol, book, side, float(leverage_position))
+                if reason:
+                    spread = f"{LiquidityFilter.spread_bps(book):.2f} bps" if reason != LiquidityFilter.NO_BOOK else "N/A"
+                    logger.info(f"[开仓] {symbol} 盘口过滤跳过开仓（{reason}，价差 {spread}，下单量 {float(leverage_position):.6f}）")
+                    return False
             
             logger.info(f"[开仓] {symbol} ====================================")
             logger.info(f"[开仓] 当前价格: {float(current_price):.2f} USDT")
             logger.info(f"[开仓] 基础仓位: {float(position_size):.6f}")
-            logger.info(f"[开仓] 杠杆仓位: {float(leverage_position):.6f} ({self.strategy_config.leverage}x)")
+            logger.info(f"[开仓] 杠杆仓位: {float(leverage_position):.6f} ({leverage}x)")
             logger.info(f"[开仓] ✅ 仓位检查通过（>= {float(min_order_size)}）")
+            if flip:
+                logger.info(f"[开仓] 反手: 同一订单平掉 {flip.direction.value} 持仓 {flip.size}")
             logger.info(f"[开仓] ====================================")
             
-            # 设置杠杆
-            await self.client.set_leverage(symbol, self.strategy_config.leverage)
+            # 信号时刻的到达价格（滑点基准）
+            arrival_price = self._arrival_price(symbol, current_price)
             
             # 创建订单
             order = Order(
                 symbol=symbol,
                 side=OrderSide.BUY if signal.direction == TradeDirection.LONG else OrderSide.SELL,
                 order_type=OrderType.MARKET,
-                quantity=float(leverage_position),
-                leverage=self.strategy_config.leverage
+                quantity=float(leverage_position + Decimal(str(flip.size))) if flip else float(leverage_position),
+                leverage=leverage,
+                position_side=signal.direction if self.position_mode == PositionMode.HEDGE else None
             )
             
-            # 下单
-            result = await self.client.place_order(order)
-            logger.info(f"[开仓] {symbol} 订单提交成功: {result}")
+            # 记录止盈止损，成交回报建仓时使用
+            self.pending_exits[symbol] = signal
+            
+            if self.shadow:
+                self.shadow.on_live_order(symbol, f"OPEN_{signal.direction.value}", current_price)
+            
+            self._record_signal_to_order(symbol)
+            
+            # 下单（限价转市价策略依赖订单回报，在后台执行，不阻塞其他交易对）
+            if self.router.policy == ExecutionPolicy.MARKET or not self._fills_streaming:
+                result = await self.client.place_order(order)
+                logger.info(f"[开仓] {symbol} 订单提交成功: {result}")
+                order_id = self._track_order(order, result, arrival_price)
+                if flip and order_id:
+                    # 成交回报到达前原持仓仍在，不重复反手或平仓
+                    self._closing_orders[position_key(symbol, flip.direction, self.position_mode)] = order_id
+            else:
+                self._execution_tasks[symbol] = asyncio.create_task(
+                    self._execute_entry(symbol, order, float(arrival_price))
+                )
             
             # 记录交易时间
-            self.last_trade_time = int(datetime.now().timestamp() * 1000)
+            self.last_trade_times[symbol] = int(time.time() * 1000)
+            self.daily.record_entry()
+            return True
             
+        except OrderStateUnknownError as e:
+            self._hold_uncertain(symbol, f"[开仓] {symbol} 下单结果未知（{e}）")
+            return True
         except Exception as e:
             logger.error(f"[开仓] {symbol} 失败: {e}")
+            if self.trading_status.record_error(symbol, e):
+                self._check_trading_status()
+            else:
+                capture_error(e, self.name, symbol, stage="open_position")
+            return False
     
-    async def _manage_position(self, symbol: str, signal: TradeSignal, klines: List[PriceData]):
+    @traced("trade.manage_position")
+    async def _manage_position(self, position: Position, signal: TradeSignal, klines: List[PriceData]):
         """管理持仓（止盈止损）"""
-        position = self.positions.get(symbol)
-        if not position:
+        symbol = position.symbol
+        key = position_key(symbol, position.direction, self.position_mode)
+        
+        # 开仓订单仍在执行或平仓订单尚未完成，等待成交回报更新持仓
+        if self._order_in_flight(symbol, key):
             return
         
         try:
             current_price = Decimal(str(self._get_current_price(klines)))
-            entry_price = Decimal(str(position.entry_price))
-            
-            # 计算盈亏
-            pnl = self._calculate_pnl(position, current_price)
             
-            # 检查止盈
-            if position.take_profit > 0:
-                if position.direction == TradeDirection.LONG and current_price >= Decimal(str(position.take_profit)):
-                    logger.info(f"[平仓] {symbol} 触发止盈 (价格: {float(current_price):.2f})")
-                    await self._close_position(symbol, current_price, pnl)
-                    return
-                    
-                elif position.direction == TradeDirection.SHORT and current_price <= Decimal(str(position.take_profit)):
-                    logger.info(f"[平仓] {symbol} 触发止盈 (价格: {float(current_price):.2f})")
-                    await self._close_position(symbol, current_price, pnl)
-                    return
-            
-            # 检查止损
-            if position.stop_loss > 0:
-                if position.direction == TradeDirection.LONG and current_price <= Decimal(str(position.stop_loss)):
-                    logger.info(f"[平仓] {symbol} 触发止损 (价格: {float(current_price):.2f})")
-                    await self._close_position(symbol, current_price, pnl)
-                    return
-                    
-                elif position.direction == TradeDirection.SHORT and current_price >= Decimal(str(position.stop_loss)):
-                    logger.info(f"[平仓] {symbol} 触发止损 (价格: {float(current_price):.2f})")
-                    await self._close_position(symbol, current_price, pnl)
-                    return
+            if await self._check_exit_levels(position, current_price):
+                return
             
-            # 检查反向信号
+            # 检查反向信号（启用反手时一笔订单平仓并反向开仓，无法开仓时只平仓）
             if signal.direction != TradeDirection.HOLD and signal.direction != position.direction:
+                if self.config.flip_on_reversal and self.position_mode == PositionMode.ONE_WAY:
+                    if await self._flip_position(position, signal, klines):
+                        return
                 logger.info(f"[平仓] {symbol} 反向信号，平仓")
-                await self._close_position(symbol, current_price, pnl)
+                await self._close_position(position, current_price, self._calculate_pnl(position, current_price))
                 
         except Exception as e:
             logger.error(f"[管理持仓] {symbol} 失败: {e}")
+            capture_error(e, self.name, symbol, stage="manage_position", direction=position.direction.value,
+                          size=position.size, entry_price=position.entry_price)
     
-    async def _close_position(self, symbol: str, exit_price: Decimal, pnl: Decimal):
-        """平仓"""
-        position = self.positions.get(symbol)
-        if not position:
-            return
+    async def _flip_position(self, position: Position, signal: TradeSignal, klines: List[PriceData]) -> bool:
+        """
+        反手：反向信号时以一笔订单平掉持仓并反向开仓（单向持仓模式）
+        
+        成交回报按持仓数量结算平仓盈亏（手续费按数量比例分摊），超出部分建立反向持仓。
+        
+        Returns:
+            bool: 是否已提交反手订单（OCO止盈腿撤销失败时同样返回True，暂不处理该持仓）
+        """
+        symbol = position.symbol
+        key = position_key(symbol, position.direction, self.position_mode)
+        if self.oco and not await self.oco.cancel(key):
+            logger.warning(f"[反手] {symbol} OCO止盈腿撤销失败，暂不反手")
+            return True
+        
+        logger.info(f"[反手] {symbol} 反向信号，{position.direction.value} {position.size} -> {signal.direction.value}")
+        if not await self._open_position(symbol, signal, klines, flip=position):
+            return False
+        self.flip_count += 1
+        return True
+    
+    async def _check_exit_levels(self, position: Position, current_price: Decimal) -> bool:
+        """
+        检查持仓的止盈止损，触发时平仓
+        
+        Returns:
+            bool: 是否触发（已提交平仓）
+        """
+        symbol = position.symbol
+        key = position_key(symbol, position.direction, self.position_mode)
+        pnl = self._calculate_pnl(position, current_price)
+        
+        # 止盈止损按配置的触发价格类型判断（默认标记价格，与交易所一致）
+        trigger_price = self._trigger_price(symbol, current_price)
+        
+        # 检查止盈（已挂出OCO止盈腿时由盘口成交）
+        if position.take_profit > 0 and not (self.oco and self.oco.get(key)):
+            if position.direction == TradeDirection.LONG and trigger_price >= Decimal(str(position.take_profit)):
+                logger.info(f"[平仓] {symbol} 触发止盈 (触发价格: {float(trigger_price):.2f})")
+                await self._close_position(position, current_price, pnl)
+                return True
+                
+            elif position.direction == TradeDirection.SHORT and trigger_price <= Decimal(str(position.take_profit)):
+                logger.info(f"[平仓] {symbol} 触发止盈 (触发价格: {float(trigger_price):.2f})")
+                await self._close_position(position, current_price, pnl)
+                return True
+        
+        # 检查止损
+        if position.stop_loss > 0:
+            if position.direction == TradeDirection.LONG and trigger_price <= Decimal(str(position.stop_loss)):
+                logger.info(f"[平仓] {symbol} 触发止损 (触发价格: {float(trigger_price):.2f})")
+                await self._close_position(position, current_price, pnl, urgent=True)
+                return True
+                
+            elif position.direction == TradeDirection.SHORT and trigger_price >= Decimal(str(position.stop_loss)):
+                logger.info(f"[平仓] {symbol} 触发止损 (触发价格: {float(trigger_price):.2f})")
+                await self._close_position(position, current_price, pnl, urgent=True)
+                return True
+        return False
+    
+    @traced("trade.close_position")
+    async def _close_position(self, position: Position, exit_price: Decimal, pnl: Decimal, urgent: bool = False,
+                              attempt: int = 0, source: str = "") -> bool:
+        """
+        平仓
+        
+        大仓位（名义价值不低于阈值）在非紧急情况下按VWAP分批平仓，止损等紧急平仓直接市价成交。
+        平仓订单均为只减仓订单（持仓数量与交易所不一致时不会反向开仓），订单终结后确认持仓已归零，
+        未归零时按剩余数量重新提交（attempt 为已重试次数，重试沿用 source）。
+        
+        Returns:
+            bool: 是否已提交平仓订单（含开始VWAP分批平仓）
+        """
+        symbol = position.symbol
+        annotate(symbol=symbol, direction=position.direction.value, size=position.size)
+        
+        # 止盈止损监控任务与主循环可能同时触发平仓，同一持仓同时只提交一笔平仓订单
+        key = position_key(symbol, position.direction, self.position_mode)
+        if key in self._closing_now:
+            return False
+        if not self.trading_status.can_close(symbol):
+            logger.debug(f"[平仓] {symbol} 暂停交易（{self.trading_status.reasons[symbol]}），恢复后再平仓")
+            return False
+        self._closing_now.add(key)
         
         try:
+            # 先撤销OCO止盈腿，撤单失败时止盈腿可能已成交，等待成交回报后再判断
+            if self.oco and not await self.oco.cancel(key, stop_triggered=urgent):
+                logger.warning(f"[平仓] {symbol} OCO止盈腿撤销失败，暂不平仓")
+                return False
+            
+            # 信号时刻的到达价格（滑点基准）
+            arrival_price = self._arrival_price(symbol, exit_price)
+            
             # 创建平仓订单（反向操作）
             order = Order(
                 symbol=symbol,
                 side=OrderSide.SELL if position.direction == TradeDirection.LONG else OrderSide.BUY,
                 order_type=OrderType.MARKET,
                 quantity=position.size,
-                leverage=position.leverage
+                leverage=position.leverage,
+                reduce_only=True,
+                position_side=position.direction if self.position_mode == PositionMode.HEDGE else None
             )
             
-            # 下单
-            result = await self.client.place_order(order)
-            logger.info(f"[平仓] {symbol} 订单提交成功: {result}")
+            if self.shadow:
+                self.shadow.on_live_order(symbol, "CLOSE", exit_price)
+            
+            self._record_signal_to_order(symbol)
+            
+            notional = position.size * float(exit_price)
+            if self.vwap and not urgent and self._fills_streaming and notional >= self.config.vwap_min_notional:
+                logger.info(f"[平仓] {symbol} 持仓名义价值 {notional:.2f} USDT，VWAP分批平仓 (预估盈亏: {float(pnl):.4f} USDT)")
+                self._unwind_tasks[key] = asyncio.create_task(self._execute_unwind(key, order, float(arrival_price)))
+                return True
+            
+            # 下单（数量为实际持仓数量，持仓和交易记录由成交回报更新）
+            result = await self.client.place_order(order, source=source)
+            logger.info(
+                f"[平仓] {symbol} 订单提交成功: {result} "
+                f"(预估盈亏: {float(pnl):.4f} USDT)"
+            )
+            
+            order_id = self._track_order(order, result, arrival_price)
+            if order_id:
+                self._closing_orders[key] = order_id
+                self._close_confirms[key] = asyncio.create_task(
+                    self._confirm_close(key, order_id, exit_price, attempt, source))
+            return True
+            
+        except OrderStateUnknownError as e:
+            self._hold_uncertain(key, f"[平仓] {symbol} 下单结果未知（{e}）")
+            return False
+        except Exception as e:
+            logger.error(f"[平仓] {symbol} 失败: {e}")
+            if self.trading_status.record_error(symbol, e):
+                self._check_trading_status()
+            else:
+                capture_error(e, self.name, symbol, stage="close_position", attempt=attempt)
+            return False
+        finally:
+            self._closing_now.discard(key)
+    
+    async def _confirm_close(self, key: str, order_id: str, exit_price: Decimal, attempt: int, source: str = ""):
+        """等待平仓订单终结并确认持仓已归零，未归零时按剩余数量重新提交只减仓平仓订单"""
+        tracked = await self.order_tracker.wait_until_done(order_id, self.CLOSE_CONFIRM_TIMEOUT)
+        if tracked and not tracked.is_done:
+            # 订单仍在执行或状态未更新，不重复下单（订单终结前该持仓不会再次平仓）
+            logger.warning(f"[平仓] {tracked.symbol} 平仓订单 {order_id} {self.CLOSE_CONFIRM_TIMEOUT}秒内未终结（{tracked.status}）")
+            return
+        await asyncio.sleep(self.CLOSE_FILL_GRACE_SECONDS)
+        
+        position = self.positions.get(key)
+        if not position:
+            self.close_stats["confirmed"] += 1
+            return
+        
+        symbol = position.symbol
+        status = tracked.status if tracked else "未知"
+        if attempt >= self.config.close_retry_attempts:
+            self.close_stats["failed"] += 1
+            logger.error(f"[平仓] {symbol} 已重试 {attempt} 次，持仓仍剩余 {position.size}（最后订单 {order_id} {status}），需人工处理")
+            capture_error(f"{symbol} 平仓重试耗尽，持仓仍剩余 {position.size}", self.name, symbol, order_id,
+                          stage="close_confirm", attempt=attempt, status=str(status))
+            return
+        
+        self.close_stats["retries"] += 1
+        logger.warning(f"[平仓] {symbol} 平仓订单 {order_id} {status}，持仓剩余 {position.size}，重新提交平仓（第 {attempt + 1} 次）")
+        price = self._get_current_price(self.price_history.get(symbol, [])) or exit_price
+        await self._close_position(position, price, self._calculate_pnl(position, price), urgent=True,
+                                   attempt=attempt + 1, source=source)
+    
+    def _hold_uncertain(self, key: str, message: str):
+        """下单结果未知（请求超时或响应缺少订单ID）：订单可能已被交易所接受，暂停对该交易对/持仓下单，等待成交回报"""
+        self._uncertain_orders[key] = time.monotonic() + self.UNCERTAIN_ORDER_HOLD_SECONDS
+        logger.warning(f"{message}，订单状态未知，{self.UNCERTAIN_ORDER_HOLD_SECONDS}秒内不重新下单")
+    
+    def _order_uncertain(self, key: str) -> bool:
+        """交易对/持仓是否处于下单结果未知后的暂停期"""
+        deadline = self._uncertain_orders.get(key)
+        if deadline is None:
+            return False
+        if time.monotonic() < deadline:
+            return True
+        del self._uncertain_orders[key]
+        return False
+    
+    async def _confirm_orders(self):
+        """
+        下单确认：提交超过确认超时仍未收到订单更新或成交回报的订单按ID查询交易所
+        
+        - 查到订单：按查询结果更新订单状态（视为已确认）
+        - 查不到：标记为已提交、未确认（UNCONFIRMED），该交易对（平仓订单为该持仓）暂停下单，之后每次检查继续查询
+        - 持续 order_unconfirmed_expire_seconds 仍查不到：按已拒绝处理，恢复下单
+        """
+        timeout = self.config.order_ack_timeout_seconds
+        if timeout <= 0 or not self._fills_streaming or time.time() - self._last_ack_check < timeout:
+            return
+        self._last_ack_check = time.time()
+        pending = self.order_tracker.get_unconfirmed(int(timeout * 1000))
+        if not pending:
+            return
+        
+        try:
+            found = {str(o.get("id")): o for o in await self.client.get_orders_by_id([o.order_id for o in pending])}
+        except Exception as e:
+            logger.warning(f"[下单确认] 查询 {len(pending)} 笔未确认订单失败: {e}")
+            return
+        
+        now_ms = int(time.time() * 1000)
+        for tracked in pending:
+            key = self._order_hold_key(tracked)
+            data = found.get(tracked.order_id)
+            if data:
+                if tracked.status == UNCONFIRMED:
+                    self.order_confirm_stats["confirmed_late"] += 1
+                    self._uncertain_orders.pop(key, None)
+                    logger.info(f"[下单确认] {tracked.symbol} 订单 {tracked.order_id} 已查询确认（{data.get('status')}）")
+                self.order_confirm_stats["polled"] += 1
+                self.order_tracker.on_order_update({**data, "symbol": tracked.symbol})
+            elif tracked.status != UNCONFIRMED:
+                self.order_confirm_stats["unconfirmed"] += 1
+                self.order_tracker.mark_unconfirmed(tracked.order_id)
+                self._hold_uncertain(key, f"[下单确认] {tracked.symbol} 订单 {tracked.order_id} {timeout}秒内未收到确认且查询不到")
+            elif now_ms - tracked.created_at >= self.config.order_unconfirmed_expire_seconds * 1000:
+                self.order_confirm_stats["missing"] += 1
+                self.order_tracker.mark_missing(tracked.order_id)
+                self._uncertain_orders.pop(key, None)
+                logger.error(f"[下单确认] {tracked.symbol} 订单 {tracked.order_id} "
+                             f"{self.config.order_unconfirmed_expire_seconds}秒内在交易所始终不存在，按已拒绝处理")
+                capture_error(f"{tracked.symbol} 订单 {tracked.order_id} 提交后始终未确认", self.name, tracked.symbol,
+                              tracked.order_id, stage="order_confirm")
+            else:
+                # 未确认期间持续暂停下单
+                self._uncertain_orders[key] = time.monotonic() + self.UNCERTAIN_ORDER_HOLD_SECONDS
+    
+    def _order_hold_key(self, tracked: TrackedOrder) -> str:
+        """订单结果未知时暂停下单的范围：开仓订单为交易对，平仓订单为对应持仓"""
+        if not tracked.reduce_only:
+            return tracked.symbol
+        key = next((k for k, order_id in self._closing_orders.items() if order_id == tracked.order_id), None)
+        if key:
+            return key
+        direction = tracked.position_side or (
+            TradeDirection.LONG if tracked.side == OrderSide.SELL else TradeDirection.SHORT
+        )
+        return position_key(tracked.symbol, direction, self.position_mode)
+    
+    def _order_in_flight(self, symbol: str, key: str) -> bool:
+        """交易对的开仓订单或该持仓的平仓订单（含VWAP分批平仓）是否仍在执行（含下单结果未知）"""
+        if self._order_uncertain(key):
+            return True
+        
+        task = self._execution_tasks.get(symbol)
+        if task and not task.done():
+            return True
+        
+        unwind = self._unwind_tasks.get(key)
+        if unwind and not unwind.done():
+            return True
+        
+        confirm = self._close_confirms.get(key)
+        if confirm and not confirm.done():
+            return True
+        
+        closing_order_id = self._closing_orders.get(key)
+        if closing_order_id:
+            tracked = self.order_tracker.get(closing_order_id)
+            if tracked and not tracked.is_done:
+                return True
+            del self._closing_orders[key]
+        return False
+    
+    async def _handle_external_signal(self, action: str, signal: TradeSignal):
+        """
+        执行外部信号（Webhook/信号总线）
+        
+        平仓动作平掉该交易对的全部持仓；开仓动作先平掉反向持仓（单向持仓模式下等待平仓完成），
+        再按内部信号相同的流程开仓（风控、盘口过滤、仓位缩放等）。按最新行情价格执行，
+        没有行情时使用告警中的价格；告警未提供止盈止损时按策略配置计算。
+        """
+        symbol = signal.symbol
+        if not self.warmup.passed:
+            raise ValueError("启动检查未通过，暂不交易")
+        if not self.reconciled:
+            raise ValueError("启动对账未完成，暂不交易")
+        klines = self.price_history.get(symbol, [])
+        price = self._get_current_price(klines) or Decimal(str(signal.price))
+        if price <= 0:
+            raise ValueError(f"{symbol} 无可用价格")
+        
+        update: Dict[str, Any] = {"price": float(price)}
+        if action == ACTION_OPEN and not (signal.stop_loss and signal.take_profit):
+            stop_loss, take_profit = self._exit_levels(symbol, signal.direction, price)
+            update["stop_loss"] = signal.stop_loss or stop_loss
+            update["take_profit"] = signal.take_profit or take_profit
+        signal = signal.model_copy(update=update)
+        
+        closing = []
+        for position in self._positions_for(symbol):
+            if action == ACTION_OPEN and position.direction == signal.direction:
+                continue
+            key = position_key(symbol, position.direction, self.position_mode)
+            if not self._order_in_flight(symbol, key):
+                logger.info(f"[外部信号] {symbol} 平仓 {position.direction.value}")
+                await self._close_position(position, price, self._calculate_pnl(position, price))
+            closing.append(key)
+        
+        if action != ACTION_OPEN:
+            return
+        
+        if self.position_mode == PositionMode.ONE_WAY and closing:
+            # 等待反向持仓平仓完成后再开仓，避免净持仓直接翻转
+            for key in closing:
+                order_id = self._closing_orders.get(key)
+                if order_id:
+                    await self.order_tracker.wait_until_done(order_id, self.CLOSE_CONFIRM_TIMEOUT)
+            if self._positions_for(symbol):
+                logger.warning(f"[外部信号] {symbol} 反向持仓尚未平完，放弃开仓")
+                return
+        
+        if any(p.direction == signal.direction for p in self._positions_for(symbol)):
+            logger.info(f"[外部信号] {symbol} 已有{signal.direction.value}持仓，忽略开仓信号")
+            return
+        
+        await self._open_position(symbol, signal, klines)
+    
+    def _manual_symbol(self, symbol: str) -> str:
+        """手动操作的交易对必须是策略当前的交易对（或仍有持仓的离开集合的交易对）"""
+        if symbol not in self._managed_symbols():
+            raise ValueError(f"交易对 {symbol} 不在策略 {self.name} 的交易对列表中")
+        return symbol
+    
+    async def manual_order(self, symbol: str, side: OrderSide, quantity: float, price: Optional[float] = None,
+                           reduce_only: bool = False) -> str:
+        """
+        手动下单（不带限价为市价单），与自动交易经过相同的下单前检查、订单通道和审计日志
+        
+        Returns:
+            str: 订单ID
+        
+        Raises:
+            ValueError: 参数无效、交易对暂停交易或没有行情
+            OrderRejected / DuplicateOrderError / StrategyError: 下单被拒绝
+        """
+        symbol = self._manual_symbol(symbol)
+        if quantity <= 0 or (price is not None and price <= 0):
+            raise ValueError("数量和限价必须大于0")
+        allowed = self.trading_status.can_close(symbol) if reduce_only else self.trading_status.can_open(symbol)
+        if not allowed:
+            raise ValueError(f"{symbol} 当前{STATUS_LABELS[self.trading_status.status[symbol]]}（{self.trading_status.reasons[symbol]}）")
+        last_price = self._get_current_price(self.price_history.get(symbol, []))
+        reference_price = Decimal(str(price)) if price else (self._arrival_price(symbol, last_price) if last_price else None)
+        if not reference_price:
+            raise ValueError(f"{symbol} 没有行情数据，无法确定参考价格")
+        
+        direction = TradeDirection.LONG if (side == OrderSide.BUY) != reduce_only else TradeDirection.SHORT
+        order = Order(
+            symbol=symbol,
+            side=side,
+            order_type=OrderType.LIMIT if price else OrderType.MARKET,
+            quantity=quantity,
+            price=price,
+            leverage=self.strategy_config.get_leverage(symbol),
+            reduce_only=reduce_only,
+            position_side=direction if self.position_mode == PositionMode.HEDGE else None
+        )
+        result = await self.client.place_order(order, source="manual")
+        order_id = self._track_order(order, result, reference_price)
+        self.manual_stats["orders"] += 1
+        logger.warning(f"[手动] {symbol} {side.value} {order.order_type.value} {order.quantity}"
+                       + (f" @ {order.price}" if price else "") + (" 只减仓" if reduce_only else "") + f" 已提交（ID: {order_id}）")
+        return order_id
+    
+    async def manual_cancel(self, symbol: str, order_id: str):
+        """手动撤单"""
+        symbol = self._manual_symbol(symbol)
+        tracked = self.order_tracker.get(order_id)
+        if tracked:
+            tracked.cancel_requested = True
+        await self.client.cancel_order(symbol, order_id, source="manual")
+        self.manual_stats["cancels"] += 1
+        logger.warning(f"[手动] {symbol} 已撤销订单 {order_id}")
+    
+    async def manual_close(self, symbol: str, direction: Optional[TradeDirection] = None) -> int:
+        """
+        手动市价平仓（按紧急平仓处理，不分批）
+        
+        Returns:
+            int: 提交平仓的持仓数
+        
+        Raises:
+            ValueError: 没有持仓，或有持仓的平仓订单未提交（正在平仓、暂停交易、OCO撤单失败或下单失败）
+        """
+        symbol = self._manual_symbol(symbol)
+        positions = [p for p in self._positions_for(symbol) if direction is None or p.direction == direction]
+        if not positions:
+            raise ValueError(f"{symbol} 没有{direction.value if direction else ''}持仓")
+        failed = []
+        for position in positions:
+            price = self._get_current_price(self.price_history.get(symbol, [])) or Decimal(str(position.entry_price))
+            logger.warning(f"[手动] 平仓 {symbol} {position.direction.value} {position.size}")
+            if await self._close_position(position, price, self._calculate_pnl(position, price), urgent=True,
+                                          source="manual"):
+                self.manual_stats["closes"] += 1
+            else:
+                failed.append(position.direction.value)
+        if failed:
+            submitted = len(positions) - len(failed)
+            raise ValueError(f"{symbol} {'/'.join(failed)} 持仓的平仓订单未提交（已提交 {submitted} 个），原因见日志")
+        return len(positions)
+    
+    async def manual_set_leverage(self, symbol: str, leverage: int):
+        """
+        手动调整杠杆：更新配置的杠杆并向交易所确认（确认前该交易对禁止开仓）
+        
+        Raises:
+            ValueError: 杠杆超出范围，或交易所未确认
+        """
+        symbol = self._manual_symbol(symbol)
+        spec = self.client.get_contract_spec(symbol)
+        max_leverage = spec.max_leverage if spec and spec.max_leverage else 100
+        if leverage < 1 or leverage > max_leverage:
+            raise ValueError(f"{symbol} 杠杆倍数必须在1-{max_leverage}之间")
+        self.strategy_config.symbol_leverage[symbol] = leverage
+        self.manual_stats["leverage"] += 1
+        logger.warning(f"[手动] {symbol} 调整杠杆为 {leverage}x")
+        await self._configure_symbol_leverage(symbol)
+        if symbol in self.leverage_blocked:
+            raise ValueError(f"{symbol} 杠杆未确认（{self.leverage_blocked[symbol]}），禁止开仓")
+    
+    def manual_pause(self, symbol: str, paused: bool = True):
+        """手动暂停/恢复交易对按信号开仓（已有持仓照常止盈止损和平仓）"""
+        symbol = self._manual_symbol(symbol)
+        if paused:
+            self.manual_paused.add(symbol)
+            self.manual_stats["pauses"] += 1
+        else:
+            self.manual_paused.discard(symbol)
+        logger.warning(f"[手动] {symbol} {'暂停' if paused else '恢复'}开仓")
+    
+    async def _execute_entry(self, symbol: str, order: Order, reference_price: float):
+        """通过订单路由执行开仓订单"""
+        try:
+            results = await self.router.execute(order, reference_price)
+            logger.info(f"[开仓] {symbol} 订单执行完成: {results}")
+        except OrderStateUnknownError as e:
+            self._hold_uncertain(symbol, f"[开仓] {symbol} 下单结果未知（{e}）")
+        except Exception as e:
+            logger.error(f"[开仓] {symbol} 订单执行失败: {e}")
+            capture_error(e, self.name, symbol, stage="execute_entry", side=order.side.value, quantity=order.quantity)
+    
+    async def _execute_unwind(self, key: str, order: Order, reference_price: float):
+        """按VWAP分批平仓，持仓平完后提前结束"""
+        def remaining() -> float:
+            position = self.positions.get(key)
+            return position.size if position else 0.0
+        
+        try:
+            min_size = float(self._min_order_size(order.symbol))
+            results = await self.vwap.execute(order, reference_price, remaining, min_size)
+            logger.info(f"[平仓] {order.symbol} VWAP分批平仓完成，共 {len(results)} 笔子订单")
+        except Exception as e:
+            logger.error(f"[平仓] {order.symbol} VWAP分批平仓失败: {e}")
+            capture_error(e, self.name, order.symbol, stage="vwap_unwind", side=order.side.value, quantity=order.quantity)
+    
+    def _order_reference_price(self, order: Order) -> Optional[float]:
+        """下单检查用的参考价格：限价/触发价，市价单为最新价格"""
+        if order.price:
+            return order.price
+        if order.trigger_price:
+            return order.trigger_price
+        history = self.price_history.get(order.symbol)
+        return history[-1].close if history else None
+    
+    def _opening_quantity(self, order: Order) -> float:
+        """订单中开仓的数量（单向持仓模式下先抵消反向持仓，如反手订单）"""
+        if order.reduce_only:
+            return 0.0
+        if self.position_mode == PositionMode.HEDGE:
+            return order.quantity
+        direction = TradeDirection.LONG if order.side == OrderSide.BUY else TradeDirection.SHORT
+        opposite = sum(p.size for p in self._positions_for(order.symbol) if p.direction != direction)
+        return max(order.quantity - opposite, 0.0)
+    
+    def _check_price_deviation(self, order: Order) -> Optional[str]:
+        """下单检查：限价和触发价不偏离盘口中间价（或已确认价格）超过上限"""
+        mid = self._book_mid(order.symbol)
+        for price in (order.price, order.trigger_price):
+            if price:
+                reason = self.price_guard.check(order.symbol, price, mid)
+                if reason:
+                    return reason
+        return None
+    
+    def _check_wash_trade(self, order: Order) -> Optional[str]:
+        """下单检查：对敲防护（市价单按盘口中间价计算价格距离）"""
+        price = order.price or self._book_mid(order.symbol)
+        return self.wash_guard.check(order, price, self.order_tracker.get_open_orders(order.symbol))
+    
+    def _check_min_notional(self, order: Order) -> Optional[str]:
+        """下单检查：开仓订单名义价值不低于配置的最小值"""
+        quantity = self._opening_quantity(order)
+        if quantity <= 0 or self.config.min_order_notional <= 0:
+            return None
+        price = self._order_reference_price(order)
+        if not price:
+            return None
+        notional = quantity * price
+        if notional < self.config.min_order_notional:
+            return f"名义价值 {notional:.2f} USDT 低于最小值 {self.config.min_order_notional} USDT"
+        return None
+    
+    def _check_margin(self, order: Order) -> Optional[str]:
+        """下单检查：开仓订单所需保证金不超过可用保证金"""
+        quantity = self._opening_quantity(order)
+        if quantity <= 0:
+            return None
+        price = self._order_reference_price(order)
+        if not price:
+            return None
+        leverage = order.leverage or self.strategy_config.get_leverage(order.symbol)
+        required = Decimal(str(quantity)) * Decimal(str(price)) / Decimal(str(leverage))
+        free_margin = self._free_margin()
+        if required > free_margin:
+            return f"所需保证金 {float(required):.2f} USDT 超过可用保证金 {float(free_margin):.2f} USDT"
+        return None
+    
+    def _check_risk_limit(self, order: Order) -> Optional[str]:
+        """下单检查：开仓后该交易对同方向持仓占用的保证金不超过资金的 max_position_pct"""
+        quantity = self._opening_quantity(order)
+        if quantity <= 0 or self.config.max_position_pct <= 0:
+            return None
+        price = self._order_reference_price(order)
+        capital = self.allocation.capital(self.balance) if self.allocation else self.balance
+        if not price or capital <= 0:
+            return None
+        direction = order.position_side or (TradeDirection.LONG if order.side == OrderSide.BUY else TradeDirection.SHORT)
+        size = quantity + sum(p.size for p in self._positions_for(order.symbol) if p.direction == direction)
+        leverage = order.leverage or self.strategy_config.get_leverage(order.symbol)
+        margin = size * price / leverage
+        limit = float(capital) * self.config.max_position_pct
+        if margin > limit:
+            return f"开仓后持仓保证金 {margin:.2f} USDT 超过上限 {limit:.2f} USDT（{self.config.max_position_pct:.0%}）"
+        return None
+    
+    def _min_order_size(self, symbol: str) -> Decimal:
+        """最小下单量：策略配置与交易所合约规格中较大者"""
+        min_size = self.strategy_config.get_min_order_size(symbol)
+        spec = self.client.get_contract_spec(symbol)
+        if spec and spec.min_order_size > 0:
+            min_size = max(min_size, Decimal(str(spec.min_order_size)))
+        return min_size
+    
+    def _round_to_step(self, symbol: str, quantity: Decimal) -> Decimal:
+        """数量按合约数量步长向下取整（合约规格未加载时原样返回）"""
+        spec = self.client.get_contract_spec(symbol)
+        if not spec or spec.step_size <= 0:
+            return quantity
+        step = Decimal(str(spec.step_size))
+        return (quantity / step).to_integral_value(rounding=ROUND_DOWN) * step
+    
+    def _regime_params(self, symbol: str) -> RegimeParams:
+        """当前波动率状态对应的参数（未启用时为默认参数）"""
+        return self.regime.params_for(symbol) if self.regime else RegimeParams()
+    
+    def _position_margin(self) -> Decimal:
+        """持仓占用的保证金（名义价值/杠杆）"""
+        return sum(
+            (Decimal(str(p.size)) * Decimal(str(p.entry_price)) / Decimal(str(p.leverage or self.strategy_config.get_leverage(p.symbol)))
+             for p in self.positions.values()),
+            Decimal("0")
+        )
+    
+    def _free_margin(self) -> Decimal:
+        """
+        可用保证金
+        
+        账户同步时交易所返回的可用余额，扣除此后新增持仓占用的保证金，
+        以及在途开仓订单剩余数量需要的保证金。
+        """
+        pending = sum(
+            (Decimal(str(o.remaining)) * Decimal(str(o.price or o.reference_price or 0))
+             / Decimal(str(self.strategy_config.get_leverage(o.symbol)))
+             for o in self.order_tracker.get_open_orders() if not o.reduce_only),
+            Decimal("0")
+        )
+        return self.available_balance - (self._position_margin() - self._synced_margin) - pending
+    
+    def _fit_to_margin(self, symbol: str, quantity: Decimal, price: Decimal, min_order_size: Decimal) -> Decimal:
+        """
+        按可用保证金调整开仓数量
+        
+        Args:
+            symbol: 交易对
+            quantity: 开仓数量（含杠杆）
+            price: 开仓价格
+            min_order_size: 最小下单量
+            
+        Returns:
+            Decimal: 调整后的数量；保证金不足以开最小下单量时返回0
+        """
+        # 单位数量需要的保证金（预留一定比例应对手续费和成交价偏离）
+        unit_margin = price / Decimal(str(self.strategy_config.get_leverage(symbol))) * Decimal(str(1 + self.config.margin_buffer_pct))
+        free_margin = self._free_margin()
+        if quantity * unit_margin <= free_margin:
+            return quantity
+        
+        fitted = (max(free_margin, Decimal("0")) / unit_margin).quantize(Decimal("0.000001"), rounding=ROUND_DOWN)
+        if fitted < min_order_size:
+            self.margin_stats["rejected"] += 1
+            logger.warning(
+                f"[开仓] {symbol} 可用保证金不足，放弃开仓（需要 {float(quantity * unit_margin):.2f} USDT，"
+                f"可用 {float(free_margin):.2f} USDT）"
+            )
+            return Decimal("0")
+        
+        self.margin_stats["shrunk"] += 1
+        logger.info(
+            f"[开仓] {symbol} 可用保证金 {float(free_margin):.2f} USDT 不足，"
+            f"仓位 {float(quantity):.6f} -> {float(fitted):.6f}"
+        )
+        return fitted
+    
+    def _equity(self) -> Decimal:
+        """账户权益：余额（含启动后的已实现盈亏，已扣手续费）加未实现盈亏"""
+        return self.balance + self._unrealized_pnl()
+    
+    def _managed_symbols(self) -> Set[str]:
+        """本策略管理的交易对：当前集合，加上离开集合后持仓尚未平掉的交易对（对账和持仓同步不能忽略）"""
+        return set(self.symbols) | set(self._winding_down) | {p.symbol for p in self.positions.values()}
+    
+    def _unrealized_pnl(self) -> Decimal:
+        """按标记价格计算的未实现盈亏（没有标记价格的交易对使用最新价）"""
+        prices = self._mark_prices()
+        return sum(
+            (self._calculate_pnl(p, Decimal(str(prices[p.symbol]))) for p in self.positions.values() if p.symbol in prices),
+            Decimal("0")
+        )
+    
+    def _latest_prices(self) -> Dict[str, float]:
+        """各交易对最新价格"""
+        return {
+            symbol: history[-1].close
+            for symbol, history in self.price_history.items() if history
+        }
+    
+    def _mark_prices(self) -> Dict[str, float]:
+        """各交易对标记价格（没有标记价格时为最新价）"""
+        prices = self._latest_prices()
+        prices.update({symbol: mark.mark_price for symbol, mark in self.mark_prices.items()})
+        return prices
+    
+    def _trigger_price(self, symbol: str, last_price: Decimal) -> Decimal:
+        """客户端止盈止损的触发价格"""
+        mark = self.mark_prices.get(symbol)
+        if self.config.exit_trigger_price_type == TriggerPriceType.MARK_PRICE.value and mark:
+            return Decimal(str(mark.mark_price))
+        return last_price
+    
+    def _liquidation_price(self, position: Position) -> float:
+        """
+        估算强平价格
+        
+        按逐仓近似：标记价格使持仓亏损达到初始保证金减去维持保证金时强平。
+        全仓模式下实际强平价取决于账户整体权益，仅作风险参考。
+        """
+        leverage = position.leverage or self.strategy_config.get_leverage(position.symbol)
+        mmr = self.config.maintenance_margin_rate
+        if position.direction == TradeDirection.LONG:
+            return position.entry_price * (1 - 1 / leverage) / (1 - mmr)
+        return position.entry_price * (1 + 1 / leverage) / (1 + mmr)
+    
+    def _check_critical_risks(self):
+        """严重风险告警：紧急停止开关打开、持仓标记价格接近预估强平价（每次进入风险状态只告警一次）"""
+        kill_switch = self.client.kill_switch.describe()
+        if kill_switch and not self._kill_switch_alerted:
+            self.alerts.send(self.name, "kill_switch", f"紧急停止开关已打开（{kill_switch}），只允许平仓", critical=True)
+        self._kill_switch_alerted = kill_switch is not None
+        
+        threshold = self.config.liquidation_alert_distance
+        if threshold <= 0:
+            return
+        marks = self._mark_prices()
+        at_risk: Set[str] = set()
+        for key, position in self.positions.items():
+            mark = marks.get(position.symbol)
+            if not mark:
+                continue
+            liquidation = self._liquidation_price(position)
+            distance = abs(mark - liquidation) / mark
+            if distance >= threshold:
+                continue
+            at_risk.add(key)
+            if key not in self._liquidation_alerted:
+                self.alerts.send(self.name, "liquidation_risk",
+                                 f"{position.symbol} {position.direction.value} 持仓标记价格 {mark:.4f} 距预估强平价 "
+                                 f"{liquidation:.4f} 仅 {distance*100:.2f}%", critical=True)
+        self._liquidation_alerted = at_risk
+    
+    def _check_trading_status(self):
+        """按合约元数据和下单错误更新各交易对的交易状态，状态变化时告警"""
+        changes = self.trading_status.refresh({s: self.client.get_contract_spec(s) for s in self.symbols})
+        for symbol, old, new, reason in changes:
+            if new == SymbolStatus.TRADING:
+                logger.info(f"[交易状态] {symbol} 已恢复正常交易（此前: {STATUS_LABELS[old]}）")
+                self.alerts.send(self.name, "trading_resumed", f"{symbol} 已恢复正常交易（此前: {STATUS_LABELS[old]}）")
+            else:
+                logger.warning(f"[交易状态] {symbol} {STATUS_LABELS[new]}: {reason}")
+                self.alerts.send(self.name, "trading_halted", f"{symbol} {STATUS_LABELS[new]}: {reason}")
+    
+    def _portfolio_weights(self) -> Dict[str, float]:
+        """当前持仓的带方向权重（名义敞口/权益，多头为正）"""
+        if self.balance <= 0:
+            return {}
+        
+        prices = self._latest_prices()
+        weights: Dict[str, float] = {}
+        for position in self.positions.values():
+            price = prices.get(position.symbol, position.entry_price)
+            sign = 1 if position.direction == TradeDirection.LONG else -1
+            weights[position.symbol] = weights.get(position.symbol, 0.0) + sign * position.size * price / float(self.balance)
+        return weights
+    
+    def _positions_for(self, symbol: str) -> List[Position]:
+        """获取交易对的所有持仓（双向持仓模式下最多多空各一个）"""
+        return [p for p in self.positions.values() if p.symbol == symbol]
+    
+    @property
+    def _fills_streaming(self) -> bool:
+        """是否能收到成交回报（模拟撮合引擎或已连接的私有WebSocket）"""
+        return self.client.is_paper or bool(self.price_stream and self.price_stream.private_connected)
+    
+    def _track_order(self, order: Order, result: Dict[str, Any], reference_price: Decimal) -> Optional[str]:
+        """
+        登记已提交的订单
+        
+        收不到成交回报时（私有WebSocket不可用），按参考价格全部成交处理。
+        
+        Returns:
+            Optional[str]: 订单ID
+        """
+        order_id = str((result or {}).get("data", {}).get("orderId", ""))
+        if not order_id:
+            return None
+        
+        self.order_tracker.register(order_id, order, float(reference_price))
+        
+        if not self._fills_streaming:
+            self._handle_fill(Fill(
+                symbol=order.symbol,
+                order_id=order_id,
+                side=order.side,
+                price=float(reference_price),
+                size=order.quantity,
+                timestamp=int(time.time() * 1000)
+            ))
+        return order_id
+    
+    def _book_mid(self, symbol: str) -> Optional[float]:
+        """盘口中间价（无盘口数据或盘口交叉时为None）"""
+        book = self.price_stream.get_order_book(symbol) if self.price_stream else None
+        if book and book.bids and book.asks:
+            best_bid = float(book.bids[0][0])
+            best_ask = float(book.asks[0][0])
+            if 0 < best_bid < best_ask:
+                return (best_bid + best_ask) / 2
+        return None
+    
+    def _arrival_price(self, symbol: str, last_price: Decimal) -> Decimal:
+        """到达价格：盘口中间价，无盘口数据时为最新价"""
+        mid = self._book_mid(symbol)
+        return Decimal(str(mid)) if mid else last_price
+    
+    def _on_order_done(self, tracked: TrackedOrder):
+        """订单完成：记录成交均价相对到达价格的滑点及TCA数据"""
+        if not self._fills_streaming:
+            # 没有成交回报时按到达价格虚拟成交，滑点无意义
+            return
+        
+        if self.tca:
+            self.tca.record_order(self.name, tracked, tracked.fills)
+        
+        record = self.slippage.record(
+            tracked.order_id, tracked.symbol, tracked.side, tracked.reduce_only,
+            tracked.reference_price, tracked.avg_fill_price, tracked.filled, tracked.updated_at
+        )
+        if record:
+            logger.info(
+                f"[滑点] {tracked.symbol} {tracked.side.value} {tracked.filled} "
+                f"到达价 {record.arrival_price:.4f} 成交均价 {record.avg_fill_price:.4f} "
+                f"滑点 {record.slippage_bps:.2f} bps ({record.cost:.4f} USDT)"
+            )
+        
+        # 开仓订单完成后为持仓挂出（或按新数量更新）OCO退出单（持仓在成交处理完成后才更新）
+        if self.oco and not tracked.reduce_only:
+            direction = tracked.position_side or (
+                TradeDirection.LONG if tracked.side == OrderSide.BUY else TradeDirection.SHORT
+            )
+            asyncio.create_task(self._place_exit_orders(position_key(tracked.symbol, direction, self.position_mode)))
+    
+    async def _place_exit_orders(self, key: str):
+        """为持仓挂出OCO退出单"""
+        position = self.positions.get(key)
+        if position:
+            position_side = position.direction if self.position_mode == PositionMode.HEDGE else None
+            await self.oco.place(key, position, position_side)
+    
+    def _on_tracked_order_update(self, tracked: TrackedOrder):
+        """订单状态变化：写入审计日志，推送订单事件"""
+        if self.client.audit:
+            self.client.audit.record(AUDIT_STATE, paper=self.client.is_paper, strategy=self.name, order=tracked.to_dict())
+        if self.event_streamer:
+            self.event_streamer.emit(EVENT_ORDER, tracked.to_dict())
+    
+    def _call_in_loop(self, func, *args):
+        """在事件循环线程中执行回调（回调可能来自WebSocket线程）"""
+        if self._loop and self._loop.is_running():
+            self._loop.call_soon_threadsafe(func, *args)
+        else:
+            func(*args)
+    
+    def _on_order_update(self, data: Dict[str, Any]):
+        """订单状态回调（私有WebSocket或模拟撮合引擎）"""
+        self._call_in_loop(self.order_tracker.on_order_update, data)
+    
+    def _on_fill(self, fill: Fill):
+        """
+        成交回报回调（可能来自WebSocket线程）
+        
+        统一切换到事件循环线程处理，避免与策略主循环并发修改持仓。
+        """
+        self._call_in_loop(self._handle_fill, fill)
+    
+    def _on_position_update(self, update: PositionUpdate):
+        """持仓推送回调（私有WebSocket线程）"""
+        self._call_in_loop(self._handle_position_update, update)
+    
+    def _handle_position_update(self, update: PositionUpdate):
+        """
+        记录交易所持仓并与本地持仓核对
+        
+        同一交易事件中的成交先于持仓处理，此时本地持仓应已与交易所一致；不一致时只告警，
+        以定期同步的账户信息为准。
+        """
+        if update.symbol not in self._managed_symbols():
+            return
+        key = position_key(update.symbol, update.direction, self.position_mode)
+        self.exchange_positions[key] = update
+        local = self.positions.get(key)
+        local_signed = (local.size if local.direction == TradeDirection.LONG else -local.size) if local else 0.0
+        exchange_signed = update.size if update.direction == TradeDirection.LONG else -update.size
+        if self.position_mode == PositionMode.HEDGE:
+            local_signed, exchange_signed = abs(local_signed), abs(exchange_signed)
+        if abs(local_signed - exchange_signed) > 1e-9:
+            self.position_mismatches += 1
+            logger.warning(f"[持仓推送] {key} 交易所持仓 {exchange_signed:+.6f} 与本地 {local_signed:+.6f} 不一致")
+    
+    def _on_funding(self, payment: FundingPayment):
+        """资金费结算回调（私有WebSocket线程）"""
+        self._call_in_loop(self._handle_funding, payment)
+    
+    def _handle_funding(self, payment: FundingPayment):
+        """累计资金费"""
+        if payment.symbol not in self._managed_symbols():
+            return
+        self.funding_totals[payment.symbol] = self.funding_totals.get(payment.symbol, 0.0) + payment.amount
+        logger.info(f"[资金费] {payment.symbol} 结算 {payment.amount:+.4f} USDT")
+    
+    def _handle_fill(self, fill: Fill):
+        """处理成交：更新持仓，向信号总线发布成交和持仓快照，向事件流推送新的交易记录"""
+        trade_count = self.trade_records.total
+        self._apply_fill(fill)
+        if self.event_streamer:
+            for record in self.trade_records.recent(self.trade_records.total - trade_count):
+                self.event_streamer.emit(EVENT_TRADE, record.model_dump(mode="json"))
+        if self.signal_bus:
+            self.signal_bus.publish(TOPIC_FILLS, fill.model_dump(mode="json"))
+            self.signal_bus.publish(TOPIC_POSITIONS, {
+                "symbol": fill.symbol,
+                "timestamp": fill.timestamp,
+                "positions": [p.model_dump(mode="json") for p in self._positions_for(fill.symbol)]
+            })
+    
+    def _apply_fill(self, fill: Fill):
+        """
+        根据成交更新持仓
+        
+        同向成交按成交量加权更新开仓均价；反向成交先平掉已有持仓（记录交易），
+        单向持仓模式下剩余数量反向开仓（只减仓订单的剩余数量忽略）。双向持仓模式下按订单的持仓方向定位持仓，
+        平仓成交只减少对应方向的持仓。
+        """
+        symbol = fill.symbol
+        fill_direction = TradeDirection.LONG if fill.side == OrderSide.BUY else TradeDirection.SHORT
+        tracked = self.order_tracker.get(fill.order_id)
+        
+        if self.position_mode == PositionMode.HEDGE:
+            if tracked and tracked.position_side:
+                position_side = tracked.position_side
+            elif tracked and tracked.reduce_only:
+                position_side = TradeDirection.SHORT if fill_direction == TradeDirection.LONG else TradeDirection.LONG
+            else:
+                position_side = fill_direction
+        else:
+            position_side = fill_direction
+        key = position_key(symbol, position_side, self.position_mode)
+        
+        self.order_tracker.on_fill(fill)
+        if self.shadow:
+            self.shadow.on_live_fill(fill)
+        self.daily.record_fill(fill.size * fill.price)
+        if self.pnl_crosscheck:
+            self.pnl_crosscheck.record_local_fill(fill)
+        if self.wash_guard:
+            self.wash_guard.record_fill(fill)
+        remaining = Decimal(str(fill.size))
+        price = Decimal(str(fill.price))
+        now = int(datetime.now().timestamp())
+        
+        position = self.positions.get(key)
+        open_fee = Decimal(str(fill.fee))
+        
+        if position and position.direction != fill_direction:
+            size = Decimal(str(position.size))
+            closed = min(size, remaining)
+            # 反手成交的手续费按数量分摊到平仓部分和新开仓部分；超出持仓的部分被忽略时全部计入平仓
+            flipping = self.position_mode == PositionMode.ONE_WAY and not (tracked and tracked.reduce_only)
+            closing_fee = open_fee * closed / remaining if flipping else open_fee
+            open_fee -= closing_fee
+            pnl = self._calculate_pnl(position, price) * closed / size - closing_fee
+            self._track_excursion(symbol, float(price))
             
-            # 记录交易
-            trade_record = TradeRecord(
+            self.trade_records.append(TradeRecord(
                 symbol=symbol,
                 direction=position.direction,
-                size=position.size,
+                size=float(closed),
                 entry_price=position.entry_price,
-                exit_price=float(exit_price),
+                exit_price=float(price),
                 pnl=float(pnl),
-                timestamp=int(datetime.now().timestamp()),
-                duration=int(datetime.now().timestamp()) - position.opening_time
-            )
+                timestamp=now,
+                duration=now - position.opening_time,
+                confidence=position.confidence,
+                mae=position.mae,
+                mfe=position.mfe
+            ))
+            self.daily.record_trade(float(pnl))
             
-            self.trade_records.append(trade_record)
+            # 实盘余额只在启动时同步，已实现盈亏（已扣手续费）同样记入本地余额
+            self.balance += pnl
+            self.available_balance += pnl
             
-            # 移除持仓
-            del self.positions[symbol]
+            remaining -= closed
+            if size - closed > 0:
+                position.size = float(size - closed)
+                logger.info(f"[成交] {symbol} 部分平仓 {float(closed)} @ {float(price):.4f}，剩余 {position.size}")
+                if self.discord:
+                    self.discord.fill(self.name, symbol, "部分平仓", position.direction.value,
+                                      float(closed), float(price), float(pnl))
+            else:
+                del self.positions[key]
+                logger.info(f"[成交] {symbol} 平仓完成 @ {float(price):.4f}，盈亏: {float(pnl):.4f} USDT")
+                if self.discord:
+                    self.discord.fill(self.name, symbol, "平仓", position.direction.value,
+                                      float(closed), float(price), float(pnl))
+                if self.cooldowns.record(symbol, float(pnl)):
+                    self.alerts.send(self.name, "symbol_cooldown",
+                                     f"{symbol} 连续亏损 {self.cooldowns.after} 笔，{self.cooldowns.seconds:.0f}秒内不再开仓")
+                if self.loss_breaker.record(float(pnl)):
+                    self.alerts.send(self.name, "loss_breaker",
+                                     f"连续亏损 {self.loss_breaker.streak} 笔，熔断 {self.loss_breaker.seconds:.0f}秒内暂停全部开仓")
+            position = None
             
-            logger.info(
-                f"[平仓] {symbol} 完成 - "
-                f"盈亏: {float(pnl):.4f} USDT, "
-                f"收益率: {float(pnl / Decimal(str(position.entry_price)) / Decimal(str(position.size))) * 100:.2f}%"
+            if self.position_mode == PositionMode.HEDGE and remaining > 0:
+                logger.warning(f"[成交] {symbol} 平仓成交超出持仓数量 {float(remaining)}，已忽略")
+                remaining = Decimal("0")
+        
+        if remaining <= 0:
+            return
+        
+        if tracked and tracked.reduce_only:
+            # 只减仓订单不会开仓（本地持仓数量与交易所不一致）
+            logger.warning(f"[成交] {symbol} 只减仓订单成交超出本地持仓数量 {float(remaining)}，已忽略")
+            if open_fee > 0:
+                self.balance -= open_fee
+                self.available_balance -= open_fee
+            return
+        
+        if fill_direction != position_side:
+            logger.warning(f"[成交] {symbol} 平仓成交没有对应的{position_side.value}持仓，已忽略")
+            return
+        
+        if position:
+            old_size = Decimal(str(position.size))
+            new_size = old_size + remaining
+            position.entry_price = float(
+                (old_size * Decimal(str(position.entry_price)) + remaining * price) / new_size
             )
-            
-        except Exception as e:
-            logger.error(f"[平仓] {symbol} 失败: {e}")
+            position.size = float(new_size)
+            logger.info(f"[成交] {symbol} 加仓 {float(remaining)} @ {float(price):.4f}，均价 {position.entry_price:.4f}")
+            action = "加仓"
+        else:
+            signal = self.pending_exits.get(symbol)
+            self.positions[key] = Position(
+                symbol=symbol,
+                direction=fill_direction,
+                size=float(remaining),
+                entry_price=float(price),
+                stop_loss=signal.stop_loss if signal and signal.direction == fill_direction else 0.0,
+                take_profit=signal.take_profit if signal and signal.direction == fill_direction else 0.0,
+                leverage=self.strategy_config.get_leverage(symbol),
+                opening_time=now,
+                confidence=signal.confidence if signal and signal.direction == fill_direction else None
+            )
+            logger.info(f"[成交] {symbol} 开仓 {fill_direction.value} {float(remaining)} @ {float(price):.4f}")
+            action = "开仓"
+        if self.discord:
+            self.discord.fill(self.name, symbol, action, fill_direction.value, float(remaining), float(price))
+        
+        if open_fee > 0:
+            self.balance -= open_fee
+            self.available_balance -= open_fee
+    
+    def _confidence_multiplier(self, confidence: float) -> float:
+        """按信号置信度（限制在0-1之间）在最小和最大倍数之间线性取仓位倍数"""
+        low, high = self.config.confidence_min_multiplier, self.config.confidence_max_multiplier
+        return low + (high - low) * min(max(confidence, 0.0), 1.0)
     
     def _calculate_position_size(
         self, 
         balance: Decimal, 
-        base_position_pct: Decimal, 
         current_price: Decimal, 
-        min_order_size: Decimal
+        min_order_size: Decimal,
+        stop_distance: Decimal = Decimal("0")
     ) -> Decimal:
         """
-        计算仓位大小（带最小下单量检查）
+        计算仓位大小（按配置的仓位单位换算，带最小下单量检查）
         
         Args:
             balance: 账户余额（USDT）
-            base_position_pct: 基础仓位比例
             current_price: 当前价格
             min_order_size: 该币种的最小下单量
+            stop_distance: 止损距离（按每笔风险计算仓位时使用）
             
         Returns:
             Decimal: 仓位大小（币的数量）
         """
-        if current_price <= 0:
-            raise ValueError("当前价格必须大于零")
         if balance <= 0:
             raise ValueError("账户余额必须大于零")
         
-        # 步骤1：计算基础投入金额
-        base_amount = balance * base_position_pct
-        
-        # 步骤2：直接使用基础金额（不再调整波动率）
-        adjusted_amount = base_amount
-        
-        # 步骤3：转换为币的数量
-        calculated_size = adjusted_amount / current_price
+        # 步骤1：按仓位单位换算为币的数量
+        sizing = self.strategy_config.sizing
+        calculated_size = sizing.size(balance, current_price, stop_distance)
         
-        # 步骤5：最小下单量检查和调整
+        # 步骤2：最小下单量检查和调整
         final_size = calculated_size
         adjusted = False
         
@@ -650,10 +3276,9 @@ class HighFrequencyStrategy:
         # 详细日志
         logger.debug(f"[仓位计算] ====================================")
         logger.debug(f"[仓位计算] 账户余额: {float(balance):.2f} USDT")
-        logger.debug(f"[仓位计算] 基础比例: {float(base_position_pct) * 100:.2f}%")
-        logger.debug(f"[仓位计算] 基础金额: {float(base_amount):.2f} USDT")
+        logger.debug(f"[仓位计算] 仓位配置: {sizing.describe()}")
         logger.debug(f"[仓位计算] 当前价格: {float(current_price):.2f} USDT")
-        logger.debug(f"[仓位计算] 调整后金额: {float(adjusted_amount):.2f} USDT")
+        logger.debug(f"[仓位计算] 名义价值: {float(calculated_size * current_price):.2f} USDT")
         logger.debug(f"[仓位计算] 计算仓位: {float(calculated_size):.4f} 币")
         
         if adjusted:
@@ -690,23 +3315,6 @@ class HighFrequencyStrategy:
         else:
             return Decimal("0")
     
-    def _calculate_moving_average(self, klines: List[PriceData], period: int) -> Decimal:
-        """
-        计算移动平均线
-        
-        Args:
-            klines: K线数据列表
-            period: 周期
-            
-        Returns:
-            Decimal: 移动平均值
-        """
-        if not klines or len(klines) < period:
-            return Decimal("0")
-        
-        total_close = sum(Decimal(str(k.close)) for k in klines[-period:])
-        return total_close / Decimal(str(period))
-    
     def _get_current_price(self, klines: List[PriceData]) -> Decimal:
         """获取当前价格"""
         if not klines:
@@ -730,32 +3338,166 @@ class HighFrequencyStrategy:
         return deviation / ma
     
     def _calculate_daily_volume(self) -> float:
-        """计算每日交易量"""
-        now = datetime.now()
-        daily_volume = 0.0
-        
-        for record in self.trade_records:
-            trade_time = datetime.fromtimestamp(record.timestamp)
-            if (now - trade_time).total_seconds() < 86400:  # 24小时内
-                daily_volume += record.size * record.entry_price
-        
-        return daily_volume
+        """计算当前交易日的成交额（开仓和平仓成交）"""
+        self.daily.maybe_rollover()
+        return self.daily.volume
+    
+    def _mark_price_summary(self) -> Dict[str, Any]:
+        """各交易对最新价/标记价格/指数价格，以及各持仓的强平价估算和距离"""
+        latest = self._latest_prices()
+        marks = self._mark_prices()
+        positions = []
+        for position in self.positions.values():
+            mark = marks.get(position.symbol)
+            liquidation = self._liquidation_price(position)
+            positions.append({
+                "symbol": position.symbol,
+                "direction": position.direction.value,
+                "mark_price": mark,
+                "liquidation_price": liquidation,
+                "distance": abs(mark - liquidation) / mark if mark else None,
+            })
+        return {
+            "symbols": {
+                symbol: {
+                    "last": latest.get(symbol),
+                    "mark": mark.mark_price,
+                    "index": mark.index_price,
+                }
+                for symbol, mark in self.mark_prices.items()
+            },
+            "positions": positions,
+        }
+    
+    def publish_snapshot(self) -> StrategySnapshot:
+        """复制当前对外展示的状态并发布到快照通道"""
+        self._last_snapshot_publish = time.time()
+        snapshot = StrategySnapshot(
+            name=self.name,
+            timestamp=self._last_snapshot_publish,
+            stats=self.get_performance_stats(),
+            positions=tuple(p.model_copy() for p in self.positions.values()),
+            balance=float(self.balance),
+            daily_volume=float(self._calculate_daily_volume()),
+            equity_history=tuple(float(v) for v in self.equity_history),
+            total_pnl=float(self.trade_records.total_pnl),
+            total_trades=self.trade_records.total,
+            trade_records=len(self.trade_records),
+            spilled_trade_records=self.trade_records.spilled,
+            shadow=self.shadow.get_summary() if self.shadow else None,
+            allocated=self.allocation is not None,
+            trading_timezone=self.config.trading_timezone,
+            report_interval=self.config.performance_report_interval,
+        )
+        self.snapshots.publish(snapshot)
+        return snapshot
     
     def get_performance_stats(self) -> Dict[str, Any]:
         """获取性能统计"""
-        total_trades = len(self.trade_records)
-        winning_trades = sum(1 for r in self.trade_records if r.pnl > 0)
-        losing_trades = sum(1 for r in self.trade_records if r.pnl < 0)
+        total_trades = self.trade_records.total
+        winning_trades = self.trade_records.wins
+        losing_trades = self.trade_records.losses
         win_rate = winning_trades / total_trades if total_trades > 0 else 0.0
         
         return {
             "balance": float(self.balance),
             "available_balance": float(self.available_balance),
+            "collateral": {
+                "reporting_currency": self.client.collateral.reporting_currency,
+                "assets": {coin: asset.model_dump() for coin, asset in self.collaterals.items()},
+            },
             "total_trades": total_trades,
             "winning_trades": winning_trades,
             "losing_trades": losing_trades,
             "win_rate": win_rate,
             "active_positions": len(self.positions),
-            "trading_interval": self.min_trade_interval // 1000  # 转换为秒
+            "trading_interval": self.min_trade_interval,  # 毫秒
+            "daily": {**self.daily.to_dict(), "max_entries": self.config.max_trades_per_day,
+                      "previous": self.daily.previous},
+            "risk": self.risk_model.risk_decomposition(self._portfolio_weights()) if self.risk_model else None,
+            "regimes": self.regime.get_summary() if self.regime else None,
+            "liquidity_filter": self.liquidity_filter.get_summary() if self.liquidity_filter else None,
+            "activity_filter": self.activity_filter.get_summary() if self.activity_filter else None,
+            "basis": self.basis.get_stats() if self.basis else None,
+            "funding_window": self.funding_window.get_stats() if self.funding_window else None,
+            "fees": self.fees.get_stats(),
+            "ev_gate": self.ev_gate.get_stats() if self.ev_gate else None,
+            "latency_guard": self.client.latency_guard.get_stats() if self.client.latency_guard else None,
+            "drawdown_throttle": self.drawdown_throttle.get_summary() if self.drawdown_throttle else None,
+            "inventory_skew": self.inventory_skew.get_stats() if self.inventory_skew else None,
+            "slippage": self.slippage.get_summary(),
+            "order_gate": self.client.gate.get_stats(),
+            "order_channel": self.client.channel.get_stats(),
+            "latency": self.latency.get_summary() if self.latency else None,
+            "request_timeouts": dict(self.client.timeout_counts),
+            "exchange_rejections": dict(self.client.rejection_counts),
+            "manual": {**self.manual_stats, "paused": sorted(self.manual_paused)},
+            "order_confirmation": {**self.order_confirm_stats, "open_unconfirmed": sum(
+                1 for o in self.order_tracker.get_open_orders() if o.status == UNCONFIRMED)},
+            "signature_clock": self.client.clock.get_stats(),
+            "warmup": self.warmup.get_stats() if self.config.warmup_enabled else None,
+            "reconcile": {"completed": self.reconciled, **self.reconcile_stats}
+            if self.config.reconcile_enabled and not self.client.is_paper else None,
+            "ws_backpressure": self.price_stream.get_backpressure_stats() if self.price_stream else None,
+            "ws_subscriptions": self.price_stream.get_subscription_stats() if self.price_stream else None,
+            "ws_compression": self.price_stream.get_compression_stats() if self.price_stream else None,
+            "ws_sequence": self.price_stream.get_sequence_stats() if self.price_stream else None,
+            "ws_book": self.price_stream.get_book_validation_stats() if self.price_stream else None,
+            "contract_specs": self.client.contract_specs.get_stats(),
+            "pre_trade": {**self.client.pre_trade.get_stats(), "kill_switch": self.client.kill_switch.describe()},
+            "pnl_crosscheck": self.pnl_crosscheck.get_stats() if self.pnl_crosscheck else None,
+            "account_events": {
+                **self.price_stream.account_events.get_stats(),
+                "position_mismatches": self.position_mismatches,
+                "funding_total": sum(self.funding_totals.values()),
+                "funding": dict(self.funding_totals),
+            } if self.price_stream and self.price_stream.private_connected else None,
+            "endpoints": {
+                "rest": self.client.endpoints.stats() if self.client.endpoints else None,
+                "websocket": self.ws_endpoints.stats() if self.ws_endpoints else None,
+            } if self.client.endpoints or self.ws_endpoints else None,
+            "oco": self.oco.get_stats() if self.oco else None,
+            "dual_quote": self.dual_quote.get_stats() if self.dual_quote else None,
+            "wash_guard": self.wash_guard.get_stats() if self.wash_guard else None,
+            "mark_price": self._mark_price_summary(),
+            "bars": {
+                "type": self.config.bar_type,
+                "size": self.config.bar_size,
+                "symbols": {s: b.completed for s, b in self.bar_builders.items()}
+            } if self.bar_builders else None,
+            "margin": {"free_margin": float(self._free_margin()), **self.margin_stats},
+            "close_confirmation": dict(self.close_stats),
+            "position_flips": self.flip_count,
+            "cooldowns": self.cooldowns.get_stats(),
+            "loss_breaker": self.loss_breaker.get_stats(),
+            "price_guard": self.price_guard.get_stats() if self.price_guard else None,
+            "price_source": self.price_source.get_stats(),
+            "confidence": self.trade_records.confidence_stats(),
+            "excursions": self.trade_records.excursion_stats(),
+            "alerts": self.alerts.get_stats(),
+            "discord": self.discord.get_stats() if self.discord else None,
+            "email": self.email.get_stats() if self.email else None,
+            "notify_throttle": self.notify_throttle.get_stats(),
+            "leverage": {
+                "symbols": {s: self.strategy_config.get_leverage(s) for s in self.symbols},
+                "blocked": dict(self.leverage_blocked)
+            },
+            "trading_status": self.trading_status.get_stats(),
+            "universe": {
+                **self.universe.get_stats(),
+                "scans": self.scanner.scans,
+                "symbols": list(self.symbols),
+                "winding_down": {s: round(time.time() - since) for s, since in self._winding_down.items()}
+            } if self.universe else None,
+            "vwap": self.vwap.get_stats() if self.vwap else None,
+            "webhook": self.webhook.get_stats() if self.webhook else None,
+            "signal_bus": self.signal_bus.get_stats() if self.signal_bus else None,
+            "event_stream": self.event_streamer.get_stats() if self.event_streamer else None,
+            "script": self.script.get_stats() if self.script else None,
+            "ensemble": self.ensemble.get_stats() if self.ensemble else None,
+            "feature_recorder": self.feature_recorder.get_stats() if self.feature_recorder else None,
+            "benchmark": self.benchmark.get_summary() if self.benchmark else None,
+            "rolling_performance": self.rolling_performance.summary(self.trade_records),
+            "allocation": self.allocation.get_stats() if self.allocation else None
         }
 
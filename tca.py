@@ -0,0 +1,318 @@
+"""
+交易成本分析（TCA）模块
+
+运行时记录：
+- 订单完成记录（orders.jsonl）：策略、交易对、方向、下单时间、到达价格及逐笔成交
+- 行情记录（market.jsonl）：买一/卖一变化和逐笔成交，格式与撮合引擎的回放事件一致
+
+离线分析（python tca.py --dir data/tca --out reports/tca）按策略和交易对拆分交易成本：
+- 手续费：成交回报中的手续费
+- 价差成本：吃单成交按到达时半个价差计算
+- 滑点：成交均价相对到达中间价的成本中扣除价差成本的部分
+- 市场冲击：成交完成后一段时间（默认5秒）中间价相对到达中间价的同向变动
+- VWAP基准：成交均价相对订单存续期间市场成交VWAP的成本
+成本以USDT计，正值表示成本；bps相对到达价格的名义价值。输出CSV和HTML报告。
+"""
+
+import argparse
+import bisect
+import csv
+import html
+import json
+import os
+import threading
+import time
+from typing import Dict, List, Optional, Any, Tuple
+
+from edgex_types import Fill, OrderBook
+
+
+ORDERS_FILE = "orders.jsonl"
+MARKET_FILE = "market.jsonl"
+
+
+class TcaRecorder:
+    """TCA数据记录器（行情回调来自WebSocket线程，写文件需要加锁）"""
+
+    def __init__(self, directory: str):
+        """
+        Args:
+            directory: 记录文件目录
+        """
+        os.makedirs(directory, exist_ok=True)
+        self.directory = directory
+        self._orders = open(os.path.join(directory, ORDERS_FILE), "a", encoding="utf-8")
+        self._market = open(os.path.join(directory, MARKET_FILE), "a", encoding="utf-8")
+        self._lock = threading.Lock()
+        self._last_top: Dict[str, Tuple] = {}
+
+    def _write(self, fp, record: Dict[str, Any]):
+        with self._lock:
+            fp.write(json.dumps(record, ensure_ascii=False) + "\n")
+            fp.flush()
+
+    def on_depth(self, symbol: str, book: OrderBook):
+        """记录买一/卖一（仅在变化时）"""
+        if not book.bids or not book.asks:
+            return
+        top = (tuple(book.bids[0]), tuple(book.asks[0]))
+        if self._last_top.get(symbol) == top:
+            return
+        self._last_top[symbol] = top
+        self._write(self._market, {
+            "type": "depth", "symbol": symbol,
+            "bids": [list(book.bids[0])], "asks": [list(book.asks[0])],
+            "timestamp": int(time.time() * 1000)
+        })
+
+    def on_trade(self, symbol: str, price: float, size: float, is_buyer_maker: Optional[bool] = None):
+        """记录市场逐笔成交"""
+        self._write(self._market, {
+            "type": "trade", "symbol": symbol, "price": price, "size": size,
+            "is_buyer_maker": is_buyer_maker, "timestamp": int(time.time() * 1000)
+        })
+
+    def record_order(self, strategy: str, tracked, fills: List[Fill]):
+        """
+        记录已完成的订单
+
+        Args:
+            strategy: 策略名称
+            tracked: 订单跟踪记录（TrackedOrder）
+            fills: 该订单的成交回报
+        """
+        self._write(self._orders, {
+            "strategy": strategy,
+            "order_id": tracked.order_id,
+            "symbol": tracked.symbol,
+            "side": tracked.side.value,
+            "order_type": tracked.order_type.value,
+            "reduce_only": tracked.reduce_only,
+            "created_at": tracked.created_at,
+            "arrival_price": tracked.reference_price,
+            "fills": [f.model_dump(mode="json") for f in fills],
+        })
+
+    def close(self):
+        with self._lock:
+            self._orders.close()
+            self._market.close()
+
+
+class _MarketData:
+    """按交易对、时间排序的行情记录"""
+
+    def __init__(self, events: List[Dict[str, Any]]):
+        self.quotes: Dict[str, List[Tuple[int, float, float]]] = {}
+        self.trades: Dict[str, List[Tuple[int, float, float]]] = {}
+        for event in sorted(events, key=lambda e: e.get("timestamp", 0)):
+            symbol = event.get("symbol", "")
+            ts = int(event.get("timestamp", 0))
+            if event.get("type") == "depth" and event.get("bids") and event.get("asks"):
+                self.quotes.setdefault(symbol, []).append(
+                    (ts, float(event["bids"][0][0]), float(event["asks"][0][0]))
+                )
+            elif event.get("type") == "trade":
+                self.trades.setdefault(symbol, []).append((ts, float(event["price"]), float(event["size"])))
+        self._quote_times = {s: [q[0] for q in quotes] for s, quotes in self.quotes.items()}
+        self._trade_times = {s: [t[0] for t in trades] for s, trades in self.trades.items()}
+
+    def quote_at(self, symbol: str, ts: int) -> Optional[Tuple[float, float]]:
+        """ts时刻（含）之前最近的买一/卖一"""
+        times = self._quote_times.get(symbol)
+        if not times:
+            return None
+        i = bisect.bisect_right(times, ts) - 1
+        if i < 0:
+            return None
+        _, bid, ask = self.quotes[symbol][i]
+        return bid, ask
+
+    def vwap(self, symbol: str, start: int, end: int) -> Optional[float]:
+        """[start, end] 区间内的市场成交VWAP"""
+        times = self._trade_times.get(symbol)
+        if not times:
+            return None
+        lo = bisect.bisect_left(times, start)
+        hi = bisect.bisect_right(times, end)
+        trades = self.trades[symbol][lo:hi]
+        volume = sum(size for _, _, size in trades)
+        if volume <= 0:
+            return None
+        return sum(price * size for _, price, size in trades) / volume
+
+
+class TcaReport:
+    """交易成本分析报告"""
+
+    # 汇总的成本字段（USDT）
+    COST_FIELDS = ["fees", "spread_cost", "slippage", "market_impact", "vwap_cost"]
+
+    def __init__(self, orders: List[Dict[str, Any]], market_events: List[Dict[str, Any]],
+                 impact_horizon_ms: int = 5000):
+        """
+        Args:
+            orders: 订单完成记录
+            market_events: 行情记录
+            impact_horizon_ms: 计算市场冲击的成交后观察时间（毫秒）
+        """
+        self.market = _MarketData(market_events)
+        self.impact_horizon_ms = impact_horizon_ms
+        self.rows = [row for row in (self._analyze(o) for o in orders) if row]
+
+    @classmethod
+    def load(cls, directory: str, impact_horizon_ms: int = 5000) -> "TcaReport":
+        """从记录目录加载"""
+        return cls(
+            _read_jsonl(os.path.join(directory, ORDERS_FILE)),
+            _read_jsonl(os.path.join(directory, MARKET_FILE)),
+            impact_horizon_ms
+        )
+
+    def _mid(self, symbol: str, ts: int) -> Optional[float]:
+        quote = self.market.quote_at(symbol, ts)
+        return (quote[0] + quote[1]) / 2 if quote else None
+
+    def _analyze(self, order: Dict[str, Any]) -> Optional[Dict[str, Any]]:
+        """计算单个订单的成本拆分"""
+        fills = order.get("fills") or []
+        qty = sum(float(f["size"]) for f in fills)
+        if qty <= 0:
+            return None
+
+        symbol = order["symbol"]
+        sign = 1 if order["side"] == "BUY" else -1
+        avg_price = sum(float(f["price"]) * float(f["size"]) for f in fills) / qty
+        created_at = int(order.get("created_at", 0))
+        last_fill = max(int(f["timestamp"]) for f in fills)
+
+        # 到达价格：下单时刻的盘口中间价，无行情记录时使用决策时的到达价格
+        quote = self.market.quote_at(symbol, created_at)
+        arrival = (quote[0] + quote[1]) / 2 if quote else order.get("arrival_price")
+        if not arrival:
+            return None
+        half_spread = (quote[1] - quote[0]) / 2 if quote else 0.0
+        notional = arrival * qty
+
+        taker_qty = sum(float(f["size"]) for f in fills if not f.get("is_maker"))
+        spread_cost = half_spread * taker_qty
+        arrival_cost = sign * (avg_price - arrival) * qty
+
+        post_mid = self._mid(symbol, last_fill + self.impact_horizon_ms)
+        impact = sign * (post_mid - arrival) * qty if post_mid else 0.0
+
+        vwap = self.market.vwap(symbol, created_at, last_fill)
+        vwap_cost = sign * (avg_price - vwap) * qty if vwap else 0.0
+
+        return {
+            "strategy": order.get("strategy", ""),
+            "symbol": symbol,
+            "order_id": order.get("order_id", ""),
+            "side": order["side"],
+            "reduce_only": bool(order.get("reduce_only")),
+            "created_at": created_at,
+            "quantity": qty,
+            "avg_price": avg_price,
+            "arrival_price": arrival,
+            "vwap": vwap or 0.0,
+            "notional": notional,
+            "fees": sum(float(f.get("fee", 0)) for f in fills),
+            "spread_cost": spread_cost,
+            "slippage": arrival_cost - spread_cost,
+            "market_impact": impact,
+            "vwap_cost": vwap_cost,
+            "vwap_notional": vwap * qty if vwap else 0.0,
+        }
+
+    def summary(self) -> List[Dict[str, Any]]:
+        """按策略和交易对汇总（每个策略另有一行 symbol=ALL 的合计）"""
+        groups: Dict[Tuple[str, str], List[Dict[str, Any]]] = {}
+        for row in self.rows:
+            groups.setdefault((row["strategy"], row["symbol"]), []).append(row)
+            groups.setdefault((row["strategy"], "ALL"), []).append(row)
+
+        result = []
+        for (strategy, symbol), rows in sorted(groups.items()):
+            notional = sum(r["notional"] for r in rows)
+            vwap_notional = sum(r["vwap_notional"] for r in rows)
+            item = {"strategy": strategy, "symbol": symbol, "orders": len(rows), "notional": notional}
+            for field in self.COST_FIELDS:
+                total = sum(r[field] for r in rows)
+                base = vwap_notional if field == "vwap_cost" else notional
+                item[field] = total
+                item[f"{field}_bps"] = total / base * 10000 if base > 0 else 0.0
+            item["total_cost"] = item["fees"] + item["spread_cost"] + item["slippage"]
+            item["total_cost_bps"] = item["total_cost"] / notional * 10000 if notional > 0 else 0.0
+            result.append(item)
+        return result
+
+    def export_csv(self, out_dir: str) -> List[str]:
+        """导出订单明细和汇总CSV"""
+        os.makedirs(out_dir, exist_ok=True)
+        paths = []
+        for name, rows in (("tca_orders.csv", self.rows), ("tca_summary.csv", self.summary())):
+            path = os.path.join(out_dir, name)
+            with open(path, "w", newline="", encoding="utf-8") as fp:
+                if rows:
+                    writer = csv.DictWriter(fp, fieldnames=list(rows[0].keys()))
+                    writer.writeheader()
+                    writer.writerows(rows)
+            paths.append(path)
+        return paths
+
+    def export_html(self, out_dir: str) -> str:
+        """导出HTML报告"""
+        os.makedirs(out_dir, exist_ok=True)
+        path = os.path.join(out_dir, "tca_report.html")
+        with open(path, "w", encoding="utf-8") as fp:
+            fp.write("<!DOCTYPE html><html><head><meta charset='utf-8'><title>TCA报告</title>"
+                     "<style>body{font-family:sans-serif}table{border-collapse:collapse;margin-bottom:24px}"
+                     "th,td{border:1px solid #ccc;padding:4px 8px;text-align:right}th{background:#eee}</style>"
+                     "</head><body>")
+            fp.write("<h1>交易成本分析</h1>")
+            fp.write("<p>成本单位为USDT，正值表示成本；bps相对到达价格名义价值（VWAP成本相对VWAP名义价值）。"
+                     "总成本 = 手续费 + 价差成本 + 滑点；市场冲击为成交后"
+                     f"{self.impact_horizon_ms}ms中间价的同向变动，不计入总成本。</p>")
+            fp.write("<h2>按策略/交易对汇总</h2>" + _html_table(self.summary()))
+            fp.write("<h2>订单明细</h2>" + _html_table(self.rows))
+            fp.write("</body></html>")
+        return path
+
+
+def _read_jsonl(path: str) -> List[Dict[str, Any]]:
+    if not os.path.exists(path):
+        return []
+    with open(path, encoding="utf-8") as fp:
+        return [json.loads(line) for line in fp if line.strip()]
+
+
+def _html_table(rows: List[Dict[str, Any]]) -> str:
+    if not rows:
+        return "<p>无数据</p>"
+    header = "".join(f"<th>{html.escape(k)}</th>" for k in rows[0])
+    body = "".join(
+        "<tr>" + "".join(
+            f"<td>{v:.4f}</td>" if isinstance(v, float) else f"<td>{html.escape(str(v))}</td>"
+            for v in row.values()
+        ) + "</tr>"
+        for row in rows
+    )
+    return f"<table><tr>{header}</tr>{body}</table>"
+
+
+def main():
+    parser = argparse.ArgumentParser(description="交易成本分析（TCA）报告")
+    parser.add_argument("--dir", default="data/tca", help="TCA记录目录")
+    parser.add_argument("--out", default="reports/tca", help="报告输出目录")
+    parser.add_argument("--impact-horizon-ms", type=int, default=5000, help="市场冲击观察时间（毫秒）")
+    args = parser.parse_args()
+
+    report = TcaReport.load(args.dir, args.impact_horizon_ms)
+    paths = report.export_csv(args.out) + [report.export_html(args.out)]
+    print(f"已分析 {len(report.rows)} 个订单")
+    for path in paths:
+        print(f"  {path}")
+
+
+if __name__ == "__main__":
+    main()
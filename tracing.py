@@ -0,0 +1,132 @@
+"""
+链路追踪模块
+基于OpenTelemetry为REST调用、WebSocket消息处理和交易流程生成Span，并通过OTLP导出
+（Jaeger/Tempo等后端），用于端到端定位订单生命周期中的延迟瓶颈。
+
+未安装opentelemetry或未启用追踪时，所有接口退化为空操作，不影响交易主流程。
+"""
+
+import functools
+from contextlib import contextmanager
+from typing import Any, Callable, Optional
+from loguru import logger
+
+try:
+    from opentelemetry import trace
+    from opentelemetry.sdk.resources import Resource
+    from opentelemetry.sdk.trace import TracerProvider
+    from opentelemetry.sdk.trace.export import BatchSpanProcessor
+    from opentelemetry.exporter.otlp.proto.grpc.trace_exporter import OTLPSpanExporter
+    OTEL_AVAILABLE = True
+except ImportError:
+    OTEL_AVAILABLE = False
+
+
+_tracer = None
+_provider = None
+
+
+def init_tracing(config) -> bool:
+    """
+    初始化OpenTelemetry追踪
+
+    Args:
+        config: 配置对象（tracing_enabled / otlp_endpoint / tracing_service_name）
+
+    Returns:
+        bool: 追踪是否成功启用
+    """
+    global _tracer, _provider
+
+    if not config.tracing_enabled:
+        return False
+
+    if not OTEL_AVAILABLE:
+        logger.warning("已启用链路追踪，但未安装opentelemetry依赖，追踪将被禁用")
+        return False
+
+    try:
+        resource = Resource.create({"service.name": config.tracing_service_name})
+        _provider = TracerProvider(resource=resource)
+        exporter = OTLPSpanExporter(endpoint=config.otlp_endpoint, insecure=True)
+        _provider.add_span_processor(BatchSpanProcessor(exporter))
+        trace.set_tracer_provider(_provider)
+        _tracer = trace.get_tracer("edgex-high-frequency-bot")
+
+        logger.info(f"链路追踪已启用: {config.otlp_endpoint} (service={config.tracing_service_name})")
+        return True
+
+    except Exception as e:
+        logger.error(f"链路追踪初始化失败: {e}")
+        _tracer = None
+        _provider = None
+        return False
+
+
+def shutdown_tracing():
+    """刷新并关闭追踪导出器"""
+    global _tracer, _provider
+
+    if _provider:
+        try:
+            _provider.shutdown()
+        except Exception as e:
+            logger.error(f"关闭链路追踪失败: {e}")
+
+    _tracer = None
+    _provider = None
+
+
+@contextmanager
+def span(name: str, **attributes: Any):
+    """
+    创建一个Span（未启用追踪时为空操作）
+
+    Args:
+        name: Span名称（如"edgex.rest.place_order"）
+        **attributes: Span属性（如symbol、order_id）
+    """
+    if _tracer is None:
+        yield None
+        return
+
+    with _tracer.start_as_current_span(name) as current:
+        for key, value in attributes.items():
+            if value is not None:
+                current.set_attribute(key, value)
+        yield current
+
+
+def annotate(**attributes: Any):
+    """
+    为当前Span追加属性
+
+    Args:
+        **attributes: Span属性
+    """
+    if _tracer is None:
+        return
+
+    current = trace.get_current_span()
+    for key, value in attributes.items():
+        if value is not None:
+            current.set_attribute(key, value)
+
+
+def traced(name: str) -> Callable:
+    """
+    异步函数追踪装饰器
+
+    Args:
+        name: Span名称
+
+    Returns:
+        Callable: 装饰器
+    """
+    def decorator(func):
+        @functools.wraps(func)
+        async def wrapper(*args, **kwargs):
+            with span(name):
+                return await func(*args, **kwargs)
+        return wrapper
+    return decorator
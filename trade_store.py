@@ -0,0 +1,143 @@
+"""
+交易记录缓冲模块
+
+内存中只保留最近的交易记录（环形缓冲），长时间运行不会无限增长：
+- 累计交易数、盈利/亏损笔数和总盈亏单独计数，不受缓冲容量影响
+- 配置溢写文件时，新记录定期（及停止时）追加写入文件（JSON Lines），
+  写入前先保存在待写列表中，被挤出缓冲的记录不会丢失
+- 按时间窗口统计（今日盈亏、交易量）、按信号置信度分组的胜率、最大不利/有利偏移（MAE/MFE）分布
+  和A/B测试、资金分配的胜率基于缓冲中的最近记录
+"""
+
+import json
+import os
+import time
+from collections import deque
+from typing import Any, Deque, Dict, Iterator, List, Optional
+
+from loguru import logger
+
+from edgex_types import TradeRecord
+
+SPILL_INTERVAL_SECONDS = 60
+
+# MAE/MFE 分布的分位数
+EXCURSION_PERCENTILES = (50, 75, 90)
+
+
+def _percentiles(values: List[float]) -> Dict[str, float]:
+    """分位数（最近秩）和最大值"""
+    ordered = sorted(values)
+    result = {f"p{p}": ordered[min(len(ordered) - 1, max(0, -(-p * len(ordered) // 100) - 1))]
+              for p in EXCURSION_PERCENTILES}
+    result["max"] = ordered[-1]
+    return result
+
+
+# 置信度分组（下限, 名称）
+CONFIDENCE_BUCKETS = ((2 / 3, "high"), (1 / 3, "medium"), (float("-inf"), "low"))
+
+
+class TradeRecordBuffer:
+    """最近交易记录的环形缓冲"""
+
+    def __init__(self, capacity: int = 10000, spill_path: str = "", strategy: str = ""):
+        """
+        Args:
+            capacity: 内存中保留的记录数
+            spill_path: 溢写文件路径（空为不溢写）
+            strategy: 策略名称（写入溢写记录，多策略共用文件时区分来源）
+        """
+        self.records: Deque[TradeRecord] = deque(maxlen=capacity)
+        self.spill_path = spill_path
+        self.strategy = strategy
+        self._pending: List[TradeRecord] = []
+        self._last_spill = time.time()
+
+        # 累计统计
+        self.total = 0
+        self.wins = 0
+        self.losses = 0
+        self.total_pnl = 0.0
+        self.spilled = 0
+
+    def append(self, record: TradeRecord):
+        self.records.append(record)
+        self.total += 1
+        self.total_pnl += record.pnl
+        if record.pnl > 0:
+            self.wins += 1
+        elif record.pnl < 0:
+            self.losses += 1
+        if self.spill_path:
+            self._pending.append(record)
+
+    def recent(self, n: int) -> List[TradeRecord]:
+        """最近n条记录（按时间顺序）"""
+        if n <= 0:
+            return []
+        return list(self.records)[-n:]
+
+    def confidence_stats(self) -> Dict[str, Dict[str, Any]]:
+        """按开仓信号置信度分组（低/中/高）的交易数、胜率和平均盈亏（只统计记录了置信度的交易）"""
+        groups: Dict[str, List[float]] = {}
+        for record in self.records:
+            if record.confidence is None:
+                continue
+            name = next(name for floor, name in CONFIDENCE_BUCKETS if record.confidence >= floor)
+            groups.setdefault(name, []).append(record.pnl)
+        return {
+            name: {"trades": len(groups[name]),
+                   "win_rate": sum(1 for pnl in groups[name] if pnl > 0) / len(groups[name]),
+                   "avg_pnl": sum(groups[name]) / len(groups[name])}
+            for _, name in reversed(CONFIDENCE_BUCKETS) if name in groups
+        }
+
+    def excursion_stats(self) -> Optional[Dict[str, Any]]:
+        """
+        最大不利偏移（MAE）和最大有利偏移（MFE）的分布（只统计记录了偏移的交易，没有时为None）
+
+        盈利交易的MAE分布可用于设置止损距离（止损应在大多数盈利交易的回撤之外），
+        亏损交易的MFE分布可用于设置止盈距离（亏损交易曾经到达的浮盈）
+        """
+        trades = [r for r in self.records if r.mae is not None and r.mfe is not None]
+        if not trades:
+            return None
+        winners = [r.mae for r in trades if r.pnl > 0]
+        losers = [r.mfe for r in trades if r.pnl <= 0]
+        return {
+            "trades": len(trades),
+            "mae": _percentiles([r.mae for r in trades]),
+            "mfe": _percentiles([r.mfe for r in trades]),
+            "winners_mae": _percentiles(winners) if winners else None,
+            "losers_mfe": _percentiles(losers) if losers else None,
+        }
+
+    def __iter__(self) -> Iterator[TradeRecord]:
+        return iter(self.records)
+
+    def __len__(self) -> int:
+        return len(self.records)
+
+    def maybe_spill(self, now: Optional[float] = None):
+        """距上次溢写超过间隔时写入待写记录（主循环调用）"""
+        now = time.time() if now is None else now
+        if now - self._last_spill >= SPILL_INTERVAL_SECONDS:
+            self.spill()
+
+    def spill(self):
+        """把待写记录追加写入溢写文件（失败时保留，下次重试）"""
+        if not self._pending:
+            return
+        try:
+            os.makedirs(os.path.dirname(self.spill_path) or ".", exist_ok=True)
+            with open(self.spill_path, "a", encoding="utf-8") as fp:
+                for record in self._pending:
+                    fp.write(json.dumps({"strategy": self.strategy, **record.model_dump(mode="json")},
+                                        ensure_ascii=False) + "\n")
+        except OSError as e:
+            logger.error(f"[交易记录] 写入 {self.spill_path} 失败: {e}")
+            return
+        self.spilled += len(self._pending)
+        self._pending = []
+        self._last_spill = time.time()
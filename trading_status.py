@@ -0,0 +1,116 @@
+"""
+交易所交易状态模块
+
+检测交易所维护或合约暂停交易，受影响的交易对暂停交易，恢复后自动继续：
+- 合约元数据: enableTrade=false 时该交易对暂停交易（不开仓也不平仓），enableOpenPosition=false 时只平仓不开仓；
+  元数据按 EDGEX_CONTRACT_SPEC_REFRESH_SECONDS 定期拉取，metadata 频道推送变化时立即更新
+- 下单错误: 错误信息包含维护/暂停交易的错误码时该交易对暂停交易，暂停时长后恢复尝试（再次出错时重新暂停）
+状态变化（暂停、只平仓、恢复）由策略发送告警。
+"""
+
+import time
+from enum import Enum
+from typing import Any, Dict, Iterable, List, Optional, Tuple
+
+from contract_specs import ContractSpec
+
+
+class SymbolStatus(Enum):
+    """交易对交易状态"""
+    TRADING = "trading"
+    CLOSE_ONLY = "close_only"
+    HALTED = "halted"
+
+
+STATUS_LABELS = {
+    SymbolStatus.TRADING: "正常交易",
+    SymbolStatus.CLOSE_ONLY: "只平仓",
+    SymbolStatus.HALTED: "暂停交易",
+}
+
+
+def parse_error_codes(text: str) -> List[str]:
+    """解析逗号分隔的错误码（不区分大小写）"""
+    return [code.strip().lower() for code in text.split(",") if code.strip()]
+
+
+class TradingStatusMonitor:
+    """交易对交易状态跟踪"""
+
+    def __init__(self, error_codes: Iterable[str] = (), halt_seconds: float = 60.0):
+        """
+        Args:
+            error_codes: 表示维护/暂停交易的错误码（下单错误信息包含其一时暂停该交易对）
+            halt_seconds: 下单错误触发暂停后恢复尝试前等待的秒数
+        """
+        self.error_codes = [code.lower() for code in error_codes]
+        self.halt_seconds = halt_seconds
+        self.status: Dict[str, SymbolStatus] = {}
+        self.reasons: Dict[str, str] = {}
+        self._error_halts: Dict[str, Tuple[float, str]] = {}   # 交易对 -> (暂停结束时间, 原因)
+        self.halts = 0
+
+    def is_halt_error(self, error: Any) -> bool:
+        """错误是否表示交易所维护或合约暂停交易"""
+        text = str(error).lower()
+        return any(code in text for code in self.error_codes)
+
+    def record_error(self, symbol: str, error: Any, now: Optional[float] = None) -> bool:
+        """
+        记录下单错误
+
+        Returns:
+            bool: 是否因此暂停该交易对
+        """
+        if not self.is_halt_error(error):
+            return False
+        now = now if now is not None else time.time()
+        self._error_halts[symbol] = (now + self.halt_seconds, f"下单返回暂停交易错误: {str(error)[:200]}")
+        return True
+
+    def refresh(self, specs: Dict[str, Optional[ContractSpec]],
+                now: Optional[float] = None) -> List[Tuple[str, SymbolStatus, SymbolStatus, str]]:
+        """
+        按合约元数据和下单错误重新计算各交易对状态
+
+        Args:
+            specs: 交易对 -> 合约规格（未加载时为None，视为正常交易）
+
+        Returns:
+            List[Tuple[str, SymbolStatus, SymbolStatus, str]]: 状态有变化的 (交易对, 原状态, 新状态, 原因)
+        """
+        now = now if now is not None else time.time()
+        changes = []
+        for symbol, spec in specs.items():
+            status, reason = SymbolStatus.TRADING, ""
+            if spec is not None and not spec.enable_trade:
+                status, reason = SymbolStatus.HALTED, "交易所暂停该合约交易（维护或下架）"
+            else:
+                halt = self._error_halts.get(symbol)
+                if halt and halt[0] > now:
+                    status, reason = SymbolStatus.HALTED, halt[1]
+                elif halt:
+                    del self._error_halts[symbol]
+                if status == SymbolStatus.TRADING and spec is not None and not spec.enable_open_position:
+                    status, reason = SymbolStatus.CLOSE_ONLY, "交易所暂停该合约开仓"
+            old = self.status.get(symbol, SymbolStatus.TRADING)
+            self.status[symbol] = status
+            self.reasons[symbol] = reason
+            if status != old:
+                if status == SymbolStatus.HALTED:
+                    self.halts += 1
+                changes.append((symbol, old, status, reason))
+        return changes
+
+    def can_open(self, symbol: str) -> bool:
+        return self.status.get(symbol, SymbolStatus.TRADING) == SymbolStatus.TRADING
+
+    def can_close(self, symbol: str) -> bool:
+        return self.status.get(symbol, SymbolStatus.TRADING) != SymbolStatus.HALTED
+
+    def get_stats(self) -> Dict[str, Any]:
+        return {
+            "affected": {symbol: {"status": status.value, "reason": self.reasons.get(symbol, "")}
+                         for symbol, status in self.status.items() if status != SymbolStatus.TRADING},
+            "halts": self.halts,
+        }
@@ -0,0 +1,171 @@
+"""
+交易对轮换模块
+
+定期扫描交易所全部可交易合约，按规则排名后选出交易的交易对集合（动态交易对）：
+- 市场扫描: 逐个获取候选合约的24小时ticker（并发受限），按排名规则计分
+  - value: 24小时成交额
+  - volatility: 24小时振幅（(最高价 - 最低价) / 最新价）
+  - value_per_volatility: 24小时成交额 / 振幅（成交活跃而波动温和，适合刷量）
+- 轮换规则: 取排名前N的合约；现有交易对排名仍在前 N + buffer 内时保留，避免在边界附近频繁换入换出；
+  固定交易对始终保留且不计入N
+- 离开集合的交易对由策略停止开仓，已有持仓平仓后（或超过平仓期限时主动平仓）再取消订阅
+"""
+
+import asyncio
+from dataclasses import dataclass
+from typing import Any, Dict, Iterable, List, Optional, Set, Tuple
+
+from loguru import logger
+
+from contract_specs import ContractSpec
+
+UNIVERSE_RANKS = ("value", "volatility", "value_per_volatility")
+
+
+@dataclass
+class ScanResult:
+    """候选合约的扫描结果"""
+    symbol: str          # 合约名称
+    contract_id: str
+    value_24h: float     # 24小时成交额（USDT）
+    range_pct: float     # 24小时振幅
+    score: float
+
+
+def _float(value: Any) -> float:
+    try:
+        return float(value) if value not in (None, "") else 0.0
+    except (TypeError, ValueError):
+        return 0.0
+
+
+def score_ticker(spec: ContractSpec, ticker: Dict[str, Any], rank: str) -> Optional[ScanResult]:
+    """
+    按排名规则为合约计分
+
+    Returns:
+        Optional[ScanResult]: ticker缺少价格或成交额时为None
+    """
+    last = _float(ticker.get("lastPrice"))
+    value = _float(ticker.get("value"))
+    if last <= 0 or value <= 0:
+        return None
+    range_pct = max(_float(ticker.get("high")) - _float(ticker.get("low")), 0.0) / last
+    if rank == "value":
+        score = value
+    elif rank == "volatility":
+        score = range_pct
+    else:
+        # 振幅为0（几乎没有成交）时不参与排名
+        if range_pct <= 0:
+            return None
+        score = value / range_pct
+    return ScanResult(spec.symbol, spec.contract_id, value, range_pct, score)
+
+
+class MarketScanner:
+    """市场扫描：获取候选合约的24小时ticker并按规则排名"""
+
+    def __init__(self, client, rank: str = "value_per_volatility", min_value_24h: float = 0.0, concurrency: int = 5):
+        """
+        Args:
+            client: EdgeX客户端（get_ticker）
+            rank: 排名规则（见 UNIVERSE_RANKS）
+            min_value_24h: 24小时成交额低于此值的合约不参与排名（USDT）
+            concurrency: 同时进行的ticker请求数
+        """
+        self.client = client
+        self.rank = rank
+        self.min_value_24h = min_value_24h
+        self.concurrency = concurrency
+        self.scans = 0
+        self.errors = 0
+        self.failed: Set[str] = set()    # 上次扫描获取ticker失败的合约ID
+
+    async def scan(self, specs: Iterable[ContractSpec]) -> List[ScanResult]:
+        """
+        扫描候选合约（交易所暂停交易或暂停开仓的合约除外）
+
+        Returns:
+            List[ScanResult]: 按得分从高到低排序
+        """
+        candidates = [s for s in specs if s.enable_trade and s.enable_open_position]
+        semaphore = asyncio.Semaphore(self.concurrency)
+        self.failed = set()
+
+        async def fetch(spec: ContractSpec) -> Optional[ScanResult]:
+            async with semaphore:
+                try:
+                    ticker = await self.client.get_ticker(spec.contract_id)
+                except Exception as e:
+                    self.errors += 1
+                    self.failed.add(spec.contract_id)
+                    logger.debug(f"[轮换] {spec.symbol} 获取ticker失败: {e}")
+                    return None
+            return score_ticker(spec, ticker, self.rank)
+
+        results = [r for r in await asyncio.gather(*(fetch(s) for s in candidates))
+                   if r is not None and r.value_24h >= self.min_value_24h]
+        results.sort(key=lambda r: r.score, reverse=True)
+        self.scans += 1
+        return results
+
+
+class UniverseRotation:
+    """交易对集合轮换规则"""
+
+    def __init__(self, size: int, buffer: int = 0, pinned: Iterable[str] = (), exclude: Iterable[str] = ()):
+        """
+        Args:
+            size: 轮换选出的交易对数量（不含固定交易对）
+            buffer: 现有交易对排名在前 size + buffer 内时保留
+            pinned: 固定交易对的合约ID（始终保留）
+            exclude: 不参与轮换的合约ID
+        """
+        self.size = size
+        self.buffer = buffer
+        self.pinned: Set[str] = set(pinned)
+        self.exclude: Set[str] = set(exclude)
+        self.rotations = 0
+        self.added = 0
+        self.removed = 0
+
+    def select(self, ranked: List[ScanResult], current: Iterable[str],
+               unscored: Iterable[str] = ()) -> Tuple[List[ScanResult], Set[str]]:
+        """
+        选出新的交易对集合
+
+        Args:
+            ranked: 扫描结果（按得分从高到低）
+            current: 当前交易对的合约ID
+            unscored: 本次获取ticker失败的合约ID（其中的现有交易对保留到下次扫描）
+
+        Returns:
+            Tuple[List[ScanResult], Set[str]]: (新加入的合约, 离开集合的合约ID)
+        """
+        current = set(current)
+        ranked = [r for r in ranked if r.contract_id not in self.exclude and r.contract_id not in self.pinned]
+        selected: List[ScanResult] = []
+        # 现有交易对排名在缓冲范围内时优先保留
+        for result in ranked[:self.size + self.buffer]:
+            if result.contract_id in current and len(selected) < self.size:
+                selected.append(result)
+        for result in ranked:
+            if len(selected) >= self.size:
+                break
+            if result not in selected:
+                selected.append(result)
+        kept = (current & set(unscored)) - self.pinned
+        selected = selected[:max(self.size - len(kept), 0)]
+        target = {r.contract_id for r in selected} | kept | self.pinned
+        added = [r for r in selected if r.contract_id not in current]
+        removed = current - target
+        if added or removed:
+            self.rotations += 1
+            self.added += len(added)
+            self.removed += len(removed)
+        return added, removed
+
+    def get_stats(self) -> Dict[str, Any]:
+        return {"size": self.size, "pinned": len(self.pinned), "rotations": self.rotations,
+                "added": self.added, "removed": self.removed}
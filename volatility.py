@@ -0,0 +1,142 @@
+"""
+波动率估计模块
+
+统一的 VolatilityEstimator 接口，逐期输入对数收益率，输出每期方差/波动率：
+- simple: 滚动窗口样本标准差（等权）
+- ewma: RiskMetrics 指数加权，λ 越小对波动变化反应越快
+- garch: GARCH(1,1)，σ²(t) = ω + α·r²(t-1) + β·σ²(t-1)，ω 由长期方差目标化得到
+
+仓位缩放（risk_model）与波动率状态识别（regime）共用同一种估计器。
+"""
+
+import math
+from abc import ABC, abstractmethod
+from typing import Callable, Optional
+
+from rolling import RollingWindow
+
+
+class VolatilityEstimator(ABC):
+    """波动率估计器接口"""
+
+    def __init__(self, min_samples: int = 2):
+        self.min_samples = min_samples
+        self.samples = 0
+
+    @abstractmethod
+    def update(self, ret: float):
+        """输入一期收益率"""
+
+    @property
+    @abstractmethod
+    def variance(self) -> float:
+        """当前每期方差估计"""
+
+    @property
+    def is_ready(self) -> bool:
+        """样本是否足够"""
+        return self.samples >= self.min_samples
+
+    def volatility(self, periods_per_year: Optional[float] = None) -> float:
+        """
+        波动率
+
+        Args:
+            periods_per_year: 每年期数，提供时返回年化波动率
+
+        Returns:
+            float: 波动率
+        """
+        var = max(self.variance, 0.0)
+        if periods_per_year:
+            var *= periods_per_year
+        return math.sqrt(var)
+
+
+class SimpleEstimator(VolatilityEstimator):
+    """滚动窗口样本标准差（增量更新）"""
+
+    def __init__(self, window: int = 30):
+        super().__init__(min_samples=window)
+        self.returns = RollingWindow(window)
+
+    def update(self, ret: float):
+        self.returns.update(ret)
+        self.samples += 1
+
+    @property
+    def variance(self) -> float:
+        return self.returns.variance
+
+
+class EwmaEstimator(VolatilityEstimator):
+    """RiskMetrics EWMA 估计（零均值）"""
+
+    def __init__(self, ewma_lambda: float = 0.94, min_samples: int = 30):
+        super().__init__(min_samples=min_samples)
+        self.ewma_lambda = ewma_lambda
+        self._variance: Optional[float] = None
+
+    def update(self, ret: float):
+        sq = ret * ret
+        if self._variance is None:
+            self._variance = sq
+        else:
+            self._variance = self.ewma_lambda * self._variance + (1 - self.ewma_lambda) * sq
+        self.samples += 1
+
+    @property
+    def variance(self) -> float:
+        return self._variance or 0.0
+
+
+class Garch11Estimator(VolatilityEstimator):
+    """GARCH(1,1) 估计（固定α、β，长期方差取收益率平方的累计均值）"""
+
+    def __init__(self, alpha: float = 0.08, beta: float = 0.9, min_samples: int = 30):
+        if alpha < 0 or beta < 0 or alpha + beta >= 1:
+            raise ValueError("GARCH参数需满足 α≥0, β≥0, α+β<1")
+        super().__init__(min_samples=min_samples)
+        self.alpha = alpha
+        self.beta = beta
+        self._variance: Optional[float] = None
+        self._sum_sq = 0.0
+
+    def update(self, ret: float):
+        sq = ret * ret
+        self._sum_sq += sq
+        self.samples += 1
+        long_run = self._sum_sq / self.samples
+        if self._variance is None:
+            self._variance = sq
+        else:
+            omega = (1 - self.alpha - self.beta) * long_run
+            self._variance = omega + self.alpha * sq + self.beta * self._variance
+
+    @property
+    def variance(self) -> float:
+        return self._variance or 0.0
+
+
+def estimator_factory(kind: str = "ewma", ewma_lambda: float = 0.94, window: int = 30,
+                      garch_alpha: float = 0.08, garch_beta: float = 0.9) -> Callable[[], VolatilityEstimator]:
+    """
+    根据配置返回估计器构造函数（每个交易对一个实例）
+
+    Args:
+        kind: 估计器类型（simple/ewma/garch）
+        ewma_lambda: EWMA衰减系数
+        window: 滚动窗口/最少样本数
+        garch_alpha: GARCH α
+        garch_beta: GARCH β
+
+    Returns:
+        Callable[[], VolatilityEstimator]: 构造函数
+    """
+    if kind == "simple":
+        return lambda: SimpleEstimator(window=window)
+    if kind == "ewma":
+        return lambda: EwmaEstimator(ewma_lambda=ewma_lambda, min_samples=window)
+    if kind == "garch":
+        return lambda: Garch11Estimator(alpha=garch_alpha, beta=garch_beta, min_samples=window)
+    raise ValueError(f"未知的波动率估计器: {kind}")
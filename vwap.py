@@ -0,0 +1,199 @@
+"""
+VWAP执行模块
+
+按历史日内成交量分布（由TCA记录的市场逐笔成交统计，UTC时间分桶）把大额订单拆分为子订单，
+在执行时段内按各时段的成交量占比下单，使成交均价贴近市场VWAP，降低对盘口的冲击。
+主要用于大仓位的分批平仓。
+"""
+
+import asyncio
+import json
+import os
+import time
+from typing import Callable, Dict, Iterable, List, Optional, Any, Tuple
+from loguru import logger
+
+from edgex_types import Order
+from order_tracker import OrderTracker
+
+
+SECONDS_PER_DAY = 24 * 3600
+
+
+class VolumeProfile:
+    """日内成交量分布（按UTC时间分桶）"""
+
+    def __init__(self, bucket_minutes: int = 5, volumes: Optional[List[float]] = None):
+        """
+        Args:
+            bucket_minutes: 分桶时长（分钟）
+            volumes: 各时段累计成交量，None表示均匀分布
+        """
+        self.bucket_seconds = bucket_minutes * 60
+        self.buckets = SECONDS_PER_DAY // self.bucket_seconds
+        self.volumes = list(volumes) if volumes else [0.0] * self.buckets
+
+    @classmethod
+    def from_trades(cls, trades: Iterable[Tuple[int, float]], bucket_minutes: int = 5) -> "VolumeProfile":
+        """
+        由逐笔成交构建
+
+        Args:
+            trades: (时间戳毫秒, 成交量) 序列
+            bucket_minutes: 分桶时长（分钟）
+        """
+        profile = cls(bucket_minutes)
+        for ts, size in trades:
+            profile.volumes[profile._bucket(ts / 1000)] += float(size)
+        return profile
+
+    @classmethod
+    def load(cls, market_file: str, bucket_minutes: int = 5) -> Dict[str, "VolumeProfile"]:
+        """
+        从TCA行情记录（market.jsonl）构建各交易对的成交量分布
+
+        Returns:
+            Dict[str, VolumeProfile]: 交易对 -> 成交量分布
+        """
+        trades: Dict[str, List[Tuple[int, float]]] = {}
+        if os.path.exists(market_file):
+            with open(market_file, encoding="utf-8") as fp:
+                for line in fp:
+                    if '"trade"' not in line:
+                        continue
+                    event = json.loads(line)
+                    if event.get("type") == "trade":
+                        trades.setdefault(event["symbol"], []).append((int(event["timestamp"]), float(event["size"])))
+        return {symbol: cls.from_trades(t, bucket_minutes) for symbol, t in trades.items()}
+
+    @property
+    def is_empty(self) -> bool:
+        return sum(self.volumes) <= 0
+
+    def _bucket(self, ts: float) -> int:
+        return int(ts % SECONDS_PER_DAY) // self.bucket_seconds
+
+    def _volume_between(self, start: float, end: float) -> float:
+        """[start, end) 时间段内的预期成交量（桶内按时间均匀分布）"""
+        total = 0.0
+        t = start
+        while t < end:
+            bucket = self._bucket(t)
+            bucket_end = t - (t % self.bucket_seconds) + self.bucket_seconds
+            seg_end = min(bucket_end, end)
+            total += self.volumes[bucket] * (seg_end - t) / self.bucket_seconds
+            t = seg_end
+        return total
+
+    def schedule(self, start: float, duration: float, slices: int) -> List[float]:
+        """
+        计算各子订单的数量占比
+
+        Args:
+            start: 开始时间（秒）
+            duration: 执行时长（秒）
+            slices: 子订单个数（等时间间隔）
+
+        Returns:
+            List[float]: 各子订单占比（和为1），无历史数据时均匀分配
+        """
+        step = duration / slices
+        weights = [self._volume_between(start + i * step, start + (i + 1) * step) for i in range(slices)]
+        total = sum(weights)
+        if total <= 0:
+            return [1.0 / slices] * slices
+        return [w / total for w in weights]
+
+
+class VwapExecutor:
+    """VWAP执行器"""
+
+    def __init__(self, client, tracker: OrderTracker, profiles: Dict[str, VolumeProfile],
+                 duration_seconds: int = 300, slices: int = 10):
+        """
+        初始化VWAP执行器
+
+        Args:
+            client: EdgeX客户端
+            tracker: 订单跟踪器
+            profiles: 交易对 -> 成交量分布
+            duration_seconds: 执行时长（秒）
+            slices: 子订单个数
+        """
+        self.client = client
+        self.tracker = tracker
+        self.profiles = profiles
+        self.duration_seconds = duration_seconds
+        self.slices = slices
+
+        # 统计
+        self.executions = 0
+        self.child_orders = 0
+
+    async def execute(self, order: Order, reference_price: Optional[float],
+                      remaining: Callable[[], float], min_size: float = 0.0) -> List[Dict[str, Any]]:
+        """
+        按成交量分布分批执行订单（子订单为市价单，沿用原订单的reduce_only/持仓方向）
+
+        Args:
+            order: 父订单
+            reference_price: 到达价格
+            remaining: 返回当前仍需执行数量的函数（如剩余持仓），为0时提前结束
+            min_size: 最小下单量，不足时并入后续子订单
+
+        Returns:
+            List[Dict[str, Any]]: 各子订单的下单响应
+        """
+        profile = self.profiles.get(order.symbol) or VolumeProfile()
+        start = time.time()
+        weights = profile.schedule(start, self.duration_seconds, self.slices)
+        step = self.duration_seconds / self.slices
+        total = float(order.quantity)
+        self.executions += 1
+
+        logger.info(
+            f"[VWAP] {order.symbol} {order.side.value} {total}，{self.duration_seconds}秒内分{self.slices}笔执行"
+            f"{'（无历史成交量，均匀分配）' if profile.is_empty else ''}"
+        )
+
+        responses: List[Dict[str, Any]] = []
+        sent = 0.0
+        target = 0.0
+        for i, weight in enumerate(weights):
+            delay = start + i * step - time.time()
+            if delay > 0:
+                await asyncio.sleep(delay)
+
+            left = min(total - sent, remaining())
+            if left <= 1e-12:
+                break
+
+            target += total * weight
+            last = i == len(weights) - 1
+            quantity = left if last else min(round(target - sent, 6), left)
+            if quantity < min_size and not last:
+                continue
+            if quantity <= 0:
+                continue
+
+            child = order.model_copy(update={"quantity": quantity})
+            # 子订单数量相同属于预期，不做重复订单抑制
+            response = await self.client.place_order(child, allow_duplicate=True)
+            responses.append(response)
+            sent += quantity
+            self.child_orders += 1
+
+            order_id = str(response.get("data", {}).get("orderId", ""))
+            if order_id:
+                self.tracker.register(order_id, child, reference_price)
+            logger.info(f"[VWAP] {order.symbol} 子订单 {i + 1}/{len(weights)}: {quantity}（累计 {sent}/{total}）")
+
+        return responses
+
+    def get_stats(self) -> Dict[str, Any]:
+        """获取执行统计"""
+        return {
+            "executions": self.executions,
+            "child_orders": self.child_orders,
+            "profiles": sorted(s for s, p in self.profiles.items() if not p.is_empty),
+        }
@@ -0,0 +1,67 @@
+"""
+启动预热模块
+
+策略启动后、第一笔订单之前依次执行启动检查（行情连接、数据预填、账户访问、杠杆、合约规格、签名请求等），
+每项检查的结果写入日志；全部通过前不下任何订单，未通过时按间隔重新检查。
+检查项由策略提供（名称、说明、返回 (是否通过, 详情) 的异步函数），检查函数抛出异常视为未通过。
+"""
+
+import time
+from typing import Any, Awaitable, Callable, Dict, List, Tuple
+
+from loguru import logger
+
+CheckFunc = Callable[[], Awaitable[Tuple[bool, str]]]
+
+
+class StartupWarmup:
+    """启动预热检查"""
+
+    def __init__(self, checks: List[Tuple[str, str, CheckFunc]], enabled: bool = True):
+        """
+        Args:
+            checks: (名称, 说明, 检查函数) 列表，按顺序执行
+            enabled: 是否启用（不启用时视为已通过）
+        """
+        self.checks = checks
+        self.passed = not enabled
+        self.attempts = 0
+        self.last_run = 0.0
+        self.results: Dict[str, Dict[str, Any]] = {}
+
+    async def run(self) -> bool:
+        """
+        执行全部检查（已通过时不再执行）
+
+        Returns:
+            bool: 是否全部通过
+        """
+        if self.passed:
+            return True
+        self.attempts += 1
+        self.last_run = time.time()
+        logger.info(f"[预热] 开始启动检查（第 {self.attempts} 次）")
+        for name, label, check in self.checks:
+            try:
+                ok, detail = await check()
+            except Exception as e:
+                ok, detail = False, f"检查出错: {e}"
+            self.results[name] = {"label": label, "ok": ok, "detail": detail}
+            if ok:
+                logger.info(f"[预热] ✅ {label}: {detail}")
+            else:
+                logger.warning(f"[预热] ❌ {label}: {detail}")
+        self.passed = all(result["ok"] for result in self.results.values())
+        if self.passed:
+            logger.info("[预热] 启动检查全部通过，开始交易")
+        else:
+            failed = [r["label"] for r in self.results.values() if not r["ok"]]
+            logger.error(f"[预热] 启动检查未通过（{'、'.join(failed)}），暂不交易")
+        return self.passed
+
+    def get_stats(self) -> Dict[str, Any]:
+        return {
+            "passed": self.passed,
+            "attempts": self.attempts,
+            "failed": {name: r["detail"] for name, r in self.results.items() if not r["ok"]},
+        }
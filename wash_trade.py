@@ -0,0 +1,174 @@
+"""
+对敲防护模块
+
+刷量策略频繁买卖同一合约，需要避免自己的买单和卖单相互成交或形成对敲特征：
+- 最小时间间隔: 距离本账户上一笔反方向成交不足 min_interval_ms 时拒绝开仓订单
+- 最小价格距离: 观察窗口内本账户有反方向成交、且与订单价格相距不足 min_price_bps 时拒绝开仓订单
+  （市价单按盘口中间价计算）
+- 自成交防护: 订单价格会与本账户未终结的反方向限价单成交时拒绝（买单价格不低于自己的卖单、卖单价格不高于自己的买单；
+  市价单与任何反方向限价挂单都视为会成交），平仓订单同样检查，被拒绝的平仓在挂单终结后重新提交
+只减仓订单（平仓、止损）不受时间和价格距离限制，避免无法平仓；条件单在触发前不检查。
+审计: 每笔成交与 audit_window_ms 内的反方向成交比较，价格相距不超过 min_price_bps（为0时价格相同）的成交对
+记为疑似自成交（自己的买卖单相互成交时两笔成交价格相同、时间几乎一致），写入审计文件（JSON Lines）并在性能报告中汇总。
+"""
+
+import json
+import os
+import time
+from collections import deque
+from typing import Any, Deque, Dict, Iterable, List, Optional
+
+from loguru import logger
+
+from edgex_types import Fill, Order, OrderSide, OrderType, TRIGGER_ORDER_TYPES
+from order_tracker import TrackedOrder
+
+
+def _distance_bps(a: float, b: float) -> float:
+    return abs(a - b) / b * 10000 if b > 0 else 0.0
+
+
+class WashTradeGuard:
+    """对敲防护与疑似自成交审计"""
+
+    def __init__(self, min_interval_ms: int = 0, min_price_bps: float = 0.0, self_match_prevention: bool = True,
+                 window_seconds: float = 60.0, audit_window_ms: int = 1000, report_path: str = "",
+                 max_recent: int = 50):
+        """
+        Args:
+            min_interval_ms: 与反方向成交的最小时间间隔（毫秒，0为不检查）
+            min_price_bps: 与观察窗口内反方向成交的最小价格距离（bps，0为不检查）
+            self_match_prevention: 是否拒绝会与自己的挂单成交的订单
+            window_seconds: 价格距离检查的观察窗口（秒）
+            audit_window_ms: 审计时两笔反方向成交相距不超过多少毫秒视为疑似自成交
+            report_path: 疑似自成交的审计文件（空为不写入）
+            max_recent: 性能报告保留的最近疑似记录数
+        """
+        self.min_interval_ms = min_interval_ms
+        self.min_price_bps = min_price_bps
+        self.self_match_prevention = self_match_prevention
+        self.window_ms = int(max(window_seconds * 1000, audit_window_ms))
+        self.audit_window_ms = audit_window_ms
+        self.report_path = report_path
+        self.fills: Dict[str, Deque[Fill]] = {}     # 交易对 -> 观察窗口内的成交
+        self.suspects: Deque[Dict[str, Any]] = deque(maxlen=max_recent)
+
+        # 统计
+        self.blocked = {"interval": 0, "price_distance": 0, "self_match": 0}
+        self.flagged = 0
+
+    def _recent(self, symbol: str, now_ms: int) -> Deque[Fill]:
+        fills = self.fills.setdefault(symbol, deque())
+        while fills and now_ms - fills[0].timestamp > self.window_ms:
+            fills.popleft()
+        return fills
+
+    def check(self, order: Order, price: Optional[float], resting: Iterable[TrackedOrder],
+              now_ms: Optional[int] = None) -> Optional[str]:
+        """
+        下单前检查
+
+        Args:
+            order: 待发送的订单
+            price: 订单价格（市价单为盘口中间价，未知时为None，此时不检查价格距离）
+            resting: 本账户在该交易对上未终结的订单
+
+        Returns:
+            Optional[str]: 拒绝原因，通过时返回None
+        """
+        if order.order_type in TRIGGER_ORDER_TYPES:
+            return None
+        now_ms = now_ms if now_ms is not None else int(time.time() * 1000)
+
+        if self.self_match_prevention:
+            for other in resting:
+                if other.side == order.side or other.order_type != OrderType.LIMIT or not other.price:
+                    continue
+                if order.order_type == OrderType.MARKET or not order.price:
+                    crosses = True
+                elif order.side == OrderSide.BUY:
+                    crosses = order.price >= other.price
+                else:
+                    crosses = order.price <= other.price
+                if crosses:
+                    self.blocked["self_match"] += 1
+                    return f"会与自己的{other.side.value}挂单 {other.order_id}（价格 {other.price}）成交"
+
+        if order.reduce_only:
+            return None
+        opposite = [f for f in self._recent(order.symbol, now_ms) if f.side != order.side]
+        if not opposite:
+            return None
+        if self.min_interval_ms > 0:
+            elapsed = now_ms - opposite[-1].timestamp
+            if elapsed < self.min_interval_ms:
+                self.blocked["interval"] += 1
+                return f"距离上一笔{opposite[-1].side.value}成交仅 {elapsed}ms（最小间隔 {self.min_interval_ms}ms）"
+        if self.min_price_bps > 0 and price:
+            for fill in opposite:
+                distance = _distance_bps(price, fill.price)
+                if distance < self.min_price_bps:
+                    self.blocked["price_distance"] += 1
+                    return (f"价格 {price} 与 {(now_ms - fill.timestamp) / 1000:.1f} 秒前的{fill.side.value}成交 "
+                            f"{fill.price} 仅相距 {distance:.2f} bps（最小 {self.min_price_bps} bps）")
+        return None
+
+    def record_fill(self, fill: Fill) -> List[Dict[str, Any]]:
+        """
+        记录成交并与 audit_window_ms 内的反方向成交比较
+
+        Returns:
+            List[Dict[str, Any]]: 本笔成交产生的疑似自成交记录
+        """
+        fills = self._recent(fill.symbol, fill.timestamp)
+        found = []
+        for other in fills:
+            if other.side == fill.side or abs(fill.timestamp - other.timestamp) > self.audit_window_ms:
+                continue
+            distance = _distance_bps(fill.price, other.price)
+            if distance > self.min_price_bps:
+                continue
+            found.append({
+                "timestamp": fill.timestamp,
+                "symbol": fill.symbol,
+                "order_id": fill.order_id,
+                "side": fill.side.value,
+                "price": fill.price,
+                "size": fill.size,
+                "is_maker": fill.is_maker,
+                "opposite_order_id": other.order_id,
+                "opposite_price": other.price,
+                "opposite_size": other.size,
+                "interval_ms": fill.timestamp - other.timestamp,
+                "distance_bps": round(distance, 4),
+            })
+        fills.append(fill)
+        for record in found:
+            self.flagged += 1
+            self.suspects.append(record)
+            logger.warning(f"[对敲防护] {fill.symbol} 疑似自成交: {record['side']} {fill.price} 与 "
+                           f"{record['interval_ms']}ms 前的反方向成交 {record['opposite_price']} 相距 "
+                           f"{record['distance_bps']} bps（订单 {fill.order_id} / {record['opposite_order_id']}）")
+            self._write(record)
+        return found
+
+    def _write(self, record: Dict[str, Any]):
+        if not self.report_path:
+            return
+        try:
+            os.makedirs(os.path.dirname(self.report_path) or ".", exist_ok=True)
+            with open(self.report_path, "a", encoding="utf-8") as f:
+                f.write(json.dumps(record, ensure_ascii=False) + "\n")
+        except OSError as e:
+            logger.error(f"[对敲防护] 写入审计文件失败: {e}")
+
+    def get_stats(self) -> Dict[str, Any]:
+        return {
+            "min_interval_ms": self.min_interval_ms,
+            "min_price_bps": self.min_price_bps,
+            "audit_window_ms": self.audit_window_ms,
+            "self_match_prevention": self.self_match_prevention,
+            "blocked": dict(self.blocked),
+            "flagged": self.flagged,
+            "recent": list(self.suspects)[-5:],
+        }
@@ -0,0 +1,183 @@
+"""
+信号Webhook模块
+
+提供HTTP接口接收TradingView风格的告警JSON，转换为 TradeSignal 交由策略执行
+（与内部信号相同的风控检查），使外部图表策略可以驱动机器人。
+
+告警示例（TradingView告警消息中填写）：
+    {
+        "passphrase": "<EDGEX_WEBHOOK_SECRET>",
+        "ticker": "{{ticker}}",
+        "action": "{{strategy.order.action}}",
+        "price": "{{close}}",
+        "stop_loss": 95.0,
+        "take_profit": 105.0
+    }
+
+action 支持 buy/long、sell/short、close/exit/flat；也可用 market_position
+（{{strategy.market_position}}，值为 long/short/flat）。ticker 支持 BTCUSDT、BTC-USDT、
+BINANCE:BTCUSDT.P 等写法，按去除交易所前缀和分隔符后匹配配置的交易对。
+"""
+
+import hmac
+import json
+import re
+from typing import Awaitable, Callable, Dict, List, Optional, Any, Tuple
+from loguru import logger
+from aiohttp import web
+
+from edgex_types import TradeDirection, TradeSignal
+
+
+# 告警动作
+ACTION_OPEN = "open"
+ACTION_CLOSE = "close"
+
+_DIRECTION_ALIASES = {
+    "buy": TradeDirection.LONG, "long": TradeDirection.LONG,
+    "sell": TradeDirection.SHORT, "short": TradeDirection.SHORT,
+}
+_CLOSE_ALIASES = {"close", "exit", "flat"}
+
+
+class AlertError(ValueError):
+    """告警内容无效"""
+
+
+def _normalize_ticker(ticker: str) -> str:
+    """去除交易所前缀、永续后缀和分隔符"""
+    ticker = ticker.split(":")[-1].upper()
+    ticker = re.sub(r"\.P$|PERP$", "", ticker)
+    return re.sub(r"[^A-Z0-9]", "", ticker)
+
+
+def _float(value: Any) -> float:
+    try:
+        return float(value) if value not in (None, "") else 0.0
+    except (TypeError, ValueError):
+        raise AlertError(f"数值字段无效: {value}")
+
+
+def parse_alert(payload: Dict[str, Any], symbols: List[str]) -> Tuple[str, TradeSignal]:
+    """
+    解析告警
+
+    Args:
+        payload: 告警JSON
+        symbols: 配置的交易对列表
+
+    Returns:
+        Tuple[str, TradeSignal]: (动作 open/close, 交易信号)；平仓动作的信号方向为HOLD
+
+    Raises:
+        AlertError: 告警内容无效
+    """
+    ticker = str(payload.get("ticker") or payload.get("symbol") or "")
+    lookup = {_normalize_ticker(s): s for s in symbols}
+    symbol = lookup.get(_normalize_ticker(ticker))
+    if not symbol:
+        raise AlertError(f"未配置的交易对: {ticker}")
+
+    action = str(payload.get("action") or payload.get("market_position") or "").strip().lower()
+    if action in _CLOSE_ALIASES:
+        direction = TradeDirection.HOLD
+    elif action in _DIRECTION_ALIASES:
+        direction = _DIRECTION_ALIASES[action]
+    else:
+        raise AlertError(f"未知的动作: {action}")
+
+    signal = TradeSignal(
+        symbol=symbol,
+        direction=direction,
+        confidence=_float(payload.get("confidence")) or 1.0,
+        price=_float(payload.get("price")),
+        stop_loss=_float(payload.get("stop_loss")),
+        take_profit=_float(payload.get("take_profit"))
+    )
+    return (ACTION_CLOSE if direction == TradeDirection.HOLD else ACTION_OPEN), signal
+
+
+class WebhookServer:
+    """信号Webhook HTTP服务"""
+
+    def __init__(self, host: str, port: int, path: str, secret: str, symbols: List[str],
+                 handler: Callable[[str, TradeSignal], Awaitable[None]]):
+        """
+        初始化Webhook服务
+
+        Args:
+            host: 监听地址
+            port: 监听端口
+            path: 接收告警的路径
+            secret: 校验口令（告警JSON的passphrase字段或X-Webhook-Secret请求头）
+            symbols: 配置的交易对列表
+            handler: 信号处理协程（参数为动作和交易信号）
+        """
+        self.host = host
+        self.port = port
+        self.path = path
+        self.secret = secret
+        self.symbols = symbols
+        self.handler = handler
+        self._runner: Optional[web.AppRunner] = None
+
+        # 统计
+        self.received = 0
+        self.accepted = 0
+        self.rejected = 0
+
+    async def start(self):
+        """启动HTTP服务"""
+        app = web.Application()
+        app.router.add_post(self.path, self._handle)
+        self._runner = web.AppRunner(app)
+        await self._runner.setup()
+        await web.TCPSite(self._runner, self.host, self.port).start()
+        logger.info(f"[Webhook] 信号接口已启动: http://{self.host}:{self.port}{self.path}")
+
+    async def stop(self):
+        """停止HTTP服务"""
+        if self._runner:
+            await self._runner.cleanup()
+            self._runner = None
+
+    def _authorized(self, request: web.Request, payload: Dict[str, Any]) -> bool:
+        provided = str(payload.get("passphrase") or request.headers.get("X-Webhook-Secret") or "")
+        return hmac.compare_digest(provided.encode(), self.secret.encode())
+
+    async def _handle(self, request: web.Request) -> web.Response:
+        """处理告警请求"""
+        self.received += 1
+        try:
+            # TradingView以text/plain发送告警消息
+            payload = json.loads(await request.text())
+            if not isinstance(payload, dict):
+                raise AlertError("告警内容必须为JSON对象")
+        except (ValueError, AlertError) as e:
+            self.rejected += 1
+            return web.json_response({"status": "error", "message": f"无效的JSON: {e}"}, status=400)
+
+        if not self._authorized(request, payload):
+            self.rejected += 1
+            logger.warning(f"[Webhook] 口令校验失败，来源 {request.remote}")
+            return web.json_response({"status": "error", "message": "unauthorized"}, status=401)
+
+        try:
+            action, signal = parse_alert(payload, self.symbols)
+        except AlertError as e:
+            self.rejected += 1
+            logger.warning(f"[Webhook] 告警无效: {e}")
+            return web.json_response({"status": "error", "message": str(e)}, status=400)
+
+        self.accepted += 1
+        logger.info(f"[Webhook] 收到告警 {signal.symbol} {action} {signal.direction.value} @ {signal.price or '市价'}")
+        try:
+            await self.handler(action, signal)
+        except Exception as e:
+            logger.error(f"[Webhook] 处理信号失败: {e}")
+            return web.json_response({"status": "error", "message": str(e)}, status=500)
+        return web.json_response({"status": "accepted", "symbol": signal.symbol, "action": action})
+
+    def get_stats(self) -> Dict[str, Any]:
+        """获取接收统计"""
+        return {"received": self.received, "accepted": self.accepted, "rejected": self.rejected}
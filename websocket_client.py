@@ -4,20 +4,57 @@ EdgeX WebSocket客户端
 """
 
 import asyncio
-import json
+import threading
 import time
-from typing import Dict, List, Callable, Optional
+from typing import Any, Dict, List, Callable, Optional, Set, Union
 from loguru import logger
-from edgex_types import PriceData
+from fast_json import loads
+from account_events import AccountEventStream
+from edgex_types import FundingRate, MarketActivity, MarkPriceData, OrderBook, PriceData
+from activity_filter import parse_market_activity
+from funding_window import parse_funding_rate
+from tracing import span
+from ws_subscriptions import SubscriptionManager
+from sequence_tracker import SequenceTracker, DEPTH_GAP, DEPTH_RESYNCING, DEPTH_STALE
+from book_validation import BookValidator
 from sdk.edgex_sdk.ws.manager import Manager as WebSocketManager
+from sdk.edgex_sdk.ws.channels import ChannelMessage
 from sdk.edgex_sdk.internal.starkex_signing_adapter import StarkExSigningAdapter
 
 
+class PublicShard:
+    """一条公共行情连接及其上的频道订阅"""
+    
+    def __init__(self, index: int, manager: WebSocketManager, ack_timeout: float):
+        self.index = index
+        self.manager = manager
+        self.subscriptions = SubscriptionManager(ack_timeout)
+        self.symbols: Set[str] = set()
+    
+    @property
+    def name(self) -> str:
+        return "public" if self.index == 0 else f"public-{self.index}"
+    
+    @property
+    def client(self):
+        return self.manager.public_client
+    
+    @property
+    def load(self) -> int:
+        """当前订阅的频道数"""
+        return len(self.subscriptions.states)
+
+
 class RealTimePriceStream:
     """实时价格数据流"""
     
     def __init__(self, symbols: List[str], contract_ids: Dict[str, str], 
-                 base_url: str, account_id: int, stark_private_key: str):
+                 base_url: str, account_id: int, stark_private_key: str,
+                 subscribe_depth: bool = False, subscribe_trades: bool = False,
+                 depth_level: int = 15, enable_private: bool = False, proxy: Optional[str] = None,
+                 cpu_affinity: Optional[Set[int]] = None, ack_timeout: float = 5.0, compression: bool = False,
+                 connections: int = 1, max_channels_per_connection: int = 0, sequence_check: bool = True,
+                 book_validation: bool = True, book_checksum: bool = False, clock=None):
         """
         初始化价格流
         
@@ -27,67 +64,237 @@ class RealTimePriceStream:
             base_url: EdgeX API基础URL
             account_id: 账户ID
             stark_private_key: Stark私钥
+            subscribe_depth: 是否订阅深度数据
+            subscribe_trades: 是否订阅逐笔成交
+            depth_level: 深度档位（15或200）
+            enable_private: 是否连接私有WebSocket（订单/成交回报）
+            proxy: 出站代理地址（None为直连）
+            cpu_affinity: 行情线程绑定的CPU（None为不绑核，仅Linux）
+            ack_timeout: 等待订阅确认的超时时间（秒）
+            compression: 是否启用消息压缩（permessage-deflate，及gzip/zlib压缩的二进制消息）
+            connections: 公共行情连接数（交易对按频道数均衡分配到各连接，私有频道只用第一条连接的管理器）
+            max_channels_per_connection: 每条连接最多订阅的频道数（0为不限制）
+            sequence_check: 是否检查深度和逐笔成交的序列（深度出现缺口时重新同步订单簿）
+            book_validation: 是否在每次深度更新后校验本地订单簿（损坏时重新同步）
+            book_checksum: 是否校验深度数据中的校验和（需启用 book_validation）
+            clock: 签名时间戳来源（与REST客户端共用，私有连接认证使用按服务器时间校正的时间戳）
         """
-        self.symbols = symbols
+        self.symbols = list(symbols)
         self.contract_ids = contract_ids
+        self.subscribe_depth = subscribe_depth
+        self.subscribe_trades = subscribe_trades
+        self.depth_level = depth_level
+        self.enable_private = enable_private
+        self.private_connected = False
         self.running = False
         self.price_callbacks: List[Callable[[str, PriceData], None]] = []
+        self.depth_callbacks: List[Callable[[str, OrderBook], None]] = []
+        self.trade_callbacks: List[Callable[[str, float, float, Optional[bool]], None]] = []
+        self.mark_price_callbacks: List[Callable[[str, MarkPriceData], None]] = []
+        self.metadata_callbacks: List[Callable[[List[Dict[str, Any]]], None]] = []
+        # 按频道模式注册的回调：(频道模式, 原回调, 交给SDK的处理器)
+        self.channel_callbacks: List[tuple] = []
+        # 私有频道的订单、成交、持仓和资金费事件
+        self.account_events = AccountEventStream(self._symbol_for_contract)
         self.price_history: Dict[str, List[PriceData]] = {}
+        self.mark_prices: Dict[str, MarkPriceData] = {}
+        self.market_activity: Dict[str, MarketActivity] = {}  # 持仓量与24小时成交量（来自ticker）
+        self.funding_rates: Dict[str, FundingRate] = {}  # 资金费率与下次结算时间（来自ticker）
+        self.order_books: Dict[str, OrderBook] = {}
+        self._contract_symbols: Dict[str, str] = {}  # 合约ID -> 交易对
+        self.base_url = base_url
+        self._account_id = account_id
+        self._stark_private_key = stark_private_key
+        self._proxy = proxy
+        self._cpu_affinity = cpu_affinity
+        self._compression = compression
+        self._clock = clock
         
-        # 创建WebSocket管理器
-        self.ws_manager = WebSocketManager(
-            base_url=base_url,
-            account_id=account_id,
-            stark_pri_key=stark_private_key,
-            signing_adapter=StarkExSigningAdapter()
-        )
+        # 公共行情连接分片：第一条连接的管理器同时负责私有连接
+        self._ack_timeout = ack_timeout
+        self.connections = max(1, connections)
+        self.max_channels_per_connection = max_channels_per_connection
+        self.ws_manager = self._create_manager(base_url)
+        self.shards = self._create_shards()
+        self.symbol_shards: Dict[str, PublicShard] = {}  # 交易对 -> 所在连接
+        # 多条连接的处理器在各自的分发线程中执行，串行化后下游回调与单连接时一样按顺序收到行情
+        self._event_lock = threading.RLock()
+        # 深度/逐笔成交序列检查（以交易对为键）
+        self.sequence: Optional[SequenceTracker] = SequenceTracker() if sequence_check else None
+        # 本地订单簿校验（一致性检查和可选的校验和）
+        self.book_validator: Optional[BookValidator] = BookValidator(book_checksum) if book_validation else None
+        self._loop: Optional[asyncio.AbstractEventLoop] = None
         
         # 为每个交易对初始化价格历史
         for symbol in symbols:
             self.price_history[symbol] = []
     
+    def _create_manager(self, base_url: str) -> WebSocketManager:
+        return WebSocketManager(
+            base_url=base_url,
+            account_id=self._account_id,
+            stark_pri_key=self._stark_private_key,
+            signing_adapter=StarkExSigningAdapter(),
+            proxy=self._proxy,
+            cpu_affinity=self._cpu_affinity,
+            compression=self._compression,
+            clock=self._clock
+        )
+    
+    def _create_shards(self) -> List[PublicShard]:
+        managers = [self.ws_manager] + [self._create_manager(self.base_url) for _ in range(self.connections - 1)]
+        return [PublicShard(i, manager, self._ack_timeout) for i, manager in enumerate(managers)]
+    
+    @property
+    def is_connected(self) -> bool:
+        """公共行情连接是否全部正常（任一连接断开时部分交易对收不到行情）"""
+        return self.running and all(shard.client is not None and shard.client.is_connected for shard in self.shards)
+    
+    def _serialized(self, handler: Callable[[Any], None]) -> Callable[[Any], None]:
+        """多条连接时串行执行处理器（单连接时原样返回）"""
+        if len(self.shards) == 1:
+            return handler
+        
+        def run(payload):
+            with self._event_lock:
+                handler(payload)
+        return run
+    
+    async def switch_endpoint(self, base_url: str):
+        """
+        切换WebSocket地址：关闭现有连接，连接新地址并重新订阅（已注册的回调保留）
+        
+        Raises:
+            Exception: 新地址连接失败（已达到最大重试次数）
+        """
+        logger.info(f"WebSocket切换到 {base_url}")
+        self.running = False
+        self.private_connected = False
+        for shard in self.shards:
+            shard.manager.disconnect_all()
+        self.base_url = base_url
+        self.ws_manager = self._create_manager(base_url)
+        self.shards = self._create_shards()
+        await self.start()
+    
     def add_price_callback(self, callback: Callable[[str, PriceData], None]):
         """添加价格数据回调函数"""
         self.price_callbacks.append(callback)
     
+    def add_mark_price_callback(self, callback: Callable[[str, MarkPriceData], None]):
+        """添加标记价格/指数价格回调函数（来自ticker频道）"""
+        self.mark_price_callbacks.append(callback)
+    
+    def add_depth_callback(self, callback: Callable[[str, OrderBook], None]):
+        """添加深度数据回调函数（参数为合并后的完整订单簿）"""
+        self.depth_callbacks.append(callback)
+    
+    def add_trade_callback(self, callback: Callable[[str, float, float, Optional[bool]], None]):
+        """添加逐笔成交回调函数（交易对, 价格, 数量, 买方是否为挂单方）"""
+        self.trade_callbacks.append(callback)
+    
+    def add_metadata_callback(self, callback: Callable[[List[Dict[str, Any]]], None]):
+        """添加合约元数据回调（参数为有变化的合约元数据列表；有回调时订阅 metadata 频道，需在 start() 前添加）"""
+        self.metadata_callbacks.append(callback)
+    
+    def add_channel_callback(self, pattern: str, callback: Callable[[Optional[str], ChannelMessage], None]):
+        """
+        按频道模式添加回调（如 "depth.10000001.*"、"ticker.*"），参数为（交易对, 已解析的频道消息）
+        
+        在SDK分发线程中调用；重新连接和切换端点后自动重新注册。
+        """
+        def handler(message: ChannelMessage):
+            try:
+                with self._event_lock:
+                    callback(self._lookup_symbol(message.contract_id), message)
+            except Exception as e:
+                logger.error(f"频道 {message.channel} 回调函数执行失败: {e}")
+        
+        self.channel_callbacks.append((pattern, callback, handler))
+        for shard in self.shards:
+            if shard.client is not None:
+                shard.client.on_channel(pattern, handler)
+    
+    def remove_channel_callback(self, pattern: str, callback: Optional[Callable] = None):
+        """移除按频道模式注册的回调（callback为None时移除该模式的全部回调）"""
+        removed = [entry for entry in self.channel_callbacks
+                   if entry[0] == pattern and (callback is None or entry[1] is callback)]
+        self.channel_callbacks = [entry for entry in self.channel_callbacks if entry not in removed]
+        for shard in self.shards:
+            if shard.client is not None:
+                for _, _, handler in removed:
+                    shard.client.off_channel(pattern, handler)
+    
     async def start(self):
         """启动WebSocket连接（带重试机制）"""
         max_retries = 3
         retry_delay = 3  # 3秒重试间隔
+        self._loop = asyncio.get_running_loop()
         
         for attempt in range(max_retries):
             try:
                 # 连接公共WebSocket
                 logger.info(f"尝试连接WebSocket (第 {attempt + 1}/{max_retries} 次)...")
-                self.ws_manager.connect_public()
+                for shard in self.shards:
+                    shard.manager.connect_public()
                 self.running = True
                 
-                logger.info("✅ WebSocket连接已建立")
+                logger.info(f"✅ WebSocket连接已建立{f'（{len(self.shards)} 条公共连接）' if len(self.shards) > 1 else ''}")
                 
                 # 等待连接稳定
                 await asyncio.sleep(1)
                 
-                # 订阅所有交易对的ticker数据
-                subscribed_count = 0
+                # 按频道类型注册处理器（同类型所有交易对共用，按频道中的合约ID区分交易对）
+                # 行情频道直接接收SDK已解码的消息，避免重复解码
+                # 深度频道合并积压：处理不过来时同一频道的增量批量交给处理器，只发布最新订单簿
+                for shard in self.shards:
+                    public_client = shard.manager.get_public_client()
+                    public_client.on_message("ticker", self._serialized(self._handle_ticker_message), parsed=True)
+                    if self.subscribe_depth:
+                        public_client.on_message("depth", self._serialized(self._handle_depth_message),
+                                                 parsed=True, coalesce=True)
+                    if self.subscribe_trades:
+                        public_client.on_message("trades", self._serialized(self._handle_trade_message), parsed=True)
+                    # 按频道模式注册的回调（重试连接时复用同一个客户端，先移除再注册）
+                    for pattern, _, handler in self.channel_callbacks:
+                        public_client.off_channel(pattern, handler)
+                        public_client.on_channel(pattern, handler)
+                    shard.subscriptions.reset()
+                    shard.subscriptions.attach(public_client)
+                    shard.symbols.clear()
+                
+                # 新连接订阅后先推送快照，之前的序列状态作废
+                if self.sequence:
+                    self.sequence.reset()
+                if self.book_validator:
+                    self.book_validator.reset()
+                
+                # 订阅所有交易对（ticker，按配置订阅深度和逐笔成交），按频道数均衡分配到各连接，并发等待服务器确认
+                self.symbol_shards.clear()
                 for symbol in self.symbols:
-                    contract_id = self.contract_ids.get(symbol)
-                    if contract_id:
-                        try:
-                            # 订阅ticker数据
-                            self.ws_manager.subscribe_ticker(
-                                contract_id=contract_id,
-                                handler=lambda msg, sym=symbol: self._handle_ticker_message(sym, msg)
-                            )
-                            logger.info(f"✅ 已订阅 {symbol} ({contract_id}) 的ticker数据")
-                            subscribed_count += 1
-                        except Exception as sub_error:
-                            logger.warning(f"订阅 {symbol} 失败: {sub_error}")
+                    self._assign_shard(symbol)
+                results = await asyncio.gather(*(self._subscribe_symbol(symbol) for symbol in self.symbols))
+                subscribed_count = sum(results)
                 
                 if subscribed_count == 0:
                     raise ValueError("没有成功订阅任何交易对")
                 
+                # 合约元数据变化推送（只在第一条连接上订阅）
+                if self.metadata_callbacks:
+                    metadata_client = self.shards[0].client
+                    metadata_client.on_message("metadata", self._serialized(self._handle_metadata_message), parsed=True)
+                    try:
+                        await self.shards[0].subscriptions.subscribe(["metadata"])
+                    except Exception as e:
+                        logger.debug(f"订阅合约元数据频道失败: {e}")
+                    if "metadata" not in self.shards[0].subscriptions.states:
+                        logger.warning("订阅合约元数据频道失败，合约规格只按定期刷新更新")
+                
                 logger.info(f"✅ 成功订阅 {subscribed_count}/{len(self.symbols)} 个交易对")
                 
+                if self.enable_private:
+                    self._start_private()
+                
                 # 连接成功，跳出重试循环
                 break
                 
@@ -95,11 +302,11 @@ class RealTimePriceStream:
                 logger.warning(f"⚠️ WebSocket连接失败 (尝试 {attempt + 1}/{max_retries}): {e}")
                 
                 # 清理连接
-                try:
-                    if self.ws_manager:
-                        self.ws_manager.disconnect_public()
-                except:
-                    pass
+                for shard in self.shards:
+                    try:
+                        shard.manager.disconnect_public()
+                    except:
+                        pass
                 
                 if attempt < max_retries - 1:
                     logger.info(f"等待 {retry_delay} 秒后重试...")
@@ -108,42 +315,387 @@ class RealTimePriceStream:
                     logger.error(f"❌ WebSocket连接失败，已达到最大重试次数 ({max_retries})")
                     raise
     
+    def _channels(self, contract_id: str) -> List[str]:
+        """交易对需要订阅的频道"""
+        channels = [f"ticker.{contract_id}"]
+        if self.subscribe_depth:
+            channels.append(f"depth.{contract_id}.{self.depth_level}")
+        if self.subscribe_trades:
+            channels.append(f"trades.{contract_id}")
+        return channels
+    
+    def _assign_shard(self, symbol: str) -> Optional[PublicShard]:
+        """
+        为交易对分配连接：已分配时沿用，否则选择（计入已分配未订阅的频道后）频道数最少的连接
+        
+        Returns:
+            Optional[PublicShard]: 所有连接都已达到频道上限时返回None
+        """
+        shard = self.symbol_shards.get(symbol)
+        if shard is not None:
+            return shard
+        contract_id = self.contract_ids.get(symbol)
+        if not contract_id:
+            return None
+        needed = len(self._channels(contract_id))
+        per_symbol = len(self._channels(""))
+        
+        def planned(s: PublicShard) -> int:
+            # 已分配但尚未发出订阅的交易对也计入
+            return max(s.load, len(s.symbols) * per_symbol)
+        
+        candidates = [s for s in self.shards
+                      if not self.max_channels_per_connection or planned(s) + needed <= self.max_channels_per_connection]
+        if not candidates:
+            logger.warning(f"订阅 {symbol} 失败: 所有连接都已达到频道上限 {self.max_channels_per_connection}")
+            return None
+        shard = min(candidates, key=lambda s: (planned(s), s.index))
+        shard.symbols.add(symbol)
+        self.symbol_shards[symbol] = shard
+        return shard
+    
+    def _release_shard(self, symbol: str):
+        shard = self.symbol_shards.pop(symbol, None)
+        if shard is not None:
+            shard.symbols.discard(symbol)
+    
+    async def _subscribe_symbol(self, symbol: str) -> bool:
+        """订阅交易对的频道（发送失败或服务器拒绝时返回False，超时未确认视为已订阅）"""
+        contract_id = self.contract_ids.get(symbol)
+        if not contract_id:
+            return False
+        shard = self._assign_shard(symbol)
+        if shard is None:
+            return False
+        try:
+            results = await shard.subscriptions.subscribe(self._channels(contract_id))
+        except Exception as e:
+            logger.warning(f"订阅 {symbol} 失败: {e}")
+            return False
+        if not all(c in shard.subscriptions.states for c in results):
+            logger.warning(f"订阅 {symbol} 失败: 服务器拒绝订阅")
+            return False
+        state = "已确认" if all(results.values()) else "未确认"
+        where = f"，连接 {shard.name}" if len(self.shards) > 1 else ""
+        logger.info(f"✅ 已订阅 {symbol} ({contract_id}) 的行情（{state}{where}）: {', '.join(results)}")
+        return True
+    
+    async def add_symbol(self, symbol: str, contract_id: str) -> bool:
+        """
+        连接期间增加交易对：订阅其行情频道并等待确认
+        
+        Args:
+            symbol: 交易对
+            contract_id: 合约ID
+            
+        Returns:
+            bool: 是否订阅成功（超时未确认也视为成功，频道状态为未确认；失败时不保留该交易对）
+        """
+        self.contract_ids[symbol] = contract_id
+        self._contract_symbols[contract_id] = symbol
+        self.price_history.setdefault(symbol, [])
+        if symbol not in self.symbols:
+            self.symbols.append(symbol)
+        if not self.is_connected:
+            # 未连接时只登记，连接建立时订阅
+            return False
+        if await self._subscribe_symbol(symbol):
+            return True
+        # 部分频道被拒绝时撤回已订阅的频道
+        await self.remove_symbol(symbol)
+        return False
+    
+    async def remove_symbol(self, symbol: str) -> bool:
+        """
+        连接期间移除交易对：取消订阅其行情频道并清理行情缓存
+        
+        Returns:
+            bool: 服务器是否确认全部取消订阅
+        """
+        if symbol in self.symbols:
+            self.symbols.remove(symbol)
+        contract_id = self.contract_ids.get(symbol)
+        shard = self.symbol_shards.get(symbol)
+        confirmed = True
+        if contract_id and shard is not None and self.is_connected:
+            results = await shard.subscriptions.unsubscribe(self._channels(contract_id))
+            confirmed = all(results.values())
+        self._release_shard(symbol)
+        self.price_history.pop(symbol, None)
+        self.order_books.pop(symbol, None)
+        self.mark_prices.pop(symbol, None)
+        self.market_activity.pop(symbol, None)
+        self.funding_rates.pop(symbol, None)
+        logger.info(f"已取消订阅 {symbol} 的行情{'' if confirmed else '（未确认）'}")
+        return confirmed
+    
+    def get_subscription_stats(self) -> Dict[str, Any]:
+        """订阅状态统计（各状态的频道数，确认超时和服务器错误次数，各连接合计；connections为各连接的频道数）"""
+        stats: Dict[str, Any] = {}
+        for shard in self.shards:
+            for key, value in shard.subscriptions.get_stats().items():
+                stats[key] = stats.get(key, 0) + value
+        stats["connections"] = {shard.name: shard.load for shard in self.shards}
+        return stats
+    
+    def _start_private(self):
+        """连接私有WebSocket，接收订单与成交回报（失败不影响行情订阅）"""
+        try:
+            self.ws_manager.connect_private()
+            private_client = self.ws_manager.get_private_client()
+            private_client.on_message("trade-event", self.account_events.handle_message, parsed=True)
+            self.private_connected = True
+            logger.info("✅ 私有WebSocket连接已建立（订单/成交回报）")
+        except Exception as e:
+            logger.warning(f"⚠️ 私有WebSocket连接失败，订单状态将无法实时更新: {e}")
+    
     async def stop(self):
         """停止WebSocket连接"""
         self.running = False
-        if self.ws_manager:
-            self.ws_manager.disconnect_all()
+        for shard in self.shards:
+            shard.manager.disconnect_all()
         logger.info("WebSocket连接已关闭")
     
-    def _handle_ticker_message(self, symbol: str, message: str):
+    def _resolve_message(self, message: Union[str, Dict[str, Any]]) -> tuple:
+        """
+        解析行情消息，返回交易对和数据列表
+        
+        频道格式为 "<类型>.<合约ID>[.<参数>]"，数据位于 content.data（兼容顶层data）。
+        
+        Args:
+            message: 原始消息或SDK已解码的消息
+        
+        Returns:
+            tuple: (交易对或None, 数据列表)
+        """
+        data = loads(message) if isinstance(message, (str, bytes)) else message
+        content = data.get("content") or {}
+        channel = data.get("channel") or content.get("channel", "")
+        parts = channel.split(".", 2)
+        symbol = self._lookup_symbol(parts[1]) if len(parts) > 1 else None
+        
+        payload = content.get("data", data.get("data"))
+        if payload is None:
+            payload = []
+        elif isinstance(payload, dict):
+            payload = [payload]
+        
+        return symbol, payload
+    
+    def _handle_ticker_message(self, message: Union[str, Dict[str, Any]]):
         """处理ticker消息"""
-        try:
-            data = json.loads(message)
-            
-            # 解析ticker数据
-            if "data" in data:
-                ticker_data = data["data"]
-                price = self._parse_ticker_data(ticker_data)
+        with span("ws.ticker"):
+            try:
+                symbol, payload = self._resolve_message(message)
+                # 已移除的交易对在取消订阅前仍可能收到消息
+                if symbol is None or not payload or symbol not in self.price_history:
+                    return
                 
-                if price:
-                    # 添加到历史记录
-                    self.price_history[symbol].append(price)
+                # 解析ticker数据（先更新标记价格，价格回调中按标记/指数价格取样时使用同一条ticker）
+                for ticker_data in payload:
+                    mark = self._parse_mark_price(ticker_data)
+                    if mark:
+                        self.mark_prices[symbol] = mark
+                        for callback in self.mark_price_callbacks:
+                            try:
+                                callback(symbol, mark)
+                            except Exception as e:
+                                logger.error(f"标记价格回调函数执行失败: {e}")
                     
-                    # 保持历史记录在合理范围内
-                    if len(self.price_history[symbol]) > 1000:
-                        self.price_history[symbol] = self.price_history[symbol][-1000:]
+                    activity = parse_market_activity(ticker_data)
+                    if activity:
+                        self.market_activity[symbol] = activity
                     
-                    # 调用回调函数
-                    for callback in self.price_callbacks:
-                        try:
-                            callback(symbol, price)
-                        except Exception as e:
-                            logger.error(f"价格回调函数执行失败: {e}")
+                    funding = parse_funding_rate(ticker_data)
+                    if funding:
+                        self.funding_rates[symbol] = funding
+                    
+                    price = self._parse_ticker_data(ticker_data)
                     
-                    logger.debug(f"{symbol}: 价格更新 {price.close}")
+                    if price:
+                        # 添加到历史记录
+                        self.price_history[symbol].append(price)
+                        
+                        # 保持历史记录在合理范围内
+                        if len(self.price_history[symbol]) > 1000:
+                            self.price_history[symbol] = self.price_history[symbol][-1000:]
+                        
+                        # 调用回调函数
+                        for callback in self.price_callbacks:
+                            try:
+                                callback(symbol, price)
+                            except Exception as e:
+                                logger.error(f"价格回调函数执行失败: {e}")
+                        
+                        logger.debug(f"{symbol}: 价格更新 {price.close}")
+            
+            except Exception as e:
+                logger.error(f"处理ticker消息失败: {e}")
+    
+    def _handle_depth_message(self, messages: List[Union[str, Dict[str, Any]]]):
+        """
+        处理深度消息（SNAPSHOT全量替换，CHANGED增量合并，数量为0表示删除档位）
         
+        SDK按频道合并积压的消息：所有增量按顺序合并（不丢失档位变化），
+        深度回调只收到合并后的最新订单簿。
+        """
+        with span("ws.depth"):
+            updated: Dict[str, OrderBook] = {}
+            for message in messages:
+                try:
+                    symbol, payload = self._resolve_message(message)
+                    if symbol is None or not payload:
+                        continue
+                    for depth in payload:
+                        if self.sequence:
+                            state = self.sequence.check_depth(symbol, depth)
+                            if state == DEPTH_GAP:
+                                # 本地订单簿已不可信：丢弃并重新订阅，收到快照前不再发布
+                                self.order_books.pop(symbol, None)
+                                updated.pop(symbol, None)
+                                self._schedule_resync(symbol)
+                                continue
+                            if state in (DEPTH_STALE, DEPTH_RESYNCING):
+                                continue
+                        if self.book_validator and not self.book_validator.accept(symbol, depth):
+                            continue
+                        book = self._merge_depth(symbol, depth)
+                        if self.book_validator:
+                            reason = self.book_validator.validate(symbol, book, depth)
+                            if reason:
+                                logger.warning(f"[订单簿校验] {symbol} {reason}，丢弃本地订单簿并重新同步")
+                                self.order_books.pop(symbol, None)
+                                updated.pop(symbol, None)
+                                self._schedule_resync(symbol)
+                                continue
+                        updated[symbol] = book
+                except Exception as e:
+                    logger.error(f"处理深度消息失败: {e}")
+            
+            for symbol, book in updated.items():
+                for callback in self.depth_callbacks:
+                    try:
+                        callback(symbol, book)
+                    except Exception as e:
+                        logger.error(f"深度回调函数执行失败: {e}")
+    
+    def _schedule_resync(self, symbol: str):
+        """在事件循环中重新订阅交易对的深度频道（SDK分发线程调用）"""
+        if self._loop and not self._loop.is_closed():
+            asyncio.run_coroutine_threadsafe(self._resync_depth(symbol), self._loop)
+    
+    async def _resync_depth(self, symbol: str):
+        """取消并重新订阅深度频道，服务器重新推送全量快照"""
+        shard = self.symbol_shards.get(symbol)
+        contract_id = self.contract_ids.get(symbol)
+        if shard is None or not contract_id or not self.is_connected:
+            return
+        channel = f"depth.{contract_id}.{self.depth_level}"
+        try:
+            await shard.subscriptions.unsubscribe([channel])
+            await shard.subscriptions.subscribe([channel])
+            if channel not in shard.subscriptions.states:
+                raise ValueError("服务器拒绝订阅")
+            logger.info(f"[序列] 已重新订阅 {channel}，等待快照")
         except Exception as e:
-            logger.error(f"处理ticker消息失败: {e}")
+            if self.sequence:
+                self.sequence.resync_failed(symbol)
+            logger.error(f"[序列] 重新订阅 {channel} 失败（重新连接后恢复）: {e}")
+    
+    def _merge_depth(self, symbol: str, depth: Dict[str, Any]) -> OrderBook:
+        """把一条深度数据合并到本地订单簿，返回合并后的订单簿"""
+        book = self.order_books.get(symbol)
+        is_snapshot = str(depth.get("depthType", "SNAPSHOT")).upper() == "SNAPSHOT"
+        
+        if book is None or is_snapshot:
+            bids: Dict[float, float] = {}
+            asks: Dict[float, float] = {}
+        else:
+            bids = {level[0]: level[1] for level in book.bids}
+            asks = {level[0]: level[1] for level in book.asks}
+        
+        for side, levels in ((bids, depth.get("bids", [])), (asks, depth.get("asks", []))):
+            for level in levels:
+                price, size = self._parse_level(level)
+                if size > 0:
+                    side[price] = size
+                else:
+                    side.pop(price, None)
+        
+        book = OrderBook(
+            symbol=symbol,
+            bids=[[p, q] for p, q in sorted(bids.items(), reverse=True)],
+            asks=[[p, q] for p, q in sorted(asks.items())],
+            timestamp=int(time.time() * 1000)
+        )
+        self.order_books[symbol] = book
+        return book
+    
+    def _handle_metadata_message(self, message: Union[str, Dict[str, Any]]):
+        """处理合约元数据推送（数据为合约列表或带 contractList 的对象）"""
+        try:
+            _, payload = self._resolve_message(message)
+            contracts = []
+            for item in payload:
+                if "contractList" in item:
+                    contracts.extend(item.get("contractList") or [])
+                elif "contractId" in item:
+                    contracts.append(item)
+            if not contracts:
+                return
+            for callback in self.metadata_callbacks:
+                try:
+                    callback(contracts)
+                except Exception as e:
+                    logger.error(f"合约元数据回调函数执行失败: {e}")
+        except Exception as e:
+            logger.error(f"处理合约元数据消息失败: {e}")
+    
+    def _handle_trade_message(self, message: Union[str, Dict[str, Any]]):
+        """处理逐笔成交消息"""
+        with span("ws.trades"):
+            try:
+                symbol, payload = self._resolve_message(message)
+                if symbol is None or not payload:
+                    return
+                
+                for trade in payload:
+                    if self.sequence and not self.sequence.check_trade(symbol, trade):
+                        continue
+                    price = float(trade.get("price", 0))
+                    size = float(trade.get("size", 0))
+                    if price <= 0 or size <= 0:
+                        continue
+                    
+                    is_buyer_maker = trade.get("isBuyerMaker")
+                    for callback in self.trade_callbacks:
+                        try:
+                            callback(symbol, price, size, is_buyer_maker)
+                        except Exception as e:
+                            logger.error(f"成交回调函数执行失败: {e}")
+            
+            except Exception as e:
+                logger.error(f"处理成交消息失败: {e}")
+    
+    def _lookup_symbol(self, contract_id: str) -> Optional[str]:
+        """合约ID转换为交易对（未知合约返回None）"""
+        symbol = self._contract_symbols.get(contract_id)
+        if symbol is None:
+            # 合约ID映射可能在运行中补充，未命中时重建反向索引
+            self._contract_symbols = {cid: sym for sym, cid in self.contract_ids.items()}
+            symbol = self._contract_symbols.get(contract_id)
+        return symbol
+    
+    def _symbol_for_contract(self, contract_id: str) -> str:
+        """合约ID转换为交易对（未知合约返回合约ID本身）"""
+        return self._lookup_symbol(contract_id) or contract_id
+    
+    @staticmethod
+    def _parse_level(level: Any) -> tuple:
+        """解析深度档位（兼容 {"price","size"} 与 [price, size] 两种格式）"""
+        if isinstance(level, dict):
+            return float(level.get("price", 0)), float(level.get("size", 0))
+        return float(level[0]), float(level[1])
     
     def _parse_ticker_data(self, data: dict) -> Optional[PriceData]:
         """解析ticker数据为PriceData格式"""
@@ -171,6 +723,22 @@ class RealTimePriceStream:
             logger.error(f"解析ticker数据失败: {e}")
             return None
     
+    @staticmethod
+    def _parse_mark_price(data: dict) -> Optional[MarkPriceData]:
+        """解析ticker中的标记价格（oraclePrice）和指数价格（indexPrice）"""
+        try:
+            mark_price = float(data.get("oraclePrice") or 0)
+            if mark_price <= 0:
+                return None
+            return MarkPriceData(
+                timestamp=int(data.get("timestamp", time.time() * 1000)),
+                mark_price=mark_price,
+                index_price=float(data.get("indexPrice") or 0)
+            )
+        except (TypeError, ValueError) as e:
+            logger.error(f"解析标记价格失败: {e}")
+            return None
+    
     def get_price_history(self, symbol: str, limit: int = 100) -> List[PriceData]:
         """获取指定交易对的价格历史"""
         history = self.price_history.get(symbol, [])
@@ -180,3 +748,72 @@ class RealTimePriceStream:
         """获取指定交易对的最新价格"""
         history = self.price_history.get(symbol, [])
         return history[-1] if history else None
+    
+    def get_market_activity(self, symbol: str) -> Optional[MarketActivity]:
+        """获取指定交易对最新的持仓量与24小时成交量"""
+        return self.market_activity.get(symbol)
+    
+    def get_funding_rate(self, symbol: str) -> Optional[FundingRate]:
+        """获取指定交易对最新的资金费率"""
+        return self.funding_rates.get(symbol)
+    
+    def get_mark_price(self, symbol: str) -> Optional[MarkPriceData]:
+        """获取指定交易对的最新标记价格"""
+        return self.mark_prices.get(symbol)
+    
+    def get_order_book(self, symbol: str) -> Optional[OrderBook]:
+        """获取指定交易对的本地订单簿"""
+        return self.order_books.get(symbol)
+    
+    def seed_order_book(self, book: OrderBook) -> bool:
+        """
+        用REST快照初始化本地订单簿（已有WebSocket订单簿时忽略，之后到达的深度快照整体替换）
+        
+        Returns:
+            bool: 是否已使用该快照
+        """
+        with self._event_lock:
+            if book.symbol in self.order_books:
+                return False
+            self.order_books[book.symbol] = book
+            for callback in self.depth_callbacks:
+                try:
+                    callback(book.symbol, book)
+                except Exception as e:
+                    logger.error(f"深度回调函数执行失败: {e}")
+        return True
+    
+    def _clients(self) -> List[tuple]:
+        """各连接的（名称, SDK客户端）"""
+        return [(shard.name, shard.client) for shard in self.shards] + [("private", self.ws_manager.private_client)]
+    
+    def get_backpressure_stats(self) -> Dict[str, Dict[str, Any]]:
+        """
+        各连接的消息队列背压统计
+        
+        深度消息积压时合并（coalesced，增量已合并进订单簿，只是少发布中间状态），
+        其他消息（ticker、逐笔成交、私有订单/成交回报）从不丢弃，队列满时读线程等待（queue_full_waits）。
+        """
+        stats = {}
+        for name, client in self._clients():
+            if client is not None:
+                stats[name] = client.backpressure_stats()
+        return stats
+    
+    def get_sequence_stats(self) -> Optional[Dict[str, Any]]:
+        """序列检查统计（深度缺口、乱序、重新同步次数，重复/乱序成交数；未启用时为None）"""
+        return self.sequence.get_stats() if self.sequence else None
+    
+    def get_book_validation_stats(self) -> Optional[Dict[str, Any]]:
+        """订单簿校验统计（校验和通过/不一致、一致性问题、重新同步次数；未启用时为None）"""
+        return self.book_validator.get_stats() if self.book_validator else None
+    
+    def get_compression_stats(self) -> Optional[Dict[str, Dict[str, Any]]]:
+        """各连接的压缩统计（是否协商了permessage-deflate，压缩前后字节数；未启用压缩时为None）"""
+        if not self._compression:
+            return None
+        stats = {}
+        for name, client in self._clients():
+            if client is not None and client.compression_stats() is not None:
+                stats[name] = client.compression_stats()
+        return stats
@@ -0,0 +1,168 @@
+"""
+WebSocket订阅管理模块
+
+跟踪公共行情连接上的频道订阅，支持连接期间增删订阅（如轮换交易对时）：
+- 订阅/取消订阅请求发出后等待服务器确认（subscribed/unsubscribed），超时未确认时记录告警，
+  频道保留在订阅列表中（行情可能照常推送），状态标记为未确认
+- 服务器返回带频道的错误时该频道订阅失败，从订阅列表中移除
+- 建立新连接（重试、切换端点）时清空订阅状态，由调用方按交易对重新订阅
+"""
+
+import asyncio
+from typing import Any, Dict, Iterable, List, Optional
+
+from loguru import logger
+
+STATE_PENDING = "pending"
+STATE_ACTIVE = "active"
+STATE_UNCONFIRMED = "unconfirmed"
+
+
+class SubscriptionManager:
+    """公共行情连接的频道订阅"""
+
+    def __init__(self, ack_timeout: float = 5.0):
+        """
+        Args:
+            ack_timeout: 等待服务器确认的超时时间（秒）
+        """
+        self.ack_timeout = ack_timeout
+        self.states: Dict[str, str] = {}  # 频道 -> 订阅状态
+        self._client = None
+        self._loop: Optional[asyncio.AbstractEventLoop] = None
+        self._waiters: Dict[tuple, asyncio.Future] = {}  # (确认类型, 频道) -> 等待确认的Future
+        self.stats = {"subscribed": 0, "unsubscribed": 0, "ack_timeouts": 0, "errors": 0}
+
+    def attach(self, client):
+        """
+        关联（新建立的）SDK公共连接，注册确认回调
+
+        Args:
+            client: SDK WebSocket客户端（sdk.edgex_sdk.ws.client.Client）
+        """
+        self._loop = asyncio.get_running_loop()
+        if client is not self._client:
+            # 重试连接时复用同一个客户端，确认回调只注册一次
+            self._client = client
+            client.on_ack(self._on_ack)
+
+    @property
+    def channels(self) -> List[str]:
+        """当前订阅列表（含未确认的频道）"""
+        return list(self.states)
+
+    def _on_ack(self, msg: Dict[str, Any]):
+        """SDK读线程回调：转交给事件循环处理"""
+        if self._loop and not self._loop.is_closed():
+            self._loop.call_soon_threadsafe(self._resolve, msg)
+
+    def _resolve(self, msg: Dict[str, Any]):
+        msg_type = msg.get("type", "")
+        channel = msg.get("channel") or (msg.get("content") or {}).get("channel", "")
+        if msg_type == "error":
+            self.stats["errors"] += 1
+            logger.warning(f"[订阅] 服务器返回错误: {msg.get('content') or msg}")
+            for kind in ("subscribed", "unsubscribed"):
+                waiter = self._waiters.pop((kind, channel), None)
+                if waiter and not waiter.done():
+                    waiter.set_result(False)
+            return
+        waiter = self._waiters.pop((msg_type, channel), None)
+        if waiter and not waiter.done():
+            waiter.set_result(True)
+        elif msg_type == "subscribed" and self.states.get(channel) == STATE_UNCONFIRMED:
+            # 超时之后才到达的确认
+            self.states[channel] = STATE_ACTIVE
+
+    async def _send(self, kind: str, channel: str) -> Optional[bool]:
+        """发送订阅/取消订阅请求并等待确认（True已确认，False服务器报错，None超时）"""
+        waiter = self._loop.create_future()
+        self._waiters[(kind, channel)] = waiter
+        try:
+            if kind == "subscribed":
+                self._client.subscribe(channel)
+            else:
+                self._client.unsubscribe(channel)
+            return await asyncio.wait_for(waiter, self.ack_timeout)
+        except asyncio.TimeoutError:
+            self.stats["ack_timeouts"] += 1
+            return None
+        finally:
+            self._waiters.pop((kind, channel), None)
+
+    async def subscribe(self, channels: Iterable[str]) -> Dict[str, bool]:
+        """
+        订阅频道（并发等待确认）
+
+        Args:
+            channels: 频道列表（如 "ticker.10000001"）
+
+        Returns:
+            Dict[str, bool]: 频道 -> 是否已确认
+
+        Raises:
+            ValueError: 连接未建立或发送失败
+        """
+        channels = [c for c in channels if self.states.get(c) != STATE_ACTIVE]
+        for channel in channels:
+            self.states[channel] = STATE_PENDING
+        results = await asyncio.gather(*(self._send("subscribed", c) for c in channels), return_exceptions=True)
+        confirmed: Dict[str, bool] = {}
+        error: Optional[Exception] = None
+        for channel, result in zip(channels, results):
+            if isinstance(result, Exception):
+                self.states.pop(channel, None)
+                error = error or result
+                continue
+            if result:
+                self.states[channel] = STATE_ACTIVE
+                self.stats["subscribed"] += 1
+            elif result is False:
+                self.states.pop(channel, None)
+                self._client.subscriptions.discard(channel)
+                logger.warning(f"[订阅] {channel} 订阅失败")
+            elif self.states.get(channel) == STATE_PENDING:
+                # 超时未确认：保留订阅（行情可能照常推送）
+                self.states[channel] = STATE_UNCONFIRMED
+                logger.warning(f"[订阅] {channel} 未在 {self.ack_timeout} 秒内确认")
+            confirmed[channel] = bool(result)
+        if error:
+            raise error
+        return confirmed
+
+    async def unsubscribe(self, channels: Iterable[str]) -> Dict[str, bool]:
+        """
+        取消订阅（无论是否确认都从订阅列表中移除）
+
+        Returns:
+            Dict[str, bool]: 频道 -> 是否已确认
+        """
+        channels = [c for c in channels if c in self.states]
+        for channel in channels:
+            self.states.pop(channel, None)
+        results = await asyncio.gather(*(self._send("unsubscribed", c) for c in channels), return_exceptions=True)
+        confirmed: Dict[str, bool] = {}
+        for channel, result in zip(channels, results):
+            if isinstance(result, Exception):
+                logger.warning(f"[订阅] 取消订阅 {channel} 失败: {result}")
+                result = False
+            elif result:
+                self.stats["unsubscribed"] += 1
+            elif result is None:
+                logger.warning(f"[订阅] 取消订阅 {channel} 未在 {self.ack_timeout} 秒内确认")
+            confirmed[channel] = bool(result)
+        return confirmed
+
+    def reset(self):
+        """清空订阅状态（新连接上还没有任何订阅）"""
+        self.states.clear()
+        for waiter in self._waiters.values():
+            if not waiter.done():
+                waiter.set_result(None)
+        self._waiters.clear()
+
+    def get_stats(self) -> Dict[str, Any]:
+        counts = {STATE_PENDING: 0, STATE_ACTIVE: 0, STATE_UNCONFIRMED: 0}
+        for state in self.states.values():
+            counts[state] += 1
+        return {**counts, **self.stats}